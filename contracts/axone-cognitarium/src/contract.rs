@@ -1,22 +1,29 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
+    Uint128,
 };
-use cw2::set_contract_version;
+use cw2::{ensure_from_older_version, set_contract_version};
 use cw_utils::nonpayable;
 
-use crate::error::ContractError;
-use crate::msg::{DataFormat, ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{Store, BLANK_NODE_IDENTIFIER_COUNTER, NAMESPACE_KEY_INCREMENT, STORE};
+use crate::error::{ContractError, StoreError};
+use crate::msg::{DataFormat, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
+use crate::state::{
+    QueryAudit, RecentWindow, Store, BLANK_NODE_IDENTIFIER_COUNTER, NAMESPACE_KEY_INCREMENT,
+    PENDING_OWNER, QUERY_AUDIT, RECENT_WINDOW, STATE_LAYOUT, STORE, TRIPLE_MIGRATION,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const SHARD_INSERT_REPLY_ID: u64 = 1;
+const SUBSCRIPTION_CALLBACK_REPLY_ID: u64 = 2;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut<'_>,
+    mut deps: DepsMut<'_>,
     _env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
@@ -24,9 +31,26 @@ pub fn instantiate(
     nonpayable(&info)?;
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    STORE.save(deps.storage, &Store::new(info.sender, msg.limits.into()))?;
+    STORE.save(
+        deps.storage,
+        &Store::new(
+            info.sender,
+            msg.limits.into(),
+            msg.conflict_policy,
+            !msg.disable_literal_normalization,
+        ),
+    )?;
     NAMESPACE_KEY_INCREMENT.save(deps.storage, &0u128)?;
     BLANK_NODE_IDENTIFIER_COUNTER.save(deps.storage, &0u128)?;
+    STATE_LAYOUT.save(deps.storage, &migrate::LAYOUT_VERSION)?;
+    if let Some(size) = msg.recent_window {
+        RECENT_WINDOW.save(deps.storage, &RecentWindow::new(size))?;
+    }
+    if let Some(capacity) = msg.query_audit {
+        QUERY_AUDIT.save(deps.storage, &QueryAudit::new(capacity))?;
+    }
+    execute::save_soft_limit(deps.branch(), msg.soft_limit)?;
+    execute::save_literal_offload(deps, msg.literal_offload)?;
 
     Ok(Response::default())
 }
@@ -34,33 +58,170 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut<'_>,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     nonpayable(&info)?;
+    if TRIPLE_MIGRATION.exists(deps.storage) && !matches!(msg, ExecuteMsg::MigrateTriples { .. }) {
+        Err(ContractError::TripleMigrationInProgress)?;
+    }
+
     match msg {
-        ExecuteMsg::InsertData { format, data } => {
-            execute::insert(deps, info, format.unwrap_or_default(), data)
-        }
+        ExecuteMsg::InsertData {
+            format,
+            data,
+            compression,
+            expires,
+            graph,
+            skip_oversized,
+        } => execute::insert(
+            deps,
+            env,
+            info,
+            format.unwrap_or_default(),
+            data,
+            compression,
+            execute::InsertOptions {
+                expires,
+                graph,
+                skip_oversized,
+            },
+        ),
         ExecuteMsg::DeleteData {
             prefixes,
             delete,
             r#where,
         } => execute::delete(deps, info, prefixes, delete, r#where),
+        ExecuteMsg::Purge { limit } => execute::purge(deps, env, limit),
+        ExecuteMsg::PruneQueryAudit { before, limit } => {
+            execute::prune_query_audit(deps, info, before, limit)
+        }
+        ExecuteMsg::TransferOwnership { new_owner } => {
+            execute::transfer_ownership(deps, info, new_owner)
+        }
+        ExecuteMsg::AcceptOwnership {} => execute::accept_ownership(deps, info),
+        ExecuteMsg::CancelOwnershipTransfer {} => execute::cancel_ownership_transfer(deps, info),
+        ExecuteMsg::UpdateLimits { limits } => execute::update_limits(deps, info, limits),
+        ExecuteMsg::UpdateConflictPolicy { policy } => {
+            execute::update_conflict_policy(deps, info, policy)
+        }
+        ExecuteMsg::UpdateLiteralOffload { literal_offload } => {
+            execute::update_literal_offload(deps, info, literal_offload)
+        }
+        ExecuteMsg::UpdateSoftLimit { soft_limit } => {
+            execute::update_soft_limit(deps, info, soft_limit)
+        }
+        ExecuteMsg::GrantPermissions {
+            grantee,
+            permissions,
+        } => execute::grant_permissions(deps, info, grantee, permissions),
+        ExecuteMsg::RevokePermissions {
+            grantee,
+            permissions,
+        } => execute::revoke_permissions(deps, info, grantee, permissions),
+        ExecuteMsg::RegisterPrefixes { prefixes } => {
+            execute::register_prefixes(deps, info, prefixes)
+        }
+        ExecuteMsg::UnregisterPrefixes { prefixes } => {
+            execute::unregister_prefixes(deps, info, prefixes)
+        }
+        ExecuteMsg::RegisterQuery {
+            name,
+            query,
+            parameters,
+        } => execute::register_query(deps, info, name, query, parameters),
+        ExecuteMsg::UnregisterQuery { name } => execute::unregister_query(deps, info, name),
+        ExecuteMsg::BeginInsert { format, graph } => {
+            execute::begin_insert(deps, info, format.unwrap_or_default(), graph)
+        }
+        ExecuteMsg::InsertChunk { data } => execute::insert_chunk(deps, info, data),
+        ExecuteMsg::CommitInsert {} => execute::commit_insert(deps, env, info),
+        ExecuteMsg::AbortInsert {} => execute::abort_insert(deps, info),
+        ExecuteMsg::Subscribe { prefixes, pattern } => {
+            execute::subscribe(deps, info, prefixes, pattern)
+        }
+        ExecuteMsg::Unsubscribe { id } => execute::unsubscribe(deps, info, id),
+        ExecuteMsg::InsertBatch {
+            format,
+            data,
+            shards,
+        } => execute::insert_batch(deps, env, info, format.unwrap_or_default(), data, shards),
+        ExecuteMsg::MoveGraph {
+            subjects,
+            from,
+            to,
+            cursor,
+            limit,
+        } => execute::move_graph(deps, info, subjects, from, to, cursor, limit),
+        ExecuteMsg::RebuildIndexes { cursor, limit } => {
+            execute::rebuild_indexes(deps, info, cursor, limit)
+        }
+        ExecuteMsg::Compact { cursor, limit } => execute::compact(deps, info, cursor, limit),
+        ExecuteMsg::MigrateTriples { limit } => execute::migrate_triples(deps, limit),
+        ExecuteMsg::Batch(msgs) => execute::batch(deps, env, info, msgs),
     }
 }
 
 pub mod execute {
     use super::*;
-    use crate::msg::{DataFormat, Prefix, TripleDeleteTemplate, WhereClause};
-    use crate::querier::{PlanBuilder, QueryEngine, QueryPlan, ResolvedVariables};
-    use crate::rdf::PrefixMap;
-    use crate::state::{HasCachedNamespaces, Triple};
+    use crate::engine;
+    use crate::error::StoreError;
+    use crate::msg::{
+        ConflictPolicy, DataCompression, DataFormat, GranteesResponse, InsertBatchReceipt,
+        InsertDataReceipt, LiteralOffload, Permission, Prefix, SelectQuery, ShardBatch, SoftLimit,
+        StoreLimitsInput, SubjectSelection, SubscriptionCallbackMsg, TripleDeleteTemplate,
+        TriplePattern, WhereClause,
+    };
+    use crate::querier::{QueryEngine, ResolvedVariables};
+    use crate::rdf::graph_name;
+    use crate::state;
+    use crate::state::Triple;
+    use crate::state::{
+        expired_triples, scan_triple_indexes, InsertSession, PendingBatch, QueryAuditEntry,
+        StoredQuery, Subscription, TermQuerier, GRANTS, INSERT_CHUNKS, INSERT_SESSIONS,
+        LITERAL_OFFLOAD, PENDING_BATCH, PREFIXES, SOFT_LIMIT, STORED_QUERIES, SUBJECT_PROVENANCE,
+        SUBSCRIPTIONS, SUBSCRIPTION_COUNTER,
+    };
     use crate::storer::StoreEngine;
+    use axone_objectarium::msg::{
+        BucketResponse, ExecuteMsg as ObjectariumExecuteMsg, QueryMsg as ObjectariumQueryMsg,
+    };
     use axone_rdf::serde::TripleReader;
+    use cosmwasm_std::{
+        from_json, to_json_binary, to_json_vec, Addr, Event, Storage, SubMsg, Uint128, Uint64,
+        WasmMsg,
+    };
+    use cw_utils::Expiration;
     use either::{Left, Right};
-    use std::io::BufReader;
+    use flate2::read::GzDecoder;
+    use std::io::{BufReader, Read};
+
+    /// Record the evaluation of a where clause for the given operation in the query audit log, if
+    /// enabled.
+    fn audit_query(
+        storage: &mut dyn Storage,
+        info: &MessageInfo,
+        operation: &str,
+        (prefixes, r#where): (&[Prefix], &WhereClause),
+        result_count: usize,
+    ) -> StdResult<()> {
+        let Some(mut audit) = QUERY_AUDIT.may_load(storage)? else {
+            return Ok(());
+        };
+
+        let query_hash = blake3::hash(&to_json_vec(&(prefixes, r#where))?);
+        audit.record(
+            storage,
+            &QueryAuditEntry {
+                operation: operation.to_string(),
+                query_hash: query_hash.to_hex().to_string(),
+                caller: info.sender.clone(),
+                pattern_count: engine::where_complexity(r#where).0 as u32,
+                result_count: result_count as u64,
+            },
+        )
+    }
 
     pub fn verify_owner(deps: &DepsMut<'_>, info: &MessageInfo) -> Result<(), ContractError> {
         if STORE.load(deps.storage)?.owner != info.sender {
@@ -70,22 +231,617 @@ pub mod execute {
         }
     }
 
+    /// Verify the sender is either the owner or has been granted the given permission.
+    pub fn verify_permission(
+        deps: &DepsMut<'_>,
+        info: &MessageInfo,
+        permission: Permission,
+    ) -> Result<(), ContractError> {
+        if GRANTS
+            .may_load(deps.storage, &info.sender)?
+            .is_some_and(|granted| granted.contains(&permission))
+        {
+            return Ok(());
+        }
+
+        verify_owner(deps, info)
+    }
+
+    /// Verify the sender is itself allowed to insert in the sibling store, either as its owner or
+    /// as granted the [Permission::Insert] permission there, so that the store doesn't lend the
+    /// permission it has been granted in its sibling to its own grantees.
+    fn verify_shard_permission(
+        deps: &DepsMut<'_>,
+        info: &MessageInfo,
+        shard: &Addr,
+    ) -> Result<(), ContractError> {
+        /// The part of the [StoreResponse](crate::msg::StoreResponse) of the sibling telling its
+        /// owner.
+        #[derive(serde::Deserialize)]
+        struct ShardOwner {
+            owner: String,
+        }
+
+        let store: ShardOwner = deps.querier.query_wasm_smart(shard, &QueryMsg::Store {})?;
+        if store.owner == info.sender.as_str() {
+            return Ok(());
+        }
+        // The grantees being ordered by address, the pages are scanned up to the sender's one.
+        let mut after = None;
+        loop {
+            let grants: GranteesResponse = deps
+                .querier
+                .query_wasm_smart(shard, &QueryMsg::Grantees { after, limit: None })?;
+            let Some(last) = grants.grantees.last() else {
+                break;
+            };
+            if let Some(grantee) = grants
+                .grantees
+                .iter()
+                .find(|grantee| grantee.address.as_str() >= info.sender.as_str())
+            {
+                if grantee.address == info.sender.as_str()
+                    && grantee.permissions.contains(&Permission::Insert)
+                {
+                    return Ok(());
+                }
+                break;
+            }
+            after = Some(last.address.clone());
+        }
+
+        Err(ContractError::ShardUnauthorized(shard.to_string()))
+    }
+
+    /// The placement of the inserted triples, see [ExecuteMsg::InsertData].
+    #[derive(Default)]
+    pub struct InsertOptions {
+        pub expires: Option<Expiration>,
+        pub graph: Option<String>,
+        pub skip_oversized: bool,
+    }
+
     pub fn insert(
         deps: DepsMut<'_>,
+        env: Env,
         info: MessageInfo,
         format: DataFormat,
         data: Binary,
+        compression: Option<DataCompression>,
+        InsertOptions {
+            expires,
+            graph,
+            skip_oversized,
+        }: InsertOptions,
     ) -> Result<Response, ContractError> {
-        verify_owner(&deps, &info)?;
+        verify_permission(&deps, &info, Permission::Insert)?;
+        verify_soft_limit(&deps, &info)?;
+        let data = match compression {
+            Some(compression) => Binary::from(decompress(deps.storage, &data, compression)?),
+            None => data,
+        };
+
+        let mut storer = StoreEngine::new(deps.storage)?.at_block(&env).notifying()?;
+        if let Some(expires) = expires {
+            if expires.is_expired(&env.block) {
+                Err(ContractError::AlreadyExpired)?;
+            }
+            storer = storer.expiring(&expires);
+        }
+        if let Some(graph) = &graph {
+            storer = storer.in_graph(graph)?;
+        }
+        if skip_oversized {
+            storer = storer.skipping_oversized();
+        }
 
         let buf = BufReader::new(data.as_slice());
-        let mut reader = TripleReader::new(&(&format).into(), buf);
-        let mut storer = StoreEngine::new(deps.storage)?;
-        let count = storer.store_all(&mut reader)?;
+        let mut reader = TripleReader::new(&(&format).try_into()?, buf);
+        let count = storer.store_all(&info.sender, &mut reader)?;
+        let receipt = InsertDataReceipt {
+            inserted_count: count,
+            duplicate_count: storer.duplicate_count(),
+            rejected_count: storer.rejected_count(),
+        };
 
-        Ok(Response::new()
+        let resp = Response::new()
             .add_attribute("action", "insert")
-            .add_attribute("triple_count", count))
+            .add_attribute("triple_count", count)
+            .set_data(to_json_binary(&receipt)?)
+            .add_event(storer.take_changes().into_event("triples_inserted", count))
+            .add_messages(offload_literals(&mut storer)?)
+            .add_submessages(notify_subscribers(&mut storer)?);
+        Ok(warn_near_limit(deps.storage, resp)?)
+    }
+
+    pub fn insert_batch(
+        mut deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        format: DataFormat,
+        data: Binary,
+        shards: Vec<ShardBatch>,
+    ) -> Result<Response, ContractError> {
+        verify_permission(&deps, &info, Permission::Insert)?;
+        let shards = shards
+            .into_iter()
+            .map(|shard| {
+                let contract_addr = deps.api.addr_validate(&shard.contract_addr)?;
+                verify_shard_permission(&deps, &info, &contract_addr)?;
+                let msg = WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::InsertData {
+                        format: Some(format.clone()),
+                        data: shard.data,
+                        compression: None,
+                        expires: None,
+                        graph: None,
+                        skip_oversized: false,
+                    })?,
+                    funds: vec![],
+                };
+                Ok(SubMsg::reply_always(msg, SHARD_INSERT_REPLY_ID)
+                    .with_payload(contract_addr.as_bytes().to_vec()))
+            })
+            .collect::<Result<Vec<_>, ContractError>>()?;
+        let shard_count = shards.len();
+
+        let inserted = insert(
+            deps.branch(),
+            env,
+            info,
+            format,
+            data,
+            None,
+            Default::default(),
+        )?;
+        let receipt = inserted_receipt(&inserted)?.unwrap_or_default();
+        let receipt = InsertBatchReceipt {
+            receipt: receipt.clone(),
+            shards: vec![],
+            total: receipt,
+        };
+        let resp = if shard_count == 0 {
+            Response::new().set_data(to_json_binary(&receipt)?)
+        } else {
+            PENDING_BATCH.save(
+                deps.storage,
+                &PendingBatch {
+                    remaining: shard_count,
+                    receipt,
+                },
+            )?;
+            Response::new()
+        };
+
+        Ok(resp
+            .add_attribute("action", "insert_batch")
+            .add_attributes(
+                inserted
+                    .attributes
+                    .into_iter()
+                    .filter(|attr| attr.key != "action"),
+            )
+            .add_attribute("shard_count", shard_count.to_string())
+            .add_events(inserted.events)
+            .add_submessages(inserted.messages)
+            .add_submessages(shards))
+    }
+
+    pub fn move_graph(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        subjects: SubjectSelection,
+        from: Option<String>,
+        to: Option<String>,
+        cursor: Option<Binary>,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+        if from == to {
+            Err(ContractError::SameGraph)?;
+        }
+
+        let store = STORE.load(deps.storage)?;
+        let limit = limit.unwrap_or(store.limits.max_query_limit);
+        if limit > store.limits.max_query_limit {
+            Err(StoreError::QueryLimit(store.limits.max_query_limit))?;
+        }
+        engine::validate_where(&subjects.r#where, &store.limits)?;
+        // the graphs are kept in their N-Quads form
+        let from = from.as_deref().map(graph_name).transpose()?;
+        let to = to.as_deref().map(graph_name).transpose()?;
+
+        let plan = engine::plan(
+            deps.storage,
+            None,
+            subjects.prefixes.clone(),
+            Some(&subjects.r#where),
+            None,
+            None,
+            None,
+        )?;
+        let (bound, cursor) = engine::bound_subjects(
+            deps.storage,
+            plan,
+            &subjects.subject,
+            from.as_deref(),
+            cursor.as_ref().map(Binary::as_slice),
+            limit as usize,
+        )?;
+        audit_query(
+            deps.storage,
+            &info,
+            "move_graph",
+            (&subjects.prefixes, &subjects.r#where),
+            bound.len(),
+        )?;
+
+        let moved = bound.len();
+        for (key, mut provenance) in bound {
+            provenance.graph.clone_from(&to);
+            SUBJECT_PROVENANCE.save(deps.storage, &key, &provenance)?;
+        }
+
+        let resp = Response::new()
+            .add_attribute("action", "move_graph")
+            .add_attribute("subject_count", moved.to_string());
+        Ok(match cursor {
+            Some(cursor) => resp.add_attribute("cursor", Binary::from(cursor).to_base64()),
+            None => resp,
+        })
+    }
+
+    pub fn rebuild_indexes(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        cursor: Option<Binary>,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+
+        let store = STORE.load(deps.storage)?;
+        let limit = limit.unwrap_or(store.limits.max_query_limit);
+        if limit > store.limits.max_query_limit {
+            Err(StoreError::QueryLimit(store.limits.max_query_limit))?;
+        }
+
+        let scan = scan_triple_indexes(
+            deps.storage,
+            cursor.as_ref().map(Binary::as_slice),
+            limit.max(1) as usize,
+        )?;
+        for fault in &scan.faults {
+            fault.repair(deps.storage)?;
+        }
+
+        let resp = Response::new()
+            .add_attribute("action", "rebuild_indexes")
+            .add_attribute("scanned_count", scan.scanned.to_string())
+            .add_attribute("repaired_count", scan.faults.len().to_string());
+        Ok(match scan.cursor {
+            Some(cursor) => resp.add_attribute("cursor", Binary::from(cursor).to_base64()),
+            None => resp,
+        })
+    }
+
+    pub fn compact(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        cursor: Option<Binary>,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+
+        let store = STORE.load(deps.storage)?;
+        let limit = limit.unwrap_or(store.limits.max_query_limit);
+        if limit > store.limits.max_query_limit {
+            Err(StoreError::QueryLimit(store.limits.max_query_limit))?;
+        }
+
+        let compaction = state::compact(
+            deps.storage,
+            cursor.as_ref().map(Binary::as_slice),
+            limit.max(1) as usize,
+        )?;
+
+        let resp = Response::new()
+            .add_attribute("action", "compact")
+            .add_attribute("scanned_count", compaction.scanned.to_string())
+            .add_attribute("reclaimed_count", compaction.reclaimed.to_string());
+        Ok(match compaction.cursor {
+            Some(cursor) => resp.add_attribute("cursor", Binary::from(cursor).to_base64()),
+            None => resp,
+        })
+    }
+
+    pub fn migrate_triples(
+        deps: DepsMut<'_>,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let batch = migrate::migrate_triples_batch(deps.storage, limit)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "migrate_triples")
+            .add_attribute("interned_triple_count", batch.interned.to_string())
+            .add_attribute("indexed_triple_count", batch.indexed.to_string())
+            .add_attribute("completed", batch.completed.to_string()))
+    }
+
+    pub fn batch(
+        mut deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        msgs: Vec<ExecuteMsg>,
+    ) -> Result<Response, ContractError> {
+        let mut resp = Response::new()
+            .add_attribute("action", "batch")
+            .add_attribute("operation_count", msgs.len().to_string());
+        let mut receipt: Option<InsertDataReceipt> = None;
+        for (index, msg) in msgs.into_iter().enumerate() {
+            let op_resp = match msg {
+                ExecuteMsg::InsertData {
+                    format,
+                    data,
+                    compression,
+                    expires,
+                    graph,
+                    skip_oversized,
+                } => insert(
+                    deps.branch(),
+                    env.clone(),
+                    info.clone(),
+                    format.unwrap_or_default(),
+                    data,
+                    compression,
+                    InsertOptions {
+                        expires,
+                        graph,
+                        skip_oversized,
+                    },
+                )?,
+                ExecuteMsg::DeleteData {
+                    prefixes,
+                    delete: templates,
+                    r#where,
+                } => delete(deps.branch(), info.clone(), prefixes, templates, r#where)?,
+                _ => Err(ContractError::UnsupportedBatchOperation)?,
+            };
+
+            if let Some(inserted) = inserted_receipt(&op_resp)? {
+                receipt = Some(match receipt {
+                    Some(receipt) => receipt.merge(&inserted),
+                    None => inserted,
+                });
+            }
+            resp = resp
+                .add_event(
+                    Event::new("batch_operation")
+                        .add_attribute("index", index.to_string())
+                        .add_attributes(op_resp.attributes),
+                )
+                .add_events(op_resp.events)
+                .add_submessages(op_resp.messages);
+        }
+
+        match receipt {
+            Some(receipt) => Ok(resp.set_data(to_json_binary(&receipt)?)),
+            None => Ok(resp),
+        }
+    }
+
+    /// Extract the [InsertDataReceipt] an insertion sets as its response data, if any.
+    fn inserted_receipt(resp: &Response) -> StdResult<Option<InsertDataReceipt>> {
+        resp.data.as_ref().map(from_json).transpose()
+    }
+
+    /// Decompress the data, failing if they exceed the maximum insert data byte size once
+    /// decompressed so a small payload can't expand into an unbounded one.
+    fn decompress(
+        storage: &dyn Storage,
+        data: &[u8],
+        compression: DataCompression,
+    ) -> Result<Vec<u8>, ContractError> {
+        let limit = STORE.load(storage)?.limits.max_insert_data_byte_size;
+        let mut decompressed = vec![];
+        match compression {
+            DataCompression::Gzip => GzDecoder::new(data)
+                .take(
+                    u64::try_from(limit.u128())
+                        .unwrap_or(u64::MAX)
+                        .saturating_add(1),
+                )
+                .read_to_end(&mut decompressed),
+        }
+        .map_err(|e| ContractError::InvalidCompressedData(e.to_string()))?;
+
+        if Uint128::from(decompressed.len() as u128) > limit {
+            Err(StoreError::InsertDataByteSize(limit))?;
+        }
+        Ok(decompressed)
+    }
+
+    /// Build the messages storing the values of the offloaded literals as objects of the bucket,
+    /// pinned so they are kept as long as the store references them, see
+    /// [unpin_offloaded_literals].
+    fn offload_literals(storer: &mut StoreEngine<'_>) -> StdResult<Vec<WasmMsg>> {
+        let Some((bucket, values)) = storer.take_offloaded_literals() else {
+            return Ok(vec![]);
+        };
+        values
+            .into_iter()
+            .map(|value| {
+                Ok(WasmMsg::Execute {
+                    contract_addr: bucket.to_string(),
+                    msg: to_json_binary(&ObjectariumExecuteMsg::StoreObject {
+                        data: Binary::from(value.into_bytes()),
+                        pin: true,
+                        compression_algorithm: None,
+                        expected_digest: None,
+                        reject_duplicate: false,
+                        // the object is referenced by the hash of the literal value
+                        keep_content: true,
+                    })?,
+                    funds: vec![],
+                })
+            })
+            .collect()
+    }
+
+    /// Build the messages unpinning the objects of the offloaded literals no longer referenced by any
+    /// stored triple, so the bucket can remove them.
+    pub(crate) fn unpin_offloaded_literals(
+        storer: &mut StoreEngine<'_>,
+    ) -> StdResult<Vec<WasmMsg>> {
+        storer
+            .take_unpinned_objects()
+            .into_iter()
+            .map(|object| {
+                Ok(WasmMsg::Execute {
+                    contract_addr: object.storage_address,
+                    msg: to_json_binary(&ObjectariumExecuteMsg::UnpinObject {
+                        id: object.object_id,
+                    })?,
+                    funds: vec![],
+                })
+            })
+            .collect()
+    }
+
+    /// Build the callback messages notifying the subscribers of the stored triples matching their
+    /// subscription.
+    fn notify_subscribers(storer: &mut StoreEngine<'_>) -> StdResult<Vec<SubMsg>> {
+        storer
+            .take_notifications()
+            .into_iter()
+            .map(|(subscription_id, subscriber, triples)| {
+                // A failing subscriber must not revert the insertion.
+                Ok(SubMsg::reply_on_error(
+                    WasmMsg::Execute {
+                        contract_addr: subscriber.into(),
+                        msg: to_json_binary(&SubscriptionCallbackMsg::TriplesInserted {
+                            subscription_id,
+                            triples,
+                        })?,
+                        funds: vec![],
+                    },
+                    SUBSCRIPTION_CALLBACK_REPLY_ID,
+                )
+                .with_payload(to_json_binary(&subscription_id)?))
+            })
+            .collect()
+    }
+
+    pub fn begin_insert(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        format: DataFormat,
+        graph: Option<String>,
+    ) -> Result<Response, ContractError> {
+        verify_permission(&deps, &info, Permission::Insert)?;
+        axone_rdf::serde::DataFormat::try_from(&format)?;
+        if let Some(graph) = &graph {
+            graph_name(graph)?;
+        }
+
+        if INSERT_SESSIONS.has(deps.storage, &info.sender) {
+            Err(ContractError::InsertSessionInProgress)?;
+        }
+        INSERT_SESSIONS.save(
+            deps.storage,
+            &info.sender,
+            &InsertSession {
+                format,
+                chunk_count: 0,
+                byte_size: Uint128::zero(),
+                graph,
+            },
+        )?;
+
+        Ok(Response::new().add_attribute("action", "begin_insert"))
+    }
+
+    pub fn insert_chunk(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        data: Binary,
+    ) -> Result<Response, ContractError> {
+        verify_permission(&deps, &info, Permission::Insert)?;
+
+        let mut session = INSERT_SESSIONS
+            .may_load(deps.storage, &info.sender)?
+            .ok_or(ContractError::NoInsertSession)?;
+        let limit = STORE
+            .load(deps.storage)?
+            .limits
+            .max_insert_session_byte_size;
+        if session.byte_size + Uint128::from(data.len() as u128) > limit {
+            Err(StoreError::InsertSessionByteSize(limit))?;
+        }
+        INSERT_CHUNKS.save(
+            deps.storage,
+            (&info.sender, session.chunk_count),
+            &data.to_vec(),
+        )?;
+        session.chunk_count += 1;
+        session.byte_size += Uint128::from(data.len() as u128);
+        INSERT_SESSIONS.save(deps.storage, &info.sender, &session)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "insert_chunk")
+            .add_attribute("chunk_count", session.chunk_count.to_string())
+            .add_attribute("byte_size", session.byte_size))
+    }
+
+    pub fn commit_insert(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        verify_permission(&deps, &info, Permission::Insert)?;
+        verify_soft_limit(&deps, &info)?;
+
+        let session = INSERT_SESSIONS
+            .may_load(deps.storage, &info.sender)?
+            .ok_or(ContractError::NoInsertSession)?;
+        let mut data = Vec::with_capacity(session.byte_size.u128() as usize);
+        for chunk in 0..session.chunk_count {
+            data.extend(INSERT_CHUNKS.load(deps.storage, (&info.sender, chunk))?);
+            INSERT_CHUNKS.remove(deps.storage, (&info.sender, chunk));
+        }
+        INSERT_SESSIONS.remove(deps.storage, &info.sender);
+
+        let buf = BufReader::new(data.as_slice());
+        let mut reader = TripleReader::new(&(&session.format).try_into()?, buf);
+        let mut storer = StoreEngine::new(deps.storage)?
+            .without_insert_limits()
+            .at_block(&env)
+            .notifying()?;
+        if let Some(graph) = &session.graph {
+            storer = storer.in_graph(graph)?;
+        }
+        let count = storer.store_all(&info.sender, &mut reader)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "commit_insert")
+            .add_attribute("chunk_count", session.chunk_count.to_string())
+            .add_attribute("triple_count", count)
+            .add_event(storer.take_changes().into_event("triples_inserted", count))
+            .add_messages(offload_literals(&mut storer)?)
+            .add_submessages(notify_subscribers(&mut storer)?);
+        Ok(warn_near_limit(deps.storage, resp)?)
+    }
+
+    pub fn abort_insert(deps: DepsMut<'_>, info: MessageInfo) -> Result<Response, ContractError> {
+        let session = INSERT_SESSIONS
+            .may_load(deps.storage, &info.sender)?
+            .ok_or(ContractError::NoInsertSession)?;
+        for chunk in 0..session.chunk_count {
+            INSERT_CHUNKS.remove(deps.storage, (&info.sender, chunk));
+        }
+        INSERT_SESSIONS.remove(deps.storage, &info.sender);
+
+        Ok(Response::new().add_attribute("action", "abort_insert"))
     }
 
     pub fn delete(
@@ -95,7 +851,7 @@ pub mod execute {
         delete: Vec<TripleDeleteTemplate>,
         r#where: Option<WhereClause>,
     ) -> Result<Response, ContractError> {
-        verify_owner(&deps, &info)?;
+        verify_permission(&deps, &info, Permission::Delete)?;
 
         let delete = if delete.is_empty() {
             Left(match r#where {
@@ -114,15 +870,26 @@ pub mod execute {
             )
         };
 
-        let prefix_map = <PrefixMap>::from(prefixes).into_inner();
-        let mut plan_builder = PlanBuilder::new(deps.storage, &prefix_map, None);
-        let plan = match r#where {
-            Some(ref w) => plan_builder.build_plan(w)?,
-            None => QueryPlan::empty_plan(),
-        };
+        if let Some(r#where) = &r#where {
+            engine::validate_where(r#where, &STORE.load(deps.storage)?.limits)?;
+        }
+
+        let engine::Plan {
+            plan,
+            prefixes: plan_prefixes,
+            namespaces,
+        } = engine::plan(
+            deps.storage,
+            None,
+            prefixes.clone(),
+            r#where.as_ref(),
+            None,
+            None,
+            None,
+        )?;
 
-        let query_engine = QueryEngine::new(deps.storage, plan_builder.cached_namespaces());
-        let delete_templates = query_engine.make_triple_templates(&plan, &prefix_map, delete)?;
+        let query_engine = QueryEngine::new(deps.storage, namespaces);
+        let delete_templates = query_engine.make_triple_templates(&plan, &plan_prefixes, delete)?;
 
         let triples = if r#where.is_none() {
             let empty_vars = ResolvedVariables::with_capacity(0);
@@ -139,1330 +906,8880 @@ pub mod execute {
                 .construct_triples(plan, delete_templates)
                 .collect::<StdResult<Vec<Triple>>>()?
         };
+        if let Some(r#where) = &r#where {
+            audit_query(
+                deps.storage,
+                &info,
+                "delete_data",
+                (&prefixes, r#where),
+                triples.len(),
+            )?;
+        }
 
         let mut store = StoreEngine::new(deps.storage)?;
         let count = store.delete_all(&triples)?;
 
         Ok(Response::new()
             .add_attribute("action", "delete")
-            .add_attribute("triple_count", count))
+            .add_attribute("triple_count", count)
+            .add_messages(unpin_offloaded_literals(&mut store)?)
+            .add_event(store.take_changes().into_event("triples_deleted", count)))
     }
-}
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps<'_>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Store {} => to_json_binary(&query::store(deps)?),
-        QueryMsg::Select { query } => to_json_binary(&query::select(deps, query)?),
-        QueryMsg::Describe { query, format } => {
-            to_json_binary(&query::describe(deps, query, format.unwrap_or_default())?)
-        }
-        QueryMsg::Construct { query, format } => to_json_binary(&query::construct(
-            deps,
-            query,
-            format.unwrap_or(DataFormat::default()),
-        )?),
-    }
-}
+    pub fn purge(
+        deps: DepsMut<'_>,
+        env: Env,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let max = STORE
+            .load(deps.storage)?
+            .limits
+            .max_insert_data_triple_count;
+        let limit = limit.map_or(max, |l| max.min(l.into()));
+
+        let mut term_querier = TermQuerier::new();
+        let triples = expired_triples(
+            deps.storage,
+            &env.block,
+            usize::try_from(limit.u128()).unwrap_or(usize::MAX),
+        )?
+        .iter()
+        .map(|t| t.resolve(deps.storage, &mut term_querier))
+        .collect::<StdResult<Vec<_>>>()?;
 
-pub mod query {
-    use super::*;
-    use crate::msg::{
-        ConstructQuery, ConstructResponse, DescribeQuery, DescribeResponse, Node, SelectQuery,
-        SelectResponse, StoreResponse, TripleConstructTemplate, TriplePattern, VarOrNamedNode,
-        VarOrNode, VarOrNodeOrLiteral, WhereClause,
-    };
-    use crate::querier::{PlanBuilder, QueryEngine};
-    use crate::rdf::PrefixMap;
-    use crate::state::HasCachedNamespaces;
-    use axone_rdf::normalize::IdentifierIssuer;
+        let mut store = StoreEngine::new(deps.storage)?;
+        let count = store.delete_all(&triples)?;
 
-    pub fn store(deps: Deps<'_>) -> StdResult<StoreResponse> {
-        STORE.load(deps.storage).map(Into::into)
+        Ok(Response::new()
+            .add_attribute("action", "purge")
+            .add_attribute("triple_count", count)
+            .add_messages(unpin_offloaded_literals(&mut store)?)
+            .add_event(store.take_changes().into_event("triples_deleted", count)))
     }
 
-    pub fn select(deps: Deps<'_>, query: SelectQuery) -> StdResult<SelectResponse> {
-        let store = STORE.load(deps.storage)?;
-
-        if query.select.len() > store.limits.max_query_variable_count as usize {
-            Err(StdError::generic_err(
-                "Maximum query variable count exceeded",
-            ))?;
-        }
+    pub fn prune_query_audit(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        before: Option<Uint64>,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
 
-        let count = query.limit.unwrap_or(store.limits.max_query_limit);
-        if count > store.limits.max_query_limit {
-            Err(StdError::generic_err("Maximum query limit exceeded"))?;
+        let max = STORE.load(deps.storage)?.limits.max_query_limit;
+        let limit = limit.map_or(max, |l| max.min(l));
+        let count = QueryAudit::prune(
+            deps.storage,
+            before.map(|before| before.u64()),
+            limit as usize,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "prune_query_audit")
+            .add_attribute("entry_count", count.to_string()))
+    }
+
+    pub fn transfer_ownership(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        new_owner: String,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+
+        let new_owner = deps.api.addr_validate(&new_owner)?;
+        PENDING_OWNER.save(deps.storage, &new_owner)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "transfer_ownership")
+            .add_attribute("pending_owner", new_owner))
+    }
+
+    pub fn accept_ownership(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        if PENDING_OWNER.may_load(deps.storage)?.as_ref() != Some(&info.sender) {
+            Err(ContractError::NotPendingOwner)?;
         }
 
-        let prefix_map = PrefixMap::from(query.prefixes).into_inner();
-        let mut plan_builder =
-            PlanBuilder::new(deps.storage, &prefix_map, None).with_limit(count as usize);
-        let plan = plan_builder.build_plan(&query.r#where)?;
+        STORE.update(deps.storage, |mut store| -> StdResult<_> {
+            store.owner = info.sender.clone();
+            Ok(store)
+        })?;
+        PENDING_OWNER.remove(deps.storage);
 
-        QueryEngine::new(deps.storage, plan_builder.cached_namespaces())
-            .select(plan, query.select)
-            .and_then(|res| util::map_select_solutions(deps, res, plan_builder.cached_namespaces()))
+        Ok(Response::new()
+            .add_attribute("action", "accept_ownership")
+            .add_attribute("owner", info.sender))
     }
 
-    pub fn describe(
-        deps: Deps<'_>,
-        query: DescribeQuery,
-        format: DataFormat,
-    ) -> StdResult<DescribeResponse> {
-        let (p, o) = ("_2p".to_owned(), "_3o".to_owned());
+    pub fn cancel_ownership_transfer(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
 
-        let (construct, r#where) = match &query.resource {
-            VarOrNamedNode::Variable(var) => {
-                let select = TriplePattern {
-                    subject: VarOrNode::Variable(var.clone()),
-                    predicate: VarOrNamedNode::Variable(format!("{var}{p}")),
-                    object: VarOrNodeOrLiteral::Variable(format!("{var}{o}")),
-                };
+        PENDING_OWNER.remove(deps.storage);
 
-                let r#where = match query.r#where {
-                    Some(c) => WhereClause::LateralJoin {
-                        left: Box::new(c),
-                        right: Box::new(WhereClause::Bgp {
-                            patterns: vec![select.clone()],
-                        }),
-                    },
-                    None => WhereClause::Bgp {
-                        patterns: vec![select.clone()],
-                    },
-                };
+        Ok(Response::new().add_attribute("action", "cancel_ownership_transfer"))
+    }
 
-                (vec![select], r#where)
-            }
-            VarOrNamedNode::NamedNode(iri) => {
-                let select = TriplePattern {
-                    subject: VarOrNode::Node(Node::NamedNode(iri.clone())),
-                    predicate: VarOrNamedNode::Variable(p),
-                    object: VarOrNodeOrLiteral::Variable(o),
-                };
+    pub fn update_limits(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        limits: StoreLimitsInput,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+        save_limits(deps, limits)?;
 
-                (
-                    vec![select.clone()],
-                    WhereClause::Bgp {
-                        patterns: vec![select],
-                    },
-                )
-            }
+        Ok(Response::new().add_attribute("action", "update_limits"))
+    }
+
+    pub fn save_limits(deps: DepsMut<'_>, limits: StoreLimitsInput) -> Result<(), ContractError> {
+        let mut store = STORE.load(deps.storage)?;
+        if limits.max_triple_count < store.stat.triple_count {
+            Err(ContractError::LimitBelowUsage(
+                "max_triple_count".to_string(),
+                store.stat.triple_count,
+            ))?;
+        }
+        if limits.max_byte_size < store.stat.byte_size {
+            Err(ContractError::LimitBelowUsage(
+                "max_byte_size".to_string(),
+                store.stat.byte_size,
+            ))?;
+        }
+
+        store.limits = limits.into();
+        STORE.save(deps.storage, &store)?;
+
+        Ok(())
+    }
+
+    pub fn update_conflict_policy(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        policy: ConflictPolicy,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+
+        STORE.update(deps.storage, |mut store| -> StdResult<_> {
+            store.conflict_policy = policy;
+            Ok(store)
+        })?;
+
+        Ok(Response::new().add_attribute("action", "update_conflict_policy"))
+    }
+
+    pub fn update_literal_offload(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        literal_offload: Option<LiteralOffload>,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+
+        let enabled = literal_offload.is_some();
+        save_literal_offload(deps, literal_offload)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "update_literal_offload")
+            .add_attribute("enabled", enabled.to_string()))
+    }
+
+    /// Save the offloading of the large literals, removing it if not provided. The bucket is queried
+    /// for the algorithm it hashes the objects with, so the literals can reference their object
+    /// before it gets stored.
+    pub fn save_literal_offload(
+        deps: DepsMut<'_>,
+        literal_offload: Option<LiteralOffload>,
+    ) -> StdResult<()> {
+        let Some(LiteralOffload { bucket, threshold }) = literal_offload else {
+            LITERAL_OFFLOAD.remove(deps.storage);
+            return Ok(());
         };
 
-        let out = util::construct_atoms(
+        let bucket = deps.api.addr_validate(&bucket)?;
+        let response: BucketResponse = deps
+            .querier
+            .query_wasm_smart(&bucket, &ObjectariumQueryMsg::Bucket {})?;
+        LITERAL_OFFLOAD.save(
             deps.storage,
-            &format,
-            query.prefixes,
-            construct
-                .into_iter()
-                .map(|t| (t.subject, t.predicate, t.object))
-                .collect(),
-            r#where,
-        )?;
+            &state::LiteralOffload {
+                bucket,
+                threshold,
+                hash_algorithm: response.config.hash_algorithm,
+            },
+        )
+    }
 
-        Ok(DescribeResponse {
-            format,
-            data: Binary::from(out),
-        })
+    pub fn update_soft_limit(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        soft_limit: Option<SoftLimit>,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+
+        let enabled = soft_limit.is_some();
+        save_soft_limit(deps, soft_limit)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "update_soft_limit")
+            .add_attribute("enabled", enabled.to_string()))
     }
 
-    pub fn construct(
-        deps: Deps<'_>,
-        query: ConstructQuery,
-        format: DataFormat,
-    ) -> StdResult<ConstructResponse> {
-        let ConstructQuery {
-            construct,
-            prefixes,
-            r#where,
-        } = query;
+    /// Save the soft limit of the store, removing it if not provided.
+    pub fn save_soft_limit(deps: DepsMut<'_>, soft_limit: Option<SoftLimit>) -> StdResult<()> {
+        let Some(SoftLimit {
+            threshold_percent,
+            allowed_writers,
+        }) = soft_limit
+        else {
+            SOFT_LIMIT.remove(deps.storage);
+            return Ok(());
+        };
 
-        let construct = if construct.is_empty() {
-            match &r#where {
-                WhereClause::Bgp { patterns } => patterns
+        if !(1..=100).contains(&threshold_percent) {
+            Err(StdError::generic_err(
+                "Soft limit threshold must be between 1 and 100 percent",
+            ))?;
+        }
+        let allowed_writers = allowed_writers
+            .map(|writers| {
+                writers
                     .iter()
-                    .map(|p| TripleConstructTemplate {
-                        subject: p.subject.clone(),
-                        predicate: p.predicate.clone(),
-                        object: p.object.clone(),
-                    })
-                    .collect(),
-                _ => Err(StdError::generic_err("missing triples to construct"))?,
-            }
-        } else {
-            construct
+                    .map(|writer| deps.api.addr_validate(writer))
+                    .collect::<StdResult<_>>()
+            })
+            .transpose()?;
+        SOFT_LIMIT.save(
+            deps.storage,
+            &state::SoftLimit {
+                threshold_percent,
+                allowed_writers,
+            },
+        )
+    }
+
+    /// Verify the sender is allowed to insert given the soft limit of the store, if any.
+    fn verify_soft_limit(deps: &DepsMut<'_>, info: &MessageInfo) -> Result<(), ContractError> {
+        let Some(soft_limit) = SOFT_LIMIT.may_load(deps.storage)? else {
+            return Ok(());
         };
 
-        let mut id_issuer = IdentifierIssuer::new("a", 0u128);
-        let construct: Vec<_> = construct
-            .into_iter()
-            .map(|t| TripleConstructTemplate {
-                subject: match t.subject {
-                    VarOrNode::Node(Node::BlankNode(n)) => {
-                        VarOrNode::Node(Node::BlankNode(id_issuer.get_str_or_issue(n).to_string()))
-                    }
-                    _ => t.subject,
-                },
-                predicate: t.predicate,
-                object: match t.object {
-                    VarOrNodeOrLiteral::Node(Node::BlankNode(n)) => VarOrNodeOrLiteral::Node(
-                        Node::BlankNode(id_issuer.get_str_or_issue(n).to_string()),
-                    ),
-                    _ => t.object,
-                },
-            })
-            .collect();
+        if soft_limit.allows(&STORE.load(deps.storage)?, &info.sender) {
+            Ok(())
+        } else {
+            Err(ContractError::StoreNearLimit)
+        }
+    }
 
-        let out = util::construct_atoms(
-            deps.storage,
-            &format,
-            prefixes,
-            construct
-                .into_iter()
-                .map(|t| (t.subject, t.predicate, t.object))
-                .collect(),
-            r#where,
-        )?;
+    /// Warn the store is near its limits in the response of an insertion, if its soft limit is
+    /// reached.
+    fn warn_near_limit(storage: &dyn Storage, resp: Response) -> StdResult<Response> {
+        let Some(soft_limit) = SOFT_LIMIT.may_load(storage)? else {
+            return Ok(resp);
+        };
 
-        Ok(ConstructResponse {
-            format,
-            data: Binary::from(out),
+        Ok(match soft_limit.warning(&STORE.load(storage)?) {
+            Some(event) => resp.add_attribute("near_limit", "true").add_event(event),
+            None => resp,
         })
     }
-}
 
-pub mod util {
-    use super::*;
-    use crate::msg::{
-        Head, Prefix, Results, SelectResponse, Value, VarOrNamedNode, VarOrNode,
-        VarOrNodeOrLiteral, WhereClause,
-    };
-    use crate::querier::{PlanBuilder, QueryEngine, SelectResults};
-    use crate::rdf::{Atom, PrefixMap};
-    use crate::state::{HasCachedNamespaces, Namespace, NamespaceResolver};
-    use axone_rdf::normalize::IdentifierIssuer;
-    use axone_rdf::serde::TripleWriter;
-    use cosmwasm_std::Storage;
-    use std::collections::BTreeMap;
+    pub fn grant_permissions(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        grantee: String,
+        permissions: Vec<Permission>,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
 
-    pub fn map_select_solutions(
-        deps: Deps<'_>,
-        res: SelectResults<'_>,
-        ns_cache: Vec<Namespace>,
-    ) -> StdResult<SelectResponse> {
-        let mut ns_solver = NamespaceResolver::new(deps.storage, ns_cache);
-        let mut id_issuer = IdentifierIssuer::new("b", 0u128);
+        let grantee = deps.api.addr_validate(&grantee)?;
+        GRANTS.update(deps.storage, &grantee, |granted| -> StdResult<_> {
+            let mut granted = granted.unwrap_or_default();
+            granted.extend(permissions);
+            Ok(granted)
+        })?;
 
-        let mut bindings: Vec<BTreeMap<String, Value>> = vec![];
-        for solution in res.solutions {
-            let vars = solution?;
-            let resolved = vars
-                .into_iter()
-                .map(|(name, var)| -> StdResult<(String, Value)> {
-                    Ok((name, var.as_value(&mut ns_solver, &mut id_issuer)?))
-                })
-                .collect::<StdResult<BTreeMap<String, Value>>>()?;
-            bindings.push(resolved);
+        Ok(Response::new()
+            .add_attribute("action", "grant_permissions")
+            .add_attribute("grantee", grantee))
+    }
+
+    pub fn revoke_permissions(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        grantee: String,
+        permissions: Vec<Permission>,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
+
+        let grantee = deps.api.addr_validate(&grantee)?;
+        if let Some(mut granted) = GRANTS.may_load(deps.storage, &grantee)? {
+            for permission in &permissions {
+                granted.remove(permission);
+            }
+
+            if granted.is_empty() {
+                GRANTS.remove(deps.storage, &grantee);
+            } else {
+                GRANTS.save(deps.storage, &grantee, &granted)?;
+            }
         }
 
-        Ok(SelectResponse {
-            head: Head { vars: res.head },
-            results: Results { bindings },
-        })
+        Ok(Response::new()
+            .add_attribute("action", "revoke_permissions")
+            .add_attribute("grantee", grantee))
     }
 
-    pub fn construct_atoms(
-        storage: &dyn Storage,
-        format: &DataFormat,
+    pub fn register_prefixes(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
         prefixes: Vec<Prefix>,
-        construct: Vec<(VarOrNode, VarOrNamedNode, VarOrNodeOrLiteral)>,
-        r#where: WhereClause,
-    ) -> StdResult<Vec<u8>> {
-        let store = STORE.load(storage)?;
-
-        let prefix_map = <PrefixMap>::from(prefixes).into_inner();
-        let mut plan_builder = PlanBuilder::new(storage, &prefix_map, None)
-            .with_limit(store.limits.max_query_limit as usize);
-        let plan = plan_builder.build_plan(&r#where)?;
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
 
-        let atoms = QueryEngine::new(storage, plan_builder.cached_namespaces())
-            .construct_atoms(plan, &prefix_map, construct)?
-            .collect::<StdResult<Vec<Atom>>>()?;
+        for Prefix { prefix, namespace } in &prefixes {
+            PREFIXES.save(deps.storage, prefix, namespace)?;
+        }
 
-        let out: Vec<u8> = Vec::default();
-        let mut writer = TripleWriter::new(&format.into(), out);
+        Ok(Response::new()
+            .add_attribute("action", "register_prefixes")
+            .add_attribute("prefix_count", prefixes.len().to_string()))
+    }
 
-        for atom in &atoms {
-            let triple = atom.into();
+    pub fn unregister_prefixes(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        prefixes: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
 
-            writer.write(&triple).map_err(|e| {
-                StdError::serialize_err(
-                    "triple",
-                    format!("Error writing triple {}: {}", &triple, e),
-                )
-            })?;
+        for prefix in &prefixes {
+            PREFIXES.remove(deps.storage, prefix);
         }
-        writer
-            .finish()
-            .map_err(|e| StdError::serialize_err("triple", format!("Error writing triple: {e}")))
+
+        Ok(Response::new()
+            .add_attribute("action", "unregister_prefixes")
+            .add_attribute("prefix_count", prefixes.len().to_string()))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::error::StoreError;
-    use crate::msg::ExecuteMsg::{DeleteData, InsertData};
-    use crate::msg::Node::{BlankNode, NamedNode};
-    use crate::msg::IRI::{Full, Prefixed};
-    use crate::msg::{
-        ConstructQuery, ConstructResponse, DescribeQuery, DescribeResponse, Head, Literal, Prefix,
-        Results, SelectItem, SelectQuery, SelectResponse, StoreLimitsInput,
-        StoreLimitsInputBuilder, StoreResponse, Value, VarOrNamedNode, VarOrNamedNodeOrLiteral,
-        VarOrNode, VarOrNodeOrLiteral,
-    };
-    use crate::msg::{TriplePattern, WhereClause};
-    use crate::state::{
-        namespaces, triples, Namespace, Node, Object, StoreLimits, StoreStat, Subject, Triple,
-    };
-    use crate::{msg, state};
-    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
-    use cosmwasm_std::{coins, from_json, Addr, Attribute, Order, Uint128};
-    use cw_utils::PaymentError;
-    use cw_utils::PaymentError::NonPayable;
-    use std::collections::BTreeMap;
-    use std::fs::File;
-    use std::io::Read;
-    use std::path::Path;
-    use std::{env, u128};
-    use testing::addr::{addr, OWNER, SENDER};
+    pub fn register_query(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        name: String,
+        query: SelectQuery,
+        parameters: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
 
-    #[test]
-    fn proper_initialization() {
-        let mut deps = mock_dependencies();
+        let store = STORE.load(deps.storage)?;
+        engine::validate_select(&query, &store.limits)?;
+        engine::validate_reasoning(&query, &store.limits)?;
+        STORED_QUERIES.save(
+            deps.storage,
+            &name,
+            &StoredQuery::try_new(query, parameters)?,
+        )?;
 
-        let msg = InstantiateMsg {
-            limits: StoreLimitsInput {
-                max_triple_count: Uint128::from(1u128),
-                max_byte_size: Uint128::from(2u128),
-                max_triple_byte_size: Uint128::from(3u128),
-                max_query_limit: 4,
-                max_query_variable_count: 5,
-                max_insert_data_byte_size: Uint128::from(6u128),
-                max_insert_data_triple_count: Uint128::from(7u128),
-            },
-        };
+        Ok(Response::new()
+            .add_attribute("action", "register_query")
+            .add_attribute("name", name))
+    }
 
-        let info = message_info(&addr(OWNER), &[]);
-        let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-        assert_eq!(0, res.messages.len());
+    pub fn unregister_query(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        name: String,
+    ) -> Result<Response, ContractError> {
+        verify_owner(&deps, &info)?;
 
-        let store = STORE.load(&deps.storage).unwrap();
-        assert_eq!(store.owner, info.sender);
-        assert_eq!(
-            store.limits,
-            state::StoreLimits {
-                max_triple_count: Uint128::from(1u128),
-                max_byte_size: Uint128::from(2u128),
-                max_triple_byte_size: Uint128::from(3u128),
-                max_query_limit: 4,
-                max_query_variable_count: 5,
-                max_insert_data_byte_size: Uint128::from(6u128),
-                max_insert_data_triple_count: Uint128::from(7u128),
-            }
-        );
-        assert_eq!(
-            store.stat,
-            StoreStat {
-                triple_count: Uint128::zero(),
-                namespace_count: Uint128::zero(),
-                byte_size: Uint128::zero(),
-            }
-        );
+        STORED_QUERIES.remove(deps.storage, &name);
 
-        assert_eq!(NAMESPACE_KEY_INCREMENT.load(&deps.storage).unwrap(), 0u128);
-        assert_eq!(
-            BLANK_NODE_IDENTIFIER_COUNTER.load(&deps.storage).unwrap(),
-            0u128
-        );
+        Ok(Response::new()
+            .add_attribute("action", "unregister_query")
+            .add_attribute("name", name))
     }
 
-    #[test]
-    fn funds_initialization() {
-        let mut deps = mock_dependencies();
-        let env = mock_env();
-        let info = message_info(&addr(SENDER), &coins(10, "uaxone"));
+    pub fn subscribe(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        prefixes: Vec<Prefix>,
+        pattern: TriplePattern,
+    ) -> Result<Response, ContractError> {
+        verify_permission(&deps, &info, Permission::Subscribe)?;
 
-        let msg = InstantiateMsg::default();
+        let [subject, predicate, object] =
+            engine::subscription_terms(deps.storage, prefixes, pattern)?;
+        let id = SUBSCRIPTION_COUNTER.may_load(deps.storage)?.unwrap_or(0) + 1;
+        SUBSCRIPTION_COUNTER.save(deps.storage, &id)?;
+        SUBSCRIPTIONS.save(
+            deps.storage,
+            id,
+            &Subscription {
+                subscriber: info.sender,
+                subject,
+                predicate,
+                object,
+            },
+        )?;
 
-        let result = instantiate(deps.as_mut(), env, info, msg);
-        assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), ContractError::Payment(NonPayable {}));
+        Ok(Response::new()
+            .add_attribute("action", "subscribe")
+            .add_attribute("subscription_id", id.to_string()))
     }
 
-    #[test]
-    fn execute_fail_with_funds() {
-        let mut deps = mock_dependencies();
-        let env = mock_env();
-        let info = message_info(&addr("sender"), &coins(10, "uaxone"));
+    pub fn unsubscribe(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        id: u64,
+    ) -> Result<Response, ContractError> {
+        if SUBSCRIPTIONS.load(deps.storage, id)?.subscriber != info.sender {
+            verify_owner(&deps, &info)?;
+        }
+        SUBSCRIPTIONS.remove(deps.storage, id);
 
-        let messages = vec![
-            InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: Binary::from("data".as_bytes()),
-            },
-            DeleteData {
-                prefixes: vec![],
-                delete: vec![],
-                r#where: None,
-            },
-        ];
+        Ok(Response::new()
+            .add_attribute("action", "unsubscribe")
+            .add_attribute("subscription_id", id.to_string()))
+    }
+}
 
-        for msg in messages {
-            let result = execute(deps.as_mut(), env.clone(), info.clone(), msg);
-            assert!(result.is_err());
-            assert_eq!(
-                result.unwrap_err(),
-                ContractError::Payment(PaymentError::NonPayable {})
-            );
-        }
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut<'_>, _env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    if TRIPLE_MIGRATION.exists(deps.storage) {
+        Err(ContractError::TripleMigrationInProgress)?;
     }
 
-    #[test]
-    fn proper_insert() {
-        let cases = vec![
-            InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
-            },
-            InsertData {
-                format: Some(DataFormat::Turtle),
-                data: read_test_data("sample.ttl"),
-            },
-            InsertData {
-                format: Some(DataFormat::NTriples),
-                data: read_test_data("sample.nt"),
-            },
-            InsertData {
-                format: Some(DataFormat::NQuads),
-                data: read_test_data("sample.nq"),
-            },
-            InsertData {
-                format: None,
-                data: read_test_data("sample.ttl"),
-            },
-        ];
+    match msg {
+        SudoMsg::ClearStore { limit } => sudo::clear_store(deps, limit),
+        SudoMsg::UpdateLimits { limits } => sudo::update_limits(deps, limits),
+        SudoMsg::TransferOwnership { new_owner } => sudo::transfer_ownership(deps, new_owner),
+    }
+}
 
-        for case in cases {
-            let mut deps = mock_dependencies();
+pub mod sudo {
+    use super::*;
+    use crate::msg::StoreLimitsInput;
+    use crate::state::{triples, TermQuerier};
+    use crate::storer::StoreEngine;
+    use cosmwasm_std::Order;
+
+    pub fn clear_store(deps: DepsMut<'_>, limit: Option<u32>) -> Result<Response, ContractError> {
+        let max = STORE
+            .load(deps.storage)?
+            .limits
+            .max_insert_data_triple_count;
+        let limit = limit.map_or(max, |l| max.min(l.into()));
+
+        let mut term_querier = TermQuerier::new();
+        let triples = triples()
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(usize::try_from(limit.u128()).unwrap_or(usize::MAX))
+            .map(|res| res.and_then(|(_, t)| t.resolve(deps.storage, &mut term_querier)))
+            .collect::<StdResult<Vec<_>>>()?;
 
-            let info = message_info(&addr(OWNER), &[]);
-            instantiate(
-                deps.as_mut(),
-                mock_env(),
-                info.clone(),
-                InstantiateMsg::default(),
-            )
-            .unwrap();
+        let mut store = StoreEngine::new(deps.storage)?;
+        let count = store.delete_all(&triples)?;
+        let event = store.take_changes().into_event("triples_deleted", count);
+        let unpin_msgs = execute::unpin_offloaded_literals(&mut store)?;
+        let completed = STORE.load(deps.storage)?.stat.triple_count.is_zero();
 
-            let res = execute(deps.as_mut(), mock_env(), info.clone(), case);
+        Ok(Response::new()
+            .add_attribute("action", "clear_store")
+            .add_attribute("triple_count", count)
+            .add_attribute("completed", completed.to_string())
+            .add_messages(unpin_msgs)
+            .add_event(event))
+    }
 
-            assert!(res.is_ok());
-            assert_eq!(
-                res.unwrap().attributes,
-                vec![
-                    Attribute::new("action", "insert"),
-                    Attribute::new("triple_count", "40"),
-                ]
-            );
+    pub fn update_limits(
+        deps: DepsMut<'_>,
+        limits: StoreLimitsInput,
+    ) -> Result<Response, ContractError> {
+        execute::save_limits(deps, limits)?;
 
-            assert_eq!(
-                triples()
-                    .range_raw(&deps.storage, None, None, Order::Ascending)
-                    .count(),
-                40
-            );
-            assert_eq!(
-                STORE.load(&deps.storage).unwrap().stat,
-                StoreStat {
-                    triple_count: 40u128.into(),
-                    namespace_count: 17u128.into(),
-                    byte_size: 7190u128.into(),
-                },
-            );
-            assert_eq!(NAMESPACE_KEY_INCREMENT.load(&deps.storage).unwrap(), 17u128);
-            assert_eq!(
-                namespaces()
-                    .load(
-                        &deps.storage,
-                        "https://ontology.axone.space/dataverse/dataspace/".to_string(),
-                    )
-                    .unwrap(),
-                Namespace {
-                    value: "https://ontology.axone.space/dataverse/dataspace/".to_string(),
-                    key: 0u128,
-                    counter: 5u128,
-                }
-            );
-            assert_eq!(
-                triples()
-                    .load(
-                        &deps.storage,
-                        (
-                            Object::Named(Node {
-                                namespace: 4u128,
-                                value: "0x04d1f1b8f8a7a28f9a5a254c326a963a22f5a5b5d5f5e5d5c5b5a5958575655"
-                                    .to_string(),
-                            }).as_hash()
-                                .as_bytes(),
-                            Node {
-                                namespace: 3u128,
-                                value: "hasRegistrar".to_string(),
-                            }
-                                .key(),
-                            Subject::Named(Node {
-                                namespace: 0u128,
-                                value: "97ff7e16-c08d-47be-8475-211016c82e33".to_string(),
-                            })
-                                .key()
-                        ),
-                    )
-                    .unwrap(),
-                Triple {
-                    object: Object::Named(Node {
-                        namespace: 4u128,
-                        value: "0x04d1f1b8f8a7a28f9a5a254c326a963a22f5a5b5d5f5e5d5c5b5a5958575655"
-                            .to_string(),
-                    }),
-                    predicate: Node {
-                        namespace: 3u128,
-                        value: "hasRegistrar".to_string(),
-                    },
-                    subject: Subject::Named(Node {
-                        namespace: 0u128,
-                        value: "97ff7e16-c08d-47be-8475-211016c82e33".to_string(),
-                    }),
-                }
-            )
-        }
+        Ok(Response::new().add_attribute("action", "update_limits"))
     }
 
-    #[test]
-    fn proper_insert_blank_nodes() {
-        let mut deps = mock_dependencies();
+    pub fn transfer_ownership(
+        deps: DepsMut<'_>,
+        new_owner: String,
+    ) -> Result<Response, ContractError> {
+        let new_owner = deps.api.addr_validate(&new_owner)?;
+        STORE.update(deps.storage, |mut store| -> StdResult<_> {
+            store.owner = new_owner.clone();
+            Ok(store)
+        })?;
+        PENDING_OWNER.remove(deps.storage);
 
-        let info = message_info(&addr(OWNER), &[]);
-        instantiate(
-            deps.as_mut(),
-            mock_env(),
-            info.clone(),
-            InstantiateMsg::default(),
-        )
-        .unwrap();
+        Ok(Response::new()
+            .add_attribute("action", "transfer_ownership")
+            .add_attribute("owner", new_owner))
+    }
+}
 
-        let insert_msg = InsertData {
-            format: None,
-            data: read_test_data("blank-nodes.ttl"),
-        };
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut<'_>, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        SHARD_INSERT_REPLY_ID => reply::shard_insert_reply(deps, msg),
+        SUBSCRIPTION_CALLBACK_REPLY_ID => reply::subscription_callback_reply(msg),
+        _ => Err(ContractError::UnknownReplyID),
+    }
+}
 
-        let res = execute(deps.as_mut(), mock_env(), info.clone(), insert_msg.clone());
-        assert!(res.is_ok());
-        assert_eq!(
-            BLANK_NODE_IDENTIFIER_COUNTER.load(&deps.storage).unwrap(),
-            2u128
-        );
+pub mod reply {
+    use super::*;
+    use crate::msg::{InsertDataReceipt, ShardInsertReceipt};
+    use crate::state::PENDING_BATCH;
+    use cosmwasm_std::{from_json, SubMsgResult};
+    use cw_utils::parse_execute_response_data;
+
+    /// Ignore the failure of a subscriber notified of inserted triples, only reporting it in the
+    /// attributes.
+    pub fn subscription_callback_reply(msg: Reply) -> Result<Response, ContractError> {
+        let subscription_id: u64 = from_json(&msg.payload)?;
+        let mut resp = Response::new()
+            .add_attribute("action", "subscription_callback_failed")
+            .add_attribute("subscription_id", subscription_id.to_string());
+        if let SubMsgResult::Err(err) = msg.result {
+            resp = resp.add_attribute("error", err);
+        }
 
-        // we insert the same data again to check the creation of new blank nodes
-        let res = execute(deps.as_mut(), mock_env(), info.clone(), insert_msg);
-        assert!(res.is_ok());
-        assert_eq!(
-            BLANK_NODE_IDENTIFIER_COUNTER.load(&deps.storage).unwrap(),
-            4u128
-        );
+        Ok(resp)
     }
 
-    #[test]
-    fn insert_existing_triples() {
-        let mut deps = mock_dependencies();
+    /// Fail the whole batch insertion when the sub-batch of a sibling store failed, rolling back
+    /// the insertion of this store, or else add the receipt of the sibling store to the one of the
+    /// batch, set as the response data.
+    pub fn shard_insert_reply(deps: DepsMut<'_>, msg: Reply) -> Result<Response, ContractError> {
+        let contract_addr = String::from_utf8_lossy(&msg.payload).into_owned();
+        let response = match msg.result {
+            SubMsgResult::Err(err) => {
+                return Err(ContractError::ShardInsertFailed(contract_addr, err))
+            }
+            SubMsgResult::Ok(response) => response,
+        };
 
-        let info = message_info(&addr(OWNER), &[]);
-        instantiate(
-            deps.as_mut(),
-            mock_env(),
-            info.clone(),
-            InstantiateMsg::default(),
-        )
-        .unwrap();
+        let receipt = response
+            .msg_responses
+            .first()
+            .map(|msg_response| {
+                parse_execute_response_data(&msg_response.value)
+                    .map_err(|e| StdError::generic_err(e.to_string()))
+            })
+            .transpose()?
+            .and_then(|data| data.data)
+            .map(|data| from_json::<InsertDataReceipt>(&data))
+            .transpose()?;
+
+        let mut pending = PENDING_BATCH.load(deps.storage)?;
+        if let Some(receipt) = &receipt {
+            pending.receipt.total = pending.receipt.total.merge(receipt);
+        }
+        pending.receipt.shards.push(ShardInsertReceipt {
+            contract_addr,
+            receipt,
+        });
+        pending.remaining -= 1;
+        if pending.remaining == 0 {
+            PENDING_BATCH.remove(deps.storage);
+        } else {
+            PENDING_BATCH.save(deps.storage, &pending)?;
+        }
 
-        execute(
-            deps.as_mut(),
-            mock_env(),
-            info.clone(),
-            InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
-            },
-        )
-        .unwrap();
+        Ok(Response::new().set_data(to_json_binary(&pending.receipt)?))
+    }
+}
 
-        let res = execute(
-            deps.as_mut(),
-            mock_env(),
-            info.clone(),
-            InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
-            },
-        );
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps<'_>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    // only the responses carrying stored data are bounded, so the configuration of the store stays
+    // readable whatever its limits
+    let bounded = matches!(
+        msg,
+        QueryMsg::Select { .. }
+            | QueryMsg::StoredQuery { .. }
+            | QueryMsg::BudgetedSelect { .. }
+            | QueryMsg::Describe { .. }
+            | QueryMsg::Provenance { .. }
+            | QueryMsg::Construct { .. }
+            | QueryMsg::Export { .. }
+            | QueryMsg::RecentChanges { .. }
+            | QueryMsg::Backlinks { .. }
+    );
+    let res = match msg {
+        QueryMsg::Store {} => to_json_binary(&query::store(deps)?),
+        QueryMsg::Stats {} => to_json_binary(&query::stats(deps)?),
+        QueryMsg::StoreChecksum {} => to_json_binary(&query::store_checksum(deps)?),
+        QueryMsg::Select { query } => to_json_binary(&query::select(deps, query)?),
+        QueryMsg::StoredQuery { name, parameters } => {
+            to_json_binary(&query::stored_query(deps, name, parameters)?)
+        }
+        QueryMsg::Explain { query } => to_json_binary(&query::explain(deps, query)?),
+        QueryMsg::Count { query } => to_json_binary(&query::count(deps, query)?),
+        QueryMsg::BudgetedSelect {
+            query,
+            max_rows,
+            cursor,
+        } => to_json_binary(&query::budgeted_select(deps, query, max_rows, cursor)?),
+        QueryMsg::Describe { query, format } => {
+            to_json_binary(&query::describe(deps, query, format.unwrap_or_default())?)
+        }
+        QueryMsg::Provenance { query } => to_json_binary(&query::provenance(deps, query)?),
+        QueryMsg::Subscription { id } => to_json_binary(&query::subscription(deps, id)?),
+        QueryMsg::Construct {
+            query,
+            format,
+            cursor,
+        } => to_json_binary(&query::construct(
+            deps,
+            query,
+            format.unwrap_or(DataFormat::default()),
+            cursor,
+        )?),
+        QueryMsg::Export {
+            cursor,
+            limit,
+            format,
+        } => to_json_binary(&query::export(
+            deps,
+            cursor,
+            limit,
+            format.unwrap_or(DataFormat::NQuads),
+        )?),
+        QueryMsg::RecentChanges { batches, format } => to_json_binary(&query::recent_changes(
+            deps,
+            batches,
+            format.unwrap_or(DataFormat::default()),
+        )?),
+        QueryMsg::PendingOwner {} => to_json_binary(&query::pending_owner(deps)?),
+        QueryMsg::Grantees { after, limit } => {
+            to_json_binary(&query::grantees(deps, after, limit)?)
+        }
+        QueryMsg::Prefixes {} => to_json_binary(&query::prefixes(deps)?),
+        QueryMsg::Namespaces { cursor, limit } => {
+            to_json_binary(&query::namespaces(deps, cursor, limit)?)
+        }
+        QueryMsg::QueryAudit { after, limit } => {
+            to_json_binary(&query::query_audit(deps, after, limit)?)
+        }
+        QueryMsg::CheckIntegrity { cursor, limit } => {
+            to_json_binary(&query::check_integrity(deps, cursor, limit)?)
+        }
+        QueryMsg::Backlinks { iri, cursor } => {
+            to_json_binary(&query::backlinks(deps, iri, cursor)?)
+        }
+    }?;
 
-        assert!(res.is_ok());
-        assert_eq!(
-            res.unwrap().attributes,
-            vec![
-                Attribute::new("action", "insert"),
-                Attribute::new("triple_count", "0"),
-            ]
-        );
+    if bounded {
+        let limits = STORE.load(deps.storage)?.limits;
+        if Uint128::from(res.len() as u128) > limits.max_result_byte_size {
+            Err(StoreError::ResultByteSize(limits.max_result_byte_size))?;
+        }
+    }
+    Ok(res)
+}
 
-        assert_eq!(
-            triples()
-                .range_raw(&deps.storage, None, None, Order::Ascending)
-                .count(),
-            40
-        );
-        assert_eq!(
-            STORE.load(&deps.storage).unwrap().stat,
-            StoreStat {
-                triple_count: 40u128.into(),
-                namespace_count: 17u128.into(),
-                byte_size: 7190u128.into(),
+pub mod query {
+    use super::*;
+    use crate::engine;
+    use crate::error::StoreError;
+    use crate::msg::{
+        BacklinksResponse, BudgetedSelectResponse, ByteSizeBreakdown, ConstructQuery,
+        ConstructResponse, CountQuery, CountResponse, DescribeQuery, DescribeResponse,
+        ExplainResponse, ExportResponse, Grantee, GranteesResponse, IntegrityFault,
+        IntegrityFaultKind, IntegrityResponse, NamespaceUsage, NamespacesResponse, Node,
+        ParameterValue, PendingOwnerResponse, Prefix, PrefixesResponse, ProvenanceQuery,
+        ProvenanceResponse, QueryAuditEntry, QueryAuditResponse, RecentChangesResponse,
+        SelectCursor, SelectQuery, SelectResponse, StatsResponse, StoreChecksumResponse,
+        StoreResponse, SubscriptionResponse, TripleConstructTemplate, TriplePattern,
+        VarOrNamedNode, VarOrNode, VarOrNodeOrLiteral, WhereClause, IRI,
+    };
+    use crate::querier::triple_as_atom;
+    use crate::state::{
+        scan_triple_indexes, IndexFault, NamespaceResolver, StoreLimits, TermQuerier, GRANTS,
+        PREFIXES, QUERY_AUDIT_ENTRIES, RECENT_TRIPLES, STORED_QUERIES, STORE_CHECKSUM,
+        SUBSCRIPTIONS, TERM_STAT,
+    };
+    use axone_rdf::normalize::IdentifierIssuer;
+    use cosmwasm_std::{to_json_vec, Addr, HexBinary, Order, Uint64};
+    use cw_storage_plus::Bound;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    pub fn store(deps: Deps<'_>) -> StdResult<StoreResponse> {
+        STORE.load(deps.storage).map(Into::into)
+    }
+
+    pub fn stats(deps: Deps<'_>) -> StdResult<StatsResponse> {
+        let stat = STORE.load(deps.storage)?.stat;
+        let term_stat = TERM_STAT.may_load(deps.storage)?.unwrap_or_default();
+
+        Ok(StatsResponse {
+            triple_count: stat.triple_count,
+            subject_count: term_stat.subject_count,
+            predicate_count: term_stat.predicate_count,
+            object_count: term_stat.object_count,
+            namespace_count: stat.namespace_count,
+            literal_tag_count: term_stat.literal_tag_count,
+            byte_size: ByteSizeBreakdown {
+                total: stat.byte_size,
+                subjects: term_stat.subject_byte_size,
+                predicates: term_stat.predicate_byte_size,
+                objects: term_stat.object_byte_size,
+                literal_tags: term_stat.literal_tag_byte_size,
             },
-        );
-        assert_eq!(NAMESPACE_KEY_INCREMENT.load(&deps.storage).unwrap(), 17u128);
+        })
     }
 
-    #[test]
-    fn insert_unauthorized() {
-        let mut deps = mock_dependencies();
-        instantiate(
-            deps.as_mut(),
-            mock_env(),
-            message_info(&addr(OWNER), &[]),
-            InstantiateMsg::default(),
-        )
-        .unwrap();
+    pub fn store_checksum(deps: Deps<'_>) -> StdResult<StoreChecksumResponse> {
+        let checksum = STORE_CHECKSUM.may_load(deps.storage)?.unwrap_or_default();
 
-        let res = execute(
-            deps.as_mut(),
-            mock_env(),
-            message_info(&addr("not-owner"), &[]),
-            InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
-            },
-        );
-        assert!(res.is_err());
-        assert_eq!(res.err().unwrap(), ContractError::Unauthorized);
+        Ok(StoreChecksumResponse {
+            checksum: HexBinary::from(checksum.to_be_bytes()),
+            triple_count: STORE.load(deps.storage)?.stat.triple_count,
+        })
     }
 
-    #[test]
-    fn insert_limits() {
-        let cases = vec![
-            (
-                StoreLimitsInputBuilder::default()
-                    .max_triple_count(30u128)
-                    .build()
-                    .unwrap(),
-                Some(ContractError::from(StoreError::TripleCount(30u128.into()))),
-            ),
-            (
-                StoreLimitsInputBuilder::default()
-                    .max_triple_count(40u128)
-                    .build()
-                    .unwrap(),
-                None,
-            ),
-            (
-                StoreLimitsInputBuilder::default()
-                    .max_byte_size(50u128)
-                    .build()
-                    .unwrap(),
-                Some(ContractError::from(StoreError::ByteSize(50u128.into()))),
-            ),
-            (
-                StoreLimitsInputBuilder::default()
-                    .max_byte_size(50000u128)
-                    .build()
-                    .unwrap(),
-                None,
-            ),
-            (
-                StoreLimitsInputBuilder::default()
-                    .max_insert_data_byte_size(500u128)
-                    .build()
-                    .unwrap(),
-                Some(ContractError::from(StoreError::InsertDataByteSize(
-                    500u128.into(),
-                ))),
-            ),
-            (
-                StoreLimitsInputBuilder::default()
-                    .max_insert_data_byte_size(50000u128)
-                    .build()
-                    .unwrap(),
-                None,
-            ),
-            (
-                StoreLimitsInputBuilder::default()
-                    .max_triple_byte_size(150u128)
-                    .build()
-                    .unwrap(),
-                Some(ContractError::from(StoreError::TripleByteSize(
-                    177u128.into(),
-                    150u128.into(),
-                ))),
-            ),
-            (
-                StoreLimitsInputBuilder::default()
-                    .max_triple_byte_size(400u128)
-                    .build()
-                    .unwrap(),
-                None,
-            ),
-            (
-                StoreLimitsInputBuilder::default()
-                    .max_insert_data_triple_count(30u128)
-                    .build()
-                    .unwrap(),
-                Some(ContractError::from(StoreError::InsertDataTripleCount(
-                    30u128.into(),
-                ))),
-            ),
-            (
-                StoreLimitsInputBuilder::default()
-                    .max_insert_data_triple_count(40u128)
-                    .build()
-                    .unwrap(),
-                None,
-            ),
-        ];
+    pub fn pending_owner(deps: Deps<'_>) -> StdResult<PendingOwnerResponse> {
+        Ok(PendingOwnerResponse {
+            pending_owner: PENDING_OWNER.may_load(deps.storage)?.map(Into::into),
+        })
+    }
 
-        let exec_msg = InsertData {
-            format: Some(DataFormat::RDFXml),
-            data: read_test_data("sample.rdf.xml"),
-        };
-        for case in cases {
-            let mut deps = mock_dependencies();
+    pub fn grantees(
+        deps: Deps<'_>,
+        after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<GranteesResponse> {
+        let store = STORE.load(deps.storage)?;
+        let limit = limit.unwrap_or(store.limits.max_query_limit);
+        if limit > store.limits.max_query_limit {
+            Err(StoreError::QueryLimit(store.limits.max_query_limit))?;
+        }
 
-            let info = message_info(&addr(OWNER), &[]);
-            instantiate(
-                deps.as_mut(),
-                mock_env(),
-                info.clone(),
-                InstantiateMsg { limits: case.0 },
+        let after = after.map(Addr::unchecked);
+        let grantees = GRANTS
+            .range(
+                deps.storage,
+                after.as_ref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
             )
-            .unwrap();
+            .take(limit as usize)
+            .map(|res| {
+                res.map(|(address, permissions)| Grantee {
+                    address: address.into(),
+                    permissions: permissions.into_iter().collect(),
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
 
-            let res = execute(deps.as_mut(), mock_env(), info.clone(), exec_msg.clone());
+        Ok(GranteesResponse { grantees })
+    }
 
-            if let Some(err) = case.1 {
-                assert!(res.is_err());
-                assert_eq!(res.err().unwrap(), err);
-            } else {
-                assert!(res.is_ok());
-            }
-        }
+    pub fn subscription(deps: Deps<'_>, id: u64) -> StdResult<SubscriptionResponse> {
+        Ok(SUBSCRIPTIONS.load(deps.storage, id)?.into_response(id))
     }
 
-    #[test]
-    fn proper_delete() {
-        let id = "https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473";
-        let cases = vec![
-            (
-                DeleteData {
-                    prefixes: vec![],
-                    delete: vec![msg::TripleDeleteTemplate {
-                        subject: VarOrNamedNode::NamedNode(Full(
+    pub fn prefixes(deps: Deps<'_>) -> StdResult<PrefixesResponse> {
+        let prefixes = PREFIXES
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|res| res.map(|(prefix, namespace)| Prefix { prefix, namespace }))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(PrefixesResponse { prefixes })
+    }
+
+    pub fn namespaces(
+        deps: Deps<'_>,
+        cursor: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<NamespacesResponse> {
+        let store = STORE.load(deps.storage)?;
+        let limit = limit.unwrap_or(store.limits.max_query_limit);
+        if limit > store.limits.max_query_limit {
+            Err(StoreError::QueryLimit(store.limits.max_query_limit))?;
+        }
+
+        let namespaces = crate::state::namespaces()
+            .range(
+                deps.storage,
+                cursor.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit as usize)
+            .map(|res| {
+                res.map(|(_, ns)| NamespaceUsage {
+                    namespace: ns.value,
+                    key: ns.key.into(),
+                    references: ns.counter.into(),
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(NamespacesResponse { namespaces })
+    }
+
+    pub fn query_audit(
+        deps: Deps<'_>,
+        after: Option<Uint64>,
+        limit: Option<u32>,
+    ) -> StdResult<QueryAuditResponse> {
+        let audit = QUERY_AUDIT
+            .may_load(deps.storage)?
+            .ok_or_else(|| StdError::generic_err("Query audit log not enabled"))?;
+        let store = STORE.load(deps.storage)?;
+        let limit = limit.unwrap_or(store.limits.max_query_limit);
+        if limit > store.limits.max_query_limit {
+            Err(StoreError::QueryLimit(store.limits.max_query_limit))?;
+        }
+
+        let entries = QUERY_AUDIT_ENTRIES
+            .range(
+                deps.storage,
+                after.map(|after| Bound::exclusive(after.u64())),
+                None,
+                Order::Ascending,
+            )
+            .take(limit as usize)
+            .map(|res| {
+                res.map(|(id, entry)| QueryAuditEntry {
+                    id: id.into(),
+                    operation: entry.operation,
+                    query_hash: entry.query_hash,
+                    caller: entry.caller.into(),
+                    pattern_count: entry.pattern_count,
+                    result_count: entry.result_count.into(),
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(QueryAuditResponse {
+            capacity: audit.capacity,
+            last_entry: audit.last_entry.into(),
+            entries,
+        })
+    }
+
+    pub fn select(deps: Deps<'_>, query: SelectQuery) -> StdResult<SelectResponse> {
+        let store = STORE.load(deps.storage)?;
+        let count = engine::validate_select(&query, &store.limits)?;
+        let inference = engine::validate_reasoning(&query, &store.limits)?;
+
+        let plan = engine::plan(
+            deps.storage,
+            Some(deps.querier),
+            query.prefixes,
+            Some(&query.r#where),
+            None,
+            (!query.ordered).then_some(count as usize),
+            inference,
+        )?
+        .with_dataset(query.from, query.from_named)?;
+        let plan = if query.adaptive_joins {
+            plan.with_adaptive_joins()
+        } else {
+            plan
+        };
+        if query.ordered {
+            engine::select_ordered(
+                deps.storage,
+                plan,
+                query.select,
+                query.skolemize,
+                count as usize,
+                store.limits.max_query_ordered_solution_count,
+            )
+        } else {
+            engine::select(deps.storage, plan, query.select, query.skolemize)
+        }
+    }
+
+    pub fn stored_query(
+        deps: Deps<'_>,
+        name: String,
+        parameters: BTreeMap<String, ParameterValue>,
+    ) -> StdResult<SelectResponse> {
+        let stored = STORED_QUERIES
+            .may_load(deps.storage, &name)?
+            .ok_or_else(|| StdError::not_found(format!("Stored query '{name}'")))?;
+
+        select(deps, stored.bind(&parameters)?)
+    }
+
+    pub fn count(deps: Deps<'_>, query: CountQuery) -> StdResult<CountResponse> {
+        let store = STORE.load(deps.storage)?;
+        let inference = engine::validate_count(&query, &store.limits)?;
+
+        let plan = engine::plan(
+            deps.storage,
+            Some(deps.querier),
+            query.prefixes,
+            Some(&query.r#where),
+            None,
+            None,
+            inference,
+        )?;
+        Ok(CountResponse {
+            count: engine::count(deps.storage, plan)?.into(),
+        })
+    }
+
+    pub fn explain(deps: Deps<'_>, query: SelectQuery) -> StdResult<ExplainResponse> {
+        let store = STORE.load(deps.storage)?;
+        let count = engine::validate_select(&query, &store.limits)?;
+        let inference = engine::validate_reasoning(&query, &store.limits)?;
+
+        let plan = engine::plan(
+            deps.storage,
+            Some(deps.querier),
+            query.prefixes,
+            Some(&query.r#where),
+            None,
+            Some(count as usize),
+            inference,
+        )?
+        .with_dataset(query.from, query.from_named)?;
+        let plan = if query.adaptive_joins {
+            plan.with_adaptive_joins()
+        } else {
+            plan
+        };
+        Ok(ExplainResponse {
+            plan: engine::explain(deps.storage, &plan)?,
+        })
+    }
+
+    pub fn budgeted_select(
+        deps: Deps<'_>,
+        query: SelectQuery,
+        max_rows: u32,
+        cursor: Option<SelectCursor>,
+    ) -> StdResult<BudgetedSelectResponse> {
+        let store = STORE.load(deps.storage)?;
+        let count = engine::validate_select(&query, &store.limits)?;
+        let inference = engine::validate_reasoning(&query, &store.limits)?;
+        let returned = cursor.as_ref().map_or(0, |c| c.results);
+
+        let plan = engine::plan(
+            deps.storage,
+            None,
+            query.prefixes,
+            Some(&query.r#where),
+            None,
+            Some(count.saturating_sub(returned) as usize),
+            inference,
+        )?
+        .with_dataset(query.from, query.from_named)?;
+        engine::select_budgeted(
+            deps.storage,
+            plan,
+            query.select,
+            max_rows,
+            cursor,
+            query.skolemize,
+        )
+    }
+
+    pub fn provenance(deps: Deps<'_>, query: ProvenanceQuery) -> StdResult<ProvenanceResponse> {
+        let store = STORE.load(deps.storage)?;
+        let limit = query.limit.unwrap_or(store.limits.max_query_limit);
+        if limit > store.limits.max_query_limit {
+            Err(StoreError::QueryLimit(store.limits.max_query_limit))?;
+        }
+
+        let plan = engine::plan(
+            deps.storage,
+            None,
+            query.prefixes,
+            Some(&WhereClause::Bgp {
+                patterns: vec![query.pattern],
+            }),
+            None,
+            None,
+            None,
+        )?;
+        engine::provenance(deps.storage, plan, limit as usize)
+    }
+
+    pub fn describe(
+        deps: Deps<'_>,
+        query: DescribeQuery,
+        format: DataFormat,
+    ) -> StdResult<DescribeResponse> {
+        let (p, o) = ("_2p".to_owned(), "_3o".to_owned());
+
+        let (construct, r#where) = match &query.resource {
+            VarOrNamedNode::Variable(var) => {
+                let select = TriplePattern {
+                    subject: VarOrNode::Variable(var.clone()),
+                    predicate: VarOrNamedNode::Variable(format!("{var}{p}")),
+                    object: VarOrNodeOrLiteral::Variable(format!("{var}{o}")),
+                };
+
+                let r#where = match query.r#where {
+                    Some(c) => WhereClause::LateralJoin {
+                        left: Box::new(c),
+                        right: Box::new(WhereClause::Bgp {
+                            patterns: vec![select.clone()],
+                        }),
+                    },
+                    None => WhereClause::Bgp {
+                        patterns: vec![select.clone()],
+                    },
+                };
+
+                (vec![select], r#where)
+            }
+            VarOrNamedNode::NamedNode(iri) => {
+                let select = TriplePattern {
+                    subject: VarOrNode::Node(Node::NamedNode(iri.clone())),
+                    predicate: VarOrNamedNode::Variable(p),
+                    object: VarOrNodeOrLiteral::Variable(o),
+                };
+
+                (
+                    vec![select.clone()],
+                    WhereClause::Bgp {
+                        patterns: vec![select],
+                    },
+                )
+            }
+        };
+
+        let out = construct_atoms(
+            deps,
+            &format,
+            query.prefixes,
+            construct
+                .into_iter()
+                .map(|t| (t.subject, t.predicate, t.object))
+                .collect(),
+            r#where,
+        )?;
+
+        Ok(DescribeResponse {
+            format,
+            data: Binary::from(out),
+        })
+    }
+
+    pub fn construct(
+        deps: Deps<'_>,
+        query: ConstructQuery,
+        format: DataFormat,
+        cursor: Option<Uint64>,
+    ) -> StdResult<ConstructResponse> {
+        let ConstructQuery {
+            construct,
+            prefixes,
+            r#where,
+            fresh_blank_nodes,
+        } = query;
+
+        let construct = if construct.is_empty() {
+            match &r#where {
+                WhereClause::Bgp { patterns } => patterns
+                    .iter()
+                    .map(|p| TripleConstructTemplate {
+                        subject: p.subject.clone(),
+                        predicate: p.predicate.clone(),
+                        object: p.object.clone(),
+                    })
+                    .collect(),
+                _ => Err(StdError::generic_err("missing triples to construct"))?,
+            }
+        } else {
+            construct
+        };
+
+        let mut id_issuer = IdentifierIssuer::new("a", 0u128);
+        let construct: Vec<_> = construct
+            .into_iter()
+            .map(|t| TripleConstructTemplate {
+                subject: match t.subject {
+                    VarOrNode::Node(Node::BlankNode(n)) => {
+                        VarOrNode::Node(Node::BlankNode(id_issuer.get_str_or_issue(n).to_string()))
+                    }
+                    _ => t.subject,
+                },
+                predicate: t.predicate,
+                object: match t.object {
+                    VarOrNodeOrLiteral::Node(Node::BlankNode(n)) => VarOrNodeOrLiteral::Node(
+                        Node::BlankNode(id_issuer.get_str_or_issue(n).to_string()),
+                    ),
+                    _ => t.object,
+                },
+            })
+            .collect();
+
+        let store = STORE.load(deps.storage)?;
+        engine::validate_where(&r#where, &store.limits)?;
+
+        let skip = cursor.map_or(0, |c| c.u64());
+        let plan = engine::plan(
+            deps.storage,
+            Some(deps.querier),
+            prefixes,
+            Some(&r#where),
+            Some(skip as usize),
+            None,
+            None,
+        )?;
+        let templates: Vec<_> = construct
+            .into_iter()
+            .map(|t| (t.subject, t.predicate, t.object))
+            .collect();
+
+        let literals = engine::OffloadedLiterals::load(deps.storage, deps.querier)?;
+        fitting_page(
+            store.limits.max_query_limit as usize,
+            &store.limits,
+            |page_size| {
+                let (out, remaining) = engine::construct_page(
+                    deps.storage,
+                    plan.clone(),
+                    templates.clone(),
+                    &format,
+                    skip as usize,
+                    page_size,
+                    fresh_blank_nodes,
+                    literals.as_ref(),
+                )?;
+                Ok(ConstructResponse {
+                    format: format.clone(),
+                    data: Binary::from(out),
+                    cursor: remaining.then(|| Uint64::new(skip + page_size as u64)),
+                })
+            },
+        )
+    }
+
+    pub fn export(
+        deps: Deps<'_>,
+        cursor: Option<Binary>,
+        limit: Option<u32>,
+        format: DataFormat,
+    ) -> StdResult<ExportResponse> {
+        let store = STORE.load(deps.storage)?;
+        let limit = limit.unwrap_or(store.limits.max_query_limit);
+        if limit > store.limits.max_query_limit {
+            Err(StoreError::QueryLimit(store.limits.max_query_limit))?;
+        }
+
+        let after: Option<Vec<u8>> = cursor.map(Binary::into);
+        fitting_page(limit as usize, &store.limits, |limit| {
+            let (data, cursor) = engine::export(deps.storage, after.clone(), limit, &format)?;
+            Ok(ExportResponse {
+                format: format.clone(),
+                data: Binary::from(data),
+                cursor: cursor.map(Binary::from),
+            })
+        })
+    }
+
+    pub fn check_integrity(
+        deps: Deps<'_>,
+        cursor: Option<Binary>,
+        limit: Option<u32>,
+    ) -> StdResult<IntegrityResponse> {
+        let store = STORE.load(deps.storage)?;
+        let limit = limit.unwrap_or(store.limits.max_query_limit);
+        if limit > store.limits.max_query_limit {
+            Err(StoreError::QueryLimit(store.limits.max_query_limit))?;
+        }
+
+        let scan = scan_triple_indexes(
+            deps.storage,
+            cursor.as_ref().map(Binary::as_slice),
+            limit.max(1) as usize,
+        )?;
+
+        Ok(IntegrityResponse {
+            scanned_count: scan.scanned as u32,
+            faults: scan
+                .faults
+                .into_iter()
+                .map(|fault| match fault {
+                    IndexFault::Missing { index, key, .. } => IntegrityFault {
+                        index,
+                        kind: IntegrityFaultKind::Missing,
+                        key: key.into(),
+                    },
+                    IndexFault::Dangling { index, key, .. } => IntegrityFault {
+                        index,
+                        kind: IntegrityFaultKind::Dangling,
+                        key: key.into(),
+                    },
+                })
+                .collect(),
+            cursor: scan.cursor.map(Binary::from),
+        })
+    }
+
+    pub fn backlinks(
+        deps: Deps<'_>,
+        iri: IRI,
+        cursor: Option<Binary>,
+    ) -> StdResult<BacklinksResponse> {
+        let store = STORE.load(deps.storage)?;
+        let after: Option<Vec<u8>> = cursor.map(Binary::into);
+        fitting_page(
+            store.limits.max_query_limit.max(1) as usize,
+            &store.limits,
+            |limit| {
+                let (backlinks, cursor) =
+                    engine::backlinks(deps.storage, iri.clone(), after.clone(), limit)?;
+                Ok(BacklinksResponse {
+                    backlinks,
+                    cursor: cursor.map(Binary::from),
+                })
+            },
+        )
+    }
+
+    /// Builds the response page of the largest size up to `limit` whose serialization fits in the
+    /// maximum result byte size of the store, looking for this size through a binary search so only
+    /// a logarithmic number of pages get built. The page of a single result is returned even if it
+    /// doesn't fit, so the pagination can go on.
+    fn fitting_page<T: Serialize>(
+        limit: usize,
+        limits: &StoreLimits,
+        page: impl Fn(usize) -> StdResult<T>,
+    ) -> StdResult<T> {
+        let response = page(limit)?;
+        if limit <= 1 || fits_result(&response, limits)? {
+            return Ok(response);
+        }
+
+        let mut fitting = None;
+        let (mut low, mut high) = (1, limit - 1);
+        while low <= high {
+            let size = low + (high - low) / 2;
+            let response = page(size)?;
+            if size == 1 || fits_result(&response, limits)? {
+                fitting = Some(response);
+                low = size + 1;
+            } else {
+                high = size - 1;
+            }
+        }
+        fitting.map_or_else(|| page(1), Ok)
+    }
+
+    /// Whether the serialized response fits in the maximum result byte size of the store.
+    fn fits_result<T: Serialize>(response: &T, limits: &StoreLimits) -> StdResult<bool> {
+        Ok(Uint128::from(to_json_vec(response)?.len() as u128) <= limits.max_result_byte_size)
+    }
+
+    pub fn recent_changes(
+        deps: Deps<'_>,
+        batches: u32,
+        format: DataFormat,
+    ) -> StdResult<RecentChangesResponse> {
+        let window = RECENT_WINDOW
+            .may_load(deps.storage)?
+            .ok_or_else(|| StdError::generic_err("Recent window index not enabled"))?;
+        if batches > window.size {
+            Err(StdError::generic_err(format!(
+                "Requested batches exceed the recent window size: {}",
+                window.size
+            )))?;
+        }
+
+        let first_batch = (window.last_batch + 1)
+            .saturating_sub(batches as u64)
+            .max(window.first_batch());
+        let mut ns_solver = NamespaceResolver::new(deps.storage, vec![]);
+        let mut term_querier = TermQuerier::new();
+        let mut id_issuer = IdentifierIssuer::new("b", 0u128);
+        let mut atoms = Vec::new();
+        for res in RECENT_TRIPLES.range(
+            deps.storage,
+            Some(Bound::inclusive((first_batch, 0))),
+            None,
+            Order::Ascending,
+        ) {
+            // The triples deleted since their insertion are skipped, their terms being possibly
+            // released from the term dictionary.
+            let (_, triple) = res?;
+            if triple.is_stored(deps.storage)? {
+                let triple = triple.resolve(deps.storage, &mut term_querier)?;
+                atoms.push(triple_as_atom(&mut ns_solver, &mut id_issuer, triple)?);
+            }
+        }
+
+        Ok(RecentChangesResponse {
+            first_batch,
+            last_batch: window.last_batch,
+            format: format.clone(),
+            data: Binary::from(engine::serialize_atoms(&format, &atoms)?),
+        })
+    }
+
+    fn construct_atoms(
+        deps: Deps<'_>,
+        format: &DataFormat,
+        prefixes: Vec<Prefix>,
+        construct: Vec<(VarOrNode, VarOrNamedNode, VarOrNodeOrLiteral)>,
+        r#where: WhereClause,
+    ) -> StdResult<Vec<u8>> {
+        let store = STORE.load(deps.storage)?;
+        engine::validate_where(&r#where, &store.limits)?;
+
+        let plan = engine::plan(
+            deps.storage,
+            Some(deps.querier),
+            prefixes,
+            Some(&r#where),
+            None,
+            Some(store.limits.max_query_limit as usize),
+            None,
+        )?;
+        let literals = engine::OffloadedLiterals::load(deps.storage, deps.querier)?;
+        engine::construct(deps.storage, plan, construct, format, literals.as_ref())
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut<'_>, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let layout = STATE_LAYOUT.may_load(deps.storage)?.unwrap_or_default();
+    migrate::migrate_state(deps.storage, layout)?;
+    if msg.rebuild_indexes {
+        migrate::index_triples(deps.storage)?;
+    }
+
+    let mut resp = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", previous.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION);
+    if TRIPLE_MIGRATION.exists(deps.storage) {
+        let batch = migrate::migrate_triples_batch(deps.storage, None)?;
+        resp = resp
+            .add_attribute("interned_triple_count", batch.interned.to_string())
+            .add_attribute("indexed_triple_count", batch.indexed.to_string())
+            .add_attribute("completed", batch.completed.to_string());
+    }
+
+    Ok(resp)
+}
+
+pub mod migrate {
+    use super::*;
+    use crate::state::{migrate_triples, TripleMigration};
+    use crate::storer::StoreEngine;
+    use cosmwasm_std::Storage;
+
+    /// The version of the storage layout written by the contract, see [STATE_LAYOUT].
+    pub const LAYOUT_VERSION: u16 = 1;
+
+    /// A state migration step, transforming the state written with layouts older than the one
+    /// associated.
+    type Migration = fn(&mut dyn Storage) -> StdResult<()>;
+
+    /// The state migration steps associated to the layout version introducing the related storage
+    /// layout change, in ascending layout version order.
+    const MIGRATIONS: &[(u16, Migration)] =
+        &[(1, intern_terms), (1, index_triples), (1, count_stats)];
+
+    /// Start the interning of the terms of the stored triples in the term dictionary, referenced by
+    /// their identifier since the layout version `1`, carried on in batches through
+    /// [migrate_triples_batch]. It comes first as the other steps read the terms.
+    fn intern_terms(storage: &mut dyn Storage) -> StdResult<()> {
+        TRIPLE_MIGRATION.save(storage, &TripleMigration::default())
+    }
+
+    /// Start indexing the stored triples by predicate and object and by object and subject, indexes
+    /// introduced in the layout version `1`, carried on in batches through [migrate_triples_batch]
+    /// along with the interning of their terms.
+    pub fn index_triples(storage: &mut dyn Storage) -> StdResult<()> {
+        let migration = TRIPLE_MIGRATION.may_load(storage)?.unwrap_or_default();
+        TRIPLE_MIGRATION.save(
+            storage,
+            &TripleMigration {
+                cursor: None,
+                index: true,
+                scanned: false,
+                ..migration
+            },
+        )
+    }
+
+    /// Count again the statistics about the distinct terms of the store and the checksum of its
+    /// triples, maintained since the layout version `1`, carried on in batches through
+    /// [migrate_triples_batch] once all the triples are migrated.
+    fn count_stats(storage: &mut dyn Storage) -> StdResult<()> {
+        let migration = TRIPLE_MIGRATION.may_load(storage)?.unwrap_or_default();
+        TRIPLE_MIGRATION.save(
+            storage,
+            &TripleMigration {
+                count_terms: true,
+                ..migration
+            },
+        )
+    }
+
+    /// The outcome of a batch of [migrate_triples_batch].
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct MigrationBatch {
+        /// The number of triples interned among the scanned ones.
+        pub interned: usize,
+        /// The number of triples indexed again among the scanned ones.
+        pub indexed: usize,
+        /// Whether the migration is completed.
+        pub completed: bool,
+    }
+
+    /// Scan the next batch of records of the migration in progress: the triples, then the records
+    /// the statistics about the terms are counted from if required, the steps deferred to its
+    /// completion being applied once all of them are scanned.
+    pub fn migrate_triples_batch(
+        storage: &mut dyn Storage,
+        limit: Option<u32>,
+    ) -> Result<MigrationBatch, ContractError> {
+        let mut migration = TRIPLE_MIGRATION
+            .may_load(storage)?
+            .ok_or_else(|| StdError::generic_err("No triple migration in progress"))?;
+
+        let store = STORE.load(storage)?;
+        let limit = limit.unwrap_or(store.limits.max_query_limit);
+        if limit > store.limits.max_query_limit {
+            Err(StoreError::QueryLimit(store.limits.max_query_limit))?;
+        }
+
+        let mut limit = limit.max(1) as usize;
+        let mut batch = MigrationBatch::default();
+        if !migration.scanned {
+            let triples = migrate_triples(storage, &migration, limit)?;
+            batch.interned = triples.interned;
+            batch.indexed = triples.indexed;
+            limit -= triples.scanned;
+            migration.scanned = triples.cursor.is_none();
+            migration.cursor = triples.cursor;
+        }
+        if migration.scanned && migration.count_terms && limit > 0 {
+            let scan =
+                StoreEngine::new(storage)?.count_stats(migration.cursor.as_deref(), limit)?;
+            migration.count_terms = scan.cursor.is_some();
+            migration.cursor = scan.cursor;
+        }
+
+        batch.completed = migration.scanned && !migration.count_terms;
+        if batch.completed {
+            TRIPLE_MIGRATION.remove(storage);
+        } else {
+            TRIPLE_MIGRATION.save(storage, &migration)?;
+        }
+
+        Ok(batch)
+    }
+
+    /// Apply the migration steps of the layout versions newer than the one of the state, which is
+    /// then marked as written with the current layout.
+    pub fn migrate_state(storage: &mut dyn Storage, from: u16) -> StdResult<()> {
+        for (layout, migration) in MIGRATIONS {
+            if from < *layout {
+                migration(storage)?;
+            }
+        }
+
+        STATE_LAYOUT.save(storage, &LAYOUT_VERSION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{QueryComplexityError, StoreError};
+    use crate::msg::ExecuteMsg::{DeleteData, InsertData};
+    use crate::msg::Node::{BlankNode, NamedNode};
+    use crate::msg::IRI::{Full, Prefixed};
+    use crate::msg::{
+        Backlink, BacklinksResponse, BudgetedSelectResponse, ByteSizeBreakdown, ConflictPolicy,
+        ConstructQuery, ConstructResponse, CountQuery, CountResponse, DataCompression,
+        DescribeQuery, DescribeResponse, ExplainResponse, ExportResponse, Grantee,
+        GranteesResponse, Head, InsertBatchReceipt, InsertDataReceipt, Insertion,
+        IntegrityFaultKind, IntegrityResponse, Literal, NamespacesResponse, ParameterValue,
+        PatternTerm, PendingOwnerResponse, Permission, PlanNode, Prefix, PrefixesResponse,
+        ProvenanceQuery, ProvenanceResponse, QueryAuditEntry, QueryAuditResponse,
+        RecentChangesResponse, Results, SelectCursor, SelectItem, SelectQuery, SelectResponse,
+        ShardBatch, ShardInsertReceipt, StatsResponse, StoreChecksumResponse, StoreLimitsInput,
+        StoreLimitsInputBuilder, StoreResponse, SubjectSelection, SubscriptionCallbackMsg,
+        SubscriptionResponse, TripleIndex, TripleProvenance, Value, VarOrNamedNode,
+        VarOrNamedNodeOrLiteral, VarOrNode, VarOrNodeOrLiteral,
+    };
+    use crate::msg::{TriplePattern, WhereClause};
+    use crate::state::{
+        namespaces, triples, Namespace, Node, Object, Provenance, StoreLimits, StoreStat,
+        StoredTriple, Subject, TermQuerier, TermRef, Triple, EXPIRING_TRIPLES, INSERT_CHUNKS,
+        INSERT_SESSIONS, PENDING_BATCH, STORE_CHECKSUM, SUBJECT_PROVENANCE, TERMS, TERM_STAT,
+        TRIPLE_EXPIRATIONS, TRIPLE_INSERTIONS,
+    };
+    use crate::{msg, state};
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{
+        coins, from_json, Addr, Attribute, ContractResult, CosmosMsg, Event, HexBinary,
+        MsgResponse, Order, QuerierWrapper, Record, ReplyOn, Storage, SubMsg, SubMsgResponse,
+        SubMsgResult, SystemError, SystemResult, Uint128, Uint64, WasmMsg, WasmQuery,
+    };
+    use cw_utils::PaymentError::NonPayable;
+    use cw_utils::{Expiration, PaymentError};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::cell::Cell;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::fs::File;
+    use std::io::{Read, Write};
+    use std::path::Path;
+    use std::{env, u128};
+    use testing::addr::{addr, OWNER, SENDER};
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            limits: StoreLimitsInput {
+                max_triple_count: Uint128::from(1u128),
+                max_byte_size: Uint128::from(2u128),
+                max_triple_byte_size: Uint128::from(3u128),
+                max_query_limit: 4,
+                max_query_variable_count: 5,
+                max_insert_data_byte_size: Uint128::from(6u128),
+                max_insert_data_triple_count: Uint128::from(7u128),
+                max_query_pattern_count: 8,
+                max_query_where_depth: 9,
+                max_query_join_variable_count: 10,
+                max_query_inference_depth: 5,
+                max_result_byte_size: Uint128::from(11u128),
+                max_insert_blank_node_count: Uint128::from(12u128),
+                max_insert_session_byte_size: Uint128::from(13u128),
+                max_query_ordered_solution_count: 14,
+            },
+            recent_window: Some(8),
+            query_audit: None,
+            conflict_policy: Default::default(),
+            literal_offload: None,
+            disable_literal_normalization: false,
+            soft_limit: None,
+        };
+
+        let info = message_info(&addr(OWNER), &[]);
+        let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        let store = STORE.load(&deps.storage).unwrap();
+        assert_eq!(store.owner, info.sender);
+        assert_eq!(
+            store.limits,
+            state::StoreLimits {
+                max_triple_count: Uint128::from(1u128),
+                max_byte_size: Uint128::from(2u128),
+                max_triple_byte_size: Uint128::from(3u128),
+                max_query_limit: 4,
+                max_query_variable_count: 5,
+                max_insert_data_byte_size: Uint128::from(6u128),
+                max_insert_data_triple_count: Uint128::from(7u128),
+                max_query_pattern_count: 8,
+                max_query_where_depth: 9,
+                max_query_join_variable_count: 10,
+                max_query_inference_depth: 5,
+                max_result_byte_size: Uint128::from(11u128),
+                max_insert_blank_node_count: Uint128::from(12u128),
+                max_insert_session_byte_size: Uint128::from(13u128),
+                max_query_ordered_solution_count: 14,
+            }
+        );
+        assert_eq!(
+            store.stat,
+            StoreStat {
+                triple_count: Uint128::zero(),
+                namespace_count: Uint128::zero(),
+                byte_size: Uint128::zero(),
+            }
+        );
+
+        assert_eq!(NAMESPACE_KEY_INCREMENT.load(&deps.storage).unwrap(), 0u128);
+        assert_eq!(
+            BLANK_NODE_IDENTIFIER_COUNTER.load(&deps.storage).unwrap(),
+            0u128
+        );
+        assert_eq!(
+            RECENT_WINDOW.load(&deps.storage).unwrap(),
+            RecentWindow::new(8)
+        );
+    }
+
+    #[test]
+    fn funds_initialization() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&addr(SENDER), &coins(10, "uaxone"));
+
+        let msg = InstantiateMsg::default();
+
+        let result = instantiate(deps.as_mut(), env, info, msg);
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), ContractError::Payment(NonPayable {}));
+    }
+
+    #[test]
+    fn execute_fail_with_funds() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&addr("sender"), &coins(10, "uaxone"));
+
+        let messages = vec![
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: Binary::from("data".as_bytes()),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![],
+                r#where: None,
+            },
+            ExecuteMsg::InsertBatch {
+                format: None,
+                data: Binary::from("data".as_bytes()),
+                shards: vec![],
+            },
+        ];
+
+        for msg in messages {
+            let result = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+            assert!(result.is_err());
+            assert_eq!(
+                result.unwrap_err(),
+                ContractError::Payment(PaymentError::NonPayable {})
+            );
+        }
+    }
+
+    #[test]
+    fn proper_insert() {
+        let cases = vec![
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: read_test_data("sample.ttl"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: read_test_data("sample.nt"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+            InsertData {
+                format: Some(DataFormat::NQuads),
+                data: read_test_data("sample.nq"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+            InsertData {
+                format: None,
+                data: read_test_data("sample.ttl"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        ];
+
+        for case in cases {
+            let mut deps = mock_dependencies();
+
+            let info = message_info(&addr(OWNER), &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg::default(),
+            )
+            .unwrap();
+
+            let res = execute(deps.as_mut(), mock_env(), info.clone(), case);
+
+            assert!(res.is_ok());
+            assert_eq!(
+                res.unwrap().attributes,
+                vec![
+                    Attribute::new("action", "insert"),
+                    Attribute::new("triple_count", "40"),
+                ]
+            );
+
+            assert_eq!(
+                triples()
+                    .range_raw(&deps.storage, None, None, Order::Ascending)
+                    .count(),
+                40
+            );
+            assert_eq!(
+                STORE.load(&deps.storage).unwrap().stat,
+                StoreStat {
+                    triple_count: 40u128.into(),
+                    namespace_count: 17u128.into(),
+                    byte_size: 7180u128.into(),
+                },
+            );
+            assert_eq!(NAMESPACE_KEY_INCREMENT.load(&deps.storage).unwrap(), 17u128);
+            assert_eq!(
+                namespaces()
+                    .load(
+                        &deps.storage,
+                        "https://ontology.axone.space/dataverse/dataspace/".to_string(),
+                    )
+                    .unwrap(),
+                Namespace {
+                    value: "https://ontology.axone.space/dataverse/dataspace/".to_string(),
+                    key: 0u128,
+                    counter: 5u128,
+                }
+            );
+            assert_eq!(
+                triples()
+                    .load(
+                        &deps.storage,
+                        (
+                            Object::Named(Node {
+                                namespace: 4u128,
+                                value: "0x04d1f1b8f8a7a28f9a5a254c326a963a22f5a5b5d5f5e5d5c5b5a5958575655"
+                                    .to_string(),
+                            }).as_hash()
+                                .as_bytes(),
+                            Node {
+                                namespace: 3u128,
+                                value: "hasRegistrar".to_string(),
+                            }
+                                .key(),
+                            Subject::Named(Node {
+                                namespace: 0u128,
+                                value: "97ff7e16-c08d-47be-8475-211016c82e33".to_string(),
+                            })
+                                .key()
+                        ),
+                    )
+                    .unwrap()
+                    .resolve(&deps.storage, &mut TermQuerier::new())
+                    .unwrap(),
+                Triple {
+                    object: Object::Named(Node {
+                        namespace: 4u128,
+                        value: "0x04d1f1b8f8a7a28f9a5a254c326a963a22f5a5b5d5f5e5d5c5b5a5958575655"
+                            .to_string(),
+                    }),
+                    predicate: Node {
+                        namespace: 3u128,
+                        value: "hasRegistrar".to_string(),
+                    },
+                    subject: Subject::Named(Node {
+                        namespace: 0u128,
+                        value: "97ff7e16-c08d-47be-8475-211016c82e33".to_string(),
+                    }),
+                }
+            )
+        }
+    }
+
+    #[test]
+    fn proper_insert_compressed() {
+        let data = read_test_data("sample.rdf.xml");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data.as_slice()).unwrap();
+        let compressed = Binary::from(encoder.finish().unwrap());
+
+        let cases = vec![
+            (
+                Uint128::MAX,
+                compressed.clone(),
+                Ok(vec![
+                    Attribute::new("action", "insert"),
+                    Attribute::new("triple_count", "40"),
+                ]),
+            ),
+            (
+                Uint128::from(data.len() as u128 - 1),
+                compressed,
+                Err(ContractError::from(StoreError::InsertDataByteSize(
+                    Uint128::from(data.len() as u128 - 1),
+                ))),
+            ),
+            (
+                Uint128::MAX,
+                data,
+                Err(ContractError::InvalidCompressedData(
+                    "invalid gzip header".to_string(),
+                )),
+            ),
+        ];
+
+        for (max_insert_data_byte_size, data, expected) in cases {
+            let mut deps = mock_dependencies();
+            let info = message_info(&addr(OWNER), &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    limits: StoreLimitsInput {
+                        max_insert_data_byte_size,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                InsertData {
+                    format: Some(DataFormat::RDFXml),
+                    data,
+                    compression: Some(DataCompression::Gzip),
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+            );
+            assert_eq!(res.map(|res| res.attributes), expected);
+        }
+    }
+
+    #[test]
+    fn proper_insert_batch() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        // the sibling store, owned by the same owner, only lets the sender insert in it
+        let mut sibling = mock_dependencies();
+        instantiate(
+            sibling.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        let grant = |grantee: &str| ExecuteMsg::GrantPermissions {
+            grantee: addr(grantee).to_string(),
+            permissions: vec![Permission::Insert],
+        };
+        execute(sibling.as_mut(), mock_env(), info.clone(), grant(SENDER)).unwrap();
+        let shard = addr("shard");
+        let queried = shard.clone();
+        deps.querier.update_wasm(move |request| match request {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == queried.as_str() => {
+                SystemResult::Ok(
+                    query(sibling.as_ref(), mock_env(), from_json(msg).unwrap()).into(),
+                )
+            }
+            _ => panic!("unexpected query: {request:?}"),
+        });
+        let shard_data = Binary::from("<https://ex.org/b> <https://ex.org/p> \"b\" .".as_bytes());
+        let msg = ExecuteMsg::InsertBatch {
+            format: Some(DataFormat::NTriples),
+            data: Binary::from("<https://ex.org/a> <https://ex.org/p> \"a\" .".as_bytes()),
+            shards: vec![ShardBatch {
+                contract_addr: shard.to_string(),
+                data: shard_data.clone(),
+            }],
+        };
+
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(SENDER), &[]),
+                msg.clone()
+            )
+            .err(),
+            Some(ContractError::Unauthorized)
+        );
+
+        // the store doesn't lend its permission in the sibling to its own grantees
+        for grantee in [SENDER, "other"] {
+            execute(deps.as_mut(), mock_env(), info.clone(), grant(grantee)).unwrap();
+        }
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr("other"), &[]),
+                msg.clone()
+            )
+            .err(),
+            Some(ContractError::ShardUnauthorized(shard.to_string()))
+        );
+        let ExecuteMsg::InsertBatch { format, shards, .. } = msg.clone() else {
+            unreachable!()
+        };
+        let shards_only = ExecuteMsg::InsertBatch {
+            format,
+            data: Binary::default(),
+            shards,
+        };
+        assert!(execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(SENDER), &[]),
+            shards_only
+        )
+        .is_ok());
+
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "insert_batch"),
+                Attribute::new("triple_count", "1"),
+                Attribute::new("shard_count", "1"),
+            ]
+        );
+        assert_eq!(res.data, None);
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::reply_always(
+                WasmMsg::Execute {
+                    contract_addr: shard.to_string(),
+                    msg: to_json_binary(&InsertData {
+                        format: Some(DataFormat::NTriples),
+                        data: shard_data,
+                        compression: None,
+                        expires: None,
+                        graph: None,
+                        skip_oversized: false,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                },
+                SHARD_INSERT_REPLY_ID,
+            )
+            .with_payload(shard.as_bytes().to_vec())]
+        );
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.triple_count,
+            Uint128::one()
+        );
+
+        let shard_receipt = InsertDataReceipt {
+            inserted_count: Uint128::one(),
+            duplicate_count: Uint128::one(),
+            rejected_count: Uint128::zero(),
+        };
+        let data = to_json_binary(&shard_receipt).unwrap();
+        #[allow(deprecated)]
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: SHARD_INSERT_REPLY_ID,
+                payload: Binary::from(shard.as_bytes()),
+                gas_used: 0,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                    msg_responses: vec![MsgResponse {
+                        type_url: "/cosmwasm.wasm.v1.MsgExecuteContractResponse".to_string(),
+                        value: Binary::from([&[0x0a, data.len() as u8], data.as_slice()].concat()),
+                    }],
+                }),
+            },
+        )
+        .unwrap();
+        let receipt = InsertDataReceipt {
+            inserted_count: Uint128::one(),
+            duplicate_count: Uint128::zero(),
+            rejected_count: Uint128::zero(),
+        };
+        assert_eq!(
+            from_json::<InsertBatchReceipt>(res.data.unwrap()).unwrap(),
+            InsertBatchReceipt {
+                receipt: receipt.clone(),
+                shards: vec![ShardInsertReceipt {
+                    contract_addr: shard.to_string(),
+                    receipt: Some(shard_receipt.clone()),
+                }],
+                total: receipt.merge(&shard_receipt),
+            }
+        );
+        assert!(PENDING_BATCH.may_load(&deps.storage).unwrap().is_none());
+
+        let cases = vec![
+            (
+                SHARD_INSERT_REPLY_ID,
+                ContractError::ShardInsertFailed(shard.to_string(), "Unauthorized".to_string()),
+            ),
+            (42, ContractError::UnknownReplyID),
+        ];
+        for (id, expected) in cases {
+            #[allow(deprecated)]
+            let res = reply(
+                deps.as_mut(),
+                mock_env(),
+                Reply {
+                    id,
+                    payload: Binary::from(shard.as_bytes()),
+                    gas_used: 0,
+                    result: SubMsgResult::Err("Unauthorized".to_string()),
+                },
+            );
+            assert_eq!(res.err(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn proper_batch() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from("<https://ex.org/a> <https://ex.org/p> \"old\" .".as_bytes()),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        let upsert = ExecuteMsg::Batch(vec![
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![],
+                r#where: Some(WhereClause::Bgp {
+                    patterns: vec![TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full("https://ex.org/a".to_string()))),
+                        predicate: VarOrNamedNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                }),
+            },
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from("<https://ex.org/a> <https://ex.org/p> \"new\" .".as_bytes()),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        ]);
+
+        let cases = vec![
+            (
+                message_info(&addr(SENDER), &[]),
+                upsert.clone(),
+                ContractError::Unauthorized,
+            ),
+            (
+                info.clone(),
+                ExecuteMsg::Batch(vec![ExecuteMsg::Purge { limit: None }]),
+                ContractError::UnsupportedBatchOperation,
+            ),
+            (
+                info.clone(),
+                ExecuteMsg::Batch(vec![upsert.clone()]),
+                ContractError::UnsupportedBatchOperation,
+            ),
+        ];
+        for (info, msg, expected) in cases {
+            assert_eq!(
+                execute(deps.as_mut(), mock_env(), info, msg).err(),
+                Some(expected)
+            );
+        }
+
+        let res = execute(deps.as_mut(), mock_env(), info, upsert).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "batch"),
+                Attribute::new("operation_count", "2"),
+            ]
+        );
+        assert_eq!(
+            res.events
+                .iter()
+                .filter(|event| event.ty == "batch_operation")
+                .cloned()
+                .collect::<Vec<_>>(),
+            vec![
+                Event::new("batch_operation")
+                    .add_attribute("index", "0")
+                    .add_attribute("action", "delete")
+                    .add_attribute("triple_count", "1"),
+                Event::new("batch_operation")
+                    .add_attribute("index", "1")
+                    .add_attribute("action", "insert")
+                    .add_attribute("triple_count", "1"),
+            ]
+        );
+        assert_eq!(
+            from_json::<InsertDataReceipt>(res.data.unwrap()).unwrap(),
+            InsertDataReceipt {
+                inserted_count: Uint128::one(),
+                duplicate_count: Uint128::zero(),
+                rejected_count: Uint128::zero(),
+            }
+        );
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Select {
+                query: SelectQuery {
+                    prefixes: vec![],
+                    limit: None,
+                    select: vec![SelectItem::Variable("o".to_string())],
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Full(
+                                "https://ex.org/a".to_string(),
+                            ))),
+                            predicate: VarOrNamedNode::Variable("p".to_string()),
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        }],
+                    },
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
+                },
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            from_json::<SelectResponse>(&res).unwrap().results.bindings,
+            vec![BTreeMap::from([(
+                "o".to_string(),
+                Value::Literal {
+                    value: "new".to_string(),
+                    lang: None,
+                    datatype: None,
+                }
+            )])]
+        );
+    }
+
+    #[test]
+    fn proper_insert_blank_nodes() {
+        let mut deps = mock_dependencies();
+
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let insert_msg = InsertData {
+            format: None,
+            data: read_test_data("blank-nodes.ttl"),
+            compression: None,
+            expires: None,
+            graph: None,
+            skip_oversized: false,
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), insert_msg.clone());
+        assert!(res.is_ok());
+        assert_eq!(
+            BLANK_NODE_IDENTIFIER_COUNTER.load(&deps.storage).unwrap(),
+            2u128
+        );
+        let triple_count = STORE.load(&deps.storage).unwrap().stat.triple_count;
+
+        // we insert the same data again to check the creation of new blank nodes
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), insert_msg);
+        assert!(res.is_ok());
+        assert_eq!(
+            BLANK_NODE_IDENTIFIER_COUNTER.load(&deps.storage).unwrap(),
+            4u128
+        );
+
+        // the triples involving blank nodes are not merged with the ones of the first insert
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.triple_count,
+            triple_count + Uint128::new(6)
+        );
+        let blank_objects = triples()
+            .range(&deps.storage, None, None, Order::Ascending)
+            .filter_map(Result::ok)
+            .filter(|(_, t)| matches!(t.object, TermRef::Blank(_)))
+            .map(|(_, t)| t.object.key())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(blank_objects.len(), 4);
+    }
+
+    #[test]
+    fn insert_existing_triples() {
+        let mut deps = mock_dependencies();
+
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        );
+
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap().attributes,
+            vec![
+                Attribute::new("action", "insert"),
+                Attribute::new("triple_count", "0"),
+            ]
+        );
+
+        assert_eq!(
+            triples()
+                .range_raw(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            40
+        );
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat,
+            StoreStat {
+                triple_count: 40u128.into(),
+                namespace_count: 17u128.into(),
+                byte_size: 7180u128.into(),
+            },
+        );
+        assert_eq!(NAMESPACE_KEY_INCREMENT.load(&deps.storage).unwrap(), 17u128);
+    }
+
+    #[test]
+    fn reuse_namespace_keys() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let insert = |deps: DepsMut<'_>, data: &str| {
+            execute(
+                deps,
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+            )
+            .unwrap();
+        };
+        let keys = |storage: &dyn Storage| {
+            namespaces()
+                .range(storage, None, None, Order::Ascending)
+                .map(|res| res.map(|(value, ns)| (value, ns.key)))
+                .collect::<StdResult<BTreeMap<_, _>>>()
+                .unwrap()
+        };
+        let free_keys = |storage: &dyn Storage| {
+            state::FREE_NAMESPACE_KEYS
+                .keys(storage, None, None, Order::Ascending)
+                .collect::<StdResult<BTreeSet<_>>>()
+                .unwrap()
+        };
+
+        insert(
+            deps.as_mut(),
+            "<https://a.org/x> <https://p.org/p> <https://b.org/y> .\n\
+             <https://c.org/z> <https://p.org/p> \"v\" .\n",
+        );
+        let initial = keys(&deps.storage);
+        assert_eq!(NAMESPACE_KEY_INCREMENT.load(&deps.storage).unwrap(), 4u128);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![],
+                r#where: Some(WhereClause::Bgp {
+                    patterns: vec![TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full("https://a.org/x".to_string()))),
+                        predicate: VarOrNamedNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                }),
+            },
+        )
+        .unwrap();
+        let freed = BTreeSet::from([initial["https://a.org/"], initial["https://b.org/"]]);
+        assert_eq!(
+            keys(&deps.storage).into_keys().collect::<Vec<_>>(),
+            vec!["https://c.org/", "https://p.org/"]
+        );
+        assert_eq!(free_keys(&deps.storage), freed);
+
+        insert(
+            deps.as_mut(),
+            "<https://d.org/w> <https://p.org/p> <https://e.org/v> .\n",
+        );
+        let reused = keys(&deps.storage);
+        assert_eq!(
+            BTreeSet::from([reused["https://d.org/"], reused["https://e.org/"]]),
+            freed
+        );
+        assert_eq!(reused["https://p.org/"], initial["https://p.org/"]);
+        assert_eq!(NAMESPACE_KEY_INCREMENT.load(&deps.storage).unwrap(), 4u128);
+        assert_eq!(free_keys(&deps.storage), BTreeSet::new());
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.namespace_count,
+            Uint128::new(4)
+        );
+
+        let res = query::select(
+            deps.as_ref(),
+            SelectQuery {
+                prefixes: vec![],
+                select: vec![SelectItem::Variable("o".to_string())],
+                r#where: WhereClause::Bgp {
+                    patterns: vec![TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full("https://d.org/w".to_string()))),
+                        predicate: VarOrNamedNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                },
+                limit: None,
+                reasoning: None,
+                skolemize: false,
+                adaptive_joins: false,
+                ordered: false,
+                from: vec![],
+                from_named: vec![],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.results.bindings,
+            vec![BTreeMap::from([(
+                "o".to_string(),
+                Value::URI {
+                    value: Full("https://e.org/v".to_string()),
+                }
+            )])]
+        );
+    }
+
+    #[test]
+    fn insert_existing_triples_at_capacity() {
+        let mut deps = mock_dependencies();
+
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_triple_count(40u128)
+                    .max_byte_size(7180u128)
+                    .max_insert_data_triple_count(40u128)
+                    .build()
+                    .unwrap(),
+                recent_window: None,
+                query_audit: None,
+                conflict_policy: Default::default(),
+                literal_offload: None,
+                disable_literal_normalization: false,
+                soft_limit: None,
+            },
+        )
+        .unwrap();
+
+        for _ in 0..2 {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::RDFXml),
+                    data: read_test_data("sample.rdf.xml"),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+            );
+            assert!(res.is_ok());
+        }
+
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat,
+            StoreStat {
+                triple_count: 40u128.into(),
+                namespace_count: 17u128.into(),
+                byte_size: 7180u128.into(),
+            },
+        );
+    }
+
+    #[test]
+    fn insert_receipt() {
+        let mut deps = mock_dependencies();
+
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_triple_byte_size(60u128)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let insert = |skip_oversized| InsertData {
+            format: Some(DataFormat::NTriples),
+            data: Binary::from(
+                "<https://ex.org/s> <https://ex.org/name> \"short\" .\n\
+                 <https://ex.org/s> <https://ex.org/name> \"short\" .\n\
+                 <https://ex.org/t> <https://other.org/desc> \"a description way too long\" .\n"
+                    .as_bytes(),
+            ),
+            compression: None,
+            expires: None,
+            graph: None,
+            skip_oversized,
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), insert(true)).unwrap();
+        assert_eq!(
+            from_json::<InsertDataReceipt>(res.data.unwrap()).unwrap(),
+            InsertDataReceipt {
+                inserted_count: Uint128::one(),
+                duplicate_count: Uint128::one(),
+                rejected_count: Uint128::one(),
+            }
+        );
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat,
+            StoreStat {
+                triple_count: 1u128.into(),
+                namespace_count: 1u128.into(),
+                byte_size: 40u128.into(),
+            },
+        );
+
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), insert(false));
+        assert_eq!(
+            res.err(),
+            Some(ContractError::from(StoreError::TripleByteSize(
+                64u128.into(),
+                60u128.into(),
+            )))
+        );
+    }
+
+    #[test]
+    fn insert_unauthorized() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("not-owner"), &[]),
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        );
+        assert!(res.is_err());
+        assert_eq!(res.err().unwrap(), ContractError::Unauthorized);
+    }
+
+    #[test]
+    fn insert_limits() {
+        let cases = vec![
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_triple_count(30u128)
+                    .build()
+                    .unwrap(),
+                Some(ContractError::from(StoreError::TripleCount(30u128.into()))),
+            ),
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_triple_count(40u128)
+                    .build()
+                    .unwrap(),
+                None,
+            ),
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_byte_size(50u128)
+                    .build()
+                    .unwrap(),
+                Some(ContractError::from(StoreError::ByteSize(50u128.into()))),
+            ),
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_byte_size(50000u128)
+                    .build()
+                    .unwrap(),
+                None,
+            ),
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_insert_data_byte_size(500u128)
+                    .build()
+                    .unwrap(),
+                Some(ContractError::from(StoreError::InsertDataByteSize(
+                    500u128.into(),
+                ))),
+            ),
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_insert_data_byte_size(50000u128)
+                    .build()
+                    .unwrap(),
+                None,
+            ),
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_triple_byte_size(150u128)
+                    .build()
+                    .unwrap(),
+                Some(ContractError::from(StoreError::TripleByteSize(
+                    177u128.into(),
+                    150u128.into(),
+                ))),
+            ),
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_triple_byte_size(400u128)
+                    .build()
+                    .unwrap(),
+                None,
+            ),
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_insert_data_triple_count(30u128)
+                    .build()
+                    .unwrap(),
+                Some(ContractError::from(StoreError::InsertDataTripleCount(
+                    30u128.into(),
+                ))),
+            ),
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_insert_data_triple_count(40u128)
+                    .build()
+                    .unwrap(),
+                None,
+            ),
+        ];
+
+        let exec_msg = InsertData {
+            format: Some(DataFormat::RDFXml),
+            data: read_test_data("sample.rdf.xml"),
+            compression: None,
+            expires: None,
+            graph: None,
+            skip_oversized: false,
+        };
+        for case in cases {
+            let mut deps = mock_dependencies();
+
+            let info = message_info(&addr(OWNER), &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    limits: case.0,
+                    recent_window: None,
+                    query_audit: None,
+                    conflict_policy: Default::default(),
+                    literal_offload: None,
+                    disable_literal_normalization: false,
+                    soft_limit: None,
+                },
+            )
+            .unwrap();
+
+            let res = execute(deps.as_mut(), mock_env(), info.clone(), exec_msg.clone());
+
+            if let Some(err) = case.1 {
+                assert!(res.is_err());
+                assert_eq!(res.err().unwrap(), err);
+            } else {
+                assert!(res.is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn insert_blank_node_count_limit() {
+        let cases = vec![
+            (
+                1u128,
+                Some(ContractError::from(StoreError::InsertBlankNodeCount(
+                    1u128.into(),
+                ))),
+            ),
+            (2u128, None),
+        ];
+
+        for (limit, expected) in cases {
+            let mut deps = mock_dependencies();
+            let info = message_info(&addr(OWNER), &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    limits: StoreLimitsInputBuilder::default()
+                        .max_insert_blank_node_count(limit)
+                        .build()
+                        .unwrap(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let insert = |deps: DepsMut<'_>| {
+                execute(
+                    deps,
+                    mock_env(),
+                    info.clone(),
+                    InsertData {
+                        format: Some(DataFormat::Turtle),
+                        data: read_test_data("blank-nodes.ttl"),
+                        compression: None,
+                        expires: None,
+                        graph: None,
+                        skip_oversized: false,
+                    },
+                )
+            };
+            assert_eq!(insert(deps.as_mut()).err(), expected, "{limit}");
+            // the limit applies to each insertion, not to the store
+            if expected.is_none() {
+                assert_eq!(insert(deps.as_mut()).err(), None, "{limit}");
+            }
+        }
+    }
+
+    #[test]
+    fn insert_conflict_policies() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg {
+                conflict_policy: ConflictPolicy::RejectDifferentWriter,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            ExecuteMsg::GrantPermissions {
+                grantee: addr(SENDER).to_string(),
+                permissions: vec![Permission::Insert],
+            },
+        )
+        .unwrap();
+
+        let insert = |deps: DepsMut<'_>, sender: &str, format: DataFormat, data: &str| {
+            execute(
+                deps,
+                mock_env(),
+                message_info(&addr(sender), &[]),
+                InsertData {
+                    format: Some(format),
+                    data: Binary::from(data.as_bytes()),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+            )
+        };
+
+        let res = insert(
+            deps.as_mut(),
+            OWNER,
+            DataFormat::NTriples,
+            "<https://ex.org/a> <https://ex.org/p> \"1\" .\n_:b <https://ex.org/p> \"1\" .",
+        );
+        assert!(res.is_ok());
+
+        let cases = vec![
+            (
+                "<https://ex.org/a> <https://ex.org/p> \"2\" .",
+                Some(ContractError::SubjectWriterConflict(
+                    "<https://ex.org/a>".to_string(),
+                )),
+            ),
+            // already stored triples and blank nodes are never in conflict
+            ("<https://ex.org/a> <https://ex.org/p> \"1\" .", None),
+            ("_:b <https://ex.org/p> \"2\" .", None),
+            ("<https://ex.org/b> <https://ex.org/p> \"1\" .", None),
+        ];
+        for (data, expected) in cases {
+            let res = insert(deps.as_mut(), SENDER, DataFormat::NTriples, data);
+            assert_eq!(res.err(), expected, "{data}");
+        }
+
+        // the provenance of a subject is forgotten with its last triple
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![],
+                r#where: Some(WhereClause::Bgp {
+                    patterns: vec![TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full("https://ex.org/a".to_string()))),
+                        predicate: VarOrNamedNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                }),
+            },
+        )
+        .unwrap();
+        let res = insert(
+            deps.as_mut(),
+            SENDER,
+            DataFormat::NTriples,
+            "<https://ex.org/a> <https://ex.org/p> \"2\" .",
+        );
+        assert!(res.is_ok());
+
+        // a subject described before the provenance got tracked belongs to the owner
+        insert(
+            deps.as_mut(),
+            OWNER,
+            DataFormat::NTriples,
+            "<https://ex.org/d> <https://ex.org/p> \"1\" .",
+        )
+        .unwrap();
+        let legacy = SUBJECT_PROVENANCE
+            .keys_raw(&deps.storage, None, None, Order::Ascending)
+            .find(|key| {
+                SUBJECT_PROVENANCE
+                    .load(&deps.storage, key)
+                    .is_ok_and(|provenance| provenance.writer == addr(OWNER))
+            })
+            .unwrap();
+        SUBJECT_PROVENANCE.remove(&mut deps.storage, &legacy);
+        assert_eq!(
+            insert(
+                deps.as_mut(),
+                SENDER,
+                DataFormat::NTriples,
+                "<https://ex.org/d> <https://ex.org/p> \"2\" .",
+            )
+            .err(),
+            Some(ContractError::SubjectWriterConflict(
+                "<https://ex.org/d>".to_string()
+            ))
+        );
+        insert(
+            deps.as_mut(),
+            OWNER,
+            DataFormat::NTriples,
+            "<https://ex.org/d> <https://ex.org/p> \"2\" .",
+        )
+        .unwrap();
+        assert_eq!(
+            SUBJECT_PROVENANCE.load(&deps.storage, &legacy).unwrap(),
+            Provenance {
+                writer: addr(OWNER),
+                graph: None,
+            }
+        );
+
+        let update = |deps: DepsMut<'_>, sender: &str, policy: ConflictPolicy| {
+            execute(
+                deps,
+                mock_env(),
+                message_info(&addr(sender), &[]),
+                ExecuteMsg::UpdateConflictPolicy { policy },
+            )
+        };
+        assert_eq!(
+            update(deps.as_mut(), SENDER, ConflictPolicy::RequireSameGraph).err(),
+            Some(ContractError::Unauthorized)
+        );
+        let res = update(deps.as_mut(), OWNER, ConflictPolicy::RequireSameGraph);
+        assert_eq!(
+            res.unwrap().attributes,
+            vec![Attribute::new("action", "update_conflict_policy")]
+        );
+
+        let cases = vec![
+            (
+                DataFormat::NQuads,
+                "<https://ex.org/c> <https://ex.org/p> \"1\" <https://ex.org/g1> .",
+                None,
+            ),
+            (
+                DataFormat::NQuads,
+                "<https://ex.org/c> <https://ex.org/p> \"2\" <https://ex.org/g2> .",
+                Some(ContractError::SubjectGraphConflict(
+                    "<https://ex.org/c>".to_string(),
+                )),
+            ),
+            (
+                DataFormat::NTriples,
+                "<https://ex.org/c> <https://ex.org/p> \"3\" .",
+                Some(ContractError::SubjectGraphConflict(
+                    "<https://ex.org/c>".to_string(),
+                )),
+            ),
+            (
+                DataFormat::NQuads,
+                "<https://ex.org/c> <https://ex.org/p> \"4\" <https://ex.org/g1> .",
+                None,
+            ),
+            (
+                DataFormat::NTriples,
+                "<https://ex.org/a> <https://ex.org/p> \"3\" .",
+                None,
+            ),
+        ];
+        for (format, data, expected) in cases {
+            let res = insert(deps.as_mut(), OWNER, format, data);
+            assert_eq!(res.err(), expected, "{data}");
+        }
+
+        let insert_in_graph = |deps: DepsMut<'_>, graph: &str| {
+            execute(
+                deps,
+                mock_env(),
+                message_info(&addr(OWNER), &[]),
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: Binary::from("<https://ex.org/c> <https://ex.org/p> \"5\" .".as_bytes()),
+                    compression: None,
+                    expires: None,
+                    graph: Some(graph.to_string()),
+                    skip_oversized: false,
+                },
+            )
+        };
+        assert_eq!(
+            insert_in_graph(deps.as_mut(), "https://ex.org/g2").err(),
+            Some(ContractError::SubjectGraphConflict(
+                "<https://ex.org/c>".to_string(),
+            ))
+        );
+        let res = insert_in_graph(deps.as_mut(), "https://ex.org/g1").unwrap();
+        assert!(res.events[0]
+            .attributes
+            .contains(&Attribute::new("graph", "<https://ex.org/g1>")));
+
+        // the graph has to be an absolute IRI, which can't escape its N-Quads form
+        for graph in ["g1", "https://ex.org/g1> <https://ex.org/g2"] {
+            assert!(matches!(
+                insert_in_graph(deps.as_mut(), graph).err(),
+                Some(ContractError::Std(StdError::GenericErr { msg, .. }))
+                    if msg.starts_with(&format!("Invalid graph IRI '{graph}'"))
+            ));
+            assert!(execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(OWNER), &[]),
+                ExecuteMsg::BeginInsert {
+                    format: None,
+                    graph: Some(graph.to_string()),
+                },
+            )
+            .is_err());
+        }
+
+        // the triples of an insert session are inserted in its graph
+        for msg in [
+            ExecuteMsg::BeginInsert {
+                format: Some(DataFormat::NTriples),
+                graph: Some("https://ex.org/g1".to_string()),
+            },
+            ExecuteMsg::InsertChunk {
+                data: Binary::from("<https://ex.org/c> <https://ex.org/p> \"6\" .".as_bytes()),
+            },
+        ] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(OWNER), &[]),
+                msg,
+            )
+            .unwrap();
+        }
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            ExecuteMsg::CommitInsert {},
+        )
+        .unwrap();
+        assert!(res.events[0]
+            .attributes
+            .contains(&Attribute::new("graph", "<https://ex.org/g1>")));
+    }
+
+    #[test]
+    fn move_graph() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInput {
+                    max_query_limit: 2,
+                    ..Default::default()
+                },
+                conflict_policy: ConflictPolicy::RequireSameGraph,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let insert = |deps: DepsMut<'_>, data: &str| {
+            execute(
+                deps,
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::NQuads),
+                    data: Binary::from(data.as_bytes()),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+            )
+        };
+        insert(
+            deps.as_mut(),
+            "<https://ex.org/a> <https://ex.org/kind> \"doc\" <https://ex.org/g1> .
+<https://ex.org/b> <https://ex.org/kind> \"doc\" <https://ex.org/g1> .
+<https://ex.org/c> <https://ex.org/kind> \"doc\" <https://ex.org/g1> .
+<https://ex.org/d> <https://ex.org/kind> \"doc\" <https://ex.org/g2> .
+<https://ex.org/e> <https://ex.org/kind> \"img\" <https://ex.org/g1> .
+_:n <https://ex.org/kind> \"doc\" <https://ex.org/g1> .",
+        )
+        .unwrap();
+
+        let move_graph =
+            |to: &str, cursor: Option<Binary>, limit: Option<u32>| ExecuteMsg::MoveGraph {
+                subjects: SubjectSelection {
+                    prefixes: vec![Prefix {
+                        prefix: "ex".to_string(),
+                        namespace: "https://ex.org/".to_string(),
+                    }],
+                    subject: "s".to_string(),
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![TriplePattern {
+                            subject: VarOrNode::Variable("s".to_string()),
+                            predicate: VarOrNamedNode::NamedNode(Prefixed("ex:kind".to_string())),
+                            object: VarOrNodeOrLiteral::Literal(Literal::Simple("doc".to_string())),
+                        }],
+                    },
+                },
+                from: Some("https://ex.org/g1".to_string()),
+                to: Some(to.to_string()),
+                cursor,
+                limit,
+            };
+
+        let cases = vec![
+            (
+                message_info(&addr(SENDER), &[]),
+                move_graph("https://ex.org/g3", None, None),
+                ContractError::Unauthorized,
+            ),
+            (
+                info.clone(),
+                move_graph("https://ex.org/g1", None, None),
+                ContractError::SameGraph,
+            ),
+            (
+                info.clone(),
+                move_graph("https://ex.org/g3", None, Some(3)),
+                StoreError::QueryLimit(2).into(),
+            ),
+        ];
+        for (info, msg, expected) in cases {
+            assert_eq!(
+                execute(deps.as_mut(), mock_env(), info, msg).err(),
+                Some(expected)
+            );
+        }
+        assert!(matches!(
+            execute(deps.as_mut(), mock_env(), info.clone(), move_graph("g3", None, None)).err(),
+            Some(ContractError::Std(StdError::GenericErr { msg, .. }))
+                if msg.starts_with("Invalid graph IRI 'g3'")
+        ));
+
+        let mut cursor = None;
+        let mut moved = 0;
+        let mut batches = 0;
+        loop {
+            batches += 1;
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                move_graph("https://ex.org/g3", cursor, None),
+            )
+            .unwrap();
+            let attribute = |key: &str| {
+                res.attributes
+                    .iter()
+                    .find(|attr| attr.key == key)
+                    .map(|attr| attr.value.clone())
+            };
+            moved += attribute("subject_count").unwrap().parse::<u64>().unwrap();
+            cursor = attribute("cursor").map(|c| Binary::from_base64(&c).unwrap());
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(moved, 3);
+        assert_eq!(batches, 3);
+
+        let cases = vec![
+            (
+                "<https://ex.org/a> <https://ex.org/p> \"1\" <https://ex.org/g3> .",
+                None,
+            ),
+            (
+                "<https://ex.org/b> <https://ex.org/p> \"1\" <https://ex.org/g1> .",
+                Some(ContractError::SubjectGraphConflict(
+                    "<https://ex.org/b>".to_string(),
+                )),
+            ),
+            (
+                "<https://ex.org/d> <https://ex.org/p> \"1\" <https://ex.org/g2> .",
+                None,
+            ),
+            (
+                "<https://ex.org/e> <https://ex.org/p> \"1\" <https://ex.org/g1> .",
+                None,
+            ),
+        ];
+        for (data, expected) in cases {
+            assert_eq!(insert(deps.as_mut(), data).err(), expected, "{data}");
+        }
+    }
+
+    #[test]
+    fn rebuild_indexes() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInput {
+                    max_query_limit: 5,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<https://ex.org/a> <https://ex.org/p> \"1\" .
+<https://ex.org/a> <https://ex.org/q> <https://ex.org/b> .
+<https://ex.org/b> <https://ex.org/p> \"2\" ."
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        // drop the entries of an index, and add one referencing no triple
+        let namespace = b"\x00\x19TRIPLE__SUBJECT_PREDICATE";
+        let keys = deps
+            .storage
+            .range(
+                Some(namespace),
+                Some(b"\x00\x19TRIPLE__SUBJECT_PREDICATF"),
+                Order::Ascending,
+            )
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>();
+        assert_eq!(keys.len(), 3);
+        for key in keys {
+            deps.storage.remove(&key);
+        }
+        deps.storage
+            .set(&[namespace.as_slice(), b"dangling"].concat(), b"4");
+
+        let check = |deps: Deps<'_>| {
+            let mut cursor = None;
+            let (mut scanned, mut faults) = (0, vec![]);
+            loop {
+                let res: IntegrityResponse = from_json(
+                    query(
+                        deps,
+                        mock_env(),
+                        QueryMsg::CheckIntegrity {
+                            cursor: cursor.clone(),
+                            limit: None,
+                        },
+                    )
+                    .unwrap(),
+                )
+                .unwrap();
+                scanned += res.scanned_count;
+                faults.extend(res.faults.into_iter().map(|f| (f.index, f.kind)));
+                cursor = res.cursor;
+                if cursor.is_none() {
+                    return (scanned, faults);
+                }
+            }
+        };
+        assert_eq!(
+            check(deps.as_ref()),
+            (
+                10,
+                vec![
+                    (
+                        TripleIndex::SubjectPredicateObject,
+                        IntegrityFaultKind::Missing
+                    ),
+                    (
+                        TripleIndex::SubjectPredicateObject,
+                        IntegrityFaultKind::Missing
+                    ),
+                    (
+                        TripleIndex::SubjectPredicateObject,
+                        IntegrityFaultKind::Missing
+                    ),
+                    (
+                        TripleIndex::SubjectPredicateObject,
+                        IntegrityFaultKind::Dangling
+                    ),
+                ]
+            )
+        );
+
+        let rebuild = |cursor: Option<Binary>, limit| ExecuteMsg::RebuildIndexes { cursor, limit };
+        let cases = vec![
+            (
+                message_info(&addr(SENDER), &[]),
+                rebuild(None, None),
+                ContractError::Unauthorized,
+            ),
+            (
+                info.clone(),
+                rebuild(None, Some(6)),
+                StoreError::QueryLimit(5).into(),
+            ),
+            (
+                info.clone(),
+                rebuild(Some(Binary::from(vec![9])), None),
+                StdError::generic_err("Invalid index scan cursor").into(),
+            ),
+        ];
+        for (info, msg, expected) in cases {
+            assert_eq!(
+                execute(deps.as_mut(), mock_env(), info, msg).err(),
+                Some(expected)
+            );
+        }
+
+        let mut cursor = None;
+        let (mut repaired, mut batches) = (0, 0);
+        loop {
+            batches += 1;
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                rebuild(cursor, Some(4)),
+            )
+            .unwrap();
+            let attribute = |key: &str| {
+                res.attributes
+                    .iter()
+                    .find(|attr| attr.key == key)
+                    .map(|attr| attr.value.clone())
+            };
+            assert_eq!(attribute("action"), Some("rebuild_indexes".to_string()));
+            repaired += attribute("repaired_count").unwrap().parse::<u32>().unwrap();
+            cursor = attribute("cursor").map(|c| Binary::from_base64(&c).unwrap());
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(repaired, 4);
+        assert_eq!(batches, 3);
+        assert_eq!(check(deps.as_ref()), (12, vec![]));
+    }
+
+    #[test]
+    fn compact() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInput {
+                    max_query_limit: 5,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<https://ex.org/a> <https://ex.org/p> \"1\" .
+<https://ex.org/a> <https://ex.org/q> <https://ex.org/b> .
+<https://ex.org/b> <https://ex.org/p> \"2\" ."
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        // leave the records of a deleted triple, a term no longer referenced, a dangling index
+        // entry, and drop the entry of a stored triple from an index
+        let deleted = StoredTriple {
+            subject: TermRef::Term(1),
+            predicate: 2,
+            object: TermRef::Blank(3),
+        };
+        TRIPLE_INSERTIONS
+            .save(
+                &mut deps.storage,
+                &deleted.key(),
+                &state::Insertion {
+                    inserter: addr(OWNER),
+                    height: 1,
+                    tx_index: None,
+                },
+            )
+            .unwrap();
+        TRIPLE_EXPIRATIONS
+            .save(&mut deps.storage, &deleted.key(), &(0, 10))
+            .unwrap();
+        TERMS
+            .save(
+                &mut deps.storage,
+                42,
+                &state::TermEntry {
+                    term: state::StoredTerm::Node(Node {
+                        namespace: 0,
+                        value: "deleted".to_string(),
+                    }),
+                    counter: 0,
+                },
+            )
+            .unwrap();
+        let (missing, _) = deps
+            .storage
+            .range(
+                Some(b"\x00\x16TRIPLE__OBJECT_SUBJECT"),
+                None,
+                Order::Ascending,
+            )
+            .next()
+            .unwrap();
+        deps.storage.remove(&missing);
+        deps.storage
+            .set(b"\x00\x19TRIPLE__SUBJECT_PREDICATEdangling", b"4");
+
+        let compact = |cursor: Option<Binary>, limit| ExecuteMsg::Compact { cursor, limit };
+        let cases = vec![
+            (
+                message_info(&addr(SENDER), &[]),
+                compact(None, None),
+                ContractError::Unauthorized,
+            ),
+            (
+                info.clone(),
+                compact(None, Some(6)),
+                StoreError::QueryLimit(5).into(),
+            ),
+            (
+                info.clone(),
+                compact(Some(Binary::from(vec![9])), None),
+                StdError::generic_err("Invalid compaction cursor").into(),
+            ),
+        ];
+        for (info, msg, expected) in cases {
+            assert_eq!(
+                execute(deps.as_mut(), mock_env(), info, msg).err(),
+                Some(expected)
+            );
+        }
+
+        for (expected_scanned, expected_reclaimed) in [(22, 4), (18, 0)] {
+            let mut cursor = None;
+            let (mut scanned, mut reclaimed) = (0, 0);
+            loop {
+                let res = execute(
+                    deps.as_mut(),
+                    mock_env(),
+                    info.clone(),
+                    compact(cursor, None),
+                )
+                .unwrap();
+                let attribute = |key: &str| {
+                    res.attributes
+                        .iter()
+                        .find(|attr| attr.key == key)
+                        .map(|attr| attr.value.clone())
+                };
+                assert_eq!(attribute("action"), Some("compact".to_string()));
+                scanned += attribute("scanned_count").unwrap().parse::<u32>().unwrap();
+                reclaimed += attribute("reclaimed_count")
+                    .unwrap()
+                    .parse::<u32>()
+                    .unwrap();
+                cursor = attribute("cursor").map(|c| Binary::from_base64(&c).unwrap());
+                if cursor.is_none() {
+                    break;
+                }
+            }
+            assert_eq!((scanned, reclaimed), (expected_scanned, expected_reclaimed));
+        }
+        assert!(!TERMS.has(&deps.storage, 42));
+        assert!(!TRIPLE_INSERTIONS.has(&deps.storage, &deleted.key()));
+        assert!(!TRIPLE_EXPIRATIONS.has(&deps.storage, &deleted.key()));
+
+        // unlike the rebuild of the indexes, the compaction only removes records
+        assert_eq!(deps.storage.get(&missing), None);
+        let res: IntegrityResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::CheckIntegrity {
+                    cursor: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.faults
+                .into_iter()
+                .map(|f| (f.index, f.kind))
+                .collect::<Vec<_>>(),
+            vec![(
+                TripleIndex::ObjectSubjectPredicate,
+                IntegrityFaultKind::Missing
+            )]
+        );
+    }
+
+    #[test]
+    fn select_from_graphs() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::NQuads),
+                data: Binary::from(
+                    "<https://ex.org/a> <https://ex.org/kind> \"doc\" <https://ex.org/g1> .
+<https://ex.org/b> <https://ex.org/kind> \"doc\" <https://ex.org/g2> .
+<https://ex.org/c> <https://ex.org/kind> \"doc\" .
+_:n <https://ex.org/kind> \"doc\" <https://ex.org/g1> ."
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        let bgp = WhereClause::Bgp {
+            patterns: vec![TriplePattern {
+                subject: VarOrNode::Variable("s".to_string()),
+                predicate: VarOrNamedNode::NamedNode(Prefixed("ex:kind".to_string())),
+                object: VarOrNodeOrLiteral::Variable("o".to_string()),
+            }],
+        };
+        let in_graph = |graph: &str| WhereClause::Graph {
+            graph: Full(graph.to_string()),
+            clause: Box::new(bgp.clone()),
+        };
+        let g1 = || Full("https://ex.org/g1".to_string());
+        let g2 = || Prefixed("ex:g2".to_string());
+
+        let cases = vec![
+            (vec![], vec![], bgp.clone(), vec!["_", "a", "b", "c"]),
+            (vec![g1()], vec![], bgp.clone(), vec!["_", "a"]),
+            (vec![g1(), g2()], vec![], bgp.clone(), vec!["_", "a", "b"]),
+            (vec![], vec![g2()], bgp.clone(), vec![]),
+            (
+                vec![],
+                vec![],
+                in_graph("https://ex.org/g2"),
+                vec!["_", "b"],
+            ),
+            (
+                vec![g1()],
+                vec![g2()],
+                in_graph("https://ex.org/g2"),
+                vec!["_", "b"],
+            ),
+            (vec![g1()], vec![], in_graph("https://ex.org/g2"), vec![]),
+        ];
+        for (from, from_named, r#where, expected) in cases {
+            let res = query::select(
+                deps.as_ref(),
+                SelectQuery {
+                    prefixes: vec![Prefix {
+                        prefix: "ex".to_string(),
+                        namespace: "https://ex.org/".to_string(),
+                    }],
+                    select: vec![SelectItem::Variable("s".to_string())],
+                    r#where: r#where.clone(),
+                    limit: None,
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: from.clone(),
+                    from_named: from_named.clone(),
+                },
+            )
+            .unwrap();
+
+            let mut subjects: Vec<_> = res
+                .results
+                .bindings
+                .iter()
+                .map(|binding| match &binding["s"] {
+                    Value::URI {
+                        value: Full(iri), ..
+                    } => iri.trim_start_matches("https://ex.org/").to_string(),
+                    _ => "_".to_string(),
+                })
+                .collect();
+            subjects.sort();
+            assert_eq!(subjects, expected, "{from:?} {from_named:?} {where:?}");
+        }
+    }
+
+    #[test]
+    fn literal_offload() {
+        let bucket = addr("bucket");
+        let reference = format!("cosmwasm:axone-objectarium:{bucket}?query=%7B%22object_data%22%3A%7B%22id%22%3A%2288d4266fd4e6338d13b845fcf289579d209c897823b9217da3e161936f031589%22%7D%7D");
+
+        let mut deps = mock_dependencies();
+        let queried = bucket.clone();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == queried.as_str() => {
+                let res = match from_json(msg).unwrap() {
+                    axone_objectarium::msg::QueryMsg::Bucket {} => {
+                        to_json_binary(&axone_objectarium::msg::BucketResponse {
+                            name: "bucket".to_string(),
+                            config: Default::default(),
+                            limits: Default::default(),
+                            pagination: Default::default(),
+                            stat: Default::default(),
+                            transformer: None,
+                        })
+                    }
+                    axone_objectarium::msg::QueryMsg::ObjectData { id } => {
+                        assert_eq!(
+                            id,
+                            "88d4266fd4e6338d13b845fcf289579d209c897823b9217da3e161936f031589"
+                        );
+                        to_json_binary(&Binary::from("abcd".as_bytes()))
+                    }
+                    q => panic!("unexpected query: {q:?}"),
+                };
+                SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg {
+                literal_offload: Some(msg::LiteralOffload {
+                    bucket: bucket.to_string(),
+                    threshold: Uint128::new(3),
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<https://ex.org/s> <https://ex.org/long> \"abcd\" .\n<https://ex.org/s> <https://ex.org/short> \"abc\" .\n<https://ex.org/o> <https://ex.org/long> \"abcd\"@en ."
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(WasmMsg::Execute {
+                contract_addr: bucket.to_string(),
+                msg: to_json_binary(&axone_objectarium::msg::ExecuteMsg::StoreObject {
+                    data: Binary::from("abcd".as_bytes()),
+                    pin: true,
+                    compression_algorithm: None,
+                    expected_digest: None,
+                    reject_duplicate: false,
+                    keep_content: true,
+                })
+                .unwrap(),
+                funds: vec![],
+            })]
+        );
+
+        let res = query::select(
+            deps.as_ref(),
+            SelectQuery {
+                prefixes: vec![],
+                select: vec![SelectItem::Variable("o".to_string())],
+                r#where: WhereClause::Bgp {
+                    patterns: vec![TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full("https://ex.org/s".to_string()))),
+                        predicate: VarOrNamedNode::NamedNode(Full(
+                            "https://ex.org/long".to_string(),
+                        )),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                },
+                limit: None,
+                reasoning: None,
+                skolemize: false,
+                adaptive_joins: false,
+                ordered: false,
+                from: vec![],
+                from_named: vec![],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.results.bindings[0]["o"],
+            Value::Literal {
+                value: reference,
+                lang: None,
+                datatype: None,
+            }
+        );
+
+        let res = query::describe(
+            deps.as_ref(),
+            DescribeQuery {
+                prefixes: vec![],
+                resource: VarOrNamedNode::NamedNode(Full("https://ex.org/s".to_string())),
+                r#where: None,
+            },
+            DataFormat::NTriples,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(res.data.to_vec()).unwrap(),
+            "<https://ex.org/s> <https://ex.org/short> \"abc\" .\n<https://ex.org/s> <https://ex.org/long> \"abcd\" .\n"
+        );
+
+        let delete_long = |subject: &str| DeleteData {
+            prefixes: vec![],
+            delete: vec![],
+            r#where: Some(WhereClause::Bgp {
+                patterns: vec![TriplePattern {
+                    subject: VarOrNode::Node(NamedNode(Full(subject.to_string()))),
+                    predicate: VarOrNamedNode::NamedNode(Full("https://ex.org/long".to_string())),
+                    object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                }],
+            }),
+        };
+        let cases = vec![
+            ("https://ex.org/s", vec![]),
+            (
+                "https://ex.org/o",
+                vec![SubMsg::new(WasmMsg::Execute {
+                    contract_addr: bucket.to_string(),
+                    msg: to_json_binary(&axone_objectarium::msg::ExecuteMsg::UnpinObject {
+                        id: "88d4266fd4e6338d13b845fcf289579d209c897823b9217da3e161936f031589"
+                            .to_string(),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                })],
+            ),
+        ];
+        for (subject, expected) in cases {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(OWNER), &[]),
+                delete_long(subject),
+            )
+            .unwrap();
+            assert_eq!(res.messages, expected);
+        }
+
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(SENDER), &[]),
+                ExecuteMsg::UpdateLiteralOffload {
+                    literal_offload: None
+                },
+            )
+            .err(),
+            Some(ContractError::Unauthorized)
+        );
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            ExecuteMsg::UpdateLiteralOffload {
+                literal_offload: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "update_literal_offload"),
+                Attribute::new("enabled", "false"),
+            ]
+        );
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from("<https://ex.org/s> <https://ex.org/p> \"efgh\" .".as_bytes()),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+    }
+
+    #[test]
+    fn insert_session() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_insert_data_triple_count(10u128)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let exec = |deps: DepsMut<'_>, sender: &str, msg: ExecuteMsg| {
+            execute(deps, mock_env(), message_info(&addr(sender), &[]), msg)
+        };
+
+        let data = read_test_data("sample.ttl");
+        assert_eq!(
+            exec(
+                deps.as_mut(),
+                OWNER,
+                ExecuteMsg::InsertChunk { data: data.clone() },
+            )
+            .err(),
+            Some(ContractError::NoInsertSession)
+        );
+        assert_eq!(
+            exec(
+                deps.as_mut(),
+                "not-owner",
+                ExecuteMsg::BeginInsert {
+                    format: None,
+                    graph: None,
+                },
+            )
+            .err(),
+            Some(ContractError::Unauthorized)
+        );
+
+        let res = exec(
+            deps.as_mut(),
+            OWNER,
+            ExecuteMsg::BeginInsert {
+                format: Some(DataFormat::Turtle),
+                graph: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![Attribute::new("action", "begin_insert")]
+        );
+        assert_eq!(
+            exec(
+                deps.as_mut(),
+                OWNER,
+                ExecuteMsg::BeginInsert {
+                    format: None,
+                    graph: None,
+                },
+            )
+            .err(),
+            Some(ContractError::InsertSessionInProgress)
+        );
+
+        // chunks don't need to be split on triple boundaries, nor fit the insert limits
+        for (i, chunk) in data.chunks(data.len() / 3 + 1).enumerate() {
+            let res = exec(
+                deps.as_mut(),
+                OWNER,
+                ExecuteMsg::InsertChunk {
+                    data: Binary::from(chunk),
+                },
+            )
+            .unwrap();
+            assert!(res
+                .attributes
+                .contains(&Attribute::new("chunk_count", (i + 1).to_string())));
+        }
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.triple_count,
+            Uint128::zero()
+        );
+
+        let res = exec(deps.as_mut(), OWNER, ExecuteMsg::CommitInsert {}).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "commit_insert"),
+                Attribute::new("chunk_count", "3"),
+                Attribute::new("triple_count", "40"),
+            ]
+        );
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.triple_count,
+            Uint128::from(40u128)
+        );
+        assert!(INSERT_SESSIONS.is_empty(&deps.storage));
+        assert!(INSERT_CHUNKS.is_empty(&deps.storage));
+        assert_eq!(
+            exec(deps.as_mut(), OWNER, ExecuteMsg::CommitInsert {}).err(),
+            Some(ContractError::NoInsertSession)
+        );
+
+        // aborting discards the session
+        exec(
+            deps.as_mut(),
+            OWNER,
+            ExecuteMsg::BeginInsert {
+                format: None,
+                graph: None,
+            },
+        )
+        .unwrap();
+        exec(deps.as_mut(), OWNER, ExecuteMsg::InsertChunk { data }).unwrap();
+        let res = exec(deps.as_mut(), OWNER, ExecuteMsg::AbortInsert {}).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![Attribute::new("action", "abort_insert")]
+        );
+        assert!(INSERT_SESSIONS.is_empty(&deps.storage));
+        assert!(INSERT_CHUNKS.is_empty(&deps.storage));
+    }
+
+    #[test]
+    fn insert_session_limits() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_triple_count(30u128)
+                    .max_insert_session_byte_size(4000u128)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        for msg in [
+            ExecuteMsg::BeginInsert {
+                format: None,
+                graph: None,
+            },
+            ExecuteMsg::InsertChunk {
+                data: read_test_data("sample.ttl"),
+            },
+        ] {
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        }
+
+        // the session can't buffer more than its limit, the rejected chunk leaving it unchanged
+        let session = INSERT_SESSIONS.load(&deps.storage, &info.sender).unwrap();
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ExecuteMsg::InsertChunk {
+                    data: Binary::from(vec![b' '; 4001 - session.byte_size.u128() as usize]),
+                },
+            )
+            .err(),
+            Some(StoreError::InsertSessionByteSize(4000u128.into()).into())
+        );
+        assert_eq!(
+            INSERT_SESSIONS.load(&deps.storage, &info.sender).unwrap(),
+            session
+        );
+
+        assert_eq!(
+            execute(deps.as_mut(), mock_env(), info, ExecuteMsg::CommitInsert {}).err(),
+            Some(StoreError::TripleCount(30u128.into()).into())
+        );
+    }
+
+    #[test]
+    fn insert_byte_size_accounting() {
+        let data = "<https://ex.org/a> <https://ex.org/p> \"v\"@en .\n\
+            <https://ex.org/a> <https://ex.org/p> \"1\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n\
+            _:b <https://ex.org/p> \"x\" .\n";
+
+        let cases = vec![
+            (StoreLimitsInput::default(), Ok(141u128)),
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_triple_byte_size(73u128)
+                    .build()
+                    .unwrap(),
+                Ok(141u128),
+            ),
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_triple_byte_size(72u128)
+                    .build()
+                    .unwrap(),
+                Err(ContractError::from(StoreError::TripleByteSize(
+                    73u128.into(),
+                    72u128.into(),
+                ))),
+            ),
+            (
+                StoreLimitsInputBuilder::default()
+                    .max_byte_size(140u128)
+                    .build()
+                    .unwrap(),
+                Err(ContractError::from(StoreError::ByteSize(140u128.into()))),
+            ),
+        ];
+
+        for (limits, expected) in cases {
+            let mut deps = mock_dependencies();
+            let info = message_info(&addr(OWNER), &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    limits,
+                    recent_window: None,
+                    query_audit: None,
+                    conflict_policy: Default::default(),
+                    literal_offload: None,
+                    disable_literal_normalization: false,
+                    soft_limit: None,
+                },
+            )
+            .unwrap();
+
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+            );
+
+            assert_eq!(
+                res.map(|_| STORE.load(&deps.storage).unwrap().stat.byte_size.u128()),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn proper_delete() {
+        let id = "https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473";
+        let cases = vec![
+            (
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![msg::TripleDeleteTemplate {
+                        subject: VarOrNamedNode::NamedNode(Full(
                             "https://ontology.axone.space/dataverse/dataspace/metadata/unknown"
                                 .to_string(),
                         )),
                         predicate: VarOrNamedNode::NamedNode(Full(
-                            "https://ontology.axone.space/core/hasTopic".to_string(),
+                            "https://ontology.axone.space/core/hasTopic".to_string(),
+                        )),
+                        object: VarOrNamedNodeOrLiteral::NamedNode(Full(
+                            "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
+                        )),
+                    }],
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Full(
+                                "https://ontology.axone.space/dataverse/dataspace/metadata/unknown"
+                                    .to_string(),
+                            ))),
+                            predicate: VarOrNamedNode::NamedNode(Full(
+                                "https://ontology.axone.space/core/hasTopic".to_string(),
+                            )),
+                            object: VarOrNodeOrLiteral::Node(NamedNode(Full(
+                                "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
+                            ))),
+                        }],
+                    }
+                    .into(),
+                },
+                0,
+                0,
+                Uint128::from(7180u128),
+            ),
+            (
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![msg::TripleDeleteTemplate {
+                        subject: VarOrNamedNode::NamedNode(Full(id.to_string())),
+                        predicate: VarOrNamedNode::NamedNode(Full(
+                            "https://ontology.axone.space/core/hasTopic".to_string(),
+                        )),
+                        object: VarOrNamedNodeOrLiteral::NamedNode(Full(
+                            "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
+                        )),
+                    }],
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                            predicate: VarOrNamedNode::NamedNode(Full(
+                                "https://ontology.axone.space/core/hasTopic".to_string(),
+                            )),
+                            object: VarOrNodeOrLiteral::Node(NamedNode(Full(
+                                "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
+                            ))),
+                        }],
+                    }
+                    .into(),
+                },
+                1,
+                0,
+                Uint128::from(6995u128),
+            ),
+            (
+                DeleteData {
+                    prefixes: vec![
+                        Prefix {
+                            prefix: "core".to_string(),
+                            namespace: "https://ontology.axone.space/core/".to_string(),
+                        },
+                        Prefix {
+                            prefix: "thesaurus".to_string(),
+                            namespace: "https://ontology.axone.space/thesaurus/topic/".to_string(),
+                        },
+                    ],
+                    delete: vec![msg::TripleDeleteTemplate {
+                        subject: VarOrNamedNode::NamedNode(Full(id.to_string())),
+                        predicate: VarOrNamedNode::NamedNode(Prefixed("core:hasTopic".to_string())),
+                        object: VarOrNamedNodeOrLiteral::NamedNode(Prefixed(
+                            "thesaurus:Test".to_string(),
+                        )),
+                    }],
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                            predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                "core:hasTopic".to_string(),
+                            )),
+                            object: VarOrNodeOrLiteral::Node(NamedNode(Prefixed(
+                                "thesaurus:Test".to_string(),
+                            ))),
+                        }],
+                    }
+                    .into(),
+                },
+                1,
+                0,
+                Uint128::from(6995u128),
+            ),
+            (
+                DeleteData {
+                    prefixes: vec![
+                        Prefix {
+                            prefix: "core".to_string(),
+                            namespace: "https://ontology.axone.space/core/".to_string(),
+                        },
+                        Prefix {
+                            prefix: "thesaurus".to_string(),
+                            namespace: "https://ontology.axone.space/thesaurus/topic/".to_string(),
+                        },
+                    ],
+                    delete: vec![msg::TripleDeleteTemplate {
+                        subject: VarOrNamedNode::NamedNode(Full(id.to_string())),
+                        predicate: VarOrNamedNode::NamedNode(Prefixed("core:hasTopic".to_string())),
+                        object: VarOrNamedNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                            predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                "core:hasTopic".to_string(),
+                            )),
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        }],
+                    }
+                    .into(),
+                },
+                1,
+                0,
+                Uint128::from(6995u128),
+            ),
+            (
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![msg::TripleDeleteTemplate {
+                        subject: VarOrNamedNode::NamedNode(Full(id.to_string())),
+                        predicate: VarOrNamedNode::Variable("p".to_string()),
+                        object: VarOrNamedNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                            predicate: VarOrNamedNode::Variable("p".to_string()),
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        }],
+                    }
+                    .into(),
+                },
+                11,
+                2,
+                Uint128::from(5324u128),
+            ),
+            (
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![],
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                            predicate: VarOrNamedNode::Variable("p".to_string()),
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        }],
+                    }
+                    .into(),
+                },
+                11,
+                2,
+                Uint128::from(5324u128),
+            ),
+            (
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![],
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![TriplePattern {
+                            subject: VarOrNode::Variable("s".to_string()),
+                            predicate: VarOrNamedNode::Variable("p".to_string()),
+                            object: VarOrNodeOrLiteral::Variable("0".to_string()),
+                        }],
+                    }
+                    .into(),
+                },
+                40,
+                17,
+                Uint128::from(0u128),
+            ),
+            (
+                DeleteData {
+                    prefixes: vec![
+                        Prefix {
+                            prefix: "core".to_string(),
+                            namespace: "https://ontology.axone.space/core/".to_string(),
+                        },
+                        Prefix {
+                            prefix: "thesaurus".to_string(),
+                            namespace: "https://ontology.axone.space/thesaurus/topic/".to_string(),
+                        },
+                    ],
+                    delete: vec![msg::TripleDeleteTemplate {
+                        subject: VarOrNamedNode::NamedNode(Full(id.to_string())),
+                        predicate: VarOrNamedNode::NamedNode(Prefixed("core:hasTopic".to_string())),
+                        object: VarOrNamedNodeOrLiteral::NamedNode(Prefixed(
+                            "thesaurus:Test".to_string(),
+                        )),
+                    }],
+                    r#where: None,
+                },
+                1,
+                0,
+                Uint128::from(6995u128),
+            ),
+        ];
+
+        for case in cases {
+            let mut deps = mock_dependencies();
+
+            let info = message_info(&addr(OWNER), &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg::default(),
+            )
+            .unwrap();
+
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::RDFXml),
+                    data: read_test_data("sample.rdf.xml"),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+            )
+            .unwrap();
+
+            let res = execute(deps.as_mut(), mock_env(), info, case.0);
+
+            assert!(res.is_ok());
+            assert_eq!(
+                res.unwrap().attributes,
+                vec![
+                    Attribute::new("action", "delete"),
+                    Attribute::new("triple_count", case.1.to_string()),
+                ]
+            );
+
+            assert_eq!(
+                STORE.load(&deps.storage).unwrap().stat,
+                StoreStat {
+                    triple_count: (40u128 - u128::try_from(case.1).unwrap()).into(),
+                    namespace_count: (17u128 - u128::try_from(case.2).unwrap()).into(),
+                    byte_size: case.3,
+                },
+            );
+            assert_eq!(
+                triples()
+                    .range_raw(&deps.storage, None, None, Order::Ascending)
+                    .count(),
+                40 - case.1
+            );
+            assert_eq!(
+                namespaces()
+                    .range_raw(&deps.storage, None, None, Order::Ascending)
+                    .count(),
+                17 - case.2
+            );
+            assert_eq!(
+                TERMS
+                    .keys(&deps.storage, None, None, Order::Ascending)
+                    .collect::<StdResult<BTreeSet<_>>>()
+                    .unwrap(),
+                triples()
+                    .range(&deps.storage, None, None, Order::Ascending)
+                    .flat_map(|res| {
+                        let (_, t) = res.unwrap();
+                        [t.subject, TermRef::Term(t.predicate), t.object]
+                    })
+                    .filter_map(|r| match r {
+                        TermRef::Term(id) => Some(id),
+                        TermRef::Blank(_) => None,
+                    })
+                    .collect::<BTreeSet<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_delete() {
+        struct TC {
+            command: ExecuteMsg,
+            expected: ContractError,
+        }
+        let cases = vec![
+            TC {
+                command: DeleteData {
+                    prefixes: vec![],
+                    delete: vec![msg::TripleDeleteTemplate {
+                        subject: VarOrNamedNode::NamedNode(Prefixed("foo:bar".to_string())),
+                        predicate: VarOrNamedNode::NamedNode(Full(
+                            "https://ontology.axone.space/core/hasTopic".to_string(),
+                        )),
+                        object: VarOrNamedNodeOrLiteral::NamedNode(Full(
+                            "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
+                        )),
+                    }],
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Prefixed("foo:bar".to_string()))),
+                            predicate: VarOrNamedNode::NamedNode(Full(
+                                "https://ontology.axone.space/core/hasTopic".to_string(),
+                            )),
+                            object: VarOrNodeOrLiteral::Node(NamedNode(Full(
+                                "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
+                            ))),
+                        }],
+                    }
+                    .into(),
+                },
+                expected: StdError::generic_err("Prefix not found: foo").into(),
+            },
+            TC {
+                command: DeleteData {
+                    prefixes: vec![],
+                    delete: vec![msg::TripleDeleteTemplate {
+                        subject: VarOrNamedNode::NamedNode(Full(
+                            "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
+                        )),
+                        predicate: VarOrNamedNode::Variable("z".to_string()),
+                        object: VarOrNamedNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Full(
+                                "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
+                            ))),
+                            predicate: VarOrNamedNode::Variable("p".to_string()),
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        }],
+                    }
+                    .into(),
+                },
+                expected: StdError::generic_err("Selected variable not found in query").into(),
+            },
+        ];
+
+        for case in cases {
+            let mut deps = mock_dependencies();
+
+            let info = message_info(&addr(OWNER), &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg::default(),
+            )
+            .unwrap();
+
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::RDFXml),
+                    data: read_test_data("sample.rdf.xml"),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+            )
+            .unwrap();
+
+            let res = execute(deps.as_mut(), mock_env(), info, case.command);
+
+            assert!(res.is_err());
+            assert_eq!(res.unwrap_err(), case.expected);
+        }
+    }
+
+    #[test]
+    fn ownership_transfer() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let new_owner = addr("new-owner");
+        let transfer = ExecuteMsg::TransferOwnership {
+            new_owner: new_owner.to_string(),
+        };
+        let pending_owner = |deps: Deps<'_>| {
+            from_json::<PendingOwnerResponse>(
+                query(deps, mock_env(), QueryMsg::PendingOwner {}).unwrap(),
+            )
+            .unwrap()
+            .pending_owner
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&new_owner, &[]),
+            transfer.clone(),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            ExecuteMsg::AcceptOwnership {},
+        );
+        assert_eq!(res.unwrap_err(), ContractError::NotPendingOwner);
+
+        // a pending transfer can be withdrawn by the owner only
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            transfer.clone(),
+        )
+        .unwrap();
+        assert_eq!(pending_owner(deps.as_ref()), Some(new_owner.to_string()));
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&new_owner, &[]),
+            ExecuteMsg::CancelOwnershipTransfer {},
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            ExecuteMsg::CancelOwnershipTransfer {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![Attribute::new("action", "cancel_ownership_transfer")]
+        );
+        assert_eq!(pending_owner(deps.as_ref()), None);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&new_owner, &[]),
+            ExecuteMsg::AcceptOwnership {},
+        );
+        assert_eq!(res.unwrap_err(), ContractError::NotPendingOwner);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            transfer,
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "transfer_ownership"),
+                Attribute::new("pending_owner", new_owner.as_str()),
+            ]
+        );
+        assert_eq!(STORE.load(&deps.storage).unwrap().owner, addr(OWNER));
+        assert_eq!(pending_owner(deps.as_ref()), Some(new_owner.to_string()));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(SENDER), &[]),
+            ExecuteMsg::AcceptOwnership {},
+        );
+        assert_eq!(res.unwrap_err(), ContractError::NotPendingOwner);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&new_owner, &[]),
+            ExecuteMsg::AcceptOwnership {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "accept_ownership"),
+                Attribute::new("owner", new_owner.as_str()),
+            ]
+        );
+        assert_eq!(STORE.load(&deps.storage).unwrap().owner, new_owner);
+        assert_eq!(pending_owner(deps.as_ref()), None);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&new_owner, &[]),
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn permissions_grants() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let grantee = addr("grantee");
+        let insert = || InsertData {
+            format: Some(DataFormat::RDFXml),
+            data: read_test_data("sample.rdf.xml"),
+            compression: None,
+            expires: None,
+            graph: None,
+            skip_oversized: false,
+        };
+        let delete = || DeleteData {
+            prefixes: vec![],
+            delete: vec![msg::TripleDeleteTemplate {
+                subject: VarOrNamedNode::Variable("s".to_string()),
+                predicate: VarOrNamedNode::Variable("p".to_string()),
+                object: VarOrNamedNodeOrLiteral::Variable("o".to_string()),
+            }],
+            r#where: Some(WhereClause::Bgp {
+                patterns: vec![TriplePattern {
+                    subject: VarOrNode::Variable("s".to_string()),
+                    predicate: VarOrNamedNode::Variable("p".to_string()),
+                    object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                }],
+            }),
+        };
+        let grantees = |deps: Deps<'_>| -> Vec<Grantee> {
+            from_json::<GranteesResponse>(
+                query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::Grantees {
+                        after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+            .grantees
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&grantee, &[]),
+            ExecuteMsg::GrantPermissions {
+                grantee: grantee.to_string(),
+                permissions: vec![Permission::Insert],
+            },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&grantee, &[]),
+            insert(),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            ExecuteMsg::GrantPermissions {
+                grantee: grantee.to_string(),
+                permissions: vec![Permission::Insert],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            grantees(deps.as_ref()),
+            vec![Grantee {
+                address: grantee.to_string(),
+                permissions: vec![Permission::Insert],
+            }]
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&grantee, &[]),
+            insert(),
+        );
+        assert!(res.is_ok());
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&grantee, &[]),
+            delete(),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            ExecuteMsg::GrantPermissions {
+                grantee: grantee.to_string(),
+                permissions: vec![Permission::Delete, Permission::Insert],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            grantees(deps.as_ref()),
+            vec![Grantee {
+                address: grantee.to_string(),
+                permissions: vec![Permission::Insert, Permission::Delete],
+            }]
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&grantee, &[]),
+            delete(),
+        );
+        assert!(res.is_ok());
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.triple_count,
+            Uint128::zero()
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            ExecuteMsg::RevokePermissions {
+                grantee: grantee.to_string(),
+                permissions: vec![Permission::Insert],
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&grantee, &[]),
+            insert(),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            ExecuteMsg::RevokePermissions {
+                grantee: grantee.to_string(),
+                permissions: vec![Permission::Delete],
+            },
+        )
+        .unwrap();
+        assert_eq!(grantees(deps.as_ref()), vec![]);
+    }
+
+    #[test]
+    fn proper_grantees_pagination() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg {
+                limits: StoreLimitsInput {
+                    max_query_limit: 2,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut addresses = ["a", "b", "c"].map(|name| addr(name).to_string());
+        addresses.sort();
+        for address in &addresses {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(OWNER), &[]),
+                ExecuteMsg::GrantPermissions {
+                    grantee: address.clone(),
+                    permissions: vec![Permission::Insert],
+                },
+            )
+            .unwrap();
+        }
+        let page = |after: Option<&String>, limit| {
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Grantees {
+                    after: after.cloned(),
+                    limit,
+                },
+            )
+            .map(|res| {
+                from_json::<GranteesResponse>(res)
+                    .unwrap()
+                    .grantees
+                    .into_iter()
+                    .map(|grantee| grantee.address)
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        assert_eq!(page(None, None).unwrap(), addresses[..2].to_vec());
+        assert_eq!(
+            page(Some(&addresses[0]), Some(1)).unwrap(),
+            addresses[1..2].to_vec()
+        );
+        assert_eq!(
+            page(Some(&addresses[1]), None).unwrap(),
+            addresses[2..].to_vec()
+        );
+        assert_eq!(
+            page(Some(&addresses[2]), None).unwrap(),
+            Vec::<String>::new()
+        );
+        assert_eq!(page(None, Some(3)), Err(StoreError::QueryLimit(2).into()));
+    }
+
+    #[test]
+    fn proper_migration() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        // Simulate a store written before the object-subject index was introduced.
+        let index_count = |storage: &dyn cosmwasm_std::Storage| {
+            triples()
+                .idx
+                .object_and_subject
+                .keys_raw(storage, None, None, Order::Ascending)
+                .count()
+        };
+        let entries = triples()
+            .range(&deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        for ((o, p, s), _) in &entries {
+            triples()
+                .remove(&mut deps.storage, (o.as_slice(), p.clone(), s.clone()))
+                .unwrap();
+        }
+        let unindexed: cw_storage_plus::Map<state::TriplePK<'_>, state::StoredTriple> =
+            cw_storage_plus::Map::new("TRIPLE");
+        for ((o, p, s), t) in &entries {
+            unindexed
+                .save(&mut deps.storage, (o.as_slice(), p.clone(), s.clone()), t)
+                .unwrap();
+        }
+        assert_eq!(index_count(&deps.storage), 0);
+        state::STATE_LAYOUT.remove(&mut deps.storage);
+
+        cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "5.0.0").unwrap();
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "migrate"),
+                Attribute::new("from_version", "5.0.0"),
+                Attribute::new("to_version", CONTRACT_VERSION),
+                Attribute::new("interned_triple_count", "0"),
+                Attribute::new("indexed_triple_count", "30"),
+                Attribute::new("completed", "false"),
+            ]
+        );
+        assert_eq!(index_count(&deps.storage), 30);
+        assert_eq!(
+            cw2::get_contract_version(&deps.storage).unwrap().version,
+            CONTRACT_VERSION
+        );
+
+        let migrate_triples = |deps: DepsMut<'_>| {
+            execute(
+                deps,
+                mock_env(),
+                message_info(&addr(SENDER), &[]),
+                ExecuteMsg::MigrateTriples { limit: None },
+            )
+            .unwrap()
+            .attributes
+        };
+        assert_eq!(
+            migrate_triples(deps.as_mut()),
+            vec![
+                Attribute::new("action", "migrate_triples"),
+                Attribute::new("interned_triple_count", "0"),
+                Attribute::new("indexed_triple_count", (entries.len() - 30).to_string()),
+                Attribute::new("completed", "false"),
+            ]
+        );
+        assert_eq!(index_count(&deps.storage), entries.len());
+
+        // the statistics about the terms are then counted again
+        while migrate_triples(deps.as_mut())[3] != Attribute::new("completed", "true") {}
+        assert!(!TRIPLE_MIGRATION.exists(&deps.storage));
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+        assert_eq!(res.attributes.len(), 3);
+        assert_eq!(index_count(&deps.storage), entries.len());
+
+        // the triples can be indexed again on demand
+        let res = migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                rebuild_indexes: true,
+            },
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("indexed_triple_count", "30")));
+        assert_eq!(
+            migrate_triples(deps.as_mut())[2],
+            Attribute::new("indexed_triple_count", (entries.len() - 30).to_string())
+        );
+
+        cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "99.0.0").unwrap();
+        assert!(migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).is_err());
+
+        cw2::set_contract_version(&mut deps.storage, "crates.io:other", "1.0.0").unwrap();
+        assert!(migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).is_err());
+    }
+
+    #[test]
+    fn update_limits() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+        let stat = STORE.load(&deps.storage).unwrap().stat;
+
+        let cases = vec![
+            (
+                SENDER,
+                StoreLimitsInputBuilder::default().build().unwrap(),
+                Err(ContractError::Unauthorized),
+            ),
+            (
+                OWNER,
+                StoreLimitsInputBuilder::default()
+                    .max_triple_count(stat.triple_count - Uint128::one())
+                    .build()
+                    .unwrap(),
+                Err(ContractError::LimitBelowUsage(
+                    "max_triple_count".to_string(),
+                    stat.triple_count,
+                )),
+            ),
+            (
+                OWNER,
+                StoreLimitsInputBuilder::default()
+                    .max_byte_size(stat.byte_size - Uint128::one())
+                    .build()
+                    .unwrap(),
+                Err(ContractError::LimitBelowUsage(
+                    "max_byte_size".to_string(),
+                    stat.byte_size,
+                )),
+            ),
+            (
+                OWNER,
+                StoreLimitsInputBuilder::default()
+                    .max_triple_count(stat.triple_count)
+                    .max_byte_size(stat.byte_size)
+                    .max_query_limit(5u32)
+                    .build()
+                    .unwrap(),
+                Ok(()),
+            ),
+        ];
+
+        for (sender, limits, expected) in cases {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(sender), &[]),
+                ExecuteMsg::UpdateLimits {
+                    limits: limits.clone(),
+                },
+            );
+
+            match expected {
+                Ok(()) => {
+                    assert!(res.is_ok());
+                    assert_eq!(
+                        STORE.load(&deps.storage).unwrap().limits,
+                        StoreLimits::from(limits)
+                    );
+                }
+                Err(e) => assert_eq!(res.unwrap_err(), e),
+            }
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: read_test_data("blank-nodes.ttl"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::from(StoreError::TripleCount(stat.triple_count))
+        );
+    }
+
+    #[test]
+    fn select_with_reasoning() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(
+                    "@prefix ex: <https://ex.org/> .
+@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+ex:Mammal rdfs:subClassOf ex:Animal .
+ex:Dog rdfs:subClassOf ex:Mammal .
+ex:Puppy rdfs:subClassOf ex:Dog .
+ex:hasParent rdfs:subPropertyOf ex:hasRelative .
+ex:kitty rdf:type ex:Animal .
+ex:tom rdf:type ex:Mammal .
+ex:rex rdf:type ex:Dog , ex:Puppy ;
+    ex:hasParent ex:max ;
+    ex:hasRelative ex:tom ."
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        let query_msg = |predicate: &str, object: VarOrNodeOrLiteral, reasoning| SelectQuery {
+            prefixes: vec![],
+            select: vec![SelectItem::Variable("x".to_string())],
+            r#where: WhereClause::Bgp {
+                patterns: vec![TriplePattern {
+                    subject: VarOrNode::Variable("x".to_string()),
+                    predicate: VarOrNamedNode::NamedNode(Full(predicate.to_string())),
+                    object,
+                }],
+            },
+            limit: None,
+            reasoning,
+            skolemize: false,
+            adaptive_joins: false,
+            ordered: false,
+            from: vec![],
+            from_named: vec![],
+        };
+        let class = |name: &str| {
+            VarOrNodeOrLiteral::Node(NamedNode(Full(format!("https://ex.org/{name}"))))
+        };
+        let rdf_type = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+        let depth = |max_depth| {
+            Some(msg::Reasoning {
+                max_depth,
+                same_as: None,
+            })
+        };
+
+        let cases = vec![
+            (query_msg(rdf_type, class("Mammal"), None), Ok(vec!["tom"])),
+            (
+                query_msg(rdf_type, class("Mammal"), depth(None)),
+                Ok(vec!["tom", "rex"]),
+            ),
+            (
+                query_msg(rdf_type, class("Animal"), depth(Some(1))),
+                Ok(vec!["kitty", "tom"]),
+            ),
+            (
+                query_msg(rdf_type, class("Animal"), depth(Some(0))),
+                Ok(vec!["kitty"]),
+            ),
+            (
+                query_msg(rdf_type, class("Animal"), depth(None)),
+                Ok(vec!["kitty", "tom", "rex"]),
+            ),
+            (
+                query_msg(
+                    "https://ex.org/hasRelative",
+                    VarOrNodeOrLiteral::Variable("y".to_string()),
+                    depth(None),
+                ),
+                Ok(vec!["rex", "rex"]),
+            ),
+            (
+                query_msg(rdf_type, class("Unknown"), depth(None)),
+                Ok(vec![]),
+            ),
+            (
+                query_msg(rdf_type, class("Animal"), depth(Some(6))),
+                Err(StdError::generic_err(
+                    QueryComplexityError::InferenceDepth(5).to_string(),
+                )),
+            ),
+        ];
+
+        for (q, expected) in cases {
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::Select { query: q });
+            assert_eq!(
+                res.map(|r| from_json::<SelectResponse>(&r)
+                    .unwrap()
+                    .results
+                    .bindings
+                    .into_iter()
+                    .map(|b| match b.get("x") {
+                        Some(Value::URI { value: Full(iri) }) => iri.clone(),
+                        _ => panic!("unexpected binding"),
+                    })
+                    .collect::<Vec<_>>()),
+                expected.map(|names| names
+                    .into_iter()
+                    .map(|name| format!("https://ex.org/{name}"))
+                    .collect::<Vec<_>>())
+            );
+        }
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Explain {
+                query: query_msg(rdf_type, class("Mammal"), depth(Some(1))),
+            },
+        );
+        assert!(matches!(
+            from_json::<ExplainResponse>(&res.unwrap()).unwrap().plan,
+            PlanNode::Limit { child, .. } if matches!(*child, PlanNode::Union { .. })
+        ));
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Select {
+                query: SelectQuery {
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![
+                            TriplePattern {
+                                subject: VarOrNode::Variable("x".to_string()),
+                                predicate: VarOrNamedNode::NamedNode(Full(rdf_type.to_string())),
+                                object: class("Animal"),
+                            };
+                            10
+                        ],
+                    },
+                    ..query_msg(rdf_type, class("Animal"), depth(None))
+                },
+            },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            StdError::generic_err(QueryComplexityError::PatternCount(30).to_string())
+        );
+    }
+
+    #[test]
+    fn select_with_same_as() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(
+                    "@prefix ex: <https://ex.org/> .
+@prefix owl: <http://www.w3.org/2002/07/owl#> .
+ex:d1 owl:sameAs ex:d2 .
+ex:d3 owl:sameAs ex:d2 , ex:d4 .
+ex:d2 ex:title \"two\" .
+ex:c1 ex:about ex:d1 .
+ex:c2 ex:about ex:d2 .
+ex:c3 ex:about ex:d4 ."
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        let select = |pattern: TriplePattern, same_as| {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Select {
+                    query: SelectQuery {
+                        prefixes: vec![],
+                        select: vec![SelectItem::Variable("x".to_string())],
+                        r#where: WhereClause::Bgp {
+                            patterns: vec![pattern],
+                        },
+                        limit: None,
+                        reasoning: Some(msg::Reasoning {
+                            max_depth: Some(0),
+                            same_as,
+                        }),
+                        skolemize: false,
+                        adaptive_joins: false,
+                        ordered: false,
+                        from: vec![],
+                        from_named: vec![],
+                    },
+                },
+            );
+            res.map(|r| {
+                from_json::<SelectResponse>(&r)
+                    .unwrap()
+                    .results
+                    .bindings
+                    .into_iter()
+                    .map(|b| match b.get("x") {
+                        Some(Value::URI { value: Full(iri) }) => iri.clone(),
+                        Some(Value::Literal { value, .. }) => value.clone(),
+                        _ => panic!("unexpected binding"),
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+        let about = |resource: &str| TriplePattern {
+            subject: VarOrNode::Variable("x".to_string()),
+            predicate: VarOrNamedNode::NamedNode(Full("https://ex.org/about".to_string())),
+            object: VarOrNodeOrLiteral::Node(NamedNode(Full(format!("https://ex.org/{resource}")))),
+        };
+        let ex = |names: Vec<&str>| {
+            Ok(names
+                .into_iter()
+                .map(|name| format!("https://ex.org/{name}"))
+                .collect::<Vec<_>>())
+        };
+
+        assert_eq!(select(about("d1"), None), ex(vec!["c1"]));
+        assert_eq!(select(about("d1"), Some(1)), ex(vec!["c1", "c2"]));
+        assert_eq!(select(about("d1"), Some(2)), ex(vec!["c1", "c2"]));
+        assert_eq!(select(about("d1"), Some(3)), ex(vec!["c1", "c2", "c3"]));
+        assert_eq!(select(about("d4"), Some(3)), ex(vec!["c3", "c2", "c1"]));
+        assert_eq!(
+            select(
+                TriplePattern {
+                    subject: VarOrNode::Node(NamedNode(Full("https://ex.org/d1".to_string()))),
+                    predicate: VarOrNamedNode::NamedNode(Full("https://ex.org/title".to_string())),
+                    object: VarOrNodeOrLiteral::Variable("x".to_string()),
+                },
+                Some(1),
+            ),
+            Ok(vec!["two".to_string()])
+        );
+        assert_eq!(
+            select(about("d1"), Some(6)),
+            Err(StdError::generic_err(
+                QueryComplexityError::InferenceDepth(5).to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn proper_stats() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let stats = |deps: Deps<'_>| {
+            from_json::<StatsResponse>(&query(deps, mock_env(), QueryMsg::Stats {}).unwrap())
+                .unwrap()
+        };
+        assert_eq!(
+            stats(deps.as_ref()),
+            StatsResponse {
+                triple_count: Uint128::zero(),
+                subject_count: Uint128::zero(),
+                predicate_count: Uint128::zero(),
+                object_count: Uint128::zero(),
+                namespace_count: Uint128::zero(),
+                literal_tag_count: Uint128::zero(),
+                byte_size: ByteSizeBreakdown {
+                    total: Uint128::zero(),
+                    subjects: Uint128::zero(),
+                    predicates: Uint128::zero(),
+                    objects: Uint128::zero(),
+                    literal_tags: Uint128::zero(),
+                },
+            }
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<https://ex.org/a> <https://ex.org/p> \"x\" .
+<https://ex.org/a> <https://ex.org/q> <https://ex.org/b> .
+<https://ex.org/b> <https://ex.org/p> \"x\" .
+<https://ex.org/b> <https://ex.org/p> \"y\"@en .
+_:n <https://ex.org/p> <https://ex.org/a> ."
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            stats(deps.as_ref()),
+            StatsResponse {
+                triple_count: 5u128.into(),
+                subject_count: 3u128.into(),
+                predicate_count: 2u128.into(),
+                object_count: 4u128.into(),
+                namespace_count: 1u128.into(),
+                literal_tag_count: 1u128.into(),
+                byte_size: ByteSizeBreakdown {
+                    total: 197u128.into(),
+                    subjects: 80u128.into(),
+                    predicates: 80u128.into(),
+                    objects: 37u128.into(),
+                    literal_tags: 2u128.into(),
+                },
+            }
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![msg::TripleDeleteTemplate {
+                    subject: VarOrNamedNode::NamedNode(Full("https://ex.org/a".to_string())),
+                    predicate: VarOrNamedNode::NamedNode(Full("https://ex.org/q".to_string())),
+                    object: VarOrNamedNodeOrLiteral::NamedNode(Full(
+                        "https://ex.org/b".to_string(),
+                    )),
+                }],
+                r#where: None,
+            },
+        )
+        .unwrap();
+        let expected = StatsResponse {
+            triple_count: 4u128.into(),
+            subject_count: 3u128.into(),
+            predicate_count: 1u128.into(),
+            object_count: 3u128.into(),
+            namespace_count: 1u128.into(),
+            literal_tag_count: 1u128.into(),
+            byte_size: ByteSizeBreakdown {
+                total: 149u128.into(),
+                subjects: 64u128.into(),
+                predicates: 64u128.into(),
+                objects: 21u128.into(),
+                literal_tags: 2u128.into(),
+            },
+        };
+        assert_eq!(stats(deps.as_ref()), expected);
+
+        // the migration recomputes the same statistics from the triples
+        TERM_STAT.remove(deps.as_mut().storage);
+        migrate::migrate_state(deps.as_mut().storage, 0).unwrap();
+        migrate::migrate_triples_batch(deps.as_mut().storage, None).unwrap();
+        assert_eq!(stats(deps.as_ref()), expected);
+
+        // the tags are removed along with the last literal they qualify
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![msg::TripleDeleteTemplate {
+                    subject: VarOrNamedNode::NamedNode(Full("https://ex.org/b".to_string())),
+                    predicate: VarOrNamedNode::NamedNode(Full("https://ex.org/p".to_string())),
+                    object: VarOrNamedNodeOrLiteral::Literal(Literal::LanguageTaggedString {
+                        value: "y".to_string(),
+                        language: "en".to_string(),
+                    }),
+                }],
+                r#where: None,
+            },
+        )
+        .unwrap();
+        let stats = stats(deps.as_ref());
+        assert_eq!(
+            (stats.literal_tag_count, stats.byte_size.literal_tags),
+            (Uint128::zero(), Uint128::zero())
+        );
+        assert_eq!(
+            state::literal_tags()
+                .keys(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn proper_store_checksum() {
+        let data = [
+            "<https://ex.org/a> <https://ex.org/p> \"x\" .",
+            "<https://ex.org/a> <https://ex.org/q> <https://ex.org/b> .",
+            "<https://ex.org/b> <https://ex.org/p> \"x\"@en .",
+        ];
+        let checksum = |deps: Deps<'_>| {
+            from_json::<StoreChecksumResponse>(
+                &query(deps, mock_env(), QueryMsg::StoreChecksum {}).unwrap(),
+            )
+            .unwrap()
+        };
+        let store_with = |lines: &[&str]| {
+            let mut deps = mock_dependencies();
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(OWNER), &[]),
+                InstantiateMsg::default(),
+            )
+            .unwrap();
+            for line in lines {
+                execute(
+                    deps.as_mut(),
+                    mock_env(),
+                    message_info(&addr(OWNER), &[]),
+                    InsertData {
+                        format: Some(DataFormat::NTriples),
+                        data: Binary::from(line.as_bytes()),
+                        compression: None,
+                        expires: None,
+                        graph: None,
+                        skip_oversized: false,
+                    },
+                )
+                .unwrap();
+            }
+            deps
+        };
+
+        let empty = store_with(&[]);
+        assert_eq!(
+            checksum(empty.as_ref()),
+            StoreChecksumResponse {
+                checksum: HexBinary::from([0u8; 32]),
+                triple_count: Uint128::zero(),
+            }
+        );
+
+        // the checksum doesn't depend on the insertion order, duplicates being ignored
+        let mut deps = store_with(&data);
+        let full = checksum(deps.as_ref());
+        assert_eq!(full.triple_count, Uint128::new(3));
+        assert_ne!(full.checksum, checksum(empty.as_ref()).checksum);
+        assert_eq!(
+            checksum(store_with(&[data[2], data[0], data[1], data[0]]).as_ref()),
+            full
+        );
+
+        // a deletion gives back the checksum of the remaining triples
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![msg::TripleDeleteTemplate {
+                    subject: VarOrNamedNode::NamedNode(Full("https://ex.org/a".to_string())),
+                    predicate: VarOrNamedNode::NamedNode(Full("https://ex.org/q".to_string())),
+                    object: VarOrNamedNodeOrLiteral::NamedNode(Full(
+                        "https://ex.org/b".to_string(),
+                    )),
+                }],
+                r#where: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            checksum(deps.as_ref()),
+            checksum(store_with(&[data[0], data[2]]).as_ref())
+        );
+
+        // the migration recomputes the same checksum from the triples
+        STORE_CHECKSUM.remove(deps.as_mut().storage);
+        migrate::migrate_state(deps.as_mut().storage, 0).unwrap();
+        migrate::migrate_triples_batch(deps.as_mut().storage, None).unwrap();
+        assert_eq!(
+            checksum(deps.as_ref()),
+            checksum(store_with(&[data[2], data[0]]).as_ref())
+        );
+    }
+
+    #[test]
+    fn proper_sudo() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<https://ex.org/a> <https://ex.org/p> \"a\" .\n\
+                     <https://ex.org/b> <https://ex.org/p> _:b .\n\
+                     _:b <https://ex.org/p> <https://ex.org/c> ."
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: Some(Expiration::AtHeight(mock_env().block.height + 10)),
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            ExecuteMsg::TransferOwnership {
+                new_owner: addr(SENDER).to_string(),
+            },
+        )
+        .unwrap();
+
+        // nothing is done while a migration of the triples is in progress
+        TRIPLE_MIGRATION
+            .save(&mut deps.storage, &state::TripleMigration::default())
+            .unwrap();
+        for msg in [
+            SudoMsg::ClearStore { limit: None },
+            SudoMsg::UpdateLimits {
+                limits: StoreLimitsInput::default(),
+            },
+            SudoMsg::TransferOwnership {
+                new_owner: addr("governance").to_string(),
+            },
+        ] {
+            assert_eq!(
+                sudo(deps.as_mut(), mock_env(), msg),
+                Err(ContractError::TripleMigrationInProgress)
+            );
+        }
+        assert_eq!(
+            STORE.load(&deps.storage).unwrap().stat.triple_count,
+            3u128.into()
+        );
+        TRIPLE_MIGRATION.remove(&mut deps.storage);
+
+        // the store is cleared in bounded steps
+        let clear = |deps: DepsMut<'_>, limit: Option<u32>| {
+            sudo(deps, mock_env(), SudoMsg::ClearStore { limit })
+                .unwrap()
+                .attributes
+        };
+        assert_eq!(
+            clear(deps.as_mut(), Some(2)),
+            vec![
+                Attribute::new("action", "clear_store"),
+                Attribute::new("triple_count", "2"),
+                Attribute::new("completed", "false"),
+            ]
+        );
+        assert_eq!(
+            clear(deps.as_mut(), None),
+            vec![
+                Attribute::new("action", "clear_store"),
+                Attribute::new("triple_count", "1"),
+                Attribute::new("completed", "true"),
+            ]
+        );
+        let store = STORE.load(&deps.storage).unwrap();
+        assert_eq!(store.stat, StoreStat::default());
+        assert_eq!(
+            triples()
+                .keys(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            0
+        );
+        assert_eq!(
+            EXPIRING_TRIPLES
+                .keys(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            0
+        );
+
+        // the limits are updated as the owner would
+        let limits = StoreLimitsInputBuilder::default()
+            .max_triple_count(Uint128::new(1))
+            .build()
+            .unwrap();
+        sudo(
+            deps.as_mut(),
+            mock_env(),
+            SudoMsg::UpdateLimits {
+                limits: limits.clone(),
+            },
+        )
+        .unwrap();
+        assert_eq!(STORE.load(&deps.storage).unwrap().limits, limits.into());
+
+        // the ownership is transferred at once, discarding the pending transfer
+        let res = sudo(
+            deps.as_mut(),
+            mock_env(),
+            SudoMsg::TransferOwnership {
+                new_owner: addr("governance").to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "transfer_ownership"),
+                Attribute::new("owner", addr("governance")),
+            ]
+        );
+        assert_eq!(STORE.load(&deps.storage).unwrap().owner, addr("governance"));
+        assert_eq!(PENDING_OWNER.may_load(&deps.storage).unwrap(), None);
+        assert!(sudo(
+            deps.as_mut(),
+            mock_env(),
+            SudoMsg::TransferOwnership {
+                new_owner: "invalid".to_string(),
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn proper_purge() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let env = mock_env();
+        let insert = |deps: DepsMut<'_>, data: &str, expires: Option<Expiration>| {
+            execute(
+                deps,
+                mock_env(),
+                message_info(&addr(OWNER), &[]),
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    compression: None,
+                    expires,
+                    graph: None,
+                    skip_oversized: false,
+                },
+            )
+        };
+        let purge = |deps: DepsMut<'_>, env: Env, limit: Option<u32>| {
+            execute(
+                deps,
+                env,
+                message_info(&addr(SENDER), &[]),
+                ExecuteMsg::Purge { limit },
+            )
+            .unwrap()
+            .attributes
+        };
+        let triple_count = |deps: Deps<'_>| STORE.load(deps.storage).unwrap().stat.triple_count;
+
+        insert(
+            deps.as_mut(),
+            "<https://ex.org/a> <https://ex.org/p> \"a1\" .\n<https://ex.org/a> <https://ex.org/p> \"a2\" .",
+            Some(Expiration::AtHeight(env.block.height + 10)),
+        )
+        .unwrap();
+        insert(
+            deps.as_mut(),
+            "<https://ex.org/b> <https://ex.org/p> \"b\" .",
+            Some(Expiration::AtTime(env.block.time.plus_seconds(60))),
+        )
+        .unwrap();
+        insert(
+            deps.as_mut(),
+            "<https://ex.org/c> <https://ex.org/p> \"c\" .",
+            Some(Expiration::Never {}),
+        )
+        .unwrap();
+        insert(
+            deps.as_mut(),
+            "<https://ex.org/d> <https://ex.org/p> \"d\" .",
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            insert(
+                deps.as_mut(),
+                "<https://ex.org/e> <https://ex.org/p> \"e\" .",
+                Some(Expiration::AtHeight(env.block.height)),
+            ),
+            Err(ContractError::AlreadyExpired)
+        );
+        assert_eq!(triple_count(deps.as_ref()), Uint128::new(5));
+
+        // nothing is expired yet
+        assert_eq!(
+            purge(deps.as_mut(), env.clone(), None),
+            vec![
+                Attribute::new("action", "purge"),
+                Attribute::new("triple_count", "0")
+            ]
+        );
+
+        // the expired triples are removed in bounded batches
+        let mut later = env.clone();
+        later.block.height += 10;
+        assert_eq!(
+            purge(deps.as_mut(), later.clone(), Some(1)),
+            vec![
+                Attribute::new("action", "purge"),
+                Attribute::new("triple_count", "1")
+            ]
+        );
+        assert_eq!(
+            purge(deps.as_mut(), later.clone(), None)[1],
+            Attribute::new("triple_count", "1")
+        );
+        assert_eq!(triple_count(deps.as_ref()), Uint128::new(3));
+
+        later.block.time = later.block.time.plus_seconds(60);
+        assert_eq!(
+            purge(deps.as_mut(), later.clone(), None)[1],
+            Attribute::new("triple_count", "1")
+        );
+        assert_eq!(triple_count(deps.as_ref()), Uint128::new(2));
+
+        // a deleted triple is no longer to be purged
+        insert(
+            deps.as_mut(),
+            "<https://ex.org/f> <https://ex.org/p> \"f\" .",
+            Some(Expiration::AtHeight(later.block.height + 1)),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![],
+                r#where: Some(WhereClause::Bgp {
+                    patterns: vec![TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full("https://ex.org/f".to_string()))),
+                        predicate: VarOrNamedNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                }),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            EXPIRING_TRIPLES
+                .range(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            0
+        );
+        assert!(TRIPLE_EXPIRATIONS.is_empty(&deps.storage));
+    }
+
+    #[test]
+    fn proper_subscriptions() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        let exec = |deps: DepsMut<'_>, sender: &str, msg: ExecuteMsg| {
+            execute(deps, mock_env(), message_info(&addr(sender), &[]), msg)
+        };
+        let insert = |deps: DepsMut<'_>, data: &str| {
+            exec(
+                deps,
+                OWNER,
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+            )
+            .unwrap()
+            .messages
+            .into_iter()
+            .map(|m| {
+                assert_eq!(m.id, SUBSCRIPTION_CALLBACK_REPLY_ID);
+                assert_eq!(m.reply_on, ReplyOn::Error);
+                m.msg
+            })
+            .collect::<Vec<_>>()
+        };
+        let subscribe = |pattern: TriplePattern| ExecuteMsg::Subscribe {
+            prefixes: vec![Prefix {
+                prefix: "ex".to_string(),
+                namespace: "https://ex.org/".to_string(),
+            }],
+            pattern,
+        };
+        let pattern = TriplePattern {
+            subject: VarOrNode::Variable("s".to_string()),
+            predicate: VarOrNamedNode::NamedNode(Prefixed("ex:p".to_string())),
+            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+        };
+
+        assert_eq!(
+            exec(deps.as_mut(), "consumer", subscribe(pattern.clone())).err(),
+            Some(ContractError::Unauthorized)
+        );
+        exec(
+            deps.as_mut(),
+            OWNER,
+            ExecuteMsg::GrantPermissions {
+                grantee: addr("consumer").to_string(),
+                permissions: vec![Permission::Subscribe],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            exec(
+                deps.as_mut(),
+                "consumer",
+                subscribe(TriplePattern {
+                    subject: VarOrNode::Node(BlankNode("b".to_string())),
+                    ..pattern.clone()
+                })
+            )
+            .err(),
+            Some(ContractError::Std(StdError::generic_err(
+                "Blank nodes are not allowed in a subscription pattern"
+            )))
+        );
+        let res = exec(deps.as_mut(), "consumer", subscribe(pattern)).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "subscribe"),
+                Attribute::new("subscription_id", "1")
+            ]
+        );
+        assert_eq!(
+            from_json::<SubscriptionResponse>(
+                &query(deps.as_ref(), mock_env(), QueryMsg::Subscription { id: 1 }).unwrap()
+            )
+            .unwrap(),
+            SubscriptionResponse {
+                id: 1,
+                subscriber: addr("consumer").to_string(),
+                subject: None,
+                predicate: Some("<https://ex.org/p>".to_string()),
+                object: None,
+            }
+        );
+
+        assert_eq!(
+            insert(
+                deps.as_mut(),
+                "<https://ex.org/a> <https://ex.org/p> \"a\" .
+<https://ex.org/a> <https://ex.org/q> \"a\" .
+<https://ex.org/b> <https://ex.org/p> <https://ex.org/a> ."
+            ),
+            vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: addr("consumer").to_string(),
+                msg: to_json_binary(&SubscriptionCallbackMsg::TriplesInserted {
+                    subscription_id: 1,
+                    triples: vec![
+                        "<https://ex.org/a> <https://ex.org/p> \"a\" .".to_string(),
+                        "<https://ex.org/b> <https://ex.org/p> <https://ex.org/a> .".to_string(),
+                    ],
+                })
+                .unwrap(),
+                funds: vec![],
+            })]
+        );
+        // already stored triples are not notified again
+        assert_eq!(
+            insert(
+                deps.as_mut(),
+                "<https://ex.org/a> <https://ex.org/p> \"a\" ."
+            ),
+            vec![]
+        );
+
+        assert_eq!(
+            exec(deps.as_mut(), SENDER, ExecuteMsg::Unsubscribe { id: 1 }).err(),
+            Some(ContractError::Unauthorized)
+        );
+        exec(deps.as_mut(), "consumer", ExecuteMsg::Unsubscribe { id: 1 }).unwrap();
+        assert_eq!(
+            insert(
+                deps.as_mut(),
+                "<https://ex.org/c> <https://ex.org/p> \"c\" ."
+            ),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn failing_subscription_callback() {
+        let mut deps = mock_dependencies();
+
+        #[allow(deprecated)]
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: SUBSCRIPTION_CALLBACK_REPLY_ID,
+                payload: to_json_binary(&1u64).unwrap(),
+                gas_used: 0,
+                result: SubMsgResult::Err("out of gas".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "subscription_callback_failed"),
+                Attribute::new("subscription_id", "1"),
+                Attribute::new("error", "out of gas"),
+            ]
+        );
+        assert!(res.messages.is_empty());
+    }
+
+    #[test]
+    fn proper_change_events() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        let byte_size = |deps: Deps<'_>| STORE.load(deps.storage).unwrap().stat.byte_size.u128();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InsertData {
+                format: Some(DataFormat::NQuads),
+                data: Binary::from(
+                    "<https://ex.org/a> <https://ex.org/p> \"a\" <https://ex.org/g> .
+<https://ex.org/b> <https://ex.org/p> \"b\" <https://ex.org/g> .
+<https://ex.org/c> <https://ex.org/p> \"c\" .
+_:n <https://ex.org/p> \"n\" ."
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.events,
+            vec![Event::new("triples_inserted")
+                .add_attribute("triple_count", "4")
+                .add_attribute("byte_delta", byte_size(deps.as_ref()).to_string())
+                .add_attribute("graph", "<https://ex.org/g>")
+                .add_attribute("subject", "<https://ex.org/a>")
+                .add_attribute("subject", "<https://ex.org/b>")
+                .add_attribute("subject", "<https://ex.org/c>")]
+        );
+
+        let before = byte_size(deps.as_ref());
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![],
+                r#where: Some(WhereClause::Bgp {
+                    patterns: vec![TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full("https://ex.org/a".to_string()))),
+                        predicate: VarOrNamedNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                }),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.events,
+            vec![Event::new("triples_deleted")
+                .add_attribute("triple_count", "1")
+                .add_attribute(
+                    "byte_delta",
+                    (byte_size(deps.as_ref()) as i128 - before as i128).to_string()
+                )
+                .add_attribute("graph", "<https://ex.org/g>")
+                .add_attribute("subject", "<https://ex.org/a>")]
+        );
+    }
+
+    #[test]
+    fn proper_provenance() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        let mut env = mock_env();
+        for (height, data) in [
+            (
+                10,
+                "<https://ex.org/a> <https://ex.org/p> \"x\" .\n<https://ex.org/a> <https://ex.org/q> <https://ex.org/b> .",
+            ),
+            (20, "<https://ex.org/b> <https://ex.org/p> \"y\" ."),
+        ] {
+            env.block.height = height;
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&addr(OWNER), &[]),
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+            )
+            .unwrap();
+        }
+
+        let provenance = |deps: Deps<'_>, predicate: &str, limit: Option<u32>| {
+            query(
+                deps,
+                mock_env(),
+                QueryMsg::Provenance {
+                    query: ProvenanceQuery {
+                        prefixes: vec![],
+                        pattern: TriplePattern {
+                            subject: VarOrNode::Variable("s".to_string()),
+                            predicate: VarOrNamedNode::NamedNode(Full(predicate.to_string())),
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        },
+                        limit,
+                    },
+                },
+            )
+            .and_then(|res| from_json::<ProvenanceResponse>(&res))
+        };
+        let insertion = |height: u64| Insertion {
+            inserter: addr(OWNER).to_string(),
+            height: Uint64::new(height),
+            tx_index: Some(mock_env().transaction.unwrap().index),
+        };
+        let literal = |value: &str| Value::Literal {
+            value: value.to_string(),
+            lang: None,
+            datatype: None,
+        };
+
+        let mut res = provenance(deps.as_ref(), "https://ex.org/p", None)
+            .unwrap()
+            .triples;
+        res.sort_by_key(|t| t.insertion.as_ref().map(|i| i.height));
+        assert_eq!(
+            res,
+            vec![
+                TripleProvenance {
+                    subject: Value::URI {
+                        value: Full("https://ex.org/a".to_string()),
+                    },
+                    predicate: Value::URI {
+                        value: Full("https://ex.org/p".to_string()),
+                    },
+                    object: literal("x"),
+                    insertion: Some(insertion(10)),
+                },
+                TripleProvenance {
+                    subject: Value::URI {
+                        value: Full("https://ex.org/b".to_string()),
+                    },
+                    predicate: Value::URI {
+                        value: Full("https://ex.org/p".to_string()),
+                    },
+                    object: literal("y"),
+                    insertion: Some(insertion(20)),
+                },
+            ]
+        );
+        assert_eq!(
+            provenance(deps.as_ref(), "https://ex.org/p", Some(1))
+                .unwrap()
+                .triples
+                .len(),
+            1
+        );
+        assert_eq!(
+            provenance(deps.as_ref(), "https://ex.org/unknown", None).unwrap(),
+            ProvenanceResponse { triples: vec![] }
+        );
+        assert_eq!(
+            provenance(deps.as_ref(), "https://ex.org/p", Some(u32::MAX)),
+            Err(StoreError::QueryLimit(StoreLimitsInput::default().max_query_limit).into())
+        );
+
+        // the insertion is forgotten along with the deleted triple
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![msg::TripleDeleteTemplate {
+                    subject: VarOrNamedNode::NamedNode(Full("https://ex.org/a".to_string())),
+                    predicate: VarOrNamedNode::NamedNode(Full("https://ex.org/q".to_string())),
+                    object: VarOrNamedNodeOrLiteral::NamedNode(Full(
+                        "https://ex.org/b".to_string(),
+                    )),
+                }],
+                r#where: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            TRIPLE_INSERTIONS
+                .range(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn proper_store() {
+        let mut deps = mock_dependencies();
+        STORE
+            .save(
+                deps.as_mut().storage,
+                &Store {
+                    owner: Addr::unchecked(OWNER),
+                    limits: StoreLimits {
+                        max_triple_count: 1u128.into(),
+                        max_byte_size: 2u128.into(),
+                        max_triple_byte_size: 3u128.into(),
+                        max_query_limit: 4u32,
+                        max_query_variable_count: 5u32,
+                        max_insert_data_byte_size: 6u128.into(),
+                        max_insert_data_triple_count: 7u128.into(),
+                        max_query_pattern_count: 8u32,
+                        max_query_where_depth: 9u32,
+                        max_query_join_variable_count: 10u32,
+                        max_query_inference_depth: 5u32,
+                        max_result_byte_size: Uint128::MAX,
+                        max_insert_blank_node_count: Uint128::MAX,
+                        max_insert_session_byte_size: 13u128.into(),
+                        max_query_ordered_solution_count: 14u32,
+                    },
+                    stat: StoreStat {
+                        triple_count: 1u128.into(),
+                        namespace_count: 2u128.into(),
+                        byte_size: 3u128.into(),
+                    },
+                    conflict_policy: ConflictPolicy::RejectDifferentWriter,
+                    literal_normalization: true,
+                },
+            )
+            .unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Store {});
+        assert!(res.is_ok());
+        assert_eq!(
+            from_json::<StoreResponse>(&res.unwrap()).unwrap(),
+            StoreResponse {
+                owner: OWNER.to_string(),
+                limits: msg::StoreLimits {
+                    max_triple_count: 1u128.into(),
+                    max_byte_size: 2u128.into(),
+                    max_triple_byte_size: 3u128.into(),
+                    max_query_limit: 4u32,
+                    max_query_variable_count: 5u32,
+                    max_insert_data_byte_size: 6u128.into(),
+                    max_insert_data_triple_count: 7u128.into(),
+                    max_query_pattern_count: 8u32,
+                    max_query_where_depth: 9u32,
+                    max_query_join_variable_count: 10u32,
+                    max_query_inference_depth: 5u32,
+                    max_result_byte_size: Uint128::MAX,
+                    max_insert_blank_node_count: Uint128::MAX,
+                    max_insert_session_byte_size: 13u128.into(),
+                    max_query_ordered_solution_count: 14u32,
+                },
+                stat: msg::StoreStat {
+                    triple_count: 1u128.into(),
+                    namespace_count: 2u128.into(),
+                    byte_size: 3u128.into(),
+                },
+                conflict_policy: ConflictPolicy::RejectDifferentWriter,
+                literal_normalization: true,
+                formats: vec![
+                    DataFormat::RDFXml,
+                    DataFormat::Turtle,
+                    DataFormat::NTriples,
+                    DataFormat::NQuads,
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn proper_namespaces() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInput {
+                    max_query_limit: 2,
+                    ..Default::default()
+                },
+                recent_window: None,
+                query_audit: None,
+                conflict_policy: Default::default(),
+                literal_offload: None,
+                disable_literal_normalization: false,
+                soft_limit: None,
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<https://a.org/s1> <https://a.org/p> <https://b.org/o> .\n\
+                     <https://a.org/s2> <https://a.org/p> <https://c.org/o> .\n"
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        let namespaces = |deps: Deps<'_>, cursor: Option<&str>, limit: Option<u32>| {
+            query(
+                deps,
+                mock_env(),
+                QueryMsg::Namespaces {
+                    cursor: cursor.map(str::to_string),
+                    limit,
+                },
+            )
+            .map(|res| {
+                from_json::<NamespacesResponse>(&res)
+                    .unwrap()
+                    .namespaces
+                    .into_iter()
+                    .map(|ns| (ns.namespace, ns.key.u128(), ns.references.u128()))
+                    .collect::<Vec<_>>()
+            })
+        };
+        let ns = |value: &str, key: u128, references: u128| (value.to_string(), key, references);
+
+        assert_eq!(
+            namespaces(deps.as_ref(), None, None),
+            Ok(vec![ns("https://a.org/", 0, 4), ns("https://b.org/", 1, 1)])
+        );
+        assert_eq!(
+            namespaces(deps.as_ref(), Some("https://a.org/"), Some(1)),
+            Ok(vec![ns("https://b.org/", 1, 1)])
+        );
+        assert_eq!(
+            namespaces(deps.as_ref(), Some("https://b.org/"), None),
+            Ok(vec![ns("https://c.org/", 2, 1)])
+        );
+        assert_eq!(
+            namespaces(deps.as_ref(), None, Some(3)),
+            Err(StoreError::QueryLimit(2).into())
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![msg::TripleDeleteTemplate {
+                    subject: VarOrNamedNode::NamedNode(Full("https://a.org/s2".to_string())),
+                    predicate: VarOrNamedNode::NamedNode(Full("https://a.org/p".to_string())),
+                    object: VarOrNamedNodeOrLiteral::NamedNode(Full("https://c.org/o".to_string())),
+                }],
+                r#where: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            namespaces(deps.as_ref(), Some("https://a.org/"), None),
+            Ok(vec![ns("https://b.org/", 1, 1)])
+        );
+        assert_eq!(
+            namespaces(deps.as_ref(), None, Some(1)),
+            Ok(vec![ns("https://a.org/", 0, 2)])
+        );
+    }
+
+    #[test]
+    fn construct_pages() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInput {
+                    max_query_limit: 2,
+                    ..Default::default()
+                },
+                recent_window: None,
+                query_audit: None,
+                conflict_policy: Default::default(),
+                literal_offload: None,
+                disable_literal_normalization: false,
+                soft_limit: None,
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<https://a.org/r1> <https://a.org/zone> <https://a.org/z1> .\n\
+                     <https://a.org/r2> <https://a.org/zone> <https://a.org/z1> .\n\
+                     <https://a.org/r3> <https://a.org/zone> <https://a.org/z1> .\n\
+                     <https://a.org/r4> <https://a.org/zone> <https://a.org/z2> .\n"
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        let construct = |deps: Deps<'_>,
+                         construct: Vec<msg::TripleConstructTemplate>,
+                         cursor: Option<Uint64>| {
+            let fresh_blank_nodes = !construct.is_empty();
+            from_json::<ConstructResponse>(
+                query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::Construct {
+                        query: ConstructQuery {
+                            prefixes: vec![],
+                            construct,
+                            r#where: WhereClause::Bgp {
+                                patterns: vec![TriplePattern {
+                                    subject: VarOrNode::Variable("s".to_string()),
+                                    predicate: VarOrNamedNode::NamedNode(Full(
+                                        "https://a.org/zone".to_string(),
+                                    )),
+                                    object: VarOrNodeOrLiteral::Node(NamedNode(Full(
+                                        "https://a.org/z1".to_string(),
+                                    ))),
+                                }],
+                            },
+                            fresh_blank_nodes,
+                        },
+                        format: Some(DataFormat::NTriples),
+                        cursor,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+        let lines = |res: &ConstructResponse| {
+            String::from_utf8(res.data.to_vec())
+                .unwrap()
+                .lines()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        let first = construct(deps.as_ref(), vec![], None);
+        assert_eq!(first.cursor, Some(Uint64::new(2)));
+        let second = construct(deps.as_ref(), vec![], first.cursor);
+        assert_eq!(second.cursor, None);
+
+        let mut triples = lines(&first);
+        assert_eq!(triples.len(), 2);
+        triples.extend(lines(&second));
+        triples.sort();
+        assert_eq!(
+            triples,
+            vec![
+                "<https://a.org/r1> <https://a.org/zone> <https://a.org/z1> .",
+                "<https://a.org/r2> <https://a.org/zone> <https://a.org/z1> .",
+                "<https://a.org/r3> <https://a.org/zone> <https://a.org/z1> .",
+            ]
+        );
+
+        let template = vec![msg::TripleConstructTemplate {
+            subject: VarOrNode::Variable("s".to_string()),
+            predicate: VarOrNamedNode::NamedNode(Full("https://a.org/entry".to_string())),
+            object: VarOrNodeOrLiteral::Node(BlankNode("e".to_string())),
+        }];
+        let first = construct(deps.as_ref(), template.clone(), None);
+        let second = construct(deps.as_ref(), template, first.cursor);
+        assert_eq!(second.cursor, None);
+
+        let mut labels = lines(&first)
+            .into_iter()
+            .chain(lines(&second))
+            .map(|line| line.split(' ').nth(2).unwrap().to_string())
+            .collect::<Vec<_>>();
+        labels.sort();
+        labels.dedup();
+        assert_eq!(labels, vec!["<a0_1>", "<a0_2>", "<a0_3>"]);
+    }
+
+    #[test]
+    fn proper_prefixes() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<https://a.org/s> <https://a.org/p> <https://b.org/o> .\n".as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        let prefix = |prefix: &str, namespace: &str| Prefix {
+            prefix: prefix.to_string(),
+            namespace: namespace.to_string(),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(SENDER), &[]),
+            ExecuteMsg::RegisterPrefixes {
+                prefixes: vec![prefix("a", "https://a.org/")],
+            },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::RegisterPrefixes {
+                prefixes: vec![prefix("b", "https://b.org/"), prefix("a", "https://a.org/")],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "register_prefixes"),
+                Attribute::new("prefix_count", "2"),
+            ]
+        );
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Prefixes {});
+        assert_eq!(
+            from_json::<PrefixesResponse>(&res.unwrap()).unwrap(),
+            PrefixesResponse {
+                prefixes: vec![prefix("a", "https://a.org/"), prefix("b", "https://b.org/")],
+            }
+        );
+
+        let select = |deps: Deps<'_>, prefixes: Vec<Prefix>| {
+            query(
+                deps,
+                mock_env(),
+                QueryMsg::Select {
+                    query: SelectQuery {
+                        prefixes,
+                        select: vec![SelectItem::Variable("o".to_string())],
+                        r#where: WhereClause::Bgp {
+                            patterns: vec![TriplePattern {
+                                subject: VarOrNode::Node(NamedNode(Prefixed("a:s".to_string()))),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed("a:p".to_string())),
+                                object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                            }],
+                        },
+                        limit: None,
+                        reasoning: None,
+                        skolemize: false,
+                        adaptive_joins: false,
+                        ordered: false,
+                        from: vec![],
+                        from_named: vec![],
+                    },
+                },
+            )
+            .map(|res| from_json::<SelectResponse>(&res).unwrap().results.bindings)
+        };
+
+        assert_eq!(
+            select(deps.as_ref(), vec![]),
+            Ok(vec![BTreeMap::from([(
+                "o".to_string(),
+                Value::URI {
+                    value: Full("https://b.org/o".to_string()),
+                }
+            )])])
+        );
+        assert_eq!(
+            select(deps.as_ref(), vec![prefix("a", "https://c.org/")]),
+            Ok(vec![])
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(SENDER), &[]),
+            ExecuteMsg::UnregisterPrefixes {
+                prefixes: vec!["a".to_string()],
+            },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UnregisterPrefixes {
+                prefixes: vec!["a".to_string(), "unknown".to_string()],
+            },
+        )
+        .unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Prefixes {});
+        assert_eq!(
+            from_json::<PrefixesResponse>(&res.unwrap()).unwrap(),
+            PrefixesResponse {
+                prefixes: vec![prefix("b", "https://b.org/")],
+            }
+        );
+        assert_eq!(
+            select(deps.as_ref(), vec![]),
+            Err(StdError::generic_err("Prefix not found: a"))
+        );
+    }
+
+    #[test]
+    fn soft_limit() {
+        let mut deps = mock_dependencies();
+        let owner = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            owner.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInput {
+                    max_triple_count: Uint128::new(4),
+                    ..Default::default()
+                },
+                soft_limit: Some(msg::SoftLimit {
+                    threshold_percent: 50,
+                    allowed_writers: Some(vec![addr("allowed").to_string()]),
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        for grantee in [addr(SENDER), addr("allowed")] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                owner.clone(),
+                ExecuteMsg::GrantPermissions {
+                    grantee: grantee.to_string(),
+                    permissions: vec![Permission::Insert],
+                },
+            )
+            .unwrap();
+        }
+
+        let insert = |deps: DepsMut<'_>, sender: &str, object: &str| {
+            execute(
+                deps,
+                mock_env(),
+                message_info(&addr(sender), &[]),
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(
+                        format!("<https://ex.org/s> <https://ex.org/p> \"{object}\" .").as_bytes(),
+                    ),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+            )
+        };
+
+        let res = insert(deps.as_mut(), OWNER, "a").unwrap();
+        assert!(!res
+            .attributes
+            .contains(&Attribute::new("near_limit", "true")));
+        assert!(res.events.iter().all(|e| e.ty != "store_near_limit"));
+
+        let res = insert(deps.as_mut(), SENDER, "b").unwrap();
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("near_limit", "true")));
+        assert_eq!(
+            res.events
+                .iter()
+                .find(|e| e.ty == "store_near_limit")
+                .map(|e| e.attributes[..3].to_vec()),
+            Some(vec![
+                Attribute::new("threshold_percent", "50"),
+                Attribute::new("triple_count", "2"),
+                Attribute::new("max_triple_count", "4"),
+            ])
+        );
+
+        assert_eq!(
+            insert(deps.as_mut(), SENDER, "c").unwrap_err(),
+            ContractError::StoreNearLimit
+        );
+        assert!(insert(deps.as_mut(), "allowed", "c").is_ok());
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            owner.clone(),
+            ExecuteMsg::UpdateSoftLimit {
+                soft_limit: Some(msg::SoftLimit {
+                    threshold_percent: 0,
+                    allowed_writers: None,
+                }),
+            },
+        );
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Generic error: Soft limit threshold must be between 1 and 100 percent"
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            owner,
+            ExecuteMsg::UpdateSoftLimit { soft_limit: None },
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "update_soft_limit"),
+                Attribute::new("enabled", "false"),
+            ]
+        );
+        let res = insert(deps.as_mut(), SENDER, "d").unwrap();
+        assert!(!res
+            .attributes
+            .contains(&Attribute::new("near_limit", "true")));
+    }
+
+    #[test]
+    fn stored_query() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<https://ex.org/a> <https://ex.org/name> \"A\" .\n<https://ex.org/b> <https://ex.org/name> \"B\" .\n"
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        let register = |query: SelectQuery, parameters: Vec<&str>| ExecuteMsg::RegisterQuery {
+            name: "name_of".to_string(),
+            query,
+            parameters: parameters.into_iter().map(str::to_string).collect(),
+        };
+        let name_of = |select: &str| SelectQuery {
+            prefixes: vec![],
+            select: vec![SelectItem::Variable(select.to_string())],
+            r#where: WhereClause::Bgp {
+                patterns: vec![TriplePattern {
+                    subject: VarOrNode::Variable("s".to_string()),
+                    predicate: VarOrNamedNode::NamedNode(Full("https://ex.org/name".to_string())),
+                    object: VarOrNodeOrLiteral::Variable("name".to_string()),
+                }],
+            },
+            limit: None,
+            reasoning: None,
+            skolemize: false,
+            adaptive_joins: false,
+            ordered: false,
+            from: vec![],
+            from_named: vec![],
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(SENDER), &[]),
+            register(name_of("name"), vec!["s"]),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            register(name_of("s"), vec!["s"]),
+        );
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Generic error: Parameter 's' can't be selected"
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            register(name_of("name"), vec!["s", "o"]),
+        );
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Generic error: Parameter 'o' doesn't occur in the query"
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            register(name_of("name"), vec!["s"]),
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "register_query"),
+                Attribute::new("name", "name_of"),
+            ]
+        );
+
+        let stored_query = |deps: Deps<'_>, name: &str, parameters: Vec<(&str, ParameterValue)>| {
+            query(
+                deps,
+                mock_env(),
+                QueryMsg::StoredQuery {
+                    name: name.to_string(),
+                    parameters: parameters
+                        .into_iter()
+                        .map(|(k, v)| (k.to_string(), v))
+                        .collect(),
+                },
+            )
+            .map(|res| from_json::<SelectResponse>(&res).unwrap().results.bindings)
+        };
+        let iri = |iri: &str| ParameterValue::NamedNode(Full(iri.to_string()));
+
+        assert_eq!(
+            stored_query(
+                deps.as_ref(),
+                "name_of",
+                vec![("s", iri("https://ex.org/b"))]
+            ),
+            Ok(vec![BTreeMap::from([(
+                "name".to_string(),
+                Value::Literal {
+                    value: "B".to_string(),
+                    lang: None,
+                    datatype: None,
+                }
+            )])])
+        );
+
+        let cases = vec![
+            ("name_of", vec![], "Generic error: Missing parameter 's'"),
+            (
+                "name_of",
+                vec![
+                    ("s", iri("https://ex.org/b")),
+                    ("p", iri("https://ex.org/p")),
+                ],
+                "Generic error: Unknown parameter 'p'",
+            ),
+            (
+                "name_of",
+                vec![(
+                    "s",
+                    ParameterValue::Literal(Literal::Simple("b".to_string())),
+                )],
+                "Generic error: Parameter 's' can't be bound to a literal as subject",
+            ),
+            ("unknown", vec![], "Stored query 'unknown' not found"),
+        ];
+        for (name, parameters, expected) in cases {
+            assert_eq!(
+                stored_query(deps.as_ref(), name, parameters)
+                    .unwrap_err()
+                    .to_string(),
+                expected
+            );
+        }
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UnregisterQuery {
+                name: "name_of".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            stored_query(
+                deps.as_ref(),
+                "name_of",
+                vec![("s", iri("https://ex.org/b"))]
+            )
+            .unwrap_err()
+            .to_string(),
+            "Stored query 'name_of' not found"
+        );
+    }
+
+    fn read_test_data(file: &str) -> Binary {
+        let mut bytes: Vec<u8> = Vec::new();
+
+        File::open(
+            Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
+                .join("testdata")
+                .join(file),
+        )
+        .unwrap()
+        .read_to_end(&mut bytes)
+        .unwrap();
+
+        Binary::from(bytes)
+    }
+
+    #[test]
+    fn proper_select() {
+        let cases = vec![
+            (
+                SelectQuery {
+                    prefixes: vec![],
+                    select: vec![
+                        SelectItem::Variable("a".to_string()),
+                        SelectItem::Variable("b".to_string()),
+                    ],
+                    r#where: WhereClause::Bgp{patterns:vec![TriplePattern {
+                            subject: VarOrNode::Variable("a".to_string()),
+                            predicate: VarOrNamedNode::NamedNode(Full(
+                                "https://ontology.axone.space/core/hasDescription".to_string(),
+                            )),
+                            object: VarOrNodeOrLiteral::Variable("b".to_string()),
+                        },
+                    ]},
+                    limit: None,
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
+                },
+                SelectResponse {
+                    head: Head {
+                        vars: vec!["a".to_string(), "b".to_string()],
+                    },
+                    results: Results {
+                        bindings: vec![
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())
+                                    }
+                                ),
+                                (
+                                    "b".to_string(),
+                                    Value::Literal {
+                                        value: "A test Data Space.".to_string(),
+                                        lang: Some("en".to_string()),
+                                        datatype: None,
+                                    }
+                                )
+                            ]),
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.axone.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string())
+                                    }
+                                ),
+                                (
+                                    "b".to_string(),
+                                    Value::Literal {
+                                        value: "Un Dataset de test.".to_string(),
+                                        lang: Some("fr".to_string()),
+                                        datatype: None,
+                                    }
+                                )
+                            ]),
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.axone.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string())
+                                    }
+                                ),
+                                (
+                                    "b".to_string(),
+                                    Value::Literal {
+                                        value: "A test Dataset.".to_string(),
+                                        lang: Some("en".to_string()),
+                                        datatype: None,
+                                    }
+                                )
+                            ]),
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())
+                                    }
+                                ),
+                                (
+                                    "b".to_string(),
+                                    Value::Literal {
+                                        value: "Un Data Space de test.".to_string(),
+                                        lang: Some("fr".to_string()),
+                                        datatype: None,
+                                    }
+                                )
+                            ]),
+                        ],
+                    },
+                },
+            ),
+            (
+                SelectQuery {
+                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
+                    select: vec![
+                        SelectItem::Variable("a".to_string()),
+                    ],
+                    r#where: WhereClause::Bgp{patterns:vec![TriplePattern {
+                            subject: VarOrNode::Variable("a".to_string()),
+                            predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                "core:hasDescription".to_string(),
+                            )),
+                            object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString { value: "A test Dataset.".to_string(), language: "en".to_string() }),
+                        },
+                    ]},
+                    limit: None,
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
+                },
+                SelectResponse {
+                    head: Head {
+                        vars: vec!["a".to_string()],
+                    },
+                    results: Results {
+                        bindings: vec![
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.axone.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string())
+                                    }
+                                ),
+                            ])
+                        ],
+                    },
+                },
+            ),
+            (
+                SelectQuery {
+                    prefixes: vec![],
+                    select: vec![
+                        SelectItem::Variable("a".to_string()),
+                    ],
+                    r#where: WhereClause::Bgp{patterns:vec![TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Full("https://ontology.axone.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string()))),
+                            predicate: VarOrNamedNode::Variable("a".to_string()),
+                            object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString { value: "A test Dataset.".to_string(), language: "en".to_string() }),
+                        },
+                    ]},
+                    limit: None,
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
+                },
+                SelectResponse {
+                    head: Head {
+                        vars: vec!["a".to_string()],
+                    },
+                    results: Results {
+                        bindings: vec![
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.axone.space/core/hasDescription".to_string())
+                                    }
+                                ),
+                            ])
+                        ],
+                    },
+                },
+            ),
+        ];
+
+        let mut deps = mock_dependencies();
+
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        for (q, expected) in cases {
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::Select { query: q });
+            assert!(res.is_ok());
+
+            let result = from_json::<SelectResponse>(&res.unwrap()).unwrap();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn proper_select_blank_nodes() {
+        let cases = vec![
+            (
+                SelectQuery {
+                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
+                    select: vec![SelectItem::Variable("a".to_string()), SelectItem::Variable("b".to_string())],
+                    r#where: WhereClause::Bgp{patterns:vec![
+                        TriplePattern {
+                                subject: VarOrNode::Variable("a".to_string()),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                    "core:hasTemporalCoverage".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Node(BlankNode("a".to_string())),
+                            },
+                        TriplePattern {
+                                subject: VarOrNode::Node(BlankNode("a".to_string())),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                    "core:hasStartDate".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("b".to_string()),
+                            },
+                        ]},
+                    limit: None,
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
+                },
+                SelectResponse {
+                    head: Head { vars: vec!["a".to_string(), "b".to_string()] },
+                    results: Results {
+                        bindings: vec![
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.axone.space/dataverse/dataset/metadata/80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string())
+                                    }
+                                ),
+                                (
+                                    "b".to_string(),
+                                    Value::Literal {
+                                        value: "2022-01-01T00:00:00Z".to_string(),
+                                        lang: None,
+                                        datatype: Some(Full("http://www.w3.org/2001/XMLSchema#dateTime".to_string())),
+                                    }
+                                )
+                            ])
+                        ],
+                    },
+                },
+            ),
+            (
+                SelectQuery {
+                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
+                    select: vec![SelectItem::Variable("a".to_string()), SelectItem::Variable("b".to_string())],
+                    r#where: WhereClause::Bgp{patterns:vec![
+                        TriplePattern {
+                                subject: VarOrNode::Variable("a".to_string()),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                    "core:hasTemporalCoverage".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("blank".to_string()),
+                            },
+                        TriplePattern {
+                                subject: VarOrNode::Variable("blank".to_string()),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                    "core:hasStartDate".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("b".to_string()),
+                            }
+                    ]},
+                    limit: None,
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
+                },
+                SelectResponse {
+                    head: Head { vars: vec!["a".to_string(), "b".to_string()] },
+                    results: Results {
+                        bindings: vec![
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.axone.space/dataverse/dataset/metadata/80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string())
+                                    }
+                                ),
+                                (
+                                    "b".to_string(),
+                                    Value::Literal {
+                                        value: "2022-01-01T00:00:00Z".to_string(),
+                                        lang: None,
+                                        datatype: Some(Full("http://www.w3.org/2001/XMLSchema#dateTime".to_string())),
+                                    }
+                                )
+                            ])
+                        ],
+                    },
+                },
+            ),
+            (
+                SelectQuery {
+                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
+                    select: vec![SelectItem::Variable("a".to_string()), SelectItem::Variable("b".to_string())],
+                    r#where: WhereClause::Bgp{patterns:vec![
+                        TriplePattern {
+                                subject: VarOrNode::Variable("a".to_string()),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                    "core:hasTemporalCoverage".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Node(BlankNode("blank1".to_string())),
+                            },
+                        TriplePattern {
+                                subject: VarOrNode::Node(BlankNode("blank2".to_string())),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                    "core:hasInformation".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("b".to_string()),
+                            },
+                    ]},
+                    limit: None,
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
+                },
+                SelectResponse {
+                    head: Head { vars: vec!["a".to_string(), "b".to_string()] },
+                    results: Results {
+                        bindings: vec![
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.axone.space/dataverse/dataset/metadata/80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string())
+                                    }
+                                ),
+                                (
+                                    "b".to_string(),
+                                    Value::Literal {
+                                        value: "this is a dataset".to_string(),
+                                        lang: None,
+                                        datatype: None,
+                                    }
+                                )
+                            ])
+                        ],
+                    },
+                },
+            ),
+            (
+                SelectQuery {
+                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
+                    select: vec![SelectItem::Variable("a".to_string()), SelectItem::Variable("b".to_string())],
+                    r#where: WhereClause::Bgp{patterns:vec![
+                        TriplePattern {
+                                subject: VarOrNode::Variable("a".to_string()),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                    "core:hasTemporalCoverage".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("b".to_string()),
+                            },
+                        ]},
+                    limit: None,
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
+                },
+                SelectResponse {
+                    head: Head { vars: vec!["a".to_string(), "b".to_string()] },
+                    results: Results {
+                        bindings: vec![
+                            BTreeMap::from([
+                                (
+                                    "a".to_string(),
+                                    Value::URI {
+                                        value: Full("https://ontology.axone.space/dataverse/dataset/metadata/80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string())
+                                    }
+                                ),
+                                (
+                                    "b".to_string(),
+                                    Value::BlankNode {
+                                        value: "b0".to_string(),
+                                    }
+                                )
+                            ])
+                        ],
+                    },
+                },
+            ),
+            (
+                SelectQuery {
+                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
+                    select: vec![SelectItem::Variable("b".to_string())],
+                    r#where: WhereClause::Bgp{patterns:vec![
+                        TriplePattern {
+                                subject: VarOrNode::Variable("a".to_string()),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                    "core:hasTemporalCoverage".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("b".to_string()),
+                            },
+                        ]},
+                    limit: None,
+                    reasoning: None,
+                    skolemize: true,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
+                },
+                SelectResponse {
+                    head: Head { vars: vec!["b".to_string()] },
+                    results: Results {
+                        bindings: vec![
+                            BTreeMap::from([
+                                (
+                                    "b".to_string(),
+                                    Value::URI {
+                                        value: Full("urn:bnode:0".to_string()),
+                                    }
+                                )
+                            ])
+                        ],
+                    },
+                },
+            ),
+        ];
+
+        let mut deps = mock_dependencies();
+
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: read_test_data("blank-nodes.ttl"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        for (q, expected) in cases {
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::Select { query: q });
+            assert!(res.is_ok());
+
+            let result = from_json::<SelectResponse>(&res.unwrap()).unwrap();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn budgeted_select() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    (0..5)
+                        .map(|i| {
+                            format!(
+                                "<https://ex.org/s{i}> <https://ex.org/name> \"n{i}\" .\n\
+                                 <https://ex.org/s{i}> <https://ex.org/age> \"{i}\" .\n"
+                            )
+                        })
+                        .collect::<String>()
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        let select_query = |limit: Option<u32>| SelectQuery {
+            prefixes: vec![],
+            select: vec![
+                SelectItem::Variable("s".to_string()),
+                SelectItem::Variable("n".to_string()),
+                SelectItem::Variable("a".to_string()),
+            ],
+            r#where: WhereClause::Bgp {
+                patterns: vec![
+                    TriplePattern {
+                        subject: VarOrNode::Variable("s".to_string()),
+                        predicate: VarOrNamedNode::NamedNode(Full(
+                            "https://ex.org/name".to_string(),
                         )),
-                        object: VarOrNamedNodeOrLiteral::NamedNode(Full(
-                            "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
+                        object: VarOrNodeOrLiteral::Variable("n".to_string()),
+                    },
+                    TriplePattern {
+                        subject: VarOrNode::Variable("s".to_string()),
+                        predicate: VarOrNamedNode::NamedNode(Full(
+                            "https://ex.org/age".to_string(),
                         )),
-                    }],
-                    r#where: WhereClause::Bgp {
-                        patterns: vec![TriplePattern {
-                            subject: VarOrNode::Node(NamedNode(Full(
-                                "https://ontology.axone.space/dataverse/dataspace/metadata/unknown"
-                                    .to_string(),
-                            ))),
-                            predicate: VarOrNamedNode::NamedNode(Full(
-                                "https://ontology.axone.space/core/hasTopic".to_string(),
-                            )),
-                            object: VarOrNodeOrLiteral::Node(NamedNode(Full(
-                                "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
-                            ))),
-                        }],
-                    }
-                    .into(),
+                        object: VarOrNodeOrLiteral::Variable("a".to_string()),
+                    },
+                ],
+            },
+            limit,
+            reasoning: None,
+            skolemize: false,
+            adaptive_joins: false,
+            ordered: false,
+            from: vec![],
+            from_named: vec![],
+        };
+        let budgeted_select = |deps: Deps<'_>, limit, max_rows, cursor| {
+            query(
+                deps,
+                mock_env(),
+                QueryMsg::BudgetedSelect {
+                    query: select_query(limit),
+                    max_rows,
+                    cursor,
+                },
+            )
+            .map(|res| from_json::<BudgetedSelectResponse>(&res).unwrap())
+        };
+
+        let cases = vec![
+            (None, 100u32, 1usize),
+            (None, 3, 5),
+            (None, 5, 3),
+            (Some(3), 3, 3),
+        ];
+        for (limit, max_rows, expected_calls) in cases {
+            let expected = from_json::<SelectResponse>(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::Select {
+                        query: select_query(limit),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+            let mut bindings = vec![];
+            let mut calls = 0;
+            let mut cursor = None;
+            loop {
+                let res = budgeted_select(deps.as_ref(), limit, max_rows, cursor).unwrap();
+                assert_eq!(res.head, expected.head);
+                bindings.extend(res.results.bindings);
+                calls += 1;
+                cursor = res.cursor;
+                if cursor.is_none() {
+                    break;
+                }
+            }
+
+            assert_eq!(bindings, expected.results.bindings);
+            assert_eq!(calls, expected_calls);
+        }
+
+        assert_eq!(
+            budgeted_select(deps.as_ref(), None, 3, None).map(|res| res.cursor),
+            Ok(Some(SelectCursor {
+                rows: 1u64.into(),
+                results: 1,
+            }))
+        );
+        assert_eq!(
+            budgeted_select(deps.as_ref(), None, 1, None),
+            Err(QueryComplexityError::RowsBudget(1).into())
+        );
+    }
+
+    #[test]
+    fn budgeted_select_over_union() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    (0..5)
+                        .map(|i| {
+                            format!(
+                                "<https://ex.org/s{i}> <https://ex.org/name> \"n{i}\" .\n\
+                                 <https://ex.org/t{i}> <https://ex.org/alias> \"a{i}\" .\n"
+                            )
+                        })
+                        .chain(std::iter::once(
+                            "<https://ex.org/alias> <http://www.w3.org/2000/01/rdf-schema#subPropertyOf> <https://ex.org/name> .\n".to_string(),
+                        ))
+                        .collect::<String>()
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        // the pattern is expanded into the union of the name and alias patterns, each of them
+        // matching more rows than the budget
+        let select_query = SelectQuery {
+            prefixes: vec![],
+            select: vec![
+                SelectItem::Variable("s".to_string()),
+                SelectItem::Variable("n".to_string()),
+            ],
+            r#where: WhereClause::Bgp {
+                patterns: vec![TriplePattern {
+                    subject: VarOrNode::Variable("s".to_string()),
+                    predicate: VarOrNamedNode::NamedNode(Full("https://ex.org/name".to_string())),
+                    object: VarOrNodeOrLiteral::Variable("n".to_string()),
+                }],
+            },
+            limit: None,
+            reasoning: Some(msg::Reasoning {
+                max_depth: None,
+                same_as: None,
+            }),
+            skolemize: false,
+            adaptive_joins: false,
+            ordered: false,
+            from: vec![],
+            from_named: vec![],
+        };
+        let expected = from_json::<SelectResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Select {
+                    query: select_query.clone(),
                 },
-                0,
-                0,
-                Uint128::from(7190u128),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(expected.results.bindings.len(), 10);
+
+        let mut bindings = vec![];
+        let mut cursors = vec![];
+        let mut cursor = None;
+        loop {
+            let res = from_json::<BudgetedSelectResponse>(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::BudgetedSelect {
+                        query: select_query.clone(),
+                        max_rows: 3,
+                        cursor: cursor.clone(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            bindings.extend(res.results.bindings);
+            cursor = res.cursor;
+            match &cursor {
+                Some(cursor) => cursors.push(cursor.clone()),
+                None => break,
+            }
+        }
+
+        assert_eq!(bindings, expected.results.bindings);
+        assert_eq!(
+            cursors
+                .into_iter()
+                .map(|cursor| (cursor.rows.u64(), cursor.results))
+                .collect::<Vec<_>>(),
+            vec![(2, 2), (4, 4), (6, 6), (8, 8)]
+        );
+    }
+
+    #[test]
+    fn proper_count() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_query_limit(2u32)
+                    .max_query_pattern_count(2u32)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    (0..5)
+                        .map(|i| {
+                            format!(
+                                "<https://ex.org/s{i}> <https://ex.org/name> \"n{i}\" .\n\
+                                 <https://ex.org/s{i}> <https://ex.org/age> \"{}\" .\n",
+                                i % 2
+                            )
+                        })
+                        .collect::<String>()
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        let pattern = |predicate: &str, object: VarOrNodeOrLiteral| TriplePattern {
+            subject: VarOrNode::Variable("s".to_string()),
+            predicate: VarOrNamedNode::NamedNode(Full(predicate.to_string())),
+            object,
+        };
+        let cases = vec![
+            (
+                vec![pattern(
+                    "https://ex.org/name",
+                    VarOrNodeOrLiteral::Variable("n".to_string()),
+                )],
+                Ok(5u64),
             ),
             (
-                DeleteData {
+                vec![
+                    pattern(
+                        "https://ex.org/name",
+                        VarOrNodeOrLiteral::Variable("n".to_string()),
+                    ),
+                    pattern(
+                        "https://ex.org/age",
+                        VarOrNodeOrLiteral::Literal(Literal::Simple("1".to_string())),
+                    ),
+                ],
+                Ok(2u64),
+            ),
+            (
+                vec![pattern(
+                    "https://ex.org/unknown",
+                    VarOrNodeOrLiteral::Variable("n".to_string()),
+                )],
+                Ok(0u64),
+            ),
+            (
+                vec![
+                    pattern(
+                        "https://ex.org/name",
+                        VarOrNodeOrLiteral::Variable("n".to_string()),
+                    ),
+                    pattern(
+                        "https://ex.org/age",
+                        VarOrNodeOrLiteral::Variable("a".to_string()),
+                    ),
+                    pattern(
+                        "https://ex.org/age",
+                        VarOrNodeOrLiteral::Variable("b".to_string()),
+                    ),
+                ],
+                Err(StdError::generic_err(
+                    "Maximum query triple pattern count exceeded: 2",
+                )),
+            ),
+        ];
+
+        for (patterns, expected) in cases {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Count {
+                    query: CountQuery {
+                        prefixes: vec![],
+                        r#where: WhereClause::Bgp { patterns },
+                        reasoning: None,
+                    },
+                },
+            );
+            assert_eq!(
+                res.map(|res| from_json::<CountResponse>(&res).unwrap().count.u64()),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn proper_explain() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    (0..5)
+                        .map(|i| {
+                            format!(
+                                "<https://ex.org/s{i}> <https://ex.org/name> \"n{i}\" .\n\
+                                 <https://ex.org/s{i}> <https://ex.org/age> \"{i}\" .\n"
+                            )
+                        })
+                        .collect::<String>()
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        let pattern = |subject: &str, predicate: &str, object: VarOrNodeOrLiteral| TriplePattern {
+            subject: VarOrNode::Variable(subject.to_string()),
+            predicate: VarOrNamedNode::NamedNode(Full(predicate.to_string())),
+            object,
+        };
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Explain {
+                query: SelectQuery {
                     prefixes: vec![],
-                    delete: vec![msg::TripleDeleteTemplate {
-                        subject: VarOrNamedNode::NamedNode(Full(id.to_string())),
-                        predicate: VarOrNamedNode::NamedNode(Full(
-                            "https://ontology.axone.space/core/hasTopic".to_string(),
-                        )),
-                        object: VarOrNamedNodeOrLiteral::NamedNode(Full(
-                            "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
-                        )),
-                    }],
+                    select: vec![SelectItem::Variable("s".to_string())],
                     r#where: WhereClause::Bgp {
-                        patterns: vec![TriplePattern {
-                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                            predicate: VarOrNamedNode::NamedNode(Full(
-                                "https://ontology.axone.space/core/hasTopic".to_string(),
-                            )),
-                            object: VarOrNodeOrLiteral::Node(NamedNode(Full(
-                                "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
-                            ))),
-                        }],
-                    }
-                    .into(),
+                        patterns: vec![
+                            pattern(
+                                "s",
+                                "https://ex.org/name",
+                                VarOrNodeOrLiteral::Variable("n".to_string()),
+                            ),
+                            pattern(
+                                "s",
+                                "https://ex.org/age",
+                                VarOrNodeOrLiteral::Variable("a".to_string()),
+                            ),
+                            pattern(
+                                "x",
+                                "https://ex.org/age",
+                                VarOrNodeOrLiteral::Literal(Literal::Simple("3".to_string())),
+                            ),
+                            pattern(
+                                "x",
+                                "https://unknown.org/p",
+                                VarOrNodeOrLiteral::Variable("y".to_string()),
+                            ),
+                        ],
+                    },
+                    limit: Some(10),
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
                 },
-                1,
-                0,
-                Uint128::from(7005u128),
+            },
+        );
+
+        let var = |name: &str, bound: bool| PatternTerm::Variable {
+            name: name.to_string(),
+            bound,
+        };
+        assert_eq!(
+            from_json::<ExplainResponse>(&res.unwrap()).unwrap(),
+            ExplainResponse {
+                plan: PlanNode::Limit {
+                    first: 10,
+                    child: Box::new(PlanNode::ForLoopJoin {
+                        left: Box::new(PlanNode::CartesianProductJoin {
+                            left: Box::new(PlanNode::ForLoopJoin {
+                                left: Box::new(PlanNode::TriplePattern {
+                                    subject: var("s", false),
+                                    predicate: PatternTerm::Constant {},
+                                    object: var("n", false),
+                                    index: TripleIndex::PredicateObjectSubject,
+                                    estimated_rows: 5,
+                                }),
+                                right: Box::new(PlanNode::TriplePattern {
+                                    subject: var("s", true),
+                                    predicate: PatternTerm::Constant {},
+                                    object: var("a", false),
+                                    index: TripleIndex::SubjectPredicateObject,
+                                    estimated_rows: 5,
+                                }),
+                            }),
+                            right: Box::new(PlanNode::TriplePattern {
+                                subject: var("x", false),
+                                predicate: PatternTerm::Constant {},
+                                object: PatternTerm::Constant {},
+                                index: TripleIndex::ObjectPredicateSubject,
+                                estimated_rows: 1,
+                            }),
+                        }),
+                        right: Box::new(PlanNode::Noop {}),
+                    }),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn adaptive_joins() {
+        /// A read-only storage counting the entries read from the wrapped one.
+        struct ReadCountingStorage<'a> {
+            inner: &'a dyn Storage,
+            reads: Cell<usize>,
+        }
+
+        impl Storage for ReadCountingStorage<'_> {
+            fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+                self.reads.set(self.reads.get() + 1);
+                self.inner.get(key)
+            }
+
+            fn range<'a>(
+                &'a self,
+                start: Option<&[u8]>,
+                end: Option<&[u8]>,
+                order: Order,
+            ) -> Box<dyn Iterator<Item = Record> + 'a> {
+                Box::new(
+                    self.inner
+                        .range(start, end, order)
+                        .inspect(|_| self.reads.set(self.reads.get() + 1)),
+                )
+            }
+
+            fn set(&mut self, _: &[u8], _: &[u8]) {
+                unreachable!("read-only storage")
+            }
+
+            fn remove(&mut self, _: &[u8]) {
+                unreachable!("read-only storage")
+            }
+        }
+
+        // a skewed registry of credentials: all of them are about the same resource, typed and
+        // issued alike but one of each.
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    (0..300)
+                        .map(|i| {
+                            let r#type = if i == 7 { "UsagePolicy" } else { "Claim" };
+                            let issuer = if i == 42 { "rare" } else { "common" };
+                            let claim = if i == 7 {
+                                "<https://ex.org/cred/7> <dataverse:credential:body#claim> _:c .\n\
+                                 _:c <https://ex.org/license> \"CC-BY-4.0\" .\n"
+                            } else {
+                                ""
+                            };
+                            format!(
+                                "<https://ex.org/cred/{i}> <dataverse:credential:body#subject> <https://ex.org/dataset> .\n\
+                                 <https://ex.org/cred/{i}> <dataverse:credential:body#type> <https://ex.org/{type}Credential> .\n\
+                                 <https://ex.org/cred/{i}> <dataverse:credential:body#issuer> <https://ex.org/issuer/{issuer}> .\n\
+                                 {claim}"
+                            )
+                        })
+                        .collect::<String>()
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        let pattern = |subject: &str, predicate: &str, object: VarOrNodeOrLiteral| TriplePattern {
+            subject: VarOrNode::Variable(subject.to_string()),
+            predicate: VarOrNamedNode::NamedNode(Full(predicate.to_string())),
+            object,
+        };
+        let named = |iri: &str| VarOrNodeOrLiteral::Node(NamedNode(Full(iri.to_string())));
+        let variable = |name: &str| VarOrNodeOrLiteral::Variable(name.to_string());
+        let cases = vec![
+            // the claims of a type issued by an issuer, the subject being selected
+            (
+                vec![
+                    pattern(
+                        "credential",
+                        "dataverse:credential:body#subject",
+                        variable("subject"),
+                    ),
+                    pattern(
+                        "credential",
+                        "dataverse:credential:body#type",
+                        named("https://ex.org/ClaimCredential"),
+                    ),
+                    pattern(
+                        "credential",
+                        "dataverse:credential:body#issuer",
+                        named("https://ex.org/issuer/rare"),
+                    ),
+                ],
+                "https://ex.org/cred/42",
             ),
+            // the usage policies declared about a resource
             (
-                DeleteData {
-                    prefixes: vec![
-                        Prefix {
-                            prefix: "core".to_string(),
-                            namespace: "https://ontology.axone.space/core/".to_string(),
-                        },
-                        Prefix {
-                            prefix: "thesaurus".to_string(),
-                            namespace: "https://ontology.axone.space/thesaurus/topic/".to_string(),
+                vec![
+                    pattern(
+                        "credential",
+                        "dataverse:credential:body#subject",
+                        named("https://ex.org/dataset"),
+                    ),
+                    pattern(
+                        "credential",
+                        "dataverse:credential:body#type",
+                        named("https://ex.org/UsagePolicyCredential"),
+                    ),
+                    pattern(
+                        "credential",
+                        "dataverse:credential:body#claim",
+                        variable("c"),
+                    ),
+                    TriplePattern {
+                        subject: VarOrNode::Variable("c".to_string()),
+                        predicate: VarOrNamedNode::Variable("p".to_string()),
+                        object: variable("o"),
+                    },
+                ],
+                "https://ex.org/cred/7",
+            ),
+        ];
+
+        for (patterns, expected) in cases {
+            let select = |adaptive_joins: bool| {
+                let storage = ReadCountingStorage {
+                    inner: &deps.storage,
+                    reads: Cell::new(0),
+                };
+                let res = query::select(
+                    Deps {
+                        storage: &storage,
+                        api: &deps.api,
+                        querier: QuerierWrapper::new(&deps.querier),
+                    },
+                    SelectQuery {
+                        prefixes: vec![],
+                        select: vec![SelectItem::Variable("credential".to_string())],
+                        r#where: WhereClause::Bgp {
+                            patterns: patterns.clone(),
                         },
+                        limit: None,
+                        reasoning: None,
+                        skolemize: false,
+                        adaptive_joins,
+                        ordered: false,
+                        from: vec![],
+                        from_named: vec![],
+                    },
+                )
+                .unwrap();
+                (res, storage.reads.get())
+            };
+
+            let (static_res, static_reads) = select(false);
+            let (adaptive_res, adaptive_reads) = select(true);
+            assert_eq!(adaptive_res, static_res);
+            assert_eq!(
+                adaptive_res.results.bindings,
+                vec![BTreeMap::from([(
+                    "credential".to_string(),
+                    Value::URI {
+                        value: Full(expected.to_string()),
+                    },
+                )])]
+            );
+            assert!(
+                adaptive_reads * 10 <= static_reads,
+                "{expected}: {adaptive_reads} reads against {static_reads}"
+            );
+        }
+
+        let res = query::explain(
+            deps.as_ref(),
+            SelectQuery {
+                prefixes: vec![],
+                select: vec![SelectItem::Variable("credential".to_string())],
+                r#where: WhereClause::Bgp {
+                    patterns: vec![
+                        pattern(
+                            "credential",
+                            "dataverse:credential:body#subject",
+                            variable("s"),
+                        ),
+                        pattern(
+                            "credential",
+                            "dataverse:credential:body#issuer",
+                            variable("i"),
+                        ),
                     ],
-                    delete: vec![msg::TripleDeleteTemplate {
-                        subject: VarOrNamedNode::NamedNode(Full(id.to_string())),
-                        predicate: VarOrNamedNode::NamedNode(Prefixed("core:hasTopic".to_string())),
-                        object: VarOrNamedNodeOrLiteral::NamedNode(Prefixed(
-                            "thesaurus:Test".to_string(),
-                        )),
-                    }],
-                    r#where: WhereClause::Bgp {
-                        patterns: vec![TriplePattern {
-                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                            predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                "core:hasTopic".to_string(),
-                            )),
-                            object: VarOrNodeOrLiteral::Node(NamedNode(Prefixed(
-                                "thesaurus:Test".to_string(),
-                            ))),
-                        }],
-                    }
-                    .into(),
                 },
-                1,
-                0,
-                Uint128::from(7005u128),
-            ),
+                limit: None,
+                reasoning: None,
+                skolemize: false,
+                adaptive_joins: true,
+                ordered: false,
+                from: vec![],
+                from_named: vec![],
+            },
+        )
+        .unwrap();
+        assert!(matches!(
+            res.plan,
+            PlanNode::Limit { child, .. }
+                if matches!(&*child, PlanNode::AdaptiveJoin { children } if children.len() == 2)
+        ));
+    }
+
+    #[test]
+    fn invalid_select() {
+        let cases = vec![
             (
-                DeleteData {
-                    prefixes: vec![
-                        Prefix {
-                            prefix: "core".to_string(),
-                            namespace: "https://ontology.axone.space/core/".to_string(),
-                        },
-                        Prefix {
-                            prefix: "thesaurus".to_string(),
-                            namespace: "https://ontology.axone.space/thesaurus/topic/".to_string(),
-                        },
+                SelectQuery {
+                    prefixes: vec![],
+                    select: vec![
+                        SelectItem::Variable("a".to_string()),
+                        SelectItem::Variable("b".to_string()),
                     ],
-                    delete: vec![msg::TripleDeleteTemplate {
-                        subject: VarOrNamedNode::NamedNode(Full(id.to_string())),
-                        predicate: VarOrNamedNode::NamedNode(Prefixed("core:hasTopic".to_string())),
-                        object: VarOrNamedNodeOrLiteral::Variable("o".to_string()),
-                    }],
-                    r#where: WhereClause::Bgp {
-                        patterns: vec![TriplePattern {
-                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                            predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                "core:hasTopic".to_string(),
-                            )),
-                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                        }],
-                    }
-                    .into(),
+                    r#where: WhereClause::Bgp { patterns: vec![] },
+                    limit: None,
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
                 },
-                1,
-                0,
-                Uint128::from(7005u128),
+                Err(StoreError::QueryVariableCount(1).into()),
             ),
             (
-                DeleteData {
+                SelectQuery {
                     prefixes: vec![],
-                    delete: vec![msg::TripleDeleteTemplate {
-                        subject: VarOrNamedNode::NamedNode(Full(id.to_string())),
-                        predicate: VarOrNamedNode::Variable("p".to_string()),
-                        object: VarOrNamedNodeOrLiteral::Variable("o".to_string()),
-                    }],
-                    r#where: WhereClause::Bgp {
-                        patterns: vec![TriplePattern {
-                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                            predicate: VarOrNamedNode::Variable("p".to_string()),
-                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                        }],
-                    }
-                    .into(),
+                    select: vec![],
+                    r#where: WhereClause::Bgp { patterns: vec![] },
+                    limit: Some(8000),
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
                 },
-                11,
-                2,
-                Uint128::from(5334u128),
+                Err(StoreError::QueryLimit(30).into()),
             ),
             (
-                DeleteData {
-                    prefixes: vec![],
-                    delete: vec![],
+                SelectQuery {
+                    prefixes: vec![Prefix {
+                        prefix: "core".to_string(),
+                        namespace: "https://ontology.axone.space/core/".to_string(),
+                    }],
+                    select: vec![SelectItem::Variable("a".to_string())],
                     r#where: WhereClause::Bgp {
                         patterns: vec![TriplePattern {
-                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                            predicate: VarOrNamedNode::Variable("p".to_string()),
-                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                            subject: VarOrNode::Variable("a".to_string()),
+                            predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                "invalid:hasDescription".to_string(),
+                            )),
+                            object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString {
+                                value: "A test Dataset.".to_string(),
+                                language: "en".to_string(),
+                            }),
                         }],
-                    }
-                    .into(),
+                    },
+                    limit: None,
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
                 },
-                11,
-                2,
-                Uint128::from(5334u128),
+                Err(StdError::generic_err("Prefix not found: invalid")),
             ),
             (
-                DeleteData {
+                SelectQuery {
                     prefixes: vec![],
-                    delete: vec![],
+                    select: vec![SelectItem::Variable("u".to_string())],
                     r#where: WhereClause::Bgp {
                         patterns: vec![TriplePattern {
-                            subject: VarOrNode::Variable("s".to_string()),
-                            predicate: VarOrNamedNode::Variable("p".to_string()),
-                            object: VarOrNodeOrLiteral::Variable("0".to_string()),
+                            subject: VarOrNode::Variable("a".to_string()),
+                            predicate: VarOrNamedNode::NamedNode(Full(
+                                "https://ontology.axone.space/core/hasDescription".to_string(),
+                            )),
+                            object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString {
+                                value: "A test Dataset.".to_string(),
+                                language: "en".to_string(),
+                            }),
                         }],
-                    }
-                    .into(),
-                },
-                40,
-                17,
-                Uint128::from(0u128),
-            ),
-            (
-                DeleteData {
-                    prefixes: vec![
-                        Prefix {
-                            prefix: "core".to_string(),
-                            namespace: "https://ontology.axone.space/core/".to_string(),
-                        },
-                        Prefix {
-                            prefix: "thesaurus".to_string(),
-                            namespace: "https://ontology.axone.space/thesaurus/topic/".to_string(),
-                        },
-                    ],
-                    delete: vec![msg::TripleDeleteTemplate {
-                        subject: VarOrNamedNode::NamedNode(Full(id.to_string())),
-                        predicate: VarOrNamedNode::NamedNode(Prefixed("core:hasTopic".to_string())),
-                        object: VarOrNamedNodeOrLiteral::NamedNode(Prefixed(
-                            "thesaurus:Test".to_string(),
-                        )),
-                    }],
-                    r#where: None,
+                    },
+                    limit: None,
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
                 },
-                1,
-                0,
-                Uint128::from(7005u128),
+                Err(StdError::generic_err(
+                    "Selected variable not found in query",
+                )),
             ),
         ];
 
-        for case in cases {
-            let mut deps = mock_dependencies();
-
-            let info = message_info(&addr(OWNER), &[]);
-            instantiate(
-                deps.as_mut(),
-                mock_env(),
-                info.clone(),
-                InstantiateMsg::default(),
-            )
-            .unwrap();
+        let mut deps = mock_dependencies();
 
-            execute(
-                deps.as_mut(),
-                mock_env(),
-                info.clone(),
-                InsertData {
-                    format: Some(DataFormat::RDFXml),
-                    data: read_test_data("sample.rdf.xml"),
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInput {
+                    max_query_variable_count: 1,
+                    ..Default::default()
                 },
-            )
-            .unwrap();
-
-            let res = execute(deps.as_mut(), mock_env(), info, case.0);
+                recent_window: None,
+                query_audit: None,
+                conflict_policy: Default::default(),
+                literal_offload: None,
+                disable_literal_normalization: false,
+                soft_limit: None,
+            },
+        )
+        .unwrap();
 
-            assert!(res.is_ok());
-            assert_eq!(
-                res.unwrap().attributes,
-                vec![
-                    Attribute::new("action", "delete"),
-                    Attribute::new("triple_count", case.1.to_string()),
-                ]
-            );
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
 
-            assert_eq!(
-                STORE.load(&deps.storage).unwrap().stat,
-                StoreStat {
-                    triple_count: (40u128 - u128::try_from(case.1).unwrap()).into(),
-                    namespace_count: (17u128 - u128::try_from(case.2).unwrap()).into(),
-                    byte_size: case.3,
-                },
-            );
-            assert_eq!(
-                triples()
-                    .range_raw(&deps.storage, None, None, Order::Ascending)
-                    .count(),
-                40 - case.1
-            );
-            assert_eq!(
-                namespaces()
-                    .range_raw(&deps.storage, None, None, Order::Ascending)
-                    .count(),
-                17 - case.2
-            );
+        for (q, expected) in cases {
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::Select { query: q });
+            assert_eq!(res, expected);
         }
     }
 
     #[test]
-    fn invalid_delete() {
-        struct TC {
-            command: ExecuteMsg,
-            expected: ContractError,
-        }
-        let cases = vec![
-            TC {
-                command: DeleteData {
-                    prefixes: vec![],
-                    delete: vec![msg::TripleDeleteTemplate {
-                        subject: VarOrNamedNode::NamedNode(Prefixed("foo:bar".to_string())),
-                        predicate: VarOrNamedNode::NamedNode(Full(
-                            "https://ontology.axone.space/core/hasTopic".to_string(),
-                        )),
-                        object: VarOrNamedNodeOrLiteral::NamedNode(Full(
-                            "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
-                        )),
-                    }],
-                    r#where: WhereClause::Bgp {
-                        patterns: vec![TriplePattern {
-                            subject: VarOrNode::Node(NamedNode(Prefixed("foo:bar".to_string()))),
-                            predicate: VarOrNamedNode::NamedNode(Full(
-                                "https://ontology.axone.space/core/hasTopic".to_string(),
-                            )),
-                            object: VarOrNodeOrLiteral::Node(NamedNode(Full(
-                                "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
-                            ))),
-                        }],
-                    }
-                    .into(),
-                },
-                expected: StdError::generic_err("Prefix not found: foo").into(),
+    fn ordered_select() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                limits: StoreLimitsInputBuilder::default()
+                    .max_query_ordered_solution_count(10u32)
+                    .build()
+                    .unwrap(),
+                ..Default::default()
             },
-            TC {
-                command: DeleteData {
-                    prefixes: vec![],
-                    delete: vec![msg::TripleDeleteTemplate {
-                        subject: VarOrNamedNode::NamedNode(Full(
-                            "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
-                        )),
-                        predicate: VarOrNamedNode::Variable("z".to_string()),
-                        object: VarOrNamedNodeOrLiteral::Variable("o".to_string()),
-                    }],
-                    r#where: WhereClause::Bgp {
-                        patterns: vec![TriplePattern {
-                            subject: VarOrNode::Node(NamedNode(Full(
-                                "https://ontology.axone.space/thesaurus/topic/Test".to_string(),
-                            ))),
-                            predicate: VarOrNamedNode::Variable("p".to_string()),
-                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                        }],
-                    }
-                    .into(),
-                },
-                expected: StdError::generic_err("Selected variable not found in query").into(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    (0..10)
+                        .map(|i| {
+                            format!(
+                                "<https://ex.org/s{i}> <https://ex.org/p> \"v{i}\" .\n\
+                                 <https://ex.org/s{i}> <https://ex.org/q> <https://ex.org/o{}> .\n",
+                                i % 3
+                            )
+                        })
+                        .collect::<String>()
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
             },
-        ];
-
-        for case in cases {
-            let mut deps = mock_dependencies();
-
-            let info = message_info(&addr(OWNER), &[]);
-            instantiate(
-                deps.as_mut(),
-                mock_env(),
-                info.clone(),
-                InstantiateMsg::default(),
-            )
-            .unwrap();
+        )
+        .unwrap();
 
-            execute(
-                deps.as_mut(),
+        let pattern = |predicate: &str, object: &str| TriplePattern {
+            subject: VarOrNode::Variable("s".to_string()),
+            predicate: VarOrNamedNode::NamedNode(Full(format!("https://ex.org/{predicate}"))),
+            object: VarOrNodeOrLiteral::Variable(object.to_string()),
+        };
+        let select = |patterns: Vec<TriplePattern>, limit: u32, adaptive_joins: bool| {
+            query(
+                deps.as_ref(),
                 mock_env(),
-                info.clone(),
-                InsertData {
-                    format: Some(DataFormat::RDFXml),
-                    data: read_test_data("sample.rdf.xml"),
+                QueryMsg::Select {
+                    query: SelectQuery {
+                        prefixes: vec![],
+                        select: vec![
+                            SelectItem::Variable("s".to_string()),
+                            SelectItem::Variable("o".to_string()),
+                        ],
+                        r#where: WhereClause::Bgp { patterns },
+                        limit: Some(limit),
+                        reasoning: None,
+                        skolemize: false,
+                        adaptive_joins,
+                        ordered: true,
+                        from: vec![],
+                        from_named: vec![],
+                    },
                 },
             )
-            .unwrap();
+            .map(|res| from_json::<SelectResponse>(&res).unwrap().results.bindings)
+        };
 
-            let res = execute(deps.as_mut(), mock_env(), info, case.command);
+        let all = select(vec![pattern("p", "v"), pattern("q", "o")], 30, false).unwrap();
+        assert_eq!(all.len(), 10);
+        assert_eq!(
+            select(vec![pattern("q", "o"), pattern("p", "v")], 30, false),
+            Ok(all.clone())
+        );
+        assert_eq!(
+            select(vec![pattern("q", "o"), pattern("p", "v")], 30, true),
+            Ok(all.clone())
+        );
+        assert_eq!(
+            select(vec![pattern("q", "o"), pattern("p", "v")], 3, false),
+            Ok(all[..3].to_vec())
+        );
 
-            assert!(res.is_err());
-            assert_eq!(res.unwrap_err(), case.expected);
-        }
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<https://ex.org/s10> <https://ex.org/p> \"v10\" .\n\
+                     <https://ex.org/s10> <https://ex.org/q> <https://ex.org/o1> .\n"
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            select(vec![pattern("p", "v"), pattern("q", "o")], 3, false),
+            Err(StdError::generic_err(
+                "Maximum number of solutions of an ordered query exceeded: 10"
+            ))
+        );
     }
 
     #[test]
-    fn proper_store() {
+    fn quoted_triples() {
         let mut deps = mock_dependencies();
-        STORE
-            .save(
-                deps.as_mut().storage,
-                &Store {
-                    owner: Addr::unchecked(OWNER),
-                    limits: StoreLimits {
-                        max_triple_count: 1u128.into(),
-                        max_byte_size: 2u128.into(),
-                        max_triple_byte_size: 3u128.into(),
-                        max_query_limit: 4u32,
-                        max_query_variable_count: 5u32,
-                        max_insert_data_byte_size: 6u128.into(),
-                        max_insert_data_triple_count: 7u128.into(),
-                    },
-                    stat: StoreStat {
-                        triple_count: 1u128.into(),
-                        namespace_count: 2u128.into(),
-                        byte_size: 3u128.into(),
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(
+                    "@prefix ex: <https://ex.org/> .\n\
+                     ex:alice ex:knows ex:bob .\n\
+                     << ex:alice ex:knows ex:bob >> ex:source ex:census .\n\
+                     << ex:bob ex:age 42 >> ex:source << ex:alice ex:says ex:it >> .\n"
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        let ex = |name: &str| Full(format!("https://ex.org/{name}"));
+        let select = |select: Vec<&str>, patterns: Vec<TriplePattern>| {
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Select {
+                    query: SelectQuery {
+                        prefixes: vec![],
+                        select: select
+                            .into_iter()
+                            .map(|v| SelectItem::Variable(v.to_string()))
+                            .collect(),
+                        r#where: WhereClause::Bgp { patterns },
+                        limit: None,
+                        reasoning: None,
+                        skolemize: false,
+                        adaptive_joins: false,
+                        ordered: true,
+                        from: vec![],
+                        from_named: vec![],
                     },
                 },
             )
-            .unwrap();
+            .map(|res| from_json::<SelectResponse>(&res).unwrap().results.bindings)
+        };
+        let quoted = |subject: VarOrNode, predicate: &str, object: VarOrNodeOrLiteral| {
+            Box::new(TriplePattern {
+                subject,
+                predicate: VarOrNamedNode::NamedNode(ex(predicate)),
+                object,
+            })
+        };
+        let uri = |name: &str| Value::URI { value: ex(name) };
 
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::Store {});
-        assert!(res.is_ok());
         assert_eq!(
-            from_json::<StoreResponse>(&res.unwrap()).unwrap(),
-            StoreResponse {
-                owner: OWNER.to_string(),
-                limits: msg::StoreLimits {
-                    max_triple_count: 1u128.into(),
-                    max_byte_size: 2u128.into(),
-                    max_triple_byte_size: 3u128.into(),
-                    max_query_limit: 4u32,
-                    max_query_variable_count: 5u32,
-                    max_insert_data_byte_size: 6u128.into(),
-                    max_insert_data_triple_count: 7u128.into(),
-                },
-                stat: msg::StoreStat {
-                    triple_count: 1u128.into(),
-                    namespace_count: 2u128.into(),
-                    byte_size: 3u128.into(),
-                },
-            }
+            select(
+                vec!["who", "whom", "src"],
+                vec![TriplePattern {
+                    subject: VarOrNode::QuotedTriple(quoted(
+                        VarOrNode::Variable("who".to_string()),
+                        "knows",
+                        VarOrNodeOrLiteral::Variable("whom".to_string()),
+                    )),
+                    predicate: VarOrNamedNode::NamedNode(ex("source")),
+                    object: VarOrNodeOrLiteral::Variable("src".to_string()),
+                }],
+            ),
+            Ok(vec![BTreeMap::from([
+                ("who".to_string(), uri("alice")),
+                ("whom".to_string(), uri("bob")),
+                ("src".to_string(), uri("census")),
+            ])])
+        );
+        assert_eq!(
+            select(
+                vec!["who"],
+                vec![TriplePattern {
+                    subject: VarOrNode::Variable("s".to_string()),
+                    predicate: VarOrNamedNode::NamedNode(ex("source")),
+                    object: VarOrNodeOrLiteral::QuotedTriple(quoted(
+                        VarOrNode::Variable("who".to_string()),
+                        "says",
+                        VarOrNodeOrLiteral::Node(NamedNode(ex("it"))),
+                    )),
+                }],
+            ),
+            Ok(vec![BTreeMap::from([("who".to_string(), uri("alice"))])])
+        );
+        assert_eq!(
+            select(
+                vec!["t"],
+                vec![TriplePattern {
+                    subject: VarOrNode::Variable("t".to_string()),
+                    predicate: VarOrNamedNode::NamedNode(ex("source")),
+                    object: VarOrNodeOrLiteral::Node(NamedNode(ex("census"))),
+                }],
+            ),
+            Ok(vec![BTreeMap::from([(
+                "t".to_string(),
+                Value::Triple {
+                    subject: Box::new(uri("alice")),
+                    predicate: Box::new(uri("knows")),
+                    object: Box::new(uri("bob")),
+                }
+            )])])
+        );
+        assert_eq!(
+            select(
+                vec!["t"],
+                vec![TriplePattern {
+                    subject: VarOrNode::QuotedTriple(quoted(
+                        VarOrNode::Node(NamedNode(ex("bob"))),
+                        "knows",
+                        VarOrNodeOrLiteral::Variable("whom".to_string()),
+                    )),
+                    predicate: VarOrNamedNode::NamedNode(ex("source")),
+                    object: VarOrNodeOrLiteral::Variable("t".to_string()),
+                }],
+            ),
+            Ok(vec![])
         );
+
+        let export = query::export(deps.as_ref(), None, None, DataFormat::NTriples).unwrap();
+        let dump = String::from_utf8(export.data.to_vec()).unwrap();
+        assert!(dump.contains(
+            "<< <https://ex.org/alice> <https://ex.org/knows> <https://ex.org/bob> >> \
+             <https://ex.org/source> <https://ex.org/census> ."
+        ));
+
+        let mut target = mock_dependencies();
+        instantiate(
+            target.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            target.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(dump.as_bytes()),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+        let stats = |deps: Deps<'_>| -> StoreResponse {
+            from_json(query(deps, mock_env(), QueryMsg::Store {}).unwrap()).unwrap()
+        };
+        assert_eq!(stats(target.as_ref()).stat, stats(deps.as_ref()).stat);
     }
 
-    fn read_test_data(file: &str) -> Binary {
-        let mut bytes: Vec<u8> = Vec::new();
+    #[test]
+    fn formats_describe() {
+        let cases = vec![
+            (
+                QueryMsg::Describe {
+                    query: DescribeQuery {
+                        prefixes: vec![],
+                        resource: VarOrNamedNode::NamedNode(Full("https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
+                        r#where: None,
+                    },
+                    format: Some(DataFormat::Turtle),
+                },
+                DescribeResponse {
+                    format: DataFormat::Turtle,
+                    data: Binary::from(
+                        "<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/metadata/dataspace/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;
+\t<https://ontology.axone.space/core/hasTopic> <https://ontology.axone.space/thesaurus/topic/Test> ;
+\t<https://ontology.axone.space/core/hasTag> \"Test\" , \"AXONE\" ;
+\t<https://ontology.axone.space/core/hasPublisher> \"AXONE\" ;
+\t<https://ontology.axone.space/core/hasDescription> \"A test Data Space.\"@en , \"Un Data Space de test.\"@fr ;
+\t<https://ontology.axone.space/core/describes> <https://ontology.axone.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> ;
+\t<https://ontology.axone.space/core/hasTitle> \"Data Space de test\"@fr , \"Test Data Space\"@en .
+\
+                ".to_string().as_bytes().to_vec()),
+                }
+            ),
+            (
+                QueryMsg::Describe {
+                    query: DescribeQuery {
+                        prefixes: vec![],
+                        resource: VarOrNamedNode::NamedNode(Full("https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
+                        r#where: None,
+                    },
+                    format: Some(DataFormat::RDFXml),
+                },
+                DescribeResponse {
+                    format: DataFormat::RDFXml,
+                    data: Binary::from(
+                        "<?xml version=\"1.0\" encoding=\"UTF-8\"?><rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\
+<rdf:Description rdf:about=\"https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473\">\
+<type xmlns=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\" rdf:resource=\"https://ontology.axone.space/metadata/dataspace/GeneralMetadata\"/>\
+<type xmlns=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\" rdf:resource=\"http://www.w3.org/2002/07/owl#NamedIndividual\"/>\
+<hasTopic xmlns=\"https://ontology.axone.space/core/\" rdf:resource=\"https://ontology.axone.space/thesaurus/topic/Test\"/>\
+<hasTag xmlns=\"https://ontology.axone.space/core/\">Test</hasTag><hasTag xmlns=\"https://ontology.axone.space/core/\">AXONE</hasTag>\
+<hasPublisher xmlns=\"https://ontology.axone.space/core/\">AXONE</hasPublisher>\
+<hasDescription xmlns=\"https://ontology.axone.space/core/\" xml:lang=\"en\">A test Data Space.</hasDescription>\
+<hasDescription xmlns=\"https://ontology.axone.space/core/\" xml:lang=\"fr\">Un Data Space de test.</hasDescription>\
+<describes xmlns=\"https://ontology.axone.space/core/\" rdf:resource=\"https://ontology.axone.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33\"/>\
+<hasTitle xmlns=\"https://ontology.axone.space/core/\" xml:lang=\"fr\">Data Space de test</hasTitle>\
+<hasTitle xmlns=\"https://ontology.axone.space/core/\" xml:lang=\"en\">Test Data Space</hasTitle></rdf:Description>\
+</rdf:RDF>\
+\
+                ".to_string().as_bytes().to_vec()),
+                }
+            ),
+            (
+                QueryMsg::Describe {
+                    query: DescribeQuery {
+                        prefixes: vec![],
+                        resource: VarOrNamedNode::NamedNode(Full("https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
+                        r#where: WhereClause::Bgp { patterns: vec![
+                            TriplePattern {
+                                subject: VarOrNode::Variable("a".to_string()),
+                                predicate: VarOrNamedNode::NamedNode(Full(
+                                    "https://ontology.axone.space/core/hasDescription".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("b".to_string()),
+                            },
+                        ]}.into(),
+                    },
+                    format: Some(DataFormat::NTriples),
+                },
+                DescribeResponse {
+                    format: DataFormat::NTriples,
+                    data: Binary::from(
+                        "<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/metadata/dataspace/GeneralMetadata> .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#NamedIndividual> .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTopic> <https://ontology.axone.space/thesaurus/topic/Test> .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTag> \"Test\" .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTag> \"AXONE\" .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasPublisher> \"AXONE\" .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasDescription> \"A test Data Space.\"@en .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasDescription> \"Un Data Space de test.\"@fr .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/describes> <https://ontology.axone.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTitle> \"Data Space de test\"@fr .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTitle> \"Test Data Space\"@en .
+\
+                ".to_string().as_bytes().to_vec()),
+                }
+            ),
+            (
+                QueryMsg::Describe {
+                    query: DescribeQuery {
+                        prefixes: vec![],
+                        resource: VarOrNamedNode::NamedNode(Full("https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
+                        r#where: None,
+                    },
+                    format: Some(DataFormat::NQuads),
+                },
+                DescribeResponse {
+                    format: DataFormat::NQuads,
+                    data: Binary::from(
+                        "<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/metadata/dataspace/GeneralMetadata> .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#NamedIndividual> .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTopic> <https://ontology.axone.space/thesaurus/topic/Test> .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTag> \"Test\" .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTag> \"AXONE\" .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasPublisher> \"AXONE\" .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasDescription> \"A test Data Space.\"@en .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasDescription> \"Un Data Space de test.\"@fr .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/describes> <https://ontology.axone.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTitle> \"Data Space de test\"@fr .
+<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTitle> \"Test Data Space\"@en .
+\
+                ".to_string().as_bytes().to_vec()),
+                }
+            ),
+        ];
+
+        let mut deps = mock_dependencies();
 
-        File::open(
-            Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
-                .join("testdata")
-                .join(file),
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
         )
-        .unwrap()
-        .read_to_end(&mut bytes)
         .unwrap();
 
-        Binary::from(bytes)
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        for (q, expected) in cases {
+            let res = query(deps.as_ref(), mock_env(), q);
+
+            assert!(res.is_ok());
+
+            let result = from_json::<DescribeResponse>(&res.unwrap()).unwrap();
+
+            assert_eq!(result.format, expected.format);
+            assert_eq!(
+                String::from_utf8_lossy(&result.data),
+                String::from_utf8_lossy(&expected.data)
+            );
+        }
     }
 
     #[test]
-    fn proper_select() {
+    fn prefixes_describe() {
         let cases = vec![
             (
-                SelectQuery {
-                    prefixes: vec![],
-                    select: vec![
-                        SelectItem::Variable("a".to_string()),
-                        SelectItem::Variable("b".to_string()),
-                    ],
-                    r#where: WhereClause::Bgp{patterns:vec![TriplePattern {
-                            subject: VarOrNode::Variable("a".to_string()),
-                            predicate: VarOrNamedNode::NamedNode(Full(
-                                "https://ontology.axone.space/core/hasDescription".to_string(),
-                            )),
-                            object: VarOrNodeOrLiteral::Variable("b".to_string()),
-                        },
-                    ]},
-                    limit: None,
-                },
-                SelectResponse {
-                    head: Head {
-                        vars: vec!["a".to_string(), "b".to_string()],
-                    },
-                    results: Results {
-                        bindings: vec![
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())
-                                    }
-                                ),
-                                (
-                                    "b".to_string(),
-                                    Value::Literal {
-                                        value: "A test Data Space.".to_string(),
-                                        lang: Some("en".to_string()),
-                                        datatype: None,
-                                    }
-                                )
-                            ]),
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.axone.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string())
-                                    }
-                                ),
-                                (
-                                    "b".to_string(),
-                                    Value::Literal {
-                                        value: "Un Dataset de test.".to_string(),
-                                        lang: Some("fr".to_string()),
-                                        datatype: None,
-                                    }
-                                )
-                            ]),
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.axone.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string())
-                                    }
-                                ),
-                                (
-                                    "b".to_string(),
-                                    Value::Literal {
-                                        value: "A test Dataset.".to_string(),
-                                        lang: Some("en".to_string()),
-                                        datatype: None,
-                                    }
-                                )
-                            ]),
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())
-                                    }
-                                ),
-                                (
-                                    "b".to_string(),
-                                    Value::Literal {
-                                        value: "Un Data Space de test.".to_string(),
-                                        lang: Some("fr".to_string()),
-                                        datatype: None,
-                                    }
-                                )
-                            ]),
-                        ],
-                    },
-                },
-            ),
-            (
-                SelectQuery {
-                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
-                    select: vec![
-                        SelectItem::Variable("a".to_string()),
-                    ],
-                    r#where: WhereClause::Bgp{patterns:vec![TriplePattern {
-                            subject: VarOrNode::Variable("a".to_string()),
-                            predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                "core:hasDescription".to_string(),
-                            )),
-                            object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString { value: "A test Dataset.".to_string(), language: "en".to_string() }),
-                        },
-                    ]},
-                    limit: None,
-                },
-                SelectResponse {
-                    head: Head {
-                        vars: vec!["a".to_string()],
-                    },
-                    results: Results {
-                        bindings: vec![
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.axone.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string())
-                                    }
-                                ),
-                            ])
+                QueryMsg::Describe {
+                    query: DescribeQuery {
+                        prefixes: vec![
+                            Prefix {
+                                prefix: "metadata".to_string(),
+                                namespace: "https://ontology.axone.space/dataverse/dataspace/metadata/".to_string(),
+                            },
                         ],
+                        resource: VarOrNamedNode::NamedNode(Prefixed("metadata:dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
+                        r#where: None,
                     },
+                    format: Some(DataFormat::Turtle),
                 },
+                DescribeResponse {
+                    format: DataFormat::Turtle,
+                    data: Binary::from(
+                        "<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/metadata/dataspace/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;
+\t<https://ontology.axone.space/core/hasTopic> <https://ontology.axone.space/thesaurus/topic/Test> ;
+\t<https://ontology.axone.space/core/hasTag> \"Test\" , \"AXONE\" ;
+\t<https://ontology.axone.space/core/hasPublisher> \"AXONE\" ;
+\t<https://ontology.axone.space/core/hasDescription> \"A test Data Space.\"@en , \"Un Data Space de test.\"@fr ;
+\t<https://ontology.axone.space/core/describes> <https://ontology.axone.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> ;
+\t<https://ontology.axone.space/core/hasTitle> \"Data Space de test\"@fr , \"Test Data Space\"@en .
+\
+                ".to_string().as_bytes().to_vec()),
+                }
             ),
-            (
-                SelectQuery {
-                    prefixes: vec![],
-                    select: vec![
-                        SelectItem::Variable("a".to_string()),
-                    ],
-                    r#where: WhereClause::Bgp{patterns:vec![TriplePattern {
-                            subject: VarOrNode::Node(NamedNode(Full("https://ontology.axone.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string()))),
-                            predicate: VarOrNamedNode::Variable("a".to_string()),
-                            object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString { value: "A test Dataset.".to_string(), language: "en".to_string() }),
-                        },
-                    ]},
-                    limit: None,
-                },
-                SelectResponse {
-                    head: Head {
-                        vars: vec!["a".to_string()],
-                    },
-                    results: Results {
-                        bindings: vec![
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.axone.space/core/hasDescription".to_string())
-                                    }
-                                ),
-                            ])
-                        ],
+        ];
+
+        let mut deps = mock_dependencies();
+
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        for (q, expected) in cases {
+            let res = query(deps.as_ref(), mock_env(), q);
+
+            assert!(res.is_ok());
+
+            let result = from_json::<DescribeResponse>(&res.unwrap()).unwrap();
+
+            assert_eq!(result.format, expected.format);
+            assert_eq!(
+                String::from_utf8_lossy(&result.data),
+                String::from_utf8_lossy(&expected.data)
+            );
+        }
+    }
+
+    #[test]
+    fn variable_describe() {
+        let cases = vec![
+            (
+                QueryMsg::Describe {
+                    query: DescribeQuery {
+                        prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
+                        resource: VarOrNamedNode::Variable("a".to_string()),
+                        r#where: WhereClause::Bgp {patterns: vec![
+                            TriplePattern {
+                                subject: VarOrNode::Variable("a".to_string()),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                    "core:hasDescription".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString { value: "A test Dataset.".to_string(), language: "en".to_string() }),
+                            },
+                        ]}.into(),
                     },
+                    format: Some(DataFormat::Turtle),
                 },
+                DescribeResponse {
+                    format: DataFormat::Turtle,
+                    data: Binary::from(
+                        "<https://ontology.axone.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e> <https://ontology.axone.space/core/hasLicense> <https://ontology.axone.space/thesaurus/license/LO-FR-1_0> ;\n\t<http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/metadata/dataset/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;\n\t<https://ontology.axone.space/core/hasTopic> <https://ontology.axone.space/thesaurus/topic/Test> ;\n\t<https://ontology.axone.space/core/hasTag> \"test\" ;\n\t<https://ontology.axone.space/core/hasFormat> <https://ontology.axone.space/thesaurus/media-type/application_vndms-excel> ;\n\t<https://ontology.axone.space/core/hasPublisher> \"AXONE\" ;\n\t<https://ontology.axone.space/core/hasDescription> \"Un Dataset de test.\"@fr , \"A test Dataset.\"@en ;\n\t<https://ontology.axone.space/core/describes> <https://ontology.axone.space/dataverse/dataset/0ea1fc7a-dd97-4adc-a10e-169c6597bcde> ;\n\t<https://ontology.axone.space/core/hasCreator> \"Me\" ;\n\t<https://ontology.axone.space/core/hasTitle> \"test Dataset\"@en , \"Dataset de test\"@fr .\n".to_string().as_bytes().to_vec()),
+                }
             ),
         ];
 
@@ -1484,196 +9801,126 @@ mod tests {
             InsertData {
                 format: Some(DataFormat::RDFXml),
                 data: read_test_data("sample.rdf.xml"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
             },
         )
         .unwrap();
 
         for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), QueryMsg::Select { query: q });
+            let res = query(deps.as_ref(), mock_env(), q);
+
             assert!(res.is_ok());
 
-            let result = from_json::<SelectResponse>(&res.unwrap()).unwrap();
-            assert_eq!(result, expected);
+            let result = from_json::<DescribeResponse>(&res.unwrap()).unwrap();
+
+            assert_eq!(result.format, expected.format);
+            assert_eq!(
+                String::from_utf8_lossy(&result.data),
+                String::from_utf8_lossy(&expected.data)
+            );
         }
     }
 
     #[test]
-    fn proper_select_blank_nodes() {
+    fn variable_multiple_resources_describe() {
         let cases = vec![
             (
-                SelectQuery {
-                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
-                    select: vec![SelectItem::Variable("a".to_string()), SelectItem::Variable("b".to_string())],
-                    r#where: WhereClause::Bgp{patterns:vec![
-                        TriplePattern {
-                                subject: VarOrNode::Variable("a".to_string()),
-                                predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                    "core:hasTemporalCoverage".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Node(BlankNode("a".to_string())),
-                            },
-                        TriplePattern {
-                                subject: VarOrNode::Node(BlankNode("a".to_string())),
-                                predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                    "core:hasStartDate".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Variable("b".to_string()),
-                            },
-                        ]},
-                    limit: None,
-                },
-                SelectResponse {
-                    head: Head { vars: vec!["a".to_string(), "b".to_string()] },
-                    results: Results {
-                        bindings: vec![
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.axone.space/dataverse/dataset/metadata/80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string())
-                                    }
-                                ),
-                                (
-                                    "b".to_string(),
-                                    Value::Literal {
-                                        value: "2022-01-01T00:00:00+00:00".to_string(),
-                                        lang: None,
-                                        datatype: Some(Full("http://www.w3.org/2001/XMLSchema#dateTime".to_string())),
-                                    }
-                                )
-                            ])
-                        ],
-                    },
-                },
-            ),
-            (
-                SelectQuery {
-                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
-                    select: vec![SelectItem::Variable("a".to_string()), SelectItem::Variable("b".to_string())],
-                    r#where: WhereClause::Bgp{patterns:vec![
-                        TriplePattern {
-                                subject: VarOrNode::Variable("a".to_string()),
-                                predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                    "core:hasTemporalCoverage".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Variable("blank".to_string()),
-                            },
-                        TriplePattern {
-                                subject: VarOrNode::Variable("blank".to_string()),
-                                predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                    "core:hasStartDate".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Variable("b".to_string()),
-                            }
-                    ]},
-                    limit: None,
-                },
-                SelectResponse {
-                    head: Head { vars: vec!["a".to_string(), "b".to_string()] },
-                    results: Results {
-                        bindings: vec![
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.axone.space/dataverse/dataset/metadata/80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string())
-                                    }
-                                ),
-                                (
-                                    "b".to_string(),
-                                    Value::Literal {
-                                        value: "2022-01-01T00:00:00+00:00".to_string(),
-                                        lang: None,
-                                        datatype: Some(Full("http://www.w3.org/2001/XMLSchema#dateTime".to_string())),
-                                    }
-                                )
-                            ])
-                        ],
-                    },
-                },
-            ),
-            (
-                SelectQuery {
-                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
-                    select: vec![SelectItem::Variable("a".to_string()), SelectItem::Variable("b".to_string())],
-                    r#where: WhereClause::Bgp{patterns:vec![
-                        TriplePattern {
+                QueryMsg::Describe {
+                    query: DescribeQuery {
+                        prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
+                        resource: VarOrNamedNode::Variable("a".to_string()),
+                        r#where: WhereClause::Bgp {patterns: vec![
+                            TriplePattern {
                                 subject: VarOrNode::Variable("a".to_string()),
                                 predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                    "core:hasTemporalCoverage".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Node(BlankNode("blank1".to_string())),
-                            },
-                        TriplePattern {
-                                subject: VarOrNode::Node(BlankNode("blank2".to_string())),
-                                predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                    "core:hasInformation".to_string(),
+                                    "core:hasPublisher".to_string(),
                                 )),
-                                object: VarOrNodeOrLiteral::Variable("b".to_string()),
+                                object: VarOrNodeOrLiteral::Literal(Literal::Simple("AXONE".to_string())),
                             },
-                    ]},
-                    limit: None,
-                },
-                SelectResponse {
-                    head: Head { vars: vec!["a".to_string(), "b".to_string()] },
-                    results: Results {
-                        bindings: vec![
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.axone.space/dataverse/dataset/metadata/80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string())
-                                    }
-                                ),
-                                (
-                                    "b".to_string(),
-                                    Value::Literal {
-                                        value: "this is a dataset".to_string(),
-                                        lang: None,
-                                        datatype: None,
-                                    }
-                                )
-                            ])
-                        ],
+                        ]}.into(),
                     },
+                    format: Some(DataFormat::Turtle),
                 },
+                DescribeResponse {
+                    format: DataFormat::Turtle,
+                    data: Binary::from(
+                        "<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/metadata/dataspace/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;\n\t<https://ontology.axone.space/core/hasTopic> <https://ontology.axone.space/thesaurus/topic/Test> ;\n\t<https://ontology.axone.space/core/hasTag> \"Test\" , \"AXONE\" ;\n\t<https://ontology.axone.space/core/hasPublisher> \"AXONE\" ;\n\t<https://ontology.axone.space/core/hasDescription> \"A test Data Space.\"@en , \"Un Data Space de test.\"@fr ;\n\t<https://ontology.axone.space/core/describes> <https://ontology.axone.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> ;\n\t<https://ontology.axone.space/core/hasTitle> \"Data Space de test\"@fr , \"Test Data Space\"@en .\n<https://ontology.axone.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e> <https://ontology.axone.space/core/hasLicense> <https://ontology.axone.space/thesaurus/license/LO-FR-1_0> ;\n\t<http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/metadata/dataset/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;\n\t<https://ontology.axone.space/core/hasTopic> <https://ontology.axone.space/thesaurus/topic/Test> ;\n\t<https://ontology.axone.space/core/hasTag> \"test\" ;\n\t<https://ontology.axone.space/core/hasFormat> <https://ontology.axone.space/thesaurus/media-type/application_vndms-excel> ;\n\t<https://ontology.axone.space/core/hasPublisher> \"AXONE\" ;\n\t<https://ontology.axone.space/core/hasDescription> \"Un Dataset de test.\"@fr , \"A test Dataset.\"@en ;\n\t<https://ontology.axone.space/core/describes> <https://ontology.axone.space/dataverse/dataset/0ea1fc7a-dd97-4adc-a10e-169c6597bcde> ;\n\t<https://ontology.axone.space/core/hasCreator> \"Me\" ;\n\t<https://ontology.axone.space/core/hasTitle> \"test Dataset\"@en , \"Dataset de test\"@fr .\n".to_string().as_bytes().to_vec()),
+                }
             ),
+        ];
+
+        let mut deps = mock_dependencies();
+
+        let info = message_info(&addr(OWNER), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            InsertData {
+                format: Some(DataFormat::RDFXml),
+                data: read_test_data("sample.rdf.xml"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+
+        for (q, expected) in cases {
+            let res = query(deps.as_ref(), mock_env(), q);
+
+            assert!(res.is_ok());
+
+            let result = from_json::<DescribeResponse>(&res.unwrap()).unwrap();
+
+            assert_eq!(result.format, expected.format);
+            assert_eq!(
+                String::from_utf8_lossy(&result.data),
+                String::from_utf8_lossy(&expected.data)
+            );
+        }
+    }
+
+    #[test]
+    fn blanknode_describe() {
+        let cases = vec![
             (
-                SelectQuery {
-                    prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
-                    select: vec![SelectItem::Variable("a".to_string()), SelectItem::Variable("b".to_string())],
-                    r#where: WhereClause::Bgp{patterns:vec![
-                        TriplePattern {
-                                subject: VarOrNode::Variable("a".to_string()),
-                                predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                    "core:hasTemporalCoverage".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Variable("b".to_string()),
-                            },
-                        ]},
-                    limit: None,
-                },
-                SelectResponse {
-                    head: Head { vars: vec!["a".to_string(), "b".to_string()] },
-                    results: Results {
-                        bindings: vec![
-                            BTreeMap::from([
-                                (
-                                    "a".to_string(),
-                                    Value::URI {
-                                        value: Full("https://ontology.axone.space/dataverse/dataset/metadata/80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string())
-                                    }
-                                ),
-                                (
-                                    "b".to_string(),
-                                    Value::BlankNode {
-                                        value: "b0".to_string(),
-                                    }
-                                )
-                            ])
+                QueryMsg::Describe {
+                    query: DescribeQuery {
+                        prefixes: vec![
+                            Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() },
+                            Prefix { prefix: "metadata-dataset".to_string(), namespace: "https://ontology.axone.space/dataverse/dataset/metadata/".to_string() },
                         ],
+                        resource: VarOrNamedNode::Variable("x".to_string()),
+                        r#where: WhereClause::Bgp {patterns: vec![
+                            TriplePattern {
+                                subject: VarOrNode::Node(NamedNode(Prefixed("metadata-dataset:80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string()))),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                    "core:hasTemporalCoverage".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("x".to_string()),
+                            },
+                        ]}.into(),
                     },
+                    format: Some(DataFormat::Turtle),
                 },
+                DescribeResponse {
+                    format: DataFormat::Turtle,
+                    data: Binary::from(
+                        "<b0> <https://ontology.axone.space/core/hasStartDate> \"2022-01-01T00:00:00Z\"^^<http://www.w3.org/2001/XMLSchema#dateTime> ;\n\t<http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#NamedIndividual> , <https://ontology.axone.space/core/Period> .\n".to_string().as_bytes().to_vec()),
+                }
             ),
         ];
 
@@ -1695,92 +9942,277 @@ mod tests {
             InsertData {
                 format: Some(DataFormat::Turtle),
                 data: read_test_data("blank-nodes.ttl"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
             },
         )
         .unwrap();
 
         for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), QueryMsg::Select { query: q });
+            let res = query(deps.as_ref(), mock_env(), q);
+
             assert!(res.is_ok());
 
-            let result = from_json::<SelectResponse>(&res.unwrap()).unwrap();
-            assert_eq!(result, expected);
+            let result = from_json::<DescribeResponse>(&res.unwrap()).unwrap();
+
+            assert_eq!(result.format, expected.format);
+            assert_eq!(
+                String::from_utf8_lossy(&result.data),
+                String::from_utf8_lossy(&expected.data)
+            );
         }
     }
 
     #[test]
-    fn invalid_select() {
+    fn proper_construct() {
+        let id = "https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473";
         let cases = vec![
             (
-                SelectQuery {
-                    prefixes: vec![],
-                    select: vec![
-                        SelectItem::Variable("a".to_string()),
-                        SelectItem::Variable("b".to_string()),
-                    ],
-                    r#where: WhereClause::Bgp { patterns: vec![] },
-                    limit: None,
+                InsertData {
+                    format: Some(DataFormat::RDFXml),
+                    data: read_test_data("sample.rdf.xml"),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
                 },
-                Err(StdError::generic_err(
-                    "Maximum query variable count exceeded",
-                )),
-            ),
-            (
-                SelectQuery {
-                    prefixes: vec![],
-                    select: vec![],
-                    r#where: WhereClause::Bgp { patterns: vec![] },
-                    limit: Some(8000),
+                QueryMsg::Construct {
+                    query: ConstructQuery {
+                        prefixes: vec![],
+                        construct: vec![],
+                        r#where: WhereClause::Bgp{patterns:vec![TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                            predicate: VarOrNamedNode::NamedNode(Full(
+                                "https://ontology.axone.space/core/hasTag".to_string(),
+                            )),
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        }]},
+                        fresh_blank_nodes: false,
+                    },
+                    format: None,
+                    cursor: None,
+                },
+                ConstructResponse {
+                    format: DataFormat::Turtle,
+                    data: Binary::from(
+                        "<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTag> \"Test\" , \"AXONE\" .\n".to_string().as_bytes().to_vec()),
+                    cursor: None,
                 },
-                Err(StdError::generic_err("Maximum query limit exceeded")),
             ),
             (
-                SelectQuery {
-                    prefixes: vec![Prefix {
-                        prefix: "core".to_string(),
-                        namespace: "https://ontology.axone.space/core/".to_string(),
-                    }],
-                    select: vec![SelectItem::Variable("a".to_string())],
-                    r#where: WhereClause::Bgp {
-                        patterns: vec![TriplePattern {
-                            subject: VarOrNode::Variable("a".to_string()),
-                            predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                "invalid:hasDescription".to_string(),
+                InsertData {
+                    format: Some(DataFormat::RDFXml),
+                    data: read_test_data("sample.rdf.xml"),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+                QueryMsg::Construct {
+                    query: ConstructQuery {
+                        prefixes: vec![
+                            Prefix { prefix: "my-ns".to_string(), namespace: "https://my-ns.org/".to_string() },
+                            Prefix { prefix: "metadata-dataset".to_string(), namespace: "https://ontology.axone.space/dataverse/dataset/metadata/".to_string() },
+                        ],
+                        construct: vec![
+                            msg::TripleConstructTemplate {
+                                subject: VarOrNode::Node(NamedNode(Prefixed("my-ns:instance-1".to_string()))),
+                                predicate: VarOrNamedNode::NamedNode(Full(
+                                    "https://my-ns/predicate/tag".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                            }
+                        ],
+                        r#where: WhereClause::Bgp{patterns:vec![TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
+                            predicate: VarOrNamedNode::NamedNode(Full(
+                                "https://ontology.axone.space/core/hasTag".to_string(),
                             )),
-                            object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString {
-                                value: "A test Dataset.".to_string(),
-                                language: "en".to_string(),
-                            }),
-                        }],
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        }]},
+                        fresh_blank_nodes: false,
                     },
-                    limit: None,
+                    format: Some(DataFormat::NTriples),
+                    cursor: None,
+                },
+                ConstructResponse {
+                    format: DataFormat::NTriples,
+                    data: Binary::from(
+                        "<https://my-ns.org/instance-1> <https://my-ns/predicate/tag> \"Test\" .\n<https://my-ns.org/instance-1> <https://my-ns/predicate/tag> \"AXONE\" .\n".to_string().as_bytes().to_vec()),
+                    cursor: None,
                 },
-                Err(StdError::generic_err("Prefix not found: invalid")),
             ),
             (
-                SelectQuery {
-                    prefixes: vec![],
-                    select: vec![SelectItem::Variable("u".to_string())],
-                    r#where: WhereClause::Bgp {
-                        patterns: vec![TriplePattern {
-                            subject: VarOrNode::Variable("a".to_string()),
+                InsertData {
+                    format: Some(DataFormat::RDFXml),
+                    data: read_test_data("sample.rdf.xml"),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+                QueryMsg::Construct {
+                    query: ConstructQuery {
+                        prefixes: vec![
+                            Prefix { prefix: "my-ns".to_string(), namespace: "https://my-ns.org/".to_string() },
+                        ],
+                        construct: vec![
+                            msg::TripleConstructTemplate {
+                                subject: VarOrNode::Node(NamedNode(Prefixed("my-ns:instance-1".to_string()))),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed("my-ns:entry".to_string())),
+                                object: VarOrNodeOrLiteral::Node(BlankNode("entry".to_string())),
+                            },
+                            msg::TripleConstructTemplate {
+                                subject: VarOrNode::Node(BlankNode("entry".to_string())),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed("my-ns:tag".to_string())),
+                                object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                            },
+                            msg::TripleConstructTemplate {
+                                subject: VarOrNode::Node(BlankNode("entry".to_string())),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed("my-ns:kind".to_string())),
+                                object: VarOrNodeOrLiteral::Literal(Literal::Simple("tag".to_string())),
+                            },
+                        ],
+                        r#where: WhereClause::Bgp{patterns:vec![TriplePattern {
+                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
                             predicate: VarOrNamedNode::NamedNode(Full(
-                                "https://ontology.axone.space/core/hasDescription".to_string(),
+                                "https://ontology.axone.space/core/hasTag".to_string(),
                             )),
-                            object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString {
-                                value: "A test Dataset.".to_string(),
-                                language: "en".to_string(),
-                            }),
-                        }],
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        }]},
+                        fresh_blank_nodes: true,
                     },
-                    limit: None,
+                    format: Some(DataFormat::NTriples),
+                    cursor: None,
+                },
+                ConstructResponse {
+                    format: DataFormat::NTriples,
+                    data: Binary::from(
+                        "<https://my-ns.org/instance-1> <https://my-ns.org/entry> <a0_1> .\n<a0_1> <https://my-ns.org/tag> \"Test\" .\n<a0_1> <https://my-ns.org/kind> \"tag\" .\n<https://my-ns.org/instance-1> <https://my-ns.org/entry> <a0_2> .\n<a0_2> <https://my-ns.org/tag> \"AXONE\" .\n<a0_2> <https://my-ns.org/kind> \"tag\" .\n".to_string().as_bytes().to_vec()),
+                    cursor: None,
+                },
+            ),
+            (
+                InsertData {
+                    format: Some(DataFormat::Turtle),
+                    data: read_test_data("blank-nodes.ttl"),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+                QueryMsg::Construct {
+                    query: ConstructQuery {
+                        prefixes: vec![
+                            Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() },
+                            Prefix { prefix: "metadata-dataset".to_string(), namespace: "https://ontology.axone.space/dataverse/dataset/metadata/".to_string() },
+                        ],
+                        construct: vec![
+                            msg::TripleConstructTemplate {
+                                subject: VarOrNode::Node(BlankNode("my-metadata".to_string())),
+                                predicate: VarOrNamedNode::NamedNode(Full(
+                                    "https://my-ns/predicate/tcov".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("tcov".to_string()),
+                            },
+                            msg::TripleConstructTemplate {
+                                subject: VarOrNode::Node(BlankNode("my-metadata".to_string())),
+                                predicate: VarOrNamedNode::NamedNode(Full(
+                                    "https://my-ns/predicate/info".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("info".to_string()),
+                            },
+                            msg::TripleConstructTemplate {
+                                subject: VarOrNode::Variable("tcov".to_string()),
+                                predicate: VarOrNamedNode::Variable("tcov_p".to_string()),
+                                object: VarOrNodeOrLiteral::Variable("tcov_o".to_string()),
+                            },
+                            msg::TripleConstructTemplate {
+                                subject: VarOrNode::Variable("info".to_string()),
+                                predicate: VarOrNamedNode::Variable("info_p".to_string()),
+                                object: VarOrNodeOrLiteral::Variable("info_o".to_string()),
+                            }
+                        ],
+                        r#where: WhereClause::Bgp {patterns:vec![
+                            TriplePattern {
+                                subject: VarOrNode::Node(NamedNode(Prefixed("metadata-dataset:80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string()))),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                    "core:hasTemporalCoverage".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("tcov".to_string()),
+                            },
+                            TriplePattern {
+                                subject: VarOrNode::Node(NamedNode(Prefixed("metadata-dataset:80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string()))),
+                                predicate: VarOrNamedNode::NamedNode(Prefixed(
+                                    "core:hasInformations".to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("info".to_string()),
+                            },
+                            TriplePattern {
+                                subject: VarOrNode::Variable("tcov".to_string()),
+                                predicate: VarOrNamedNode::Variable("tcov_p".to_string()),
+                                object: VarOrNodeOrLiteral::Variable("tcov_o".to_string()),
+                            },
+                            TriplePattern {
+                                subject: VarOrNode::Variable("info".to_string()),
+                                predicate: VarOrNamedNode::Variable("info_p".to_string()),
+                                object: VarOrNodeOrLiteral::Variable("info_o".to_string()),
+                            }
+                        ]},
+                        fresh_blank_nodes: false,
+                    },
+                    format: Some(DataFormat::NTriples),
+                    cursor: None,
+                },
+                ConstructResponse {
+                    format: DataFormat::NTriples,
+                    data: Binary::from(
+                        "<a0> <https://my-ns/predicate/tcov> <b0> .\n<a0> <https://my-ns/predicate/info> <b1> .\n<b0> <https://ontology.axone.space/core/hasStartDate> \"2022-01-01T00:00:00Z\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n<b1> <https://ontology.axone.space/core/hasInformation> \"this is a dataset\" .\n<a0> <https://my-ns/predicate/tcov> <b0> .\n<a0> <https://my-ns/predicate/info> <b1> .\n<b0> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#NamedIndividual> .\n<b1> <https://ontology.axone.space/core/hasInformation> \"this is a dataset\" .\n<a0> <https://my-ns/predicate/tcov> <b0> .\n<a0> <https://my-ns/predicate/info> <b1> .\n<b0> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/core/Period> .\n<b1> <https://ontology.axone.space/core/hasInformation> \"this is a dataset\" .\n".to_string().as_bytes().to_vec()),
+                    cursor: None,
                 },
-                Err(StdError::generic_err(
-                    "Selected variable not found in query",
-                )),
             ),
         ];
 
+        for (data, q, expected) in cases {
+            let mut deps = mock_dependencies();
+
+            let info = message_info(&addr(OWNER), &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    limits: StoreLimitsInput::default(),
+                    recent_window: None,
+                    query_audit: None,
+                    conflict_policy: Default::default(),
+                    literal_offload: None,
+                    disable_literal_normalization: false,
+                    soft_limit: None,
+                },
+            )
+            .unwrap();
+
+            execute(deps.as_mut(), mock_env(), info.clone(), data).unwrap();
+
+            let res = query(deps.as_ref(), mock_env(), q);
+
+            assert!(res.is_ok());
+
+            let result = from_json::<DescribeResponse>(&res.unwrap()).unwrap();
+
+            assert_eq!(result.format, expected.format);
+            assert_eq!(
+                String::from_utf8_lossy(&result.data),
+                String::from_utf8_lossy(&expected.data)
+            );
+        }
+    }
+
+    #[test]
+    fn proper_recent_changes() {
         let mut deps = mock_dependencies();
 
         let info = message_info(&addr(OWNER), &[]);
@@ -1789,153 +10221,102 @@ mod tests {
             mock_env(),
             info.clone(),
             InstantiateMsg {
-                limits: StoreLimitsInput {
-                    max_query_variable_count: 1,
-                    ..Default::default()
-                },
-            },
-        )
-        .unwrap();
-
-        execute(
-            deps.as_mut(),
-            mock_env(),
-            info,
-            InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
+                limits: StoreLimitsInput::default(),
+                recent_window: Some(2),
+                query_audit: None,
+                conflict_policy: Default::default(),
+                literal_offload: None,
+                disable_literal_normalization: false,
+                soft_limit: None,
             },
         )
         .unwrap();
 
-        for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), QueryMsg::Select { query: q });
-            assert_eq!(res, expected);
+        for data in [
+            "<https://ex.org/a> <https://ex.org/p> \"1\" .\n",
+            "<https://ex.org/a> <https://ex.org/p> \"1\" .\n<https://ex.org/b> <https://ex.org/p> _:x .\n",
+            "<https://ex.org/c> <https://ex.org/p> <https://ex.org/a> .\n",
+        ] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(data.as_bytes()),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+            )
+            .unwrap();
         }
-    }
 
-    #[test]
-    fn formats_describe() {
         let cases = vec![
             (
-                QueryMsg::Describe {
-                    query: DescribeQuery {
-                        prefixes: vec![],
-                        resource: VarOrNamedNode::NamedNode(Full("https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
-                        r#where: None,
-                    },
-                    format: Some(DataFormat::Turtle),
-                },
-                DescribeResponse {
-                    format: DataFormat::Turtle,
-                    data: Binary::from(
-                        "<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/metadata/dataspace/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;
-\t<https://ontology.axone.space/core/hasTag> \"Test\" , \"AXONE\" ;
-\t<https://ontology.axone.space/core/hasTitle> \"Data Space de test\"@fr , \"Test Data Space\"@en ;
-\t<https://ontology.axone.space/core/hasTopic> <https://ontology.axone.space/thesaurus/topic/Test> ;
-\t<https://ontology.axone.space/core/describes> <https://ontology.axone.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> ;
-\t<https://ontology.axone.space/core/hasPublisher> \"AXONE\" ;
-\t<https://ontology.axone.space/core/hasDescription> \"A test Data Space.\"@en , \"Un Data Space de test.\"@fr .
-\
-                ".to_string().as_bytes().to_vec()),
-                }
-            ),
-            (
-                QueryMsg::Describe {
-                    query: DescribeQuery {
-                        prefixes: vec![],
-                        resource: VarOrNamedNode::NamedNode(Full("https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
-                        r#where: None,
-                    },
-                    format: Some(DataFormat::RDFXml),
-                },
-                DescribeResponse {
-                    format: DataFormat::RDFXml,
+                1,
+                Ok(RecentChangesResponse {
+                    first_batch: 3,
+                    last_batch: 3,
+                    format: DataFormat::NTriples,
                     data: Binary::from(
-                        "<?xml version=\"1.0\" encoding=\"UTF-8\"?><rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\
-<rdf:Description rdf:about=\"https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473\">\
-<type xmlns=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\" rdf:resource=\"https://ontology.axone.space/metadata/dataspace/GeneralMetadata\"/>\
-<type xmlns=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\" rdf:resource=\"http://www.w3.org/2002/07/owl#NamedIndividual\"/>\
-<hasTag xmlns=\"https://ontology.axone.space/core/\">Test</hasTag><hasTag xmlns=\"https://ontology.axone.space/core/\">AXONE</hasTag>\
-<hasTitle xmlns=\"https://ontology.axone.space/core/\" xml:lang=\"fr\">Data Space de test</hasTitle>\
-<hasTitle xmlns=\"https://ontology.axone.space/core/\" xml:lang=\"en\">Test Data Space</hasTitle>\
-<hasTopic xmlns=\"https://ontology.axone.space/core/\" rdf:resource=\"https://ontology.axone.space/thesaurus/topic/Test\"/>\
-<describes xmlns=\"https://ontology.axone.space/core/\" rdf:resource=\"https://ontology.axone.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33\"/>\
-<hasPublisher xmlns=\"https://ontology.axone.space/core/\">AXONE</hasPublisher>\
-<hasDescription xmlns=\"https://ontology.axone.space/core/\" xml:lang=\"en\">A test Data Space.</hasDescription>\
-<hasDescription xmlns=\"https://ontology.axone.space/core/\" xml:lang=\"fr\">Un Data Space de test.</hasDescription></rdf:Description>\
-</rdf:RDF>\
-\
-                ".to_string().as_bytes().to_vec()),
-                }
+                        "<https://ex.org/c> <https://ex.org/p> <https://ex.org/a> .\n".as_bytes(),
+                    ),
+                }),
             ),
             (
-                QueryMsg::Describe {
-                    query: DescribeQuery {
-                        prefixes: vec![],
-                        resource: VarOrNamedNode::NamedNode(Full("https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
-                        r#where: WhereClause::Bgp { patterns: vec![
-                            TriplePattern {
-                                subject: VarOrNode::Variable("a".to_string()),
-                                predicate: VarOrNamedNode::NamedNode(Full(
-                                    "https://ontology.axone.space/core/hasDescription".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Variable("b".to_string()),
-                            },
-                        ]}.into(),
-                    },
-                    format: Some(DataFormat::NTriples),
-                },
-                DescribeResponse {
+                2,
+                Ok(RecentChangesResponse {
+                    first_batch: 2,
+                    last_batch: 3,
                     format: DataFormat::NTriples,
                     data: Binary::from(
-                        "<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/metadata/dataspace/GeneralMetadata> .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#NamedIndividual> .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTag> \"Test\" .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTag> \"AXONE\" .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTitle> \"Data Space de test\"@fr .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTitle> \"Test Data Space\"@en .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTopic> <https://ontology.axone.space/thesaurus/topic/Test> .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/describes> <https://ontology.axone.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasPublisher> \"AXONE\" .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasDescription> \"A test Data Space.\"@en .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasDescription> \"Un Data Space de test.\"@fr .
-\
-                ".to_string().as_bytes().to_vec()),
-                }
+                        "<https://ex.org/b> <https://ex.org/p> <b0> .\n<https://ex.org/c> <https://ex.org/p> <https://ex.org/a> .\n".as_bytes(),
+                    ),
+                }),
             ),
             (
-                QueryMsg::Describe {
-                    query: DescribeQuery {
-                        prefixes: vec![],
-                        resource: VarOrNamedNode::NamedNode(Full("https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
-                        r#where: None,
-                    },
-                    format: Some(DataFormat::NQuads),
-                },
-                DescribeResponse {
-                    format: DataFormat::NQuads,
-                    data: Binary::from(
-                        "<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/metadata/dataspace/GeneralMetadata> .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#NamedIndividual> .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTag> \"Test\" .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTag> \"AXONE\" .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTitle> \"Data Space de test\"@fr .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTitle> \"Test Data Space\"@en .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTopic> <https://ontology.axone.space/thesaurus/topic/Test> .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/describes> <https://ontology.axone.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasPublisher> \"AXONE\" .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasDescription> \"A test Data Space.\"@en .
-<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasDescription> \"Un Data Space de test.\"@fr .
-\
-                ".to_string().as_bytes().to_vec()),
-                }
+                3,
+                Err(StdError::generic_err(
+                    "Requested batches exceed the recent window size: 2",
+                )),
             ),
         ];
 
-        let mut deps = mock_dependencies();
+        for (batches, expected) in cases {
+            let res = query::recent_changes(deps.as_ref(), batches, DataFormat::NTriples);
+            assert_eq!(res, expected);
+        }
 
-        let info = message_info(&addr(OWNER), &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![],
+                r#where: Some(WhereClause::Bgp {
+                    patterns: vec![TriplePattern {
+                        subject: VarOrNode::Node(NamedNode(Full("https://ex.org/c".to_string()))),
+                        predicate: VarOrNamedNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                }),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            query::recent_changes(deps.as_ref(), 2, DataFormat::NTriples),
+            Ok(RecentChangesResponse {
+                first_batch: 2,
+                last_batch: 3,
+                format: DataFormat::NTriples,
+                data: Binary::from("<https://ex.org/b> <https://ex.org/p> <b0> .\n".as_bytes()),
+            })
+        );
+
+        let mut deps = mock_dependencies();
         instantiate(
             deps.as_mut(),
             mock_env(),
@@ -1943,198 +10324,327 @@ mod tests {
             InstantiateMsg::default(),
         )
         .unwrap();
+        assert_eq!(
+            query::recent_changes(deps.as_ref(), 1, DataFormat::NTriples),
+            Err(StdError::generic_err("Recent window index not enabled"))
+        );
+    }
 
+    #[test]
+    fn proper_export() {
+        let info = message_info(&addr(OWNER), &[]);
+        let mut source = mock_dependencies();
+        instantiate(
+            source.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
         execute(
-            deps.as_mut(),
+            source.as_mut(),
             mock_env(),
-            info,
+            info.clone(),
             InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
+                format: Some(DataFormat::Turtle),
+                data: read_test_data("blank-nodes.ttl"),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
             },
         )
         .unwrap();
+        execute(
+            source.as_mut(),
+            mock_env(),
+            info.clone(),
+            InsertData {
+                format: Some(DataFormat::NTriples),
+                data: Binary::from("<https://ex.org/s> <https://ex.org/p> \"o\" .\n".as_bytes()),
+                compression: None,
+                expires: None,
+                graph: Some("https://ex.org/g".to_string()),
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+        let count = triples()
+            .range_raw(&source.storage, None, None, Order::Ascending)
+            .count();
 
-        for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), q);
-
-            assert!(res.is_ok());
-
-            let result = from_json::<DescribeResponse>(&res.unwrap()).unwrap();
+        assert_eq!(
+            query::export(source.as_ref(), None, Some(31), DataFormat::NQuads),
+            Err(StdError::generic_err(
+                StoreError::QueryLimit(30).to_string()
+            ))
+        );
 
-            assert_eq!(result.format, expected.format);
-            assert_eq!(
-                String::from_utf8_lossy(&result.data),
-                String::from_utf8_lossy(&expected.data)
-            );
+        let mut dump = vec![];
+        let mut cursor = None;
+        let mut pages = 0;
+        loop {
+            let res: ExportResponse = from_json(
+                query(
+                    source.as_ref(),
+                    mock_env(),
+                    QueryMsg::Export {
+                        cursor,
+                        limit: Some(2),
+                        format: None,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(res.format, DataFormat::NQuads);
+            dump.extend(res.data.to_vec());
+            pages += 1;
+            cursor = res.cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(pages, count.div_ceil(2));
+        let dump = String::from_utf8(dump).unwrap();
+        assert_eq!(dump.lines().count(), count);
+        assert!(dump.contains("_:b"));
+        assert!(dump.contains("<https://ex.org/s> <https://ex.org/p> \"o\" <https://ex.org/g> .\n"));
+        assert_eq!(dump.matches("<https://ex.org/g>").count(), 1);
+
+        let mut target = mock_dependencies();
+        instantiate(
+            target.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        for msg in [
+            ExecuteMsg::BeginInsert {
+                format: Some(DataFormat::NQuads),
+                graph: None,
+            },
+            ExecuteMsg::InsertChunk {
+                data: Binary::from(dump.as_bytes()),
+            },
+            ExecuteMsg::CommitInsert {},
+        ] {
+            execute(target.as_mut(), mock_env(), info.clone(), msg).unwrap();
         }
+
+        let stats = |deps: Deps<'_>| -> StoreResponse {
+            from_json(query(deps, mock_env(), QueryMsg::Store {}).unwrap()).unwrap()
+        };
+        assert_eq!(stats(target.as_ref()).stat, stats(source.as_ref()).stat);
     }
 
     #[test]
-    fn prefixes_describe() {
-        let cases = vec![
-            (
-                QueryMsg::Describe {
-                    query: DescribeQuery {
-                        prefixes: vec![
-                            Prefix {
-                                prefix: "metadata".to_string(),
-                                namespace: "https://ontology.axone.space/dataverse/dataspace/metadata/".to_string(),
-                            },
-                        ],
-                        resource: VarOrNamedNode::NamedNode(Prefixed("metadata:dcf48417-01c5-4b43-9bc7-49e54c028473".to_string())),
-                        r#where: None,
-                    },
-                    format: Some(DataFormat::Turtle),
-                },
-                DescribeResponse {
-                    format: DataFormat::Turtle,
-                    data: Binary::from(
-                        "<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/metadata/dataspace/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;
-\t<https://ontology.axone.space/core/hasTag> \"Test\" , \"AXONE\" ;
-\t<https://ontology.axone.space/core/hasTitle> \"Data Space de test\"@fr , \"Test Data Space\"@en ;
-\t<https://ontology.axone.space/core/hasTopic> <https://ontology.axone.space/thesaurus/topic/Test> ;
-\t<https://ontology.axone.space/core/describes> <https://ontology.axone.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> ;
-\t<https://ontology.axone.space/core/hasPublisher> \"AXONE\" ;
-\t<https://ontology.axone.space/core/hasDescription> \"A test Data Space.\"@en , \"Un Data Space de test.\"@fr .
-\
-                ".to_string().as_bytes().to_vec()),
-                }
-            ),
-        ];
-
+    fn proper_backlinks() {
         let mut deps = mock_dependencies();
-
         let info = message_info(&addr(OWNER), &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
             info.clone(),
-            InstantiateMsg::default(),
+            InstantiateMsg {
+                limits: StoreLimitsInput {
+                    max_query_limit: 2,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
         )
         .unwrap();
-
+        let data = "@prefix ex: <https://ex.org/> .\n\
+            ex:c1 ex:about ex:dataset .\n\
+            ex:c2 ex:about ex:dataset ; ex:cites ex:dataset .\n\
+            [ ex:about ex:dataset ] .\n\
+            ex:c3 ex:about ex:other ; ex:title \"https://ex.org/dataset\" .\n";
         execute(
             deps.as_mut(),
             mock_env(),
             info,
             InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(data.as_bytes()),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
             },
         )
         .unwrap();
 
-        for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), q);
-
-            assert!(res.is_ok());
-
-            let result = from_json::<DescribeResponse>(&res.unwrap()).unwrap();
+        let backlinks = |iri: &str| -> (Vec<Backlink>, usize) {
+            let (mut backlinks, mut cursor, mut pages) = (vec![], None, 0);
+            loop {
+                let res: BacklinksResponse = from_json(
+                    query(
+                        deps.as_ref(),
+                        mock_env(),
+                        QueryMsg::Backlinks {
+                            iri: Full(iri.to_string()),
+                            cursor,
+                        },
+                    )
+                    .unwrap(),
+                )
+                .unwrap();
+                assert!(res.backlinks.len() <= 2);
+                backlinks.extend(res.backlinks);
+                pages += 1;
+                cursor = res.cursor;
+                if cursor.is_none() {
+                    return (backlinks, pages);
+                }
+            }
+        };
+        let named = |subject: &str, predicate: &str| Backlink {
+            subject: Value::URI {
+                value: Full(format!("https://ex.org/{subject}")),
+            },
+            predicate: Full(format!("https://ex.org/{predicate}")),
+        };
 
-            assert_eq!(result.format, expected.format);
-            assert_eq!(
-                String::from_utf8_lossy(&result.data),
-                String::from_utf8_lossy(&expected.data)
-            );
+        let (found, pages) = backlinks("https://ex.org/dataset");
+        assert_eq!(pages, 2);
+        assert_eq!(found.len(), 4);
+        for backlink in [
+            named("c1", "about"),
+            named("c2", "about"),
+            named("c2", "cites"),
+        ] {
+            assert!(found.contains(&backlink), "{backlink:?}");
         }
+        assert!(found.iter().any(|backlink| matches!(
+            backlink,
+            Backlink {
+                subject: Value::BlankNode { value },
+                predicate: Full(predicate),
+            } if value.starts_with('b') && predicate == "https://ex.org/about"
+        )));
+
+        assert_eq!(
+            backlinks("https://ex.org/other"),
+            (vec![named("c3", "about")], 1)
+        );
+        assert_eq!(backlinks("https://ex.org/none"), (vec![], 1));
+        assert_eq!(backlinks("https://unknown.org/dataset"), (vec![], 1));
     }
 
     #[test]
-    fn variable_describe() {
-        let cases = vec![
-            (
-                QueryMsg::Describe {
-                    query: DescribeQuery {
-                        prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
-                        resource: VarOrNamedNode::Variable("a".to_string()),
-                        r#where: WhereClause::Bgp {patterns: vec![
-                            TriplePattern {
-                                subject: VarOrNode::Variable("a".to_string()),
-                                predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                    "core:hasDescription".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Literal(Literal::LanguageTaggedString { value: "A test Dataset.".to_string(), language: "en".to_string() }),
-                            },
-                        ]}.into(),
-                    },
-                    format: Some(DataFormat::Turtle),
-                },
-                DescribeResponse {
-                    format: DataFormat::Turtle,
-                    data: Binary::from(
-                        "<https://ontology.axone.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/metadata/dataset/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;\n\t<https://ontology.axone.space/core/hasTag> \"test\" ;\n\t<https://ontology.axone.space/core/hasTitle> \"test Dataset\"@en , \"Dataset de test\"@fr ;\n\t<https://ontology.axone.space/core/hasTopic> <https://ontology.axone.space/thesaurus/topic/Test> ;\n\t<https://ontology.axone.space/core/describes> <https://ontology.axone.space/dataverse/dataset/0ea1fc7a-dd97-4adc-a10e-169c6597bcde> ;\n\t<https://ontology.axone.space/core/hasFormat> <https://ontology.axone.space/thesaurus/media-type/application_vndms-excel> ;\n\t<https://ontology.axone.space/core/hasCreator> \"Me\" ;\n\t<https://ontology.axone.space/core/hasLicense> <https://ontology.axone.space/thesaurus/license/LO-FR-1_0> ;\n\t<https://ontology.axone.space/core/hasPublisher> \"AXONE\" ;\n\t<https://ontology.axone.space/core/hasDescription> \"Un Dataset de test.\"@fr , \"A test Dataset.\"@en .\n".to_string().as_bytes().to_vec()),
-                }
-            ),
-        ];
-
+    fn result_byte_size_limit() {
         let mut deps = mock_dependencies();
-
         let info = message_info(&addr(OWNER), &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
             info.clone(),
-            InstantiateMsg::default(),
+            InstantiateMsg {
+                limits: StoreLimitsInput {
+                    max_result_byte_size: Uint128::new(200),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
         )
         .unwrap();
-
+        let data = "<https://ex.org/a> <https://ex.org/p> \"a\" .\n<https://ex.org/b> <https://ex.org/p> \"b\" .\n<https://ex.org/c> <https://ex.org/p> \"c\" .\n";
         execute(
             deps.as_mut(),
             mock_env(),
             info,
             InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(data.as_bytes()),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
             },
         )
         .unwrap();
 
-        for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), q);
-
-            assert!(res.is_ok());
-
-            let result = from_json::<DescribeResponse>(&res.unwrap()).unwrap();
-
-            assert_eq!(result.format, expected.format);
-            assert_eq!(
-                String::from_utf8_lossy(&result.data),
-                String::from_utf8_lossy(&expected.data)
-            );
+        let mut dump = vec![];
+        let mut cursor = None;
+        let mut pages = 0;
+        loop {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Export {
+                    cursor,
+                    limit: None,
+                    format: Some(DataFormat::NTriples),
+                },
+            )
+            .unwrap();
+            assert!(res.len() <= 200);
+            let res: ExportResponse = from_json(res).unwrap();
+            dump.extend(res.data.to_vec());
+            pages += 1;
+            cursor = res.cursor;
+            if cursor.is_none() {
+                break;
+            }
         }
-    }
+        assert!(pages > 1);
+        let mut lines: Vec<_> = std::str::from_utf8(&dump).unwrap().lines().collect();
+        lines.sort();
+        assert_eq!(lines, data.lines().collect::<Vec<_>>());
 
-    #[test]
-    fn variable_multiple_resources_describe() {
-        let cases = vec![
-            (
-                QueryMsg::Describe {
-                    query: DescribeQuery {
-                        prefixes: vec![Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() }],
-                        resource: VarOrNamedNode::Variable("a".to_string()),
-                        r#where: WhereClause::Bgp {patterns: vec![
-                            TriplePattern {
-                                subject: VarOrNode::Variable("a".to_string()),
-                                predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                    "core:hasPublisher".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Literal(Literal::Simple("AXONE".to_string())),
-                            },
-                        ]}.into(),
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Select {
+                query: SelectQuery {
+                    prefixes: vec![],
+                    select: vec![
+                        SelectItem::Variable("s".to_string()),
+                        SelectItem::Variable("o".to_string()),
+                    ],
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![TriplePattern {
+                            subject: VarOrNode::Variable("s".to_string()),
+                            predicate: VarOrNamedNode::Variable("p".to_string()),
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        }],
                     },
-                    format: Some(DataFormat::Turtle),
+                    limit: None,
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
                 },
-                DescribeResponse {
-                    format: DataFormat::Turtle,
-                    data: Binary::from(
-                        "<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/metadata/dataspace/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;\n\t<https://ontology.axone.space/core/hasTag> \"Test\" , \"AXONE\" ;\n\t<https://ontology.axone.space/core/hasTitle> \"Data Space de test\"@fr , \"Test Data Space\"@en ;\n\t<https://ontology.axone.space/core/hasTopic> <https://ontology.axone.space/thesaurus/topic/Test> ;\n\t<https://ontology.axone.space/core/describes> <https://ontology.axone.space/dataverse/dataspace/97ff7e16-c08d-47be-8475-211016c82e33> ;\n\t<https://ontology.axone.space/core/hasPublisher> \"AXONE\" ;\n\t<https://ontology.axone.space/core/hasDescription> \"A test Data Space.\"@en , \"Un Data Space de test.\"@fr .\n<https://ontology.axone.space/dataverse/dataset/metadata/d1615703-4ee1-4e2f-997e-15aecf1eea4e> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/metadata/dataset/GeneralMetadata> , <http://www.w3.org/2002/07/owl#NamedIndividual> ;\n\t<https://ontology.axone.space/core/hasTag> \"test\" ;\n\t<https://ontology.axone.space/core/hasTitle> \"test Dataset\"@en , \"Dataset de test\"@fr ;\n\t<https://ontology.axone.space/core/hasTopic> <https://ontology.axone.space/thesaurus/topic/Test> ;\n\t<https://ontology.axone.space/core/describes> <https://ontology.axone.space/dataverse/dataset/0ea1fc7a-dd97-4adc-a10e-169c6597bcde> ;\n\t<https://ontology.axone.space/core/hasFormat> <https://ontology.axone.space/thesaurus/media-type/application_vndms-excel> ;\n\t<https://ontology.axone.space/core/hasCreator> \"Me\" ;\n\t<https://ontology.axone.space/core/hasLicense> <https://ontology.axone.space/thesaurus/license/LO-FR-1_0> ;\n\t<https://ontology.axone.space/core/hasPublisher> \"AXONE\" ;\n\t<https://ontology.axone.space/core/hasDescription> \"Un Dataset de test.\"@fr , \"A test Dataset.\"@en .\n".to_string().as_bytes().to_vec()),
-                }
-            ),
-        ];
+            },
+        );
+        assert_eq!(
+            res,
+            Err(StdError::generic_err(
+                StoreError::ResultByteSize(Uint128::new(200)).to_string()
+            ))
+        );
 
-        let mut deps = mock_dependencies();
+        // the configuration of the store remains readable whatever the limit
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Store {}).unwrap();
+        assert!(res.len() > 200);
+        assert!(query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Grantees {
+                after: None,
+                limit: None
+            }
+        )
+        .is_ok());
+    }
 
+    #[test]
+    fn proper_select_service() {
+        let mut deps = mock_dependencies();
         let info = message_info(&addr(OWNER), &[]);
         instantiate(
             deps.as_mut(),
@@ -2143,267 +10653,496 @@ mod tests {
             InstantiateMsg::default(),
         )
         .unwrap();
-
         execute(
             deps.as_mut(),
             mock_env(),
             info,
             InsertData {
-                format: Some(DataFormat::RDFXml),
-                data: read_test_data("sample.rdf.xml"),
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<https://ex.org/alice> <https://ex.org/knows> <https://ex.org/bob> .\n"
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
             },
         )
         .unwrap();
 
-        for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), q);
-
-            assert!(res.is_ok());
+        let service = WhereClause::Service {
+            contract_addr: "remote".to_string(),
+            clause: Box::new(WhereClause::Bgp {
+                patterns: vec![TriplePattern {
+                    subject: VarOrNode::Variable("person".to_string()),
+                    predicate: VarOrNamedNode::NamedNode(Full("https://ex.org/name".to_string())),
+                    object: VarOrNodeOrLiteral::Variable("name".to_string()),
+                }],
+            }),
+        };
+        let remote_clause = match &service {
+            WhereClause::Service { clause, .. } => *clause.clone(),
+            _ => unreachable!(),
+        };
+        let remote = move |max_query_limit: u32| {
+            let expected_query = QueryMsg::Select {
+                query: SelectQuery {
+                    prefixes: vec![],
+                    select: vec![
+                        SelectItem::Variable("name".to_string()),
+                        SelectItem::Variable("person".to_string()),
+                    ],
+                    r#where: remote_clause.clone(),
+                    limit: Some(max_query_limit),
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
+                },
+            };
+            move |query: &WasmQuery| match query {
+                WasmQuery::Smart { contract_addr, msg } if contract_addr == "remote" => {
+                    let msg = from_json::<QueryMsg>(msg).unwrap();
+                    if msg == (QueryMsg::Store {}) {
+                        return SystemResult::Ok(ContractResult::Ok(
+                            to_json_binary(&StoreResponse {
+                                owner: OWNER.to_string(),
+                                limits: msg::StoreLimits {
+                                    max_query_limit,
+                                    ..msg::StoreLimits::default()
+                                },
+                                stat: msg::StoreStat {
+                                    triple_count: Uint128::zero(),
+                                    namespace_count: Uint128::zero(),
+                                    byte_size: Uint128::zero(),
+                                },
+                                conflict_policy: Default::default(),
+                                literal_normalization: true,
+                                formats: vec![],
+                            })
+                            .unwrap(),
+                        ));
+                    }
+                    assert_eq!(msg, expected_query);
+                    let binding = |person: &str, name: &str| {
+                        BTreeMap::from([
+                            (
+                                "person".to_string(),
+                                Value::URI {
+                                    value: Full(person.to_string()),
+                                },
+                            ),
+                            (
+                                "name".to_string(),
+                                Value::Literal {
+                                    value: name.to_string(),
+                                    lang: None,
+                                    datatype: None,
+                                },
+                            ),
+                        ])
+                    };
+                    SystemResult::Ok(ContractResult::Ok(
+                        to_json_binary(&SelectResponse {
+                            head: Head {
+                                vars: vec!["name".to_string(), "person".to_string()],
+                            },
+                            results: Results {
+                                bindings: vec![
+                                    binding("https://ex.org/bob", "Bob"),
+                                    binding("https://other.org/carol", "Carol"),
+                                ],
+                            },
+                        })
+                        .unwrap(),
+                    ))
+                }
+                _ => SystemResult::Err(SystemError::Unknown {}),
+            }
+        };
+        deps.querier.update_wasm(remote(3));
+
+        let select = |r#where: WhereClause| SelectQuery {
+            prefixes: vec![],
+            select: vec![
+                SelectItem::Variable("person".to_string()),
+                SelectItem::Variable("name".to_string()),
+            ],
+            r#where,
+            limit: None,
+            reasoning: None,
+            skolemize: false,
+            adaptive_joins: false,
+            ordered: false,
+            from: vec![],
+            from_named: vec![],
+        };
+        let names = |res: SelectResponse| {
+            res.results
+                .bindings
+                .into_iter()
+                .map(|b| (b["person"].clone(), b["name"].clone()))
+                .collect::<Vec<_>>()
+        };
+        let person = |iri: &str| Value::URI {
+            value: Full(iri.to_string()),
+        };
+        let name = |name: &str| Value::Literal {
+            value: name.to_string(),
+            lang: None,
+            datatype: None,
+        };
 
-            let result = from_json::<DescribeResponse>(&res.unwrap()).unwrap();
+        assert_eq!(
+            names(query::select(deps.as_ref(), select(service.clone())).unwrap()),
+            vec![
+                (person("https://ex.org/bob"), name("Bob")),
+                (person("https://other.org/carol"), name("Carol")),
+            ]
+        );
+        assert_eq!(
+            names(
+                query::select(
+                    deps.as_ref(),
+                    select(WhereClause::LateralJoin {
+                        left: Box::new(WhereClause::Bgp {
+                            patterns: vec![TriplePattern {
+                                subject: VarOrNode::Node(NamedNode(Full(
+                                    "https://ex.org/alice".to_string()
+                                ))),
+                                predicate: VarOrNamedNode::NamedNode(Full(
+                                    "https://ex.org/knows".to_string()
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("person".to_string()),
+                            }],
+                        }),
+                        right: Box::new(service.clone()),
+                    })
+                )
+                .unwrap()
+            ),
+            vec![(person("https://ex.org/bob"), name("Bob"))]
+        );
+        assert_eq!(
+            query::explain(deps.as_ref(), select(service.clone()))
+                .unwrap()
+                .plan,
+            PlanNode::Limit {
+                child: Box::new(PlanNode::Service {
+                    contract_addr: "remote".to_string(),
+                    solutions: 2,
+                }),
+                first: 30,
+            }
+        );
+        assert_eq!(
+            query::budgeted_select(deps.as_ref(), select(service.clone()), 10, None),
+            Err(StdError::generic_err(
+                "Service clauses are not supported here"
+            ))
+        );
 
-            assert_eq!(result.format, expected.format);
-            assert_eq!(
-                String::from_utf8_lossy(&result.data),
-                String::from_utf8_lossy(&expected.data)
-            );
-        }
+        deps.querier.update_wasm(remote(2));
+        assert_eq!(
+            query::select(deps.as_ref(), select(service)),
+            Err(StdError::generic_err(
+                "Service solutions of contract 'remote' truncated by its query limit: 2"
+            ))
+        );
     }
 
     #[test]
-    fn blanknode_describe() {
-        let cases = vec![
-            (
-                QueryMsg::Describe {
-                    query: DescribeQuery {
-                        prefixes: vec![
-                            Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() },
-                            Prefix { prefix: "metadata-dataset".to_string(), namespace: "https://ontology.axone.space/dataverse/dataset/metadata/".to_string() },
-                        ],
-                        resource: VarOrNamedNode::Variable("x".to_string()),
-                        r#where: WhereClause::Bgp {patterns: vec![
-                            TriplePattern {
-                                subject: VarOrNode::Node(NamedNode(Prefixed("metadata-dataset:80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string()))),
-                                predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                    "core:hasTemporalCoverage".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Variable("x".to_string()),
+    fn literal_normalization() {
+        const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+        let integer = |value: &str| Literal::TypedValue {
+            value: value.to_string(),
+            datatype: Full(XSD_INTEGER.to_string()),
+        };
+        let select = |deps: Deps<'_>, object: VarOrNodeOrLiteral| -> Vec<Value> {
+            let res: SelectResponse = from_json(
+                query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::Select {
+                        query: SelectQuery {
+                            prefixes: vec![],
+                            select: vec![SelectItem::Variable("s".to_string())],
+                            r#where: WhereClause::Bgp {
+                                patterns: vec![TriplePattern {
+                                    subject: VarOrNode::Variable("s".to_string()),
+                                    predicate: VarOrNamedNode::NamedNode(Full(
+                                        "https://ex.org/p".to_string(),
+                                    )),
+                                    object,
+                                }],
                             },
-                        ]}.into(),
+                            limit: None,
+                            reasoning: None,
+                            skolemize: false,
+                            adaptive_joins: false,
+                            ordered: false,
+                            from: vec![],
+                            from_named: vec![],
+                        },
                     },
-                    format: Some(DataFormat::Turtle),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            res.results
+                .bindings
+                .into_iter()
+                .filter_map(|mut b| b.remove("s"))
+                .collect()
+        };
+
+        for disable in [false, true] {
+            let mut deps = mock_dependencies();
+            let info = message_info(&addr(OWNER), &[]);
+            instantiate(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InstantiateMsg {
+                    disable_literal_normalization: disable,
+                    soft_limit: None,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let store: StoreResponse =
+                from_json(query(deps.as_ref(), mock_env(), QueryMsg::Store {}).unwrap()).unwrap();
+            assert_eq!(store.literal_normalization, !disable);
+
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                InsertData {
+                    format: Some(DataFormat::NTriples),
+                    data: Binary::from(
+                        format!(
+                            "<https://ex.org/a> <https://ex.org/p> \"+007\"^^<{XSD_INTEGER}> ."
+                        )
+                        .as_bytes(),
+                    ),
+                    compression: None,
+                    expires: None,
+                    graph: None,
+                    skip_oversized: false,
+                },
+            )
+            .unwrap();
+
+            let stored = if disable { "+007" } else { "7" };
+            let res: DescribeResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::Describe {
+                        query: DescribeQuery {
+                            prefixes: vec![],
+                            resource: VarOrNamedNode::NamedNode(Full(
+                                "https://ex.org/a".to_string(),
+                            )),
+                            r#where: None,
+                        },
+                        format: Some(DataFormat::NTriples),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(
+                String::from_utf8(res.data.to_vec()).unwrap(),
+                format!("<https://ex.org/a> <https://ex.org/p> \"{stored}\"^^<{XSD_INTEGER}> .\n")
+            );
+            for value in ["7", "07", "+007"] {
+                let found =
+                    select(deps.as_ref(), VarOrNodeOrLiteral::Literal(integer(value))).len();
+                assert_eq!(found, usize::from(!disable || value == stored), "{value}");
+            }
+
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![msg::TripleDeleteTemplate {
+                        subject: VarOrNamedNode::NamedNode(Full("https://ex.org/a".to_string())),
+                        predicate: VarOrNamedNode::NamedNode(Full("https://ex.org/p".to_string())),
+                        object: VarOrNamedNodeOrLiteral::Literal(integer("07")),
+                    }],
+                    r#where: None,
                 },
-                DescribeResponse {
-                    format: DataFormat::Turtle,
-                    data: Binary::from(
-                        "<b0> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#NamedIndividual> , <https://ontology.axone.space/core/Period> ;\n\t<https://ontology.axone.space/core/hasStartDate> \"2022-01-01T00:00:00+00:00\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n".to_string().as_bytes().to_vec()),
-                }
-            ),
-        ];
+            )
+            .unwrap();
+            assert_eq!(
+                res.attributes,
+                vec![
+                    Attribute::new("action", "delete"),
+                    Attribute::new("triple_count", if disable { "0" } else { "1" }),
+                ]
+            );
+        }
+    }
 
+    #[test]
+    fn query_audit() {
         let mut deps = mock_dependencies();
-
         let info = message_info(&addr(OWNER), &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
             info.clone(),
-            InstantiateMsg::default(),
+            InstantiateMsg {
+                query_audit: Some(2),
+                ..Default::default()
+            },
         )
         .unwrap();
-
         execute(
             deps.as_mut(),
             mock_env(),
-            info,
+            info.clone(),
             InsertData {
-                format: Some(DataFormat::Turtle),
-                data: read_test_data("blank-nodes.ttl"),
+                format: Some(DataFormat::NTriples),
+                data: Binary::from(
+                    "<https://ex.org/a> <https://ex.org/p> \"a\" .\n<https://ex.org/b> <https://ex.org/p> \"b\" .\n<https://ex.org/c> <https://ex.org/p> \"c\" .\n"
+                        .as_bytes(),
+                ),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
             },
         )
         .unwrap();
 
-        for (q, expected) in cases {
-            let res = query(deps.as_ref(), mock_env(), q);
-
-            assert!(res.is_ok());
-
-            let result = from_json::<DescribeResponse>(&res.unwrap()).unwrap();
-
-            assert_eq!(result.format, expected.format);
-            assert_eq!(
-                String::from_utf8_lossy(&result.data),
-                String::from_utf8_lossy(&expected.data)
-            );
+        let pattern = |subject: &str| WhereClause::Bgp {
+            patterns: vec![TriplePattern {
+                subject: VarOrNode::Node(NamedNode(Full(subject.to_string()))),
+                predicate: VarOrNamedNode::Variable("p".to_string()),
+                object: VarOrNodeOrLiteral::Variable("o".to_string()),
+            }],
+        };
+        for subject in ["https://ex.org/z", "https://ex.org/a", "https://ex.org/b"] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                DeleteData {
+                    prefixes: vec![],
+                    delete: vec![],
+                    r#where: Some(pattern(subject)),
+                },
+            )
+            .unwrap();
         }
-    }
+        // deleting explicit triples doesn't evaluate any where clause
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            DeleteData {
+                prefixes: vec![],
+                delete: vec![msg::TripleDeleteTemplate {
+                    subject: VarOrNamedNode::NamedNode(Full("https://ex.org/c".to_string())),
+                    predicate: VarOrNamedNode::NamedNode(Full("https://ex.org/p".to_string())),
+                    object: VarOrNamedNodeOrLiteral::Literal(Literal::Simple("c".to_string())),
+                }],
+                r#where: None,
+            },
+        )
+        .unwrap();
 
-    #[test]
-    fn proper_construct() {
-        let id = "https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473";
-        let cases = vec![
-            (
-                InsertData {
-                    format: Some(DataFormat::RDFXml),
-                    data: read_test_data("sample.rdf.xml"),
-                },
-                QueryMsg::Construct {
-                    query: ConstructQuery {
-                        prefixes: vec![],
-                        construct: vec![],
-                        r#where: WhereClause::Bgp{patterns:vec![TriplePattern {
-                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                            predicate: VarOrNamedNode::NamedNode(Full(
-                                "https://ontology.axone.space/core/hasTag".to_string(),
-                            )),
-                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                        }]},
-                    },
-                    format: None,
-                },
-                ConstructResponse {
-                    format: DataFormat::Turtle,
-                    data: Binary::from(
-                        "<https://ontology.axone.space/dataverse/dataspace/metadata/dcf48417-01c5-4b43-9bc7-49e54c028473> <https://ontology.axone.space/core/hasTag> \"Test\" , \"AXONE\" .\n".to_string().as_bytes().to_vec()),
-                },
-            ),
-            (
-                InsertData {
-                    format: Some(DataFormat::RDFXml),
-                    data: read_test_data("sample.rdf.xml"),
-                },
-                QueryMsg::Construct {
-                    query: ConstructQuery {
-                        prefixes: vec![
-                            Prefix { prefix: "my-ns".to_string(), namespace: "https://my-ns.org/".to_string() },
-                            Prefix { prefix: "metadata-dataset".to_string(), namespace: "https://ontology.axone.space/dataverse/dataset/metadata/".to_string() },
-                        ],
-                        construct: vec![
-                            msg::TripleConstructTemplate {
-                                subject: VarOrNode::Node(NamedNode(Prefixed("my-ns:instance-1".to_string()))),
-                                predicate: VarOrNamedNode::NamedNode(Full(
-                                    "https://my-ns/predicate/tag".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                            }
-                        ],
-                        r#where: WhereClause::Bgp{patterns:vec![TriplePattern {
-                            subject: VarOrNode::Node(NamedNode(Full(id.to_string()))),
-                            predicate: VarOrNamedNode::NamedNode(Full(
-                                "https://ontology.axone.space/core/hasTag".to_string(),
-                            )),
-                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                        }]},
-                    },
-                    format: Some(DataFormat::NTriples),
-                },
-                ConstructResponse {
-                    format: DataFormat::NTriples,
-                    data: Binary::from(
-                        "<https://my-ns.org/instance-1> <https://my-ns/predicate/tag> \"Test\" .\n<https://my-ns.org/instance-1> <https://my-ns/predicate/tag> \"AXONE\" .\n".to_string().as_bytes().to_vec()),
-                },
-            ),
-            (
-                InsertData {
-                    format: Some(DataFormat::Turtle),
-                    data: read_test_data("blank-nodes.ttl"),
-                },
-                QueryMsg::Construct {
-                    query: ConstructQuery {
-                        prefixes: vec![
-                            Prefix { prefix: "core".to_string(), namespace: "https://ontology.axone.space/core/".to_string() },
-                            Prefix { prefix: "metadata-dataset".to_string(), namespace: "https://ontology.axone.space/dataverse/dataset/metadata/".to_string() },
-                        ],
-                        construct: vec![
-                            msg::TripleConstructTemplate {
-                                subject: VarOrNode::Node(BlankNode("my-metadata".to_string())),
-                                predicate: VarOrNamedNode::NamedNode(Full(
-                                    "https://my-ns/predicate/tcov".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Variable("tcov".to_string()),
-                            },
-                            msg::TripleConstructTemplate {
-                                subject: VarOrNode::Node(BlankNode("my-metadata".to_string())),
-                                predicate: VarOrNamedNode::NamedNode(Full(
-                                    "https://my-ns/predicate/info".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Variable("info".to_string()),
-                            },
-                            msg::TripleConstructTemplate {
-                                subject: VarOrNode::Variable("tcov".to_string()),
-                                predicate: VarOrNamedNode::Variable("tcov_p".to_string()),
-                                object: VarOrNodeOrLiteral::Variable("tcov_o".to_string()),
-                            },
-                            msg::TripleConstructTemplate {
-                                subject: VarOrNode::Variable("info".to_string()),
-                                predicate: VarOrNamedNode::Variable("info_p".to_string()),
-                                object: VarOrNodeOrLiteral::Variable("info_o".to_string()),
-                            }
-                        ],
-                        r#where: WhereClause::Bgp {patterns:vec![
-                            TriplePattern {
-                                subject: VarOrNode::Node(NamedNode(Prefixed("metadata-dataset:80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string()))),
-                                predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                    "core:hasTemporalCoverage".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Variable("tcov".to_string()),
-                            },
-                            TriplePattern {
-                                subject: VarOrNode::Node(NamedNode(Prefixed("metadata-dataset:80b1f84e-86dc-4730-b54f-701ad9b1888a".to_string()))),
-                                predicate: VarOrNamedNode::NamedNode(Prefixed(
-                                    "core:hasInformations".to_string(),
-                                )),
-                                object: VarOrNodeOrLiteral::Variable("info".to_string()),
-                            },
-                            TriplePattern {
-                                subject: VarOrNode::Variable("tcov".to_string()),
-                                predicate: VarOrNamedNode::Variable("tcov_p".to_string()),
-                                object: VarOrNodeOrLiteral::Variable("tcov_o".to_string()),
-                            },
-                            TriplePattern {
-                                subject: VarOrNode::Variable("info".to_string()),
-                                predicate: VarOrNamedNode::Variable("info_p".to_string()),
-                                object: VarOrNodeOrLiteral::Variable("info_o".to_string()),
-                            }
-                        ]},
-                    },
-                    format: Some(DataFormat::NTriples),
+        let audit = |deps: Deps<'_>, after: Option<u64>| -> StdResult<QueryAuditResponse> {
+            from_json(query(
+                deps,
+                mock_env(),
+                QueryMsg::QueryAudit {
+                    after: after.map(Uint64::new),
+                    limit: None,
                 },
-                ConstructResponse {
-                    format: DataFormat::NTriples,
-                    data: Binary::from(
-                        "<a0> <https://my-ns/predicate/tcov> <b0> .\n<a0> <https://my-ns/predicate/info> <b1> .\n<b0> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#NamedIndividual> .\n<b1> <https://ontology.axone.space/core/hasInformation> \"this is a dataset\" .\n<a0> <https://my-ns/predicate/tcov> <b0> .\n<a0> <https://my-ns/predicate/info> <b1> .\n<b0> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ontology.axone.space/core/Period> .\n<b1> <https://ontology.axone.space/core/hasInformation> \"this is a dataset\" .\n<a0> <https://my-ns/predicate/tcov> <b0> .\n<a0> <https://my-ns/predicate/info> <b1> .\n<b0> <https://ontology.axone.space/core/hasStartDate> \"2022-01-01T00:00:00+00:00\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n<b1> <https://ontology.axone.space/core/hasInformation> \"this is a dataset\" .\n".to_string().as_bytes().to_vec()),
+            )?)
+        };
+        let hash = |subject: &str| {
+            blake3::hash(
+                &cosmwasm_std::to_json_vec(&(Vec::<Prefix>::new(), pattern(subject))).unwrap(),
+            )
+            .to_hex()
+            .to_string()
+        };
+        let entry = |id: u64, subject: &str| QueryAuditEntry {
+            id: id.into(),
+            operation: "delete_data".to_string(),
+            query_hash: hash(subject),
+            caller: addr(OWNER).to_string(),
+            pattern_count: 1,
+            result_count: 1u64.into(),
+        };
+        assert_eq!(
+            audit(deps.as_ref(), None),
+            Ok(QueryAuditResponse {
+                capacity: 2,
+                last_entry: 3u64.into(),
+                entries: vec![entry(2, "https://ex.org/a"), entry(3, "https://ex.org/b")],
+            })
+        );
+
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr("not-owner"), &[]),
+                ExecuteMsg::PruneQueryAudit {
+                    before: None,
+                    limit: None,
                 },
-            ),
-        ];
+            )
+            .unwrap_err(),
+            ContractError::Unauthorized
+        );
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::PruneQueryAudit {
+                before: Some(Uint64::new(3)),
+                limit: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "prune_query_audit"),
+                Attribute::new("entry_count", "1"),
+            ]
+        );
+        assert_eq!(
+            audit(deps.as_ref(), Some(1)).unwrap().entries,
+            vec![entry(3, "https://ex.org/b")]
+        );
 
-        for (data, q, expected) in cases {
+        let deps = {
             let mut deps = mock_dependencies();
-
-            let info = message_info(&addr(OWNER), &[]);
             instantiate(
                 deps.as_mut(),
                 mock_env(),
-                info.clone(),
-                InstantiateMsg {
-                    limits: StoreLimitsInput::default(),
-                },
+                message_info(&addr(OWNER), &[]),
+                InstantiateMsg::default(),
             )
             .unwrap();
-
-            execute(deps.as_mut(), mock_env(), info.clone(), data).unwrap();
-
-            let res = query(deps.as_ref(), mock_env(), q);
-
-            assert!(res.is_ok());
-
-            let result = from_json::<DescribeResponse>(&res.unwrap()).unwrap();
-
-            assert_eq!(result.format, expected.format);
-            assert_eq!(
-                String::from_utf8_lossy(&result.data),
-                String::from_utf8_lossy(&expected.data)
-            );
-        }
+            deps
+        };
+        assert_eq!(
+            audit(deps.as_ref(), None),
+            Err(StdError::generic_err("Query audit log not enabled"))
+        );
     }
 }
@@ -0,0 +1,1174 @@
+//! The query evaluation pipeline of the triple store.
+//!
+//! A query goes through the following stages:
+//! - validation: the query is checked against the store limits, see [validate_select],
+//!   [validate_count] and [validate_where];
+//! - planning: the where clause is turned into a [Plan], see [plan];
+//! - execution and serialization: the plan is evaluated against the state and its results are mapped
+//!   into their message representation, see [select] and [construct], or only counted, see [count].
+//!   The evaluation can also be bounded to a number of index rows and resumed across calls, see
+//!   [select_budgeted].
+//!
+//! Those stages are exposed under the `library` feature so other contracts can embed the plan
+//! construction and the results serialization of the triple store.
+
+use crate::error::{QueryComplexityError, StoreError};
+use crate::msg::{
+    Backlink, BudgetedSelectResponse, CountQuery, DataFormat, Head, PatternTerm, PlanNode, Prefix,
+    ProvenanceResponse, Reasoning, Results, SelectCursor, SelectItem, SelectQuery, SelectResponse,
+    TripleIndex, TriplePattern, TripleProvenance, Value, VarOrNamedNode, VarOrNode,
+    VarOrNodeOrLiteral, WhereClause, IRI,
+};
+use crate::querier::{
+    count_pattern_rows, iri_as_graph_name, iri_as_node, pattern_triples, pattern_variables,
+    stored_blank_label, stored_triple_as_atom, Budget, HasBoundVariables, PatternValue,
+    PlanBuilder, PlanVariable, QueryEngine, QueryNode, QueryPlan, ResolvedVariable,
+    ResolvedVariables, SelectResults,
+};
+use crate::rdf::{Atom, PrefixMap};
+use crate::state::{
+    triples, HasCachedNamespaces, Namespace, NamespaceQuerier, NamespaceResolver, Provenance,
+    StoreLimits, Term, TermQuerier, TermRef, PREFIXES, STORE, SUBJECT_PROVENANCE,
+    TRIPLE_INSERTIONS,
+};
+use crate::{msg, rdf, state};
+use axone_objectarium::msg::QueryMsg as ObjectariumQueryMsg;
+use axone_rdf::normalize::IdentifierIssuer;
+use axone_rdf::serde::TripleWriter;
+use cosmwasm_std::{Binary, Order, QuerierWrapper, StdError, StdResult, Storage};
+use cw_storage_plus::{Bound, PrimaryKey};
+use rio_api::model::{Literal, NamedNode};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// The evaluation plan of a query where clause, along with the context needed to execute it.
+#[derive(Clone)]
+pub struct Plan {
+    pub(crate) plan: QueryPlan,
+    pub(crate) prefixes: HashMap<String, String>,
+    pub(crate) namespaces: Vec<Namespace>,
+}
+
+/// Resolves the literals offloaded to an `axone-objectarium` bucket at insertion back to their
+/// value, see [state::LiteralOffload].
+pub struct OffloadedLiterals<'a> {
+    querier: QuerierWrapper<'a>,
+    offload: state::LiteralOffload,
+}
+
+impl<'a> OffloadedLiterals<'a> {
+    /// Returns the resolver of the literals offloaded by the store, [None] if the offloading is
+    /// disabled.
+    pub fn load(storage: &dyn Storage, querier: QuerierWrapper<'a>) -> StdResult<Option<Self>> {
+        Ok(state::LITERAL_OFFLOAD
+            .may_load(storage)?
+            .map(|offload| Self { querier, offload }))
+    }
+
+    /// Replace the value of the offloaded literals of the atoms by the content of their object, each
+    /// object being fetched once.
+    fn resolve(&self, atoms: &mut [Atom]) -> StdResult<()> {
+        let mut objects: BTreeMap<String, String> = BTreeMap::new();
+        for atom in atoms {
+            let value = match &mut atom.value {
+                rdf::Value::LiteralSimple(value)
+                | rdf::Value::LiteralLang(value, _)
+                | rdf::Value::LiteralDatatype(value, _) => value,
+                _ => continue,
+            };
+            let Some(id) = self.offload.object_id(value) else {
+                continue;
+            };
+            if !objects.contains_key(&id) {
+                let data: Binary = self.querier.query_wasm_smart(
+                    &self.offload.bucket,
+                    &ObjectariumQueryMsg::ObjectData { id: id.clone() },
+                )?;
+                objects.insert(id.clone(), String::from_utf8(data.into())?);
+            }
+            value.clone_from(&objects[&id]);
+        }
+        Ok(())
+    }
+}
+
+impl Plan {
+    /// Order the joined nodes of the plan at evaluation time, based on the rows they match given
+    /// the variables bound so far, see [SelectQuery::adaptive_joins].
+    pub fn with_adaptive_joins(mut self) -> Self {
+        self.plan.entrypoint = self.plan.entrypoint.into_adaptive_joins();
+        self
+    }
+
+    /// Match the plan against the dataset made of the given default and named graphs, see
+    /// [SelectQuery::from]. The plan is left untouched if no graph is given.
+    pub fn with_dataset(mut self, from: Vec<IRI>, from_named: Vec<IRI>) -> StdResult<Self> {
+        if from.is_empty() && from_named.is_empty() {
+            return Ok(self);
+        }
+
+        let graph_names = |iris: Vec<IRI>| {
+            iris.into_iter()
+                .map(|iri| iri_as_graph_name(iri, &self.prefixes))
+                .collect::<StdResult<BTreeSet<_>>>()
+        };
+        let (default, named) = (graph_names(from)?, graph_names(from_named)?);
+        self.plan.entrypoint = self.plan.entrypoint.within_dataset(default, &named);
+        Ok(self)
+    }
+}
+
+/// Check a select query against the store limits, returning the effective limit of the query.
+pub fn validate_select(query: &SelectQuery, limits: &StoreLimits) -> StdResult<u32> {
+    if query.select.len() > limits.max_query_variable_count as usize {
+        Err(StoreError::QueryVariableCount(
+            limits.max_query_variable_count,
+        ))?;
+    }
+
+    let count = query.limit.unwrap_or(limits.max_query_limit);
+    if count > limits.max_query_limit {
+        Err(StoreError::QueryLimit(limits.max_query_limit))?;
+    }
+
+    validate_where(&query.r#where, limits)?;
+
+    Ok(count)
+}
+
+/// The inference over the class and property hierarchies applied when planning a where clause.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Inference {
+    /// The maximum number of hierarchy levels to infer over.
+    pub max_depth: u32,
+    /// The maximum number of `owl:sameAs` links to follow, 0 disabling it.
+    pub same_as_depth: u32,
+    /// The maximum number of triple patterns the where clause can expand to.
+    pub max_pattern_count: u32,
+}
+
+/// Check the reasoning requested by a select query against the store limits, returning the
+/// inference to apply when planning it, if any.
+pub fn validate_reasoning(
+    query: &SelectQuery,
+    limits: &StoreLimits,
+) -> Result<Option<Inference>, QueryComplexityError> {
+    reasoning_inference(query.reasoning.as_ref(), limits)
+}
+
+/// Check a count query against the store limits, returning the inference to apply when planning
+/// it, if any.
+pub fn validate_count(query: &CountQuery, limits: &StoreLimits) -> StdResult<Option<Inference>> {
+    validate_where(&query.r#where, limits)?;
+
+    Ok(reasoning_inference(query.reasoning.as_ref(), limits)?)
+}
+
+fn reasoning_inference(
+    reasoning: Option<&Reasoning>,
+    limits: &StoreLimits,
+) -> Result<Option<Inference>, QueryComplexityError> {
+    let Some(reasoning) = reasoning else {
+        return Ok(None);
+    };
+
+    let max_depth = reasoning
+        .max_depth
+        .unwrap_or(limits.max_query_inference_depth);
+    let same_as_depth = reasoning.same_as.unwrap_or(0);
+    if max_depth.max(same_as_depth) > limits.max_query_inference_depth {
+        Err(QueryComplexityError::InferenceDepth(
+            limits.max_query_inference_depth,
+        ))?;
+    }
+
+    Ok(Some(Inference {
+        max_depth,
+        same_as_depth,
+        max_pattern_count: limits.max_query_pattern_count,
+    }))
+}
+
+/// Check the shape of a where clause against the store limits, i.e. its number of triple patterns,
+/// its nesting depth and its number of join variables.
+pub fn validate_where(
+    r#where: &WhereClause,
+    limits: &StoreLimits,
+) -> Result<(), QueryComplexityError> {
+    let (pattern_count, depth) = where_complexity(r#where);
+    if pattern_count > limits.max_query_pattern_count as usize {
+        Err(QueryComplexityError::PatternCount(
+            limits.max_query_pattern_count,
+        ))?;
+    }
+    if depth > limits.max_query_where_depth as usize {
+        Err(QueryComplexityError::WhereDepth(
+            limits.max_query_where_depth,
+        ))?;
+    }
+    if join_variable_count(r#where) > limits.max_query_join_variable_count as usize {
+        Err(QueryComplexityError::JoinVariableCount(
+            limits.max_query_join_variable_count,
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the number of join variables of a where clause, i.e. of the variables shared by several
+/// of its triple patterns.
+pub fn join_variable_count(r#where: &WhereClause) -> usize {
+    fn count_occurrences(r#where: &WhereClause, occurrences: &mut BTreeMap<String, usize>) {
+        match r#where {
+            WhereClause::Bgp { patterns } => {
+                for variable in patterns.iter().flat_map(pattern_variables) {
+                    *occurrences.entry(variable).or_default() += 1;
+                }
+            }
+            WhereClause::LateralJoin { left, right } => {
+                count_occurrences(left, occurrences);
+                count_occurrences(right, occurrences);
+            }
+            WhereClause::Filter { inner, .. }
+            | WhereClause::Service { clause: inner, .. }
+            | WhereClause::Graph { clause: inner, .. } => count_occurrences(inner, occurrences),
+        }
+    }
+
+    let mut occurrences = BTreeMap::new();
+    count_occurrences(r#where, &mut occurrences);
+    occurrences.values().filter(|count| **count > 1).count()
+}
+
+/// Returns the number of triple patterns and the nesting depth of a where clause.
+pub fn where_complexity(r#where: &WhereClause) -> (usize, usize) {
+    match r#where {
+        WhereClause::Bgp { patterns } => (patterns.iter().map(pattern_count).sum(), 1),
+        WhereClause::LateralJoin { left, right } => {
+            let (left_count, left_depth) = where_complexity(left);
+            let (right_count, right_depth) = where_complexity(right);
+            (left_count + right_count, left_depth.max(right_depth) + 1)
+        }
+        WhereClause::Filter { inner, .. }
+        | WhereClause::Service { clause: inner, .. }
+        | WhereClause::Graph { clause: inner, .. } => {
+            let (count, depth) = where_complexity(inner);
+            (count, depth + 1)
+        }
+    }
+}
+
+/// The number of triple patterns a triple pattern stands for, i.e. 1 plus its quoted triple
+/// patterns.
+fn pattern_count(pattern: &TriplePattern) -> usize {
+    let subject = match &pattern.subject {
+        VarOrNode::QuotedTriple(quoted) => pattern_count(quoted),
+        _ => 0,
+    };
+    let object = match &pattern.object {
+        VarOrNodeOrLiteral::QuotedTriple(quoted) => pattern_count(quoted),
+        _ => 0,
+    };
+    1 + subject + object
+}
+
+/// The prefixes registered in the store along with the given ones, taking precedence over them.
+fn prefix_map(storage: &dyn Storage, prefixes: Vec<Prefix>) -> StdResult<HashMap<String, String>> {
+    Ok(PREFIXES
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<HashMap<_, _>>>()?
+        .into_iter()
+        .chain(<PrefixMap>::from(prefixes).into_inner())
+        .collect())
+}
+
+/// Resolve the terms of a subscription pattern into their N-Triples form, the variables being
+/// resolved as [None] as they match any term.
+///
+/// The prefixes registered in the store are available to the pattern, the given ones taking
+/// precedence over them.
+pub fn subscription_terms(
+    storage: &dyn Storage,
+    prefixes: Vec<Prefix>,
+    pattern: TriplePattern,
+) -> StdResult<[Option<String>; 3]> {
+    let prefixes = prefix_map(storage, prefixes)?;
+    let blank_node_err =
+        || StdError::generic_err("Blank nodes are not allowed in a subscription pattern");
+    let quoted_triple_err = || {
+        StdError::generic_err("Quoted triple patterns are not allowed in a subscription pattern")
+    };
+
+    let subject = match pattern.subject {
+        VarOrNode::Variable(_) => None,
+        VarOrNode::Node(msg::Node::BlankNode(_)) => Err(blank_node_err())?,
+        VarOrNode::Node(node) => Some(rdf::Value::try_from((node, &prefixes))?),
+        VarOrNode::QuotedTriple(_) => Err(quoted_triple_err())?,
+    };
+    let predicate = match pattern.predicate {
+        VarOrNamedNode::Variable(_) => None,
+        VarOrNamedNode::NamedNode(iri) => Some(rdf::Value::NamedNode(
+            rdf::Property::try_from((iri, &prefixes))?.0,
+        )),
+    };
+    let object = match pattern.object {
+        VarOrNodeOrLiteral::Variable(_) => None,
+        VarOrNodeOrLiteral::Node(msg::Node::BlankNode(_)) => Err(blank_node_err())?,
+        VarOrNodeOrLiteral::Node(node) => Some(rdf::Value::try_from((node, &prefixes))?),
+        VarOrNodeOrLiteral::Literal(literal) => Some(rdf::Value::try_from((literal, &prefixes))?),
+        VarOrNodeOrLiteral::QuotedTriple(_) => Err(quoted_triple_err())?,
+    };
+
+    Ok([subject, predicate, object].map(|term| term.as_ref().map(n_triples_term)))
+}
+
+/// The N-Triples form of a named node or a literal.
+fn n_triples_term(value: &rdf::Value) -> String {
+    match value {
+        rdf::Value::NamedNode(iri) | rdf::Value::BlankNode(iri) => NamedNode { iri }.to_string(),
+        rdf::Value::LiteralSimple(value) => Literal::Simple { value }.to_string(),
+        rdf::Value::LiteralLang(value, language) => {
+            Literal::LanguageTaggedString { value, language }.to_string()
+        }
+        rdf::Value::LiteralDatatype(value, datatype) => Literal::Typed {
+            value,
+            datatype: NamedNode { iri: datatype },
+        }
+        .to_string(),
+        rdf::Value::Triple(atom) => {
+            atom.with_triple(false, &mut |triple| format!("<< {triple} >>"))
+        }
+    }
+}
+
+/// Build the evaluation plan of a where clause, the optional skip and limit being applied to its
+/// solutions, and its triple patterns being expanded over the class and property hierarchies if an
+/// inference is given.
+///
+/// The prefixes registered in the store are available to the where clause, the given ones taking
+/// precedence over them. Its service clauses are evaluated through the querier, if given, and
+/// rejected otherwise.
+pub fn plan(
+    storage: &dyn Storage,
+    querier: Option<QuerierWrapper<'_>>,
+    prefixes: Vec<Prefix>,
+    r#where: Option<&WhereClause>,
+    skip: Option<usize>,
+    limit: Option<usize>,
+    inference: Option<Inference>,
+) -> StdResult<Plan> {
+    let prefixes = prefix_map(storage, prefixes)?;
+    let mut plan_builder = PlanBuilder::new(storage, &prefixes, None);
+    if let Some(querier) = querier {
+        plan_builder = plan_builder.with_querier(querier);
+    }
+    if let Some(skip) = skip {
+        plan_builder = plan_builder.with_skip(skip);
+    }
+    if let Some(limit) = limit {
+        plan_builder = plan_builder.with_limit(limit);
+    }
+    if STORE
+        .may_load(storage)?
+        .is_some_and(|store| store.literal_normalization)
+    {
+        plan_builder = plan_builder.with_literal_normalization();
+    }
+    if let Some(inference) = inference {
+        plan_builder = plan_builder.with_inference(
+            inference.max_depth as usize,
+            inference.same_as_depth as usize,
+            inference.max_pattern_count as usize,
+        );
+    }
+    let plan = match r#where {
+        Some(w) => plan_builder.build_plan(w)?,
+        None => QueryPlan::empty_plan(),
+    };
+    let namespaces = plan_builder.cached_namespaces();
+
+    Ok(Plan {
+        plan,
+        prefixes,
+        namespaces,
+    })
+}
+
+/// Execute the plan, selecting the given items from its solutions.
+pub fn select(
+    storage: &dyn Storage,
+    plan: Plan,
+    selection: Vec<SelectItem>,
+    skolemize: bool,
+) -> StdResult<SelectResponse> {
+    let engine = QueryEngine::new(storage, plan.namespaces.clone());
+    let SelectResults { head, solutions } = engine.select(plan.plan, selection)?;
+
+    Ok(SelectResponse {
+        head: Head { vars: head },
+        results: serialize_solutions(storage, solutions, plan.namespaces, skolemize)?,
+    })
+}
+
+/// Execute the plan, selecting the given items from its solutions ordered by the keys of the terms
+/// bound to them, in the order of the items names, and keeping up to `limit` of them.
+///
+/// Unlike the order of [select], which follows the evaluation of the plan, this order only depends
+/// on the stored terms, so it remains the same whatever the indexes and joins chosen to evaluate the
+/// plan. The plan is expected not to be limited, as all its solutions have to be evaluated, up to
+/// `max_solutions` of them, the evaluation failing beyond.
+pub fn select_ordered(
+    storage: &dyn Storage,
+    plan: Plan,
+    selection: Vec<SelectItem>,
+    skolemize: bool,
+    limit: usize,
+    max_solutions: u32,
+) -> StdResult<SelectResponse> {
+    let engine = QueryEngine::new(storage, plan.namespaces.clone());
+    let SelectResults { head, solutions } = engine.select(plan.plan, selection)?;
+    let mut solutions = solutions
+        .enumerate()
+        .map(|(i, solution)| {
+            if i >= max_solutions as usize {
+                Err(QueryComplexityError::OrderedSolutionCount(max_solutions))?;
+            }
+            solution
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    solutions.sort_by_cached_key(|solution| {
+        solution
+            .values()
+            .map(ResolvedVariable::key)
+            .collect::<Vec<_>>()
+    });
+    solutions.truncate(limit);
+
+    Ok(SelectResponse {
+        head: Head { vars: head },
+        results: serialize_solutions(
+            storage,
+            solutions.into_iter().map(Ok),
+            plan.namespaces,
+            skolemize,
+        )?,
+    })
+}
+
+/// Execute the plan, counting its solutions without resolving their values.
+pub fn count(storage: &dyn Storage, plan: Plan) -> StdResult<u64> {
+    let engine = QueryEngine::new(storage, plan.namespaces);
+    let count = engine
+        .eval_plan(plan.plan)
+        .try_fold(0u64, |count, solution| solution.map(|_| count + 1));
+    count
+}
+
+/// Scan up to `limit` of the named subjects whose key follows `after`, in the order of their key,
+/// returning the ones described in the graph, in its N-Quads form, and bound to the variable by a
+/// solution of the plan, along with their provenance.
+///
+/// The plan is evaluated for each scanned subject, bound to the variable, rather than in full, so
+/// the subjects can be processed in batches resumed from the key of the last scanned one, returned
+/// if some may remain, each batch only costing the subjects it scans.
+pub fn bound_subjects(
+    storage: &dyn Storage,
+    plan: Plan,
+    variable: &str,
+    graph: Option<&str>,
+    after: Option<&[u8]>,
+    limit: usize,
+) -> StdResult<(Vec<(Vec<u8>, Provenance)>, Option<Vec<u8>>)> {
+    let index = plan
+        .plan
+        .get_var_index(variable)
+        .ok_or_else(|| StdError::generic_err("Selected variable not found in query"))?;
+    let variable_count = plan.plan.variables.len();
+    let engine = QueryEngine::new(storage, plan.namespaces);
+    let eval = engine.eval_plan_with(plan.plan);
+
+    let page = SUBJECT_PROVENANCE
+        .range(storage, after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let cursor = match page.last() {
+        Some((key, _)) if page.len() == limit => Some(key.clone()),
+        _ => None,
+    };
+
+    let mut term_querier = TermQuerier::new();
+    let mut subjects = vec![];
+    for (key, provenance) in page {
+        if provenance.graph.as_deref() != graph {
+            continue;
+        }
+        let mut vars = ResolvedVariables::with_capacity(variable_count);
+        vars.merge_index(
+            index,
+            ResolvedVariable::Subject(stored_subject(storage, &mut term_querier, &key)?),
+        );
+        if eval(vars).next().transpose()?.is_some() {
+            subjects.push((key, provenance));
+        }
+    }
+
+    Ok((subjects, cursor))
+}
+
+/// Resolve the named subject, or quoted triple, of the given key from the term dictionary.
+fn stored_subject(
+    storage: &dyn Storage,
+    term_querier: &mut TermQuerier,
+    key: &[u8],
+) -> StdResult<state::Subject> {
+    match TermRef::from_key(key) {
+        Some(TermRef::Term(id)) => match term_querier.resolve(storage, id)? {
+            Term::Node(node) => Ok(state::Subject::Named(node)),
+            Term::Triple(triple) => Ok(state::Subject::Triple(Box::new(
+                triple.resolve(storage, term_querier)?,
+            ))),
+            Term::Literal(_) => Err(StdError::generic_err("Unexpected literal term as subject")),
+        },
+        _ => Err(StdError::generic_err("Invalid subject key")),
+    }
+}
+
+/// Execute the plan within a budget of index rows, resuming from the cursor if any, and map its
+/// solutions into their message representation.
+///
+/// When the budget gets exhausted, the solutions of the driving pattern row being evaluated are
+/// discarded so the evaluation can be resumed from the next call at a row boundary.
+pub fn select_budgeted(
+    storage: &dyn Storage,
+    plan: Plan,
+    selection: Vec<SelectItem>,
+    max_rows: u32,
+    cursor: Option<SelectCursor>,
+    skolemize: bool,
+) -> StdResult<BudgetedSelectResponse> {
+    let (skip_rows, returned) = cursor.map_or((0, 0), |c| (c.rows.u64(), c.results));
+    let budget = Budget::new(max_rows.into(), skip_rows);
+    let engine = QueryEngine::with_budget(storage, plan.namespaces.clone(), &budget);
+    let SelectResults { head, solutions } = engine.select(plan.plan, selection)?;
+
+    let mut evaluated: Vec<(u64, StdResult<BTreeMap<String, ResolvedVariable>>)> = vec![];
+    for solution in solutions {
+        evaluated.push((budget.driving_rows(), solution));
+    }
+
+    let cursor = if budget.is_exhausted() {
+        let completed_rows = budget.driving_rows().saturating_sub(1);
+        if completed_rows == 0 {
+            Err(QueryComplexityError::RowsBudget(max_rows))?;
+        }
+        evaluated.retain(|(row, _)| *row <= completed_rows);
+
+        Some(SelectCursor {
+            rows: (skip_rows + completed_rows).into(),
+            results: returned + evaluated.len() as u32,
+        })
+    } else {
+        None
+    };
+
+    Ok(BudgetedSelectResponse {
+        head: Head { vars: head },
+        results: serialize_solutions(
+            storage,
+            evaluated.into_iter().map(|(_, solution)| solution),
+            plan.namespaces,
+            skolemize,
+        )?,
+        cursor,
+    })
+}
+
+/// The maximum number of rows counted when estimating the rows matched by a triple pattern.
+pub const ROWS_ESTIMATE_CAP: usize = 1000;
+
+/// Describe the plan without executing it: its nodes in evaluation order, the index used to match
+/// each triple pattern given the variables bound by the previous nodes, and the number of rows
+/// matching their constant terms.
+pub fn explain(storage: &dyn Storage, plan: &Plan) -> StdResult<PlanNode> {
+    explain_node(
+        storage,
+        &plan.plan.entrypoint,
+        &plan.plan.variables,
+        &BTreeSet::new(),
+    )
+}
+
+fn explain_node(
+    storage: &dyn Storage,
+    node: &QueryNode,
+    variables: &[PlanVariable],
+    bound: &BTreeSet<usize>,
+) -> StdResult<PlanNode> {
+    let explain = |node: &QueryNode, bound: &BTreeSet<usize>| {
+        explain_node(storage, node, variables, bound).map(Box::new)
+    };
+
+    Ok(match node {
+        QueryNode::TriplePattern {
+            subject,
+            predicate,
+            object,
+        } => {
+            let (subject_term, s) = explain_term(subject, variables, bound);
+            let (predicate_term, p) = explain_term(predicate, variables, bound);
+            let (object_term, o) = explain_term(object, variables, bound);
+
+            PlanNode::TriplePattern {
+                subject: subject_term,
+                predicate: predicate_term,
+                object: object_term,
+                index: match (s, p, o) {
+                    (true, true, true) => TripleIndex::Lookup,
+                    (true, true, false) | (true, false, false) => {
+                        TripleIndex::SubjectPredicateObject
+                    }
+                    (false, true, true) | (false, false, true) => {
+                        TripleIndex::ObjectPredicateSubject
+                    }
+                    (true, false, true) => TripleIndex::ObjectSubjectPredicate,
+                    (false, true, false) => TripleIndex::PredicateObjectSubject,
+                    (false, false, false) => TripleIndex::FullScan,
+                },
+                estimated_rows: count_pattern_rows(
+                    storage,
+                    subject,
+                    predicate,
+                    object,
+                    ROWS_ESTIMATE_CAP,
+                )? as u32,
+            }
+        }
+        QueryNode::Noop { .. } => PlanNode::Noop {},
+        QueryNode::CartesianProductJoin { left, right } => PlanNode::CartesianProductJoin {
+            left: explain(left, bound)?,
+            right: explain(right, bound)?,
+        },
+        QueryNode::ForLoopJoin { left, right } => PlanNode::ForLoopJoin {
+            left: explain(left, bound)?,
+            right: explain(
+                right,
+                &bound.union(&left.bound_variables()).cloned().collect(),
+            )?,
+        },
+        QueryNode::AdaptiveJoin { children } => PlanNode::AdaptiveJoin {
+            children: children
+                .iter()
+                .map(|child| explain_node(storage, child, variables, bound))
+                .collect::<StdResult<_>>()?,
+        },
+        QueryNode::Union { left, right } => PlanNode::Union {
+            left: explain(left, bound)?,
+            right: explain(right, bound)?,
+        },
+        QueryNode::Filter { inner, .. } => PlanNode::Filter {
+            inner: explain(inner, bound)?,
+        },
+        QueryNode::Service {
+            contract_addr,
+            solutions,
+            ..
+        } => PlanNode::Service {
+            contract_addr: contract_addr.clone(),
+            solutions: solutions.len() as u32,
+        },
+        QueryNode::Graph { graphs, child } => PlanNode::Graph {
+            child: explain(child, bound)?,
+            graphs: graphs.iter().cloned().collect(),
+        },
+        QueryNode::Skip { child, first } => PlanNode::Skip {
+            child: explain(child, bound)?,
+            first: *first as u32,
+        },
+        QueryNode::Limit { child, first } => PlanNode::Limit {
+            child: explain(child, bound)?,
+            first: *first as u32,
+        },
+        QueryNode::QuotedTriple {
+            child,
+            subject,
+            predicate,
+            object,
+            ..
+        } => {
+            let child_bound = bound.union(&child.bound_variables()).cloned().collect();
+            PlanNode::QuotedTriple {
+                child: explain(child, bound)?,
+                subject: explain_term(subject, variables, &child_bound).0,
+                predicate: explain_term(predicate, variables, &child_bound).0,
+                object: explain_term(object, variables, &child_bound).0,
+            }
+        }
+    })
+}
+
+/// Describe a triple pattern term, returning along whether its value is known when evaluated.
+fn explain_term<T>(
+    value: &PatternValue<T>,
+    variables: &[PlanVariable],
+    bound: &BTreeSet<usize>,
+) -> (PatternTerm, bool) {
+    match value {
+        PatternValue::Constant(_) => (PatternTerm::Constant {}, true),
+        PatternValue::Variable(v) | PatternValue::BlankVariable(v) => {
+            let name = match &variables[*v] {
+                PlanVariable::Basic(name) => name.clone(),
+                PlanVariable::BlankNode(name) => format!("_:{name}"),
+                PlanVariable::QuotedTriple => format!("<<{v}>>"),
+            };
+            let bound = bound.contains(v);
+            (PatternTerm::Variable { name, bound }, bound)
+        }
+    }
+}
+
+/// Execute the plan of a single triple pattern, returning up to `limit` of the stored triples
+/// matching it along with their insertion.
+pub fn provenance(
+    storage: &dyn Storage,
+    plan: Plan,
+    limit: usize,
+) -> StdResult<ProvenanceResponse> {
+    let (subject, predicate, object) = match plan.plan.entrypoint {
+        QueryNode::TriplePattern {
+            subject,
+            predicate,
+            object,
+        } => (subject, predicate, object),
+        QueryNode::Noop { .. } => return Ok(ProvenanceResponse { triples: vec![] }),
+        _ => Err(StdError::generic_err(
+            "Provenance plan shall be a single triple pattern",
+        ))?,
+    };
+
+    let mut term_querier = TermQuerier::new();
+    let mut ns_solver = NamespaceResolver::new(storage, plan.namespaces);
+    let mut id_issuer = IdentifierIssuer::new("b", 0u128);
+    let triples = pattern_triples(storage, &subject, &predicate, &object)
+        .take(limit)
+        .map(|res| -> StdResult<TripleProvenance> {
+            let stored = res?;
+            let insertion = TRIPLE_INSERTIONS.may_load(storage, &stored.key())?;
+            let triple = stored.resolve(storage, &mut term_querier)?;
+
+            Ok(TripleProvenance {
+                subject: ResolvedVariable::Subject(triple.subject)
+                    .as_value(&mut ns_solver, &mut id_issuer)?,
+                predicate: ResolvedVariable::Predicate(triple.predicate)
+                    .as_value(&mut ns_solver, &mut id_issuer)?,
+                object: ResolvedVariable::Object(triple.object)
+                    .as_value(&mut ns_solver, &mut id_issuer)?,
+                insertion: insertion.map(Into::into),
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(ProvenanceResponse { triples })
+}
+
+/// Execute the plan, constructing triples from the given templates and serializing them in the
+/// given format, the offloaded literals being resolved if a resolver is given.
+pub fn construct(
+    storage: &dyn Storage,
+    plan: Plan,
+    templates: Vec<(VarOrNode, VarOrNamedNode, VarOrNodeOrLiteral)>,
+    format: &DataFormat,
+    literals: Option<&OffloadedLiterals<'_>>,
+) -> StdResult<Vec<u8>> {
+    let mut atoms = QueryEngine::new(storage, plan.namespaces)
+        .construct_atoms(plan.plan, &plan.prefixes, templates)?
+        .collect::<StdResult<Vec<Atom>>>()?;
+    if let Some(literals) = literals {
+        literals.resolve(&mut atoms)?;
+    }
+
+    serialize_atoms(format, &atoms)
+}
+
+/// Execute the plan, constructing the triples of its first solutions up to the page size, and tell
+/// whether solutions remain beyond the page, the plan skipping the solutions of the previous pages.
+/// The blank nodes of the templates are minted for each solution if `fresh_blank_nodes` is set, and
+/// the offloaded literals resolved if a resolver is given.
+///
+/// So that the pages can be concatenated, the blank nodes are labelled consistently across them:
+/// the stored ones after their identifier in the store, and the minted ones after the number of
+/// their solution among all the pages.
+#[allow(clippy::too_many_arguments)]
+pub fn construct_page(
+    storage: &dyn Storage,
+    plan: Plan,
+    templates: Vec<(VarOrNode, VarOrNamedNode, VarOrNodeOrLiteral)>,
+    format: &DataFormat,
+    skipped: usize,
+    page_size: usize,
+    fresh_blank_nodes: bool,
+    literals: Option<&OffloadedLiterals<'_>>,
+) -> StdResult<(Vec<u8>, bool)> {
+    let (mut atoms, remaining) = QueryEngine::new(storage, plan.namespaces).construct_atoms_page(
+        plan.plan,
+        &plan.prefixes,
+        templates,
+        skipped,
+        page_size,
+        fresh_blank_nodes,
+    )?;
+    if let Some(literals) = literals {
+        literals.resolve(&mut atoms)?;
+    }
+
+    Ok((serialize_atoms(format, &atoms)?, remaining))
+}
+
+/// The skolem IRI of a stored blank node, see [SelectQuery::skolemize].
+pub fn skolem_iri(blank: state::BlankNode) -> String {
+    format!("urn:bnode:{blank}")
+}
+
+fn serialize_solutions(
+    storage: &dyn Storage,
+    solutions: impl IntoIterator<Item = StdResult<BTreeMap<String, ResolvedVariable>>>,
+    ns_cache: Vec<Namespace>,
+    skolemize: bool,
+) -> StdResult<Results> {
+    let mut ns_solver = NamespaceResolver::new(storage, ns_cache);
+    let mut id_issuer = IdentifierIssuer::new("b", 0u128);
+
+    let mut bindings: Vec<BTreeMap<String, Value>> = vec![];
+    for solution in solutions {
+        let vars = solution?;
+        let resolved = vars
+            .into_iter()
+            .map(|(name, var)| -> StdResult<(String, Value)> {
+                let value = match var.as_object() {
+                    Some(state::Object::Blank(blank)) if skolemize => Value::URI {
+                        value: msg::IRI::Full(skolem_iri(blank)),
+                    },
+                    _ => var.as_value(&mut ns_solver, &mut id_issuer)?,
+                };
+                Ok((name, value))
+            })
+            .collect::<StdResult<BTreeMap<String, Value>>>()?;
+        bindings.push(resolved);
+    }
+
+    Ok(Results { bindings })
+}
+
+/// Export a page of up to `limit` of the stored triples, in the order of their key and starting
+/// after the given one, serialized in the given format along with the key of the last exported
+/// triple if some remain.
+///
+/// Unlike the other serializations, blank nodes are written as such and labelled after their
+/// identifier in the store, so their labels are consistent across pages.
+///
+/// In N-Quads, the triples are written in the graph their subject has first been described in, as
+/// they are matched by the queries, the triples about blank nodes being written in the default one.
+pub fn export(
+    storage: &dyn Storage,
+    after: Option<Vec<u8>>,
+    limit: usize,
+    format: &DataFormat,
+) -> StdResult<(Vec<u8>, Option<Vec<u8>>)> {
+    let mut page = triples()
+        .range_raw(
+            storage,
+            after.map(Bound::ExclusiveRaw),
+            None,
+            Order::Ascending,
+        )
+        .take(limit + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    let cursor = if page.len() > limit {
+        page.truncate(limit);
+        page.last().map(|(key, _)| key.clone())
+    } else {
+        None
+    };
+
+    let mut ns_solver = NamespaceResolver::new(storage, vec![]);
+    let mut term_querier = TermQuerier::new();
+    let atoms = page
+        .into_iter()
+        .map(|(_, stored)| {
+            let graph = match stored.subject {
+                TermRef::Blank(_) => None,
+                _ => SUBJECT_PROVENANCE
+                    .may_load(storage, &stored.subject.key())?
+                    .and_then(|provenance| provenance.graph),
+            };
+            stored
+                .resolve(storage, &mut term_querier)
+                .and_then(|triple| stored_triple_as_atom(&mut ns_solver, triple))
+                .map(|atom| (atom, graph))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    serialize(
+        format,
+        atoms.iter().map(|(atom, graph)| (atom, graph.as_deref())),
+        true,
+    )
+    .map(|data| (data, cursor))
+}
+
+/// Returns a page of up to `limit` of the stored triples having the given IRI as object, in the
+/// order of their subject and starting after the given cursor, along with the cursor of the page
+/// following it if some triples remain.
+///
+/// The triples are looked up through the `object_and_subject` index, the cursor being the part of
+/// the key of the last returned entry following the object. As in [export], blank node subjects are
+/// labelled after their identifier in the store.
+pub fn backlinks(
+    storage: &dyn Storage,
+    iri: IRI,
+    after: Option<Vec<u8>>,
+    limit: usize,
+) -> StdResult<(Vec<Backlink>, Option<Vec<u8>>)> {
+    let prefixes = prefix_map(storage, vec![])?;
+    let mut ns_solver = NamespaceResolver::new(storage, vec![]);
+    let object = match iri_as_node(&mut ns_solver, &prefixes, iri) {
+        Ok(node) => TermRef::Term(node.id()),
+        Err(err) if NamespaceQuerier::is_ns_not_found_error(&err) => return Ok((vec![], None)),
+        Err(err) => Err(err)?,
+    };
+
+    let mut page = triples()
+        .idx
+        .object_and_subject
+        .sub_prefix(object.key())
+        .range_raw(
+            storage,
+            after.map(Bound::ExclusiveRaw),
+            None,
+            Order::Ascending,
+        )
+        .take(limit + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    let cursor = if page.len() > limit {
+        page.truncate(limit);
+        page.last()
+            .map(|(pk, triple)| (triple.subject.key(), pk.clone()).joined_key())
+    } else {
+        None
+    };
+
+    let mut term_querier = TermQuerier::new();
+    let mut id_issuer = IdentifierIssuer::new("b", 0u128);
+    let backlinks = page
+        .into_iter()
+        .map(|(_, stored)| -> StdResult<Backlink> {
+            let triple = stored.resolve(storage, &mut term_querier)?;
+            Ok(Backlink {
+                subject: match triple.subject {
+                    state::Subject::Blank(id) => Value::BlankNode {
+                        value: stored_blank_label(id),
+                    },
+                    subject => ResolvedVariable::Subject(subject)
+                        .as_value(&mut ns_solver, &mut id_issuer)?,
+                },
+                predicate: IRI::Full(triple.predicate.as_iri(&mut ns_solver)?),
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok((backlinks, cursor))
+}
+
+pub(crate) fn serialize_atoms(format: &DataFormat, atoms: &[Atom]) -> StdResult<Vec<u8>> {
+    serialize(format, atoms.iter().map(|atom| (atom, None)), false)
+}
+
+/// Serialize the atoms along with the graph they belong to, in its N-Quads form, [None] standing
+/// for the default graph.
+fn serialize<'a>(
+    format: &DataFormat,
+    atoms: impl IntoIterator<Item = (&'a Atom, Option<&'a str>)>,
+    blank_nodes: bool,
+) -> StdResult<Vec<u8>> {
+    let mut writer = TripleWriter::new(&format.try_into()?, Vec::default());
+    for (atom, graph) in atoms {
+        let graph_name = graph
+            .and_then(|g| g.strip_prefix('<'))
+            .and_then(|g| g.strip_suffix('>'))
+            .map(|iri| NamedNode { iri }.into());
+        atom.with_triple(blank_nodes, &mut |triple| {
+            writer.write_in_graph(&triple, graph_name).map_err(|e| {
+                StdError::serialize_err(
+                    "triple",
+                    format!("Error writing triple {}: {}", &triple, e),
+                )
+            })
+        })?;
+    }
+    writer
+        .finish()
+        .map_err(|e| StdError::serialize_err("triple", format!("Error writing triple: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::{Expression, TriplePattern};
+    use cosmwasm_std::Uint128;
+
+    #[test]
+    fn select_validation() {
+        let limits = StoreLimits {
+            max_triple_count: Uint128::MAX,
+            max_byte_size: Uint128::MAX,
+            max_triple_byte_size: Uint128::MAX,
+            max_query_limit: 10,
+            max_query_variable_count: 1,
+            max_insert_data_byte_size: Uint128::MAX,
+            max_insert_data_triple_count: Uint128::MAX,
+            max_query_pattern_count: 2,
+            max_query_where_depth: 2,
+            max_query_join_variable_count: 3,
+            max_query_inference_depth: 3,
+            max_result_byte_size: Uint128::MAX,
+            max_insert_blank_node_count: Uint128::MAX,
+            max_insert_session_byte_size: Uint128::MAX,
+            max_query_ordered_solution_count: 1000,
+        };
+        let query = |select: usize, limit: Option<u32>| SelectQuery {
+            prefixes: vec![],
+            select: (0..select)
+                .map(|i| SelectItem::Variable(format!("v{i}")))
+                .collect(),
+            r#where: WhereClause::Bgp { patterns: vec![] },
+            limit,
+            reasoning: None,
+            skolemize: false,
+            adaptive_joins: false,
+            ordered: false,
+            from: vec![],
+            from_named: vec![],
+        };
+
+        let cases = vec![
+            (query(1, None), Ok(10)),
+            (query(1, Some(3)), Ok(3)),
+            (
+                query(2, None),
+                Err(StoreError::QueryVariableCount(1).into()),
+            ),
+            (query(1, Some(11)), Err(StoreError::QueryLimit(10).into())),
+        ];
+
+        for (query, expected) in cases {
+            assert_eq!(validate_select(&query, &limits), expected);
+        }
+    }
+
+    #[test]
+    fn where_validation() {
+        let limits = StoreLimits {
+            max_triple_count: Uint128::MAX,
+            max_byte_size: Uint128::MAX,
+            max_triple_byte_size: Uint128::MAX,
+            max_query_limit: 10,
+            max_query_variable_count: 10,
+            max_insert_data_byte_size: Uint128::MAX,
+            max_insert_data_triple_count: Uint128::MAX,
+            max_query_pattern_count: 2,
+            max_query_where_depth: 2,
+            max_query_join_variable_count: 3,
+            max_query_inference_depth: 3,
+            max_result_byte_size: Uint128::MAX,
+            max_insert_blank_node_count: Uint128::MAX,
+            max_insert_session_byte_size: Uint128::MAX,
+            max_query_ordered_solution_count: 1000,
+        };
+        let bgp = |count: usize| WhereClause::Bgp {
+            patterns: (0..count)
+                .map(|i| TriplePattern {
+                    subject: VarOrNode::Variable(format!("s{i}")),
+                    predicate: VarOrNamedNode::Variable(format!("p{i}")),
+                    object: VarOrNodeOrLiteral::Variable(format!("o{i}")),
+                })
+                .collect(),
+        };
+        let join = |left: WhereClause, right: WhereClause| WhereClause::LateralJoin {
+            left: Box::new(left),
+            right: Box::new(right),
+        };
+        let filter = |inner: WhereClause| WhereClause::Filter {
+            expr: Expression::Variable("s0".to_string()),
+            inner: Box::new(inner),
+        };
+
+        let cases = vec![
+            (bgp(2), Ok(())),
+            (join(bgp(1), bgp(1)), Ok(())),
+            (filter(bgp(2)), Ok(())),
+            (bgp(3), Err(QueryComplexityError::PatternCount(2))),
+            (
+                join(bgp(1), bgp(2)),
+                Err(QueryComplexityError::PatternCount(2)),
+            ),
+            (
+                filter(join(bgp(1), bgp(1))),
+                Err(QueryComplexityError::WhereDepth(2)),
+            ),
+        ];
+
+        for (r#where, expected) in cases {
+            assert_eq!(validate_where(&r#where, &limits), expected);
+        }
+    }
+
+    #[test]
+    fn join_variable_validation() {
+        let limits = StoreLimits {
+            max_triple_count: Uint128::MAX,
+            max_byte_size: Uint128::MAX,
+            max_triple_byte_size: Uint128::MAX,
+            max_query_limit: 10,
+            max_query_variable_count: 10,
+            max_insert_data_byte_size: Uint128::MAX,
+            max_insert_data_triple_count: Uint128::MAX,
+            max_query_pattern_count: 10,
+            max_query_where_depth: 10,
+            max_query_join_variable_count: 2,
+            max_query_inference_depth: 3,
+            max_result_byte_size: Uint128::MAX,
+            max_insert_blank_node_count: Uint128::MAX,
+            max_insert_session_byte_size: Uint128::MAX,
+            max_query_ordered_solution_count: 1000,
+        };
+        let pattern = |s: &str, o: &str| TriplePattern {
+            subject: VarOrNode::Variable(s.to_string()),
+            predicate: VarOrNamedNode::NamedNode(msg::IRI::Full("https://ex.org/p".to_string())),
+            object: VarOrNodeOrLiteral::Variable(o.to_string()),
+        };
+        let chain = |variables: &[&str]| WhereClause::Bgp {
+            patterns: variables.windows(2).map(|w| pattern(w[0], w[1])).collect(),
+        };
+
+        let cases = vec![
+            (chain(&["a", "b", "c"]), 1, Ok(())),
+            (chain(&["a", "b", "c", "d"]), 2, Ok(())),
+            (
+                chain(&["a", "b", "c", "d", "e"]),
+                3,
+                Err(QueryComplexityError::JoinVariableCount(2)),
+            ),
+            (
+                WhereClause::LateralJoin {
+                    left: Box::new(chain(&["a", "b", "c"])),
+                    right: Box::new(chain(&["c", "d", "a"])),
+                },
+                4,
+                Err(QueryComplexityError::JoinVariableCount(2)),
+            ),
+        ];
+
+        for (r#where, count, expected) in cases {
+            assert_eq!(join_variable_count(&r#where), count);
+            assert_eq!(validate_where(&r#where, &limits), expected);
+        }
+    }
+}
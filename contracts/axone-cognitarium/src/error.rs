@@ -1,6 +1,7 @@
 use cosmwasm_std::{StdError, Uint128};
 use cw_utils::PaymentError;
 use rio_turtle::TurtleError;
+#[cfg(feature = "rdf-xml")]
 use rio_xml::RdfXmlError;
 use thiserror::Error;
 
@@ -18,13 +19,62 @@ pub enum ContractError {
     #[error("{0}")]
     Store(#[from] StoreError),
 
+    #[error("{0}")]
+    QueryComplexity(#[from] QueryComplexityError),
+
     #[error("Only the owner can perform this operation.")]
     Unauthorized,
 
+    #[error("Only the pending owner can accept the ownership transfer.")]
+    NotPendingOwner,
+
+    #[error("Limit '{0}' is below the current usage: {1}")]
+    LimitBelowUsage(String, Uint128),
+
+    #[error("An insert session is already in progress.")]
+    InsertSessionInProgress,
+
+    #[error("No insert session in progress.")]
+    NoInsertSession,
+
+    #[error("Invalid compressed data: {0}")]
+    InvalidCompressedData(String),
+
+    #[error("The expiration has already been reached.")]
+    AlreadyExpired,
+
+    #[error("Subject '{0}' is already described by another writer.")]
+    SubjectWriterConflict(String),
+
+    #[error("Subject '{0}' is already described in another graph.")]
+    SubjectGraphConflict(String),
+
+    #[error("Not allowed to insert in store '{0}'.")]
+    ShardUnauthorized(String),
+
+    #[error("Batch insertion failed in store '{0}': {1}")]
+    ShardInsertFailed(String, String),
+
+    #[error("Only data insertions and deletions can be batched.")]
+    UnsupportedBatchOperation,
+
+    #[error("The store is near its limits, only the allowed writers can insert.")]
+    StoreNearLimit,
+
+    #[error("A migration of the stored triples is in progress.")]
+    TripleMigrationInProgress,
+
+    #[error("The graphs to move the subjects from and to must differ.")]
+    SameGraph,
+
+    #[error("An unknown reply ID was received.")]
+    UnknownReplyID,
+
     #[error("{0}")]
     Payment(#[from] PaymentError),
 }
 
+#[cfg(feature = "rdf-xml")]
 impl From<RdfXmlError> for ContractError {
     fn from(value: RdfXmlError) -> Self {
         RDFParseError::from(value).into()
@@ -53,6 +103,54 @@ pub enum StoreError {
 
     #[error("Maximum insert triple count exceeded: {0}")]
     InsertDataTripleCount(Uint128),
+
+    #[error("Maximum insert blank node count exceeded: {0}")]
+    InsertBlankNodeCount(Uint128),
+
+    #[error("Maximum insert session byte size exceeded: {0}")]
+    InsertSessionByteSize(Uint128),
+
+    #[error("Maximum query limit exceeded: {0}")]
+    QueryLimit(u32),
+
+    #[error("Maximum query variable count exceeded: {0}")]
+    QueryVariableCount(u32),
+
+    #[error("Maximum result byte size exceeded: {0}")]
+    ResultByteSize(Uint128),
+}
+
+impl From<StoreError> for StdError {
+    fn from(value: StoreError) -> Self {
+        StdError::generic_err(value.to_string())
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum QueryComplexityError {
+    #[error("Maximum query triple pattern count exceeded: {0}")]
+    PatternCount(u32),
+
+    #[error("Maximum query where clause depth exceeded: {0}")]
+    WhereDepth(u32),
+
+    #[error("Maximum query join variable count exceeded: {0}")]
+    JoinVariableCount(u32),
+
+    #[error("Maximum query inference depth exceeded: {0}")]
+    InferenceDepth(u32),
+
+    #[error("Query rows budget too low to evaluate a single row of the first pattern: {0}")]
+    RowsBudget(u32),
+
+    #[error("Maximum number of solutions of an ordered query exceeded: {0}")]
+    OrderedSolutionCount(u32),
+}
+
+impl From<QueryComplexityError> for StdError {
+    fn from(value: QueryComplexityError) -> Self {
+        StdError::generic_err(value.to_string())
+    }
 }
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -64,6 +162,7 @@ pub enum RDFParseError {
     Turtle(String),
 }
 
+#[cfg(feature = "rdf-xml")]
 impl From<RdfXmlError> for RDFParseError {
     fn from(value: RdfXmlError) -> Self {
         RDFParseError::Xml(value.to_string())
@@ -1,4 +1,8 @@
 pub mod contract;
+#[cfg(feature = "library")]
+pub mod engine;
+#[cfg(not(feature = "library"))]
+mod engine;
 mod error;
 pub mod msg;
 mod querier;
@@ -7,3 +11,4 @@ pub mod state;
 mod storer;
 
 pub use crate::error::ContractError;
+pub use crate::querier::parse_date_time;
@@ -1,5 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Binary, Uint128};
+use cosmwasm_std::{Binary, HexBinary, StdError, Uint128, Uint64};
+use cw_utils::Expiration;
 use derive_builder::Builder;
 use std::collections::BTreeMap;
 
@@ -10,6 +11,80 @@ pub struct InstantiateMsg {
     /// Limitations regarding store usage.
     #[serde(default)]
     pub limits: StoreLimitsInput,
+    /// The number of insert batches to retain in the recent window index, allowing to query the
+    /// triples inserted by the last batches through [QueryMsg::RecentChanges].
+    /// If not provided, the index is disabled.
+    #[serde(default)]
+    pub recent_window: Option<u32>,
+    /// The number of entries to retain in the query audit log, recording the where clauses
+    /// evaluated by the executed messages, see [QueryMsg::QueryAudit].
+    /// If not provided, the log is disabled.
+    #[serde(default)]
+    pub query_audit: Option<u32>,
+    /// The policy applied when inserting triples about a subject already described by another
+    /// insertion.
+    /// If not provided, such insertions are allowed.
+    #[serde(default)]
+    pub conflict_policy: ConflictPolicy,
+    /// The offloading of the large literals to an `axone-objectarium` bucket, see [LiteralOffload].
+    /// If not provided, the literals are always kept in the store.
+    #[serde(default)]
+    pub literal_offload: Option<LiteralOffload>,
+    /// Whether to keep the lexical form of the `xsd:integer`, `xsd:decimal`, `xsd:boolean` and
+    /// `xsd:dateTime` literals as inserted, instead of normalizing it to its canonical form (e.g.
+    /// `01` to `1`) so equivalent values match alike.
+    #[serde(default)]
+    pub disable_literal_normalization: bool,
+    /// The threshold past which the store is considered near its limits, see [SoftLimit].
+    /// If not provided, the insertions only fail once the limits are exceeded.
+    #[serde(default)]
+    pub soft_limit: Option<SoftLimit>,
+}
+
+/// Migrate message
+#[cw_serde]
+#[derive(Default)]
+pub struct MigrateMsg {
+    /// Whether to index again all the stored triples once the state migrated, in batches carried on
+    /// through [ExecuteMsg::MigrateTriples]. The migration of a store whose indexes layout differs
+    /// indexes them already.
+    #[serde(default)]
+    pub rebuild_indexes: bool,
+}
+
+/// Sudo messages, only sent by the chain itself (e.g. through a governance proposal), exposing
+/// the owner operations so stores whose owner is no longer able to act can still be administered.
+///
+/// As the execute messages, they are rejected while a migration of the stored triples is in
+/// progress, see [ExecuteMsg::MigrateTriples].
+#[cw_serde]
+pub enum SudoMsg {
+    /// # ClearStore
+    /// Remove the triples from the store, the grants, prefixes and subscriptions being kept.
+    ///
+    /// The removal is bounded, the number of removed triples being reported in the `triple_count`
+    /// attribute of the response, and the `completed` one telling whether the store is now empty or
+    /// the message should be sent again.
+    ClearStore {
+        /// The maximum number of triples to remove.
+        /// If not provided, or greater, the maximum number of triples of a single insertion is used.
+        limit: Option<u32>,
+    },
+
+    /// # UpdateLimits
+    /// Replace the limits of the store, as [ExecuteMsg::UpdateLimits] does.
+    UpdateLimits {
+        /// The new limits of the store.
+        limits: StoreLimitsInput,
+    },
+
+    /// # TransferOwnership
+    /// Set the owner of the store, the transfer being effective immediately and replacing any pending
+    /// one.
+    TransferOwnership {
+        /// The address of the new owner.
+        new_owner: String,
+    },
 }
 
 /// Execute messages
@@ -19,8 +94,12 @@ pub enum ExecuteMsg {
     /// Insert the data as RDF triples in the store.
     /// For already existing triples it acts as no-op.
     ///
-    /// Only the smart contract owner (i.e. the address who instantiated it) is authorized to perform
-    /// this action.
+    /// The response data is an [InsertDataReceipt] reporting the number of triples inserted, skipped
+    /// as already existing and rejected as oversized, so the caller can verify the whole data has
+    /// been persisted.
+    ///
+    /// Only the current smart contract owner, or an address granted the [Permission::Insert]
+    /// permission, is authorized to perform this action.
     InsertData {
         /// The data format in which the triples are serialized.
         /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
@@ -29,12 +108,35 @@ pub enum ExecuteMsg {
         /// The data must be serialized in the format specified by the `format` field. And the data
         /// are subject to the limitations defined by the `limits` specified at contract instantiation.
         data: Binary,
+        /// The compression applied to the data, decompressed before being parsed, the decompressed
+        /// data being subject to the `max_insert_data_byte_size` limit.
+        /// If not provided, the data are not compressed.
+        compression: Option<DataCompression>,
+        /// The expiration of the inserted triples, after which they can be removed by anyone through
+        /// [ExecuteMsg::Purge]. The triples already present in the store keep their expiration, if
+        /// any. If not provided, the triples never expire.
+        expires: Option<Expiration>,
+        /// The IRI of the named graph the triples of the default graph are inserted into, so the
+        /// triple-only formats (e.g. Turtle or N-Triples) can be loaded in a named graph without
+        /// being rewritten as N-Quads. The quads of the other graphs keep their graph. The IRI has to
+        /// be absolute.
+        /// If not provided, the triples are inserted in the default graph.
+        #[serde(default)]
+        graph: Option<String>,
+        /// Whether the triples exceeding the `max_triple_byte_size` limit are skipped, and reported
+        /// as rejected in the [InsertDataReceipt], instead of failing the whole insertion.
+        #[serde(default)]
+        skip_oversized: bool,
     },
 
     /// # DeleteData
     /// Delete the data (RDF triples) from the store matching the patterns defined by the provided
     /// query. For non-existing triples it acts as no-op.
     ///
+    /// When no triple template is provided, the triple patterns of the `where` clause are used as
+    /// templates (i.e. a SPARQL `DELETE WHERE`), removing all the triples matching them at once.
+    /// The number of deleted triples is reported in the `triple_count` attribute of the response.
+    ///
     /// Example:
     /// ```json
     /// {
@@ -62,8 +164,8 @@ pub enum ExecuteMsg {
     ///  ]
     /// ```
     ///
-    /// Only the smart contract owner (i.e. the address who instantiated it) is authorized to perform
-    /// this action.
+    /// Only the current smart contract owner, or an address granted the [Permission::Delete]
+    /// permission, is authorized to perform this action.
     DeleteData {
         /// The prefixes used in the operation.
         prefixes: Vec<Prefix>,
@@ -75,6 +177,541 @@ pub enum ExecuteMsg {
         /// considered for deletion, if any.
         r#where: Option<WhereClause>,
     },
+
+    /// # Purge
+    /// Remove the expired triples from the store, i.e. the triples inserted with an expiration
+    /// which has been reached, the ones which have expired first being removed first.
+    ///
+    /// The number of removed triples is reported in the `triple_count` attribute of the response.
+    ///
+    /// Anyone is authorized to perform this action.
+    Purge {
+        /// The maximum number of triples to remove.
+        /// If not provided, or greater, the maximum number of triples of a single insertion is used.
+        limit: Option<u32>,
+    },
+
+    /// # PruneQueryAudit
+    /// Remove entries of the query audit log, the oldest ones being removed first, e.g. once
+    /// archived off-chain.
+    ///
+    /// The number of removed entries is reported in the `entry_count` attribute of the response.
+    ///
+    /// Only the smart contract owner is authorized to perform this action.
+    PruneQueryAudit {
+        /// The sequence number of the entry before which to remove the entries, excluded.
+        /// If not provided, all the entries are removed.
+        before: Option<Uint64>,
+        /// The maximum number of entries to remove.
+        /// If not provided, or greater, the maximum query limit of the store is used.
+        limit: Option<u32>,
+    },
+
+    /// # TransferOwnership
+    /// Propose a new owner for the store, the transfer being effective only once accepted by the
+    /// proposed owner through [ExecuteMsg::AcceptOwnership]. A new proposal replaces the pending one.
+    ///
+    /// Only the smart contract owner is authorized to perform this action.
+    TransferOwnership {
+        /// The address of the proposed owner.
+        new_owner: String,
+    },
+
+    /// # AcceptOwnership
+    /// Accept the pending ownership transfer, the sender becoming the owner of the store and thus the
+    /// only address authorized to insert and delete data.
+    ///
+    /// Only the proposed owner is authorized to perform this action.
+    AcceptOwnership {},
+
+    /// # CancelOwnershipTransfer
+    /// Withdraw the pending ownership transfer, if any, so the proposed owner can no longer accept
+    /// it.
+    ///
+    /// Only the smart contract owner is authorized to perform this action.
+    CancelOwnershipTransfer {},
+
+    /// # UpdateLimits
+    /// Replace the limits of the store, the limits not provided being set to their default value.
+    /// The new limits can't be below the current usage of the store, i.e. its number of triples and
+    /// its byte size.
+    ///
+    /// Only the smart contract owner is authorized to perform this action.
+    UpdateLimits {
+        /// The new limits of the store.
+        limits: StoreLimitsInput,
+    },
+
+    /// # UpdateConflictPolicy
+    /// Replace the policy applied when inserting triples about a subject already described by another
+    /// insertion, see [ConflictPolicy].
+    ///
+    /// Only the smart contract owner is authorized to perform this action.
+    UpdateConflictPolicy {
+        /// The new conflict policy of the store.
+        policy: ConflictPolicy,
+    },
+
+    /// # UpdateLiteralOffload
+    /// Replace the offloading of the large literals, see [LiteralOffload], disabling it if not
+    /// provided. The literals already offloaded are only resolved back to their value as long as
+    /// they have been offloaded to the configured bucket.
+    ///
+    /// Only the smart contract owner is authorized to perform this action.
+    UpdateLiteralOffload {
+        /// The new literal offloading of the store.
+        literal_offload: Option<LiteralOffload>,
+    },
+
+    /// # UpdateSoftLimit
+    /// Replace the threshold past which the store is considered near its limits, see [SoftLimit],
+    /// removing it if not provided.
+    ///
+    /// Only the smart contract owner is authorized to perform this action.
+    UpdateSoftLimit {
+        /// The new soft limit of the store.
+        soft_limit: Option<SoftLimit>,
+    },
+
+    /// # GrantPermissions
+    /// Grant permissions to an address other than the owner, allowing it to insert and/or delete
+    /// data. Already granted permissions are kept.
+    ///
+    /// Only the smart contract owner is authorized to perform this action.
+    GrantPermissions {
+        /// The address to grant the permissions to.
+        grantee: String,
+        /// The permissions to grant.
+        permissions: Vec<Permission>,
+    },
+
+    /// # RevokePermissions
+    /// Revoke permissions previously granted to an address. Revoking a permission not granted is a
+    /// no-op.
+    ///
+    /// Only the smart contract owner is authorized to perform this action.
+    RevokePermissions {
+        /// The address to revoke the permissions from.
+        grantee: String,
+        /// The permissions to revoke.
+        permissions: Vec<Permission>,
+    },
+
+    /// # RegisterPrefixes
+    /// Register well-known prefixes (e.g. `rdf`, `rdfs`, `schema`) in the store, allowing prefixed
+    /// IRIs to be expanded in queries not declaring them. Registering an already registered prefix
+    /// replaces its namespace.
+    ///
+    /// A prefix declared in a query takes precedence over the registered one.
+    ///
+    /// Only the smart contract owner is authorized to perform this action.
+    RegisterPrefixes {
+        /// The prefixes to register.
+        prefixes: Vec<Prefix>,
+    },
+
+    /// # UnregisterPrefixes
+    /// Unregister prefixes previously registered in the store. Unregistering a prefix not registered
+    /// is a no-op.
+    ///
+    /// Only the smart contract owner is authorized to perform this action.
+    UnregisterPrefixes {
+        /// The prefixes to unregister.
+        prefixes: Vec<String>,
+    },
+
+    /// # RegisterQuery
+    /// Register a select query under a name, for it to be evaluated through the
+    /// [QueryMsg::StoredQuery] query instead of being sent in full. Registering a query under an
+    /// already registered name replaces it.
+    ///
+    /// Only the smart contract owner is authorized to perform this action.
+    RegisterQuery {
+        /// The name of the query.
+        name: String,
+        /// The query.
+        query: SelectQuery,
+        /// The variables of the query the caller binds to a value at evaluation. They must occur in
+        /// the triple patterns of the query and can't be selected.
+        #[serde(default)]
+        parameters: Vec<String>,
+    },
+
+    /// # UnregisterQuery
+    /// Unregister a query previously registered in the store. Unregistering a query not registered
+    /// is a no-op.
+    ///
+    /// Only the smart contract owner is authorized to perform this action.
+    UnregisterQuery {
+        /// The name of the query.
+        name: String,
+    },
+
+    /// # BeginInsert
+    /// Open an insert session for the sender, allowing to load data too large for a single
+    /// [ExecuteMsg::InsertData] by sending it in several chunks through [ExecuteMsg::InsertChunk]
+    /// over multiple transactions, the whole data being inserted at once by
+    /// [ExecuteMsg::CommitInsert].
+    ///
+    /// A sender can only have one session in progress at a time.
+    ///
+    /// Only the smart contract owner, or an address granted the [Permission::Insert] permission, is
+    /// authorized to perform this action.
+    BeginInsert {
+        /// The data format in which the triples are serialized.
+        /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
+        format: Option<DataFormat>,
+        /// The IRI of the named graph the triples of the default graph are inserted into, as for
+        /// [ExecuteMsg::InsertData].
+        /// If not provided, the triples are inserted in the default graph.
+        #[serde(default)]
+        graph: Option<String>,
+    },
+
+    /// # InsertChunk
+    /// Append a chunk of data to the insert session of the sender. The chunks are concatenated as
+    /// raw bytes, hence they don't need to be split on triple boundaries, and are only parsed at
+    /// commit. The session can't buffer more than the [StoreLimits::max_insert_session_byte_size]
+    /// limit.
+    InsertChunk {
+        /// The chunk of data to append.
+        data: Binary,
+    },
+
+    /// # CommitInsert
+    /// Insert the data of the insert session of the sender as RDF triples in the store, closing the
+    /// session. The insertion is atomic: if the data are invalid or exceed the store limits, nothing
+    /// is inserted and the session is kept.
+    ///
+    /// The `max_triple_count`, `max_byte_size` and `max_triple_byte_size` limits apply, while the
+    /// limits on the size of a single insertion don't.
+    CommitInsert {},
+
+    /// # AbortInsert
+    /// Discard the insert session of the sender along with its chunks.
+    AbortInsert {},
+
+    /// # Subscribe
+    /// Register a triple pattern on behalf of the sender, which is notified of the inserted triples
+    /// matching it through a [SubscriptionCallbackMsg::TriplesInserted] message, once per
+    /// insertion.
+    ///
+    /// The variables of the pattern match any term, without being joined, and blank nodes are not
+    /// allowed. A failing callback doesn't fail the insertion, its failure is only reported in
+    /// the `subscription_callback_failed` action attributes.
+    ///
+    /// The identifier of the subscription is returned in the `subscription_id` attribute.
+    ///
+    /// Only the smart contract owner, or an address granted the [Permission::Subscribe] permission,
+    /// is authorized to perform this action.
+    Subscribe {
+        /// The prefixes used in the pattern.
+        prefixes: Vec<Prefix>,
+        /// The pattern the inserted triples shall match.
+        pattern: TriplePattern,
+    },
+
+    /// # Unsubscribe
+    /// Remove a subscription.
+    ///
+    /// Only the subscriber or the smart contract owner is authorized to perform this action.
+    Unsubscribe {
+        /// The identifier of the subscription.
+        id: u64,
+    },
+
+    /// # InsertBatch
+    /// Insert the data as RDF triples in the store, as [ExecuteMsg::InsertData], along with
+    /// sub-batches forwarded to sibling stores, e.g. the shards of a dataset split across several
+    /// named graphs, the whole batch being inserted all or nothing.
+    ///
+    /// Each sub-batch is sent to its store as an [ExecuteMsg::InsertData] on behalf of this store,
+    /// which must thus be allowed to insert in it, the sender having to be allowed to insert in it
+    /// too, as its owner or granted the [Permission::Insert] permission there. If any of them fails, the insertion of this store
+    /// is rolled back along with the others and the failure is reported with the address of the
+    /// failing store.
+    ///
+    /// The response data is an [InsertBatchReceipt] gathering the [InsertDataReceipt] of this store
+    /// and of each sibling store, along with their total.
+    ///
+    /// Only the smart contract owner, or an address granted the [Permission::Insert] permission, is
+    /// authorized to perform this action.
+    InsertBatch {
+        /// The data format in which the triples of all the sub-batches are serialized.
+        /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
+        format: Option<DataFormat>,
+        /// The data to insert in this store, if any.
+        data: Binary,
+        /// The sub-batches to insert in the sibling stores.
+        shards: Vec<ShardBatch>,
+    },
+
+    /// # MoveGraph
+    /// Move the named subjects bound to a variable of a where clause from a graph to another, e.g.
+    /// when restructuring the graph naming scheme of the store, in batches resumable through a
+    /// cursor.
+    ///
+    /// As the store only keeps track of the graph of the triples through the provenance of their
+    /// subject (see [ConflictPolicy]), the subjects are moved along with all the triples about
+    /// them, the ones not described in the `from` graph being left untouched.
+    ///
+    /// The named subjects of the store are scanned in the order of their stored key, up to `limit`
+    /// at a time, the where clause being evaluated for each of the ones described in the `from`
+    /// graph, bound to the variable, so a batch only costs the subjects it scans. The number of
+    /// moved subjects is reported in the `subject_count` attribute of the response, along with a
+    /// base64 encoded `cursor` attribute, the key of the last scanned subject, to scan the following
+    /// ones with if some may remain. As the batches resume after this key, the subjects moved or
+    /// inserted in between don't shift the following ones.
+    ///
+    /// Only the current smart contract owner is authorized to perform this action.
+    MoveGraph {
+        /// The subjects to move.
+        subjects: SubjectSelection,
+        /// The IRI of the graph to move the subjects from, the default graph if not provided.
+        from: Option<String>,
+        /// The IRI of the graph to move the subjects to, the default graph if not provided.
+        to: Option<String>,
+        /// The cursor returned by the previous batch, the first subjects being scanned if not
+        /// provided.
+        cursor: Option<Binary>,
+        /// The maximum number of subjects to scan.
+        /// If not provided, the maximum query limit of the store is used, which it can't exceed.
+        limit: Option<u32>,
+    },
+
+    /// # RebuildIndexes
+    /// Repair the secondary indexes of the triples so they agree with the stored triples, e.g.
+    /// after a faulty migration, in batches resumable through a cursor.
+    ///
+    /// The triples are scanned first, the entries missing from the indexes being written, then the
+    /// entries of each index, the ones not referencing a stored triple being removed (see
+    /// [QueryMsg::CheckIntegrity]). The number of scanned records and of repaired entries are
+    /// reported in the `scanned_count` and `repaired_count` attributes of the response, along with
+    /// a base64 encoded `cursor` attribute to scan the following records with if some may remain.
+    ///
+    /// Only the current smart contract owner is authorized to perform this action.
+    RebuildIndexes {
+        /// The cursor returned by the previous batch, the first records being scanned if not
+        /// provided.
+        cursor: Option<Binary>,
+        /// The maximum number of records to scan.
+        /// If not provided, the maximum query limit of the store is used, which it can't exceed.
+        limit: Option<u32>,
+    },
+
+    /// # Compact
+    /// Physically remove the obsolete records left in the state, in batches resumable through a
+    /// cursor, so long-lived stores don't accrete dead data.
+    ///
+    /// The entries of the secondary indexes are scanned first, the ones referencing no stored triple
+    /// being removed, then the insertion and expiration records of the triples no longer stored,
+    /// and lastly the entries of the literal tags, terms and namespaces dictionaries whose reference
+    /// counter dropped to zero. Unlike [ExecuteMsg::RebuildIndexes], no missing record is written.
+    /// The number of scanned records and of removed ones are reported in the `scanned_count` and
+    /// `reclaimed_count` attributes of the response, along with a base64 encoded `cursor`
+    /// attribute to scan the following records with if some may remain.
+    ///
+    /// Only the current smart contract owner is authorized to perform this action.
+    Compact {
+        /// The cursor returned by the previous batch, the first records being scanned if not
+        /// provided.
+        cursor: Option<Binary>,
+        /// The maximum number of records to scan.
+        /// If not provided, the maximum query limit of the store is used, which it can't exceed.
+        limit: Option<u32>,
+    },
+
+    /// # MigrateTriples
+    /// Continues the migration of the stored triples started by the migration of a store written by
+    /// `6.0.0` or older, whose triples are stored in full rather than referencing their terms in the
+    /// term dictionary and aren't indexed by predicate and object nor by object and subject.
+    ///
+    /// Each call scans the next batch of stored triples, interning the terms of the ones stored in
+    /// full and indexing them, the other ones being indexed again if the indexes layout changed
+    /// (see [MigrateMsg::rebuild_indexes]). When migrating a store written by `6.0.0` or older,
+    /// the following calls then scan the index entries and the literal tags the statistics about
+    /// the terms of the store and the checksum of its triples are counted again from. The number
+    /// of triples interned and indexed again by the call are reported in the
+    /// `interned_triple_count` and `indexed_triple_count` attributes of the response, and the
+    /// `completed` attribute tells whether the migration is completed. Until then, the other
+    /// messages are rejected and the queries only see the triples already migrated.
+    ///
+    /// Anyone can invoke this message while a migration is in progress.
+    MigrateTriples {
+        /// The maximum number of records to scan.
+        /// If not provided, the maximum query limit of the store is used, which it can't exceed.
+        limit: Option<u32>,
+    },
+
+    /// # Batch
+    /// Apply several mutations of the store in sequence, all or nothing, e.g. to replace the
+    /// description of a resource by deleting it and inserting the new one, without any partially
+    /// applied state being observable.
+    ///
+    /// Only [ExecuteMsg::InsertData] and [ExecuteMsg::DeleteData] can be batched, each one being
+    /// authorized as if executed on its own. The response of each operation is reported through a
+    /// `batch_operation` event carrying its `index` in the batch along with its attributes, the
+    /// response data being the [InsertDataReceipt] of all the insertions of the batch added up, if
+    /// any.
+    Batch(Vec<ExecuteMsg>),
+}
+
+/// # SubjectSelection
+/// The subjects bound to a variable of a where clause, see [ExecuteMsg::MoveGraph].
+#[cw_serde]
+pub struct SubjectSelection {
+    /// The prefixes used in the where clause.
+    pub prefixes: Vec<Prefix>,
+    /// The variable of the where clause bound to the subjects.
+    pub subject: String,
+    /// The patterns the subjects must match.
+    pub r#where: WhereClause,
+}
+
+/// # ShardBatch
+/// The data to insert in a sibling store as part of an [ExecuteMsg::InsertBatch].
+#[cw_serde]
+pub struct ShardBatch {
+    /// The address of the sibling triple store.
+    pub contract_addr: String,
+    /// The data to insert, serialized in the format of the batch.
+    pub data: Binary,
+}
+
+/// # InsertDataReceipt
+/// The data of the response to an [ExecuteMsg::InsertData].
+#[cw_serde]
+#[derive(Default)]
+pub struct InsertDataReceipt {
+    /// The number of triples inserted.
+    pub inserted_count: Uint128,
+    /// The number of triples skipped as already existing in the store.
+    pub duplicate_count: Uint128,
+    /// The number of triples skipped as exceeding the `max_triple_byte_size` limit, see the
+    /// `skip_oversized` option.
+    pub rejected_count: Uint128,
+}
+
+impl InsertDataReceipt {
+    /// Add up the counts of two receipts, e.g. of the insertions of a batch.
+    pub fn merge(&self, other: &InsertDataReceipt) -> InsertDataReceipt {
+        InsertDataReceipt {
+            inserted_count: self.inserted_count + other.inserted_count,
+            duplicate_count: self.duplicate_count + other.duplicate_count,
+            rejected_count: self.rejected_count + other.rejected_count,
+        }
+    }
+}
+
+/// # InsertBatchReceipt
+/// The data of the response to an [ExecuteMsg::InsertBatch].
+#[cw_serde]
+pub struct InsertBatchReceipt {
+    /// The receipt of the insertion in this store.
+    pub receipt: InsertDataReceipt,
+    /// The receipts of the insertions in the sibling stores, in the order of the sub-batches.
+    pub shards: Vec<ShardInsertReceipt>,
+    /// The counts of all the receipts of the batch added up.
+    pub total: InsertDataReceipt,
+}
+
+/// # ShardInsertReceipt
+/// The receipt of the insertion of a sub-batch in a sibling store, see [InsertBatchReceipt].
+#[cw_serde]
+pub struct ShardInsertReceipt {
+    /// The address of the sibling triple store.
+    pub contract_addr: String,
+    /// The receipt returned by the sibling triple store, if any.
+    pub receipt: Option<InsertDataReceipt>,
+}
+
+/// Messages sent by the triple store to the subscribers, see [ExecuteMsg::Subscribe].
+#[cw_serde]
+pub enum SubscriptionCallbackMsg {
+    /// # TriplesInserted
+    /// Notify the subscriber of the inserted triples matching the pattern of its subscription.
+    TriplesInserted {
+        /// The identifier of the subscription.
+        subscription_id: u64,
+        /// The matching triples, in their N-Triples form.
+        triples: Vec<String>,
+    },
+}
+
+/// # ConflictPolicy
+/// Represents the policy applied when inserting triples about a named subject already described in
+/// the store, protecting the resource descriptions of shared stores against writers clobbering
+/// each other.
+///
+/// The store keeps track of the provenance of every named subject, i.e. the address which first
+/// inserted triples about it and the graph they belonged to, until all its triples are deleted.
+/// The subjects described before the provenance got tracked are attributed to the store owner in
+/// the default graph, on their next insertion.
+/// Blank node subjects, being scoped to a single insertion, are never in conflict.
+#[cw_serde]
+#[derive(Copy, Default, Eq)]
+pub enum ConflictPolicy {
+    /// # Allow
+    /// Any authorized writer can insert triples about any subject.
+    #[default]
+    Allow,
+    /// # RejectDifferentWriter
+    /// Only the address which first described a subject can insert further triples about it.
+    RejectDifferentWriter,
+    /// # RequireSameGraph
+    /// Triples about a subject can only be inserted in the graph it has first been described in,
+    /// which is the default graph for all the formats but [DataFormat::NQuads].
+    RequireSameGraph,
+}
+
+/// # LiteralOffload
+/// Offloads the literals whose value exceeds a byte size to an `axone-objectarium` bucket at
+/// insertion, the value being stored as an object pinned by the store while the literal only keeps
+/// a `cosmwasm:` URI referencing the object by its id, i.e. the hash of its content.
+///
+/// The literals are transparently resolved back to their value when describing or constructing
+/// triples, while the select queries return the reference and the query patterns can only match an
+/// offloaded literal through its reference.
+#[cw_serde]
+pub struct LiteralOffload {
+    /// The address of the `axone-objectarium` bucket the literals are offloaded to.
+    pub bucket: String,
+    /// The byte size above which the value of a literal is offloaded.
+    pub threshold: Uint128,
+}
+
+/// # SoftLimit
+/// A threshold on the usage of the store past which it is considered near its limits, giving time
+/// to raise them or to shard the data before the insertions start failing.
+///
+/// Past the threshold, the insertions still succeed but their response carries a `near_limit`
+/// attribute and a `store_near_limit` event reporting the usage of the store, and can be restricted
+/// to some writers.
+#[cw_serde]
+pub struct SoftLimit {
+    /// The percentage of the `max_triple_count` and `max_byte_size` limits the threshold stands at,
+    /// between 1 and 100, the threshold being reached as soon as one of them is.
+    pub threshold_percent: u8,
+    /// The writers, besides the owner, still allowed to insert once the threshold is reached.
+    /// If not provided, all the writers keep being allowed to.
+    pub allowed_writers: Option<Vec<String>>,
+}
+
+/// # Permission
+/// Represents an action on the store data that can be granted to addresses other than the owner.
+#[cw_serde]
+#[derive(Copy, Eq, PartialOrd, Ord)]
+pub enum Permission {
+    /// # Insert
+    /// Allows to insert data through [ExecuteMsg::InsertData] or an insert session (see
+    /// [ExecuteMsg::BeginInsert]).
+    Insert,
+    /// # Delete
+    /// Allows to delete data through [ExecuteMsg::DeleteData].
+    Delete,
+    /// # Subscribe
+    /// Allows to be notified of the inserted triples through [ExecuteMsg::Subscribe].
+    Subscribe,
 }
 
 /// # SelectQuery
@@ -88,6 +725,25 @@ pub enum QueryMsg {
     #[returns(StoreResponse)]
     Store {},
 
+    /// # Stats
+    ///
+    /// Returns statistics about the content of the triple store: the number of distinct subjects,
+    /// predicates and objects and the breakdown of its byte size, maintained on each insertion and
+    /// deletion so they can be retrieved without going through the triples.
+    #[returns(StatsResponse)]
+    Stats {},
+
+    /// # StoreChecksum
+    ///
+    /// Returns an order-independent checksum of all the triples of the store, maintained on each
+    /// insertion and deletion, so replicas and migrated instances can verify they hold the same
+    /// content without exporting it.
+    ///
+    /// Blank nodes being identified internally by the store, two stores only share the same
+    /// checksum for triples involving blank nodes if they were inserted in the same order.
+    #[returns(StoreChecksumResponse)]
+    StoreChecksum {},
+
     /// # Select
     ///
     /// Returns the resources matching the criteria defined by the provided query.
@@ -98,6 +754,63 @@ pub enum QueryMsg {
         query: SelectQuery,
     },
 
+    /// # StoredQuery
+    ///
+    /// Returns the resources matching the criteria of a query registered by the owner through
+    /// [ExecuteMsg::RegisterQuery], its parameters being replaced by the provided values.
+    #[returns(SelectResponse)]
+    StoredQuery {
+        /// The name of the query.
+        name: String,
+        /// The values of all the parameters of the query, by parameter.
+        #[serde(default)]
+        parameters: BTreeMap<String, ParameterValue>,
+    },
+
+    /// # BudgetedSelect
+    ///
+    /// Returns the resources matching the criteria defined by the provided query, bounding its
+    /// evaluation to a number of index rows touched. When the budget is exhausted, the results found
+    /// so far are returned along with a cursor to resume the query evaluation in a next call, allowing
+    /// large queries to be split across several calls.
+    ///
+    /// The progress is tracked on the first triple pattern evaluated, whose rows already evaluated
+    /// are skipped on resumption without being accounted in the budget. When this pattern is
+    /// expanded by the reasoning into alternative patterns, their rows are tracked in turn as a
+    /// single sequence, a solution inferred from several alternatives being possibly returned again
+    /// after a resumption.
+    #[returns(BudgetedSelectResponse)]
+    BudgetedSelect {
+        /// The query to execute.
+        query: SelectQuery,
+        /// The maximum number of index rows the evaluation can touch in this call.
+        max_rows: u32,
+        /// The cursor returned by the previous call, if resuming the query evaluation.
+        cursor: Option<SelectCursor>,
+    },
+
+    /// # Explain
+    ///
+    /// Returns the evaluation plan of the provided select query without executing it, i.e. the join
+    /// order of its triple patterns, the index used to match each of them and an estimation of the
+    /// number of rows they match. Allows to understand why a query is slow or hits the limits before
+    /// running it.
+    #[returns(ExplainResponse)]
+    Explain {
+        /// The query to explain.
+        query: SelectQuery,
+    },
+
+    /// # Count
+    ///
+    /// Returns the number of solutions of the provided where clause, i.e. the number of results a
+    /// select query over it would return without limit, without materializing their bindings.
+    #[returns(CountResponse)]
+    Count {
+        /// The query to execute.
+        query: CountQuery,
+    },
+
     /// # Describe
     ///
     /// Returns a description of the resource identified by the provided IRI as a set of RDF triples
@@ -111,10 +824,38 @@ pub enum QueryMsg {
         format: Option<DataFormat>,
     },
 
+    /// # Provenance
+    ///
+    /// Returns the triples matching the provided triple pattern along with their insertion, i.e.
+    /// the address which inserted them, and the block and transaction they were inserted in.
+    ///
+    /// The variables of the pattern match any term, without being joined. The insertion of the
+    /// triples inserted before the provenance was recorded is not known.
+    #[returns(ProvenanceResponse)]
+    Provenance {
+        /// The query to execute.
+        query: ProvenanceQuery,
+    },
+
+    /// # Subscription
+    ///
+    /// Returns a subscription registered through [ExecuteMsg::Subscribe].
+    #[returns(SubscriptionResponse)]
+    Subscription {
+        /// The identifier of the subscription.
+        id: u64,
+    },
+
     /// # Construct
     ///
     /// Returns the resources matching the criteria defined by the provided query as a set of RDF
     /// triples serialized in the provided format.
+    ///
+    /// The triples are constructed from at most the maximum query limit of the store solutions of
+    /// the `where` clause, a cursor being returned to export the following ones when some remain,
+    /// allowing to export the whole subgraph matching the clause page by page. Blank node labels
+    /// are consistent across the pages: a stored blank node is labelled after its identifier in the
+    /// store, and a minted one after its template label and the number of its solution.
     #[returns(ConstructResponse)]
     Construct {
         /// The query to execute.
@@ -122,7 +863,152 @@ pub enum QueryMsg {
         /// The format in which the triples are serialized.
         /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
         format: Option<DataFormat>,
+        /// The cursor returned by the previous call, if exporting the following page.
+        cursor: Option<Uint64>,
+    },
+
+    /// # Export
+    ///
+    /// Returns a page of the whole store content as RDF triples serialized in the provided format,
+    /// a cursor being returned to export the following page when some triples remain, allowing to
+    /// back up the store or to re-seed another one with it.
+    ///
+    /// Blank node labels are stable across pages, so the pages of a line-based format can be
+    /// concatenated and inserted back at once through an insert session (see
+    /// [ExecuteMsg::BeginInsert]). In [DataFormat::NQuads], the triples are exported in the graph
+    /// their subject has first been described in, as they are matched by the queries (see
+    /// [SelectQuery::from]), the triples about blank nodes being exported in the default graph.
+    #[returns(ExportResponse)]
+    Export {
+        /// The cursor returned by the previous call, if exporting the following page.
+        cursor: Option<Binary>,
+        /// The maximum number of triples to export.
+        /// If not provided, the maximum query limit of the store is used, which it can't exceed.
+        limit: Option<u32>,
+        /// The format in which the triples are serialized.
+        /// If not provided, the default format is [N-Quads](https://www.w3.org/TR/n-quads/) format.
+        format: Option<DataFormat>,
     },
+
+    /// # RecentChanges
+    ///
+    /// Returns the triples inserted by the last insert batches as tracked by the recent window index,
+    /// serialized in the provided format. Allows indexers to incrementally sync the store content.
+    ///
+    /// The triples deleted since their insertion are no longer returned.
+    ///
+    /// The recent window index must have been enabled at instantiation.
+    #[returns(RecentChangesResponse)]
+    RecentChanges {
+        /// The number of last insert batches to return the triples of, it can't exceed the recent
+        /// window size.
+        batches: u32,
+        /// The format in which the triples are serialized.
+        /// If not provided, the default format is [Turtle](https://www.w3.org/TR/turtle/) format.
+        format: Option<DataFormat>,
+    },
+
+    /// # PendingOwner
+    ///
+    /// Returns the owner proposed by a pending ownership transfer, see
+    /// [ExecuteMsg::TransferOwnership].
+    #[returns(PendingOwnerResponse)]
+    PendingOwner {},
+
+    /// # Grantees
+    ///
+    /// Returns the addresses granted with permissions on the store, along with their permissions.
+    #[returns(GranteesResponse)]
+    Grantees {
+        /// The address after which to start listing, excluded.
+        after: Option<String>,
+        /// The maximum number of grantees to return.
+        /// If not provided, the maximum query limit of the store is used.
+        limit: Option<u32>,
+    },
+
+    /// # Prefixes
+    ///
+    /// Returns the prefixes registered in the store, ordered by prefix.
+    #[returns(PrefixesResponse)]
+    Prefixes {},
+
+    /// # Namespaces
+    ///
+    /// Returns the namespaces persisted in the store, ordered by value, along with the internal key
+    /// they are referenced by in the triples and the number of references to them.
+    #[returns(NamespacesResponse)]
+    Namespaces {
+        /// The cursor to resume the listing from, i.e. the last namespace of the previous page, which
+        /// is excluded.
+        cursor: Option<String>,
+        /// The maximum number of namespaces to return.
+        /// If not provided, the maximum query limit of the store is used.
+        limit: Option<u32>,
+    },
+
+    /// # QueryAudit
+    ///
+    /// Returns the entries of the query audit log, ordered by sequence number, each one recording
+    /// a where clause evaluated by an executed message (i.e. [ExecuteMsg::DeleteData] and
+    /// [ExecuteMsg::MoveGraph]), along with its caller and evaluation cost. The smart queries can't
+    /// be recorded, as they can't modify the state.
+    ///
+    /// The query audit log must have been enabled at instantiation. As any contract state, its
+    /// entries are readable by anyone.
+    #[returns(QueryAuditResponse)]
+    QueryAudit {
+        /// The sequence number of the entry after which to start listing, excluded.
+        after: Option<Uint64>,
+        /// The maximum number of entries to return.
+        /// If not provided, the maximum query limit of the store is used.
+        limit: Option<u32>,
+    },
+
+    /// # CheckIntegrity
+    ///
+    /// Returns the entries of the secondary indexes of the triples disagreeing with the stored
+    /// triples, i.e. the entries missing for a stored triple and the ones referencing a triple
+    /// which isn't stored, a cursor being returned to check the following records when some may
+    /// remain.
+    ///
+    /// The faults can be repaired by the owner through [ExecuteMsg::RebuildIndexes].
+    #[returns(IntegrityResponse)]
+    CheckIntegrity {
+        /// The cursor returned by the previous call, if checking the following records.
+        cursor: Option<Binary>,
+        /// The maximum number of records to scan.
+        /// If not provided, the maximum query limit of the store is used, which it can't exceed.
+        limit: Option<u32>,
+    },
+
+    /// # Backlinks
+    ///
+    /// Returns the subjects and predicates of the triples having the given IRI as object, i.e. the
+    /// resources referencing it, in the order of their subject.
+    ///
+    /// The triples are looked up through the object-subject index, only the matching ones being
+    /// scanned. They are returned by pages of up to the maximum query limit of the store, a cursor
+    /// being returned to get the following page when some triples remain. Blank node subjects are
+    /// labelled after their identifier in the store, so their labels are stable across pages.
+    #[returns(BacklinksResponse)]
+    Backlinks {
+        /// The IRI referenced by the triples, its prefix being resolved against the prefixes
+        /// registered in the store.
+        iri: IRI,
+        /// The cursor returned by the previous call, if getting the following page.
+        cursor: Option<Binary>,
+    },
+}
+
+/// # DataCompression
+/// Represents the compression applied to the data inserted in the store.
+#[cw_serde]
+pub enum DataCompression {
+    /// # Gzip
+    /// Data compressed in [gzip](https://www.rfc-editor.org/rfc/rfc1952) format.
+    #[serde(rename = "gzip")]
+    Gzip,
 }
 
 /// # DataFormat
@@ -150,13 +1036,38 @@ pub enum DataFormat {
     NQuads,
 }
 
-impl From<&DataFormat> for axone_rdf::serde::DataFormat {
-    fn from(value: &DataFormat) -> Self {
+impl DataFormat {
+    /// Returns the formats supported by the store, depending on the features it has been built with.
+    pub fn supported() -> Vec<DataFormat> {
+        [
+            (DataFormat::RDFXml, cfg!(feature = "rdf-xml")),
+            (DataFormat::Turtle, cfg!(feature = "turtle")),
+            (DataFormat::NTriples, cfg!(feature = "n-triples")),
+            (DataFormat::NQuads, cfg!(feature = "n-quads")),
+        ]
+        .into_iter()
+        .filter_map(|(format, enabled)| enabled.then_some(format))
+        .collect()
+    }
+}
+
+impl TryFrom<&DataFormat> for axone_rdf::serde::DataFormat {
+    type Error = StdError;
+
+    fn try_from(value: &DataFormat) -> Result<Self, Self::Error> {
         match value {
-            DataFormat::RDFXml => Self::RDFXml,
-            DataFormat::Turtle => Self::Turtle,
-            DataFormat::NTriples => Self::NTriples,
-            DataFormat::NQuads => Self::NQuads,
+            #[cfg(feature = "rdf-xml")]
+            DataFormat::RDFXml => Ok(Self::RDFXml),
+            #[cfg(feature = "turtle")]
+            DataFormat::Turtle => Ok(Self::Turtle),
+            #[cfg(feature = "n-triples")]
+            DataFormat::NTriples => Ok(Self::NTriples),
+            #[cfg(feature = "n-quads")]
+            DataFormat::NQuads => Ok(Self::NQuads),
+            #[allow(unreachable_patterns)]
+            _ => Err(StdError::generic_err(format!(
+                "Unsupported data format: {value:?}"
+            ))),
         }
     }
 }
@@ -200,6 +1111,50 @@ pub struct StoreLimitsInput {
     /// Default to [Uint128::MAX] if not set, which can be considered as no limit.
     #[serde(default = "StoreLimitsInput::default_max_insert_data_triple_count")]
     pub max_insert_data_triple_count: Uint128,
+    /// The maximum number of triple patterns the where clause of a query can contain.
+    /// Default to 30 if not set.
+    #[serde(default = "StoreLimitsInput::default_max_query_pattern_count")]
+    pub max_query_pattern_count: u32,
+    /// The maximum nesting depth of the where clause of a query, a basic graph pattern having a
+    /// depth of 1.
+    /// Default to 10 if not set.
+    #[serde(default = "StoreLimitsInput::default_max_query_where_depth")]
+    pub max_query_where_depth: u32,
+    /// The maximum number of join variables of the where clause of a query, i.e. of the variables
+    /// shared by several of its triple patterns.
+    /// Default to 20 if not set.
+    #[serde(default = "StoreLimitsInput::default_max_query_join_variable_count")]
+    pub max_query_join_variable_count: u32,
+    /// The maximum number of `rdfs:subClassOf` and `rdfs:subPropertyOf` hierarchy levels a query
+    /// can infer over, see [Reasoning].
+    /// Default to 5 if not set.
+    #[serde(default = "StoreLimitsInput::default_max_query_inference_depth")]
+    pub max_query_inference_depth: u32,
+    /// The maximum number of bytes of the serialized response of a query returning stored data,
+    /// i.e. selecting, describing, constructing or exporting triples. The pages of the
+    /// [QueryMsg::Construct], [QueryMsg::Export] and [QueryMsg::Backlinks] queries are trimmed to
+    /// fit, the other ones failing if their response exceeds it. The queries about the store
+    /// itself, e.g. [QueryMsg::Store] or [QueryMsg::Grantees], aren't bounded.
+    /// Default to [Uint128::MAX] if not set, which can be considered as no limit.
+    #[serde(default = "StoreLimitsInput::default_max_result_byte_size")]
+    pub max_result_byte_size: Uint128,
+    /// The maximum number of distinct blank nodes an insert data query can contain, enforced while
+    /// parsing so a crafted payload can't exhaust the memory before any other limit applies.
+    /// Default to [Uint128::MAX] if not set, which can be considered as no limit.
+    #[serde(default = "StoreLimitsInput::default_max_insert_blank_node_count")]
+    pub max_insert_blank_node_count: Uint128,
+    /// The maximum number of bytes an insert session can buffer before being committed, see
+    /// [ExecuteMsg::InsertChunk], thus the maximum number of bytes buffered for a sender, which can
+    /// only have one session in progress.
+    /// Default to 16 MiB if not set.
+    #[serde(default = "StoreLimitsInput::default_max_insert_session_byte_size")]
+    pub max_insert_session_byte_size: Uint128,
+    /// The maximum number of solutions a select query returning its results in a deterministic
+    /// order (see [SelectQuery::ordered]) can evaluate, as they all have to be held to be ordered.
+    /// The query fails when its where clause has more solutions.
+    /// Default to 1000 if not set.
+    #[serde(default = "StoreLimitsInput::default_max_query_ordered_solution_count")]
+    pub max_query_ordered_solution_count: u32,
 }
 
 impl StoreLimitsInput {
@@ -224,6 +1179,30 @@ impl StoreLimitsInput {
     const fn default_max_insert_data_triple_count() -> Uint128 {
         Uint128::MAX
     }
+    pub(crate) const fn default_max_query_pattern_count() -> u32 {
+        30
+    }
+    pub(crate) const fn default_max_query_where_depth() -> u32 {
+        10
+    }
+    pub(crate) const fn default_max_query_join_variable_count() -> u32 {
+        20
+    }
+    pub(crate) const fn default_max_query_inference_depth() -> u32 {
+        5
+    }
+    pub(crate) const fn default_max_result_byte_size() -> Uint128 {
+        Uint128::MAX
+    }
+    pub(crate) const fn default_max_insert_blank_node_count() -> Uint128 {
+        Uint128::MAX
+    }
+    pub(crate) const fn default_max_insert_session_byte_size() -> Uint128 {
+        Uint128::new(16 * 1024 * 1024)
+    }
+    pub(crate) const fn default_max_query_ordered_solution_count() -> u32 {
+        1000
+    }
 }
 
 impl Default for StoreLimitsInput {
@@ -236,6 +1215,14 @@ impl Default for StoreLimitsInput {
             max_query_variable_count: Self::default_max_query_variable_count(),
             max_insert_data_byte_size: Self::default_max_insert_data_byte_size(),
             max_insert_data_triple_count: Self::default_max_insert_data_triple_count(),
+            max_query_pattern_count: Self::default_max_query_pattern_count(),
+            max_query_where_depth: Self::default_max_query_where_depth(),
+            max_query_join_variable_count: Self::default_max_query_join_variable_count(),
+            max_query_inference_depth: Self::default_max_query_inference_depth(),
+            max_result_byte_size: Self::default_max_result_byte_size(),
+            max_insert_blank_node_count: Self::default_max_insert_blank_node_count(),
+            max_insert_session_byte_size: Self::default_max_insert_session_byte_size(),
+            max_query_ordered_solution_count: Self::default_max_query_ordered_solution_count(),
         }
     }
 }
@@ -253,6 +1240,16 @@ pub struct StoreResponse {
 
     /// The store current usage.
     pub stat: StoreStat,
+
+    /// The policy applied when inserting triples about an already described subject.
+    pub conflict_policy: ConflictPolicy,
+
+    /// Whether the lexical form of the typed literals is normalized on insert, see
+    /// [InstantiateMsg::disable_literal_normalization].
+    pub literal_normalization: bool,
+
+    /// The data formats the store has been built with support for, to insert and export triples.
+    pub formats: Vec<DataFormat>,
 }
 
 /// # StoreLimits
@@ -286,6 +1283,31 @@ pub struct StoreLimits {
 
     /// The maximum number of triples an insert data query can contain (after parsing).
     pub max_insert_data_triple_count: Uint128,
+
+    /// The maximum number of triple patterns the where clause of a query can contain.
+    pub max_query_pattern_count: u32,
+
+    /// The maximum nesting depth of the where clause of a query.
+    pub max_query_where_depth: u32,
+
+    /// The maximum number of join variables of the where clause of a query, i.e. of the variables
+    /// shared by several of its triple patterns.
+    pub max_query_join_variable_count: u32,
+
+    /// The maximum number of class and property hierarchy levels a query can infer over.
+    pub max_query_inference_depth: u32,
+
+    /// The maximum number of bytes of the serialized response of a query.
+    pub max_result_byte_size: Uint128,
+
+    /// The maximum number of distinct blank nodes an insert data query can contain.
+    pub max_insert_blank_node_count: Uint128,
+
+    /// The maximum number of bytes an insert session can buffer before being committed.
+    pub max_insert_session_byte_size: Uint128,
+
+    /// The maximum number of solutions an ordered select query can evaluate.
+    pub max_query_ordered_solution_count: u32,
 }
 
 /// # StoreStat
@@ -303,6 +1325,70 @@ pub struct StoreStat {
     pub byte_size: Uint128,
 }
 
+/// # StatsResponse
+///
+/// Contains statistics about the content of the triple store.
+#[cw_serde]
+pub struct StatsResponse {
+    /// The total number of triples present in the store.
+    pub triple_count: Uint128,
+
+    /// The number of distinct subjects, blank nodes included.
+    pub subject_count: Uint128,
+
+    /// The number of distinct predicates.
+    pub predicate_count: Uint128,
+
+    /// The number of distinct objects, blank nodes and literals included.
+    pub object_count: Uint128,
+
+    /// The number of IRI namespaces present in the store.
+    pub namespace_count: Uint128,
+
+    /// The number of distinct language tags and datatypes qualifying the literals.
+    pub literal_tag_count: Uint128,
+
+    /// The breakdown of the total triple size in the store.
+    pub byte_size: ByteSizeBreakdown,
+}
+
+/// # StoreChecksumResponse
+///
+/// Contains the checksum of the triples of the store.
+#[cw_serde]
+pub struct StoreChecksumResponse {
+    /// The checksum, i.e. the wrapping sum of the 256 bits digests of the triples, in big-endian
+    /// byte order. The checksum of an empty store is zero.
+    pub checksum: HexBinary,
+
+    /// The total number of triples present in the store.
+    pub triple_count: Uint128,
+}
+
+/// # ByteSizeBreakdown
+///
+/// The total triple size in the store, in bytes, broken down by triple component, a term being
+/// counted as many times as it appears in triples.
+#[cw_serde]
+pub struct ByteSizeBreakdown {
+    /// The total triple size in the store.
+    pub total: Uint128,
+
+    /// The size of the subjects.
+    pub subjects: Uint128,
+
+    /// The size of the predicates.
+    pub predicates: Uint128,
+
+    /// The size of the objects, including the size of data types and language tags if any.
+    pub objects: Uint128,
+
+    /// The size of the distinct language tags and datatypes qualifying the literals, each one
+    /// being stored once whatever the number of literals it qualifies. Not part of the total, as
+    /// already counted in the size of the objects.
+    pub literal_tags: Uint128,
+}
+
 /// # IRI
 /// Represents an IRI.
 #[cw_serde]
@@ -327,6 +1413,179 @@ pub struct SelectResponse {
     pub results: Results,
 }
 
+/// # BudgetedSelectResponse
+/// Represents the response of a [QueryMsg::BudgetedSelect] query.
+#[cw_serde]
+pub struct BudgetedSelectResponse {
+    /// The head of the response, i.e. the set of variables mentioned in the results.
+    pub head: Head,
+    /// The results of the select query found in this call.
+    pub results: Results,
+    /// The cursor to resume the query evaluation with, if the budget has been exhausted before its
+    /// completion.
+    pub cursor: Option<SelectCursor>,
+}
+
+/// # SelectCursor
+/// Represents the progress of a [QueryMsg::BudgetedSelect] query evaluation.
+#[cw_serde]
+pub struct SelectCursor {
+    /// The number of rows of the first triple pattern completely evaluated.
+    pub rows: Uint64,
+    /// The number of results already returned, deducted from the query limit.
+    pub results: u32,
+}
+
+/// # CountResponse
+/// Represents the response of a [QueryMsg::Count] query.
+#[cw_serde]
+pub struct CountResponse {
+    /// The number of solutions of the where clause.
+    pub count: Uint64,
+}
+
+/// # ExplainResponse
+/// Represents the response of a [QueryMsg::Explain] query.
+#[cw_serde]
+pub struct ExplainResponse {
+    /// The root node of the evaluation plan.
+    pub plan: PlanNode,
+}
+
+/// # PlanNode
+/// Represents a node of a query evaluation plan, the nodes being evaluated from left to right.
+#[cw_serde]
+pub enum PlanNode {
+    /// # TriplePattern
+    /// Match a triple pattern against the store.
+    TriplePattern {
+        /// The subject of the pattern.
+        subject: PatternTerm,
+        /// The predicate of the pattern.
+        predicate: PatternTerm,
+        /// The object of the pattern.
+        object: PatternTerm,
+        /// The index used to match the pattern.
+        index: TripleIndex,
+        /// The number of rows matching the constant terms of the pattern, i.e. an upper bound of
+        /// the rows matched on each evaluation, counted up to 1000.
+        estimated_rows: u32,
+    },
+    /// # Noop
+    /// Result in no solutions, known before the evaluation, e.g. when a pattern references an
+    /// unknown namespace.
+    Noop {},
+    /// # CartesianProductJoin
+    /// Join the solutions of two nodes not sharing any variable, the right node being evaluated once.
+    CartesianProductJoin {
+        left: Box<PlanNode>,
+        right: Box<PlanNode>,
+    },
+    /// # ForLoopJoin
+    /// Join the solutions of two nodes, the right node being evaluated for each solution of the left
+    /// one.
+    ForLoopJoin {
+        left: Box<PlanNode>,
+        right: Box<PlanNode>,
+    },
+    /// # AdaptiveJoin
+    /// Join the solutions of several nodes evaluated one after the other, the node matching the
+    /// fewest rows given the variables bound so far being chosen next for each intermediate
+    /// solution, see [SelectQuery::adaptive_joins].
+    AdaptiveJoin { children: Vec<PlanNode> },
+    /// # Union
+    /// Merge the distinct solutions of two alternative nodes, e.g. the triple patterns inferred from
+    /// the class and property hierarchies, the right node being evaluated after the left one.
+    Union {
+        left: Box<PlanNode>,
+        right: Box<PlanNode>,
+    },
+    /// # Filter
+    /// Filter the solutions of the inner node by an expression.
+    Filter { inner: Box<PlanNode> },
+    /// # QuotedTriple
+    /// Match the terms of the quoted triples bound by the child node against a quoted triple
+    /// pattern, the variables bound to quoted triples being named `<<n>>`.
+    QuotedTriple {
+        child: Box<PlanNode>,
+        /// The subject of the quoted triple pattern.
+        subject: PatternTerm,
+        /// The predicate of the quoted triple pattern.
+        predicate: PatternTerm,
+        /// The object of the quoted triple pattern.
+        object: PatternTerm,
+    },
+    /// # Service
+    /// Join the solutions of a clause evaluated by another cognitarium contract, fetched at planning.
+    Service {
+        /// The address of the contract the clause has been evaluated against.
+        contract_addr: String,
+        /// The number of solutions returned by the contract.
+        solutions: u32,
+    },
+    /// # Graph
+    /// Restrict the triple patterns of the child node to the triples of the given graphs, in their
+    /// N-Quads form, see [WhereClause::Graph].
+    Graph {
+        child: Box<PlanNode>,
+        graphs: Vec<String>,
+    },
+    /// # Skip
+    /// Skip the first solutions of the child node.
+    Skip { child: Box<PlanNode>, first: u32 },
+    /// # Limit
+    /// Limit the solutions of the child node.
+    Limit { child: Box<PlanNode>, first: u32 },
+}
+
+/// # PatternTerm
+/// Represents a term of a triple pattern in a [PlanNode].
+#[cw_serde]
+pub enum PatternTerm {
+    /// # Constant
+    /// A constant node or literal.
+    Constant {},
+    /// # Variable
+    /// A variable, blank nodes being named with the `_:` prefix.
+    Variable {
+        /// The name of the variable.
+        name: String,
+        /// Whether the variable is bound by a previous node when the pattern is evaluated.
+        bound: bool,
+    },
+}
+
+/// # TripleIndex
+/// Represents the way the triples matching a pattern are looked up, depending on the pattern terms
+/// known when evaluated.
+#[cw_serde]
+pub enum TripleIndex {
+    /// # Lookup
+    /// Direct lookup of a single triple, all its terms being known.
+    #[serde(rename = "lookup")]
+    Lookup,
+    /// # ObjectPredicateSubject
+    /// Range over the primary key, ordered by object, predicate and subject.
+    #[serde(rename = "ops")]
+    ObjectPredicateSubject,
+    /// # SubjectPredicateObject
+    /// Range over the subject and predicate index.
+    #[serde(rename = "spo")]
+    SubjectPredicateObject,
+    /// # PredicateObjectSubject
+    /// Range over the predicate and object index.
+    #[serde(rename = "pos")]
+    PredicateObjectSubject,
+    /// # ObjectSubjectPredicate
+    /// Range over the object and subject index.
+    #[serde(rename = "osp")]
+    ObjectSubjectPredicate,
+    /// # FullScan
+    /// Range over all the triples, no term being known.
+    #[serde(rename = "full_scan")]
+    FullScan,
+}
+
 /// # DescribeResponse
 /// Represents the response of a [QueryMsg::Describe] query.
 #[cw_serde]
@@ -337,6 +1596,56 @@ pub struct DescribeResponse {
     pub data: Binary,
 }
 
+/// # SubscriptionResponse
+/// Represents the response of a [QueryMsg::Subscription] query.
+#[cw_serde]
+pub struct SubscriptionResponse {
+    /// The identifier of the subscription.
+    pub id: u64,
+    /// The address of the subscriber.
+    pub subscriber: String,
+    /// The subject of the pattern, in its N-Triples form, any subject matching if not provided.
+    pub subject: Option<String>,
+    /// The predicate of the pattern, in its N-Triples form, any predicate matching if not provided.
+    pub predicate: Option<String>,
+    /// The object of the pattern, in its N-Triples form, any object matching if not provided.
+    pub object: Option<String>,
+}
+
+/// # ProvenanceResponse
+/// Represents the response of a [QueryMsg::Provenance] query.
+#[cw_serde]
+pub struct ProvenanceResponse {
+    /// The triples matching the pattern, along with their insertion.
+    pub triples: Vec<TripleProvenance>,
+}
+
+/// # TripleProvenance
+/// Represents a stored triple along with its insertion.
+#[cw_serde]
+pub struct TripleProvenance {
+    /// The subject of the triple.
+    pub subject: Value,
+    /// The predicate of the triple.
+    pub predicate: Value,
+    /// The object of the triple.
+    pub object: Value,
+    /// The insertion of the triple, if known.
+    pub insertion: Option<Insertion>,
+}
+
+/// # Insertion
+/// Represents the insertion of a triple in the store.
+#[cw_serde]
+pub struct Insertion {
+    /// The address which inserted the triple.
+    pub inserter: String,
+    /// The height of the block the triple was inserted at.
+    pub height: Uint64,
+    /// The index in its block of the transaction the triple was inserted by, if known.
+    pub tx_index: Option<u32>,
+}
+
 /// # ConstructResponse
 /// Represents the response of a [QueryMsg::Construct] query.
 #[cw_serde]
@@ -345,6 +1654,179 @@ pub struct ConstructResponse {
     pub format: DataFormat,
     /// The data serialized in the specified format.
     pub data: Binary,
+    /// The cursor to export the following page with, i.e. the number of solutions of the `where`
+    /// clause consumed so far, if some remain.
+    pub cursor: Option<Uint64>,
+}
+
+/// # ExportResponse
+/// Represents the response of a [QueryMsg::Export] query.
+#[cw_serde]
+pub struct ExportResponse {
+    /// The format of the data.
+    pub format: DataFormat,
+    /// The exported triples serialized in the specified format.
+    pub data: Binary,
+    /// The cursor to export the following page with, if some triples remain.
+    pub cursor: Option<Binary>,
+}
+
+/// # RecentChangesResponse
+/// Represents the response of a [QueryMsg::RecentChanges] query.
+#[cw_serde]
+pub struct RecentChangesResponse {
+    /// The sequence number of the oldest insert batch included in the response.
+    pub first_batch: u64,
+    /// The sequence number of the last insert batch, i.e. the one to resume from.
+    pub last_batch: u64,
+    /// The format of the data.
+    pub format: DataFormat,
+    /// The triples inserted by the batches, serialized in the specified format.
+    pub data: Binary,
+}
+
+/// # QueryAuditResponse
+/// Represents the response of a [QueryMsg::QueryAudit] query.
+#[cw_serde]
+pub struct QueryAuditResponse {
+    /// The maximum number of entries retained by the log, the oldest ones being evicted first.
+    pub capacity: u32,
+    /// The sequence number of the last recorded entry, 0 if none.
+    pub last_entry: Uint64,
+    /// The entries, ordered by sequence number.
+    pub entries: Vec<QueryAuditEntry>,
+}
+
+/// # QueryAuditEntry
+/// A where clause evaluated by an executed message, see [QueryMsg::QueryAudit].
+#[cw_serde]
+pub struct QueryAuditEntry {
+    /// The sequence number of the entry.
+    pub id: Uint64,
+    /// The message the where clause has been evaluated for, e.g. `delete_data`.
+    pub operation: String,
+    /// The hex encoded `blake3` hash of the JSON serialization of the prefixes and where clause.
+    pub query_hash: String,
+    /// The sender of the message.
+    pub caller: String,
+    /// The number of triple patterns of the where clause.
+    pub pattern_count: u32,
+    /// The number of results the where clause led to, i.e. the triples to delete or the subjects to
+    /// move.
+    pub result_count: Uint64,
+}
+
+/// # IntegrityResponse
+/// Represents the response of a [QueryMsg::CheckIntegrity] query.
+#[cw_serde]
+pub struct IntegrityResponse {
+    /// The number of triples and index entries scanned.
+    pub scanned_count: u32,
+    /// The faults found among the scanned records.
+    pub faults: Vec<IntegrityFault>,
+    /// The cursor to check the following records with, if some may remain.
+    pub cursor: Option<Binary>,
+}
+
+/// # IntegrityFault
+/// An entry of a secondary index disagreeing with the stored triples, see
+/// [QueryMsg::CheckIntegrity].
+#[cw_serde]
+pub struct IntegrityFault {
+    /// The index of the entry.
+    pub index: TripleIndex,
+    /// The kind of fault.
+    pub kind: IntegrityFaultKind,
+    /// The raw key of the entry in the index.
+    pub key: HexBinary,
+}
+
+/// # IntegrityFaultKind
+/// Represents the way an index entry disagrees with the stored triples.
+#[cw_serde]
+pub enum IntegrityFaultKind {
+    /// # Missing
+    /// The entry of a stored triple is missing from the index.
+    #[serde(rename = "missing")]
+    Missing,
+    /// # Dangling
+    /// The entry references a triple which isn't stored, or which isn't indexed under this key.
+    #[serde(rename = "dangling")]
+    Dangling,
+}
+
+/// # BacklinksResponse
+/// Represents the response of a [QueryMsg::Backlinks] query.
+#[cw_serde]
+pub struct BacklinksResponse {
+    /// The triples referencing the IRI.
+    pub backlinks: Vec<Backlink>,
+    /// The cursor to get the following page with, if some triples remain.
+    pub cursor: Option<Binary>,
+}
+
+/// # Backlink
+/// A triple referencing an IRI as object, see [QueryMsg::Backlinks].
+#[cw_serde]
+pub struct Backlink {
+    /// The subject of the triple.
+    pub subject: Value,
+    /// The predicate of the triple.
+    pub predicate: IRI,
+}
+
+/// # PendingOwnerResponse
+/// Represents the response of a [QueryMsg::PendingOwner] query.
+#[cw_serde]
+pub struct PendingOwnerResponse {
+    /// The proposed owner, if an ownership transfer is pending.
+    pub pending_owner: Option<String>,
+}
+
+/// # GranteesResponse
+/// Represents the response of a [QueryMsg::Grantees] query.
+#[cw_serde]
+pub struct GranteesResponse {
+    /// The granted addresses, ordered by address.
+    pub grantees: Vec<Grantee>,
+}
+
+/// # PrefixesResponse
+/// Represents the response of a [QueryMsg::Prefixes] query.
+#[cw_serde]
+pub struct PrefixesResponse {
+    /// The registered prefixes, ordered by prefix.
+    pub prefixes: Vec<Prefix>,
+}
+
+/// # Grantee
+/// An address granted with permissions on the store.
+#[cw_serde]
+pub struct Grantee {
+    /// The granted address.
+    pub address: String,
+    /// The permissions granted to the address.
+    pub permissions: Vec<Permission>,
+}
+
+/// # NamespacesResponse
+/// Represents the response of a [QueryMsg::Namespaces] query.
+#[cw_serde]
+pub struct NamespacesResponse {
+    /// The namespaces, ordered by value.
+    pub namespaces: Vec<NamespaceUsage>,
+}
+
+/// # NamespaceUsage
+/// A namespace persisted in the store.
+#[cw_serde]
+pub struct NamespaceUsage {
+    /// The namespace value.
+    pub namespace: String,
+    /// The internal key referencing the namespace in the triples.
+    pub key: Uint128,
+    /// The number of references to the namespace in the triples.
+    pub references: Uint128,
 }
 
 /// # Head
@@ -391,6 +1873,16 @@ pub enum Value {
         /// The identifier of the blank node.
         value: String,
     },
+    /// # Triple
+    /// Represents a quoted triple, as in [RDF-star](https://www.w3.org/2021/12/rdf-star.html).
+    Triple {
+        /// The subject of the quoted triple.
+        subject: Box<Value>,
+        /// The predicate of the quoted triple.
+        predicate: Box<Value>,
+        /// The object of the quoted triple.
+        object: Box<Value>,
+    },
 }
 
 /// # SelectQuery
@@ -412,6 +1904,91 @@ pub struct SelectQuery {
     /// Note: the value of the limit cannot exceed the maximum query limit defined in the store
     /// limitations.
     pub limit: Option<u32>,
+    /// The RDFS inference to apply when matching the triple patterns of the WHERE clause.
+    /// If `None`, the patterns only match the stored triples.
+    pub reasoning: Option<Reasoning>,
+    /// Whether to return the blank nodes as skolem IRIs (i.e. `urn:bnode:<id>`) derived from their
+    /// stored identifier, which are stable across queries while the node is stored and allow to
+    /// join the results of several queries, instead of identifiers scoped to the response.
+    /// Note: skolem IRIs are not resolved back to the blank nodes when used in a query.
+    #[serde(default)]
+    pub skolemize: bool,
+    /// Whether to order the joined triple patterns at evaluation time instead of following their
+    /// order in the WHERE clause, the pattern matching the fewest stored rows given the variables
+    /// bound so far being evaluated next for each intermediate solution, which counts are probed
+    /// up to a small bound. The order of the results may differ from the static evaluation.
+    /// Note: it is ignored by [QueryMsg::BudgetedSelect], whose cursor relies on the static order.
+    #[serde(default)]
+    pub adaptive_joins: bool,
+    /// Whether to return the results in a deterministic order, by the stored keys of the terms
+    /// bound to the selected variables taken in the order of their names, so the results and the
+    /// ones kept by the limit only depend on the stored triples and not on how the query is
+    /// evaluated. All the solutions are evaluated to be ordered, which costs more than the default
+    /// order following the evaluation, so the query fails when they exceed the
+    /// `max_query_ordered_solution_count` limit of the store.
+    /// Note: it is ignored by [QueryMsg::BudgetedSelect], whose cursor relies on the evaluation order.
+    #[serde(default)]
+    pub ordered: bool,
+    /// The IRIs of the named graphs merged into the default graph the triple patterns are matched
+    /// against, as the SPARQL `FROM` clauses.
+    ///
+    /// If neither `from` nor `from_named` is given, the default graph is made of all the stored
+    /// triples, whatever their graph. Otherwise, it is made of the triples of the `from` graphs
+    /// only, and is empty if none is given.
+    ///
+    /// Note: the graph of a triple isn't stored, a triple belonging to the graph its subject has
+    /// first been described in, even if inserted later in another graph, and the triples about
+    /// blank nodes to any graph.
+    #[serde(default)]
+    pub from: Vec<IRI>,
+    /// The IRIs of the named graphs [WhereClause::Graph] clauses can match, as the SPARQL
+    /// `FROM NAMED` clauses. If not given while `from` is, no named graph can be matched.
+    ///
+    /// Note: as for `from`, a triple belongs to the graph its subject has first been described in.
+    #[serde(default)]
+    pub from_named: Vec<IRI>,
+}
+
+/// # ParameterValue
+/// The value a parameter of a stored query is bound to, see [QueryMsg::StoredQuery].
+#[cw_serde]
+pub enum ParameterValue {
+    /// # NamedNode
+    /// An IRI.
+    NamedNode(IRI),
+
+    /// # Literal
+    /// A literal, which can't be bound to a parameter in subject or predicate position.
+    Literal(Literal),
+}
+
+/// # Reasoning
+/// Enables the RDFS inference over the class and property hierarchies stored in the triple store
+/// when evaluating a query.
+///
+/// A triple pattern with a named predicate also matches the triples whose predicate is one of its
+/// sub-properties, as declared by `rdfs:subPropertyOf` triples. A triple pattern with the `rdf:type`
+/// predicate and a named object also matches the resources typed with one of its sub-classes, as
+/// declared by `rdfs:subClassOf` triples. Patterns with a variable predicate or class are matched
+/// as is.
+///
+/// Optionally, the resources linked by `owl:sameAs` triples, in either direction, can be treated as
+/// equivalent: a triple pattern with a named subject or object also matches the triples about the
+/// resources equivalent to it. Only the named nodes of the patterns are expanded, the values bound
+/// to variables being returned as stored.
+#[cw_serde]
+pub struct Reasoning {
+    /// The maximum number of hierarchy levels to infer over, e.g. 1 only considers the direct
+    /// sub-classes and sub-properties, and 0 disables the inference over the hierarchies.
+    /// If `None`, default to the maximum query inference depth defined in the store limitations,
+    /// which the value cannot exceed.
+    pub max_depth: Option<u32>,
+    /// The maximum number of `owl:sameAs` links to follow from a named node to find its equivalent
+    /// resources.
+    /// If `None`, the `owl:sameAs` links are not followed.
+    /// Note: the value cannot exceed the maximum query inference depth defined in the store
+    /// limitations.
+    pub same_as: Option<u32>,
 }
 
 /// # DescribeQuery
@@ -428,6 +2005,32 @@ pub struct DescribeQuery {
     pub r#where: Option<WhereClause>,
 }
 
+/// # CountQuery
+/// Represents a query counting the solutions of a WHERE clause.
+#[cw_serde]
+pub struct CountQuery {
+    /// The prefixes used in the query.
+    pub prefixes: Vec<Prefix>,
+    /// The WHERE clause whose solutions are counted.
+    pub r#where: WhereClause,
+    /// The RDFS inference to apply when matching the triple patterns of the WHERE clause.
+    /// If `None`, the patterns only match the stored triples.
+    pub reasoning: Option<Reasoning>,
+}
+
+/// # ProvenanceQuery
+/// Represents a query over the insertion of the triples matching a pattern.
+#[cw_serde]
+pub struct ProvenanceQuery {
+    /// The prefixes used in the query.
+    pub prefixes: Vec<Prefix>,
+    /// The pattern the triples shall match.
+    pub pattern: TriplePattern,
+    /// The maximum number of triples to return.
+    /// If not provided, the maximum query limit of the store is used.
+    pub limit: Option<u32>,
+}
+
 /// # ConstructQuery
 /// Represents a CONSTRUCT query over the triple store, allowing to retrieve a set of triples
 /// serialized in a specific format.
@@ -442,6 +2045,12 @@ pub struct ConstructQuery {
     /// The WHERE clause.
     /// This clause is used to specify the triples to construct using variable bindings.
     pub r#where: WhereClause,
+    /// Whether the blank nodes of the templates are minted afresh for each solution of the `where`
+    /// clause, as in SPARQL, instead of denoting the same node across all the solutions.
+    /// The minted ones are labelled after their template label and the number of their solution,
+    /// e.g. `a0_1`, so they stay distinct across the pages of the results.
+    #[serde(default)]
+    pub fresh_blank_nodes: bool,
 }
 
 /// # Prefix
@@ -480,10 +2089,45 @@ pub enum WhereClause {
     /// The solutions coming from the inner clause that do not match the expression are discarded.
     /// The variables provided in the inner clause are available in the filter expression.
     Filter { expr: Expression, inner: Box<Self> },
+
+    /// # Service
+    /// Evaluates the clause against another cognitarium contract, its solutions being joined with
+    /// the local ones on their common variables.
+    ///
+    /// The clause is sent once through a [QueryMsg::Select] query selecting all its variables,
+    /// along with the prefixes available to the query, independently of the rest of the where
+    /// clause. Hence, its solutions are bounded by the maximum query limit of the other store, the
+    /// query failing when this limit is reached as some solutions may be missing, and blank nodes
+    /// can't be returned as they are scoped to their store.
+    Service {
+        /// The address of the cognitarium contract to evaluate the clause against.
+        contract_addr: String,
+        /// The clause to evaluate.
+        clause: Box<Self>,
+    },
+
+    /// # Graph
+    /// Evaluates the clause against the triples of a named graph, i.e. the ones whose named subject
+    /// has first been described in it (see [ExecuteMsg::InsertData]), as the SPARQL `GRAPH`
+    /// pattern. As the graph of the blank nodes isn't tracked, the triples about them are matched
+    /// whatever the graph.
+    ///
+    /// When the query defines a dataset through [SelectQuery::from] or [SelectQuery::from_named],
+    /// the graph has to be one of the `from_named` ones to match any triple.
+    Graph {
+        /// The IRI of the named graph.
+        graph: IRI,
+        /// The clause to evaluate.
+        clause: Box<Self>,
+    },
 }
 
 /// # Expression
 /// Represents a logical combination of operations whose evaluation results in a term.
+///
+/// Literals typed as `xsd:dateTime` or `xsd:date` are compared by the instant they denote, whatever
+/// their timezone or precision, a date denoting the start of its day and values without timezone
+/// being considered as UTC. Other terms are compared by their lexical form.
 #[cw_serde]
 pub enum Expression {
     /// A named node constant.
@@ -512,6 +2156,41 @@ pub enum Expression {
     LessOrEqual(Box<Self>, Box<Self>),
     /// Negation of an expression.
     Not(Box<Self>),
+    /// Language range matching, as the SPARQL `langMatches(lang(...), ...)` function.
+    /// True if the expression evaluates to a language-tagged literal whose language tag matches the
+    /// given [BCP-47](https://www.rfc-editor.org/rfc/rfc4647#section-3.3.1) basic language range,
+    /// case-insensitively: `en` matches the `en` and `en-*` tags (e.g. `en-US`), while `*` matches any
+    /// language-tagged literal.
+    LangMatches(Box<Self>, String),
+    /// Substring matching, as the SPARQL `CONTAINS` function.
+    /// True if the expression evaluates to a literal whose lexical form contains the given string.
+    Contains {
+        expr: Box<Self>,
+        substring: String,
+        /// Whether the strings are compared case-insensitively.
+        #[serde(default)]
+        case_insensitive: bool,
+    },
+    /// Prefix matching, as the SPARQL `STRSTARTS` function.
+    /// True if the expression evaluates to a literal whose lexical form starts with the given string.
+    #[serde(rename = "strstarts")]
+    StrStarts {
+        expr: Box<Self>,
+        prefix: String,
+        /// Whether the strings are compared case-insensitively.
+        #[serde(default)]
+        case_insensitive: bool,
+    },
+    /// Suffix matching, as the SPARQL `STRENDS` function.
+    /// True if the expression evaluates to a literal whose lexical form ends with the given string.
+    #[serde(rename = "strends")]
+    StrEnds {
+        expr: Box<Self>,
+        suffix: String,
+        /// Whether the strings are compared case-insensitively.
+        #[serde(default)]
+        case_insensitive: bool,
+    },
 }
 
 /// # TripleDeleteTemplate
@@ -560,6 +2239,13 @@ pub enum VarOrNode {
     /// # Node
     /// A node, i.e. an IRI or a blank node.
     Node(Node),
+    /// # QuotedTriple
+    /// A quoted triple pattern, as in [RDF-star](https://www.w3.org/2021/12/rdf-star.html), matching
+    /// the quoted triples whose terms match it.
+    ///
+    /// Quoted triple patterns are only supported in where clauses, and count as triple patterns in
+    /// the store limits.
+    QuotedTriple(Box<TriplePattern>),
 }
 
 /// # VarOrNamedNode {
@@ -588,6 +2274,9 @@ pub enum VarOrNodeOrLiteral {
     /// An RDF [literal](https://www.w3.org/TR/rdf11-concepts/#dfn-literal), i.e. a simple literal,
     /// a language-tagged string or a typed value.
     Literal(Literal),
+    /// # QuotedTriple
+    /// A quoted triple pattern, see [VarOrNode::QuotedTriple].
+    QuotedTriple(Box<TriplePattern>),
 }
 
 /// # VarOrNamedNodeOrLiteral
@@ -663,6 +2352,13 @@ mod tests {
         assert_eq!(input.max_triple_byte_size, Uint128::MAX);
         assert_eq!(input.max_insert_data_byte_size, Uint128::MAX);
         assert_eq!(input.max_insert_data_triple_count, Uint128::MAX);
+        assert_eq!(input.max_result_byte_size, Uint128::MAX);
+        assert_eq!(input.max_insert_blank_node_count, Uint128::MAX);
+        assert_eq!(
+            input.max_insert_session_byte_size,
+            Uint128::new(16 * 1024 * 1024)
+        );
+        assert_eq!(input.max_query_ordered_solution_count, 1000);
     }
 
     #[test]
@@ -679,5 +2375,7 @@ mod tests {
         assert_eq!(msg.limits.max_triple_byte_size, Uint128::MAX);
         assert_eq!(msg.limits.max_insert_data_byte_size, Uint128::MAX);
         assert_eq!(msg.limits.max_insert_data_triple_count, Uint128::MAX);
+        assert_eq!(msg.limits.max_result_byte_size, Uint128::MAX);
+        assert_eq!(msg.recent_window, None);
     }
 }
@@ -2,24 +2,102 @@ use crate::msg::{
     Node, SelectItem, VarOrNamedNode, VarOrNamedNodeOrLiteral, VarOrNode, VarOrNodeOrLiteral,
 };
 use crate::querier::expression::Expression;
-use crate::querier::mapper::{iri_as_node, literal_as_object};
+use crate::querier::mapper::{
+    iri_as_node, literal_as_object, object_as_rdf, stored_object_as_rdf, stored_subject_as_rdf,
+    subject_as_rdf,
+};
 use crate::querier::plan::{PatternValue, QueryNode, QueryPlan};
 use crate::querier::variable::{ResolvedVariable, ResolvedVariables};
+use crate::rdf;
 use crate::rdf::Atom;
 use crate::state::{
-    triples, Namespace, NamespaceResolver, NamespaceSolver, Object, Predicate, Subject, Triple,
+    self, triples, Namespace, NamespaceResolver, NamespaceSolver, Object, Predicate, StoredTriple,
+    Subject, Term, TermQuerier, TermRef, Triple, STORE, SUBJECT_PROVENANCE,
 };
-use crate::{rdf, state};
 use axone_rdf::normalize::IdentifierIssuer;
 use cosmwasm_std::{Order, StdError, StdResult, Storage};
 use either::{Either, Left, Right};
-use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::cell::Cell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::iter;
 use std::rc::Rc;
 
 pub struct QueryEngine<'a> {
     storage: &'a dyn Storage,
     ns_cache: Vec<Namespace>,
+    budget: Option<&'a Budget>,
+}
+
+/// The bound on the number of index rows a plan evaluation can touch, tracking the evaluation
+/// progress so it can be resumed later on.
+///
+/// The progress is tracked on the driving pattern of the plan, i.e. its leftmost triple pattern,
+/// whose rows are streamed to the rest of the plan in order. When the driving node is a union, the
+/// driving patterns of its branches are evaluated in turn, their rows being tracked as a single
+/// sequence.
+pub struct Budget {
+    /// The maximum number of rows the evaluation can touch.
+    max_rows: u64,
+    /// The number of leading rows of the driving patterns still to skip, already evaluated.
+    skip_rows: Cell<u64>,
+    rows: Cell<u64>,
+    driving_rows: Cell<u64>,
+    exhausted: Cell<bool>,
+}
+
+impl Budget {
+    pub fn new(max_rows: u64, skip_rows: u64) -> Self {
+        Self {
+            max_rows,
+            skip_rows: Cell::new(skip_rows),
+            rows: Cell::new(0),
+            driving_rows: Cell::new(0),
+            exhausted: Cell::new(false),
+        }
+    }
+
+    /// The number of rows of the driving pattern streamed so far, skipped rows excluded.
+    pub fn driving_rows(&self) -> u64 {
+        self.driving_rows.get()
+    }
+
+    /// Whether the evaluation has been interrupted by the budget.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted.get()
+    }
+
+    /// Account for a touched row, returning false if the budget doesn't allow it.
+    fn touch(&self) -> bool {
+        let rows = self.rows.get();
+        if rows >= self.max_rows {
+            self.exhausted.set(true);
+            return false;
+        }
+        self.rows.set(rows + 1);
+        true
+    }
+
+    fn track<'a, T: 'a>(
+        &'a self,
+        rows: Box<dyn Iterator<Item = T> + 'a>,
+        driving: bool,
+    ) -> Box<dyn Iterator<Item = T> + 'a> {
+        if !driving {
+            return Box::new(rows.take_while(|_| self.touch()));
+        }
+
+        // the rows to skip are shared by the driving patterns, which are evaluated one after the
+        // other, so the branches of a union are skipped in turn
+        Box::new(
+            rows.skip_while(|_| {
+                let skip_rows = self.skip_rows.get();
+                self.skip_rows.set(skip_rows.saturating_sub(1));
+                skip_rows > 0
+            })
+            .take_while(|_| self.touch())
+            .inspect(|_| self.driving_rows.set(self.driving_rows.get() + 1)),
+        )
+    }
 }
 
 pub struct SelectResults<'a> {
@@ -29,7 +107,24 @@ pub struct SelectResults<'a> {
 
 impl<'a> QueryEngine<'a> {
     pub fn new(storage: &'a dyn Storage, ns_cache: Vec<Namespace>) -> Self {
-        Self { storage, ns_cache }
+        Self {
+            storage,
+            ns_cache,
+            budget: None,
+        }
+    }
+
+    /// Create an engine whose plan evaluations are bounded by the given budget.
+    pub fn with_budget(
+        storage: &'a dyn Storage,
+        ns_cache: Vec<Namespace>,
+        budget: &'a Budget,
+    ) -> Self {
+        Self {
+            storage,
+            ns_cache,
+            budget: Some(budget),
+        }
     }
 
     pub fn select(
@@ -78,6 +173,47 @@ impl<'a> QueryEngine<'a> {
         ))
     }
 
+    /// Construct the atoms of the first solutions of the plan up to the page size, telling whether
+    /// solutions remain beyond the page, the plan skipping the given number of solutions of the
+    /// previous pages. The blank nodes of the templates are minted for each solution if
+    /// `fresh_blank_nodes` is set.
+    ///
+    /// The blank nodes are labelled consistently across the pages: the stored ones after their
+    /// identifier in the store and the minted ones after the number of their solution.
+    pub fn construct_atoms_page(
+        &'a self,
+        plan: QueryPlan,
+        prefixes: &HashMap<String, String>,
+        templates: Vec<(VarOrNode, VarOrNamedNode, VarOrNodeOrLiteral)>,
+        skipped: usize,
+        page_size: usize,
+        fresh_blank_nodes: bool,
+    ) -> StdResult<(Vec<Atom>, bool)> {
+        let templates = templates
+            .into_iter()
+            .map(|t| AtomTemplate::try_new(&plan, prefixes, t))
+            .collect::<StdResult<Vec<AtomTemplate>>>()?;
+
+        let mut solutions = self.eval_plan(plan);
+        let page: Vec<_> = solutions.by_ref().take(page_size).collect();
+        let remaining = solutions.next().is_some();
+
+        let mut atoms = ResolvedAtomIterator::new(
+            self.storage,
+            self.ns_cache.clone(),
+            IdentifierIssuer::new("b", 0u128),
+            Box::new(page.into_iter()),
+            templates,
+        )
+        .with_stored_blank_labels();
+        if fresh_blank_nodes {
+            atoms = atoms.minting_blank_nodes(skipped);
+        }
+        let atoms = atoms.collect::<StdResult<Vec<Atom>>>()?;
+
+        Ok((atoms, remaining))
+    }
+
     pub fn construct_triples(
         &'a self,
         plan: QueryPlan,
@@ -93,47 +229,73 @@ impl<'a> QueryEngine<'a> {
         templates: Either<Vec<TripleTemplateWithBlankNode>, Vec<TripleTemplateNoBlankNode>>,
     ) -> StdResult<Vec<TripleTemplate>> {
         let mut ns_resolver = NamespaceResolver::new(self.storage, self.ns_cache.clone());
+        let normalize = STORE
+            .may_load(self.storage)?
+            .is_some_and(|store| store.literal_normalization);
 
         match templates {
             Left(tpl) => tpl
                 .into_iter()
-                .map(|t| TripleTemplate::try_new(&mut ns_resolver, plan, prefixes, Left(t)))
+                .map(|t| {
+                    TripleTemplate::try_new(&mut ns_resolver, plan, prefixes, Left(t), normalize)
+                })
                 .collect::<StdResult<Vec<TripleTemplate>>>(),
             Right(tpl) => tpl
                 .into_iter()
-                .map(|t| TripleTemplate::try_new(&mut ns_resolver, plan, prefixes, Right(t)))
+                .map(|t| {
+                    TripleTemplate::try_new(&mut ns_resolver, plan, prefixes, Right(t), normalize)
+                })
                 .collect::<StdResult<Vec<TripleTemplate>>>(),
         }
     }
 
     pub fn eval_plan(&'a self, plan: QueryPlan) -> ResolvedVariablesIterator<'_> {
-        return self.eval_node(plan.entrypoint)(ResolvedVariables::with_capacity(
+        return self.eval_node(plan.entrypoint, true, None)(ResolvedVariables::with_capacity(
             plan.variables.len(),
         ));
     }
 
+    /// Build the evaluation function of the plan, evaluating it from the given bindings of its
+    /// variables, e.g. to check whether it has solutions for a given value of one of them.
+    pub fn eval_plan_with(
+        &'a self,
+        plan: QueryPlan,
+    ) -> Rc<dyn Fn(ResolvedVariables) -> ResolvedVariablesIterator<'a> + 'a> {
+        self.eval_node(plan.entrypoint, true, None)
+    }
+
+    /// Build the evaluation function of a plan node, the driving node being the one whose results
+    /// lead the evaluation of the whole plan. The triple patterns of the node are restricted to the
+    /// given graphs, if any, see [QueryNode::Graph].
     fn eval_node(
         &'a self,
         node: QueryNode,
+        driving: bool,
+        graphs: Option<&Rc<BTreeSet<String>>>,
     ) -> Rc<dyn Fn(ResolvedVariables) -> ResolvedVariablesIterator<'a> + 'a> {
         match node {
             QueryNode::TriplePattern {
                 subject,
                 predicate,
                 object,
-            } => Rc::new(move |vars| {
-                Box::new(TriplePatternIterator::new(
-                    self.storage,
-                    vars,
-                    subject.clone(),
-                    predicate.clone(),
-                    object.clone(),
-                ))
-            }),
+            } => {
+                let graphs = graphs.cloned();
+                Rc::new(move |vars| {
+                    Box::new(TriplePatternIterator::new(
+                        self.storage,
+                        self.budget.map(|budget| (budget, driving)),
+                        graphs.clone(),
+                        vars,
+                        subject.clone(),
+                        predicate.clone(),
+                        object.clone(),
+                    ))
+                })
+            }
             QueryNode::Noop { .. } => Rc::new(|_| Box::new(iter::empty())),
             QueryNode::CartesianProductJoin { left, right } => {
-                let left = self.eval_node(*left);
-                let right = self.eval_node(*right);
+                let left = self.eval_node(*left, driving, graphs);
+                let right = self.eval_node(*right, false, graphs);
                 Rc::new(move |vars| {
                     let mut buffered_errors = VecDeque::new();
                     let values = right(vars.clone())
@@ -153,15 +315,37 @@ impl<'a> QueryEngine<'a> {
                 })
             }
             QueryNode::ForLoopJoin { left, right } => {
-                let left = self.eval_node(*left);
-                let right = self.eval_node(*right);
+                let left = self.eval_node(*left, driving, graphs);
+                let right = self.eval_node(*right, false, graphs);
                 Rc::new(move |vars| {
                     let right = Rc::clone(&right);
                     Box::new(ForLoopJoinIterator::new(left(vars), right))
                 })
             }
+            QueryNode::AdaptiveJoin { children } => {
+                let join = Rc::new(AdaptiveJoin {
+                    storage: self.storage,
+                    evals: children
+                        .iter()
+                        .map(|child| self.eval_node(child.clone(), false, graphs))
+                        .collect(),
+                    children,
+                });
+                Rc::new(move |vars| {
+                    let remaining = (0..join.children.len()).collect();
+                    AdaptiveJoin::eval(&join, vars, remaining)
+                })
+            }
+            QueryNode::Union { left, right } => {
+                // the branches drive the evaluation in turn, see [Budget]
+                let left = self.eval_node(*left, driving, graphs);
+                let right = self.eval_node(*right, driving, graphs);
+                Rc::new(move |vars| {
+                    Box::new(UnionIterator::new(left(vars.clone()).chain(right(vars))))
+                })
+            }
             QueryNode::Filter { expr, inner } => {
-                let inner = self.eval_node(*inner);
+                let inner = self.eval_node(*inner, driving, graphs);
                 Rc::new(move |vars| {
                     Box::new(FilterIterator::new(
                         self.storage,
@@ -171,16 +355,201 @@ impl<'a> QueryEngine<'a> {
                     ))
                 })
             }
+            QueryNode::Service {
+                variables,
+                solutions,
+                ..
+            } => Rc::new(move |vars| {
+                let variables = variables.clone();
+                Box::new(solutions.clone().into_iter().filter_map(move |solution| {
+                    let mut vars = vars.clone();
+                    for (index, value) in variables.iter().zip(solution) {
+                        match vars.get(*index) {
+                            Some(bound) if bound.as_object() != value.as_object() => return None,
+                            Some(_) => {}
+                            None => vars.merge_index(*index, value)?,
+                        }
+                    }
+                    Some(Ok(vars))
+                }))
+            }),
+            QueryNode::Graph { graphs, child } => {
+                self.eval_node(*child, driving, Some(&Rc::new(graphs)))
+            }
             QueryNode::Skip { child, first } => {
-                let upstream = self.eval_node(*child);
+                let upstream = self.eval_node(*child, driving, graphs);
                 Rc::new(move |vars| Box::new(upstream(vars).skip(first)))
             }
             QueryNode::Limit { child, first } => {
-                let upstream = self.eval_node(*child);
+                let upstream = self.eval_node(*child, driving, graphs);
                 Rc::new(move |vars| Box::new(upstream(vars).take(first)))
             }
+            QueryNode::QuotedTriple {
+                child,
+                triple,
+                subject,
+                predicate,
+                object,
+            } => {
+                let upstream = self.eval_node(*child, driving, graphs);
+                Rc::new(move |vars| {
+                    let (subject, predicate, object) =
+                        (subject.clone(), predicate.clone(), object.clone());
+                    Box::new(upstream(vars).filter_map(move |res| {
+                        let Ok(mut vars) = res else {
+                            return Some(res);
+                        };
+                        let Some(Subject::Triple(quoted)) = vars
+                            .get(triple)
+                            .as_ref()
+                            .and_then(ResolvedVariable::as_subject)
+                        else {
+                            return None;
+                        };
+                        let Triple {
+                            subject: s,
+                            predicate: p,
+                            object: o,
+                        } = *quoted;
+
+                        bind_quoted_term(&mut vars, &subject, s, ResolvedVariable::Subject)?;
+                        bind_quoted_term(&mut vars, &predicate, p, ResolvedVariable::Predicate)?;
+                        bind_quoted_term(&mut vars, &object, o, ResolvedVariable::Object)?;
+                        Some(Ok(vars))
+                    }))
+                })
+            }
+        }
+    }
+}
+
+/// Match a term of a quoted triple against its pattern, binding the pattern variable if not bound
+/// yet. The terms are compared by their key, as they may have been bound from another position.
+fn bind_quoted_term<T: Clone>(
+    vars: &mut ResolvedVariables,
+    pattern: &PatternValue<T>,
+    value: T,
+    wrap: fn(T) -> ResolvedVariable,
+) -> Option<()> {
+    let value = wrap(value);
+    match pattern {
+        PatternValue::Constant(constant) => {
+            (wrap(constant.clone()).key() == value.key()).then_some(())
+        }
+        PatternValue::Variable(v) | PatternValue::BlankVariable(v) => match vars.get(*v) {
+            Some(bound) => (bound.key() == value.key()).then_some(()),
+            None => {
+                let blank = matches!(
+                    value,
+                    ResolvedVariable::Subject(Subject::Blank(_))
+                        | ResolvedVariable::Object(Object::Blank(_))
+                );
+                if matches!(pattern, PatternValue::BlankVariable(_)) && !blank {
+                    None?;
+                }
+                vars.merge_index(*v, value)
+            }
+        },
+    }
+}
+
+/// Resolve the named subjects of the stored triples having the given predicate and object, e.g. the
+/// direct sub-classes of a class.
+pub fn named_subjects(
+    storage: &dyn Storage,
+    predicate: &Predicate,
+    object: &Object,
+) -> StdResult<Vec<state::Node>> {
+    let mut term_querier = TermQuerier::new();
+    TriplePatternIterator::make_stored_iter(
+        storage,
+        (None, Some(predicate.clone()), Some(object.clone())),
+        (false, false),
+    )
+    .map(|res| res.and_then(|t| t.resolve(storage, &mut term_querier)))
+    .filter_map(|res| match res {
+        Ok(Triple {
+            subject: Subject::Named(node),
+            ..
+        }) => Some(Ok(node)),
+        Ok(_) => None,
+        Err(e) => Some(Err(e)),
+    })
+    .collect()
+}
+
+/// Resolve the named objects of the stored triples having the given subject and predicate.
+pub fn named_objects(
+    storage: &dyn Storage,
+    subject: &Subject,
+    predicate: &Predicate,
+) -> StdResult<Vec<state::Node>> {
+    let mut term_querier = TermQuerier::new();
+    TriplePatternIterator::make_stored_iter(
+        storage,
+        (Some(subject.clone()), Some(predicate.clone()), None),
+        (false, false),
+    )
+    .map(|res| res.and_then(|t| t.resolve(storage, &mut term_querier)))
+    .filter_map(|res| match res {
+        Ok(Triple {
+            object: Object::Named(node),
+            ..
+        }) => Some(Ok(node)),
+        Ok(_) => None,
+        Err(e) => Some(Err(e)),
+    })
+    .collect()
+}
+
+/// Tell whether the stored triple belongs to one of the graphs, in their N-Quads form, i.e. whether
+/// its named subject has first been described in one of them, the triples about blank nodes
+/// belonging to any graph.
+fn in_graphs(
+    storage: &dyn Storage,
+    triple: &StoredTriple,
+    graphs: &BTreeSet<String>,
+) -> StdResult<bool> {
+    if let TermRef::Blank(_) = triple.subject {
+        return Ok(!graphs.is_empty());
+    }
+
+    Ok(SUBJECT_PROVENANCE
+        .may_load(storage, &triple.subject.key())?
+        .and_then(|provenance| provenance.graph)
+        .is_some_and(|graph| graphs.contains(&graph)))
+}
+
+/// Iterate over the stored triples matching the constant parts of a triple pattern, its variables
+/// matching any term.
+pub fn pattern_triples<'a>(
+    storage: &'a dyn Storage,
+    subject: &PatternValue<Subject>,
+    predicate: &PatternValue<Predicate>,
+    object: &PatternValue<Object>,
+) -> Box<dyn Iterator<Item = StdResult<StoredTriple>> + 'a> {
+    fn constant<T: Clone>(value: &PatternValue<T>) -> Option<T> {
+        match value {
+            PatternValue::Constant(v) => Some(v.clone()),
+            _ => None,
         }
     }
+
+    let filters = (constant(subject), constant(predicate), constant(object));
+    TriplePatternIterator::make_stored_iter(storage, filters, (false, false))
+}
+
+/// Count the stored triples matching the constant parts of a triple pattern, up to the given cap.
+pub fn count_pattern_rows(
+    storage: &dyn Storage,
+    subject: &PatternValue<Subject>,
+    predicate: &PatternValue<Predicate>,
+    object: &PatternValue<Object>,
+    cap: usize,
+) -> StdResult<usize> {
+    pattern_triples(storage, subject, predicate, object)
+        .take(cap)
+        .try_fold(0, |count, res| res.map(|_| count + 1))
 }
 
 type ResolvedVariablesIterator<'a> = Box<dyn Iterator<Item = StdResult<ResolvedVariables>> + 'a>;
@@ -267,6 +636,114 @@ impl<'a> Iterator for ForLoopJoinIterator<'a> {
     }
 }
 
+/// The maximum number of stored rows counted to estimate the rows matched by a node when choosing
+/// the next node of an adaptive join, bounding the work spent on each choice.
+const ADAPTIVE_JOIN_PROBE_CAP: usize = 16;
+
+/// Evaluate the nodes of an [QueryNode::AdaptiveJoin], the next node being chosen for each
+/// intermediate solution as the one matching the fewest rows given its bound variables.
+struct AdaptiveJoin<'a> {
+    storage: &'a dyn Storage,
+    children: Vec<QueryNode>,
+    evals: Vec<Rc<dyn Fn(ResolvedVariables) -> ResolvedVariablesIterator<'a> + 'a>>,
+}
+
+impl<'a> AdaptiveJoin<'a> {
+    /// Join the solution with the remaining nodes, the node matching no rows ending the evaluation.
+    fn eval(
+        join: &Rc<Self>,
+        vars: ResolvedVariables,
+        mut remaining: Vec<usize>,
+    ) -> ResolvedVariablesIterator<'a> {
+        let next = match remaining.len() {
+            0 => return Box::new(iter::once(Ok(vars))),
+            1 => 0,
+            _ => {
+                let (next, rows) = remaining
+                    .iter()
+                    .map(|child| join.estimate_rows(&join.children[*child], &vars))
+                    .enumerate()
+                    .min_by_key(|(_, rows)| *rows)
+                    .expect("remaining nodes should not be empty");
+                if rows == 0 {
+                    return Box::new(iter::empty());
+                }
+                next
+            }
+        };
+
+        let child = remaining.remove(next);
+        let this = Rc::clone(join);
+        Box::new(ForLoopJoinIterator::new(
+            (join.evals[child])(vars),
+            Rc::new(move |vars| Self::eval(&this, vars, remaining.clone())),
+        ))
+    }
+
+    /// Count the stored rows the node matches given the bound variables, up to the probe cap, the
+    /// nodes which can't be probed being given the cap.
+    fn estimate_rows(&self, node: &QueryNode, vars: &ResolvedVariables) -> usize {
+        match node {
+            QueryNode::TriplePattern {
+                subject,
+                predicate,
+                object,
+            } => TriplePatternIterator::compute_iter_io(
+                vars,
+                subject.clone(),
+                predicate.clone(),
+                object.clone(),
+            )
+            .map_or(0, |(filters, blank_filters, _)| {
+                TriplePatternIterator::make_stored_iter(self.storage, filters, blank_filters)
+                    .take(ADAPTIVE_JOIN_PROBE_CAP)
+                    .count()
+            }),
+            QueryNode::Noop { .. } => 0,
+            QueryNode::Union { left, right } => (self.estimate_rows(left, vars)
+                + self.estimate_rows(right, vars))
+            .min(ADAPTIVE_JOIN_PROBE_CAP),
+            QueryNode::Service { solutions, .. } => solutions.len().min(ADAPTIVE_JOIN_PROBE_CAP),
+            QueryNode::Graph { child, .. } => self.estimate_rows(child, vars),
+            _ => ADAPTIVE_JOIN_PROBE_CAP,
+        }
+    }
+}
+
+/// Iterate over the distinct solutions of the alternatives of a union.
+struct UnionIterator<'a> {
+    upstream: iter::Chain<ResolvedVariablesIterator<'a>, ResolvedVariablesIterator<'a>>,
+    seen: Vec<ResolvedVariables>,
+}
+
+impl<'a> UnionIterator<'a> {
+    fn new(
+        upstream: iter::Chain<ResolvedVariablesIterator<'a>, ResolvedVariablesIterator<'a>>,
+    ) -> Self {
+        Self {
+            upstream,
+            seen: Vec::new(),
+        }
+    }
+}
+
+impl<'a> Iterator for UnionIterator<'a> {
+    type Item = StdResult<ResolvedVariables>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.upstream.next()? {
+                Ok(vars) if self.seen.contains(&vars) => continue,
+                Ok(vars) => {
+                    self.seen.push(vars.clone());
+                    return Some(Ok(vars));
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
 struct CartesianProductJoinIterator<'a> {
     values: Vec<ResolvedVariables>,
     upstream_iter: ResolvedVariablesIterator<'a>,
@@ -330,6 +807,8 @@ type TriplePatternBindings = (Option<usize>, Option<usize>, Option<usize>);
 impl<'a> TriplePatternIterator<'a> {
     fn new(
         storage: &'a dyn Storage,
+        budget: Option<(&'a Budget, bool)>,
+        graphs: Option<Rc<BTreeSet<String>>>,
         input: ResolvedVariables,
         subject: PatternValue<Subject>,
         predicate: PatternValue<Predicate>,
@@ -341,7 +820,7 @@ impl<'a> TriplePatternIterator<'a> {
             return Self {
                 input,
                 output_bindings,
-                triple_iter: Self::make_state_iter(storage, filters, blank_filters),
+                triple_iter: Self::make_state_iter(storage, budget, graphs, filters, blank_filters),
             };
         }
 
@@ -352,14 +831,44 @@ impl<'a> TriplePatternIterator<'a> {
         }
     }
 
+    /// Iterate over the triples of the given graphs, if any, matching the filters, the stored rows
+    /// being accounted in the budget, if any, before their terms are resolved.
     fn make_state_iter(
         storage: &'a dyn Storage,
+        budget: Option<(&'a Budget, bool)>,
+        graphs: Option<Rc<BTreeSet<String>>>,
         filters: TriplePatternFilters,
         blank_filters: (bool, bool),
     ) -> Box<dyn Iterator<Item = StdResult<Triple>> + 'a> {
-        let post_filter = move |t: &Triple| {
-            let s = !blank_filters.0 || matches!(t.subject, Subject::Blank(_));
-            let o = !blank_filters.1 || matches!(t.object, Object::Blank(_));
+        let mut term_querier = TermQuerier::from(Self::filter_terms(&filters));
+        let stored = Self::make_stored_iter(storage, filters, blank_filters);
+        let stored = match budget {
+            Some((budget, driving)) => budget.track(stored, driving),
+            None => stored,
+        };
+        let stored: Box<dyn Iterator<Item = StdResult<StoredTriple>>> = match graphs {
+            Some(graphs) => Box::new(stored.filter_map(move |res| {
+                match res.and_then(|t| Ok(in_graphs(storage, &t, &graphs)?.then_some(t))) {
+                    Ok(t) => t.map(Ok),
+                    Err(e) => Some(Err(e)),
+                }
+            })),
+            None => stored,
+        };
+        Box::new(stored.map(move |res| res.and_then(|t| t.resolve(storage, &mut term_querier))))
+    }
+
+    /// Iterate over the stored triples matching the filters, the bound pattern parts selecting the
+    /// storage ordering to range over: primary key (OPS) or one of the SPO, POS or OSP indexes, so
+    /// only a fully unbound pattern leads to a full scan.
+    fn make_stored_iter(
+        storage: &'a dyn Storage,
+        filters: TriplePatternFilters,
+        blank_filters: (bool, bool),
+    ) -> Box<dyn Iterator<Item = StdResult<StoredTriple>> + 'a> {
+        let post_filter = move |t: &StoredTriple| {
+            let s = !blank_filters.0 || matches!(t.subject, TermRef::Blank(_));
+            let o = !blank_filters.1 || matches!(t.object, TermRef::Blank(_));
             o && s
         };
 
@@ -396,11 +905,11 @@ impl<'a> TriplePatternIterator<'a> {
             (Some(s), None, Some(o)) => Box::new(
                 triples()
                     .idx
-                    .subject_and_predicate
-                    .sub_prefix(s.key())
+                    .object_and_subject
+                    .prefix((o.key(), s.key()))
                     .range(storage, None, None, Order::Ascending)
                     .filter(move |res| match res {
-                        Ok((_, triple)) => triple.object == o && post_filter(triple),
+                        Ok((_, triple)) => post_filter(triple),
                         Err(_) => true,
                     })
                     .map(|res| res.map(|(_, t)| t)),
@@ -419,9 +928,12 @@ impl<'a> TriplePatternIterator<'a> {
             ),
             (None, Some(p), None) => Box::new(
                 triples()
+                    .idx
+                    .predicate_and_object
+                    .sub_prefix(p.key())
                     .range(storage, None, None, Order::Ascending)
                     .filter(move |res| match res {
-                        Ok((_, triple)) => triple.predicate == p && post_filter(triple),
+                        Ok((_, triple)) => post_filter(triple),
                         Err(_) => true,
                     })
                     .map(|res| res.map(|(_, t)| t)),
@@ -448,6 +960,25 @@ impl<'a> TriplePatternIterator<'a> {
         }
     }
 
+    /// Extract the terms already known from the pattern filters, sparing their resolution from the
+    /// term dictionary.
+    fn filter_terms(filters: &TriplePatternFilters) -> Vec<Term> {
+        let mut terms = Vec::with_capacity(3);
+        if let Some(Subject::Named(n)) = &filters.0 {
+            terms.push(Term::Node(n.clone()));
+        }
+        if let Some(p) = &filters.1 {
+            terms.push(Term::Node(p.clone()));
+        }
+        match &filters.2 {
+            Some(Object::Named(n)) => terms.push(Term::Node(n.clone())),
+            Some(Object::Literal(l)) => terms.push(Term::Literal(l.clone())),
+            _ => {}
+        }
+
+        terms
+    }
+
     fn compute_iter_io(
         input: &ResolvedVariables,
         subject: PatternValue<Subject>,
@@ -640,6 +1171,7 @@ impl TripleTemplate {
         plan: &QueryPlan,
         prefixes: &HashMap<String, String>,
         template: Either<TripleTemplateWithBlankNode, TripleTemplateNoBlankNode>,
+        normalize_literals: bool,
     ) -> StdResult<TripleTemplate> {
         let (s_tpl, p_tpl, o_tpl) = match template {
             Right((s, p, o)) => (Right(s), p, Right(o)),
@@ -649,7 +1181,13 @@ impl TripleTemplate {
         Ok(TripleTemplate {
             subject: Self::build_subject_template(ns_solver, plan, prefixes, s_tpl)?,
             predicate: Self::build_predicate_template(ns_solver, plan, prefixes, p_tpl)?,
-            object: Self::build_object_template(ns_solver, plan, prefixes, o_tpl)?,
+            object: Self::build_object_template(
+                ns_solver,
+                plan,
+                prefixes,
+                o_tpl,
+                normalize_literals,
+            )?,
         })
     }
 
@@ -730,6 +1268,7 @@ impl TripleTemplate {
             Left(VarOrNode::Node(Node::NamedNode(iri))) | Right(VarOrNamedNode::NamedNode(iri)) => {
                 Left(Subject::Named(iri_as_node(ns_solver, prefixes, iri)?))
             }
+            Left(VarOrNode::QuotedTriple(_)) => Err(StdError::generic_err(QUOTED_TEMPLATE_ERROR))?,
         })
     }
 
@@ -752,6 +1291,7 @@ impl TripleTemplate {
         plan: &QueryPlan,
         prefixes: &HashMap<String, String>,
         value: Either<VarOrNodeOrLiteral, VarOrNamedNodeOrLiteral>,
+        normalize_literals: bool,
     ) -> StdResult<Either<Object, usize>> {
         Ok(match value {
             Left(VarOrNodeOrLiteral::Variable(v)) | Right(VarOrNamedNodeOrLiteral::Variable(v)) => {
@@ -770,7 +1310,15 @@ impl TripleTemplate {
                 Left(Object::Named(iri_as_node(ns_solver, prefixes, iri)?))
             }
             Left(VarOrNodeOrLiteral::Literal(l)) | Right(VarOrNamedNodeOrLiteral::Literal(l)) => {
-                Left(literal_as_object(ns_solver, prefixes, l)?)
+                Left(literal_as_object(
+                    ns_solver,
+                    prefixes,
+                    l,
+                    normalize_literals,
+                )?)
+            }
+            Left(VarOrNodeOrLiteral::QuotedTriple(_)) => {
+                Err(StdError::generic_err(QUOTED_TEMPLATE_ERROR))?
             }
         })
     }
@@ -782,6 +1330,12 @@ pub struct ResolvedAtomIterator<'a> {
     upstream_iter: ResolvedVariablesIterator<'a>,
     templates: Vec<AtomTemplate>,
     buffer: VecDeque<StdResult<Atom>>,
+    /// The number of solutions resolved so far, if the blank nodes of the templates are minted
+    /// for each solution.
+    solution: Option<usize>,
+    /// Whether the stored blank nodes are labelled after their identifier in the store instead of
+    /// being issued a label.
+    stored_blank_labels: bool,
 }
 
 impl<'a> ResolvedAtomIterator<'a> {
@@ -798,8 +1352,24 @@ impl<'a> ResolvedAtomIterator<'a> {
             upstream_iter,
             templates,
             buffer: VecDeque::new(),
+            solution: None,
+            stored_blank_labels: false,
         }
     }
+
+    /// Mint the blank nodes of the templates afresh for each solution instead of sharing them
+    /// across the solutions, the solutions being numbered after the given number of skipped ones.
+    pub fn minting_blank_nodes(mut self, skipped: usize) -> Self {
+        self.solution = Some(skipped);
+        self
+    }
+
+    /// Label the stored blank nodes after their identifier in the store, see
+    /// [stored_blank_label](crate::querier::stored_blank_label).
+    pub fn with_stored_blank_labels(mut self) -> Self {
+        self.stored_blank_labels = true;
+        self
+    }
 }
 
 impl<'a> Iterator for ResolvedAtomIterator<'a> {
@@ -821,8 +1391,12 @@ impl<'a> Iterator for ResolvedAtomIterator<'a> {
                     self.buffer.push_back(Err(err));
                 }
                 Ok(vars) => {
+                    if let Some(solution) = self.solution.as_mut() {
+                        *solution += 1;
+                    }
                     for res in self.templates.iter().map(|template| {
-                        template.resolve(&mut self.ns_resolver, &mut self.id_issuer, &vars)
+                        let id_issuer = (!self.stored_blank_labels).then_some(&mut self.id_issuer);
+                        template.resolve(&mut self.ns_resolver, id_issuer, &vars, self.solution)
                     }) {
                         match res {
                             Ok(Some(atom)) => self.buffer.push_back(Ok(atom)),
@@ -836,6 +1410,9 @@ impl<'a> Iterator for ResolvedAtomIterator<'a> {
     }
 }
 
+/// The error of the templates holding a quoted triple pattern, which only match stored triples.
+const QUOTED_TEMPLATE_ERROR: &str = "Quoted triple patterns are not supported in templates";
+
 pub struct AtomTemplate {
     subject: Either<rdf::Subject, usize>,
     property: Either<rdf::Property, usize>,
@@ -854,6 +1431,7 @@ impl AtomTemplate {
                     StdError::generic_err("Selected variable not found in query"),
                 )?),
                 VarOrNode::Node(n) => Left((n, prefixes).try_into()?),
+                VarOrNode::QuotedTriple(_) => Err(StdError::generic_err(QUOTED_TEMPLATE_ERROR))?,
             },
             property: match p_tpl {
                 VarOrNamedNode::Variable(key) => Right(plan.get_var_index(key.as_str()).ok_or(
@@ -870,27 +1448,35 @@ impl AtomTemplate {
                 ),
                 VarOrNodeOrLiteral::Node(n) => Left((n, prefixes).try_into()?),
                 VarOrNodeOrLiteral::Literal(l) => Left((l, prefixes).try_into()?),
+                VarOrNodeOrLiteral::QuotedTriple(_) => {
+                    Err(StdError::generic_err(QUOTED_TEMPLATE_ERROR))?
+                }
             },
         })
     }
 
+    /// Resolve the template against the variables of a solution, the blank nodes of the template
+    /// being minted for the solution if its number is given. The stored blank nodes are issued a
+    /// label by the issuer if any, and labelled after their identifier in the store otherwise.
     pub fn resolve(
         &self,
         ns_solver: &mut dyn NamespaceSolver,
-        id_issuer: &mut IdentifierIssuer,
+        mut id_issuer: Option<&mut IdentifierIssuer>,
         vars: &ResolvedVariables,
+        solution: Option<usize>,
     ) -> StdResult<Option<Atom>> {
-        let subject = match self.resolve_atom_subject(ns_solver, id_issuer, vars)? {
-            Some(s) => s,
-            None => return Ok(None),
-        };
+        let subject =
+            match self.resolve_atom_subject(ns_solver, id_issuer.as_deref_mut(), vars, solution)? {
+                Some(s) => s,
+                None => return Ok(None),
+            };
 
         let property = match self.resolve_atom_property(ns_solver, vars)? {
             Some(p) => p,
             None => return Ok(None),
         };
 
-        let value = match self.resolve_atom_value(ns_solver, id_issuer, vars)? {
+        let value = match self.resolve_atom_value(ns_solver, id_issuer, vars, solution)? {
             Some(v) => v,
             None => return Ok(None),
         };
@@ -905,20 +1491,23 @@ impl AtomTemplate {
     fn resolve_atom_subject(
         &self,
         ns_solver: &mut dyn NamespaceSolver,
-        id_issuer: &mut IdentifierIssuer,
+        mut id_issuer: Option<&mut IdentifierIssuer>,
         vars: &ResolvedVariables,
+        solution: Option<usize>,
     ) -> StdResult<Option<rdf::Subject>> {
+        if let (Left(rdf::Subject::BlankNode(label)), Some(solution)) = (&self.subject, solution) {
+            return Ok(Some(rdf::Subject::BlankNode(mint_blank_node(
+                label, solution,
+            ))));
+        }
+
         Self::resolve_atom_term(
             &self.subject,
             ResolvedVariable::as_subject,
             vars,
-            &mut |value| {
-                Ok(match value {
-                    Subject::Named(n) => rdf::Subject::NamedNode(n.as_iri(ns_solver)?),
-                    Subject::Blank(n) => rdf::Subject::BlankNode(
-                        id_issuer.get_str_or_issue(n.to_string()).to_string(),
-                    ),
-                })
+            &mut |value| match id_issuer.as_deref_mut() {
+                Some(id_issuer) => subject_as_rdf(ns_solver, id_issuer, value),
+                None => stored_subject_as_rdf(ns_solver, value),
             },
             "subject",
         )
@@ -941,29 +1530,23 @@ impl AtomTemplate {
     fn resolve_atom_value(
         &self,
         ns_solver: &mut dyn NamespaceSolver,
-        id_issuer: &mut IdentifierIssuer,
+        mut id_issuer: Option<&mut IdentifierIssuer>,
         vars: &ResolvedVariables,
+        solution: Option<usize>,
     ) -> StdResult<Option<rdf::Value>> {
+        if let (Left(rdf::Value::BlankNode(label)), Some(solution)) = (&self.value, solution) {
+            return Ok(Some(rdf::Value::BlankNode(mint_blank_node(
+                label, solution,
+            ))));
+        }
+
         Self::resolve_atom_term(
             &self.value,
             ResolvedVariable::as_object,
             vars,
-            &mut |value| {
-                Ok(match value {
-                    Object::Named(n) => rdf::Value::NamedNode(n.as_iri(ns_solver)?),
-                    Object::Blank(n) => {
-                        rdf::Value::BlankNode(id_issuer.get_str_or_issue(n.to_string()).to_string())
-                    }
-                    Object::Literal(l) => match l {
-                        state::Literal::Simple { value } => rdf::Value::LiteralSimple(value),
-                        state::Literal::I18NString { value, language } => {
-                            rdf::Value::LiteralLang(value, language)
-                        }
-                        state::Literal::Typed { value, datatype } => {
-                            rdf::Value::LiteralDatatype(value, datatype.as_iri(ns_solver)?)
-                        }
-                    },
-                })
+            &mut |value| match id_issuer.as_deref_mut() {
+                Some(id_issuer) => object_as_rdf(ns_solver, id_issuer, value),
+                None => stored_object_as_rdf(ns_solver, value),
             },
             "object",
         )
@@ -997,6 +1580,12 @@ impl AtomTemplate {
     }
 }
 
+/// The label of a template blank node minted for a solution, after the number of the solution, so
+/// it is consistent across the pages and distinct from the labels of the stored blank nodes.
+fn mint_blank_node(label: &str, solution: usize) -> String {
+    format!("{label}_{solution}")
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1040,6 +1629,8 @@ mod test {
                     owner: Addr::unchecked("owner"),
                     limits: StoreLimitsInput::default().into(),
                     stat: StoreStat::default(),
+                    conflict_policy: Default::default(),
+                    literal_normalization: true,
                 },
             )
             .unwrap();
@@ -1049,7 +1640,9 @@ mod test {
         let buf = BufReader::new(data.as_slice());
         let mut reader = TripleReader::new(&axone_rdf::serde::DataFormat::RDFXml, buf);
         let mut storer = StoreEngine::new(storage).unwrap();
-        let count = storer.store_all(&mut reader).unwrap();
+        let count = storer
+            .store_all(&Addr::unchecked("owner"), &mut reader)
+            .unwrap();
 
         assert_eq!(count, Uint128::new(40u128));
     }
@@ -1164,7 +1757,7 @@ mod test {
                                 "subject".to_string(),
                                 ResolvedVariable::Subject(Subject::Named(Node {
                                     namespace: 11,
-                                    value: "d1615703-4ee1-4e2f-997e-15aecf1eea4e".to_string(),
+                                    value: "15592fd4-e368-46d3-b113-5d0ef8d4d10f".to_string(),
                                 })),
                             ),
                             (
@@ -1789,6 +2382,20 @@ mod test {
                 ),
                 expects: 5,
             },
+            TestCase {
+                filters: (
+                    Some(Subject::Named(state::Node {
+                        namespace: 0u128,
+                        value: "97ff7e16-c08d-47be-8475-211016c82e33".to_string(),
+                    })),
+                    None,
+                    Some(Object::Named(state::Node {
+                        namespace: 2u128,
+                        value: "NamedIndividual".to_string(),
+                    })),
+                ),
+                expects: 1,
+            },
             TestCase {
                 filters: (
                     Some(Subject::Named(state::Node {
@@ -1827,8 +2434,14 @@ mod test {
 
         for case in cases {
             assert_eq!(
-                TriplePatternIterator::make_state_iter(&deps.storage, case.filters, (false, false))
-                    .count(),
+                TriplePatternIterator::make_state_iter(
+                    &deps.storage,
+                    None,
+                    None,
+                    case.filters,
+                    (false, false),
+                )
+                .count(),
                 case.expects
             );
         }
@@ -1,8 +1,9 @@
 use crate::msg;
 use crate::querier::mapper::iri_as_string;
-use crate::querier::variable::HasBoundVariables;
+use crate::querier::temporal::{parse_instant, Instant};
+use crate::querier::variable::{HasBoundVariables, ResolvedVariable};
 use crate::querier::ResolvedVariables;
-use crate::state::NamespaceSolver;
+use crate::state::{Literal, NamespaceSolver, Object};
 use cosmwasm_std::{StdError, StdResult};
 use std::cmp::Ordering;
 use std::collections::HashMap;
@@ -19,6 +20,13 @@ pub enum Expression {
     Less(Box<Self>, Box<Self>),
     LessOrEqual(Box<Self>, Box<Self>),
     Not(Box<Self>),
+    LangMatches(Box<Self>, String),
+    /// Tells if the lexical form of a literal contains a string, case-insensitively if set.
+    Contains(Box<Self>, String, bool),
+    /// Tells if the lexical form of a literal starts with a string, case-insensitively if set.
+    StrStarts(Box<Self>, String, bool),
+    /// Tells if the lexical form of a literal ends with a string, case-insensitively if set.
+    StrEnds(Box<Self>, String, bool),
 }
 
 impl Expression {
@@ -66,10 +74,97 @@ impl Expression {
                 left.evaluate(vars, ns_solver)? <= right.evaluate(vars, ns_solver)?,
             )),
             Expression::Not(expr) => Ok(Term::Boolean(!expr.evaluate(vars, ns_solver)?.as_bool())),
+            Expression::LangMatches(expr, range) => Ok(Term::Boolean(
+                expr.language(vars)?
+                    .is_some_and(|tag| lang_matches(tag, range)),
+            )),
+            Expression::Contains(expr, substring, case_insensitive) => {
+                expr.text_matches(vars, substring, *case_insensitive, |value, substring| {
+                    value.contains(substring)
+                })
+            }
+            Expression::StrStarts(expr, prefix, case_insensitive) => {
+                expr.text_matches(vars, prefix, *case_insensitive, |value, prefix| {
+                    value.starts_with(prefix)
+                })
+            }
+            Expression::StrEnds(expr, suffix, case_insensitive) => {
+                expr.text_matches(vars, suffix, *case_insensitive, |value, suffix| {
+                    value.ends_with(suffix)
+                })
+            }
+        }
+    }
+
+    /// Tells if the expression evaluates to a literal whose lexical form matches the given string
+    /// according to the predicate, both being lowercased first if case-insensitive.
+    fn text_matches(
+        &self,
+        vars: &ResolvedVariables,
+        text: &str,
+        case_insensitive: bool,
+        predicate: impl Fn(&str, &str) -> bool,
+    ) -> StdResult<Term> {
+        Ok(Term::Boolean(self.lexical_form(vars)?.is_some_and(
+            |value| {
+                if case_insensitive {
+                    predicate(&value.to_lowercase(), &text.to_lowercase())
+                } else {
+                    predicate(value, text)
+                }
+            },
+        )))
+    }
+
+    /// Returns the language tag of the literal the expression evaluates to, if language-tagged.
+    ///
+    /// Constants being evaluated as plain terms, only variables can hold a language tag.
+    fn language<'v>(&self, vars: &'v ResolvedVariables) -> StdResult<Option<&'v str>> {
+        match self {
+            Expression::Variable(v) => match vars.get(*v) {
+                Some(ResolvedVariable::Object(Object::Literal(Literal::I18NString {
+                    language,
+                    ..
+                }))) => Ok(Some(language.as_str())),
+                Some(_) => Ok(None),
+                None => Err(StdError::generic_err("Unbound filter variable")),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the lexical form of the literal the expression evaluates to, if a literal, i.e.
+    /// without its language tag or datatype.
+    ///
+    /// As for [Expression::language], only variables are considered.
+    fn lexical_form<'v>(&self, vars: &'v ResolvedVariables) -> StdResult<Option<&'v str>> {
+        match self {
+            Expression::Variable(v) => match vars.get(*v) {
+                Some(ResolvedVariable::Object(Object::Literal(
+                    Literal::Simple { value }
+                    | Literal::I18NString { value, .. }
+                    | Literal::Typed { value, .. },
+                ))) => Ok(Some(value.as_str())),
+                Some(_) => Ok(None),
+                None => Err(StdError::generic_err("Unbound filter variable")),
+            },
+            _ => Ok(None),
         }
     }
 }
 
+/// Tells if a language tag matches a language range according to the basic filtering scheme of
+/// [RFC 4647](https://www.rfc-editor.org/rfc/rfc4647#section-3.3.1).
+fn lang_matches(tag: &str, range: &str) -> bool {
+    if range == "*" {
+        return !tag.is_empty();
+    }
+
+    let (tag, range) = (tag.to_ascii_lowercase(), range.to_ascii_lowercase());
+    tag.strip_prefix(&range)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+}
+
 impl HasBoundVariables for Expression {
     fn lookup_bound_variables(&self, callback: &mut impl FnMut(usize)) {
         match self {
@@ -90,7 +185,11 @@ impl HasBoundVariables for Expression {
                 left.lookup_bound_variables(callback);
                 right.lookup_bound_variables(callback);
             }
-            Expression::Not(expr) => {
+            Expression::Not(expr)
+            | Expression::LangMatches(expr, _)
+            | Expression::Contains(expr, ..)
+            | Expression::StrStarts(expr, ..)
+            | Expression::StrEnds(expr, ..) => {
                 expr.lookup_bound_variables(callback);
             }
         }
@@ -101,6 +200,8 @@ impl HasBoundVariables for Expression {
 pub enum Term {
     String(String),
     Boolean(bool),
+    /// A `xsd:dateTime` or `xsd:date` value, compared as the instant it denotes.
+    DateTime(Instant),
 }
 
 impl Term {
@@ -112,21 +213,31 @@ impl Term {
         literal: msg::Literal,
         prefixes: &HashMap<String, String>,
     ) -> StdResult<Self> {
-        Ok(Term::String(match literal {
-            msg::Literal::Simple(value) => value,
+        Ok(match literal {
+            msg::Literal::Simple(value) => Term::String(value),
             msg::Literal::LanguageTaggedString { value, language } => {
-                format!("{}{}", value, language)
+                Term::String(format!("{}{}", value, language))
             }
             msg::Literal::TypedValue { value, datatype } => {
-                format!("{}{}", value, iri_as_string(datatype, prefixes)?)
+                Term::from_typed(value, iri_as_string(datatype, prefixes)?)
             }
-        }))
+        })
+    }
+
+    /// Build the term of a typed literal, temporal values being valued by the instant they denote
+    /// so they can be compared whatever their lexical form.
+    pub fn from_typed(value: String, datatype: String) -> Self {
+        match parse_instant(&value, &datatype) {
+            Some(instant) => Term::DateTime(instant),
+            None => Term::String(format!("{}{}", value, datatype)),
+        }
     }
 
     pub fn as_string(&self) -> String {
         match self {
             Term::String(t) => t.clone(),
             Term::Boolean(b) => b.to_string(),
+            Term::DateTime((seconds, nanos)) => format!("{seconds}.{nanos:09}"),
         }
     }
 
@@ -134,6 +245,7 @@ impl Term {
         match self {
             Term::String(s) => !s.is_empty(),
             Term::Boolean(b) => *b,
+            Term::DateTime(_) => true,
         }
     }
 }
@@ -147,6 +259,7 @@ impl PartialOrd<Term> for Term {
         match (self, other) {
             (Term::String(left), Term::String(right)) => Some(left.cmp(right)),
             (Term::Boolean(left), Term::Boolean(right)) => Some(left.cmp(right)),
+            (Term::DateTime(left), Term::DateTime(right)) => Some(left.cmp(right)),
             _ => None,
         }
     }
@@ -211,6 +324,14 @@ mod tests {
                 vec![0, 1],
             ),
             (Expression::Not(Box::new(Expression::Variable(0))), vec![0]),
+            (
+                Expression::LangMatches(Box::new(Expression::Variable(0)), "en".to_string()),
+                vec![0],
+            ),
+            (
+                Expression::Contains(Box::new(Expression::Variable(0)), "a".to_string(), false),
+                vec![0],
+            ),
         ];
 
         for case in cases {
@@ -383,6 +504,225 @@ mod tests {
         }
     }
 
+    #[test]
+    fn expression_compare_date_times() {
+        let date_time = |value: &str| {
+            Box::new(Expression::Constant(Term::from_typed(
+                value.to_string(),
+                "http://www.w3.org/2001/XMLSchema#dateTime".to_string(),
+            )))
+        };
+        let cases = vec![
+            (
+                Expression::GreaterOrEqual(
+                    Box::new(Expression::Variable(0)),
+                    date_time("2024-01-01T00:00:00Z"),
+                ),
+                Ok(Term::Boolean(true)),
+            ),
+            (
+                Expression::Greater(
+                    Box::new(Expression::Variable(0)),
+                    date_time("2024-06-01T12:00:00.5+02:00"),
+                ),
+                Ok(Term::Boolean(false)),
+            ),
+            (
+                Expression::Equal(
+                    Box::new(Expression::Variable(0)),
+                    date_time("2024-06-01T12:00:00+02:00"),
+                ),
+                Ok(Term::Boolean(true)),
+            ),
+            (
+                Expression::Less(
+                    Box::new(Expression::Variable(0)),
+                    Box::new(Expression::Constant(Term::from_typed(
+                        "2024-06-02".to_string(),
+                        "http://www.w3.org/2001/XMLSchema#date".to_string(),
+                    ))),
+                ),
+                Ok(Term::Boolean(true)),
+            ),
+            (
+                Expression::Less(
+                    Box::new(Expression::Variable(0)),
+                    Box::new(Expression::Constant(Term::String("2025".to_string()))),
+                ),
+                Ok(Term::Boolean(false)),
+            ),
+        ];
+
+        let mut vars = ResolvedVariables::with_capacity(1);
+        vars.merge_index(
+            0,
+            ResolvedVariable::Object(Object::Literal(Literal::Typed {
+                value: "2024-06-01T10:00:00Z".to_string(),
+                datatype: Node {
+                    namespace: 0,
+                    value: "dateTime".to_string(),
+                },
+            })),
+        );
+
+        let mut ns_solver =
+            InMemoryNamespaceSolver::with(vec![(0, "http://www.w3.org/2001/XMLSchema#")]);
+        for case in cases {
+            assert_eq!(case.0.evaluate(&vars, &mut ns_solver), case.1);
+        }
+    }
+
+    #[test]
+    fn expression_lang_matches() {
+        let cases = vec![
+            (0, "en", Ok(Term::Boolean(true))),
+            (0, "EN", Ok(Term::Boolean(true))),
+            (0, "en-us", Ok(Term::Boolean(true))),
+            (0, "en-GB", Ok(Term::Boolean(false))),
+            (0, "*", Ok(Term::Boolean(true))),
+            (0, "e", Ok(Term::Boolean(false))),
+            (1, "en", Ok(Term::Boolean(true))),
+            (1, "en-US", Ok(Term::Boolean(false))),
+            (1, "fr", Ok(Term::Boolean(false))),
+            (2, "*", Ok(Term::Boolean(false))),
+            (3, "*", Ok(Term::Boolean(false))),
+            (
+                4,
+                "en",
+                Err(StdError::generic_err("Unbound filter variable")),
+            ),
+        ];
+
+        let mut vars = ResolvedVariables::with_capacity(5);
+        vars.merge_index(
+            0,
+            ResolvedVariable::Object(Object::Literal(Literal::I18NString {
+                value: "color".to_string(),
+                language: "en-US".to_string(),
+            })),
+        );
+        vars.merge_index(
+            1,
+            ResolvedVariable::Object(Object::Literal(Literal::I18NString {
+                value: "colour".to_string(),
+                language: "en".to_string(),
+            })),
+        );
+        vars.merge_index(
+            2,
+            ResolvedVariable::Object(Object::Literal(Literal::Simple {
+                value: "color".to_string(),
+            })),
+        );
+        vars.merge_index(
+            3,
+            ResolvedVariable::Object(Object::Named(Node {
+                namespace: 0,
+                value: "color".to_string(),
+            })),
+        );
+
+        let mut ns_solver = InMemoryNamespaceSolver::with(vec![(0, "http:://example.com/")]);
+        for (var, range, expected) in cases {
+            assert_eq!(
+                Expression::LangMatches(Box::new(Expression::Variable(var)), range.to_string())
+                    .evaluate(&vars, &mut ns_solver),
+                expected,
+                "langMatches({var}, {range})"
+            );
+        }
+    }
+
+    #[test]
+    fn expression_text_matches() {
+        let contains = |var, text: &str, case_insensitive| {
+            Expression::Contains(
+                Box::new(Expression::Variable(var)),
+                text.to_string(),
+                case_insensitive,
+            )
+        };
+        let starts = |var, text: &str, case_insensitive| {
+            Expression::StrStarts(
+                Box::new(Expression::Variable(var)),
+                text.to_string(),
+                case_insensitive,
+            )
+        };
+        let ends = |var, text: &str, case_insensitive| {
+            Expression::StrEnds(
+                Box::new(Expression::Variable(var)),
+                text.to_string(),
+                case_insensitive,
+            )
+        };
+        let cases = vec![
+            (contains(0, "Dataset", false), Ok(Term::Boolean(true))),
+            (contains(0, "dataset", false), Ok(Term::Boolean(false))),
+            (contains(0, "dataset", true), Ok(Term::Boolean(true))),
+            (contains(0, "", false), Ok(Term::Boolean(true))),
+            (starts(0, "Open", false), Ok(Term::Boolean(true))),
+            (starts(0, "OPEN", true), Ok(Term::Boolean(true))),
+            (starts(0, "Dataset", false), Ok(Term::Boolean(false))),
+            (ends(0, "Dataset", false), Ok(Term::Boolean(true))),
+            (ends(0, "open", true), Ok(Term::Boolean(false))),
+            (contains(1, "couleur", false), Ok(Term::Boolean(true))),
+            (contains(1, "fr", false), Ok(Term::Boolean(false))),
+            (ends(2, "42", false), Ok(Term::Boolean(true))),
+            (contains(2, "integer", false), Ok(Term::Boolean(false))),
+            (contains(3, "foo", false), Ok(Term::Boolean(false))),
+            (
+                contains(4, "foo", false),
+                Err(StdError::generic_err("Unbound filter variable")),
+            ),
+            (
+                Expression::Contains(
+                    Box::new(Expression::Constant(Term::String("foo".to_string()))),
+                    "foo".to_string(),
+                    false,
+                ),
+                Ok(Term::Boolean(false)),
+            ),
+        ];
+
+        let mut vars = ResolvedVariables::with_capacity(5);
+        vars.merge_index(
+            0,
+            ResolvedVariable::Object(Object::Literal(Literal::Simple {
+                value: "Open Dataset".to_string(),
+            })),
+        );
+        vars.merge_index(
+            1,
+            ResolvedVariable::Object(Object::Literal(Literal::I18NString {
+                value: "couleur".to_string(),
+                language: "fr".to_string(),
+            })),
+        );
+        vars.merge_index(
+            2,
+            ResolvedVariable::Object(Object::Literal(Literal::Typed {
+                value: "42".to_string(),
+                datatype: Node {
+                    namespace: 0,
+                    value: "integer".to_string(),
+                },
+            })),
+        );
+        vars.merge_index(
+            3,
+            ResolvedVariable::Object(Object::Named(Node {
+                namespace: 0,
+                value: "foo".to_string(),
+            })),
+        );
+
+        let mut ns_solver = InMemoryNamespaceSolver::with(vec![(0, "http:://example.com/")]);
+        for (expr, expected) in cases {
+            assert_eq!(expr.evaluate(&vars, &mut ns_solver), expected, "{expr:?}");
+        }
+    }
+
     #[test]
     fn term_from_iri() {
         let cases = vec![
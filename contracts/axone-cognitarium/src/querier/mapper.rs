@@ -1,24 +1,35 @@
 use crate::msg::{Literal, IRI};
-use crate::state;
-use crate::state::{NamespaceSolver, Object};
+use crate::rdf::Atom;
+use crate::state::{NamespaceSolver, Object, Subject, Triple};
+use crate::{rdf, state};
+use axone_rdf::normalize::IdentifierIssuer;
 use axone_rdf::uri::{expand_uri, explode_iri};
 use cosmwasm_std::StdResult;
 use std::collections::HashMap;
 
+/// Map a literal to the object it is stored as, its lexical form being normalized if the store
+/// normalizes the inserted literals, see [rdf::canonical_lexical_form].
 pub fn literal_as_object(
     ns_solver: &mut dyn NamespaceSolver,
     prefixes: &HashMap<String, String>,
     literal: Literal,
+    normalize: bool,
 ) -> StdResult<Object> {
     Ok(Object::Literal(match literal {
         Literal::Simple(value) => state::Literal::Simple { value },
         Literal::LanguageTaggedString { value, language } => {
             state::Literal::I18NString { value, language }
         }
-        Literal::TypedValue { value, datatype } => state::Literal::Typed {
-            value,
-            datatype: iri_as_node(ns_solver, prefixes, datatype)?,
-        },
+        Literal::TypedValue { value, datatype } => {
+            let datatype = iri_as_string(datatype, prefixes)?;
+            state::Literal::Typed {
+                value: normalize
+                    .then(|| rdf::canonical_lexical_form(&datatype, &value))
+                    .flatten()
+                    .unwrap_or(value),
+                datatype: iri_as_node(ns_solver, prefixes, IRI::Full(datatype))?,
+            }
+        }
     }))
 }
 
@@ -46,3 +57,106 @@ pub fn iri_as_string(iri: IRI, prefixes: &HashMap<String, String>) -> StdResult<
         IRI::Full(full) => Ok(full),
     }
 }
+
+/// The N-Quads form of the IRI of a graph, the form the graphs are kept in.
+pub fn iri_as_graph_name(iri: IRI, prefixes: &HashMap<String, String>) -> StdResult<String> {
+    iri_as_string(iri, prefixes).map(|iri| format!("<{iri}>"))
+}
+
+pub fn subject_as_rdf(
+    ns_solver: &mut dyn NamespaceSolver,
+    id_issuer: &mut IdentifierIssuer,
+    subject: Subject,
+) -> StdResult<rdf::Subject> {
+    Ok(match subject {
+        Subject::Named(n) => rdf::Subject::NamedNode(n.as_iri(ns_solver)?),
+        Subject::Blank(n) => {
+            rdf::Subject::BlankNode(id_issuer.get_str_or_issue(n.to_string()).to_string())
+        }
+        Subject::Triple(t) => {
+            rdf::Subject::Triple(Box::new(triple_as_atom(ns_solver, id_issuer, *t)?))
+        }
+    })
+}
+
+pub fn object_as_rdf(
+    ns_solver: &mut dyn NamespaceSolver,
+    id_issuer: &mut IdentifierIssuer,
+    object: Object,
+) -> StdResult<rdf::Value> {
+    Ok(match object {
+        Object::Named(n) => rdf::Value::NamedNode(n.as_iri(ns_solver)?),
+        Object::Blank(n) => {
+            rdf::Value::BlankNode(id_issuer.get_str_or_issue(n.to_string()).to_string())
+        }
+        Object::Literal(l) => match l {
+            state::Literal::Simple { value } => rdf::Value::LiteralSimple(value),
+            state::Literal::I18NString { value, language } => {
+                rdf::Value::LiteralLang(value, language)
+            }
+            state::Literal::Typed { value, datatype } => {
+                rdf::Value::LiteralDatatype(value, datatype.as_iri(ns_solver)?)
+            }
+        },
+        Object::Triple(t) => {
+            rdf::Value::Triple(Box::new(triple_as_atom(ns_solver, id_issuer, *t)?))
+        }
+    })
+}
+
+pub fn triple_as_atom(
+    ns_solver: &mut dyn NamespaceSolver,
+    id_issuer: &mut IdentifierIssuer,
+    triple: Triple,
+) -> StdResult<Atom> {
+    Ok(Atom {
+        subject: subject_as_rdf(ns_solver, id_issuer, triple.subject)?,
+        property: rdf::Property(triple.predicate.as_iri(ns_solver)?),
+        value: object_as_rdf(ns_solver, id_issuer, triple.object)?,
+    })
+}
+
+/// The label of a stored blank node, after its identifier in the store, so it is consistent across
+/// the pages of a result.
+pub fn stored_blank_label(blank: state::BlankNode) -> String {
+    format!("b{blank}")
+}
+
+/// Map a stored subject as [subject_as_rdf] does, its blank nodes, including the ones of its quoted
+/// triples, being labelled after their identifier in the store, see [stored_blank_label].
+pub fn stored_subject_as_rdf(
+    ns_solver: &mut dyn NamespaceSolver,
+    subject: Subject,
+) -> StdResult<rdf::Subject> {
+    Ok(match subject {
+        Subject::Blank(blank) => rdf::Subject::BlankNode(stored_blank_label(blank)),
+        Subject::Triple(t) => rdf::Subject::Triple(Box::new(stored_triple_as_atom(ns_solver, *t)?)),
+        subject => subject_as_rdf(ns_solver, &mut IdentifierIssuer::new("b", 0u128), subject)?,
+    })
+}
+
+/// Map a stored object as [object_as_rdf] does, its blank nodes, including the ones of its quoted
+/// triples, being labelled after their identifier in the store, see [stored_blank_label].
+pub fn stored_object_as_rdf(
+    ns_solver: &mut dyn NamespaceSolver,
+    object: Object,
+) -> StdResult<rdf::Value> {
+    Ok(match object {
+        Object::Blank(blank) => rdf::Value::BlankNode(stored_blank_label(blank)),
+        Object::Triple(t) => rdf::Value::Triple(Box::new(stored_triple_as_atom(ns_solver, *t)?)),
+        object => object_as_rdf(ns_solver, &mut IdentifierIssuer::new("b", 0u128), object)?,
+    })
+}
+
+/// Map a stored triple as [triple_as_atom] does, its blank nodes being labelled after their
+/// identifier in the store, see [stored_blank_label].
+pub fn stored_triple_as_atom(
+    ns_solver: &mut dyn NamespaceSolver,
+    triple: Triple,
+) -> StdResult<Atom> {
+    Ok(Atom {
+        subject: stored_subject_as_rdf(ns_solver, triple.subject)?,
+        property: rdf::Property(triple.predicate.as_iri(ns_solver)?),
+        value: stored_object_as_rdf(ns_solver, triple.object)?,
+    })
+}
@@ -3,9 +3,14 @@ mod expression;
 mod mapper;
 mod plan;
 mod plan_builder;
+mod temporal;
 mod variable;
 
 pub use engine::*;
+pub use mapper::{
+    iri_as_graph_name, iri_as_node, stored_blank_label, stored_triple_as_atom, triple_as_atom,
+};
 pub use plan::*;
 pub use plan_builder::*;
-pub use variable::ResolvedVariables;
+pub use temporal::{format_date_time, parse_date_time};
+pub use variable::{HasBoundVariables, ResolvedVariable, ResolvedVariables};
@@ -1,6 +1,7 @@
 use crate::querier::expression::Expression;
-use crate::querier::variable::HasBoundVariables;
+use crate::querier::variable::{HasBoundVariables, ResolvedVariable};
 use crate::state::{Object, Predicate, Subject};
+use std::collections::BTreeSet;
 
 /// Represents a querying plan.
 #[derive(Eq, PartialEq, Debug, Clone)]
@@ -18,6 +19,8 @@ pub struct QueryPlan {
 pub enum PlanVariable {
     Basic(String),
     BlankNode(String),
+    /// An anonymous variable bound to the quoted triples matching a quoted triple pattern.
+    QuotedTriple,
 }
 
 impl QueryPlan {
@@ -74,9 +77,52 @@ pub enum QueryNode {
     /// left node to use them as right node values.
     ForLoopJoin { left: Box<Self>, right: Box<Self> },
 
+    /// Join the nodes by evaluating them one after the other, the order being chosen at evaluation
+    /// time for each intermediate solution: the node matching the fewest stored rows given the
+    /// variables bound so far comes next.
+    ///
+    /// This is used in place of the trees of joins when the patterns order given by the query can't
+    /// be relied on, e.g. on skewed datasets.
+    AdaptiveJoin { children: Vec<Self> },
+
+    /// Merge the distinct results of two nodes binding the same variables, the right node being
+    /// evaluated once the left one is exhausted.
+    ///
+    /// This is used to match the alternatives of a triple pattern inferred from the class and
+    /// property hierarchies.
+    Union { left: Box<Self>, right: Box<Self> },
+
     /// Filter the results of the inner node by applying the expression.
     Filter { expr: Expression, inner: Box<Self> },
 
+    /// Match the terms of the quoted triple bound to the `triple` variable by the child node against
+    /// the pattern, binding its variables. The solutions whose variable isn't bound to a matching
+    /// quoted triple are discarded.
+    QuotedTriple {
+        child: Box<Self>,
+        triple: usize,
+        subject: PatternValue<Subject>,
+        predicate: PatternValue<Predicate>,
+        object: PatternValue<Object>,
+    },
+
+    /// Join the solutions of a clause evaluated by another store, fetched when building the plan,
+    /// each solution giving the values of the variables of the clause, in order.
+    Service {
+        contract_addr: String,
+        variables: Vec<usize>,
+        solutions: Vec<Vec<ResolvedVariable>>,
+    },
+
+    /// Restrict the triple patterns of the child node to the triples of the given graphs, in their
+    /// N-Quads form, i.e. the ones whose named subject has first been described in one of them. The
+    /// triples about blank nodes, whose graph isn't tracked, belong to any graph. The innermost
+    /// restriction applies to a triple pattern.
+    Graph {
+        graphs: BTreeSet<String>,
+        child: Box<Self>,
+    },
+
     /// Skip the specified first elements from the child node.
     Skip { child: Box<Self>, first: usize },
 
@@ -90,6 +136,127 @@ impl QueryNode {
             bound_variables: Vec::new(),
         }
     }
+
+    /// Rewrite the trees of joins of the node into [QueryNode::AdaptiveJoin] nodes, the joined nodes
+    /// being ordered at evaluation time.
+    pub fn into_adaptive_joins(self) -> Self {
+        match self {
+            QueryNode::CartesianProductJoin { .. } | QueryNode::ForLoopJoin { .. } => {
+                let mut children = Vec::new();
+                self.flatten_join(&mut children);
+                QueryNode::AdaptiveJoin { children }
+            }
+            QueryNode::Union { left, right } => QueryNode::Union {
+                left: Box::new(left.into_adaptive_joins()),
+                right: Box::new(right.into_adaptive_joins()),
+            },
+            QueryNode::Filter { expr, inner } => QueryNode::Filter {
+                expr,
+                inner: Box::new(inner.into_adaptive_joins()),
+            },
+            QueryNode::QuotedTriple {
+                child,
+                triple,
+                subject,
+                predicate,
+                object,
+            } => QueryNode::QuotedTriple {
+                child: Box::new(child.into_adaptive_joins()),
+                triple,
+                subject,
+                predicate,
+                object,
+            },
+            QueryNode::Graph { graphs, child } => QueryNode::Graph {
+                graphs,
+                child: Box::new(child.into_adaptive_joins()),
+            },
+            QueryNode::Skip { child, first } => QueryNode::Skip {
+                child: Box::new(child.into_adaptive_joins()),
+                first,
+            },
+            QueryNode::Limit { child, first } => QueryNode::Limit {
+                child: Box::new(child.into_adaptive_joins()),
+                first,
+            },
+            node => node,
+        }
+    }
+
+    /// Restrict the node to the dataset made of the given default and named graphs, the triple
+    /// patterns outside of a [QueryNode::Graph] node matching the default graphs, and the ones
+    /// within only matching the named graphs.
+    pub fn within_dataset(self, default: BTreeSet<String>, named: &BTreeSet<String>) -> Self {
+        QueryNode::Graph {
+            graphs: default,
+            child: Box::new(self.within_named_graphs(named)),
+        }
+    }
+
+    fn within_named_graphs(self, named: &BTreeSet<String>) -> Self {
+        let within = |node: Box<Self>| Box::new(node.within_named_graphs(named));
+        match self {
+            QueryNode::Graph { graphs, child } => QueryNode::Graph {
+                graphs: graphs.intersection(named).cloned().collect(),
+                child: within(child),
+            },
+            QueryNode::CartesianProductJoin { left, right } => QueryNode::CartesianProductJoin {
+                left: within(left),
+                right: within(right),
+            },
+            QueryNode::ForLoopJoin { left, right } => QueryNode::ForLoopJoin {
+                left: within(left),
+                right: within(right),
+            },
+            QueryNode::AdaptiveJoin { children } => QueryNode::AdaptiveJoin {
+                children: children
+                    .into_iter()
+                    .map(|child| child.within_named_graphs(named))
+                    .collect(),
+            },
+            QueryNode::Union { left, right } => QueryNode::Union {
+                left: within(left),
+                right: within(right),
+            },
+            QueryNode::Filter { expr, inner } => QueryNode::Filter {
+                expr,
+                inner: within(inner),
+            },
+            QueryNode::QuotedTriple {
+                child,
+                triple,
+                subject,
+                predicate,
+                object,
+            } => QueryNode::QuotedTriple {
+                child: within(child),
+                triple,
+                subject,
+                predicate,
+                object,
+            },
+            QueryNode::Skip { child, first } => QueryNode::Skip {
+                child: within(child),
+                first,
+            },
+            QueryNode::Limit { child, first } => QueryNode::Limit {
+                child: within(child),
+                first,
+            },
+            node => node,
+        }
+    }
+
+    fn flatten_join(self, children: &mut Vec<Self>) {
+        match self {
+            QueryNode::CartesianProductJoin { left, right }
+            | QueryNode::ForLoopJoin { left, right } => {
+                left.flatten_join(children);
+                right.flatten_join(children);
+            }
+            node => children.push(node.into_adaptive_joins()),
+        }
+    }
 }
 
 impl HasBoundVariables for QueryNode {
@@ -108,15 +275,38 @@ impl HasBoundVariables for QueryNode {
                 bound_variables.iter().for_each(|v| callback(*v));
             }
             QueryNode::CartesianProductJoin { left, right }
-            | QueryNode::ForLoopJoin { left, right } => {
+            | QueryNode::ForLoopJoin { left, right }
+            | QueryNode::Union { left, right } => {
                 left.lookup_bound_variables(callback);
                 right.lookup_bound_variables(callback);
             }
+            QueryNode::AdaptiveJoin { children } => {
+                children
+                    .iter()
+                    .for_each(|child| child.lookup_bound_variables(callback));
+            }
             QueryNode::Filter { expr, inner } => {
                 expr.lookup_bound_variables(callback);
                 inner.lookup_bound_variables(callback);
             }
-            QueryNode::Skip { child, .. } | QueryNode::Limit { child, .. } => {
+            QueryNode::QuotedTriple {
+                child,
+                subject,
+                predicate,
+                object,
+                ..
+            } => {
+                child.lookup_bound_variables(callback);
+                subject.lookup_bound_variable(callback);
+                predicate.lookup_bound_variable(callback);
+                object.lookup_bound_variable(callback);
+            }
+            QueryNode::Service { variables, .. } => {
+                variables.iter().for_each(|v| callback(*v));
+            }
+            QueryNode::Graph { child, .. }
+            | QueryNode::Skip { child, .. }
+            | QueryNode::Limit { child, .. } => {
                 child.lookup_bound_variables(callback);
             }
         }
@@ -215,4 +405,94 @@ mod tests {
         assert_eq!(plan.get_var_index("2"), Some(1usize));
         assert_eq!(plan.get_var_index("3"), None);
     }
+
+    #[test]
+    fn within_dataset() {
+        let pattern = |v: usize| QueryNode::TriplePattern {
+            subject: PatternValue::Variable(v),
+            predicate: PatternValue::Variable(v + 1),
+            object: PatternValue::Variable(v + 2),
+        };
+        let graphs = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+
+        let node = QueryNode::ForLoopJoin {
+            left: Box::new(pattern(0)),
+            right: Box::new(QueryNode::Graph {
+                graphs: graphs(&["<g2>"]),
+                child: Box::new(QueryNode::Union {
+                    left: Box::new(QueryNode::Graph {
+                        graphs: graphs(&["<g3>"]),
+                        child: Box::new(pattern(3)),
+                    }),
+                    right: Box::new(pattern(6)),
+                }),
+            }),
+        };
+
+        assert_eq!(
+            node.within_dataset(graphs(&["<g1>"]), &graphs(&["<g2>", "<g4>"])),
+            QueryNode::Graph {
+                graphs: graphs(&["<g1>"]),
+                child: Box::new(QueryNode::ForLoopJoin {
+                    left: Box::new(pattern(0)),
+                    right: Box::new(QueryNode::Graph {
+                        graphs: graphs(&["<g2>"]),
+                        child: Box::new(QueryNode::Union {
+                            left: Box::new(QueryNode::Graph {
+                                graphs: BTreeSet::new(),
+                                child: Box::new(pattern(3)),
+                            }),
+                            right: Box::new(pattern(6)),
+                        }),
+                    }),
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn into_adaptive_joins() {
+        let pattern = |v: usize| QueryNode::TriplePattern {
+            subject: PatternValue::Variable(v),
+            predicate: PatternValue::Variable(v + 1),
+            object: PatternValue::Variable(v + 2),
+        };
+
+        let node = QueryNode::Limit {
+            first: 10,
+            child: Box::new(QueryNode::ForLoopJoin {
+                left: Box::new(QueryNode::CartesianProductJoin {
+                    left: Box::new(pattern(0)),
+                    right: Box::new(pattern(3)),
+                }),
+                right: Box::new(QueryNode::Union {
+                    left: Box::new(pattern(6)),
+                    right: Box::new(QueryNode::ForLoopJoin {
+                        left: Box::new(pattern(9)),
+                        right: Box::new(pattern(12)),
+                    }),
+                }),
+            }),
+        };
+
+        assert_eq!(
+            node.into_adaptive_joins(),
+            QueryNode::Limit {
+                first: 10,
+                child: Box::new(QueryNode::AdaptiveJoin {
+                    children: vec![
+                        pattern(0),
+                        pattern(3),
+                        QueryNode::Union {
+                            left: Box::new(pattern(6)),
+                            right: Box::new(QueryNode::AdaptiveJoin {
+                                children: vec![pattern(9), pattern(12)],
+                            }),
+                        },
+                    ],
+                }),
+            }
+        );
+        assert_eq!(pattern(0).into_adaptive_joins(), pattern(0));
+    }
 }
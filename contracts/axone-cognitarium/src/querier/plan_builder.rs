@@ -1,21 +1,75 @@
+use crate::error::QueryComplexityError;
 use crate::msg;
-use crate::msg::{Node, TriplePattern, VarOrNamedNode, VarOrNode, VarOrNodeOrLiteral, WhereClause};
+use crate::msg::{
+    Node, Prefix, QueryMsg, SelectItem, SelectQuery, SelectResponse, StoreResponse, TriplePattern,
+    Value, VarOrNamedNode, VarOrNode, VarOrNodeOrLiteral, WhereClause, IRI,
+};
+use crate::querier::engine::{named_objects, named_subjects};
 use crate::querier::expression::{Expression, Term};
-use crate::querier::mapper::{iri_as_node, literal_as_object};
+use crate::querier::mapper::{iri_as_graph_name, iri_as_node, iri_as_string, literal_as_object};
 use crate::querier::plan::{PatternValue, PlanVariable, QueryNode, QueryPlan};
-use crate::querier::variable::HasBoundVariables;
+use crate::querier::variable::{HasBoundVariables, ResolvedVariable};
+use crate::state;
 use crate::state::{
     HasCachedNamespaces, Namespace, NamespaceQuerier, NamespaceResolver, Object, Predicate, Subject,
 };
-use cosmwasm_std::{StdError, StdResult, Storage};
-use std::collections::HashMap;
+use axone_rdf::uri::explode_iri;
+use cosmwasm_std::{QuerierWrapper, StdError, StdResult, Storage};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDFS_SUB_CLASS_OF: &str = "http://www.w3.org/2000/01/rdf-schema#subClassOf";
+const RDFS_SUB_PROPERTY_OF: &str = "http://www.w3.org/2000/01/rdf-schema#subPropertyOf";
+const OWL_SAME_AS: &str = "http://www.w3.org/2002/07/owl#sameAs";
 
 pub struct PlanBuilder<'a> {
+    storage: &'a dyn Storage,
+    querier: Option<QuerierWrapper<'a>>,
     ns_resolver: NamespaceResolver<'a>,
     prefixes: &'a HashMap<String, String>,
     variables: Vec<PlanVariable>,
     limit: Option<usize>,
     skip: Option<usize>,
+    inference: Option<Inference>,
+    pattern_count: usize,
+    normalize_literals: bool,
+    /// The quoted triple patterns met while building a triple pattern, along with the variable
+    /// standing for them, pending to be matched.
+    quoted_patterns: Vec<(usize, TriplePattern)>,
+}
+
+/// The bounds of the inference over the class and property hierarchies and the `owl:sameAs` links.
+#[derive(Clone, Copy)]
+struct Inference {
+    max_depth: usize,
+    same_as_depth: usize,
+    max_pattern_count: usize,
+}
+
+/// Returns the given node followed by the distinct nodes reachable from it through the `next`
+/// relation, exploring it breadth-first down to the given depth.
+fn closure<F>(node: &state::Node, max_depth: usize, mut next: F) -> StdResult<Vec<state::Node>>
+where
+    F: FnMut(&state::Node) -> StdResult<Vec<state::Node>>,
+{
+    let mut nodes = vec![node.clone()];
+    let mut level = 0;
+    for _ in 0..max_depth {
+        let parents = nodes[level..].to_vec();
+        level = nodes.len();
+        for parent in &parents {
+            for child in next(parent)? {
+                if !nodes.contains(&child) {
+                    nodes.push(child);
+                }
+            }
+        }
+        if level == nodes.len() {
+            break;
+        }
+    }
+
+    Ok(nodes)
 }
 
 impl<'a> PlanBuilder<'a> {
@@ -25,11 +79,17 @@ impl<'a> PlanBuilder<'a> {
         ns_cache: Option<Vec<Namespace>>,
     ) -> Self {
         Self {
+            storage,
+            querier: None,
             ns_resolver: NamespaceResolver::new(storage, ns_cache.unwrap_or_default()),
             prefixes,
             variables: Vec::new(),
             skip: None,
             limit: None,
+            inference: None,
+            pattern_count: 0,
+            normalize_literals: false,
+            quoted_patterns: Vec::new(),
         }
     }
 
@@ -38,12 +98,44 @@ impl<'a> PlanBuilder<'a> {
         self
     }
 
-    #[allow(dead_code)]
     pub fn with_skip(mut self, skip: usize) -> Self {
         self.skip = Some(skip);
         self
     }
 
+    /// Allow the where clause to contain [WhereClause::Service] clauses, evaluated through the
+    /// given querier.
+    pub fn with_querier(mut self, querier: QuerierWrapper<'a>) -> Self {
+        self.querier = Some(querier);
+        self
+    }
+
+    /// Normalize the lexical form of the typed literals of the triple patterns, to match the ones
+    /// of a store normalizing its inserted literals.
+    pub fn with_literal_normalization(mut self) -> Self {
+        self.normalize_literals = true;
+        self
+    }
+
+    /// Expand the triple patterns over the sub-properties of their predicate and, for `rdf:type`
+    /// patterns, over the sub-classes of their object, exploring the hierarchies up to the given
+    /// depth. Their named subject and object are also expanded over the nodes linked by
+    /// `owl:sameAs`, up to the given number of links. The total number of expanded patterns cannot
+    /// exceed the given count.
+    pub fn with_inference(
+        mut self,
+        max_depth: usize,
+        same_as_depth: usize,
+        max_pattern_count: usize,
+    ) -> Self {
+        self.inference = Some(Inference {
+            max_depth,
+            same_as_depth,
+            max_pattern_count,
+        });
+        self
+    }
+
     pub fn build_plan(&mut self, where_clause: &WhereClause) -> StdResult<QueryPlan> {
         let mut node = self.build_node(where_clause)?;
 
@@ -84,7 +176,141 @@ impl<'a> PlanBuilder<'a> {
 
                 Ok(QueryNode::Filter { expr, inner })
             }
+            WhereClause::Service {
+                contract_addr,
+                clause,
+            } => self.build_service(contract_addr, clause),
+            WhereClause::Graph { graph, clause } => Ok(QueryNode::Graph {
+                graphs: BTreeSet::from([iri_as_graph_name(graph.clone(), self.prefixes)?]),
+                child: Box::new(self.build_node(clause)?),
+            }),
+        }
+    }
+
+    /// Evaluate the clause against another store, the terms of its solutions being resolved
+    /// against the local namespaces so they can be joined with the local ones, the unknown
+    /// namespaces being cached under transient keys.
+    fn build_service(&mut self, contract_addr: &str, clause: &WhereClause) -> StdResult<QueryNode> {
+        let querier = self
+            .querier
+            .ok_or_else(|| StdError::generic_err("Service clauses are not supported here"))?;
+        let names = clause_variables(clause);
+        let limit = querier
+            .query_wasm_smart::<StoreResponse>(contract_addr, &QueryMsg::Store {})?
+            .limits
+            .max_query_limit;
+        let response: SelectResponse = querier.query_wasm_smart(
+            contract_addr,
+            &QueryMsg::Select {
+                query: SelectQuery {
+                    prefixes: self
+                        .prefixes
+                        .iter()
+                        .collect::<BTreeMap<_, _>>()
+                        .into_iter()
+                        .map(|(prefix, namespace)| Prefix {
+                            prefix: prefix.clone(),
+                            namespace: namespace.clone(),
+                        })
+                        .collect(),
+                    select: names.iter().cloned().map(SelectItem::Variable).collect(),
+                    r#where: clause.clone(),
+                    limit: Some(limit),
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
+                },
+            },
+        )?;
+        // The solutions can't be paged through, so they may have been truncated when the limit is
+        // reached.
+        if response.results.bindings.len() >= limit as usize {
+            Err(StdError::generic_err(format!(
+                "Service solutions of contract '{contract_addr}' truncated by its query limit: {limit}"
+            )))?;
         }
+
+        let solutions = response
+            .results
+            .bindings
+            .into_iter()
+            .map(|mut binding| {
+                names
+                    .iter()
+                    .map(|name| match binding.remove(name) {
+                        Some(value) => self.resolve_service_value(value),
+                        None => Err(StdError::generic_err(format!(
+                            "Unbound variable '{name}' in service solution"
+                        ))),
+                    })
+                    .collect()
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(QueryNode::Service {
+            contract_addr: contract_addr.to_string(),
+            variables: names
+                .into_iter()
+                .map(|name| self.resolve_basic_variable(name))
+                .collect(),
+            solutions,
+        })
+    }
+
+    fn resolve_service_value(&mut self, value: Value) -> StdResult<ResolvedVariable> {
+        let mut node = |iri: IRI| -> StdResult<state::Node> {
+            let (namespace, value) = explode_iri(&iri_as_string(iri, self.prefixes)?)?;
+            Ok(state::Node {
+                namespace: self.ns_resolver.resolve_or_transient(namespace)?.key,
+                value,
+            })
+        };
+
+        Ok(ResolvedVariable::Object(match value {
+            Value::URI { value } => Object::Named(node(value)?),
+            Value::Literal {
+                value,
+                lang: Some(language),
+                ..
+            } => Object::Literal(state::Literal::I18NString { value, language }),
+            Value::Literal {
+                value,
+                datatype: Some(datatype),
+                ..
+            } => Object::Literal(state::Literal::Typed {
+                value,
+                datatype: node(datatype)?,
+            }),
+            Value::Literal { value, .. } => Object::Literal(state::Literal::Simple { value }),
+            Value::BlankNode { .. } => Err(StdError::generic_err(
+                "Blank nodes can't be returned by a service clause",
+            ))?,
+            Value::Triple {
+                subject,
+                predicate,
+                object,
+            } => {
+                let invalid =
+                    || StdError::generic_err("Invalid quoted triple returned by a service clause");
+                Object::Triple(Box::new(state::Triple {
+                    subject: self
+                        .resolve_service_value(*subject)?
+                        .as_subject()
+                        .ok_or_else(invalid)?,
+                    predicate: self
+                        .resolve_service_value(*predicate)?
+                        .as_predicate()
+                        .ok_or_else(invalid)?,
+                    object: self
+                        .resolve_service_value(*object)?
+                        .as_object()
+                        .ok_or_else(invalid)?,
+                }))
+            }
+        }))
     }
 
     fn build_from_bgp<'b>(
@@ -161,6 +387,37 @@ impl<'a> PlanBuilder<'a> {
                 .build_expression(child)
                 .map(Box::new)
                 .map(Expression::Not),
+            msg::Expression::LangMatches(child, range) => Ok(Expression::LangMatches(
+                Box::new(self.build_expression(child)?),
+                range.clone(),
+            )),
+            msg::Expression::Contains {
+                expr,
+                substring,
+                case_insensitive,
+            } => Ok(Expression::Contains(
+                Box::new(self.build_expression(expr)?),
+                substring.clone(),
+                *case_insensitive,
+            )),
+            msg::Expression::StrStarts {
+                expr,
+                prefix,
+                case_insensitive,
+            } => Ok(Expression::StrStarts(
+                Box::new(self.build_expression(expr)?),
+                prefix.clone(),
+                *case_insensitive,
+            )),
+            msg::Expression::StrEnds {
+                expr,
+                suffix,
+                case_insensitive,
+            } => Ok(Expression::StrEnds(
+                Box::new(self.build_expression(expr)?),
+                suffix.clone(),
+                *case_insensitive,
+            )),
         }
     }
 
@@ -177,8 +434,50 @@ impl<'a> PlanBuilder<'a> {
         let maybe_object =
             Self::recover_ns_not_found_pattern_res(object_res, &mut bound_variables)?;
 
+        let mut node = match (maybe_subject, maybe_predicate, maybe_object) {
+            (Some(subject), Some(predicate), Some(object)) => match self.inference {
+                Some(inference) => {
+                    self.infer_triple_pattern(inference, subject, predicate, object)?
+                }
+                None => QueryNode::TriplePattern {
+                    subject,
+                    predicate,
+                    object,
+                },
+            },
+            _ => QueryNode::Noop { bound_variables },
+        };
+
+        // the quoted triple patterns are matched once their variable is bound, the ones nested in
+        // a quoted triple pattern being pushed when building it
+        while let Some((triple, pattern)) = self.quoted_patterns.pop() {
+            node = self.build_quoted_triple_pattern(node, triple, pattern)?;
+        }
+        Ok(node)
+    }
+
+    fn build_quoted_triple_pattern(
+        &mut self,
+        child: QueryNode,
+        triple: usize,
+        pattern: TriplePattern,
+    ) -> StdResult<QueryNode> {
+        let subject_res = self.build_subject_pattern(pattern.subject);
+        let predicate_res = self.build_predicate_pattern(pattern.predicate);
+        let object_res = self.build_object_pattern(pattern.object);
+
+        let mut bound_variables: Vec<usize> = child.bound_variables().into_iter().collect();
+        let maybe_subject =
+            Self::recover_ns_not_found_pattern_res(subject_res, &mut bound_variables)?;
+        let maybe_predicate =
+            Self::recover_ns_not_found_pattern_res(predicate_res, &mut bound_variables)?;
+        let maybe_object =
+            Self::recover_ns_not_found_pattern_res(object_res, &mut bound_variables)?;
+
         Ok(match (maybe_subject, maybe_predicate, maybe_object) {
-            (Some(subject), Some(predicate), Some(object)) => QueryNode::TriplePattern {
+            (Some(subject), Some(predicate), Some(object)) => QueryNode::QuotedTriple {
+                child: Box::new(child),
+                triple,
                 subject,
                 predicate,
                 object,
@@ -187,6 +486,140 @@ impl<'a> PlanBuilder<'a> {
         })
     }
 
+    /// Build the union of the triple patterns inferred from the class and property hierarchies,
+    /// and from the `owl:sameAs` links of their named subject and object.
+    fn infer_triple_pattern(
+        &mut self,
+        inference: Inference,
+        subject: PatternValue<Subject>,
+        predicate: PatternValue<Predicate>,
+        object: PatternValue<Object>,
+    ) -> StdResult<QueryNode> {
+        let (predicates, objects) = match &predicate {
+            PatternValue::Constant(p) => {
+                let objects = match &object {
+                    PatternValue::Constant(Object::Named(class))
+                        if self.resolve_known_iri(RDF_TYPE)?.as_ref() == Some(p) =>
+                    {
+                        self.sub_terms(RDFS_SUB_CLASS_OF, class, inference.max_depth)?
+                            .into_iter()
+                            .map(|class| PatternValue::Constant(Object::Named(class)))
+                            .collect()
+                    }
+                    _ => vec![object],
+                };
+                let predicates = self
+                    .sub_terms(RDFS_SUB_PROPERTY_OF, p, inference.max_depth)?
+                    .into_iter()
+                    .map(PatternValue::Constant)
+                    .collect::<Vec<_>>();
+
+                (predicates, objects)
+            }
+            _ => (vec![predicate], vec![object]),
+        };
+
+        let subjects = match &subject {
+            PatternValue::Constant(Subject::Named(node)) => self
+                .same_terms(node, inference.same_as_depth)?
+                .into_iter()
+                .map(|node| PatternValue::Constant(Subject::Named(node)))
+                .collect(),
+            _ => vec![subject],
+        };
+        let mut same_objects = Vec::with_capacity(objects.len());
+        for object in objects {
+            match &object {
+                PatternValue::Constant(Object::Named(node)) => {
+                    for node in self.same_terms(node, inference.same_as_depth)? {
+                        let object = PatternValue::Constant(Object::Named(node));
+                        if !same_objects.contains(&object) {
+                            same_objects.push(object);
+                        }
+                    }
+                }
+                _ => same_objects.push(object),
+            }
+        }
+        let objects = same_objects;
+
+        self.pattern_count += subjects.len() * predicates.len() * objects.len();
+        if self.pattern_count > inference.max_pattern_count {
+            Err(QueryComplexityError::PatternCount(
+                inference.max_pattern_count as u32,
+            ))?;
+        }
+
+        let mut patterns = Vec::with_capacity(subjects.len() * predicates.len() * objects.len());
+        for subject in &subjects {
+            for predicate in &predicates {
+                for object in &objects {
+                    patterns.push(QueryNode::TriplePattern {
+                        subject: subject.clone(),
+                        predicate: predicate.clone(),
+                        object: object.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(patterns
+            .into_iter()
+            .reduce(|acc, item| QueryNode::Union {
+                left: Box::new(acc),
+                right: Box::new(item),
+            })
+            .unwrap_or_else(QueryNode::noop))
+    }
+
+    /// Returns the given node followed by its distinct descendants through the relation, e.g.
+    /// `rdfs:subClassOf`, down to the given depth.
+    fn sub_terms(
+        &mut self,
+        relation: &str,
+        node: &state::Node,
+        max_depth: usize,
+    ) -> StdResult<Vec<state::Node>> {
+        let Some(relation) = self.resolve_known_iri(relation)? else {
+            return Ok(vec![node.clone()]);
+        };
+
+        closure(node, max_depth, |parent| {
+            named_subjects(self.storage, &relation, &Object::Named(parent.clone()))
+        })
+    }
+
+    /// Returns the given node followed by the distinct nodes linked to it by `owl:sameAs` in either
+    /// direction, following up to the given number of links.
+    fn same_terms(&mut self, node: &state::Node, max_depth: usize) -> StdResult<Vec<state::Node>> {
+        let Some(same_as) = self.resolve_known_iri(OWL_SAME_AS)? else {
+            return Ok(vec![node.clone()]);
+        };
+
+        closure(node, max_depth, |node| {
+            let mut nodes = named_subjects(self.storage, &same_as, &Object::Named(node.clone()))?;
+            nodes.extend(named_objects(
+                self.storage,
+                &Subject::Named(node.clone()),
+                &same_as,
+            )?);
+            Ok(nodes)
+        })
+    }
+
+    /// Resolve a well-known IRI, returning `None` if its namespace is not present in the store.
+    fn resolve_known_iri(&mut self, iri: &str) -> StdResult<Option<state::Node>> {
+        match iri_as_node(
+            &mut self.ns_resolver,
+            self.prefixes,
+            IRI::Full(iri.to_string()),
+        ) {
+            Ok(node) => Ok(Some(node)),
+            Err(err) if NamespaceQuerier::is_ns_not_found_error(&err) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
     fn recover_ns_not_found_pattern_res<T>(
         pattern_res: StdResult<PatternValue<T>>,
         bound_variables: &mut Vec<usize>,
@@ -210,6 +643,9 @@ impl<'a> PlanBuilder<'a> {
             VarOrNode::Node(Node::NamedNode(iri)) => PatternValue::Constant(Subject::Named(
                 iri_as_node(&mut self.ns_resolver, self.prefixes, iri)?,
             )),
+            VarOrNode::QuotedTriple(pattern) => {
+                PatternValue::Variable(self.resolve_quoted_variable(*pattern))
+            }
         })
     }
 
@@ -239,8 +675,14 @@ impl<'a> PlanBuilder<'a> {
             VarOrNodeOrLiteral::Node(Node::NamedNode(iri)) => PatternValue::Constant(
                 Object::Named(iri_as_node(&mut self.ns_resolver, self.prefixes, iri)?),
             ),
-            VarOrNodeOrLiteral::Literal(l) => {
-                PatternValue::Constant(literal_as_object(&mut self.ns_resolver, self.prefixes, l)?)
+            VarOrNodeOrLiteral::Literal(l) => PatternValue::Constant(literal_as_object(
+                &mut self.ns_resolver,
+                self.prefixes,
+                l,
+                self.normalize_literals,
+            )?),
+            VarOrNodeOrLiteral::QuotedTriple(pattern) => {
+                PatternValue::Variable(self.resolve_quoted_variable(*pattern))
             }
         })
     }
@@ -248,7 +690,7 @@ impl<'a> PlanBuilder<'a> {
     fn resolve_basic_variable(&mut self, v: String) -> usize {
         if let Some(index) = self.variables.iter().position(|var| match var {
             PlanVariable::Basic(name) => name == &v,
-            PlanVariable::BlankNode(_) => false,
+            _ => false,
         }) {
             return index;
         }
@@ -260,7 +702,7 @@ impl<'a> PlanBuilder<'a> {
     fn resolve_blank_variable(&mut self, v: String) -> usize {
         if let Some(index) = self.variables.iter().position(|var| match var {
             PlanVariable::BlankNode(name) => name == &v,
-            PlanVariable::Basic(_) => false,
+            _ => false,
         }) {
             return index;
         }
@@ -268,6 +710,54 @@ impl<'a> PlanBuilder<'a> {
         self.variables.push(PlanVariable::BlankNode(v));
         self.variables.len() - 1
     }
+
+    /// Allocate the variable standing for the quoted triples matching the pattern, the pattern
+    /// being matched once the variable is bound, see [Self::build_triple_pattern].
+    fn resolve_quoted_variable(&mut self, pattern: TriplePattern) -> usize {
+        self.variables.push(PlanVariable::QuotedTriple);
+        let index = self.variables.len() - 1;
+        self.quoted_patterns.push((index, pattern));
+        index
+    }
+}
+
+/// The names of the variables of the triple patterns of a where clause.
+pub(crate) fn clause_variables(clause: &WhereClause) -> BTreeSet<String> {
+    match clause {
+        WhereClause::Bgp { patterns } => patterns.iter().flat_map(pattern_variables).collect(),
+        WhereClause::LateralJoin { left, right } => {
+            let mut variables = clause_variables(left);
+            variables.extend(clause_variables(right));
+            variables
+        }
+        WhereClause::Filter { inner, .. }
+        | WhereClause::Service { clause: inner, .. }
+        | WhereClause::Graph { clause: inner, .. } => clause_variables(inner),
+    }
+}
+
+/// The names of the variables of a triple pattern, including the ones of its quoted triple
+/// patterns.
+pub(crate) fn pattern_variables(pattern: &TriplePattern) -> BTreeSet<String> {
+    let mut variables = BTreeSet::new();
+    match &pattern.subject {
+        VarOrNode::Variable(v) => {
+            variables.insert(v.clone());
+        }
+        VarOrNode::QuotedTriple(quoted) => variables.extend(pattern_variables(quoted)),
+        VarOrNode::Node(_) => {}
+    }
+    if let VarOrNamedNode::Variable(v) = &pattern.predicate {
+        variables.insert(v.clone());
+    }
+    match &pattern.object {
+        VarOrNodeOrLiteral::Variable(v) => {
+            variables.insert(v.clone());
+        }
+        VarOrNodeOrLiteral::QuotedTriple(quoted) => variables.extend(pattern_variables(quoted)),
+        _ => {}
+    }
+    variables
 }
 
 impl<'a> HasCachedNamespaces for PlanBuilder<'a> {
@@ -752,6 +1242,52 @@ mod test {
                 msg::Expression::Not(Box::new(msg::Expression::Variable("v1".to_string()))),
                 Ok(Expression::Not(Box::new(Expression::Variable(0usize)))),
             ),
+            (
+                msg::Expression::LangMatches(
+                    Box::new(msg::Expression::Variable("v1".to_string())),
+                    "en".to_string(),
+                ),
+                Ok(Expression::LangMatches(
+                    Box::new(Expression::Variable(0usize)),
+                    "en".to_string(),
+                )),
+            ),
+            (
+                msg::Expression::Contains {
+                    expr: Box::new(msg::Expression::Variable("v1".to_string())),
+                    substring: "foo".to_string(),
+                    case_insensitive: true,
+                },
+                Ok(Expression::Contains(
+                    Box::new(Expression::Variable(0usize)),
+                    "foo".to_string(),
+                    true,
+                )),
+            ),
+            (
+                msg::Expression::StrStarts {
+                    expr: Box::new(msg::Expression::Variable("v1".to_string())),
+                    prefix: "foo".to_string(),
+                    case_insensitive: false,
+                },
+                Ok(Expression::StrStarts(
+                    Box::new(Expression::Variable(0usize)),
+                    "foo".to_string(),
+                    false,
+                )),
+            ),
+            (
+                msg::Expression::StrEnds {
+                    expr: Box::new(msg::Expression::Variable("v1".to_string())),
+                    suffix: "foo".to_string(),
+                    case_insensitive: false,
+                },
+                Ok(Expression::StrEnds(
+                    Box::new(Expression::Variable(0usize)),
+                    "foo".to_string(),
+                    false,
+                )),
+            ),
         ];
 
         let deps = mock_dependencies();
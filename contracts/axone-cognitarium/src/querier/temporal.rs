@@ -0,0 +1,291 @@
+pub const XSD_DATE_TIME: &str = "http://www.w3.org/2001/XMLSchema#dateTime";
+pub const XSD_DATE: &str = "http://www.w3.org/2001/XMLSchema#date";
+
+/// An instant on the time-line, as seconds and nanoseconds since the Unix epoch in UTC.
+pub type Instant = (i64, u32);
+
+/// Parse the lexical form of a temporal literal of the given datatype into the instant it denotes,
+/// returning [None] if the datatype is not temporal or the lexical form is invalid.
+///
+/// A `xsd:date` denotes the instant starting the day, and values without timezone are considered
+/// as UTC.
+pub fn parse_instant(value: &str, datatype: &str) -> Option<Instant> {
+    match datatype {
+        XSD_DATE_TIME => parse_date_time(value).map(|(instant, _)| instant),
+        XSD_DATE => parse_date(value),
+        _ => None,
+    }
+}
+
+/// Parse the lexical form of a `xsd:dateTime` into the instant it denotes, values without timezone
+/// being considered as UTC, telling whether it has a timezone.
+pub fn parse_date_time(value: &str) -> Option<(Instant, bool)> {
+    let (date, rest) = value.split_once('T')?;
+    let days = parse_day(date)?;
+
+    let (time, offset) = split_timezone(rest)?;
+    let (hms, fraction) = match time.split_once('.') {
+        Some((hms, fraction)) => (hms, Some(fraction)),
+        None => (time, None),
+    };
+    let [hour, minute, second] = match hms.split(':').collect::<Vec<_>>()[..] {
+        [h, m, s] => [
+            parse_digits(h, 2)?,
+            parse_digits(m, 2)?,
+            parse_digits(s, 2)?,
+        ],
+        _ => None?,
+    };
+    let nanos = match fraction {
+        Some(f) if !f.is_empty() && f.bytes().all(|b| b.is_ascii_digit()) => {
+            format!("{:0<9}", &f[..f.len().min(9)]).parse().ok()?
+        }
+        Some(_) => None?,
+        None => 0,
+    };
+    if minute > 59
+        || second > 59
+        || hour > 24
+        || (hour == 24 && (minute, second, nanos) != (0, 0, 0))
+    {
+        None?
+    }
+
+    Some((
+        (
+            days * 86_400 + hour * 3_600 + minute * 60 + second - offset.unwrap_or(0),
+            nanos,
+        ),
+        offset.is_some(),
+    ))
+}
+
+/// Format an instant as the canonical lexical form of a `xsd:dateTime`, expressed in UTC if
+/// timezoned, returning [None] if its year doesn't have 4 digits.
+pub fn format_date_time((seconds, nanos): Instant, timezoned: bool) -> Option<String> {
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    if !(0..=9999).contains(&year) {
+        None?
+    }
+
+    let seconds = seconds.rem_euclid(86_400);
+    let fraction = format!("{nanos:09}");
+    let fraction = match fraction.trim_end_matches('0') {
+        "" => String::new(),
+        fraction => format!(".{fraction}"),
+    };
+    Some(format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}{fraction}{}",
+        seconds / 3_600,
+        seconds / 60 % 60,
+        seconds % 60,
+        if timezoned { "Z" } else { "" }
+    ))
+}
+
+fn parse_date(value: &str) -> Option<Instant> {
+    let (date, offset) = split_timezone(value)?;
+    Some((parse_day(date)? * 86_400 - offset.unwrap_or(0), 0))
+}
+
+/// Split a value from its optional timezone, returning the offset of the timezone in seconds.
+fn split_timezone(value: &str) -> Option<(&str, Option<i64>)> {
+    if let Some(value) = value.strip_suffix('Z') {
+        return Some((value, Some(0)));
+    }
+
+    match value.len().checked_sub(6) {
+        Some(i)
+            if value.is_char_boundary(i)
+                && value[i..].starts_with(['+', '-'])
+                && value.as_bytes()[i + 3] == b':' =>
+        {
+            let (value, timezone) = value.split_at(i);
+            let (hours, minutes) = (
+                parse_digits(&timezone[1..3], 2)?,
+                parse_digits(&timezone[4..], 2)?,
+            );
+            if hours > 14 || minutes > 59 || (hours == 14 && minutes != 0) {
+                None?
+            }
+
+            let offset = hours * 3_600 + minutes * 60;
+            Some((
+                value,
+                Some(if timezone.starts_with('-') {
+                    -offset
+                } else {
+                    offset
+                }),
+            ))
+        }
+        _ => Some((value, None)),
+    }
+}
+
+/// Parse a `-?YYYY-MM-DD` date, with a year of at most 9 digits, into the number of days since the Unix epoch.
+fn parse_day(value: &str) -> Option<i64> {
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value),
+    };
+    let [year, month, day] = match value.split('-').collect::<Vec<_>>()[..] {
+        [y, m, d] if (4..=9).contains(&y.len()) => [
+            parse_digits(y, y.len())?,
+            parse_digits(m, 2)?,
+            parse_digits(d, 2)?,
+        ],
+        _ => None?,
+    };
+    let year = if negative { -year } else { year };
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        None?
+    }
+
+    Some(days_from_civil(year, month, day))
+}
+
+fn parse_digits(value: &str, len: usize) -> Option<i64> {
+    if value.len() != len || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Number of days since the Unix epoch of a proleptic Gregorian date, see
+/// http://howardhinnant.github.io/date_algorithms.html#days_from_civil.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The proleptic Gregorian date of a number of days since the Unix epoch, the inverse of
+/// [days_from_civil].
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_of_year = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_of_year + 2) / 5 + 1;
+    let month = if month_of_year < 10 {
+        month_of_year + 3
+    } else {
+        month_of_year - 9
+    };
+    (era * 400 + year_of_era + i64::from(month <= 2), month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proper_parse_instant() {
+        let cases = vec![
+            ("1970-01-01T00:00:00Z", XSD_DATE_TIME, Some((0, 0))),
+            ("1970-01-01T00:00:00", XSD_DATE_TIME, Some((0, 0))),
+            (
+                "2024-01-01T00:00:00Z",
+                XSD_DATE_TIME,
+                Some((1_704_067_200, 0)),
+            ),
+            (
+                "2024-01-01T01:30:00+01:30",
+                XSD_DATE_TIME,
+                Some((1_704_067_200, 0)),
+            ),
+            (
+                "2023-12-31T19:00:00-05:00",
+                XSD_DATE_TIME,
+                Some((1_704_067_200, 0)),
+            ),
+            (
+                "2024-01-01T00:00:00.25Z",
+                XSD_DATE_TIME,
+                Some((1_704_067_200, 250_000_000)),
+            ),
+            (
+                "2023-12-31T24:00:00Z",
+                XSD_DATE_TIME,
+                Some((1_704_067_200, 0)),
+            ),
+            (
+                "2024-02-29T00:00:00Z",
+                XSD_DATE_TIME,
+                Some((1_709_164_800, 0)),
+            ),
+            ("1969-12-31T23:59:59Z", XSD_DATE_TIME, Some((-1, 0))),
+            (
+                "-0001-01-01T00:00:00Z",
+                XSD_DATE_TIME,
+                Some((-62_198_755_200, 0)),
+            ),
+            ("2024-01-01", XSD_DATE, Some((1_704_067_200, 0))),
+            ("2024-01-01Z", XSD_DATE, Some((1_704_067_200, 0))),
+            ("2024-01-01-01:00", XSD_DATE, Some((1_704_070_800, 0))),
+            ("2023-02-29T00:00:00Z", XSD_DATE_TIME, None),
+            ("2024-13-01T00:00:00Z", XSD_DATE_TIME, None),
+            ("2024-01-01T24:00:01Z", XSD_DATE_TIME, None),
+            ("2024-01-01T00:60:00Z", XSD_DATE_TIME, None),
+            ("2024-01-01T00:00:00.Z", XSD_DATE_TIME, None),
+            ("2024-01-01T00:00:00+15:00", XSD_DATE_TIME, None),
+            ("2024-01-01T00:00", XSD_DATE_TIME, None),
+            ("24-01-01T00:00:00Z", XSD_DATE_TIME, None),
+            ("2024-01-01", XSD_DATE_TIME, None),
+            ("2024-01-01T00:00:00Z", XSD_DATE, None),
+            (
+                "2024-01-01T00:00:00Z",
+                "http://www.w3.org/2001/XMLSchema#string",
+                None,
+            ),
+        ];
+
+        for (value, datatype, expected) in cases {
+            assert_eq!(
+                parse_instant(value, datatype),
+                expected,
+                "{value}^^{datatype}"
+            );
+        }
+    }
+
+    #[test]
+    fn proper_format_date_time() {
+        let cases = vec![
+            ((0, 0), true, Some("1970-01-01T00:00:00Z")),
+            ((1_709_164_799, 0), false, Some("2024-02-28T23:59:59")),
+            (
+                (1_704_067_200, 250_000_000),
+                true,
+                Some("2024-01-01T00:00:00.25Z"),
+            ),
+            ((-1, 1), true, Some("1969-12-31T23:59:59.000000001Z")),
+            ((-62_198_755_200, 0), true, None),
+            ((253_402_300_800, 0), true, None),
+        ];
+
+        for (instant, timezoned, expected) in cases {
+            assert_eq!(
+                format_date_time(instant, timezoned).as_deref(),
+                expected,
+                "{instant:?}"
+            );
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::msg::{Value, IRI};
 use crate::querier::expression::Term;
-use crate::state::{Literal, NamespaceSolver, Object, Predicate, Subject};
+use crate::state::{Literal, NamespaceSolver, Object, Predicate, Subject, TermRef, Triple};
 use axone_rdf::normalize::IdentifierIssuer;
 use cosmwasm_std::StdResult;
 use std::collections::BTreeSet;
@@ -20,6 +20,7 @@ impl ResolvedVariable {
             ResolvedVariable::Object(o) => match o {
                 Object::Named(node) => Subject::Named(node.clone()),
                 Object::Blank(node) => Subject::Blank(*node),
+                Object::Triple(triple) => Subject::Triple(triple.clone()),
                 Object::Literal(_) => None?,
             },
         })
@@ -29,22 +30,33 @@ impl ResolvedVariable {
         Some(match self {
             ResolvedVariable::Subject(s) => match s {
                 Subject::Named(node) => node.clone(),
-                Subject::Blank(_) => None?,
+                Subject::Blank(_) | Subject::Triple(_) => None?,
             },
             ResolvedVariable::Predicate(p) => p.clone(),
             ResolvedVariable::Object(o) => match o {
                 Object::Named(node) => node.clone(),
-                Object::Blank(_) | Object::Literal(_) => None?,
+                Object::Blank(_) | Object::Literal(_) | Object::Triple(_) => None?,
             },
         })
     }
 
+    /// The key of the bound term in the state, the same whatever the position it has been bound
+    /// from.
+    pub fn key(&self) -> Vec<u8> {
+        match self {
+            ResolvedVariable::Subject(s) => s.key(),
+            ResolvedVariable::Predicate(p) => TermRef::Term(p.id()).key(),
+            ResolvedVariable::Object(o) => o.key(),
+        }
+    }
+
     #[allow(clippy::unnecessary_wraps)]
     pub fn as_object(&self) -> Option<Object> {
         Some(match self {
             ResolvedVariable::Subject(s) => match s {
                 Subject::Named(node) => Object::Named(node.clone()),
                 Subject::Blank(node) => Object::Blank(*node),
+                Subject::Triple(triple) => Object::Triple(triple.clone()),
             },
             ResolvedVariable::Predicate(p) => Object::Named(p.clone()),
             ResolvedVariable::Object(o) => o.clone(),
@@ -64,6 +76,7 @@ impl ResolvedVariable {
                 Subject::Blank(blank) => Value::BlankNode {
                     value: id_issuer.get_str_or_issue(blank.to_string()).to_string(),
                 },
+                Subject::Triple(triple) => triple_as_value(triple, ns_fn, id_issuer)?,
             },
             ResolvedVariable::Predicate(predicate) => {
                 predicate.as_iri(ns_fn).map(|iri| Value::URI {
@@ -77,6 +90,7 @@ impl ResolvedVariable {
                 Object::Blank(blank) => Value::BlankNode {
                     value: id_issuer.get_str_or_issue(blank.to_string()).to_string(),
                 },
+                Object::Triple(triple) => triple_as_value(triple, ns_fn, id_issuer)?,
                 Object::Literal(literal) => match literal {
                     Literal::Simple { value } => Value::Literal {
                         value: value.clone(),
@@ -103,6 +117,7 @@ impl ResolvedVariable {
             ResolvedVariable::Subject(subject) => match subject {
                 Subject::Named(named) => named.as_iri(ns_solver).map(Term::String)?,
                 Subject::Blank(blank) => Term::String(format!("_:{}", blank)),
+                Subject::Triple(triple) => triple_as_term(triple, ns_solver)?,
             },
             ResolvedVariable::Predicate(predicate) => {
                 predicate.as_iri(ns_solver).map(Term::String)?
@@ -110,20 +125,56 @@ impl ResolvedVariable {
             ResolvedVariable::Object(object) => match object {
                 Object::Named(named) => named.as_iri(ns_solver).map(Term::String)?,
                 Object::Blank(blank) => Term::String(format!("_:{}", blank)),
-                Object::Literal(literal) => Term::String(match literal {
-                    Literal::Simple { value } => value.clone(),
+                Object::Triple(triple) => triple_as_term(triple, ns_solver)?,
+                Object::Literal(literal) => match literal {
+                    Literal::Simple { value } => Term::String(value.clone()),
                     Literal::I18NString { value, language } => {
-                        format!("{}{}", value, language)
+                        Term::String(format!("{}{}", value, language))
                     }
                     Literal::Typed { value, datatype } => {
-                        format!("{}{}", value, datatype.as_iri(ns_solver)?)
+                        Term::from_typed(value.clone(), datatype.as_iri(ns_solver)?)
                     }
-                }),
+                },
             },
         })
     }
 }
 
+fn triple_as_value(
+    triple: &Triple,
+    ns_fn: &mut dyn NamespaceSolver,
+    id_issuer: &mut IdentifierIssuer,
+) -> StdResult<Value> {
+    Ok(Value::Triple {
+        subject: Box::new(
+            ResolvedVariable::Subject(triple.subject.clone()).as_value(ns_fn, id_issuer)?,
+        ),
+        predicate: Box::new(
+            ResolvedVariable::Predicate(triple.predicate.clone()).as_value(ns_fn, id_issuer)?,
+        ),
+        object: Box::new(
+            ResolvedVariable::Object(triple.object.clone()).as_value(ns_fn, id_issuer)?,
+        ),
+    })
+}
+
+/// The term of a quoted triple in an expression, i.e. the string of its terms between `<<` and
+/// `>>`.
+fn triple_as_term(triple: &Triple, ns_solver: &mut dyn NamespaceSolver) -> StdResult<Term> {
+    Ok(Term::String(format!(
+        "<< {} {} {} >>",
+        ResolvedVariable::Subject(triple.subject.clone())
+            .as_term(ns_solver)?
+            .as_string(),
+        ResolvedVariable::Predicate(triple.predicate.clone())
+            .as_term(ns_solver)?
+            .as_string(),
+        ResolvedVariable::Object(triple.object.clone())
+            .as_term(ns_solver)?
+            .as_string(),
+    )))
+}
+
 #[derive(Eq, PartialEq, Debug, Clone)]
 pub struct ResolvedVariables {
     variables: Vec<Option<ResolvedVariable>>,
@@ -1,16 +1,27 @@
-use rio_api::model::{Literal, NamedNode, Triple};
+use cosmwasm_std::{StdError, StdResult};
+use oxiri::Iri;
+use rio_api::model::{BlankNode, Literal, NamedNode, Term, Triple};
 use std::fmt;
 
+/// Returns the N-Quads form of the named graph of the given IRI, which has to be absolute.
+pub fn graph_name(iri: &str) -> StdResult<String> {
+    Iri::parse(iri)
+        .map(|iri| format!("<{iri}>"))
+        .map_err(|e| StdError::generic_err(format!("Invalid graph IRI '{iri}': {e}")))
+}
+
 #[derive(Eq, PartialEq, Debug, Clone, Hash)]
 pub enum Subject {
     NamedNode(String),
     BlankNode(String),
+    Triple(Box<Atom>),
 }
 
 impl fmt::Display for Subject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Subject::NamedNode(s) | Subject::BlankNode(s) => write!(f, "{s}"),
+            Subject::Triple(atom) => write!(f, "<< {atom} >>"),
         }
     }
 }
@@ -31,6 +42,7 @@ pub enum Value {
     LiteralSimple(String),
     LiteralLang(String, String),
     LiteralDatatype(String, String),
+    Triple(Box<Atom>),
 }
 
 impl fmt::Display for Value {
@@ -39,6 +51,7 @@ impl fmt::Display for Value {
             Value::NamedNode(s) | Value::BlankNode(s) | Value::LiteralSimple(s) => write!(f, "{s}"),
             Value::LiteralLang(s, l) => write!(f, "{s}@{l}"),
             Value::LiteralDatatype(s, d) => write!(f, "{s}^^{d}"),
+            Value::Triple(atom) => write!(f, "<< {atom} >>"),
         }
     }
 }
@@ -56,31 +69,81 @@ impl fmt::Display for Atom {
     }
 }
 
-impl<'a> From<&'a Atom> for Triple<'a> {
-    fn from(atom: &'a Atom) -> Self {
-        Triple {
-            subject: match &atom.subject {
-                Subject::NamedNode(s) | Subject::BlankNode(s) => NamedNode { iri: s.as_str() },
-            }
-            .into(),
-            predicate: NamedNode {
-                iri: &atom.property.0,
-            },
-            object: match &atom.value {
-                Value::NamedNode(s) | Value::BlankNode(s) => NamedNode { iri: s.as_str() }.into(),
-                Value::LiteralSimple(s) => Literal::Simple { value: s.as_str() }.into(),
-                Value::LiteralLang(s, l) => Literal::LanguageTaggedString {
-                    value: s,
-                    language: l,
+impl Atom {
+    /// Call `f` with the RDF triple of the atom, its blank nodes being written as named nodes unless
+    /// `blank_nodes` is set.
+    ///
+    /// The RDF triples of the quoted triples the atom holds are built on the stack, being borrowed by
+    /// the one of the atom.
+    pub fn with_triple<R>(
+        &self,
+        blank_nodes: bool,
+        f: &mut dyn for<'t> FnMut(Triple<'t>) -> R,
+    ) -> R {
+        let with_quoted =
+            |atom: Option<&Atom>, f: &mut dyn for<'t> FnMut(Option<&Triple<'t>>) -> R| match atom {
+                Some(atom) => atom.with_triple(blank_nodes, &mut |triple| f(Some(&triple))),
+                None => f(None),
+            };
+        let quoted_subject = match &self.subject {
+            Subject::Triple(atom) => Some(atom.as_ref()),
+            _ => None,
+        };
+        let quoted_object = match &self.value {
+            Value::Triple(atom) => Some(atom.as_ref()),
+            _ => None,
+        };
+
+        with_quoted(quoted_subject, &mut |subject| {
+            with_quoted(quoted_object, &mut |object| {
+                let mut triple = flat_triple(self);
+                if let Some(subject) = subject {
+                    triple.subject = rio_api::model::Subject::Triple(subject);
                 }
-                .into(),
-                Value::LiteralDatatype(s, d) => Literal::Typed {
-                    value: s,
-                    datatype: NamedNode { iri: d },
+                if let Some(object) = object {
+                    triple.object = Term::Triple(object);
                 }
-                .into(),
-            },
+                if blank_nodes {
+                    if let Subject::BlankNode(id) = &self.subject {
+                        triple.subject = BlankNode { id }.into();
+                    }
+                    if let Value::BlankNode(id) = &self.value {
+                        triple.object = BlankNode { id }.into();
+                    }
+                }
+                f(triple)
+            })
+        })
+    }
+}
+
+/// The RDF triple of an atom, its blank nodes being written as named nodes and its quoted triples
+/// left as empty named nodes for [Atom::with_triple] to replace.
+fn flat_triple(atom: &Atom) -> Triple<'_> {
+    Triple {
+        subject: match &atom.subject {
+            Subject::NamedNode(s) | Subject::BlankNode(s) => NamedNode { iri: s.as_str() },
+            Subject::Triple(_) => NamedNode { iri: "" },
         }
+        .into(),
+        predicate: NamedNode {
+            iri: &atom.property.0,
+        },
+        object: match &atom.value {
+            Value::NamedNode(s) | Value::BlankNode(s) => NamedNode { iri: s.as_str() }.into(),
+            Value::Triple(_) => NamedNode { iri: "" }.into(),
+            Value::LiteralSimple(s) => Literal::Simple { value: s.as_str() }.into(),
+            Value::LiteralLang(s, l) => Literal::LanguageTaggedString {
+                value: s,
+                language: l,
+            }
+            .into(),
+            Value::LiteralDatatype(s, d) => Literal::Typed {
+                value: s,
+                datatype: NamedNode { iri: d },
+            }
+            .into(),
+        },
     }
 }
 
@@ -0,0 +1,160 @@
+use crate::querier::{format_date_time, parse_date_time};
+
+const XSD_NS: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// Returns the canonical lexical form of a value of the given datatype, as defined by XML Schema
+/// 1.1, so equivalent values are written alike (e.g. `01` and `+1` as `1` for `xsd:integer`).
+///
+/// Only the `xsd:integer`, `xsd:decimal`, `xsd:boolean` and `xsd:dateTime` datatypes are
+/// normalized, [None] being returned for the other ones and for the values which aren't a valid
+/// lexical form of their datatype, which are meant to be kept as is.
+pub fn canonical_lexical_form(datatype: &str, value: &str) -> Option<String> {
+    let value = value.trim_matches([' ', '\t', '\n', '\r']);
+    match datatype.strip_prefix(XSD_NS)? {
+        "integer" => canonical_integer(value),
+        "decimal" => canonical_decimal(value),
+        "boolean" => canonical_boolean(value),
+        "dateTime" => canonical_date_time(value),
+        _ => None,
+    }
+}
+
+fn split_sign(value: &str) -> (bool, &str) {
+    match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    }
+}
+
+fn is_digits(value: &str) -> bool {
+    value.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn signed(negative: bool, abs: String) -> String {
+    if negative && abs != "0" {
+        format!("-{abs}")
+    } else {
+        abs
+    }
+}
+
+fn canonical_integer(value: &str) -> Option<String> {
+    let (negative, digits) = split_sign(value);
+    if digits.is_empty() || !is_digits(digits) {
+        return None;
+    }
+
+    let digits = digits.trim_start_matches('0');
+    Some(signed(
+        negative,
+        if digits.is_empty() { "0" } else { digits }.to_string(),
+    ))
+}
+
+fn canonical_decimal(value: &str) -> Option<String> {
+    let (negative, digits) = split_sign(value);
+    let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+    if (int.is_empty() && frac.is_empty()) || !is_digits(int) || !is_digits(frac) {
+        return None;
+    }
+
+    let int = match int.trim_start_matches('0') {
+        "" => "0",
+        int => int,
+    };
+    Some(signed(
+        negative,
+        match frac.trim_end_matches('0') {
+            "" => int.to_string(),
+            frac => format!("{int}.{frac}"),
+        },
+    ))
+}
+
+fn canonical_boolean(value: &str) -> Option<String> {
+    match value {
+        "true" | "1" => Some("true".to_string()),
+        "false" | "0" => Some("false".to_string()),
+        _ => None,
+    }
+}
+
+/// The canonical form of a date time has its fractional seconds without trailing zeros and, if
+/// timezoned, is expressed in UTC, the fractional seconds being truncated to the nanosecond as
+/// when compared by the queries.
+fn canonical_date_time(value: &str) -> Option<String> {
+    let (instant, timezoned) = parse_date_time(value)?;
+    format_date_time(instant, timezoned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proper_canonical_lexical_form() {
+        let cases = vec![
+            ("integer", "42", Some("42")),
+            ("integer", "+0042", Some("42")),
+            ("integer", "-007", Some("-7")),
+            ("integer", "-0", Some("0")),
+            ("integer", " 1 ", Some("1")),
+            ("integer", "1.0", None),
+            ("integer", "", None),
+            ("integer", "+", None),
+            ("decimal", "01.500", Some("1.5")),
+            ("decimal", "+.5", Some("0.5")),
+            ("decimal", "-1.0", Some("-1")),
+            ("decimal", "-0.00", Some("0")),
+            ("decimal", "3.", Some("3")),
+            ("decimal", ".", None),
+            ("decimal", "1e3", None),
+            ("boolean", "1", Some("true")),
+            ("boolean", "false", Some("false")),
+            ("boolean", "True", None),
+            (
+                "dateTime",
+                "2024-01-22T10:00:00.500+00:00",
+                Some("2024-01-22T10:00:00.5Z"),
+            ),
+            (
+                "dateTime",
+                "2024-01-22T10:00:00-00:00",
+                Some("2024-01-22T10:00:00Z"),
+            ),
+            (
+                "dateTime",
+                "2024-03-01T01:30:00+02:00",
+                Some("2024-02-29T23:30:00Z"),
+            ),
+            (
+                "dateTime",
+                "2023-12-31T23:00:00.000-01:00",
+                Some("2024-01-01T00:00:00Z"),
+            ),
+            (
+                "dateTime",
+                "2024-01-22T10:00:00",
+                Some("2024-01-22T10:00:00"),
+            ),
+            ("dateTime", "2023-02-29T10:00:00Z", None),
+            ("dateTime", "2024-01-22T10:00:00.Z", None),
+            (
+                "dateTime",
+                "2024-01-22T24:00:00Z",
+                Some("2024-01-23T00:00:00Z"),
+            ),
+            ("dateTime", "2024-01-22", None),
+            ("string", "01", None),
+        ];
+
+        for (datatype, value, expected) in cases {
+            assert_eq!(
+                canonical_lexical_form(&format!("{XSD_NS}{datatype}"), value).as_deref(),
+                expected,
+                "{datatype}: {value}"
+            );
+        }
+        assert_eq!(canonical_lexical_form("https://ex.org/integer", "01"), None);
+    }
+}
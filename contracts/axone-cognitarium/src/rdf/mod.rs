@@ -1,5 +1,7 @@
 mod atom;
+mod lexical;
 mod mapper;
 
 pub use self::atom::*;
+pub use self::lexical::*;
 pub use self::mapper::*;
@@ -0,0 +1,117 @@
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Item, Map};
+use serde::{Deserialize, Serialize};
+
+/// The configuration and position of the query audit log, only present when the log has been
+/// enabled at instantiation.
+pub const QUERY_AUDIT: Item<QueryAudit> = Item::new("query_audit");
+
+/// The entries of the query audit log by their sequence number, starting from 1.
+pub const QUERY_AUDIT_ENTRIES: Map<u64, QueryAuditEntry> = Map::new("QUERY_AUDIT_ENTRY");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct QueryAudit {
+    /// The maximum number of entries retained by the log, the oldest ones being evicted first.
+    pub capacity: u32,
+
+    /// The sequence number of the last recorded entry, 0 meaning no query has been recorded yet.
+    pub last_entry: u64,
+}
+
+/// A query evaluated while executing a message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct QueryAuditEntry {
+    /// The message the query has been evaluated for, e.g. `delete_data`.
+    pub operation: String,
+
+    /// The hex encoded `blake3` hash of the query, i.e. of the JSON serialization of its prefixes
+    /// and where clause.
+    pub query_hash: String,
+
+    /// The sender of the message.
+    pub caller: Addr,
+
+    /// The number of triple patterns of the where clause.
+    pub pattern_count: u32,
+
+    /// The number of results the where clause led to, i.e. the triples to delete or the subjects to
+    /// move.
+    pub result_count: u64,
+}
+
+impl QueryAudit {
+    pub fn new(capacity: u32) -> Self {
+        Self {
+            capacity,
+            last_entry: 0,
+        }
+    }
+
+    /// Record an entry, evicting the one going out of the log capacity.
+    pub fn record(&mut self, storage: &mut dyn Storage, entry: &QueryAuditEntry) -> StdResult<()> {
+        self.last_entry += 1;
+        QUERY_AUDIT_ENTRIES.save(storage, self.last_entry, entry)?;
+        if self.last_entry > self.capacity as u64 {
+            QUERY_AUDIT_ENTRIES.remove(storage, self.last_entry - self.capacity as u64);
+        }
+
+        QUERY_AUDIT.save(storage, self)
+    }
+
+    /// Remove, oldest first, up to `limit` entries recorded before the given sequence number, if any,
+    /// returning the number of removed entries.
+    pub fn prune(storage: &mut dyn Storage, before: Option<u64>, limit: usize) -> StdResult<u64> {
+        let keys = QUERY_AUDIT_ENTRIES
+            .keys(
+                storage,
+                None,
+                before.map(Bound::exclusive),
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+        for key in &keys {
+            QUERY_AUDIT_ENTRIES.remove(storage, *key);
+        }
+
+        Ok(keys.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn record_evict_and_prune() {
+        let mut deps = mock_dependencies();
+        let entry = |result_count: u64| QueryAuditEntry {
+            operation: "delete_data".to_string(),
+            query_hash: "00".to_string(),
+            caller: Addr::unchecked("caller"),
+            pattern_count: 1,
+            result_count,
+        };
+        let entries = |storage: &dyn Storage| {
+            QUERY_AUDIT_ENTRIES
+                .range(storage, None, None, Order::Ascending)
+                .map(|res| res.map(|(id, entry)| (id, entry.result_count)))
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap()
+        };
+
+        let mut audit = QueryAudit::new(3);
+        for count in 10..14 {
+            audit.record(&mut deps.storage, &entry(count)).unwrap();
+        }
+        assert_eq!(QUERY_AUDIT.load(&deps.storage).unwrap(), audit);
+        assert_eq!(audit.last_entry, 4);
+        assert_eq!(entries(&deps.storage), vec![(2, 11), (3, 12), (4, 13)]);
+
+        assert_eq!(QueryAudit::prune(&mut deps.storage, Some(4), 1), Ok(1));
+        assert_eq!(entries(&deps.storage), vec![(3, 12), (4, 13)]);
+        assert_eq!(QueryAudit::prune(&mut deps.storage, None, 10), Ok(2));
+        assert_eq!(entries(&deps.storage), vec![]);
+    }
+}
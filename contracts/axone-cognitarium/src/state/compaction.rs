@@ -0,0 +1,354 @@
+use crate::state::{
+    literal_tags, namespaces, raw_entries_after, scan_phases, scan_triple_indexes, IndexFault,
+    Insertion, LiteralTagEntry, Namespace, StoredTriple, TermEntry, EXPIRING_TRIPLES_NAMESPACE,
+    FREE_NAMESPACE_KEYS, LITERAL_TAGS_NAMESPACE, NAMESPACES_NAMESPACE, TERMS_NAMESPACE,
+    TRIPLE_EXPIRATIONS_NAMESPACE, TRIPLE_INSERTIONS_NAMESPACE,
+};
+use cosmwasm_std::{Empty, StdResult, Storage};
+use cw_storage_plus::Map;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// The number of phases of a compaction, see [compact].
+const COMPACTION_PHASES: u8 = 7;
+
+/// The raw entries of a map, by their key as written in the storage.
+fn raw_entries<T>(namespace: &'static str) -> Map<Vec<u8>, T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    Map::new(namespace)
+}
+
+/// The outcome of a batch of [compact].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Compaction {
+    /// The number of records scanned.
+    pub scanned: usize,
+    /// The number of obsolete records removed among the scanned ones.
+    pub reclaimed: usize,
+    /// The cursor to compact the following records with, if some may remain.
+    pub cursor: Option<Vec<u8>>,
+}
+
+/// Remove the obsolete records left in the state by up to `limit` records, resuming after the
+/// cursor returned by the previous batch, if any.
+///
+/// The records are scanned in turn by phase:
+/// - the entries of the secondary indexes referencing no stored triple (see
+///   [scan_triple_indexes]);
+/// - the insertions, expirations and expiring entries of the triples no longer stored;
+/// - the entries of the interned dictionaries no longer referenced, i.e. the literal tags, the
+///   terms and the namespaces whose reference counter dropped to zero without being removed.
+///
+/// The cursor is made of the phase followed by the last key scanned (see [scan_phases]).
+pub fn compact(
+    storage: &mut dyn Storage,
+    cursor: Option<&[u8]>,
+    limit: usize,
+) -> StdResult<Compaction> {
+    let mut reclaimed = 0;
+    let scan = scan_phases(
+        cursor,
+        COMPACTION_PHASES,
+        limit,
+        "Invalid compaction cursor",
+        |phase, after, limit| match phase {
+            0 => compact_index_entries(storage, after, limit, &mut reclaimed),
+            1 => compact_entries(
+                storage,
+                TRIPLE_INSERTIONS_NAMESPACE,
+                after,
+                limit,
+                &mut reclaimed,
+                |storage, key, _: &Insertion| is_obsolete_triple(storage, key),
+                remove_raw(TRIPLE_INSERTIONS_NAMESPACE),
+            ),
+            2 => compact_entries(
+                storage,
+                TRIPLE_EXPIRATIONS_NAMESPACE,
+                after,
+                limit,
+                &mut reclaimed,
+                |storage, key, _: &(u8, u64)| is_obsolete_triple(storage, key),
+                remove_raw(TRIPLE_EXPIRATIONS_NAMESPACE),
+            ),
+            3 => compact_entries(
+                storage,
+                EXPIRING_TRIPLES_NAMESPACE,
+                after,
+                limit,
+                &mut reclaimed,
+                |storage, _, triple: &StoredTriple| Ok(!triple.is_stored(storage)?),
+                remove_raw(EXPIRING_TRIPLES_NAMESPACE),
+            ),
+            4 => compact_entries(
+                storage,
+                LITERAL_TAGS_NAMESPACE,
+                after,
+                limit,
+                &mut reclaimed,
+                |_, _, entry: &LiteralTagEntry| Ok(entry.counter == 0),
+                |storage, key, _| literal_tags().remove(storage, key),
+            ),
+            5 => compact_entries(
+                storage,
+                TERMS_NAMESPACE,
+                after,
+                limit,
+                &mut reclaimed,
+                |_, _, entry: &TermEntry| Ok(entry.counter == 0),
+                remove_raw(TERMS_NAMESPACE),
+            ),
+            _ => compact_entries(
+                storage,
+                NAMESPACES_NAMESPACE,
+                after,
+                limit,
+                &mut reclaimed,
+                |_, _, ns: &Namespace| Ok(ns.counter == 0),
+                |storage, key, ns| {
+                    let value = String::from_utf8(key)?;
+                    namespaces().remove(storage, value)?;
+                    FREE_NAMESPACE_KEYS.save(storage, ns.key, &Empty {})
+                },
+            ),
+        },
+    )?;
+
+    Ok(Compaction {
+        scanned: scan.scanned,
+        reclaimed,
+        cursor: scan.cursor,
+    })
+}
+
+fn compact_entries<T, O, R>(
+    storage: &mut dyn Storage,
+    namespace: &'static str,
+    after: Option<Vec<u8>>,
+    limit: usize,
+    reclaimed: &mut usize,
+    is_obsolete: O,
+    remove: R,
+) -> StdResult<(usize, Option<Vec<u8>>)>
+where
+    T: Serialize + DeserializeOwned,
+    O: Fn(&dyn Storage, &[u8], &T) -> StdResult<bool>,
+    R: Fn(&mut dyn Storage, Vec<u8>, T) -> StdResult<()>,
+{
+    let entries = raw_entries_after::<T>(storage, namespace, after, limit)?;
+    let scanned = entries.len();
+    let mut last = None;
+    for (key, value) in entries {
+        last = Some(key.clone());
+        if is_obsolete(storage, &key, &value)? {
+            remove(storage, key, value)?;
+            *reclaimed += 1;
+        }
+    }
+
+    Ok((scanned, last))
+}
+
+/// Remove an entry of the map of the given namespace by its raw key.
+fn remove_raw<T>(namespace: &'static str) -> impl Fn(&mut dyn Storage, Vec<u8>, T) -> StdResult<()>
+where
+    T: Serialize + DeserializeOwned,
+{
+    move |storage, key, _| {
+        raw_entries::<T>(namespace).remove(storage, key);
+        Ok(())
+    }
+}
+
+/// Remove the dangling entries of the secondary indexes, the cursor being the one of the index
+/// scan, which is started past the triples so only the index entries are scanned.
+fn compact_index_entries(
+    storage: &mut dyn Storage,
+    after: Option<Vec<u8>>,
+    limit: usize,
+    reclaimed: &mut usize,
+) -> StdResult<(usize, Option<Vec<u8>>)> {
+    let scan = scan_triple_indexes(storage, Some(&after.unwrap_or_else(|| vec![1])), limit)?;
+    for fault in &scan.faults {
+        if let IndexFault::Dangling { .. } = fault {
+            fault.repair(storage)?;
+            *reclaimed += 1;
+        }
+    }
+
+    Ok((scan.scanned, scan.cursor))
+}
+
+/// Tells whether the triple of the given key (see [StoredTriple::key]) is no longer stored.
+fn is_obsolete_triple(storage: &dyn Storage, key: &[u8]) -> StdResult<bool> {
+    match StoredTriple::from_key(key) {
+        Some(triple) => Ok(!triple.is_stored(storage)?),
+        None => Ok(true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{
+        triples, LiteralTag, Node, StoredTerm, TermRef, EXPIRING_TRIPLES, TERMS,
+        TRIPLE_EXPIRATIONS, TRIPLE_INSERTIONS,
+    };
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::{Addr, StdError};
+    use cw_storage_plus::PrimaryKey;
+
+    fn stored(object: u128) -> StoredTriple {
+        StoredTriple {
+            subject: TermRef::Term(1),
+            predicate: 2,
+            object: TermRef::Term(object),
+        }
+    }
+
+    /// The object of the stored triple, the deleted one referencing the term `4` instead.
+    fn object() -> Node {
+        Node {
+            namespace: 0,
+            value: "o".to_string(),
+        }
+    }
+
+    /// A store holding a triple along with the obsolete records of a deleted one, a dangling index
+    /// entry and dictionary entries no longer referenced.
+    fn obsolete_storage() -> MockStorage {
+        let mut storage = MockStorage::new();
+
+        let object_hash = object().as_hash();
+        let pk = (
+            object_hash.as_bytes().as_slice(),
+            2u128.to_be_bytes().to_vec(),
+            TermRef::Term(1).key(),
+        );
+        triples()
+            .save(&mut storage, pk.clone(), &stored(object().id()))
+            .unwrap();
+        TERMS
+            .save(
+                &mut storage,
+                object().id(),
+                &TermEntry {
+                    term: StoredTerm::Node(object()),
+                    counter: 1,
+                },
+            )
+            .unwrap();
+        for object in [object().id(), 4] {
+            let key = stored(object).key();
+            TRIPLE_INSERTIONS
+                .save(
+                    &mut storage,
+                    &key,
+                    &Insertion {
+                        inserter: Addr::unchecked("inserter"),
+                        height: 1,
+                        tx_index: None,
+                    },
+                )
+                .unwrap();
+            TRIPLE_EXPIRATIONS
+                .save(&mut storage, &key, &(0, 10))
+                .unwrap();
+            EXPIRING_TRIPLES
+                .save(&mut storage, (0, 10, &key), &stored(object))
+                .unwrap();
+        }
+        let pk = pk.joined_key();
+        raw_entries::<u32>("TRIPLE__OBJECT_SUBJECT")
+            .save(
+                &mut storage,
+                (TermRef::Term(4).key(), TermRef::Term(1).key()).joined_extra_key(&pk),
+                &(pk.len() as u32),
+            )
+            .unwrap();
+
+        for (counter, value) in [(0u128, "a"), (1, "b")] {
+            let term = StoredTerm::Node(Node {
+                namespace: counter,
+                value: value.to_string(),
+            });
+            TERMS
+                .save(&mut storage, 5 + counter, &TermEntry { term, counter })
+                .unwrap();
+            let tag = LiteralTag::Language(value.to_string());
+            literal_tags()
+                .save(
+                    &mut storage,
+                    tag.key(),
+                    &LiteralTagEntry {
+                        tag,
+                        id: counter as u32,
+                        counter,
+                    },
+                )
+                .unwrap();
+            let value = format!("https://ex.org/{value}/");
+            namespaces()
+                .save(
+                    &mut storage,
+                    value.clone(),
+                    &Namespace {
+                        value,
+                        key: counter,
+                        counter,
+                    },
+                )
+                .unwrap();
+        }
+
+        storage
+    }
+
+    fn compact_all(storage: &mut dyn Storage, limit: usize) -> (usize, usize) {
+        let (mut scanned, mut reclaimed, mut cursor) = (0, 0, None);
+        loop {
+            let compaction = compact(storage, cursor.as_deref(), limit).unwrap();
+            assert!(compaction.scanned <= limit);
+            scanned += compaction.scanned;
+            reclaimed += compaction.reclaimed;
+            match compaction.cursor {
+                Some(next) => cursor = Some(next),
+                None => return (scanned, reclaimed),
+            }
+        }
+    }
+
+    #[test]
+    fn proper_compact() {
+        for limit in [1, 4, 100] {
+            let mut storage = obsolete_storage();
+            assert_eq!(compact_all(&mut storage, limit), (17, 7), "{limit}");
+            assert_eq!(compact_all(&mut storage, limit), (10, 0), "{limit}");
+
+            assert!(TRIPLE_INSERTIONS.has(&storage, &stored(object().id()).key()));
+            assert!(!TRIPLE_INSERTIONS.has(&storage, &stored(4).key()));
+            assert!(!TRIPLE_EXPIRATIONS.has(&storage, &stored(4).key()));
+            assert!(!EXPIRING_TRIPLES.has(&storage, (0, 10, &stored(4).key())));
+            assert!(!TERMS.has(&storage, 5));
+            assert!(TERMS.has(&storage, 6));
+            assert_eq!(
+                literal_tags()
+                    .idx
+                    .id
+                    .item(&storage, 0)
+                    .map(|item| item.is_some()),
+                Ok(false)
+            );
+            assert!(!namespaces().has(&storage, "https://ex.org/a/".to_string()));
+            assert!(namespaces().has(&storage, "https://ex.org/b/".to_string()));
+            assert!(FREE_NAMESPACE_KEYS.has(&storage, 0));
+        }
+
+        assert_eq!(
+            compact(&mut MockStorage::new(), Some(&[COMPACTION_PHASES]), 1),
+            Err(StdError::generic_err("Invalid compaction cursor"))
+        );
+    }
+}
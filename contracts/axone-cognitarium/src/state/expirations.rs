@@ -0,0 +1,57 @@
+use crate::state::StoredTriple;
+use cosmwasm_std::{BlockInfo, Order, StdResult, Storage};
+use cw_storage_plus::{Map, PrefixBound};
+use cw_utils::Expiration;
+
+/// The expiration of the triples inserted with one, by triple key (see
+/// [StoredTriple::key](crate::state::StoredTriple::key)), removed along with the triple.
+pub const TRIPLE_EXPIRATIONS: Map<&[u8], (u8, u64)> = Map::new(TRIPLE_EXPIRATIONS_NAMESPACE);
+
+/// The storage namespace of [TRIPLE_EXPIRATIONS].
+pub const TRIPLE_EXPIRATIONS_NAMESPACE: &str = "triple_expirations";
+
+/// The triples inserted with an expiration, by expiration and triple key so the triples expiring
+/// first come first, the expiration being given as a pair of its kind (i.e. block height or time)
+/// and the height or time in nanoseconds.
+pub const EXPIRING_TRIPLES: Map<(u8, u64, &[u8]), StoredTriple> =
+    Map::new(EXPIRING_TRIPLES_NAMESPACE);
+
+/// The storage namespace of [EXPIRING_TRIPLES].
+pub const EXPIRING_TRIPLES_NAMESPACE: &str = "expiring_triples";
+
+const HEIGHT_EXPIRATION: u8 = 0;
+const TIME_EXPIRATION: u8 = 1;
+
+/// The key of an expiration in the [EXPIRING_TRIPLES] index, [None] if it never expires.
+pub fn expiration_key(expiration: &Expiration) -> Option<(u8, u64)> {
+    match expiration {
+        Expiration::AtHeight(height) => Some((HEIGHT_EXPIRATION, *height)),
+        Expiration::AtTime(time) => Some((TIME_EXPIRATION, time.nanos())),
+        Expiration::Never {} => None,
+    }
+}
+
+/// Returns up to `limit` of the triples expired at the given block, the ones having expired first
+/// coming first for each kind of expiration.
+pub fn expired_triples(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    limit: usize,
+) -> StdResult<Vec<StoredTriple>> {
+    [
+        (HEIGHT_EXPIRATION, block.height),
+        (TIME_EXPIRATION, block.time.nanos()),
+    ]
+    .into_iter()
+    .flat_map(|(kind, at)| {
+        EXPIRING_TRIPLES.prefix_range(
+            storage,
+            Some(PrefixBound::inclusive((kind, 0))),
+            Some(PrefixBound::inclusive((kind, at))),
+            Order::Ascending,
+        )
+    })
+    .take(limit)
+    .map(|res| res.map(|(_, triple)| triple))
+    .collect()
+}
@@ -0,0 +1,8 @@
+use crate::msg::Permission;
+use cosmwasm_std::Addr;
+use cw_storage_plus::Map;
+use std::collections::BTreeSet;
+
+/// The permissions granted to addresses other than the owner, an address without any permission left
+/// being removed.
+pub const GRANTS: Map<&Addr, BTreeSet<Permission>> = Map::new("grants");
@@ -0,0 +1,339 @@
+use crate::msg::TripleIndex;
+use crate::state::{
+    object_and_subject, predicate_and_object, subject_and_predicate, StoredTriple,
+    TRIPLES_NAMESPACE,
+};
+use cosmwasm_std::{Order, StdError, StdResult, Storage};
+use cw_storage_plus::{Bound, Map, PrimaryKey};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+type IndexKeyFn = fn(&[u8], &StoredTriple) -> (Vec<u8>, Vec<u8>);
+
+/// The secondary indexes of the triples along with their namespace and the key they index a triple
+/// by, see [TripleIndexes](crate::state::TripleIndexes).
+pub const TRIPLE_INDEXES: [(TripleIndex, &str, IndexKeyFn); 3] = [
+    (
+        TripleIndex::SubjectPredicateObject,
+        "TRIPLE__SUBJECT_PREDICATE",
+        subject_and_predicate,
+    ),
+    (
+        TripleIndex::PredicateObjectSubject,
+        "TRIPLE__PREDICATE_OBJECT",
+        predicate_and_object,
+    ),
+    (
+        TripleIndex::ObjectSubjectPredicate,
+        "TRIPLE__OBJECT_SUBJECT",
+        object_and_subject,
+    ),
+];
+
+/// The triples by their raw primary key, as referenced by the secondary index entries.
+pub const RAW_TRIPLES: Map<Vec<u8>, StoredTriple> = Map::new(TRIPLES_NAMESPACE);
+
+/// The raw entries of a secondary index, i.e. the length of the primary key ending their key.
+fn index_entries(namespace: &'static str) -> Map<Vec<u8>, u32> {
+    Map::new(namespace)
+}
+
+/// The key of the term a secondary index entry is ordered by, i.e. the first component of its key,
+/// if well formed.
+pub fn leading_term_key(key: &[u8]) -> Option<&[u8]> {
+    let len = u16::from_be_bytes(key.get(..2)?.try_into().ok()?) as usize;
+    key.get(2..2 + len)
+}
+
+/// A secondary index entry of the triples disagreeing with their primary storage.
+#[derive(Clone, Debug, PartialEq)]
+pub enum IndexFault {
+    /// The entry of a stored triple is missing from the index.
+    Missing {
+        index: TripleIndex,
+        namespace: &'static str,
+        key: Vec<u8>,
+        pk_len: u32,
+    },
+    /// The entry references a triple which isn't stored, or which isn't indexed under this key.
+    Dangling {
+        index: TripleIndex,
+        namespace: &'static str,
+        key: Vec<u8>,
+    },
+}
+
+impl IndexFault {
+    /// Repair the fault, writing the missing entry or removing the dangling one.
+    pub fn repair(&self, storage: &mut dyn Storage) -> StdResult<()> {
+        match self {
+            IndexFault::Missing {
+                namespace,
+                key,
+                pk_len,
+                ..
+            } => index_entries(namespace).save(storage, key.clone(), pk_len),
+            IndexFault::Dangling { namespace, key, .. } => {
+                index_entries(namespace).remove(storage, key.clone());
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The outcome of a batch of [scan_triple_indexes].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IndexScan {
+    /// The number of triples and index entries scanned.
+    pub scanned: usize,
+    /// The faults found among the scanned records.
+    pub faults: Vec<IndexFault>,
+    /// The cursor to scan the following records with, if some may remain.
+    pub cursor: Option<Vec<u8>>,
+}
+
+/// The outcome of a batch of [scan_phases].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PhasedScan {
+    /// The number of records scanned.
+    pub scanned: usize,
+    /// The cursor to scan the following records with, if some may remain.
+    pub cursor: Option<Vec<u8>>,
+}
+
+/// Scan up to `limit` records in turn by phase, resuming after the cursor returned by the previous
+/// batch, if any, the cursor being made of the phase followed by the last key scanned.
+///
+/// Each phase is scanned by `scan_phase`, given the key to resume after and the maximum number of
+/// records to scan, and returning the number of scanned records along with the last key.
+pub fn scan_phases<F>(
+    cursor: Option<&[u8]>,
+    phases: u8,
+    limit: usize,
+    invalid_cursor: &str,
+    mut scan_phase: F,
+) -> StdResult<PhasedScan>
+where
+    F: FnMut(u8, Option<Vec<u8>>, usize) -> StdResult<(usize, Option<Vec<u8>>)>,
+{
+    let (mut phase, mut after) = match cursor.and_then(<[u8]>::split_first) {
+        Some((phase, key)) if *phase < phases => (*phase, Some(key.to_vec())),
+        Some(_) => Err(StdError::generic_err(invalid_cursor))?,
+        None => (0, None),
+    };
+
+    let mut scan = PhasedScan::default();
+    while phase < phases && scan.scanned < limit {
+        let (scanned, last) = scan_phase(phase, after.take(), limit - scan.scanned)?;
+        scan.scanned += scanned;
+        match last {
+            Some(key) if scan.scanned == limit => {
+                scan.cursor = Some([vec![phase], key].concat());
+            }
+            _ => phase += 1,
+        }
+    }
+
+    Ok(scan)
+}
+
+/// The raw entries of a map after the given key, up to `limit` of them.
+pub fn raw_entries_after<T>(
+    storage: &dyn Storage,
+    namespace: &'static str,
+    after: Option<Vec<u8>>,
+    limit: usize,
+) -> StdResult<Vec<(Vec<u8>, T)>>
+where
+    T: Serialize + DeserializeOwned,
+{
+    Map::<Vec<u8>, T>::new(namespace)
+        .range_raw(
+            storage,
+            after.map(Bound::ExclusiveRaw),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect()
+}
+
+/// Check up to `limit` records of the triples and of their secondary indexes agree with each other,
+/// resuming after the cursor returned by the previous batch, if any.
+///
+/// The triples are scanned first, each one having to be referenced by an entry of each index, then
+/// the entries of each index in turn, each one having to reference a stored triple indexed under
+/// its key. The cursor is made of the position of the scanned records followed by the last key
+/// scanned (see [scan_phases]).
+pub fn scan_triple_indexes(
+    storage: &dyn Storage,
+    cursor: Option<&[u8]>,
+    limit: usize,
+) -> StdResult<IndexScan> {
+    let mut faults = vec![];
+    let scan = scan_phases(
+        cursor,
+        TRIPLE_INDEXES.len() as u8 + 1,
+        limit,
+        "Invalid index scan cursor",
+        |phase, after, limit| match phase {
+            0 => scan_triples(storage, after, limit, &mut faults),
+            _ => scan_index_entries(
+                storage,
+                &TRIPLE_INDEXES[phase as usize - 1],
+                after,
+                limit,
+                &mut faults,
+            ),
+        },
+    )?;
+
+    Ok(IndexScan {
+        scanned: scan.scanned,
+        faults,
+        cursor: scan.cursor,
+    })
+}
+
+fn scan_triples(
+    storage: &dyn Storage,
+    after: Option<Vec<u8>>,
+    limit: usize,
+    faults: &mut Vec<IndexFault>,
+) -> StdResult<(usize, Option<Vec<u8>>)> {
+    let triples = raw_entries_after::<StoredTriple>(storage, TRIPLES_NAMESPACE, after, limit)?;
+    let scanned = triples.len();
+    let mut last = None;
+    for (pk, triple) in triples {
+        for (index, namespace, key_fn) in &TRIPLE_INDEXES {
+            let key = key_fn(&pk, &triple).joined_extra_key(&pk);
+            if !index_entries(namespace).has(storage, key.clone()) {
+                faults.push(IndexFault::Missing {
+                    index: index.clone(),
+                    namespace,
+                    key,
+                    pk_len: pk.len() as u32,
+                });
+            }
+        }
+        last = Some(pk);
+    }
+
+    Ok((scanned, last))
+}
+
+fn scan_index_entries(
+    storage: &dyn Storage,
+    (index, namespace, key_fn): &(TripleIndex, &'static str, IndexKeyFn),
+    after: Option<Vec<u8>>,
+    limit: usize,
+    faults: &mut Vec<IndexFault>,
+) -> StdResult<(usize, Option<Vec<u8>>)> {
+    let entries = raw_entries_after::<u32>(storage, namespace, after, limit)?;
+    let scanned = entries.len();
+    let mut last = None;
+    for (key, pk_len) in entries {
+        let pk = key
+            .len()
+            .checked_sub(pk_len as usize)
+            .map(|offset| &key[offset..]);
+        let referenced = match pk {
+            Some(pk) => RAW_TRIPLES
+                .may_load(storage, pk.to_vec())?
+                .is_some_and(|triple| key_fn(pk, &triple).joined_extra_key(pk) == key),
+            None => false,
+        };
+        if !referenced {
+            faults.push(IndexFault::Dangling {
+                index: index.clone(),
+                namespace,
+                key: key.clone(),
+            });
+        }
+        last = Some(key);
+    }
+
+    Ok((scanned, last))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{triples, TermRef};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    fn scan_all(storage: &dyn Storage, limit: usize) -> (usize, Vec<IndexFault>) {
+        let (mut scanned, mut faults, mut cursor) = (0, vec![], None);
+        loop {
+            let scan = scan_triple_indexes(storage, cursor.as_deref(), limit).unwrap();
+            assert!(scan.scanned <= limit);
+            scanned += scan.scanned;
+            faults.extend(scan.faults);
+            match scan.cursor {
+                Some(next) => cursor = Some(next),
+                None => return (scanned, faults),
+            }
+        }
+    }
+
+    #[test]
+    fn scan_and_repair() {
+        let mut deps = mock_dependencies();
+        let stored = |object| StoredTriple {
+            subject: TermRef::Term(1),
+            predicate: 2,
+            object: TermRef::Term(object),
+        };
+        for object in [3u128, 4] {
+            triples()
+                .save(
+                    &mut deps.storage,
+                    (&object.to_be_bytes(), vec![2], vec![1]),
+                    &stored(object),
+                )
+                .unwrap();
+        }
+
+        for limit in [1, 3, 100] {
+            assert_eq!(scan_all(&deps.storage, limit), (8, vec![]), "{limit}");
+        }
+
+        let pk = (3u128.to_be_bytes().as_slice(), vec![2], vec![1]).joined_key();
+        let missing = predicate_and_object(&pk, &stored(3)).joined_extra_key(&pk);
+        index_entries("TRIPLE__PREDICATE_OBJECT").remove(&mut deps.storage, missing.clone());
+        let dangling = object_and_subject(&pk, &stored(5)).joined_extra_key(&pk);
+        index_entries("TRIPLE__OBJECT_SUBJECT")
+            .save(&mut deps.storage, dangling.clone(), &(pk.len() as u32))
+            .unwrap();
+
+        let expected = vec![
+            IndexFault::Missing {
+                index: TripleIndex::PredicateObjectSubject,
+                namespace: "TRIPLE__PREDICATE_OBJECT",
+                key: missing,
+                pk_len: pk.len() as u32,
+            },
+            IndexFault::Dangling {
+                index: TripleIndex::ObjectSubjectPredicate,
+                namespace: "TRIPLE__OBJECT_SUBJECT",
+                key: dangling,
+            },
+        ];
+        for limit in [1, 3, 100] {
+            assert_eq!(
+                scan_all(&deps.storage, limit),
+                (8, expected.clone()),
+                "{limit}"
+            );
+        }
+
+        for fault in &expected {
+            fault.repair(&mut deps.storage).unwrap();
+        }
+        assert_eq!(scan_all(&deps.storage, 3), (8, vec![]));
+        assert_eq!(
+            scan_triple_indexes(&deps.storage, Some(&[4]), 1),
+            Err(StdError::generic_err("Invalid index scan cursor"))
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use crate::msg::DataFormat;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+/// An insert session opened by an address, whose chunks are inserted at once on commit.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InsertSession {
+    /// The format in which the data of the session are serialized.
+    pub format: DataFormat,
+    /// The number of chunks appended to the session.
+    pub chunk_count: u32,
+    /// The total size in bytes of the chunks appended to the session.
+    pub byte_size: Uint128,
+    /// The IRI of the named graph the triples of the default graph are inserted into, if any.
+    #[serde(default)]
+    pub graph: Option<String>,
+}
+
+/// The insert sessions in progress, by the address which opened them.
+pub const INSERT_SESSIONS: Map<&Addr, InsertSession> = Map::new("insert_sessions");
+
+/// The chunks of the insert sessions in progress, by address and position in the session.
+pub const INSERT_CHUNKS: Map<(&Addr, u32), Vec<u8>> = Map::new("insert_chunks");
@@ -0,0 +1,414 @@
+use crate::state::{Literal, Node};
+use cosmwasm_std::{StdError, StdResult, Storage};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, UniqueIndex};
+use serde::{Deserialize, Serialize};
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The identifier of a [LiteralTag] in the lookup table.
+pub type LiteralTagId = u32;
+
+/// Store the key increment the identifiers of the literal tags are issued from.
+pub const LITERAL_TAG_KEY_INCREMENT: Item<LiteralTagId> = Item::new("literal_tag_key");
+
+/// The tag qualifying a literal, i.e. its language or its datatype, interned in a lookup table as a
+/// few of them (e.g. `en`, `xsd:string`, `xsd:dateTime`) are shared by most of the literals.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum LiteralTag {
+    Language(String),
+    Datatype(Node),
+}
+
+impl LiteralTag {
+    pub fn key(&self) -> Vec<u8> {
+        match self {
+            LiteralTag::Language(language) => [b"l".as_slice(), language.as_bytes()].concat(),
+            LiteralTag::Datatype(datatype) => [b"d".as_slice(), &datatype.key()].concat(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LiteralTagEntry {
+    /// The interned tag.
+    pub tag: LiteralTag,
+
+    /// The unique, incremented identifier issued to reference this tag from a literal.
+    pub id: LiteralTagId,
+
+    /// A reference counter to this tag from the terms of the dictionary.
+    #[serde(default)]
+    pub counter: u128,
+}
+
+pub struct LiteralTagIndexes<'a> {
+    pub id: UniqueIndex<'a, LiteralTagId, LiteralTagEntry, Vec<u8>>,
+}
+
+impl IndexList<LiteralTagEntry> for LiteralTagIndexes<'_> {
+    fn get_indexes(&self) -> Box<dyn Iterator<Item = &'_ dyn Index<LiteralTagEntry>> + '_> {
+        let id: &dyn Index<LiteralTagEntry> = &self.id;
+        Box::new(vec![id].into_iter())
+    }
+}
+
+/// The storage namespace of [literal_tags].
+pub const LITERAL_TAGS_NAMESPACE: &str = "LITERAL_TAG";
+
+/// The literal tags by [LiteralTag::key].
+pub fn literal_tags<'a>() -> IndexedMap<Vec<u8>, LiteralTagEntry, LiteralTagIndexes<'a>> {
+    IndexedMap::new(
+        LITERAL_TAGS_NAMESPACE,
+        LiteralTagIndexes {
+            id: UniqueIndex::new(|entry| entry.id, "LITERAL_TAG__ID"),
+        },
+    )
+}
+
+/// A literal as stored in the term dictionary, its language or datatype being referenced by the
+/// identifier of its [LiteralTag].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum StoredLiteral {
+    Simple {
+        value: String,
+    },
+    I18NString {
+        value: String,
+        language: LiteralTagId,
+    },
+    Typed {
+        value: String,
+        datatype: LiteralTagId,
+    },
+}
+
+impl StoredLiteral {
+    /// The identifier of the tag of the literal, if any.
+    pub fn tag(&self) -> Option<LiteralTagId> {
+        match self {
+            StoredLiteral::Simple { .. } => None,
+            StoredLiteral::I18NString { language, .. } => Some(*language),
+            StoredLiteral::Typed { datatype, .. } => Some(*datatype),
+        }
+    }
+}
+
+/// The tags added to and removed from the lookup table by a [LiteralTagService::flush].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LiteralTagChanges {
+    pub added: Vec<LiteralTag>,
+    pub removed: Vec<LiteralTag>,
+}
+
+/// [LiteralTagService] resolves and interns [LiteralTag]s, it implements an in-memory cache of the
+/// tags in both directions. The newly interned tags and the changes of the count of references to
+/// the tags are kept in memory until calling [Self::flush].
+pub struct LiteralTagService {
+    by_id: BTreeMap<LiteralTagId, LiteralTag>,
+    by_key: BTreeMap<Vec<u8>, LiteralTagId>,
+    interned: Vec<LiteralTagId>,
+    key_increment: Option<LiteralTagId>,
+    counters: BTreeMap<LiteralTagId, u128>,
+}
+
+impl LiteralTagService {
+    pub fn new() -> Self {
+        Self {
+            by_id: BTreeMap::new(),
+            by_key: BTreeMap::new(),
+            interned: Vec::new(),
+            key_increment: None,
+            counters: BTreeMap::new(),
+        }
+    }
+
+    /// Resolve a [LiteralTag] from its identifier, returning it from cache in priority before
+    /// accessing the state.
+    pub fn resolve(&mut self, storage: &dyn Storage, id: LiteralTagId) -> StdResult<LiteralTag> {
+        if let Some(tag) = self.by_id.get(&id) {
+            return Ok(tag.clone());
+        }
+
+        let tag = literal_tags()
+            .idx
+            .id
+            .item(storage, id)?
+            .map(|(_, entry)| entry.tag)
+            .ok_or_else(|| StdError::not_found("LiteralTag"))?;
+        self.cache(id, tag.clone());
+
+        Ok(tag)
+    }
+
+    /// Returns the identifier of a [LiteralTag], issuing a new one if not already interned.
+    pub fn intern(&mut self, storage: &dyn Storage, tag: LiteralTag) -> StdResult<LiteralTagId> {
+        let key = tag.key();
+        if let Some(id) = self.by_key.get(&key) {
+            return Ok(*id);
+        }
+        if let Some(entry) = literal_tags().may_load(storage, key)? {
+            self.cache(entry.id, tag);
+            return Ok(entry.id);
+        }
+
+        let id = match self.key_increment {
+            Some(id) => id,
+            None => LITERAL_TAG_KEY_INCREMENT
+                .may_load(storage)?
+                .unwrap_or_default(),
+        };
+        self.key_increment = Some(
+            id.checked_add(1)
+                .ok_or_else(|| StdError::generic_err("Literal tag identifiers exhausted"))?,
+        );
+        self.cache(id, tag);
+        self.interned.push(id);
+
+        Ok(id)
+    }
+
+    /// Returns the stored form of a literal, interning its tag.
+    pub fn intern_literal(
+        &mut self,
+        storage: &dyn Storage,
+        literal: Literal,
+    ) -> StdResult<StoredLiteral> {
+        Ok(match literal {
+            Literal::Simple { value } => StoredLiteral::Simple { value },
+            Literal::I18NString { value, language } => StoredLiteral::I18NString {
+                value,
+                language: self.intern(storage, LiteralTag::Language(language))?,
+            },
+            Literal::Typed { value, datatype } => StoredLiteral::Typed {
+                value,
+                datatype: self.intern(storage, LiteralTag::Datatype(datatype))?,
+            },
+        })
+    }
+
+    /// Returns the literal of a stored one, resolving its tag.
+    pub fn resolve_literal(
+        &mut self,
+        storage: &dyn Storage,
+        literal: StoredLiteral,
+    ) -> StdResult<Literal> {
+        let unexpected = |id| StdError::generic_err(format!("Unexpected literal tag: {id}"));
+        Ok(match literal {
+            StoredLiteral::Simple { value } => Literal::Simple { value },
+            StoredLiteral::I18NString { value, language } => {
+                match self.resolve(storage, language)? {
+                    LiteralTag::Language(language) => Literal::I18NString { value, language },
+                    LiteralTag::Datatype(_) => Err(unexpected(language))?,
+                }
+            }
+            StoredLiteral::Typed { value, datatype } => match self.resolve(storage, datatype)? {
+                LiteralTag::Datatype(datatype) => Literal::Typed { value, datatype },
+                LiteralTag::Language(_) => Err(unexpected(datatype))?,
+            },
+        })
+    }
+
+    /// Increment the count of references to this tag from the terms of the dictionary.
+    /// This is applied to the in-memory cache only, [Self::flush] must be called to write the changes
+    /// to the state.
+    pub fn count_ref(&mut self, storage: &dyn Storage, id: LiteralTagId) -> StdResult<()> {
+        *self.resolve_counter(storage, id)? += 1;
+        Ok(())
+    }
+
+    /// Decrement the count of references to this tag, deleting it if not used anymore.
+    /// This is applied to the in-memory cache only, [Self::flush] must be called to write the changes
+    /// to the state.
+    pub fn free_ref(&mut self, storage: &dyn Storage, id: LiteralTagId) -> StdResult<()> {
+        let counter = self.resolve_counter(storage, id)?;
+        if *counter == 0 {
+            Err(StdError::generic_err(
+                "Trying to delete a non existing literal tag",
+            ))?;
+        }
+        *counter -= 1;
+        Ok(())
+    }
+
+    /// Writes the newly interned tags and the changed reference counters to the state, the tags no
+    /// longer referenced being removed, and returns the tags added and removed.
+    pub fn flush(&mut self, storage: &mut dyn Storage) -> StdResult<LiteralTagChanges> {
+        let interned: BTreeSet<_> = self.interned.drain(..).collect();
+        let mut changes = LiteralTagChanges::default();
+        for (id, counter) in std::mem::take(&mut self.counters) {
+            let tag = self.resolve(storage, id)?;
+            let key = tag.key();
+            if counter > 0 {
+                literal_tags().save(
+                    storage,
+                    key,
+                    &LiteralTagEntry {
+                        tag: tag.clone(),
+                        id,
+                        counter,
+                    },
+                )?;
+                if interned.contains(&id) {
+                    changes.added.push(tag);
+                }
+            } else {
+                if !interned.contains(&id) {
+                    literal_tags().remove(storage, key.clone())?;
+                    changes.removed.push(tag);
+                }
+                self.by_key.remove(&key);
+                self.by_id.remove(&id);
+            }
+        }
+        if let Some(id) = self.key_increment.take() {
+            LITERAL_TAG_KEY_INCREMENT.save(storage, &id)?;
+        }
+
+        Ok(changes)
+    }
+
+    fn resolve_counter(&mut self, storage: &dyn Storage, id: LiteralTagId) -> StdResult<&mut u128> {
+        Ok(match self.counters.entry(id) {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(
+                literal_tags()
+                    .idx
+                    .id
+                    .item(storage, id)?
+                    .map_or(0, |(_, entry)| entry.counter),
+            ),
+        })
+    }
+
+    fn cache(&mut self, id: LiteralTagId, tag: LiteralTag) {
+        self.by_key.insert(tag.key(), id);
+        self.by_id.insert(id, tag);
+    }
+}
+
+impl Default for LiteralTagService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::Order;
+
+    #[test]
+    fn intern_and_resolve() {
+        let mut deps = mock_dependencies();
+        let literal = |language: &str| Literal::I18NString {
+            value: "val".to_string(),
+            language: language.to_string(),
+        };
+        let typed = Literal::Typed {
+            value: "1".to_string(),
+            datatype: Node {
+                namespace: 0,
+                value: "integer".to_string(),
+            },
+        };
+
+        let mut svc = LiteralTagService::new();
+        assert_eq!(
+            svc.intern_literal(&deps.storage, literal("en")),
+            Ok(StoredLiteral::I18NString {
+                value: "val".to_string(),
+                language: 0,
+            })
+        );
+        assert_eq!(
+            svc.intern_literal(&deps.storage, typed.clone()),
+            Ok(StoredLiteral::Typed {
+                value: "1".to_string(),
+                datatype: 1,
+            })
+        );
+        assert_eq!(
+            svc.intern(&deps.storage, LiteralTag::Language("en".to_string())),
+            Ok(0)
+        );
+        for id in [0, 0, 1] {
+            svc.count_ref(&deps.storage, id).unwrap();
+        }
+        assert_eq!(
+            svc.flush(&mut deps.storage),
+            Ok(LiteralTagChanges {
+                added: vec![
+                    LiteralTag::Language("en".to_string()),
+                    LiteralTag::Datatype(Node {
+                        namespace: 0,
+                        value: "integer".to_string(),
+                    }),
+                ],
+                removed: vec![],
+            })
+        );
+
+        let mut svc = LiteralTagService::new();
+        assert_eq!(
+            svc.intern(&deps.storage, LiteralTag::Language("fr".to_string())),
+            Ok(2)
+        );
+        assert_eq!(
+            svc.intern_literal(&deps.storage, literal("en")),
+            Ok(StoredLiteral::I18NString {
+                value: "val".to_string(),
+                language: 0,
+            })
+        );
+        // the tags interned but not referenced are not written
+        assert_eq!(
+            svc.intern(&deps.storage, LiteralTag::Language("de".to_string())),
+            Ok(3)
+        );
+        svc.count_ref(&deps.storage, 2).unwrap();
+        svc.free_ref(&deps.storage, 0).unwrap();
+        svc.free_ref(&deps.storage, 0).unwrap();
+        assert_eq!(
+            svc.free_ref(&deps.storage, 0),
+            Err(StdError::generic_err(
+                "Trying to delete a non existing literal tag"
+            ))
+        );
+        assert_eq!(
+            svc.flush(&mut deps.storage),
+            Ok(LiteralTagChanges {
+                added: vec![LiteralTag::Language("fr".to_string())],
+                removed: vec![LiteralTag::Language("en".to_string())],
+            })
+        );
+        assert_eq!(
+            literal_tags()
+                .range(&deps.storage, None, None, Order::Ascending)
+                .map(|res| res.map(|(_, entry)| (entry.id, entry.counter)))
+                .collect::<StdResult<BTreeSet<_>>>(),
+            Ok(BTreeSet::from([(1, 1), (2, 1)]))
+        );
+
+        let mut svc = LiteralTagService::new();
+        assert_eq!(
+            svc.resolve_literal(
+                &deps.storage,
+                StoredLiteral::Typed {
+                    value: "1".to_string(),
+                    datatype: 1,
+                }
+            ),
+            Ok(typed)
+        );
+        assert!(svc
+            .resolve_literal(
+                &deps.storage,
+                StoredLiteral::Typed {
+                    value: "1".to_string(),
+                    datatype: 0,
+                }
+            )
+            .is_err());
+    }
+}
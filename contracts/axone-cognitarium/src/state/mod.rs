@@ -1,11 +1,43 @@
+mod audit;
 mod blank_nodes;
+mod compaction;
+mod expirations;
+mod grants;
+mod index_integrity;
+mod insert_sessions;
+mod literal_tags;
 mod namespaces;
+mod offload;
+mod pending_batches;
+mod prefixes;
+mod provenance;
+mod recent;
+mod soft_limit;
 mod store;
+mod stored_queries;
+mod subscriptions;
+mod terms;
 mod triples;
 
+pub use audit::*;
 pub use blank_nodes::*;
+pub use compaction::*;
+pub use expirations::*;
+pub use grants::*;
+pub use index_integrity::*;
+pub use insert_sessions::*;
+pub use literal_tags::*;
 pub use namespaces::*;
+pub use offload::*;
+pub use pending_batches::*;
+pub use prefixes::*;
+pub use provenance::*;
+pub use recent::*;
+pub use soft_limit::*;
 pub use store::*;
+pub use stored_queries::*;
+pub use subscriptions::*;
+pub use terms::*;
 pub use triples::*;
 
 #[cfg(test)]
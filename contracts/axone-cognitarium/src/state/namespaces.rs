@@ -1,14 +1,18 @@
-use cosmwasm_std::{StdError, StdResult, Storage};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, UniqueIndex};
+use cosmwasm_std::{Empty, Order, StdError, StdResult, Storage};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, UniqueIndex};
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::rc::Rc;
 
-/// Store a key increment used a unique key for referencing a namespace. Given the size of an `u128`
-/// there is no need to implement a garbage collector mechanism in case some namespaces are removed.
+/// Store a key increment used a unique key for referencing a namespace, only issuing a new key when
+/// no key of a removed namespace can be reused, see [FREE_NAMESPACE_KEYS].
 pub const NAMESPACE_KEY_INCREMENT: Item<u128> = Item::new("namespace_key");
 
+/// The keys of the namespaces removed once their last reference got deleted, reused in priority
+/// when allocating a new namespace so the key space doesn't grow with the namespaces churn.
+pub const FREE_NAMESPACE_KEYS: Map<u128, Empty> = Map::new("free_namespace_keys");
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Namespace {
     /// The namespace value.
@@ -32,15 +36,21 @@ impl IndexList<Namespace> for NamespaceIndexes<'_> {
     }
 }
 
+/// The storage namespace of [namespaces].
+pub const NAMESPACES_NAMESPACE: &str = "NAMESPACE";
+
 pub fn namespaces<'a>() -> IndexedMap<String, Namespace, NamespaceIndexes<'a>> {
     IndexedMap::new(
-        "NAMESPACE",
+        NAMESPACES_NAMESPACE,
         NamespaceIndexes {
             key: UniqueIndex::new(|ns| ns.key, "NAMESPACE__KEY"),
         },
     )
 }
 
+/// The lower bound of the transient namespace keys, see [NamespaceQuerier::insert_transient].
+const TRANSIENT_KEYS: u128 = u128::MAX / 2;
+
 /// [NamespaceQuerier] is a [Namespace] querying service allowing to resolve namespaces either by
 /// namespace's value or namespace's internal state key. It implements a two way indexed in-memory
 /// cache to mitigate state access.
@@ -124,6 +134,26 @@ impl NamespaceQuerier {
         ns_rc
     }
 
+    /// Cache a namespace not present in the state under a transient key, allocated from the top of
+    /// the key space so it can't collide with the keys of the stored namespaces. As no stored
+    /// triple references it, it allows to represent terms coming from outside of the store, e.g.
+    /// from another store, for the time of a query.
+    pub fn insert_transient(&mut self, value: String) -> Namespace {
+        let key = self
+            .by_key
+            .range(TRANSIENT_KEYS..)
+            .next()
+            .map_or(u128::MAX, |(key, _)| key - 1);
+        let ns = Namespace {
+            value,
+            key,
+            counter: 0,
+        };
+        self.insert(ns.clone());
+
+        ns
+    }
+
     /// Utility middleware to consider `StdResult::Ok(None)` as `Err(StdError::NotFound)` of namespace.
     /// Typically used with [Self::resolve_from_key].
     pub fn none_as_error_middleware(resolve_res: Option<Namespace>) -> StdResult<Namespace> {
@@ -196,6 +226,20 @@ impl<'a> NamespaceResolver<'a> {
             ns_querier: ns_cache.into(),
         }
     }
+
+    /// Resolve a [Namespace] from its value, caching it under a transient key if not present in the
+    /// state (see [NamespaceQuerier::insert_transient]).
+    pub fn resolve_or_transient(&mut self, value: String) -> StdResult<Namespace> {
+        Ok(
+            match self
+                .ns_querier
+                .resolve_from_val(self.storage, value.clone())?
+            {
+                Some(ns) => ns,
+                None => self.ns_querier.insert_transient(value),
+            },
+        )
+    }
 }
 
 impl<'a> NamespaceSolver for NamespaceResolver<'a> {
@@ -222,14 +266,15 @@ impl<'a> HasCachedNamespaces for NamespaceResolver<'a> {
     }
 }
 
-/// Allow to batch write operations on [Namespace] taking care of the [NAMESPACE_KEY_INCREMENT], it
-/// manages insertions/deletions as well as counting references. It internally use a [NamespaceQuerier]
-/// as a cache of new/removed/modified namespaces, to finally apply writing to the state when
-/// calling [Self::flush].
+/// Allow to batch write operations on [Namespace] taking care of the [NAMESPACE_KEY_INCREMENT] and
+/// [FREE_NAMESPACE_KEYS], it manages insertions/deletions as well as counting references. It
+/// internally use a [NamespaceQuerier] as a cache of new/removed/modified namespaces, to finally
+/// apply writing to the state when calling [Self::flush].
 pub struct NamespaceBatchService {
     ns_resolver: NamespaceQuerier,
     ns_key_inc: u128,
     ns_count_diff: i128,
+    reused_keys: BTreeSet<u128>,
 }
 
 impl NamespaceBatchService {
@@ -238,6 +283,7 @@ impl NamespaceBatchService {
             ns_resolver: NamespaceQuerier::new(),
             ns_key_inc: NAMESPACE_KEY_INCREMENT.load(storage)?,
             ns_count_diff: 0,
+            reused_keys: BTreeSet::new(),
         })
     }
 
@@ -258,11 +304,13 @@ impl NamespaceBatchService {
         storage: &dyn Storage,
         value: String,
     ) -> StdResult<Namespace> {
-        self.ns_resolver
-            .resolve_cell_from_val(storage, value.clone())
-            .map(|maybe_cell| {
-                maybe_cell.map_or_else(|| self.allocate(value), |cell| cell.borrow().clone())
-            })
+        match self
+            .ns_resolver
+            .resolve_cell_from_val(storage, value.clone())?
+        {
+            Some(cell) => Ok(cell.borrow().clone()),
+            None => self.allocate(storage, value),
+        }
     }
 
     /// Increment the count of references to this namespace. This is applied to the in-memory cache
@@ -276,6 +324,9 @@ impl NamespaceBatchService {
                     |cell| {
                         let mut ns = cell.borrow_mut();
                         ns.counter += 1;
+                        if ns.counter == 1 {
+                            self.ns_count_diff += 1;
+                        }
                         Ok(ns.clone())
                     },
                 )
@@ -310,6 +361,9 @@ impl NamespaceBatchService {
     /// Writes all the cached changes to the state, returning the namespace count diff.
     pub fn flush(&mut self, storage: &mut dyn Storage) -> StdResult<i128> {
         NAMESPACE_KEY_INCREMENT.save(storage, &self.ns_key_inc)?;
+        for key in std::mem::take(&mut self.reused_keys) {
+            FREE_NAMESPACE_KEYS.remove(storage, key);
+        }
 
         for entry in &self.ns_resolver.by_val {
             if entry.1.borrow().counter > 0 {
@@ -320,6 +374,7 @@ impl NamespaceBatchService {
                     Err(StdError::NotFound { .. }) => Ok(()),
                     _ => res,
                 }?;
+                FREE_NAMESPACE_KEYS.save(storage, entry.1.borrow().key, &Empty {})?;
             }
         }
 
@@ -330,16 +385,34 @@ impl NamespaceBatchService {
         Ok(count_diff)
     }
 
-    fn allocate(&mut self, value: String) -> Namespace {
+    /// Allocate a new namespace, reusing the lowest free key not already reused in this batch if
+    /// any, or issuing a new one.
+    fn allocate(&mut self, storage: &dyn Storage, value: String) -> StdResult<Namespace> {
+        let free_key = FREE_NAMESPACE_KEYS
+            .keys(
+                storage,
+                self.reused_keys.last().copied().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .next()
+            .transpose()?;
+        let key = match free_key {
+            Some(key) => {
+                self.reused_keys.insert(key);
+                key
+            }
+            None => {
+                self.ns_key_inc += 1;
+                self.ns_key_inc - 1
+            }
+        };
+
         let ns = Namespace {
             value,
-            key: self.ns_key_inc,
+            key,
             counter: 0u128,
         };
-
-        self.ns_key_inc += 1;
-        self.ns_count_diff += 1;
-
-        self.ns_resolver.insert(ns).borrow().clone()
+        Ok(self.ns_resolver.insert(ns).borrow().clone())
     }
 }
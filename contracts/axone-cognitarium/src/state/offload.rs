@@ -0,0 +1,101 @@
+use axone_objectarium::msg::HashAlgorithm;
+use axone_objectarium::{crypto, state as bucket_state};
+use axone_objectarium_client::ObjectRef;
+use axone_wasm::uri::CosmwasmUri;
+use cosmwasm_std::{Addr, StdError, StdResult, Uint128};
+use cw_storage_plus::{Item, Map};
+use serde::{Deserialize, Serialize};
+
+/// The configuration of the literals offloading, only present when enabled.
+pub const LITERAL_OFFLOAD: Item<LiteralOffload> = Item::new("literal_offload");
+
+/// The number of stored triples referencing each offloaded object, by the reference to the object
+/// held by their literal, the object being unpinned once no longer referenced.
+pub const OFFLOADED_OBJECTS: Map<&str, u64> = Map::new("offloaded_objects");
+
+/// The `axone-objectarium` bucket the literals exceeding a byte size are offloaded to at insertion,
+/// the stored literal only keeping a reference to the object holding its value.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LiteralOffload {
+    /// The address of the bucket.
+    pub bucket: Addr,
+
+    /// The byte size above which the value of a literal is offloaded.
+    pub threshold: Uint128,
+
+    /// The algorithm the bucket hashes the objects with to identify them, allowing to reference an
+    /// object before it gets stored.
+    pub hash_algorithm: HashAlgorithm,
+}
+
+impl LiteralOffload {
+    /// Returns the reference to the object the literal value is offloaded to along with the object
+    /// id, or [None] if the value doesn't exceed the threshold.
+    pub fn offload(&self, value: &str) -> StdResult<Option<(String, String)>> {
+        if Uint128::from(value.len() as u128) <= self.threshold {
+            return Ok(None);
+        }
+
+        let object_id = crypto::hash(
+            &bucket_state::HashAlgorithm::from(self.hash_algorithm).into(),
+            &value.as_bytes().to_vec(),
+        )
+        .to_string();
+        let uri = CosmwasmUri::try_from(ObjectRef {
+            object_id: object_id.clone(),
+            storage_address: self.bucket.to_string(),
+        })
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+        Ok(Some((uri.to_string(), object_id)))
+    }
+
+    /// Returns the id of the object a literal value references, if it has been offloaded to the
+    /// bucket.
+    pub fn object_id(&self, value: &str) -> Option<String> {
+        object_ref(value)
+            .filter(|object| object.storage_address == self.bucket.as_str())
+            .map(|object| object.object_id)
+    }
+}
+
+/// Returns the object a literal value references, if any, whatever the bucket it belongs to.
+pub fn object_ref(value: &str) -> Option<ObjectRef> {
+    if !value.starts_with("cosmwasm:") {
+        return None;
+    }
+
+    CosmwasmUri::try_from(value.to_string())
+        .ok()
+        .and_then(|uri| ObjectRef::try_from(uri).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offload_round_trip() {
+        let offload = LiteralOffload {
+            bucket: Addr::unchecked("bucket"),
+            threshold: Uint128::new(3),
+            hash_algorithm: HashAlgorithm::Sha256,
+        };
+
+        assert_eq!(offload.offload("abc"), Ok(None));
+
+        let (reference, object_id) = offload.offload("abcd").unwrap().unwrap();
+        assert_eq!(
+            object_id,
+            "88d4266fd4e6338d13b845fcf289579d209c897823b9217da3e161936f031589"
+        );
+        assert_eq!(offload.object_id(&reference), Some(object_id));
+
+        let other = LiteralOffload {
+            bucket: Addr::unchecked("other"),
+            ..offload.clone()
+        };
+        assert_eq!(other.object_id(&reference), None);
+        assert_eq!(offload.object_id("abcd"), None);
+    }
+}
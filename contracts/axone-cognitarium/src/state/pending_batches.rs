@@ -0,0 +1,16 @@
+use crate::msg::InsertBatchReceipt;
+use cw_storage_plus::Item;
+use serde::{Deserialize, Serialize};
+
+/// A batch insertion awaiting the replies of its sibling stores, see
+/// [crate::msg::ExecuteMsg::InsertBatch].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PendingBatch {
+    /// The number of sibling stores whose reply is awaited.
+    pub remaining: usize,
+    /// The receipt of the batch, completed as the sibling stores reply.
+    pub receipt: InsertBatchReceipt,
+}
+
+/// The batch insertion awaiting the replies of its sibling stores, if any.
+pub const PENDING_BATCH: Item<PendingBatch> = Item::new("pending_batch");
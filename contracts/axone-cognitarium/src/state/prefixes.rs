@@ -0,0 +1,5 @@
+use cw_storage_plus::Map;
+
+/// The prefixes registered by the owner, by prefix, expanding to their namespace in every query even
+/// when not declared in it.
+pub const PREFIXES: Map<&str, String> = Map::new("prefixes");
@@ -0,0 +1,45 @@
+use crate::msg;
+use cosmwasm_std::Addr;
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+/// The origin of the description of a named subject, i.e. of its first inserted triples.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Provenance {
+    /// The address which inserted the triples.
+    pub writer: Addr,
+    /// The graph the triples belonged to, in its N-Quads form, [None] standing for the default graph.
+    pub graph: Option<String>,
+}
+
+/// The provenance of the named subjects, by subject key, removed once all the triples about the
+/// subject have been deleted.
+pub const SUBJECT_PROVENANCE: Map<&[u8], Provenance> = Map::new("subject_provenance");
+
+/// The insertion of a triple in the store.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Insertion {
+    /// The address which inserted the triple.
+    pub inserter: Addr,
+    /// The height of the block the triple was inserted at.
+    pub height: u64,
+    /// The index in its block of the transaction the triple was inserted by, if known.
+    pub tx_index: Option<u32>,
+}
+
+impl From<Insertion> for msg::Insertion {
+    fn from(value: Insertion) -> Self {
+        Self {
+            inserter: value.inserter.into(),
+            height: value.height.into(),
+            tx_index: value.tx_index,
+        }
+    }
+}
+
+/// The insertion of the stored triples, by triple key (see
+/// [StoredTriple::key](crate::state::StoredTriple::key)), removed along with the triple.
+pub const TRIPLE_INSERTIONS: Map<&[u8], Insertion> = Map::new(TRIPLE_INSERTIONS_NAMESPACE);
+
+/// The storage namespace of [TRIPLE_INSERTIONS].
+pub const TRIPLE_INSERTIONS_NAMESPACE: &str = "triple_insertions";
@@ -0,0 +1,107 @@
+use crate::state::StoredTriple;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Item, Map};
+use serde::{Deserialize, Serialize};
+
+/// The configuration and position of the recent window index, only present when the index has been
+/// enabled at instantiation.
+pub const RECENT_WINDOW: Item<RecentWindow> = Item::new("recent_window");
+
+/// The recent window index, referencing the triples inserted by each of the last insert batches by
+/// their insertion sequence, i.e. the insert batch sequence number and the position of the triple
+/// in this batch.
+pub const RECENT_TRIPLES: Map<(u64, u32), StoredTriple> = Map::new("RECENT_TRIPLE");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RecentWindow {
+    /// The number of insert batches retained by the index.
+    pub size: u32,
+
+    /// The sequence number of the last insert batch, starting from 1, 0 meaning no batch has been
+    /// inserted yet.
+    pub last_batch: u64,
+}
+
+impl RecentWindow {
+    pub fn new(size: u32) -> Self {
+        Self {
+            size,
+            last_batch: 0,
+        }
+    }
+
+    /// Returns the sequence number of the oldest insert batch still retained by the index.
+    pub fn first_batch(&self) -> u64 {
+        (self.last_batch + 1)
+            .saturating_sub(self.size as u64)
+            .max(1)
+    }
+
+    /// Record the triples inserted by a new insert batch, evicting the batch going out of the window.
+    pub fn record(
+        &mut self,
+        storage: &mut dyn Storage,
+        inserted: &[StoredTriple],
+    ) -> StdResult<()> {
+        self.last_batch += 1;
+        for (pos, triple) in inserted.iter().enumerate() {
+            RECENT_TRIPLES.save(storage, (self.last_batch, pos as u32), triple)?;
+        }
+
+        if self.last_batch > self.size as u64 {
+            let evicted = self.last_batch - self.size as u64;
+            let keys = RECENT_TRIPLES
+                .prefix(evicted)
+                .keys(storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for pos in keys {
+                RECENT_TRIPLES.remove(storage, (evicted, pos));
+            }
+        }
+
+        RECENT_WINDOW.save(storage, self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::TermRef;
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn record_and_evict() {
+        let mut deps = mock_dependencies();
+        let triple = |n: u128| StoredTriple {
+            subject: TermRef::Blank(n),
+            predicate: n,
+            object: TermRef::Term(n),
+        };
+
+        let mut window = RecentWindow::new(2);
+        assert_eq!(window.first_batch(), 1);
+
+        window
+            .record(&mut deps.storage, &[triple(1), triple(2)])
+            .unwrap();
+        window.record(&mut deps.storage, &[triple(3)]).unwrap();
+        assert_eq!(window.first_batch(), 1);
+        assert_eq!(
+            RECENT_TRIPLES
+                .range(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            3
+        );
+
+        window.record(&mut deps.storage, &[]).unwrap();
+        assert_eq!(window.first_batch(), 2);
+        assert_eq!(RECENT_WINDOW.load(&deps.storage).unwrap(), window);
+        assert_eq!(
+            RECENT_TRIPLES
+                .range(&deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap(),
+            vec![((2, 0), triple(3))]
+        );
+    }
+}
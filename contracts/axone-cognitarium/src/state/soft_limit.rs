@@ -0,0 +1,96 @@
+use crate::state::Store;
+use cosmwasm_std::{Addr, Event, Uint128};
+use cw_storage_plus::Item;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// The soft limit of the store, only present when configured.
+pub const SOFT_LIMIT: Item<SoftLimit> = Item::new("soft_limit");
+
+/// A threshold on the usage of the store past which it is considered near its limits, the
+/// insertions still succeeding but carrying a warning.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SoftLimit {
+    /// The percentage of the triple count and byte size limits the threshold stands at.
+    pub threshold_percent: u8,
+
+    /// The writers, besides the owner, still allowed to insert once the threshold is reached, all
+    /// the writers being allowed if not set.
+    pub allowed_writers: Option<BTreeSet<Addr>>,
+}
+
+impl SoftLimit {
+    /// Tell whether the usage of the store reached the threshold of either its triple count or
+    /// byte size limit.
+    pub fn is_reached(&self, store: &Store) -> bool {
+        let reached = |usage: Uint128, max: Uint128| {
+            usage.full_mul(100u128) >= max.full_mul(self.threshold_percent)
+        };
+
+        reached(store.stat.triple_count, store.limits.max_triple_count)
+            || reached(store.stat.byte_size, store.limits.max_byte_size)
+    }
+
+    /// Tell whether the writer is allowed to insert in the store, given its current usage.
+    pub fn allows(&self, store: &Store, writer: &Addr) -> bool {
+        match &self.allowed_writers {
+            Some(allowed) if self.is_reached(store) => {
+                *writer == store.owner || allowed.contains(writer)
+            }
+            _ => true,
+        }
+    }
+
+    /// The event warning the store is near its limits, if the threshold is reached.
+    pub fn warning(&self, store: &Store) -> Option<Event> {
+        self.is_reached(store).then(|| {
+            Event::new("store_near_limit")
+                .add_attribute("threshold_percent", self.threshold_percent.to_string())
+                .add_attribute("triple_count", store.stat.triple_count)
+                .add_attribute("max_triple_count", store.limits.max_triple_count)
+                .add_attribute("byte_size", store.stat.byte_size)
+                .add_attribute("max_byte_size", store.limits.max_byte_size)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::{ConflictPolicy, StoreLimitsInput};
+
+    #[test]
+    fn near_limit() {
+        let mut store = Store::new(
+            Addr::unchecked("owner"),
+            StoreLimitsInput {
+                max_triple_count: Uint128::new(10),
+                max_byte_size: Uint128::MAX,
+                ..Default::default()
+            }
+            .into(),
+            ConflictPolicy::Allow,
+            true,
+        );
+        let soft_limit = SoftLimit {
+            threshold_percent: 80,
+            allowed_writers: Some(BTreeSet::from([Addr::unchecked("allowed")])),
+        };
+
+        store.stat.triple_count = Uint128::new(7);
+        assert!(!soft_limit.is_reached(&store));
+        assert!(soft_limit.allows(&store, &Addr::unchecked("other")));
+        assert_eq!(soft_limit.warning(&store), None);
+
+        store.stat.triple_count = Uint128::new(8);
+        assert!(soft_limit.is_reached(&store));
+        assert!(!soft_limit.allows(&store, &Addr::unchecked("other")));
+        assert!(soft_limit.allows(&store, &Addr::unchecked("allowed")));
+        assert!(soft_limit.allows(&store, &Addr::unchecked("owner")));
+        assert!(soft_limit.warning(&store).is_some());
+
+        store.stat.byte_size = Uint128::MAX;
+        store.stat.triple_count = Uint128::zero();
+        assert!(soft_limit.is_reached(&store));
+    }
+}
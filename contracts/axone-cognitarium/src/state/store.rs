@@ -1,24 +1,45 @@
 use crate::msg;
-use crate::msg::StoreResponse;
-use cosmwasm_std::{Addr, Uint128};
+use crate::msg::{ConflictPolicy, DataFormat, StoreResponse};
+use cosmwasm_std::{Addr, Uint128, Uint256};
 use cw_storage_plus::Item;
 use serde::{Deserialize, Serialize};
 
 pub const STORE: Item<Store> = Item::new("store");
 
+/// The version of the storage layout of the store, bumped along each change of the layout requiring
+/// to migrate the state, see [migrate_state](crate::contract::migrate::migrate_state). It is
+/// missing from the stores written before it got introduced, i.e. by `6.0.0`.
+pub const STATE_LAYOUT: Item<u16> = Item::new("state_layout");
+
+/// The address proposed to become the owner of the store, until it accepts the ownership transfer.
+pub const PENDING_OWNER: Item<Addr> = Item::new("pending_owner");
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Store {
     pub owner: Addr,
     pub limits: StoreLimits,
     pub stat: StoreStat,
+    #[serde(default)]
+    pub conflict_policy: ConflictPolicy,
+    /// Defaults to `false` for the stores instantiated before the normalization got introduced, so
+    /// their literals keep being matched as inserted.
+    #[serde(default)]
+    pub literal_normalization: bool,
 }
 
 impl Store {
-    pub fn new(owner: Addr, limits: StoreLimits) -> Store {
+    pub fn new(
+        owner: Addr,
+        limits: StoreLimits,
+        conflict_policy: ConflictPolicy,
+        literal_normalization: bool,
+    ) -> Store {
         Store {
             owner,
             limits,
             stat: StoreStat::default(),
+            conflict_policy,
+            literal_normalization,
         }
     }
 }
@@ -29,6 +50,9 @@ impl From<Store> for StoreResponse {
             owner: value.owner.into(),
             limits: value.limits.into(),
             stat: value.stat.into(),
+            conflict_policy: value.conflict_policy,
+            literal_normalization: value.literal_normalization,
+            formats: DataFormat::supported(),
         }
     }
 }
@@ -42,6 +66,22 @@ pub struct StoreLimits {
     pub max_query_variable_count: u32,
     pub max_insert_data_byte_size: Uint128,
     pub max_insert_data_triple_count: Uint128,
+    #[serde(default = "msg::StoreLimitsInput::default_max_query_pattern_count")]
+    pub max_query_pattern_count: u32,
+    #[serde(default = "msg::StoreLimitsInput::default_max_query_where_depth")]
+    pub max_query_where_depth: u32,
+    #[serde(default = "msg::StoreLimitsInput::default_max_query_join_variable_count")]
+    pub max_query_join_variable_count: u32,
+    #[serde(default = "msg::StoreLimitsInput::default_max_query_inference_depth")]
+    pub max_query_inference_depth: u32,
+    #[serde(default = "msg::StoreLimitsInput::default_max_result_byte_size")]
+    pub max_result_byte_size: Uint128,
+    #[serde(default = "msg::StoreLimitsInput::default_max_insert_blank_node_count")]
+    pub max_insert_blank_node_count: Uint128,
+    #[serde(default = "msg::StoreLimitsInput::default_max_insert_session_byte_size")]
+    pub max_insert_session_byte_size: Uint128,
+    #[serde(default = "msg::StoreLimitsInput::default_max_query_ordered_solution_count")]
+    pub max_query_ordered_solution_count: u32,
 }
 
 impl From<msg::StoreLimitsInput> for StoreLimits {
@@ -54,6 +94,14 @@ impl From<msg::StoreLimitsInput> for StoreLimits {
             max_query_variable_count: value.max_query_variable_count,
             max_insert_data_byte_size: value.max_insert_data_byte_size,
             max_insert_data_triple_count: value.max_insert_data_triple_count,
+            max_query_pattern_count: value.max_query_pattern_count,
+            max_query_where_depth: value.max_query_where_depth,
+            max_query_join_variable_count: value.max_query_join_variable_count,
+            max_query_inference_depth: value.max_query_inference_depth,
+            max_result_byte_size: value.max_result_byte_size,
+            max_insert_blank_node_count: value.max_insert_blank_node_count,
+            max_insert_session_byte_size: value.max_insert_session_byte_size,
+            max_query_ordered_solution_count: value.max_query_ordered_solution_count,
         }
     }
 }
@@ -68,6 +116,14 @@ impl From<StoreLimits> for msg::StoreLimits {
             max_query_variable_count: value.max_query_variable_count,
             max_insert_data_byte_size: value.max_insert_data_byte_size,
             max_insert_data_triple_count: value.max_insert_data_triple_count,
+            max_query_pattern_count: value.max_query_pattern_count,
+            max_query_where_depth: value.max_query_where_depth,
+            max_query_join_variable_count: value.max_query_join_variable_count,
+            max_query_inference_depth: value.max_query_inference_depth,
+            max_result_byte_size: value.max_result_byte_size,
+            max_insert_blank_node_count: value.max_insert_blank_node_count,
+            max_insert_session_byte_size: value.max_insert_session_byte_size,
+            max_query_ordered_solution_count: value.max_query_ordered_solution_count,
         }
     }
 }
@@ -79,6 +135,30 @@ pub struct StoreStat {
     pub byte_size: Uint128,
 }
 
+/// The distinct terms of the store and the breakdown of its byte size, maintained aside the
+/// [StoreStat] of the [Store] as only needed by the statistics query.
+pub const TERM_STAT: Item<TermStat> = Item::new("term_stat");
+
+/// The order-independent checksum of the stored triples, i.e. the wrapping sum of their digests,
+/// maintained on each insertion and deletion, see [triple_digest](crate::state::triple_digest).
+pub const STORE_CHECKSUM: Item<Uint256> = Item::new("store_checksum");
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+pub struct TermStat {
+    pub subject_count: Uint128,
+    pub predicate_count: Uint128,
+    pub object_count: Uint128,
+    pub subject_byte_size: Uint128,
+    pub predicate_byte_size: Uint128,
+    pub object_byte_size: Uint128,
+    /// The number of interned literal tags, see [literal_tags](crate::state::literal_tags).
+    #[serde(default)]
+    pub literal_tag_count: Uint128,
+    /// The size of the interned literal tags, each one being counted once.
+    #[serde(default)]
+    pub literal_tag_byte_size: Uint128,
+}
+
 impl From<StoreStat> for msg::StoreStat {
     fn from(value: StoreStat) -> Self {
         Self {
@@ -0,0 +1,201 @@
+use crate::msg::{
+    Expression, Node, ParameterValue, SelectItem, SelectQuery, TriplePattern, VarOrNamedNode,
+    VarOrNode, VarOrNodeOrLiteral, WhereClause,
+};
+use crate::querier::clause_variables;
+use cosmwasm_std::{StdError, StdResult};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The select queries registered by the owner, by name.
+pub const STORED_QUERIES: Map<&str, StoredQuery> = Map::new("stored_queries");
+
+/// A select query registered under a name, some of its variables being parameters bound to a
+/// value by the caller at evaluation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredQuery {
+    pub query: SelectQuery,
+
+    /// The variables of the query the caller binds to a value, each of them having to occur in its
+    /// triple patterns.
+    pub parameters: Vec<String>,
+}
+
+impl StoredQuery {
+    pub fn try_new(query: SelectQuery, parameters: Vec<String>) -> StdResult<Self> {
+        if let Some(parameter) = query.select.iter().find_map(|item| match item {
+            SelectItem::Variable(v) if parameters.contains(v) => Some(v),
+            _ => None,
+        }) {
+            return Err(StdError::generic_err(format!(
+                "Parameter '{parameter}' can't be selected"
+            )));
+        }
+        let variables = clause_variables(&query.r#where);
+        if let Some(parameter) = parameters.iter().find(|p| !variables.contains(*p)) {
+            return Err(StdError::generic_err(format!(
+                "Parameter '{parameter}' doesn't occur in the query"
+            )));
+        }
+
+        Ok(Self { query, parameters })
+    }
+
+    /// Returns the query with its parameters replaced by the values they are bound to, all of them
+    /// having to be bound.
+    pub fn bind(&self, values: &BTreeMap<String, ParameterValue>) -> StdResult<SelectQuery> {
+        if let Some(parameter) = self.parameters.iter().find(|p| !values.contains_key(*p)) {
+            return Err(StdError::generic_err(format!(
+                "Missing parameter '{parameter}'"
+            )));
+        }
+        if let Some(parameter) = values.keys().find(|p| !self.parameters.contains(p)) {
+            return Err(StdError::generic_err(format!(
+                "Unknown parameter '{parameter}'"
+            )));
+        }
+
+        Ok(SelectQuery {
+            r#where: bind_clause(&self.query.r#where, values)?,
+            ..self.query.clone()
+        })
+    }
+}
+
+fn bind_clause(
+    clause: &WhereClause,
+    values: &BTreeMap<String, ParameterValue>,
+) -> StdResult<WhereClause> {
+    Ok(match clause {
+        WhereClause::Bgp { patterns } => WhereClause::Bgp {
+            patterns: patterns
+                .iter()
+                .map(|pattern| bind_pattern(pattern, values))
+                .collect::<StdResult<_>>()?,
+        },
+        WhereClause::LateralJoin { left, right } => WhereClause::LateralJoin {
+            left: Box::new(bind_clause(left, values)?),
+            right: Box::new(bind_clause(right, values)?),
+        },
+        WhereClause::Filter { expr, inner } => WhereClause::Filter {
+            expr: bind_expression(expr, values),
+            inner: Box::new(bind_clause(inner, values)?),
+        },
+        WhereClause::Service {
+            contract_addr,
+            clause,
+        } => WhereClause::Service {
+            contract_addr: contract_addr.clone(),
+            clause: Box::new(bind_clause(clause, values)?),
+        },
+        WhereClause::Graph { graph, clause } => WhereClause::Graph {
+            graph: graph.clone(),
+            clause: Box::new(bind_clause(clause, values)?),
+        },
+    })
+}
+
+fn bind_pattern(
+    pattern: &TriplePattern,
+    values: &BTreeMap<String, ParameterValue>,
+) -> StdResult<TriplePattern> {
+    let literal_error = |v: &str, position: &str| {
+        StdError::generic_err(format!(
+            "Parameter '{v}' can't be bound to a literal as {position}"
+        ))
+    };
+
+    Ok(TriplePattern {
+        subject: match &pattern.subject {
+            VarOrNode::Variable(v) => match values.get(v) {
+                Some(ParameterValue::NamedNode(iri)) => {
+                    VarOrNode::Node(Node::NamedNode(iri.clone()))
+                }
+                Some(ParameterValue::Literal(_)) => Err(literal_error(v, "subject"))?,
+                None => pattern.subject.clone(),
+            },
+            VarOrNode::QuotedTriple(quoted) => {
+                VarOrNode::QuotedTriple(Box::new(bind_pattern(quoted, values)?))
+            }
+            subject => subject.clone(),
+        },
+        predicate: match &pattern.predicate {
+            VarOrNamedNode::Variable(v) => match values.get(v) {
+                Some(ParameterValue::NamedNode(iri)) => VarOrNamedNode::NamedNode(iri.clone()),
+                Some(ParameterValue::Literal(_)) => Err(literal_error(v, "predicate"))?,
+                None => pattern.predicate.clone(),
+            },
+            predicate => predicate.clone(),
+        },
+        object: match &pattern.object {
+            VarOrNodeOrLiteral::Variable(v) => match values.get(v) {
+                Some(ParameterValue::NamedNode(iri)) => {
+                    VarOrNodeOrLiteral::Node(Node::NamedNode(iri.clone()))
+                }
+                Some(ParameterValue::Literal(literal)) => {
+                    VarOrNodeOrLiteral::Literal(literal.clone())
+                }
+                None => pattern.object.clone(),
+            },
+            VarOrNodeOrLiteral::QuotedTriple(quoted) => {
+                VarOrNodeOrLiteral::QuotedTriple(Box::new(bind_pattern(quoted, values)?))
+            }
+            object => object.clone(),
+        },
+    })
+}
+
+fn bind_expression(expr: &Expression, values: &BTreeMap<String, ParameterValue>) -> Expression {
+    let bind = |expr: &Expression| Box::new(bind_expression(expr, values));
+    match expr {
+        Expression::Variable(v) => match values.get(v) {
+            Some(ParameterValue::NamedNode(iri)) => Expression::NamedNode(iri.clone()),
+            Some(ParameterValue::Literal(literal)) => Expression::Literal(literal.clone()),
+            None => expr.clone(),
+        },
+        Expression::NamedNode(_) | Expression::Literal(_) => expr.clone(),
+        Expression::And(exprs) => {
+            Expression::And(exprs.iter().map(|e| bind_expression(e, values)).collect())
+        }
+        Expression::Or(exprs) => {
+            Expression::Or(exprs.iter().map(|e| bind_expression(e, values)).collect())
+        }
+        Expression::Equal(left, right) => Expression::Equal(bind(left), bind(right)),
+        Expression::Greater(left, right) => Expression::Greater(bind(left), bind(right)),
+        Expression::GreaterOrEqual(left, right) => {
+            Expression::GreaterOrEqual(bind(left), bind(right))
+        }
+        Expression::Less(left, right) => Expression::Less(bind(left), bind(right)),
+        Expression::LessOrEqual(left, right) => Expression::LessOrEqual(bind(left), bind(right)),
+        Expression::Not(expr) => Expression::Not(bind(expr)),
+        Expression::LangMatches(expr, range) => Expression::LangMatches(bind(expr), range.clone()),
+        Expression::Contains {
+            expr,
+            substring,
+            case_insensitive,
+        } => Expression::Contains {
+            expr: bind(expr),
+            substring: substring.clone(),
+            case_insensitive: *case_insensitive,
+        },
+        Expression::StrStarts {
+            expr,
+            prefix,
+            case_insensitive,
+        } => Expression::StrStarts {
+            expr: bind(expr),
+            prefix: prefix.clone(),
+            case_insensitive: *case_insensitive,
+        },
+        Expression::StrEnds {
+            expr,
+            suffix,
+            case_insensitive,
+        } => Expression::StrEnds {
+            expr: bind(expr),
+            suffix: suffix.clone(),
+            case_insensitive: *case_insensitive,
+        },
+    }
+}
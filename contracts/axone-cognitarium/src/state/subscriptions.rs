@@ -0,0 +1,44 @@
+use crate::msg::SubscriptionResponse;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+use rio_api::model;
+use serde::{Deserialize, Serialize};
+
+/// A triple pattern registered by a subscriber, notified of the inserted triples matching it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Subscription {
+    pub subscriber: Addr,
+    /// The terms of the pattern in their N-Triples form, [None] matching any term.
+    pub subject: Option<String>,
+    pub predicate: Option<String>,
+    pub object: Option<String>,
+}
+
+impl Subscription {
+    /// Tell whether the triple matches the pattern of the subscription.
+    pub fn matches(&self, triple: &model::Triple<'_>) -> bool {
+        [
+            (&self.subject, triple.subject.to_string()),
+            (&self.predicate, triple.predicate.to_string()),
+            (&self.object, triple.object.to_string()),
+        ]
+        .into_iter()
+        .all(|(term, value)| term.as_ref().map_or(true, |term| *term == value))
+    }
+
+    pub fn into_response(self, id: u64) -> SubscriptionResponse {
+        SubscriptionResponse {
+            id,
+            subscriber: self.subscriber.into(),
+            subject: self.subject,
+            predicate: self.predicate,
+            object: self.object,
+        }
+    }
+}
+
+/// The identifier of the last registered subscription.
+pub const SUBSCRIPTION_COUNTER: Item<u64> = Item::new("subscription_counter");
+
+/// The subscriptions, by identifier.
+pub const SUBSCRIPTIONS: Map<u64, Subscription> = Map::new("subscriptions");
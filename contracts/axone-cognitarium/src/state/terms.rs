@@ -0,0 +1,298 @@
+use crate::state::{
+    Literal, LiteralTagChanges, LiteralTagId, LiteralTagService, Node, StoredLiteral, StoredTriple,
+};
+use blake3::Hash;
+use cosmwasm_std::{StdError, StdResult, Storage};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
+/// The identifier of a [Term] in the dictionary. It is derived from the hash of the term content so it
+/// can be computed without accessing the state, which allows to build keys straight from a query.
+pub type TermId = u128;
+
+/// The term dictionary, triples only reference IRIs and literals through their [TermId] so the
+/// related strings are stored once whatever the number of triples and index entries using them.
+pub const TERMS: Map<TermId, TermEntry> = Map::new(TERMS_NAMESPACE);
+
+/// The storage namespace of [TERMS].
+pub const TERMS_NAMESPACE: &str = "TERM";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum Term {
+    Node(Node),
+    Literal(Literal),
+    /// A quoted triple, referencing its own terms by their identifier.
+    Triple(StoredTriple),
+}
+
+impl Term {
+    pub fn as_hash(&self) -> Hash {
+        match self {
+            Term::Node(n) => n.as_hash(),
+            Term::Literal(l) => l.as_hash(),
+            Term::Triple(t) => t.as_hash(),
+        }
+    }
+
+    pub fn id(&self) -> TermId {
+        hash_as_id(self.as_hash())
+    }
+}
+
+/// Truncate a term hash to its [TermId].
+pub fn hash_as_id(hash: Hash) -> TermId {
+    let mut id = [0u8; 16];
+    id.copy_from_slice(&hash.as_bytes()[..16]);
+    TermId::from_be_bytes(id)
+}
+
+/// A [Term] as stored in the dictionary, the tags of literals being referenced from the
+/// [literal_tags](crate::state::literal_tags) lookup table.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum StoredTerm {
+    Node(Node),
+    Literal(StoredLiteral),
+    Triple(StoredTriple),
+}
+
+impl StoredTerm {
+    fn intern(storage: &dyn Storage, tags: &mut LiteralTagService, term: Term) -> StdResult<Self> {
+        Ok(match term {
+            Term::Node(n) => StoredTerm::Node(n),
+            Term::Literal(l) => StoredTerm::Literal(tags.intern_literal(storage, l)?),
+            Term::Triple(t) => StoredTerm::Triple(t),
+        })
+    }
+
+    /// The identifier of the tag of the term, if a tagged literal.
+    fn literal_tag(&self) -> Option<LiteralTagId> {
+        match self {
+            StoredTerm::Literal(l) => l.tag(),
+            _ => None,
+        }
+    }
+
+    fn resolve(self, storage: &dyn Storage, tags: &mut LiteralTagService) -> StdResult<Term> {
+        Ok(match self {
+            StoredTerm::Node(n) => Term::Node(n),
+            StoredTerm::Literal(l) => Term::Literal(tags.resolve_literal(storage, l)?),
+            StoredTerm::Triple(t) => Term::Triple(t),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TermEntry {
+    /// The interned term.
+    pub term: StoredTerm,
+
+    /// A reference counter to this term.
+    pub counter: u128,
+}
+
+/// [TermQuerier] resolves [Term]s from their [TermId], it implements an in-memory cache to mitigate
+/// state access.
+pub struct TermQuerier {
+    cache: BTreeMap<TermId, Term>,
+    tags: LiteralTagService,
+}
+
+impl TermQuerier {
+    pub fn new() -> Self {
+        Self {
+            cache: BTreeMap::new(),
+            tags: LiteralTagService::new(),
+        }
+    }
+
+    /// Resolve a [Term] from its identifier, returning it from cache in priority before accessing
+    /// the state.
+    pub fn resolve(&mut self, storage: &dyn Storage, id: TermId) -> StdResult<Term> {
+        if let Some(term) = self.cache.get(&id) {
+            return Ok(term.clone());
+        }
+
+        let term = TERMS
+            .load(storage, id)?
+            .term
+            .resolve(storage, &mut self.tags)?;
+        self.cache.insert(id, term.clone());
+
+        Ok(term)
+    }
+}
+
+impl Default for TermQuerier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Vec<Term>> for TermQuerier {
+    fn from(value: Vec<Term>) -> Self {
+        Self {
+            cache: value.into_iter().map(|t| (t.id(), t)).collect(),
+            tags: LiteralTagService::new(),
+        }
+    }
+}
+
+/// Allow to batch write operations on the term dictionary, it manages insertions/deletions as well as
+/// counting references. Changes are kept in memory until calling [Self::flush].
+pub struct TermBatchService {
+    entries: BTreeMap<TermId, Option<TermEntry>>,
+    tags: LiteralTagService,
+}
+
+impl TermBatchService {
+    pub fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            tags: LiteralTagService::new(),
+        }
+    }
+
+    /// Increment the count of references to this term, interning it if not already existing.
+    /// This is applied to the in-memory cache only, [Self::flush] must be called to write the changes
+    /// to the state.
+    pub fn count_ref(&mut self, storage: &dyn Storage, term: Term) -> StdResult<TermId> {
+        let id = term.id();
+        let term = StoredTerm::intern(storage, &mut self.tags, term)?;
+        let tag = term.literal_tag();
+        let entry = self.resolve_entry(storage, id)?;
+        match entry {
+            Some(e) if e.term != term => Err(StdError::generic_err(format!(
+                "Term identifier collision: {id}"
+            )))?,
+            Some(e) => e.counter += 1,
+            None => {
+                *entry = Some(TermEntry { term, counter: 1 });
+                if let Some(tag) = tag {
+                    self.tags.count_ref(storage, tag)?;
+                }
+            }
+        }
+
+        Ok(id)
+    }
+
+    /// Decrement the count of references to this term, deleting it if not used anymore.
+    /// This is applied to the in-memory cache only, [Self::flush] must be called to write the changes
+    /// to the state.
+    pub fn free_ref(&mut self, storage: &dyn Storage, id: TermId) -> StdResult<()> {
+        match self.resolve_entry(storage, id)? {
+            Some(e) if e.counter > 0 => {
+                e.counter -= 1;
+                Ok(())
+            }
+            _ => Err(StdError::generic_err(
+                "Trying to delete a non existing term",
+            )),
+        }
+    }
+
+    /// Writes all the cached changes to the state, returning the literal tags added and removed
+    /// along with the terms qualified by them.
+    pub fn flush(&mut self, storage: &mut dyn Storage) -> StdResult<LiteralTagChanges> {
+        for (id, entry) in &self.entries {
+            match entry {
+                Some(e) if e.counter > 0 => TERMS.save(storage, *id, e)?,
+                Some(e) => {
+                    if let Some(tag) = e.term.literal_tag() {
+                        self.tags.free_ref(storage, tag)?;
+                    }
+                    TERMS.remove(storage, *id)
+                }
+                None => {}
+            }
+        }
+        self.entries.clear();
+
+        self.tags.flush(storage)
+    }
+
+    fn resolve_entry(
+        &mut self,
+        storage: &dyn Storage,
+        id: TermId,
+    ) -> StdResult<&mut Option<TermEntry>> {
+        Ok(match self.entries.entry(id) {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(TERMS.may_load(storage, id)?),
+        })
+    }
+}
+
+impl Default for TermBatchService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::Order;
+
+    #[test]
+    fn term_id() {
+        let node = Term::Node(Node {
+            namespace: 0,
+            value: "val".to_string(),
+        });
+        let literal = Term::Literal(Literal::Simple {
+            value: "val".to_string(),
+        });
+
+        assert_eq!(node.id(), node.clone().id());
+        assert_ne!(node.id(), literal.id());
+        assert_eq!(&node.id().to_be_bytes(), &node.as_hash().as_bytes()[..16]);
+    }
+
+    #[test]
+    fn batch_ref_counting() {
+        let mut deps = mock_dependencies();
+        let term = Term::Literal(Literal::Simple {
+            value: "val".to_string(),
+        });
+
+        let mut svc = TermBatchService::new();
+        let id = svc.count_ref(&deps.storage, term.clone()).unwrap();
+        assert_eq!(svc.count_ref(&deps.storage, term.clone()).unwrap(), id);
+        svc.flush(&mut deps.storage).unwrap();
+
+        assert_eq!(
+            TERMS.load(&deps.storage, id).unwrap(),
+            TermEntry {
+                term: StoredTerm::Literal(StoredLiteral::Simple {
+                    value: "val".to_string(),
+                }),
+                counter: 2,
+            }
+        );
+        assert_eq!(TermQuerier::new().resolve(&deps.storage, id).unwrap(), term);
+
+        svc.free_ref(&deps.storage, id).unwrap();
+        svc.flush(&mut deps.storage).unwrap();
+        assert_eq!(TERMS.load(&deps.storage, id).unwrap().counter, 1);
+
+        svc.free_ref(&deps.storage, id).unwrap();
+        svc.flush(&mut deps.storage).unwrap();
+        assert_eq!(
+            TERMS
+                .range_raw(&deps.storage, None, None, Order::Ascending)
+                .count(),
+            0
+        );
+
+        assert_eq!(
+            svc.free_ref(&deps.storage, id),
+            Err(StdError::generic_err(
+                "Trying to delete a non existing term"
+            ))
+        );
+    }
+}
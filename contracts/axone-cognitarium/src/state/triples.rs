@@ -1,7 +1,9 @@
-use crate::state::NamespaceSolver;
+use crate::state::{
+    hash_as_id, NamespaceSolver, Term, TermBatchService, TermId, TermQuerier, TERMS,
+};
 use blake3::Hash;
-use cosmwasm_std::StdResult;
-use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
+use cosmwasm_std::{from_json, Order, StdError, StdResult, Storage, Uint256};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex, PrimaryKey};
 use serde::{Deserialize, Serialize};
 
 /// Represents a triple primary key as a tuple of:
@@ -10,30 +12,210 @@ use serde::{Deserialize, Serialize};
 /// - Subject in a binary format
 pub type TriplePK<'a> = (&'a [u8], Vec<u8>, Vec<u8>);
 
+/// The secondary indexes of the triples, complementing the primary key (i.e. OPS ordering) so any
+/// triple pattern can be served by a prefix range:
+/// - `subject_and_predicate`: SPO ordering;
+/// - `predicate_and_object`: POS ordering;
+/// - `object_and_subject`: OSP ordering.
 pub struct TripleIndexes<'a> {
-    pub subject_and_predicate: MultiIndex<'a, (Vec<u8>, Vec<u8>), Triple, TriplePK<'a>>,
+    pub subject_and_predicate: MultiIndex<'a, (Vec<u8>, Vec<u8>), StoredTriple, TriplePK<'a>>,
+    pub predicate_and_object: MultiIndex<'a, (Vec<u8>, Vec<u8>), StoredTriple, TriplePK<'a>>,
+    pub object_and_subject: MultiIndex<'a, (Vec<u8>, Vec<u8>), StoredTriple, TriplePK<'a>>,
 }
 
-impl IndexList<Triple> for TripleIndexes<'_> {
-    fn get_indexes(&self) -> Box<dyn Iterator<Item = &'_ dyn Index<Triple>> + '_> {
-        let subject_and_predicate: &dyn Index<Triple> = &self.subject_and_predicate;
-        Box::new(vec![subject_and_predicate].into_iter())
+impl IndexList<StoredTriple> for TripleIndexes<'_> {
+    fn get_indexes(&self) -> Box<dyn Iterator<Item = &'_ dyn Index<StoredTriple>> + '_> {
+        let subject_and_predicate: &dyn Index<StoredTriple> = &self.subject_and_predicate;
+        let predicate_and_object: &dyn Index<StoredTriple> = &self.predicate_and_object;
+        let object_and_subject: &dyn Index<StoredTriple> = &self.object_and_subject;
+        Box::new(
+            vec![
+                subject_and_predicate,
+                predicate_and_object,
+                object_and_subject,
+            ]
+            .into_iter(),
+        )
     }
 }
 
-pub fn triples<'a>() -> IndexedMap<TriplePK<'a>, Triple, TripleIndexes<'a>> {
+/// The storage namespace of the triples, see [triples].
+pub const TRIPLES_NAMESPACE: &str = "TRIPLE";
+
+pub fn triples<'a>() -> IndexedMap<TriplePK<'a>, StoredTriple, TripleIndexes<'a>> {
     IndexedMap::new(
-        "TRIPLE",
+        TRIPLES_NAMESPACE,
         TripleIndexes {
             subject_and_predicate: MultiIndex::new(
-                |_pk, triple| (triple.subject.key(), triple.predicate.key()),
-                "TRIPLE",
+                subject_and_predicate,
+                TRIPLES_NAMESPACE,
                 "TRIPLE__SUBJECT_PREDICATE",
             ),
+            predicate_and_object: MultiIndex::new(
+                predicate_and_object,
+                TRIPLES_NAMESPACE,
+                "TRIPLE__PREDICATE_OBJECT",
+            ),
+            object_and_subject: MultiIndex::new(
+                object_and_subject,
+                TRIPLES_NAMESPACE,
+                "TRIPLE__OBJECT_SUBJECT",
+            ),
         },
     )
 }
 
+/// The key of a triple in the `subject_and_predicate` index, see [TripleIndexes].
+pub fn subject_and_predicate(_pk: &[u8], triple: &StoredTriple) -> (Vec<u8>, Vec<u8>) {
+    (
+        triple.subject.key(),
+        triple.predicate.to_be_bytes().to_vec(),
+    )
+}
+
+/// The key of a triple in the `predicate_and_object` index, see [TripleIndexes].
+pub fn predicate_and_object(_pk: &[u8], triple: &StoredTriple) -> (Vec<u8>, Vec<u8>) {
+    (triple.predicate.to_be_bytes().to_vec(), triple.object.key())
+}
+
+/// The key of a triple in the `object_and_subject` index, see [TripleIndexes].
+pub fn object_and_subject(_pk: &[u8], triple: &StoredTriple) -> (Vec<u8>, Vec<u8>) {
+    (triple.object.key(), triple.subject.key())
+}
+
+/// The migration of the stored triples in progress, if any, see [migrate_triples].
+pub const TRIPLE_MIGRATION: Item<TripleMigration> = Item::new("triple_migration");
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TripleMigration {
+    /// The key of the last triple scanned, [None] if none was scanned yet. Once all the triples
+    /// are scanned, the cursor of the counting of their terms.
+    pub cursor: Option<Vec<u8>>,
+    /// Whether the triples already in their stored form are indexed again, populating the indexes
+    /// introduced by a storage layout change.
+    pub index: bool,
+    /// Whether the statistics about the terms of the store and the checksum of its triples remain to
+    /// be counted again from the migrated triples, see
+    /// [count_stats](crate::storer::StoreEngine::count_stats).
+    pub count_terms: bool,
+    /// Whether all the triples are scanned.
+    pub scanned: bool,
+}
+
+/// The outcome of a batch of [migrate_triples].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TripleMigrationBatch {
+    /// The number of triples scanned.
+    pub scanned: usize,
+    /// The number of triples interned among the scanned ones.
+    pub interned: usize,
+    /// The number of triples indexed again among the scanned ones.
+    pub indexed: usize,
+    /// The cursor to migrate the following triples with, if some may remain.
+    pub cursor: Option<Vec<u8>>,
+}
+
+/// Migrate up to `limit` stored triples, resuming after the cursor of the migration.
+///
+/// The triples stored in full, as written before the term dictionary was introduced, have their
+/// terms interned and are replaced by their [StoredTriple] form keyed by the identifiers of their
+/// terms, then indexed. The ones already in their stored form are indexed again if the migration
+/// requires it, index entries being written without removing any existing one.
+pub fn migrate_triples(
+    storage: &mut dyn Storage,
+    migration: &TripleMigration,
+    limit: usize,
+) -> StdResult<TripleMigrationBatch> {
+    let legacy: Map<TriplePK<'_>, Triple> = Map::new(TRIPLES_NAMESPACE);
+    let legacy_index: MultiIndex<'_, _, _, TriplePK<'_>> = MultiIndex::new(
+        legacy_subject_and_predicate,
+        TRIPLES_NAMESPACE,
+        "TRIPLE__SUBJECT_PREDICATE",
+    );
+
+    let keys = legacy
+        .keys(
+            storage,
+            migration.cursor.clone().map(Bound::ExclusiveRaw),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut terms = TermBatchService::new();
+    let scanned = keys.len();
+    let mut batch = TripleMigrationBatch {
+        scanned,
+        ..Default::default()
+    };
+    for (object, predicate, subject) in keys {
+        let pk = (object.as_slice(), predicate, subject);
+        if scanned == limit {
+            batch.cursor = Some(pk.joined_key());
+        }
+        // the triples already migrated by a previous batch are stored in their compact form
+        let raw = storage
+            .get(&legacy.key(pk.clone()))
+            .ok_or_else(|| StdError::not_found("triple"))?;
+        if let Ok(stored) = from_json::<StoredTriple>(&raw) {
+            if migration.index {
+                triples().replace(storage, pk, Some(&stored), None)?;
+                batch.indexed += 1;
+            }
+            continue;
+        }
+        let triple: Triple = from_json(&raw).map_err(|_| {
+            StdError::parse_err(
+                "Triple",
+                "the triple is stored in none of the known layouts",
+            )
+        })?;
+
+        for term in triple.terms() {
+            terms.count_ref(storage, term)?;
+        }
+        legacy_index.remove(storage, &pk.joined_key(), &triple)?;
+        legacy.remove(storage, pk.clone());
+        triples().replace(
+            storage,
+            (pk.0, triple.predicate.key(), triple.subject.key()),
+            Some(&StoredTriple::from(&triple)),
+            None,
+        )?;
+        batch.interned += 1;
+    }
+    terms.flush(storage)?;
+
+    Ok(batch)
+}
+
+/// The key of a triple stored in full in the `subject_and_predicate` index, as written before the
+/// term dictionary was introduced, the nodes being keyed by their namespace followed by their value.
+fn legacy_subject_and_predicate(_pk: &[u8], triple: &Triple) -> (Vec<u8>, Vec<u8>) {
+    let node_key = |node: &Node| {
+        [
+            node.namespace.to_be_bytes().as_slice(),
+            node.value.as_bytes(),
+        ]
+        .concat()
+    };
+    let subject = match &triple.subject {
+        Subject::Named(n) => [b"n".as_slice(), node_key(n).as_slice()].concat(),
+        Subject::Blank(n) => [b"b".as_slice(), n.to_be_bytes().as_slice()].concat(),
+        Subject::Triple(_) => triple.subject.key(),
+    };
+
+    (subject, node_key(&triple.predicate))
+}
+
+/// The digest of a triple given the components of its primary key, the store checksum being the
+/// wrapping sum of the digests of all its triples, see [STORE_CHECKSUM](crate::state::STORE_CHECKSUM).
+pub fn triple_digest(object_hash: &[u8], predicate: &[u8], subject: &[u8]) -> Uint256 {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(object_hash).update(predicate).update(subject);
+    Uint256::from_be_bytes(*hasher.finalize().as_bytes())
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Triple {
     pub subject: Subject,
@@ -42,10 +224,14 @@ pub struct Triple {
 }
 
 impl Triple {
+    /// Returns the namespaces referenced by the triple, including the ones of the quoted triples it
+    /// holds.
     pub fn namespaces(&self) -> Vec<u128> {
         let mut namespaces = Vec::with_capacity(3);
-        if let Subject::Named(n) = &self.subject {
-            namespaces.push(n.namespace);
+        match &self.subject {
+            Subject::Named(n) => namespaces.push(n.namespace),
+            Subject::Triple(t) => namespaces.extend(t.namespaces()),
+            Subject::Blank(_) => {}
         }
 
         namespaces.push(self.predicate.namespace);
@@ -53,42 +239,221 @@ impl Triple {
         match &self.object {
             Object::Named(n) => namespaces.push(n.namespace),
             Object::Literal(Literal::Typed { datatype, .. }) => namespaces.push(datatype.namespace),
+            Object::Triple(t) => namespaces.extend(t.namespaces()),
             _ => {}
         }
 
         namespaces
     }
+
+    /// Returns the terms of the triple to intern in the dictionary, blank nodes being excluded.
+    ///
+    /// A quoted triple is interned along with its own terms, so referencing it counts a reference
+    /// to each of them.
+    pub fn terms(&self) -> Vec<Term> {
+        let mut terms = Vec::with_capacity(3);
+        match &self.subject {
+            Subject::Named(n) => terms.push(Term::Node(n.clone())),
+            Subject::Triple(t) => terms.extend(t.quoted_terms()),
+            Subject::Blank(_) => {}
+        }
+
+        terms.push(Term::Node(self.predicate.clone()));
+
+        match &self.object {
+            Object::Named(n) => terms.push(Term::Node(n.clone())),
+            Object::Literal(l) => terms.push(Term::Literal(l.clone())),
+            Object::Triple(t) => terms.extend(t.quoted_terms()),
+            Object::Blank(_) => {}
+        }
+
+        terms
+    }
+
+    fn quoted_terms(&self) -> Vec<Term> {
+        let mut terms = self.terms();
+        terms.push(Term::Triple(self.into()));
+        terms
+    }
+
+    /// The hash of the triple as a quoted triple term, see [StoredTriple::as_hash].
+    pub fn as_hash(&self) -> Hash {
+        StoredTriple::from(self).as_hash()
+    }
+
+    pub fn id(&self) -> TermId {
+        hash_as_id(self.as_hash())
+    }
 }
 
+/// The compact form of a [Triple] as persisted in the state, where IRIs and literals are replaced
+/// by their identifier in the term dictionary.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
-pub enum Subject {
-    Named(Node),
+pub struct StoredTriple {
+    pub subject: TermRef,
+    pub predicate: TermId,
+    pub object: TermRef,
+}
+
+impl StoredTriple {
+    /// The hash of the triple as a quoted triple term, derived from the identifiers of its terms.
+    pub fn as_hash(&self) -> Hash {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"t").update(&self.key());
+        hasher.finalize()
+    }
+
+    /// The key identifying the triple by its terms, in the subject, predicate and object order.
+    pub fn key(&self) -> Vec<u8> {
+        [
+            self.subject.key(),
+            self.predicate.to_be_bytes().to_vec(),
+            self.object.key(),
+        ]
+        .concat()
+    }
+
+    /// The triple of the given key, if well formed (see [Self::key]).
+    pub fn from_key(key: &[u8]) -> Option<Self> {
+        let object = TermRef::KEY_LEN + 16;
+        Some(Self {
+            subject: TermRef::from_key(key.get(..TermRef::KEY_LEN)?)?,
+            predicate: TermId::from_be_bytes(key.get(TermRef::KEY_LEN..object)?.try_into().ok()?),
+            object: TermRef::from_key(key.get(object..)?)?,
+        })
+    }
+
+    /// Tells whether the triple is currently stored, its terms being possibly no longer in the term
+    /// dictionary otherwise.
+    pub fn is_stored(&self, storage: &dyn Storage) -> StdResult<bool> {
+        // The primary key being keyed by the hash of the object, the object term is resolved to
+        // compute it, the triple not being stored if its object is no longer in the dictionary.
+        let object_hash = match self.object {
+            TermRef::Term(id) if !TERMS.has(storage, id) => return Ok(false),
+            TermRef::Term(id) => TermQuerier::new().resolve(storage, id)?.as_hash(),
+            TermRef::Blank(n) => Object::Blank(n).as_hash(),
+        };
+        let pk = (
+            object_hash.as_bytes().as_slice(),
+            self.predicate.to_be_bytes().to_vec(),
+            self.subject.key(),
+        );
+
+        Ok(triples().may_load(storage, pk)?.as_ref() == Some(self))
+    }
+
+    /// Resolve the terms referenced by the triple from the term dictionary.
+    pub fn resolve(&self, storage: &dyn Storage, querier: &mut TermQuerier) -> StdResult<Triple> {
+        Ok(Triple {
+            subject: match self.subject {
+                TermRef::Term(id) => match querier.resolve(storage, id)? {
+                    Term::Node(n) => Subject::Named(n),
+                    Term::Triple(t) => Subject::Triple(Box::new(t.resolve(storage, querier)?)),
+                    Term::Literal(_) => Err(unexpected_term_error("literal", "subject"))?,
+                },
+                TermRef::Blank(n) => Subject::Blank(n),
+            },
+            predicate: match querier.resolve(storage, self.predicate)? {
+                Term::Node(n) => n,
+                Term::Literal(_) => Err(unexpected_term_error("literal", "predicate"))?,
+                Term::Triple(_) => Err(unexpected_term_error("quoted triple", "predicate"))?,
+            },
+            object: match self.object {
+                TermRef::Term(id) => match querier.resolve(storage, id)? {
+                    Term::Node(n) => Object::Named(n),
+                    Term::Literal(l) => Object::Literal(l),
+                    Term::Triple(t) => Object::Triple(Box::new(t.resolve(storage, querier)?)),
+                },
+                TermRef::Blank(n) => Object::Blank(n),
+            },
+        })
+    }
+}
+
+fn unexpected_term_error(term: &str, position: &str) -> StdError {
+    StdError::generic_err(format!("Unexpected {term} term as triple {position}"))
+}
+
+impl From<&Triple> for StoredTriple {
+    fn from(value: &Triple) -> Self {
+        Self {
+            subject: (&value.subject).into(),
+            predicate: value.predicate.id(),
+            object: (&value.object).into(),
+        }
+    }
+}
+
+/// A reference to a triple term, either a term of the dictionary or a blank node.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum TermRef {
+    Term(TermId),
     Blank(BlankNode),
 }
 
-impl Subject {
+impl TermRef {
     pub fn key(&self) -> Vec<u8> {
-        match self {
-            Subject::Named(n) => {
-                let node = n.key();
-                let mut key: Vec<u8> = Vec::with_capacity(node.len() + 1);
-                key.push(b'n');
-                key.extend(node);
+        let (tag, val) = match self {
+            TermRef::Term(id) => (b'n', id.to_be_bytes()),
+            TermRef::Blank(n) => (b'b', n.to_be_bytes()),
+        };
+        let mut key: Vec<u8> = Vec::with_capacity(val.len() + 1);
+        key.push(tag);
+        key.extend(val);
 
-                key
-            }
-            Subject::Blank(n) => {
-                let val = n.to_be_bytes();
-                let mut key: Vec<u8> = Vec::with_capacity(val.len() + 1);
-                key.push(b'b');
-                key.extend(val);
+        key
+    }
 
-                key
-            }
+    /// The length of the key of a term reference, i.e. its tag followed by its identifier.
+    pub const KEY_LEN: usize = 17;
+
+    /// The term reference of the given key, if well formed (see [Self::key]).
+    pub fn from_key(key: &[u8]) -> Option<Self> {
+        let (tag, val) = key.split_first()?;
+        let val = u128::from_be_bytes(val.try_into().ok()?);
+        match tag {
+            b'n' => Some(TermRef::Term(val)),
+            b'b' => Some(TermRef::Blank(val)),
+            _ => None,
+        }
+    }
+}
+
+impl From<&Subject> for TermRef {
+    fn from(value: &Subject) -> Self {
+        match value {
+            Subject::Named(n) => TermRef::Term(n.id()),
+            Subject::Blank(n) => TermRef::Blank(*n),
+            Subject::Triple(t) => TermRef::Term(t.id()),
+        }
+    }
+}
+
+impl From<&Object> for TermRef {
+    fn from(value: &Object) -> Self {
+        match value {
+            Object::Named(n) => TermRef::Term(n.id()),
+            Object::Literal(l) => TermRef::Term(l.id()),
+            Object::Blank(n) => TermRef::Blank(*n),
+            Object::Triple(t) => TermRef::Term(t.id()),
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum Subject {
+    Named(Node),
+    Blank(BlankNode),
+    /// A quoted triple, as in [RDF-star](https://www.w3.org/2021/12/rdf-star.html).
+    Triple(Box<Triple>),
+}
+
+impl Subject {
+    pub fn key(&self) -> Vec<u8> {
+        TermRef::from(self).key()
+    }
+}
+
 pub type Predicate = Node;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -96,39 +461,26 @@ pub enum Object {
     Named(Node),
     Blank(BlankNode),
     Literal(Literal),
+    /// A quoted triple, as in [RDF-star](https://www.w3.org/2021/12/rdf-star.html).
+    Triple(Box<Triple>),
 }
 
 impl Object {
     pub fn as_hash(&self) -> Hash {
-        let mut hasher = blake3::Hasher::new();
         match self {
-            Object::Named(n) => {
-                hasher
-                    .update(&[b'n'])
-                    .update(n.namespace.to_be_bytes().as_slice())
-                    .update(n.value.as_bytes());
-            }
+            Object::Named(n) => n.as_hash(),
             Object::Blank(n) => {
-                hasher.update(&[b'b']).update(n.to_be_bytes().as_slice());
-            }
-            Object::Literal(l) => {
-                hasher.update(&[b'l']);
-                match l {
-                    Literal::Simple { value } => hasher.update(&[b's']).update(value.as_bytes()),
-                    Literal::I18NString { value, language } => hasher
-                        .update(&[b'i'])
-                        .update(value.as_bytes())
-                        .update(language.as_bytes()),
-                    Literal::Typed { value, datatype } => hasher
-                        .update(&[b't'])
-                        .update(value.as_bytes())
-                        .update(datatype.namespace.to_be_bytes().as_slice())
-                        .update(datatype.value.as_bytes()),
-                };
+                let mut hasher = blake3::Hasher::new();
+                hasher.update(b"b").update(n.to_be_bytes().as_slice());
+                hasher.finalize()
             }
+            Object::Literal(l) => l.as_hash(),
+            Object::Triple(t) => t.as_hash(),
         }
+    }
 
-        hasher.finalize()
+    pub fn key(&self) -> Vec<u8> {
+        TermRef::from(self).key()
     }
 }
 
@@ -142,13 +494,22 @@ pub struct Node {
 }
 
 impl Node {
-    pub fn key(&self) -> Vec<u8> {
-        let val = self.value.as_bytes();
-        let mut key: Vec<u8> = Vec::with_capacity(val.len() + 16);
-        key.extend(self.namespace.to_be_bytes());
-        key.extend(val);
+    pub fn as_hash(&self) -> Hash {
+        let mut hasher = blake3::Hasher::new();
+        hasher
+            .update(b"n")
+            .update(self.namespace.to_be_bytes().as_slice())
+            .update(self.value.as_bytes());
 
-        key
+        hasher.finalize()
+    }
+
+    pub fn id(&self) -> TermId {
+        hash_as_id(self.as_hash())
+    }
+
+    pub fn key(&self) -> Vec<u8> {
+        self.id().to_be_bytes().to_vec()
     }
 
     pub fn as_iri(&self, ns_solver: &mut dyn NamespaceSolver) -> StdResult<String> {
@@ -163,9 +524,47 @@ pub enum Literal {
     Typed { value: String, datatype: Node },
 }
 
+impl Literal {
+    pub fn as_hash(&self) -> Hash {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"l");
+        match self {
+            Literal::Simple { value } => hasher.update(b"s").update(value.as_bytes()),
+            Literal::I18NString { value, language } => hasher
+                .update(b"i")
+                .update(value.as_bytes())
+                .update(language.as_bytes()),
+            Literal::Typed { value, datatype } => hasher
+                .update(b"t")
+                .update(value.as_bytes())
+                .update(datatype.namespace.to_be_bytes().as_slice())
+                .update(datatype.value.as_bytes()),
+        };
+
+        hasher.finalize()
+    }
+
+    pub fn id(&self) -> TermId {
+        hash_as_id(self.as_hash())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    #[test]
+    fn migrate_unknown_triple_layout() {
+        let mut storage = MockStorage::new();
+        let legacy: Map<TriplePK<'_>, Triple> = Map::new(TRIPLES_NAMESPACE);
+        let key = legacy.key((&[1u8; 32][..], vec![2u8], vec![3u8]));
+        storage.set(&key, br#"{"unknown":true}"#);
+
+        let res = migrate_triples(&mut storage, &TripleMigration::default(), 10);
+        assert!(matches!(res, Err(StdError::ParseErr { .. })));
+        assert_eq!(storage.get(&key), Some(br#"{"unknown":true}"#.to_vec()));
+    }
 
     #[test]
     fn object_hash() {
@@ -279,4 +678,54 @@ mod test {
             assert_ne!(case.0.as_hash(), case.1.as_hash())
         }
     }
+
+    #[test]
+    fn is_stored() {
+        let mut storage = MockStorage::new();
+        let node = |value: &str| Node {
+            namespace: 0,
+            value: value.to_string(),
+        };
+        let triple = Triple {
+            subject: Subject::Named(node("s")),
+            predicate: node("p"),
+            object: Object::Literal(Literal::Simple {
+                value: "o".to_string(),
+            }),
+        };
+        let mut terms = TermBatchService::new();
+        for term in triple.terms() {
+            terms.count_ref(&storage, term).unwrap();
+        }
+        terms.flush(&mut storage).unwrap();
+        let stored = StoredTriple::from(&triple);
+        let object_hash = triple.object.as_hash();
+        let pk = (
+            object_hash.as_bytes().as_slice(),
+            triple.predicate.key(),
+            triple.subject.key(),
+        );
+        triples().save(&mut storage, pk.clone(), &stored).unwrap();
+        assert!(stored.is_stored(&storage).unwrap());
+
+        // the lookup doesn't depend on the secondary indexes
+        triples()
+            .idx
+            .subject_and_predicate
+            .remove(&mut storage, &pk.joined_key(), &stored)
+            .unwrap();
+        assert!(stored.is_stored(&storage).unwrap());
+
+        let other = StoredTriple {
+            object: TermRef::Blank(1),
+            ..stored.clone()
+        };
+        assert!(!other.is_stored(&storage).unwrap());
+
+        let TermRef::Term(object) = stored.object else {
+            unreachable!()
+        };
+        TERMS.remove(&mut storage, object);
+        assert!(!stored.is_stored(&storage).unwrap());
+    }
 }
@@ -1,64 +1,250 @@
 use crate::error::StoreError;
+use crate::msg::ConflictPolicy;
+use crate::rdf;
 use crate::state::{
-    triples, Literal, NamespaceBatchService, NamespaceQuerier, Node, Object, Store, Subject,
-    Triple, BLANK_NODE_IDENTIFIER_COUNTER, BLANK_NODE_SIZE, STORE,
+    expiration_key, leading_term_key, object_ref, raw_entries_after, scan_phases, triple_digest,
+    triples, Insertion, Literal, LiteralOffload, LiteralTag, LiteralTagEntry,
+    NamespaceBatchService, NamespaceQuerier, Node, Object, PhasedScan, Provenance, RecentWindow,
+    Store, StoredTriple, Subject, Subscription, TermBatchService, TermQuerier, TermStat, Triple,
+    TriplePK, BLANK_NODE_IDENTIFIER_COUNTER, BLANK_NODE_SIZE, EXPIRING_TRIPLES, LITERAL_OFFLOAD,
+    LITERAL_TAGS_NAMESPACE, OFFLOADED_OBJECTS, RAW_TRIPLES, RECENT_WINDOW, STORE, STORE_CHECKSUM,
+    SUBJECT_PROVENANCE, SUBSCRIPTIONS, TERM_STAT, TRIPLE_EXPIRATIONS, TRIPLE_INDEXES,
+    TRIPLE_INSERTIONS,
 };
 use crate::ContractError;
+use axone_objectarium_client::ObjectRef;
 use axone_rdf::normalize::IdentifierIssuer;
 use axone_rdf::serde::TripleReader;
 use axone_rdf::uri::explode_iri;
-use cosmwasm_std::{StdError, StdResult, Storage, Uint128};
+use cosmwasm_std::{Addr, Env, Event, Order, StdError, StdResult, Storage, Uint128, Uint256};
+use cw_storage_plus::KeyDeserialize;
+use cw_utils::Expiration;
 use rio_api::model;
 use rio_api::model::Term;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::BufRead;
 use std::ops::Neg;
 
 pub struct StoreEngine<'a> {
     storage: &'a mut dyn Storage,
     store: Store,
+    term_stat: TermStat,
+    checksum: Uint256,
     ns_batch_svc: NamespaceBatchService,
+    term_batch_svc: TermBatchService,
+    recent_window: Option<RecentWindow>,
+    recent_inserts: Vec<StoredTriple>,
     blank_node_id_issuer: IdentifierIssuer,
     initial_triple_count: Uint128,
     initial_byte_size: Uint128,
+    initial_blank_node_counter: u128,
+    insert_limits: bool,
+    skip_oversized: bool,
+    duplicate_count: Uint128,
+    rejected_count: Uint128,
+    block_height: u64,
+    tx_index: Option<u32>,
+    expiration: Option<(u8, u64)>,
+    /// The graph the triples of the default graph are stored in, in its N-Quads form.
+    default_graph: Option<String>,
+    changes: StoreChanges,
+    subscriptions: Vec<(u64, Subscription)>,
+    notifications: BTreeMap<u64, Vec<String>>,
+    literal_offload: Option<LiteralOffload>,
+    offloaded_literals: BTreeMap<String, String>,
+    unpinned_objects: BTreeSet<String>,
+}
+
+/// A summary of the changes made to the store, meant to be emitted as an event so they can be
+/// followed without querying the store.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct StoreChanges {
+    /// The named graphs of the changed triples, in their N-Quads form. For deleted triples, this is
+    /// the graph their subject has first been described in, if named.
+    pub graphs: BTreeSet<String>,
+    /// The named subjects of the changed triples, in their N-Triples form.
+    pub subjects: BTreeSet<String>,
+    /// The difference in the byte size of the store.
+    pub byte_delta: i128,
+}
+
+impl StoreChanges {
+    /// Make an event of the given type out of the changes, with a `graph` and a `subject` attribute
+    /// for each of the graphs and subjects changed.
+    pub fn into_event(self, ty: &str, triple_count: Uint128) -> Event {
+        Event::new(ty)
+            .add_attribute("triple_count", triple_count)
+            .add_attribute("byte_delta", self.byte_delta.to_string())
+            .add_attributes(self.graphs.into_iter().map(|g| ("graph", g)))
+            .add_attributes(self.subjects.into_iter().map(|s| ("subject", s)))
+    }
 }
 
 impl<'a> StoreEngine<'a> {
     pub fn new(storage: &'a mut dyn Storage) -> StdResult<Self> {
         let store = STORE.load(storage)?;
+        let term_stat = TERM_STAT.may_load(storage)?.unwrap_or_default();
+        let checksum = STORE_CHECKSUM.may_load(storage)?.unwrap_or_default();
         let blank_node_id_counter = BLANK_NODE_IDENTIFIER_COUNTER.load(storage)?;
         let ns_batch_svc = NamespaceBatchService::new(storage)?;
+        let recent_window = RECENT_WINDOW.may_load(storage)?;
+        let literal_offload = LITERAL_OFFLOAD.may_load(storage)?;
         Ok(Self {
             storage,
             store: store.clone(),
+            term_stat,
+            checksum,
             ns_batch_svc,
+            term_batch_svc: TermBatchService::new(),
+            recent_window,
+            recent_inserts: Vec::new(),
             blank_node_id_issuer: IdentifierIssuer::new("", blank_node_id_counter),
             initial_triple_count: store.stat.triple_count,
             initial_byte_size: store.stat.byte_size,
+            initial_blank_node_counter: blank_node_id_counter,
+            insert_limits: true,
+            skip_oversized: false,
+            duplicate_count: Uint128::zero(),
+            rejected_count: Uint128::zero(),
+            block_height: 0,
+            tx_index: None,
+            expiration: None,
+            default_graph: None,
+            changes: StoreChanges::default(),
+            subscriptions: Vec::new(),
+            notifications: BTreeMap::new(),
+            literal_offload,
+            offloaded_literals: BTreeMap::new(),
+            unpinned_objects: BTreeSet::new(),
         })
     }
 
+    /// Lift the limits on the size of a single insertion, the other store limits still applying.
+    pub fn without_insert_limits(mut self) -> Self {
+        self.insert_limits = false;
+        self
+    }
+
+    /// Skip the triples exceeding the maximum triple byte size instead of failing, see
+    /// [Self::rejected_count].
+    pub fn skipping_oversized(mut self) -> Self {
+        self.skip_oversized = true;
+        self
+    }
+
+    /// Record the block and transaction of the environment as the insertion of the stored triples.
+    pub fn at_block(mut self, env: &Env) -> Self {
+        self.block_height = env.block.height;
+        self.tx_index = env.transaction.as_ref().map(|tx| tx.index);
+        self
+    }
+
+    /// Make the stored triples expire, so they can be purged once expired, see
+    /// [expired_triples](crate::state::expired_triples). Already stored triples keep their
+    /// expiration, if any.
+    pub fn expiring(mut self, expiration: &Expiration) -> Self {
+        self.expiration = expiration_key(expiration);
+        self
+    }
+
+    /// Store the triples of the default graph in the given named graph, whose IRI has to be
+    /// absolute.
+    pub fn in_graph(mut self, graph: &str) -> StdResult<Self> {
+        // the graphs are kept in their N-Quads form
+        self.default_graph = Some(rdf::graph_name(graph)?);
+        Ok(self)
+    }
+
+    /// Match the stored triples against the patterns of the subscriptions, see
+    /// [Self::take_notifications].
+    pub fn notifying(mut self) -> StdResult<Self> {
+        self.subscriptions = SUBSCRIPTIONS
+            .range(self.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+        Ok(self)
+    }
+
+    /// Returns the stored triples, in their N-Triples form, matching the pattern of each
+    /// subscription since the engine creation or the last call, along with the subscriber.
+    pub fn take_notifications(&mut self) -> Vec<(u64, Addr, Vec<String>)> {
+        let notifications = std::mem::take(&mut self.notifications);
+        self.subscriptions
+            .iter()
+            .filter_map(|(id, subscription)| {
+                notifications
+                    .get(id)
+                    .map(|triples| (*id, subscription.subscriber.clone(), triples.clone()))
+            })
+            .collect()
+    }
+
+    /// Returns the bucket the literals are offloaded to along with the values of the literals
+    /// offloaded since the engine creation or the last call, each value being meant to be stored as
+    /// an object of the bucket, see [LiteralOffload].
+    pub fn take_offloaded_literals(&mut self) -> Option<(Addr, Vec<String>)> {
+        let values = std::mem::take(&mut self.offloaded_literals);
+        self.literal_offload
+            .as_ref()
+            .filter(|_| !values.is_empty())
+            .map(|offload| (offload.bucket.clone(), values.into_values().collect()))
+    }
+
+    /// Returns the objects of the offloaded literals no longer referenced by any stored triple since
+    /// the engine creation or the last call, each object being meant to be unpinned from its bucket.
+    pub fn take_unpinned_objects(&mut self) -> Vec<ObjectRef> {
+        std::mem::take(&mut self.unpinned_objects)
+            .iter()
+            .filter_map(|reference| object_ref(reference))
+            .collect()
+    }
+
+    /// Returns the number of triples skipped as already existing since the engine creation.
+    pub fn duplicate_count(&self) -> Uint128 {
+        self.duplicate_count
+    }
+
+    /// Returns the number of triples skipped as exceeding the maximum triple byte size since the
+    /// engine creation, see [Self::skipping_oversized].
+    pub fn rejected_count(&self) -> Uint128 {
+        self.rejected_count
+    }
+
+    /// Returns the changes made to the store since the engine creation or the last call.
+    pub fn take_changes(&mut self) -> StoreChanges {
+        std::mem::take(&mut self.changes)
+    }
+
+    /// Store all the triples read on behalf of the writer, enforcing the conflict policy of the store.
     pub fn store_all<R: BufRead>(
         &mut self,
+        writer: &Addr,
         reader: &mut TripleReader<R>,
     ) -> Result<Uint128, ContractError> {
-        reader.read_all(|t| self.store_triple(t))?;
+        reader.read_all_in_graphs(|t, graph| self.store_triple(writer, t, graph))?;
+        if let Some(window) = &mut self.recent_window {
+            window.record(self.storage, &self.recent_inserts)?;
+            self.recent_inserts.clear();
+        }
         self.finish()
     }
 
-    fn store_triple(&mut self, t: model::Triple<'_>) -> Result<(), ContractError> {
-        self.store.stat.triple_count += Uint128::one();
-        if self.store.stat.triple_count > self.store.limits.max_triple_count {
-            Err(StoreError::TripleCount(self.store.limits.max_triple_count))?;
-        }
-        if self.store.stat.triple_count - self.initial_triple_count
-            > self.store.limits.max_insert_data_triple_count
-        {
-            Err(StoreError::InsertDataTripleCount(
-                self.store.limits.max_insert_data_triple_count,
-            ))?;
-        }
-
-        let triple = Self::rio_to_triple(
+    /// Store a single triple, already existing triples, as well as oversized ones if
+    /// [Self::skipping_oversized], being skipped without consuming any of the store limits.
+    fn store_triple(
+        &mut self,
+        writer: &Addr,
+        t: model::Triple<'_>,
+        graph: Option<model::GraphName<'_>>,
+    ) -> Result<(), ContractError> {
+        let subject = t.subject;
+        let matching = self
+            .subscriptions
+            .iter()
+            .filter(|(_, subscription)| subscription.matches(&t))
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        let statement = (!matching.is_empty()).then(|| format!("{t} ."));
+        let mut triple = Self::rio_to_triple(
             t,
             &mut |ns_str| {
                 self.ns_batch_svc
@@ -67,51 +253,221 @@ impl<'a> StoreEngine<'a> {
             },
             &mut self.blank_node_id_issuer,
         )?;
-        let t_size = Uint128::from(self.triple_size(&triple).map_err(ContractError::Std)? as u128);
+        if self.insert_limits
+            && Uint128::from(self.blank_node_id_issuer.counter - self.initial_blank_node_counter)
+                > self.store.limits.max_insert_blank_node_count
+        {
+            Err(StoreError::InsertBlankNodeCount(
+                self.store.limits.max_insert_blank_node_count,
+            ))?;
+        }
+        self.normalize_literal(&t.object, &mut triple.object);
+        let offloaded = self.offload_literal(&mut triple.object)?;
+        let object_hash = triple.object.as_hash();
+        let pk = (
+            object_hash.as_bytes().as_slice(),
+            triple.predicate.key(),
+            triple.subject.key(),
+        );
+        if triples().has(self.storage, pk.clone()) {
+            self.duplicate_count += Uint128::one();
+            return Ok(());
+        }
+
+        let sizes = self.term_sizes(&triple).map_err(ContractError::Std)?;
+        let t_size = Uint128::from(sizes.iter().sum::<usize>() as u128);
         if t_size > self.store.limits.max_triple_byte_size {
+            if self.skip_oversized {
+                self.rejected_count += Uint128::one();
+                return Ok(());
+            }
             Err(StoreError::TripleByteSize(
                 t_size,
                 self.store.limits.max_triple_byte_size,
             ))?;
         }
 
+        if let Some((object_id, value)) = offloaded {
+            if let Some(reference) = Self::literal_value(&triple.object) {
+                let count = OFFLOADED_OBJECTS.may_load(self.storage, reference)?;
+                OFFLOADED_OBJECTS.save(self.storage, reference, &(count.unwrap_or(0) + 1))?;
+            }
+            self.offloaded_literals.insert(object_id, value);
+        }
+        let graph = graph
+            .map(|g| g.to_string())
+            .or_else(|| self.default_graph.clone());
+        self.check_provenance(writer, &triple.subject, graph.clone(), || {
+            subject.to_string()
+        })?;
+        if let model::Subject::NamedNode(node) = subject {
+            self.changes.subjects.insert(node.to_string());
+        }
+        self.changes.graphs.extend(graph);
+
+        self.store.stat.triple_count += Uint128::one();
+        if self.store.stat.triple_count > self.store.limits.max_triple_count {
+            Err(StoreError::TripleCount(self.store.limits.max_triple_count))?;
+        }
+        if self.insert_limits
+            && self.store.stat.triple_count - self.initial_triple_count
+                > self.store.limits.max_insert_data_triple_count
+        {
+            Err(StoreError::InsertDataTripleCount(
+                self.store.limits.max_insert_data_triple_count,
+            ))?;
+        }
+
         self.store.stat.byte_size += t_size;
         if self.store.stat.byte_size > self.store.limits.max_byte_size {
             Err(StoreError::ByteSize(self.store.limits.max_byte_size))?;
         }
-        if self.store.stat.byte_size - self.initial_byte_size
-            > self.store.limits.max_insert_data_byte_size
+        if self.insert_limits
+            && self.store.stat.byte_size - self.initial_byte_size
+                > self.store.limits.max_insert_data_byte_size
         {
             Err(StoreError::InsertDataByteSize(
                 self.store.limits.max_insert_data_byte_size,
             ))?;
         }
 
-        let mut new_ns_refs = Vec::new();
+        let stored = StoredTriple::from(&triple);
+        let referenced = self.referenced_terms(&stored);
+        self.account_terms(referenced, sizes, true);
+        self.checksum = self
+            .checksum
+            .wrapping_add(triple_digest(pk.0, &pk.1, &pk.2));
         triples()
-            .update(
-                self.storage,
-                (
-                    triple.object.as_hash().as_bytes(),
-                    triple.predicate.key(),
-                    triple.subject.key(),
-                ),
-                |maybe_triple| {
-                    if let Some(t) = maybe_triple {
-                        self.store.stat.triple_count -= Uint128::one();
-                        self.store.stat.byte_size -= t_size;
-                        Ok(t)
-                    } else {
-                        new_ns_refs.append(&mut triple.namespaces());
-                        Ok(triple)
-                    }
-                },
-            )
+            .save(self.storage, pk, &stored)
             .map_err(ContractError::Std)?;
+        TRIPLE_INSERTIONS.save(
+            self.storage,
+            &stored.key(),
+            &Insertion {
+                inserter: writer.clone(),
+                height: self.block_height,
+                tx_index: self.tx_index,
+            },
+        )?;
+        if let Some((kind, at)) = self.expiration {
+            TRIPLE_EXPIRATIONS.save(self.storage, &stored.key(), &(kind, at))?;
+            EXPIRING_TRIPLES.save(self.storage, (kind, at, &stored.key()), &stored)?;
+        }
 
-        for ns_key in new_ns_refs {
+        for ns_key in triple.namespaces() {
             self.ns_batch_svc.count_ref(self.storage, ns_key)?;
         }
+        for term in triple.terms() {
+            self.term_batch_svc.count_ref(self.storage, term)?;
+        }
+        if self.recent_window.is_some() {
+            self.recent_inserts.push(stored);
+        }
+        if let Some(statement) = statement {
+            for id in matching {
+                self.notifications
+                    .entry(id)
+                    .or_default()
+                    .push(statement.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the value of a typed literal by its canonical lexical form, if the store normalizes
+    /// the literals and its datatype has one.
+    fn normalize_literal(&self, term: &Term<'_>, object: &mut Object) {
+        if !self.store.literal_normalization {
+            return;
+        }
+        if let (
+            Term::Literal(model::Literal::Typed { value, datatype }),
+            Object::Literal(Literal::Typed { value: stored, .. }),
+        ) = (term, object)
+        {
+            if let Some(canonical) = rdf::canonical_lexical_form(datatype.iri, value) {
+                *stored = canonical;
+            }
+        }
+    }
+
+    /// Replace the value of a literal object exceeding the offload threshold by the reference to the
+    /// object it is offloaded to, returning the object id along with the original value.
+    fn offload_literal(&self, object: &mut Object) -> StdResult<Option<(String, String)>> {
+        let (Some(offload), Object::Literal(literal)) = (&self.literal_offload, object) else {
+            return Ok(None);
+        };
+        let value = match literal {
+            Literal::Simple { value }
+            | Literal::I18NString { value, .. }
+            | Literal::Typed { value, .. } => value,
+        };
+
+        Ok(offload
+            .offload(value)?
+            .map(|(reference, object_id)| (object_id, std::mem::replace(value, reference))))
+    }
+
+    fn literal_value(object: &Object) -> Option<&str> {
+        match object {
+            Object::Literal(
+                Literal::Simple { value }
+                | Literal::I18NString { value, .. }
+                | Literal::Typed { value, .. },
+            ) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Check the insertion of a triple about the subject by the writer in the graph complies with the
+    /// conflict policy of the store, recording the provenance of the subject if not described yet.
+    ///
+    /// A subject described before the provenance got tracked has stored triples but no provenance,
+    /// and is attributed to the owner of the store in the default graph, the only one there was, so
+    /// its first writer since can't claim it.
+    fn check_provenance<F>(
+        &mut self,
+        writer: &Addr,
+        subject: &Subject,
+        graph: Option<String>,
+        subject_label: F,
+    ) -> Result<(), ContractError>
+    where
+        F: Fn() -> String,
+    {
+        if let Subject::Blank(_) = subject {
+            return Ok(());
+        }
+
+        let key = subject.key();
+        let mut provenance = SUBJECT_PROVENANCE.may_load(self.storage, &key)?;
+        if provenance.is_none() && self.is_described(subject) {
+            let legacy = Provenance {
+                writer: self.store.owner.clone(),
+                graph: None,
+            };
+            SUBJECT_PROVENANCE.save(self.storage, &key, &legacy)?;
+            provenance = Some(legacy);
+        }
+        match provenance {
+            None => SUBJECT_PROVENANCE.save(
+                self.storage,
+                &key,
+                &Provenance {
+                    writer: writer.clone(),
+                    graph,
+                },
+            )?,
+            Some(provenance) => match self.store.conflict_policy {
+                ConflictPolicy::RejectDifferentWriter if provenance.writer != *writer => {
+                    Err(ContractError::SubjectWriterConflict(subject_label()))?
+                }
+                ConflictPolicy::RequireSameGraph if provenance.graph != graph => {
+                    Err(ContractError::SubjectGraphConflict(subject_label()))?
+                }
+                _ => {}
+            },
+        }
         Ok(())
     }
 
@@ -123,44 +479,254 @@ impl<'a> StoreEngine<'a> {
     }
 
     fn delete_triple(&mut self, triple: &Triple) -> Result<(), ContractError> {
+        let object_hash = triple.object.as_hash();
+        let pk = (
+            object_hash.as_bytes().as_slice(),
+            triple.predicate.key(),
+            triple.subject.key(),
+        );
         let old = triples()
-            .may_load(
-                self.storage,
-                (
-                    triple.object.as_hash().as_bytes(),
-                    triple.predicate.key(),
-                    triple.subject.key(),
-                ),
-            )
+            .may_load(self.storage, pk.clone())
             .map_err(ContractError::Std)?;
 
-        if old.is_some() {
-            triples().replace(
-                self.storage,
-                (
-                    triple.object.as_hash().as_bytes(),
-                    triple.predicate.key(),
-                    triple.subject.key(),
-                ),
-                None,
-                old.as_ref(),
-            )?;
+        if let Some(old) = old {
+            self.checksum = self
+                .checksum
+                .wrapping_sub(triple_digest(pk.0, &pk.1, &pk.2));
+            if let Subject::Named(node) = &triple.subject {
+                let ns = self
+                    .ns_batch_svc
+                    .resolve_from_key(self.storage, node.namespace)?
+                    .ok_or_else(|| StdError::not_found("Namespace"))?;
+                self.changes
+                    .subjects
+                    .insert(format!("<{}{}>", ns.value, node.value));
+                if let Some(provenance) =
+                    SUBJECT_PROVENANCE.may_load(self.storage, &triple.subject.key())?
+                {
+                    self.changes.graphs.extend(provenance.graph);
+                }
+            }
+            triples().replace(self.storage, pk, None, Some(&old))?;
+            if let Some(reference) = Self::literal_value(&triple.object) {
+                match OFFLOADED_OBJECTS.may_load(self.storage, reference)? {
+                    Some(count) if count > 1 => {
+                        OFFLOADED_OBJECTS.save(self.storage, reference, &(count - 1))?
+                    }
+                    Some(_) => {
+                        OFFLOADED_OBJECTS.remove(self.storage, reference);
+                        self.unpinned_objects.insert(reference.to_string());
+                    }
+                    None => {}
+                }
+            }
+            TRIPLE_INSERTIONS.remove(self.storage, &old.key());
+            if let Some((kind, at)) = TRIPLE_EXPIRATIONS.may_load(self.storage, &old.key())? {
+                TRIPLE_EXPIRATIONS.remove(self.storage, &old.key());
+                EXPIRING_TRIPLES.remove(self.storage, (kind, at, &old.key()));
+            }
             self.store.stat.triple_count -= Uint128::one();
-            let triple_size = self.triple_size(triple).map_err(ContractError::Std)?;
-            self.store.stat.byte_size -= Uint128::from(triple_size as u128);
+            let sizes = self.term_sizes(triple).map_err(ContractError::Std)?;
+            self.store.stat.byte_size -= Uint128::from(sizes.iter().sum::<usize>() as u128);
+            let referenced = self.referenced_terms(&old);
+            self.account_terms(referenced, sizes, false);
 
             for ns_key in triple.namespaces() {
                 self.ns_batch_svc
                     .free_ref(self.storage, ns_key)
                     .map_err(ContractError::Std)?;
             }
+            for term in triple.terms() {
+                self.term_batch_svc
+                    .free_ref(self.storage, term.id())
+                    .map_err(ContractError::Std)?;
+            }
+
+            if !referenced[0] {
+                SUBJECT_PROVENANCE.remove(self.storage, &triple.subject.key());
+            }
         }
         Ok(())
     }
 
+    /// Tell whether any stored triple is about the subject, relying on the secondary indexes.
+    fn is_described(&self, subject: &Subject) -> bool {
+        triples()
+            .idx
+            .subject_and_predicate
+            .sub_prefix(subject.key())
+            .keys_raw(self.storage, None, None, Order::Ascending)
+            .next()
+            .is_some()
+    }
+
+    /// Tell whether the subject, predicate and object of a triple are referenced by any stored
+    /// triple, relying on the secondary indexes.
+    fn referenced_terms(&self, triple: &StoredTriple) -> [bool; 3] {
+        let indexes = &triples().idx;
+        [
+            indexes
+                .subject_and_predicate
+                .sub_prefix(triple.subject.key())
+                .keys_raw(self.storage, None, None, Order::Ascending)
+                .next()
+                .is_some(),
+            indexes
+                .predicate_and_object
+                .sub_prefix(triple.predicate.to_be_bytes().to_vec())
+                .keys_raw(self.storage, None, None, Order::Ascending)
+                .next()
+                .is_some(),
+            indexes
+                .object_and_subject
+                .sub_prefix(triple.object.key())
+                .keys_raw(self.storage, None, None, Order::Ascending)
+                .next()
+                .is_some(),
+        ]
+    }
+
+    /// Account the terms of a triple inserted or removed in the [TermStat], given whether they are
+    /// referenced by other triples, and the size of each of them.
+    fn account_terms(&mut self, referenced: [bool; 3], sizes: [usize; 3], insert: bool) {
+        let stat = &mut self.term_stat;
+        for ((referenced, size), (count, byte_size)) in referenced.into_iter().zip(sizes).zip([
+            (&mut stat.subject_count, &mut stat.subject_byte_size),
+            (&mut stat.predicate_count, &mut stat.predicate_byte_size),
+            (&mut stat.object_count, &mut stat.object_byte_size),
+        ]) {
+            let size = Uint128::from(size as u128);
+            if insert {
+                *count += Uint128::from(!referenced as u8);
+                *byte_size += size;
+            } else {
+                *count -= Uint128::from(!referenced as u8);
+                *byte_size -= size;
+            }
+        }
+    }
+
+    /// Count the [TermStat] of the store and the checksum of its triples again by up to `limit`
+    /// records, resuming after the cursor returned by the previous batch, if any, both being reset
+    /// when starting.
+    ///
+    /// The entries of each secondary index are scanned in turn, giving the distinct terms at the
+    /// position the index is ordered by, the sizes of the terms and the digest of each triple being
+    /// accounted along with its entry in the subject and predicate index. The literal tags are
+    /// scanned last. The cursor is made of the phase followed by the last key scanned (see
+    /// [scan_phases]).
+    pub fn count_stats(&mut self, cursor: Option<&[u8]>, limit: usize) -> StdResult<PhasedScan> {
+        if cursor.is_none() {
+            self.term_stat = TermStat::default();
+            self.checksum = Uint256::zero();
+        }
+
+        let scan = scan_phases(
+            cursor,
+            TRIPLE_INDEXES.len() as u8 + 1,
+            limit,
+            "Invalid term count cursor",
+            |phase, after, limit| match phase as usize {
+                position if position < TRIPLE_INDEXES.len() => {
+                    self.count_index_terms(position, after, limit)
+                }
+                _ => self.count_literal_tags(after, limit),
+            },
+        )?;
+
+        TERM_STAT.save(self.storage, &self.term_stat)?;
+        STORE_CHECKSUM.save(self.storage, &self.checksum)?;
+        Ok(scan)
+    }
+
+    /// Count the distinct terms of up to `limit` entries of the secondary index at the given
+    /// position in [TRIPLE_INDEXES], after the given key, a term being counted on the first entry
+    /// ordered by it. The triples are accounted along with the entries of the first index.
+    fn count_index_terms(
+        &mut self,
+        position: usize,
+        after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> StdResult<(usize, Option<Vec<u8>>)> {
+        let namespace = TRIPLE_INDEXES[position].1;
+        let entries = raw_entries_after::<u32>(self.storage, namespace, after.clone(), limit)?;
+        let scanned = entries.len();
+        let mut querier = TermQuerier::new();
+        let mut last = after;
+        for (key, pk_len) in entries {
+            if last.as_deref().and_then(leading_term_key) != leading_term_key(&key) {
+                let count = match position {
+                    0 => &mut self.term_stat.subject_count,
+                    1 => &mut self.term_stat.predicate_count,
+                    _ => &mut self.term_stat.object_count,
+                };
+                *count += Uint128::one();
+            }
+
+            if position == 0 {
+                let pk = key
+                    .len()
+                    .checked_sub(pk_len as usize)
+                    .map(|offset| key[offset..].to_vec())
+                    .ok_or_else(|| StdError::generic_err("Invalid index entry"))?;
+                let (object, predicate, subject) =
+                    <TriplePK<'_> as KeyDeserialize>::from_vec(pk.clone())?;
+                self.checksum = self
+                    .checksum
+                    .wrapping_add(triple_digest(&object, &predicate, &subject));
+                let triple = RAW_TRIPLES
+                    .load(self.storage, pk)?
+                    .resolve(self.storage, &mut querier)?;
+                let sizes = self.term_sizes(&triple)?;
+                self.term_stat.subject_byte_size += Uint128::from(sizes[0] as u128);
+                self.term_stat.predicate_byte_size += Uint128::from(sizes[1] as u128);
+                self.term_stat.object_byte_size += Uint128::from(sizes[2] as u128);
+            }
+            last = Some(key);
+        }
+
+        Ok((scanned, last))
+    }
+
+    /// Count up to `limit` literal tags after the given key, along with their size.
+    fn count_literal_tags(
+        &mut self,
+        after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> StdResult<(usize, Option<Vec<u8>>)> {
+        let entries = raw_entries_after::<LiteralTagEntry>(
+            self.storage,
+            LITERAL_TAGS_NAMESPACE,
+            after,
+            limit,
+        )?;
+        let scanned = entries.len();
+        let mut last = None;
+        for (key, entry) in entries {
+            let size = Uint128::from(self.literal_tag_size(&entry.tag)? as u128);
+            self.term_stat.literal_tag_count += Uint128::one();
+            self.term_stat.literal_tag_byte_size += size;
+            last = Some(key);
+        }
+
+        Ok((scanned, last))
+    }
+
     /// Flushes the store to the storage.
     /// Returns the number of triples added or removed (absolute value).
     fn finish(&mut self) -> Result<Uint128, ContractError> {
+        let tags = self.term_batch_svc.flush(self.storage)?;
+        // the namespaces of the datatypes are resolved before being flushed, as possibly freed
+        for tag in &tags.added {
+            let size = Uint128::from(self.literal_tag_size(tag)? as u128);
+            self.term_stat.literal_tag_count += Uint128::one();
+            self.term_stat.literal_tag_byte_size += size;
+        }
+        for tag in &tags.removed {
+            let size = Uint128::from(self.literal_tag_size(tag)? as u128);
+            self.term_stat.literal_tag_count -= Uint128::one();
+            self.term_stat.literal_tag_byte_size -= size;
+        }
         let ns_diff = self.ns_batch_svc.flush(self.storage)?;
         if ns_diff > 0 {
             self.store.stat.namespace_count += Uint128::new(ns_diff as u128);
@@ -171,6 +737,8 @@ impl<'a> StoreEngine<'a> {
         BLANK_NODE_IDENTIFIER_COUNTER.save(self.storage, &self.blank_node_id_issuer.counter)?;
 
         STORE.save(self.storage, &self.store)?;
+        TERM_STAT.save(self.storage, &self.term_stat)?;
+        STORE_CHECKSUM.save(self.storage, &self.checksum)?;
 
         let count_diff = self
             .store
@@ -178,6 +746,8 @@ impl<'a> StoreEngine<'a> {
             .triple_count
             .abs_diff(self.initial_triple_count);
 
+        self.changes.byte_delta +=
+            self.store.stat.byte_size.u128() as i128 - self.initial_byte_size.u128() as i128;
         self.initial_triple_count = self.store.stat.triple_count;
         self.initial_byte_size = self.store.stat.byte_size;
 
@@ -212,7 +782,9 @@ impl<'a> StoreEngine<'a> {
             model::Subject::BlankNode(node) => Ok(Subject::Blank(
                 id_issuer.get_n_or_issue(node.id.to_string()),
             )),
-            model::Subject::Triple(_) => Err(StdError::generic_err("RDF star syntax unsupported")),
+            model::Subject::Triple(t) => {
+                Self::rio_to_triple(*t, ns_fn, id_issuer).map(|t| Subject::Triple(Box::new(t)))
+            }
         }
     }
 
@@ -241,7 +813,9 @@ impl<'a> StoreEngine<'a> {
             }
             Term::NamedNode(node) => Self::rio_to_node(node, ns_fn).map(Object::Named),
             Term::Literal(literal) => Self::rio_to_literal(literal, ns_fn).map(Object::Literal),
-            Term::Triple(_) => Err(StdError::generic_err("RDF star syntax unsupported")),
+            Term::Triple(t) => {
+                Self::rio_to_triple(*t, ns_fn, id_issuer).map(|t| Object::Triple(Box::new(t)))
+            }
         }
     }
 
@@ -266,16 +840,21 @@ impl<'a> StoreEngine<'a> {
         }
     }
 
-    fn triple_size(&mut self, triple: &Triple) -> StdResult<usize> {
-        Ok(self.subject_size(&triple.subject)?
-            + self.node_size(&triple.predicate)?
-            + self.object_size(&triple.object)?)
+    /// Returns the size of the subject, predicate and object of the triple, summing up to the size of
+    /// the triple.
+    fn term_sizes(&mut self, triple: &Triple) -> StdResult<[usize; 3]> {
+        Ok([
+            self.subject_size(&triple.subject)?,
+            self.node_size(&triple.predicate)?,
+            self.object_size(&triple.object)?,
+        ])
     }
 
     fn subject_size(&mut self, subject: &Subject) -> StdResult<usize> {
         match subject {
             Subject::Named(n) => self.node_size(n),
             Subject::Blank(_) => Ok(BLANK_NODE_SIZE),
+            Subject::Triple(t) => self.term_sizes(t).map(|sizes| sizes.iter().sum()),
         }
     }
 
@@ -286,6 +865,13 @@ impl<'a> StoreEngine<'a> {
             .map(|ns| ns.value.len() + node.value.len())
     }
 
+    fn literal_tag_size(&mut self, tag: &LiteralTag) -> StdResult<usize> {
+        match tag {
+            LiteralTag::Language(language) => Ok(language.len()),
+            LiteralTag::Datatype(datatype) => self.node_size(datatype),
+        }
+    }
+
     fn object_size(&mut self, object: &Object) -> StdResult<usize> {
         Ok(match object {
             Object::Blank(_) => BLANK_NODE_SIZE,
@@ -295,6 +881,7 @@ impl<'a> StoreEngine<'a> {
                 Literal::I18NString { value, language } => value.len() + language.len(),
                 Literal::Typed { value, datatype } => value.len() + self.node_size(datatype)?,
             },
+            Object::Triple(t) => self.term_sizes(t)?.iter().sum(),
         })
     }
 }
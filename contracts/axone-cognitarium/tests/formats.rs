@@ -0,0 +1,68 @@
+use axone_cognitarium::contract::{execute, instantiate, query};
+use axone_cognitarium::msg::{DataFormat, ExecuteMsg, InstantiateMsg, QueryMsg, StoreResponse};
+use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+use cosmwasm_std::{from_json, Binary};
+use testing::addr::addr;
+
+const RDF_XML: &str = r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:ex="https://ex.org/">
+  <rdf:Description rdf:about="https://ex.org/s">
+    <ex:p rdf:resource="https://ex.org/o"/>
+  </rdf:Description>
+</rdf:RDF>"#;
+const TRIPLE: &str = "<https://ex.org/s> <https://ex.org/p> <https://ex.org/o> .";
+const QUAD: &str = "<https://ex.org/s> <https://ex.org/p> <https://ex.org/o> <https://ex.org/g> .";
+
+#[test]
+fn only_enabled_formats_are_supported() {
+    let cases = [
+        (DataFormat::RDFXml, RDF_XML, cfg!(feature = "rdf-xml")),
+        (DataFormat::Turtle, TRIPLE, cfg!(feature = "turtle")),
+        (DataFormat::NTriples, TRIPLE, cfg!(feature = "n-triples")),
+        (DataFormat::NQuads, QUAD, cfg!(feature = "n-quads")),
+    ];
+
+    let mut deps = mock_dependencies();
+    let info = message_info(&addr("owner"), &[]);
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+
+    let store: StoreResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Store {}).unwrap()).unwrap();
+    assert_eq!(
+        store.formats,
+        cases
+            .iter()
+            .filter(|(_, _, enabled)| *enabled)
+            .map(|(format, _, _)| format.clone())
+            .collect::<Vec<_>>()
+    );
+
+    for (format, data, enabled) in cases {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::InsertData {
+                format: Some(format.clone()),
+                data: Binary::from(data.as_bytes()),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        );
+        assert_eq!(res.is_ok(), enabled, "format {format:?}: {res:?}");
+        if let Err(err) = res {
+            assert_eq!(
+                err.to_string(),
+                format!("Generic error: Unsupported data format: {format:?}")
+            );
+        }
+    }
+}
@@ -0,0 +1,358 @@
+//! Checks the state written by the prior versions of the contract is read, queried and migrated by
+//! the current one, so the changes to the storage layout don't break the deployed stores.
+//!
+//! Each prior version has a dump of the raw storage records of a store in `testdata/state`, named
+//! after the version. The store has been instantiated by `owner` with the default limits, and [DATA]
+//! inserted in it.
+use axone_cognitarium::contract::{execute, migrate, query};
+use axone_cognitarium::msg::{
+    DataFormat, ExecuteMsg, InsertDataReceipt, IntegrityResponse, MigrateMsg, Node, QueryMsg,
+    SelectItem, SelectQuery, SelectResponse, StatsResponse, StoreResponse, TriplePattern, Value,
+    VarOrNamedNode, VarOrNode, VarOrNodeOrLiteral, WhereClause, IRI,
+};
+use axone_cognitarium::state::STORE;
+use cosmwasm_std::testing::{
+    message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{from_json, Attribute, Binary, HexBinary, OwnedDeps, Storage, Uint128};
+use std::env;
+use std::fs;
+use std::path::Path;
+use testing::addr::addr;
+
+/// The prior versions of the contract a state dump is available for.
+const VERSIONS: &[&str] = &["6.0.0"];
+
+const DATA: &str = r#"@prefix ex: <https://ex.org/> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+ex:alice a ex:Person ;
+    ex:name "Alice" ;
+    ex:label "Alice"@en , "Alicia"@es ;
+    ex:age "42"^^xsd:integer ;
+    ex:knows ex:bob , [ ex:name "Anonymous" ] .
+ex:bob a ex:Person ;
+    ex:name "Bob" ;
+    ex:birthDate "1990-01-01"^^xsd:date .
+"#;
+
+fn load_state(version: &str) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let dump = fs::read(
+        Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("testdata")
+            .join("state")
+            .join(format!("{version}.json")),
+    )
+    .unwrap();
+
+    let mut deps = mock_dependencies();
+    for (key, value) in serde_json::from_slice::<Vec<(HexBinary, Binary)>>(&dump).unwrap() {
+        deps.storage.set(&key, &value);
+    }
+    deps
+}
+
+fn select(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    subject: VarOrNode,
+    predicate: VarOrNamedNode,
+) -> Vec<Value> {
+    let res: SelectResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Select {
+                query: SelectQuery {
+                    prefixes: vec![],
+                    select: vec![SelectItem::Variable("o".to_string())],
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![TriplePattern {
+                            subject,
+                            predicate,
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        }],
+                    },
+                    limit: None,
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
+                },
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    res.results
+        .bindings
+        .into_iter()
+        .filter_map(|mut b| b.remove("o"))
+        .collect()
+}
+
+fn iri(value: &str) -> IRI {
+    IRI::Full(format!("https://ex.org/{value}"))
+}
+
+fn literal(value: &str, lang: Option<&str>, datatype: Option<&str>) -> Value {
+    Value::Literal {
+        value: value.to_string(),
+        lang: lang.map(str::to_string),
+        datatype: datatype.map(|d| IRI::Full(format!("http://www.w3.org/2001/XMLSchema#{d}"))),
+    }
+}
+
+#[test]
+fn migrate_prior_states() {
+    for version in VERSIONS {
+        let mut deps = load_state(version);
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+        assert!(
+            res.attributes
+                .contains(&Attribute::new("from_version", *version)),
+            "{version}"
+        );
+        let mut attributes = res.attributes;
+        while !attributes.contains(&Attribute::new("completed", "true")) {
+            attributes = execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr("anyone"), &[]),
+                ExecuteMsg::MigrateTriples { limit: None },
+            )
+            .unwrap()
+            .attributes;
+        }
+
+        // the statistics about the terms are counted from the migrated triples
+        let stats: StatsResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Stats {}).unwrap()).unwrap();
+        assert_eq!(
+            (
+                stats.subject_count,
+                stats.predicate_count,
+                stats.object_count,
+                stats.literal_tag_count,
+            ),
+            (
+                Uint128::new(3),
+                Uint128::new(6),
+                Uint128::new(10),
+                Uint128::new(4),
+            ),
+            "{version}"
+        );
+
+        // the index entries of the triples stored in full are replaced along with them
+        let mut cursor = None;
+        loop {
+            let res: IntegrityResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::CheckIntegrity {
+                        cursor: cursor.take(),
+                        limit: None,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(res.faults, vec![], "{version}");
+            match res.cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        let store: StoreResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Store {}).unwrap()).unwrap();
+        assert_eq!(store.owner, addr("owner").to_string(), "{version}");
+        assert_eq!(store.stat.triple_count, Uint128::new(11), "{version}");
+        assert_eq!(store.stat.namespace_count, Uint128::new(3), "{version}");
+
+        let alice = select(
+            &deps,
+            VarOrNode::Node(Node::NamedNode(iri("alice"))),
+            VarOrNamedNode::Variable("p".to_string()),
+        );
+        assert_eq!(alice.len(), 7, "{version}");
+        for value in [
+            Value::URI { value: iri("bob") },
+            literal("Alice", None, None),
+            literal("Alicia", Some("es"), None),
+            literal("42", None, Some("integer")),
+        ] {
+            assert!(alice.contains(&value), "{version}: {value:?}");
+        }
+        assert_eq!(
+            select(
+                &deps,
+                VarOrNode::Variable("s".to_string()),
+                VarOrNamedNode::NamedNode(iri("name")),
+            )
+            .len(),
+            3,
+            "{version}"
+        );
+
+        // the stored triples are found again, except the ones of the blank node, made distinct
+        let info = message_info(&addr("owner"), &[]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::InsertData {
+                format: Some(DataFormat::Turtle),
+                data: Binary::from(DATA.as_bytes()),
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            from_json::<InsertDataReceipt>(res.data.unwrap()).unwrap(),
+            InsertDataReceipt {
+                inserted_count: Uint128::new(2),
+                duplicate_count: Uint128::new(9),
+                rejected_count: Uint128::zero(),
+            },
+            "{version}"
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::DeleteData {
+                prefixes: vec![],
+                delete: vec![],
+                r#where: Some(WhereClause::Bgp {
+                    patterns: vec![TriplePattern {
+                        subject: VarOrNode::Variable("s".to_string()),
+                        predicate: VarOrNamedNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                }),
+            },
+        )
+        .unwrap();
+        let store: StoreResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Store {}).unwrap()).unwrap();
+        assert_eq!(store.stat.triple_count, Uint128::zero(), "{version}");
+        assert_eq!(store.stat.namespace_count, Uint128::zero(), "{version}");
+        assert_eq!(store.stat.byte_size, Uint128::zero(), "{version}");
+    }
+}
+
+#[test]
+fn migrate_prior_states_in_batches() {
+    for version in VERSIONS {
+        let mut deps = load_state(version);
+        let set_query_limit = |storage: &mut dyn Storage, limit| {
+            STORE
+                .update(storage, |mut store| -> cosmwasm_std::StdResult<_> {
+                    store.limits.max_query_limit = limit;
+                    Ok(store)
+                })
+                .unwrap();
+        };
+        set_query_limit(&mut deps.storage, 4);
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+        assert!(
+            res.attributes
+                .contains(&Attribute::new("interned_triple_count", "4")),
+            "{version}"
+        );
+        assert!(
+            res.attributes
+                .contains(&Attribute::new("completed", "false")),
+            "{version}"
+        );
+
+        let info = message_info(&addr("owner"), &[]);
+        let insert = ExecuteMsg::InsertData {
+            format: Some(DataFormat::Turtle),
+            data: Binary::from(DATA.as_bytes()),
+            compression: None,
+            expires: None,
+            graph: None,
+            skip_oversized: false,
+        };
+        assert_eq!(
+            execute(deps.as_mut(), mock_env(), info.clone(), insert.clone())
+                .unwrap_err()
+                .to_string(),
+            "A migration of the stored triples is in progress.",
+            "{version}"
+        );
+
+        let mut interned = 4;
+        loop {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr("anyone"), &[]),
+                ExecuteMsg::MigrateTriples { limit: None },
+            )
+            .unwrap();
+            let attribute = |key: &str| {
+                res.attributes
+                    .iter()
+                    .find(|a| a.key == key)
+                    .map(|a| a.value.clone())
+                    .unwrap()
+            };
+            interned += attribute("interned_triple_count").parse::<usize>().unwrap();
+            if attribute("completed") == "true" {
+                break;
+            }
+        }
+        assert_eq!(interned, 11, "{version}");
+        set_query_limit(&mut deps.storage, 30);
+
+        let store: StoreResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Store {}).unwrap()).unwrap();
+        assert_eq!(store.stat.triple_count, Uint128::new(11), "{version}");
+        assert_eq!(
+            select(
+                &deps,
+                VarOrNode::Node(Node::NamedNode(iri("alice"))),
+                VarOrNamedNode::Variable("p".to_string()),
+            )
+            .len(),
+            7,
+            "{version}"
+        );
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ExecuteMsg::MigrateTriples { limit: None },
+            )
+            .unwrap_err()
+            .to_string(),
+            "Generic error: No triple migration in progress",
+            "{version}"
+        );
+
+        let res = execute(deps.as_mut(), mock_env(), info, insert).unwrap();
+        assert_eq!(
+            from_json::<InsertDataReceipt>(res.data.unwrap()).unwrap(),
+            InsertDataReceipt {
+                inserted_count: Uint128::new(2),
+                duplicate_count: Uint128::new(9),
+                rejected_count: Uint128::zero(),
+            },
+            "{version}"
+        );
+    }
+}
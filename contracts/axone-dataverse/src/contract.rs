@@ -4,11 +4,11 @@ use cosmwasm_std::{
     instantiate2_address, to_json_binary, Binary, CodeInfoResponse, Deps, DepsMut, Env,
     MessageInfo, Response, StdError, StdResult, WasmMsg,
 };
-use cw2::set_contract_version;
+use cw2::{ensure_from_older_version, set_contract_version};
 use cw_utils::nonpayable;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::state::{Dataverse, DATAVERSE};
 
 // version info for migration info
@@ -59,6 +59,12 @@ pub fn instantiate(
             label: format!("{}_triplestore", msg.name),
             msg: to_json_binary(&axone_cognitarium::msg::InstantiateMsg {
                 limits: msg.triplestore_config.limits.into(),
+                recent_window: None,
+                query_audit: None,
+                conflict_policy: Default::default(),
+                literal_offload: None,
+                disable_literal_normalization: false,
+                soft_limit: None,
             })?,
             funds: vec![],
             salt,
@@ -77,249 +83,2594 @@ pub fn execute(
         ExecuteMsg::SubmitClaims { claims, format: _ } => {
             execute::submit_claims(deps, env, info, claims)
         }
+        ExecuteMsg::RegisterProofVerifier {
+            proof_type,
+            verifier,
+        } => execute::register_proof_verifier(deps, env, info, proof_type, verifier),
+        ExecuteMsg::UnregisterProofVerifier { proof_type } => {
+            execute::unregister_proof_verifier(deps, env, info, proof_type)
+        }
+        ExecuteMsg::RegisterPeer { address } => execute::register_peer(deps, env, info, address),
+        ExecuteMsg::UnregisterPeer { address } => {
+            execute::unregister_peer(deps, env, info, address)
+        }
+        ExecuteMsg::SetGovernance { law_stone } => {
+            execute::set_governance(deps, env, info, law_stone)
+        }
+        ExecuteMsg::Tick { limit } => execute::tick(deps, env, limit),
+        ExecuteMsg::RebuildIndexes { limit } => execute::rebuild_indexes(deps, env, limit),
+        ExecuteMsg::RegisterGateway { address } => {
+            execute::register_gateway(deps, env, info, address)
+        }
+        ExecuteMsg::UnregisterGateway { address } => {
+            execute::unregister_gateway(deps, env, info, address)
+        }
+        ExecuteMsg::RecordAccess { credential_id } => {
+            execute::record_access(deps, info, credential_id)
+        }
+        ExecuteMsg::RevokeAllByIssuer {
+            did,
+            revocation,
+            limit,
+        } => execute::revoke_all_by_issuer(deps, env, did, revocation, limit),
+        ExecuteMsg::SetGuardian { guardian, delay } => {
+            execute::set_guardian(deps, env, info, guardian, delay)
+        }
+        ExecuteMsg::QueueOverride { action } => execute::queue_override(deps, env, info, action),
+        ExecuteMsg::CancelOverride { id } => execute::cancel_override(deps, env, info, id),
+        ExecuteMsg::ExecuteOverride { id } => execute::execute_override(deps, env, id),
+        ExecuteMsg::SetAnchoring { anchoring } => {
+            execute::set_anchoring(deps, env, info, anchoring)
+        }
+        ExecuteMsg::OpenHolderSession { holder_key } => {
+            execute::open_holder_session(deps, env, info, holder_key)
+        }
+        ExecuteMsg::RetrieveHolderClaims {
+            session,
+            signature,
+            first,
+            after,
+        } => execute::retrieve_holder_claims(deps, env, info, session, signature, first, after),
         _ => Err(StdError::generic_err("Not implemented").into()),
     }
 }
 
 pub mod execute {
     use super::*;
+    use crate::credential::error::VerificationError;
     use crate::credential::vc::VerifiableCredential;
-    use crate::registrar::credential::DataverseCredential;
+    use crate::msg::{
+        Anchoring, ClaimSummary, ClaimsResponse, HolderSessionReceipt, OverrideAction,
+        SubmitClaimsReceipt,
+    };
+    use crate::registrar::credential::{DataverseCredential, Submitter};
+    use crate::registrar::emergency::OverrideRecorder;
     use crate::registrar::registry::ClaimRegistrar;
+    use crate::state::{
+        Anchor, BoundClaim, ClaimOperation, ExpiringCredential, Guardian, HolderSession,
+        IndexRebuild, IssuedCredential, IssuerRevocation, Override, StateDigest, ACCESS_COUNTS,
+        ADDRESS_HOLDERS, ANCHORING, ANCHORS, EXPIRING_CREDENTIALS, GATEWAYS, GOVERNANCE, GUARDIAN,
+        HOLDER_BINDINGS, HOLDER_CLAIMS, HOLDER_SESSIONS, HOLDER_SESSION_SEQUENCE, INDEX_REBUILD,
+        ISSUER_CREDENTIALS, ISSUER_REVOCATIONS, OVERRIDES, OVERRIDE_SEQUENCE, PAUSED, PEERS,
+        PROOF_VERIFIERS, STATE_DIGEST, SUBMISSION_SEQUENCE,
+    };
+    use axone_cognitarium_client::parse_date_time;
     use axone_rdf::dataset::Dataset;
     use axone_rdf::serde::NQuadsReader;
+    use cosmwasm_std::{Addr, Empty, Event, HexBinary, Order, Storage, Timestamp, Uint64};
+    use cw_storage_plus::{Bound, PrefixBound};
+    use rio_api::model::{Literal, Term};
+    use sha2::{Digest, Sha256};
     use std::io::BufReader;
 
+    /// The period, in seconds, before their expiration the credentials are notified by
+    /// [tick].
+    const EXPIRY_NOTICE_PERIOD: u64 = 7 * 24 * 60 * 60;
+    const DEFAULT_TICK_LIMIT: u32 = 10;
+    const MAX_TICK_LIMIT: u32 = 30;
+    const DEFAULT_REVOCATION_LIMIT: u32 = 10;
+    const MAX_REVOCATION_LIMIT: u32 = 30;
+    const MAX_ANCHOR_RETENTION: u32 = 100;
+    /// The minimum delay, in seconds, the emergency overrides are timelocked for, so the
+    /// governance always has a chance to cancel them.
+    const MIN_OVERRIDE_DELAY: u64 = 60 * 60;
+    /// The period, in seconds, the holder sessions remain open for.
+    const HOLDER_SESSION_TTL: u64 = 5 * 60;
+    /// The maximum number of expired holder sessions pruned on each session opening.
+    const MAX_HOLDER_SESSION_PRUNING: usize = 10;
+    const DEFAULT_HOLDER_CLAIMS_PAGE_SIZE: u32 = 10;
+    const MAX_HOLDER_CLAIMS_PAGE_SIZE: u32 = 30;
+
+    /// The type of the credentials through which an issuer revokes all its credentials, see
+    /// [revoke_all_by_issuer].
+    pub const ISSUER_REVOCATION_CREDENTIAL: &str = "https://w3id.org/axone/ontology/vnext/schema/credential/issuer-revocation/IssuerRevocationCredential";
+
+    /// The type of the credentials through which a holder links the submitting address to its DID,
+    /// see [link_address].
+    pub const ADDRESS_LINKAGE_CREDENTIAL: &str = "https://w3id.org/axone/ontology/vnext/schema/credential/address-linkage/AddressLinkageCredential";
+
+    /// The property of the address linkage credentials giving the linked address.
+    pub const ADDRESS_LINKAGE_ADDRESS: &str =
+        "https://w3id.org/axone/ontology/vnext/schema/credential/address-linkage/hasAddress";
+
+    /// The property of the holder-bound claims giving the hex-encoded public key of their holder,
+    /// see [open_holder_session].
+    pub const HOLDER_BINDING_KEY: &str =
+        "https://w3id.org/axone/ontology/vnext/schema/credential/holder-binding/holderKey";
+
     pub fn submit_claims(
-        deps: DepsMut<'_>,
+        mut deps: DepsMut<'_>,
         env: Env,
         info: MessageInfo,
         claims: Binary,
     ) -> Result<Response, ContractError> {
+        if INDEX_REBUILD.exists(deps.storage) {
+            Err(ContractError::IndexRebuildInProgress)?;
+        }
+        if PAUSED.exists(deps.storage) {
+            Err(ContractError::Paused)?;
+        }
+
         let buf = BufReader::new(claims.as_slice());
         let mut reader = NQuadsReader::new(buf);
         let rdf_quads = reader.read_all()?;
         let vc_dataset = Dataset::from(rdf_quads.as_slice());
         let vc = VerifiableCredential::try_from(&vc_dataset)?;
-        vc.verify(&deps)?;
+        verify_credential(&deps, &vc)?;
 
-        let credential = DataverseCredential::try_from((env, info, &vc))?;
+        let submitted_at = env.block.time.seconds();
+        let mut sender = Submitter::try_new(deps.as_ref(), info.sender.as_str())?;
+        let linked = vc.types.contains(&ADDRESS_LINKAGE_CREDENTIAL);
+        if linked {
+            link_address(&mut deps, &vc, &mut sender)?;
+        }
+        let credential = DataverseCredential::try_from((env, sender, &vc))?;
+        let holder_key = bound_holder_key(&credential)?;
         let registrar = ClaimRegistrar::try_new(deps.storage)?;
+        let msg = registrar.submit_claim(&deps, &credential)?;
 
-        Ok(Response::default()
+        let expires_at = credential.expires_at()?;
+        if let (Some(expires_at), Some(valid_until)) = (expires_at, credential.valid_until) {
+            EXPIRING_CREDENTIALS.save(
+                deps.storage,
+                (expires_at, credential.id),
+                &ExpiringCredential {
+                    issuer: credential.issuer.to_string(),
+                    subject: credential.claim.id.to_string(),
+                    valid_until: valid_until.to_string(),
+                },
+            )?;
+        }
+
+        let sequence = SUBMISSION_SEQUENCE
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            + 1;
+        SUBMISSION_SEQUENCE.save(deps.storage, &sequence)?;
+
+        let canonical_hash = vc.canonical_hash()?;
+        ISSUER_CREDENTIALS.save(
+            deps.storage,
+            (credential.issuer, credential.id),
+            &IssuedCredential {
+                canonical_hash: canonical_hash.clone(),
+                submitted_at,
+                expires_at,
+            },
+        )?;
+        if let Some(holder_key) = holder_key {
+            HOLDER_BINDINGS.save(deps.storage, credential.id, &holder_key)?;
+            HOLDER_CLAIMS.save(
+                deps.storage,
+                (&holder_key, credential.id),
+                &BoundClaim {
+                    subject: credential.claim.id.to_string(),
+                    r#type: credential.r#type.to_string(),
+                    issuer: credential.issuer.to_string(),
+                },
+            )?;
+        }
+
+        let digest = STATE_DIGEST
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            .chain(ClaimOperation::Submission, credential.id, &canonical_hash);
+        STATE_DIGEST.save(deps.storage, &digest)?;
+        let anchor = anchor_state(deps.storage, sequence, &digest, submitted_at)?;
+
+        let receipt = SubmitClaimsReceipt {
+            credential: credential.id.to_string(),
+            canonical_hash: HexBinary::from(canonical_hash),
+            sequence: Uint64::new(sequence),
+            triplestore_address: DATAVERSE.load(deps.storage)?.triplestore_address,
+        };
+
+        let mut resp = Response::default()
             .add_attribute("action", "submit_claims")
             .add_attribute("credential", credential.id)
             .add_attribute("subject", credential.claim.id)
             .add_attribute("type", credential.r#type)
-            .add_message(registrar.submit_claim(&deps, &credential)?))
+            .add_message(msg)
+            .set_data(to_json_binary(&receipt)?);
+        if linked {
+            resp = resp.add_event(
+                Event::new("address_linked")
+                    .add_attribute("address", credential.sender.address.as_str())
+                    .add_attribute("holder", credential.issuer),
+            );
+        }
+        if let Some(anchor) = anchor {
+            resp = resp.add_event(anchor);
+        }
+
+        Ok(resp)
     }
-}
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps<'_>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Dataverse {} => to_json_binary(&query::dataverse(deps)?),
+    /// Stores an anchor of the state digest if the accepted submission completes an interval of
+    /// the anchoring, the anchors past its retention being removed, returning the event notifying
+    /// it.
+    fn anchor_state(
+        storage: &mut dyn Storage,
+        submission_sequence: u64,
+        digest: &StateDigest,
+        anchored_at: u64,
+    ) -> StdResult<Option<Event>> {
+        let Some(anchoring) = ANCHORING.may_load(storage)? else {
+            return Ok(None);
+        };
+        if submission_sequence % u64::from(anchoring.interval) != 0 {
+            return Ok(None);
+        }
+
+        let from_sequence = ANCHORS
+            .last(storage)?
+            .map_or(1, |(to_sequence, _)| to_sequence + 1);
+        ANCHORS.save(
+            storage,
+            digest.sequence,
+            &Anchor {
+                from_sequence,
+                digest: digest.digest.clone(),
+                anchored_at,
+            },
+        )?;
+
+        let expired = ANCHORS
+            .keys(storage, None, None, Order::Descending)
+            .skip(anchoring.retention as usize)
+            .collect::<StdResult<Vec<_>>>()?;
+        for to_sequence in expired {
+            ANCHORS.remove(storage, to_sequence);
+        }
+
+        Ok(Some(
+            Event::new("anchor")
+                .add_attribute("from_sequence", from_sequence.to_string())
+                .add_attribute("to_sequence", digest.sequence.to_string())
+                .add_attribute("digest", HexBinary::from(digest.digest.as_slice()).to_hex()),
+        ))
     }
-}
 
-pub mod query {
-    use crate::msg::DataverseResponse;
-    use crate::state::DATAVERSE;
-    use cosmwasm_std::{Deps, StdResult};
+    /// Links the submitter address to the holder DID of an address linkage credential, the
+    /// credential being expected to be issued by the holder about itself and to claim the submitter
+    /// address, so both the holder and the address owner consent to the link.
+    ///
+    /// The addresses being linked by their canonical form, the submissions of the account are
+    /// attributed to the holder whatever the bech32 prefix of the address they're made with.
+    pub fn link_address(
+        deps: &mut DepsMut<'_>,
+        vc: &VerifiableCredential<'_>,
+        submitter: &mut Submitter,
+    ) -> Result<(), ContractError> {
+        let [claim] = vc.claims.as_slice() else {
+            Err(ContractError::InvalidAddressLinkage(
+                "credential is expected to contain exactly one claim".to_string(),
+            ))?
+        };
+        if claim.id != vc.issuer {
+            Err(ContractError::InvalidAddressLinkage(
+                "credential is expected to be issued by the linked holder".to_string(),
+            ))?;
+        }
 
-    pub fn dataverse(deps: Deps<'_>) -> StdResult<DataverseResponse> {
-        DATAVERSE.load(deps.storage).map(|d| DataverseResponse {
-            name: d.name,
-            triplestore_address: d.triplestore_address,
-        })
+        let account = claim
+            .content
+            .iter()
+            .find_map(|quad| match quad.object {
+                Term::Literal(Literal::Simple { value })
+                    if quad.predicate.iri == ADDRESS_LINKAGE_ADDRESS =>
+                {
+                    deps.api.addr_canonicalize(value).ok()
+                }
+                _ => None,
+            })
+            .filter(|account| {
+                deps.api
+                    .addr_canonicalize(submitter.address.as_str())
+                    .is_ok_and(|sender| sender == *account)
+            })
+            .ok_or_else(|| {
+                ContractError::InvalidAddressLinkage(
+                    "credential is expected to claim the submitter address".to_string(),
+                )
+            })?;
+
+        ADDRESS_HOLDERS.save(deps.storage, account.as_slice(), &vc.issuer.to_string())?;
+        submitter.holder = Some(vc.issuer.to_string());
+
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::msg::{
-        DataverseResponse, RdfDatasetFormat, TripleStoreConfig, TripleStoreLimitsInput,
-    };
-    use crate::testutil::testutil::read_test_data;
-    use axone_cognitarium::msg::{
-        DataFormat, Head, Node, Results, SelectItem, SelectQuery, SelectResponse, TriplePattern,
-        Value, VarOrNamedNode, VarOrNode, VarOrNodeOrLiteral, WhereClause, IRI,
-    };
-    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
-    use cosmwasm_std::{
-        coins, from_json, Addr, Attribute, Checksum, ContractResult, CosmosMsg, SubMsg,
-        SystemError, SystemResult, Uint128, Uint64, WasmQuery,
-    };
-    use cw_utils::PaymentError::NonPayable;
-    use std::collections::BTreeMap;
-    use testing::addr::{addr, CREATOR, SENDER};
-    use testing::mock::mock_env_addr;
+    /// Returns the public key of the holder the claim of the credential is bound to, if any, as
+    /// declared through the [HOLDER_BINDING_KEY] property.
+    fn bound_holder_key(
+        credential: &DataverseCredential<'_>,
+    ) -> Result<Option<Vec<u8>>, ContractError> {
+        credential
+            .claim
+            .content
+            .iter()
+            .find_map(|quad| match quad.object {
+                Term::Literal(Literal::Simple { value })
+                    if quad.predicate.iri == HOLDER_BINDING_KEY =>
+                {
+                    Some(value)
+                }
+                _ => None,
+            })
+            .map(|value| {
+                let key = HexBinary::from_hex(value).map_err(|_| {
+                    ContractError::InvalidHolderKey("key is expected to be hex encoded".to_string())
+                })?;
+                check_holder_key(&key)?;
+                Ok(key.to_vec())
+            })
+            .transpose()
+    }
 
-    #[test]
-    fn proper_instantiate() {
-        let mut deps = mock_dependencies();
-        deps.querier.update_wasm(|query| match query {
-            WasmQuery::CodeInfo { code_id, .. } => {
-                let resp = CodeInfoResponse::new(
-                    code_id.clone(),
-                    addr(CREATOR),
-                    Checksum::from_hex(
-                        "3B94AAF0B7D804B5B458DED0D20CACF95D2A1C8DF78ED3C89B61291760454AEC",
-                    )
-                    .unwrap(),
-                );
-                SystemResult::Ok(ContractResult::Ok(to_json_binary(&resp).unwrap()))
-            }
-            _ => SystemResult::Err(SystemError::Unknown {}),
-        });
+    /// Ensures the holder key is either an Ed25519 public key or a compressed secp256k1 one.
+    fn check_holder_key(key: &[u8]) -> Result<(), ContractError> {
+        match (key.len(), key.first()) {
+            (32, _) | (33, Some(0x02 | 0x03)) => Ok(()),
+            _ => Err(ContractError::InvalidHolderKey(
+                "key is expected to be a 32 bytes Ed25519 key or a compressed secp256k1 key"
+                    .to_string(),
+            )),
+        }
+    }
 
-        let store_limits = TripleStoreLimitsInput {
-            max_byte_size: Some(Uint128::from(50000u128)),
-            ..Default::default()
-        };
+    pub fn register_proof_verifier(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        proof_type: String,
+        verifier: String,
+    ) -> Result<Response, ContractError> {
+        ensure_admin(deps.as_ref(), &env, &info)?;
 
-        let msg = InstantiateMsg {
-            name: "my-dataverse".to_string(),
-            triplestore_config: TripleStoreConfig {
-                code_id: Uint64::from(17u64),
-                limits: store_limits.clone(),
-            },
-        };
+        let verifier = deps.api.addr_validate(&verifier)?;
+        PROOF_VERIFIERS.save(deps.storage, &proof_type, &verifier)?;
 
-        let env = mock_env_addr();
-        let info = message_info(&addr(CREATOR), &[]);
-        let res = instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+        Ok(Response::default()
+            .add_attribute("action", "register_proof_verifier")
+            .add_attribute("proof_type", proof_type)
+            .add_attribute("verifier", verifier))
+    }
 
-        assert_eq!(
-            res.attributes,
-            vec![Attribute::new("triplestore_address", "predicted address")]
-        );
-        assert_eq!(
-            res.messages,
-            vec![SubMsg::new(WasmMsg::Instantiate2 {
-                admin: Some(env.contract.address.to_string()),
-                code_id: 17,
-                label: "my-dataverse_triplestore".to_string(),
-                msg: to_json_binary(&axone_cognitarium::msg::InstantiateMsg {
-                    limits: store_limits.into(),
-                })
-                .unwrap(),
-                funds: vec![],
-                salt: Binary::from("my-dataverse".as_bytes()),
-            })]
-        );
-        assert_eq!(
-            DATAVERSE.load(&deps.storage).unwrap(),
-            Dataverse {
-                name: "my-dataverse".to_string(),
-                triplestore_address: Addr::unchecked("predicted address"),
-            }
-        )
+    pub fn unregister_proof_verifier(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        proof_type: String,
+    ) -> Result<Response, ContractError> {
+        ensure_admin(deps.as_ref(), &env, &info)?;
+
+        if !PROOF_VERIFIERS.has(deps.storage, &proof_type) {
+            Err(StdError::not_found(format!(
+                "proof verifier for '{proof_type}'"
+            )))?;
+        }
+        PROOF_VERIFIERS.remove(deps.storage, &proof_type);
+
+        Ok(Response::default()
+            .add_attribute("action", "unregister_proof_verifier")
+            .add_attribute("proof_type", proof_type))
     }
 
-    #[test]
-    fn funds_initialization() {
-        let mut deps = mock_dependencies();
-        let env = mock_env();
-        let info = message_info(&addr(SENDER), &coins(10, "uaxone"));
+    pub fn register_peer(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        ensure_admin(deps.as_ref(), &env, &info)?;
 
-        let msg = InstantiateMsg {
-            name: "my-dataverse".to_string(),
-            triplestore_config: TripleStoreConfig {
-                code_id: Uint64::from(17u64),
-                limits: TripleStoreLimitsInput::default(),
-            },
-        };
+        let address = deps.api.addr_validate(&address)?;
+        if address == env.contract.address {
+            Err(StdError::generic_err("A dataverse cannot be its own peer"))?;
+        }
+        PEERS.save(deps.storage, &address, &Empty {})?;
 
-        let result = instantiate(deps.as_mut(), env, info, msg);
-        assert!(result.is_err());
-        assert!(matches!(
-            result.unwrap_err(),
-            ContractError::Payment(NonPayable {})
-        ));
+        Ok(Response::default()
+            .add_attribute("action", "register_peer")
+            .add_attribute("address", address))
     }
 
-    #[test]
-    fn proper_dataverse() {
-        let mut deps = mock_dependencies();
+    pub fn unregister_peer(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        ensure_admin(deps.as_ref(), &env, &info)?;
 
-        DATAVERSE
-            .save(
-                deps.as_mut().storage,
-                &Dataverse {
-                    name: "my-dataverse".to_string(),
-                    triplestore_address: Addr::unchecked("my-dataverse-addr"),
-                },
-            )
-            .unwrap();
+        let address = deps.api.addr_validate(&address)?;
+        if !PEERS.has(deps.storage, &address) {
+            Err(StdError::not_found(format!("peer '{address}'")))?;
+        }
+        PEERS.remove(deps.storage, &address);
 
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::Dataverse {});
-        assert!(res.is_ok());
-        let res: StdResult<DataverseResponse> = from_json(res.unwrap());
-        assert!(res.is_ok());
-        assert_eq!(
-            res.unwrap(),
-            DataverseResponse {
-                name: "my-dataverse".to_string(),
-                triplestore_address: Addr::unchecked("my-dataverse-addr"),
-            }
-        );
+        Ok(Response::default()
+            .add_attribute("action", "unregister_peer")
+            .add_attribute("address", address))
     }
 
-    #[test]
-    fn execute_fail_with_funds() {
-        let mut deps = mock_dependencies();
-        let env = mock_env();
-        let info = message_info(&addr(SENDER), &coins(10, "uaxone"));
+    pub fn register_gateway(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        ensure_admin(deps.as_ref(), &env, &info)?;
 
-        let msg = ExecuteMsg::SubmitClaims {
-            claims: Binary::from("data".as_bytes()),
-            format: Some(RdfDatasetFormat::NQuads),
-        };
+        let address = deps.api.addr_validate(&address)?;
+        GATEWAYS.save(deps.storage, &address, &Empty {})?;
 
-        let result = execute(deps.as_mut(), env, info, msg);
-        assert!(result.is_err());
-        assert!(matches!(
-            result.unwrap_err(),
-            ContractError::Payment(NonPayable {})
-        ));
+        Ok(Response::default()
+            .add_attribute("action", "register_gateway")
+            .add_attribute("address", address))
     }
 
-    #[test]
-    fn proper_submit_claims() {
-        let mut deps = mock_dependencies();
-        deps.querier.update_wasm(|query| match query {
-            WasmQuery::Smart { contract_addr, msg } => {
-                if contract_addr != "my-dataverse-addr" {
-                    return SystemResult::Err(SystemError::NoSuchContract {
-                        addr: contract_addr.to_string(),
-                    });
-                }
-                let query_msg: StdResult<axone_cognitarium::msg::QueryMsg> = from_json(msg);
-                assert_eq!(
-                    query_msg,
-                    Ok(axone_cognitarium::msg::QueryMsg::Select {
-                        query: SelectQuery {
-                            prefixes: vec![],
-                            limit: Some(1u32),
-                            select: vec![SelectItem::Variable("p".to_string())],
-                            r#where: WhereClause::Bgp {
-                                patterns: vec![TriplePattern {
-                                    subject: VarOrNode::Node(Node::NamedNode(IRI::Full(
-                                        "http://example.edu/credentials/3732".to_string(),
-                                    ))),
-                                    predicate: VarOrNamedNode::Variable("p".to_string()),
-                                    object: VarOrNodeOrLiteral::Variable("o".to_string()),
-                                }]
-                            },
-                        }
-                    })
-                );
+    pub fn unregister_gateway(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        ensure_admin(deps.as_ref(), &env, &info)?;
+
+        let address = deps.api.addr_validate(&address)?;
+        if !GATEWAYS.has(deps.storage, &address) {
+            Err(StdError::not_found(format!("gateway '{address}'")))?;
+        }
+        GATEWAYS.remove(deps.storage, &address);
+
+        Ok(Response::default()
+            .add_attribute("action", "unregister_gateway")
+            .add_attribute("address", address))
+    }
+
+    pub fn record_access(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        credential_id: String,
+    ) -> Result<Response, ContractError> {
+        if !GATEWAYS.has(deps.storage, &info.sender) {
+            Err(ContractError::Unauthorized)?;
+        }
+        // fails if the credential is not registered
+        ClaimRegistrar::try_new(deps.storage)?.credential_parties(deps.querier, &credential_id)?;
+
+        let count = ACCESS_COUNTS
+            .may_load(deps.storage, &credential_id)?
+            .unwrap_or_default()
+            + 1;
+        ACCESS_COUNTS.save(deps.storage, &credential_id, &count)?;
+
+        Ok(Response::default()
+            .add_attribute("action", "record_access")
+            .add_attribute("credential", credential_id)
+            .add_attribute("access_count", count.to_string()))
+    }
+
+    pub fn set_governance(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        law_stone: Option<String>,
+    ) -> Result<Response, ContractError> {
+        ensure_admin(deps.as_ref(), &env, &info)?;
+
+        let resp = Response::default().add_attribute("action", "set_governance");
+        match law_stone {
+            Some(law_stone) => {
+                let law_stone = deps.api.addr_validate(&law_stone)?;
+                GOVERNANCE.save(deps.storage, &law_stone)?;
+                Ok(resp.add_attribute("law_stone", law_stone))
+            }
+            None => {
+                GOVERNANCE.remove(deps.storage);
+                Ok(resp)
+            }
+        }
+    }
+
+    pub fn tick(
+        deps: DepsMut<'_>,
+        env: Env,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let limit = limit.unwrap_or(DEFAULT_TICK_LIMIT).min(MAX_TICK_LIMIT) as usize;
+        let horizon = env
+            .block
+            .time
+            .seconds()
+            .saturating_add(EXPIRY_NOTICE_PERIOD);
+        let expiring = EXPIRING_CREDENTIALS
+            .prefix_range(
+                deps.storage,
+                None,
+                Some(PrefixBound::inclusive(horizon)),
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut resp = Response::default()
+            .add_attribute("action", "tick")
+            .add_attribute("notified_count", expiring.len().to_string());
+        for ((expires_at, id), credential) in expiring {
+            EXPIRING_CREDENTIALS.remove(deps.storage, (expires_at, &id));
+            resp = resp.add_event(
+                Event::new("credential_expiring_soon")
+                    .add_attribute("credential", id)
+                    .add_attribute("issuer", credential.issuer)
+                    .add_attribute("subject", credential.subject)
+                    .add_attribute("valid_until", credential.valid_until),
+            );
+        }
+
+        Ok(resp)
+    }
+
+    pub fn revoke_all_by_issuer(
+        deps: DepsMut<'_>,
+        env: Env,
+        did: String,
+        revocation: Binary,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let buf = BufReader::new(revocation.as_slice());
+        let rdf_quads = NQuadsReader::new(buf).read_all()?;
+        let vc_dataset = Dataset::from(rdf_quads.as_slice());
+        let vc = VerifiableCredential::try_from(&vc_dataset)?;
+
+        if vc.issuer != did {
+            Err(ContractError::InvalidRevocation(
+                "credential is expected to be issued by the revoking issuer".to_string(),
+            ))?;
+        }
+        if !vc.types.contains(&ISSUER_REVOCATION_CREDENTIAL) {
+            Err(ContractError::InvalidRevocation(format!(
+                "credential is expected to be of type '{ISSUER_REVOCATION_CREDENTIAL}'"
+            )))?;
+        }
+        if vc.claims.is_empty() || vc.claims.iter().any(|claim| claim.id != did) {
+            Err(ContractError::InvalidRevocation(
+                "credential is expected to be about the revoking issuer".to_string(),
+            ))?;
+        }
+        let issued_at = parse_date_time(vc.issuance_date).ok_or_else(|| {
+            ContractError::InvalidRevocation(format!(
+                "credential issuance date '{}' is not a valid date time",
+                vc.issuance_date
+            ))
+        })?;
+        if issued_at > env.block.time.seconds() {
+            Err(ContractError::InvalidRevocation(
+                "credential is expected to be issued in the past".to_string(),
+            ))?;
+        }
+        let cursor = match ISSUER_REVOCATIONS.may_load(deps.storage, vc.id)? {
+            Some(IssuerRevocation { cursor: None }) => Err(ContractError::InvalidRevocation(
+                "credential has already been applied".to_string(),
+            ))?,
+            Some(IssuerRevocation { cursor }) => cursor,
+            None => None,
+        };
+        verify_credential(&deps, &vc)?;
+
+        let limit = limit
+            .unwrap_or(DEFAULT_REVOCATION_LIMIT)
+            .min(MAX_REVOCATION_LIMIT) as usize;
+        let mut scanned = ISSUER_CREDENTIALS
+            .prefix(&did)
+            .range(
+                deps.storage,
+                cursor.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit + 1)
+            .collect::<StdResult<Vec<_>>>()?;
+        let completed = scanned.len() <= limit;
+        scanned.truncate(limit);
+        ISSUER_REVOCATIONS.save(
+            deps.storage,
+            vc.id,
+            &IssuerRevocation {
+                cursor: scanned
+                    .last()
+                    .filter(|_| !completed)
+                    .map(|(id, _)| id.clone()),
+            },
+        )?;
+        let covered = scanned
+            .into_iter()
+            .filter(|(_, credential)| credential.submitted_at <= issued_at)
+            .collect::<Vec<_>>();
+
+        let registrar = ClaimRegistrar::try_new(deps.storage)?;
+        let mut digest = STATE_DIGEST.may_load(deps.storage)?.unwrap_or_default();
+        let mut resp = Response::default()
+            .add_attribute("action", "revoke_all_by_issuer")
+            .add_attribute("issuer", &did)
+            .add_attribute("revoked_count", covered.len().to_string())
+            .add_attribute("completed", completed.to_string());
+        for (id, credential) in covered {
+            digest = unindex_credential(deps.storage, digest, &did, &id, &credential)?;
+
+            resp = resp
+                .add_messages(registrar.revoke_claim(&id)?)
+                .add_event(Event::new("credential_revoked").add_attribute("credential", id));
+        }
+        STATE_DIGEST.save(deps.storage, &digest)?;
+
+        Ok(resp)
+    }
+
+    /// Removes a revoked credential from the secondary indexes, returning the state digest with its
+    /// revocation chained.
+    fn unindex_credential(
+        storage: &mut dyn Storage,
+        digest: StateDigest,
+        issuer: &str,
+        id: &str,
+        credential: &IssuedCredential,
+    ) -> StdResult<StateDigest> {
+        ISSUER_CREDENTIALS.remove(storage, (issuer, id));
+        if let Some(expires_at) = credential.expires_at {
+            EXPIRING_CREDENTIALS.remove(storage, (expires_at, id));
+        }
+        ACCESS_COUNTS.remove(storage, id);
+        if let Some(holder_key) = HOLDER_BINDINGS.may_load(storage, id)? {
+            HOLDER_BINDINGS.remove(storage, id);
+            HOLDER_CLAIMS.remove(storage, (&holder_key, id));
+        }
+        Ok(digest.chain(ClaimOperation::Revocation, id, &credential.canonical_hash))
+    }
+
+    pub fn set_guardian(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        guardian: Option<String>,
+        delay: Uint64,
+    ) -> Result<Response, ContractError> {
+        ensure_admin(deps.as_ref(), &env, &info)?;
+
+        let resp = Response::default().add_attribute("action", "set_guardian");
+        match guardian {
+            Some(guardian) => {
+                let address = deps.api.addr_validate(&guardian)?;
+                if delay.u64() < MIN_OVERRIDE_DELAY {
+                    Err(ContractError::InvalidGuardian(format!(
+                        "delay must be at least {MIN_OVERRIDE_DELAY} seconds"
+                    )))?;
+                }
+                GUARDIAN.save(
+                    deps.storage,
+                    &Guardian {
+                        address: address.clone(),
+                        delay: delay.u64(),
+                    },
+                )?;
+                Ok(resp
+                    .add_attribute("guardian", address)
+                    .add_attribute("delay", delay))
+            }
+            None => {
+                GUARDIAN.remove(deps.storage);
+                Ok(resp)
+            }
+        }
+    }
+
+    pub fn set_anchoring(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        anchoring: Option<Anchoring>,
+    ) -> Result<Response, ContractError> {
+        ensure_admin(deps.as_ref(), &env, &info)?;
+
+        let resp = Response::default().add_attribute("action", "set_anchoring");
+        match anchoring {
+            Some(anchoring) => {
+                if anchoring.interval == 0 {
+                    Err(ContractError::InvalidAnchoring(
+                        "interval must be positive".to_string(),
+                    ))?;
+                }
+                if !(1..=MAX_ANCHOR_RETENTION).contains(&anchoring.retention) {
+                    Err(ContractError::InvalidAnchoring(format!(
+                        "retention must be between 1 and {MAX_ANCHOR_RETENTION}"
+                    )))?;
+                }
+                ANCHORING.save(deps.storage, &anchoring)?;
+                Ok(resp
+                    .add_attribute("interval", anchoring.interval.to_string())
+                    .add_attribute("retention", anchoring.retention.to_string()))
+            }
+            None => {
+                ANCHORING.remove(deps.storage);
+                Ok(resp)
+            }
+        }
+    }
+
+    pub fn queue_override(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        action: OverrideAction,
+    ) -> Result<Response, ContractError> {
+        let guardian = GUARDIAN
+            .may_load(deps.storage)?
+            .filter(|guardian| guardian.address == info.sender)
+            .ok_or(ContractError::Unauthorized)?;
+        if let OverrideAction::SetGovernance {
+            law_stone: Some(law_stone),
+        } = &action
+        {
+            deps.api.addr_validate(law_stone)?;
+        }
+
+        let id = OVERRIDE_SEQUENCE
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            + 1;
+        OVERRIDE_SEQUENCE.save(deps.storage, &id)?;
+        let queued_at = env.block.time.seconds();
+        let r#override = Override {
+            action,
+            guardian: guardian.address,
+            queued_at,
+            executable_at: queued_at.saturating_add(guardian.delay),
+        };
+        OVERRIDES.save(deps.storage, id, &r#override)?;
+
+        Ok(Response::default()
+            .add_attribute("action", "queue_override")
+            .add_attribute("id", id.to_string())
+            .add_message(OverrideRecorder::try_new(deps.storage)?.record_queued(id, &r#override)?)
+            .add_event(
+                Event::new("override_queued")
+                    .add_attribute("id", id.to_string())
+                    .add_attribute("action", r#override.action.name())
+                    .add_attribute("executable_at", r#override.executable_at.to_string()),
+            ))
+    }
+
+    pub fn cancel_override(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        id: Uint64,
+    ) -> Result<Response, ContractError> {
+        ensure_admin(deps.as_ref(), &env, &info)?;
+        let id = id.u64();
+        if !OVERRIDES.has(deps.storage, id) {
+            Err(StdError::not_found(format!("override '{id}'")))?;
+        }
+        OVERRIDES.remove(deps.storage, id);
+
+        Ok(Response::default()
+            .add_attribute("action", "cancel_override")
+            .add_attribute("id", id.to_string())
+            .add_messages(OverrideRecorder::try_new(deps.storage)?.record_status(id, "cancelled")?)
+            .add_event(Event::new("override_cancelled").add_attribute("id", id.to_string())))
+    }
+
+    pub fn execute_override(
+        deps: DepsMut<'_>,
+        env: Env,
+        id: Uint64,
+    ) -> Result<Response, ContractError> {
+        let id = id.u64();
+        let r#override = OVERRIDES
+            .may_load(deps.storage, id)?
+            .ok_or_else(|| StdError::not_found(format!("override '{id}'")))?;
+        if env.block.time.seconds() < r#override.executable_at {
+            Err(ContractError::OverrideTimelocked(
+                id,
+                r#override.executable_at,
+            ))?;
+        }
+        // the overrides queued by a removed or replaced guardian are disowned along with it
+        if GUARDIAN
+            .may_load(deps.storage)?
+            .map_or(true, |guardian| guardian.address != r#override.guardian)
+        {
+            Err(ContractError::OverrideGuardianRevoked(id))?;
+        }
+        OVERRIDES.remove(deps.storage, id);
+
+        let mut resp = Response::default()
+            .add_attribute("action", "execute_override")
+            .add_attribute("id", id.to_string());
+        match &r#override.action {
+            OverrideAction::Pause {} => PAUSED.save(deps.storage, &Empty {})?,
+            OverrideAction::Resume {} => PAUSED.remove(deps.storage),
+            OverrideAction::RevokeClaims { identifier } => {
+                let registrar = ClaimRegistrar::try_new(deps.storage)?;
+                let (issuer, _) = registrar.credential_parties(deps.querier, identifier)?;
+                // the credentials submitted before the issuer index was introduced aren't indexed
+                // until the indexes are rebuilt, their revocation not being chained in the state
+                // digest meanwhile
+                if let Some(credential) =
+                    ISSUER_CREDENTIALS.may_load(deps.storage, (&issuer, identifier))?
+                {
+                    let digest = STATE_DIGEST.may_load(deps.storage)?.unwrap_or_default();
+                    let digest =
+                        unindex_credential(deps.storage, digest, &issuer, identifier, &credential)?;
+                    STATE_DIGEST.save(deps.storage, &digest)?;
+                }
+                resp = resp
+                    .add_messages(registrar.revoke_claim(identifier)?)
+                    .add_event(
+                        Event::new("credential_revoked").add_attribute("credential", identifier),
+                    );
+            }
+            OverrideAction::SetGovernance { law_stone } => match law_stone {
+                Some(law_stone) => GOVERNANCE.save(deps.storage, &Addr::unchecked(law_stone))?,
+                None => GOVERNANCE.remove(deps.storage),
+            },
+        }
+
+        Ok(resp
+            .add_messages(OverrideRecorder::try_new(deps.storage)?.record_status(id, "executed")?)
+            .add_event(
+                Event::new("override_executed")
+                    .add_attribute("id", id.to_string())
+                    .add_attribute("action", r#override.action.name()),
+            ))
+    }
+
+    /// Opens a session for the holder of the key to retrieve its bound claims, the nonce of the
+    /// session being the SHA-256 of the contract address, the big-endian session identifier, block
+    /// height and block time in nanoseconds, and the holder key.
+    pub fn open_holder_session(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        holder_key: HexBinary,
+    ) -> Result<Response, ContractError> {
+        check_holder_key(&holder_key)?;
+
+        let now = env.block.time.seconds();
+        // the sessions all living for the same period, the first ones are the first to expire
+        let expired = HOLDER_SESSIONS
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(MAX_HOLDER_SESSION_PRUNING)
+            .filter(|res| {
+                res.as_ref()
+                    .map_or(true, |(_, session)| session.expires_at <= now)
+            })
+            .map(|res| res.map(|(id, _)| id))
+            .collect::<StdResult<Vec<_>>>()?;
+        for id in expired {
+            HOLDER_SESSIONS.remove(deps.storage, id);
+        }
+
+        let id = HOLDER_SESSION_SEQUENCE
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            + 1;
+        HOLDER_SESSION_SEQUENCE.save(deps.storage, &id)?;
+
+        let nonce = Sha256::new()
+            .chain_update(env.contract.address.as_bytes())
+            .chain_update(id.to_be_bytes())
+            .chain_update(env.block.height.to_be_bytes())
+            .chain_update(env.block.time.nanos().to_be_bytes())
+            .chain_update(holder_key.as_slice())
+            .finalize()
+            .to_vec();
+        let expires_at = now + HOLDER_SESSION_TTL;
+        HOLDER_SESSIONS.save(
+            deps.storage,
+            id,
+            &HolderSession {
+                opener: info.sender,
+                holder_key: holder_key.to_vec(),
+                nonce: nonce.clone(),
+                expires_at,
+            },
+        )?;
+
+        let nonce = HexBinary::from(nonce);
+        Ok(Response::default()
+            .add_attribute("action", "open_holder_session")
+            .add_attribute("session", id.to_string())
+            .add_attribute("nonce", nonce.to_hex())
+            .set_data(to_json_binary(&HolderSessionReceipt {
+                session: Uint64::new(id),
+                nonce,
+                expires_at: Timestamp::from_seconds(expires_at),
+            })?))
+    }
+
+    /// Lists a page of the claims bound to the holder of the session given the signature of its
+    /// nonce along with the address of the sender, closing the session once the last page listed
+    /// so the signature can't be presented again.
+    pub fn retrieve_holder_claims(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        session: Uint64,
+        signature: HexBinary,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> Result<Response, ContractError> {
+        let id = session.u64();
+        let session = HOLDER_SESSIONS
+            .may_load(deps.storage, id)?
+            .filter(|session| env.block.time.seconds() < session.expires_at)
+            .ok_or_else(|| {
+                StdError::generic_err(format!("Holder session '{id}' not found or expired"))
+            })?;
+        if info.sender != session.opener {
+            Err(ContractError::Unauthorized)?;
+        }
+
+        let message = Sha256::new()
+            .chain_update(&session.nonce)
+            .chain_update(info.sender.as_bytes())
+            .finalize();
+        let verified = match session.holder_key.len() {
+            32 => deps
+                .api
+                .ed25519_verify(&message, &signature, &session.holder_key),
+            _ => deps
+                .api
+                .secp256k1_verify(&message, &signature, &session.holder_key),
+        };
+        if !verified.unwrap_or(false) {
+            Err(StdError::generic_err("Invalid holder signature"))?;
+        }
+
+        let first = first
+            .unwrap_or(DEFAULT_HOLDER_CLAIMS_PAGE_SIZE)
+            .min(MAX_HOLDER_CLAIMS_PAGE_SIZE) as usize;
+        let mut claims = HOLDER_CLAIMS
+            .prefix(&session.holder_key)
+            .range(
+                deps.storage,
+                after.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(first + 1)
+            .map(|res| {
+                res.map(|(credential, claim)| ClaimSummary {
+                    credential,
+                    subject: claim.subject,
+                    r#type: claim.r#type,
+                    issuer: claim.issuer,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        let completed = claims.len() <= first;
+        claims.truncate(first);
+        if completed {
+            HOLDER_SESSIONS.remove(deps.storage, id);
+        }
+
+        Ok(Response::default()
+            .add_attribute("action", "retrieve_holder_claims")
+            .add_attribute("session", id.to_string())
+            .add_attribute("claim_count", claims.len().to_string())
+            .add_attribute("completed", completed.to_string())
+            .set_data(to_json_binary(&ClaimsResponse {
+                claims,
+                truncated: false,
+            })?))
+    }
+
+    pub fn rebuild_indexes(
+        deps: DepsMut<'_>,
+        env: Env,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let (indexed, completed) = rebuild_indexes_batch(deps, &env, limit)?;
+
+        Ok(Response::default()
+            .add_attribute("action", "rebuild_indexes")
+            .add_attribute("indexed_credential_count", indexed.to_string())
+            .add_attribute("completed", completed.to_string()))
+    }
+
+    /// Scans the next batch of triples of the index rebuild in progress, returning the number of
+    /// credentials indexed and whether the rebuild is completed.
+    ///
+    /// The credentials already expired are not indexed for their notification, which would be
+    /// pointless and may have been performed before the rebuild. The credentials already indexed by
+    /// issuer are kept as is, the canonical hash of the other ones being unknown as it can't be
+    /// recovered from the triple store.
+    pub fn rebuild_indexes_batch(
+        deps: DepsMut<'_>,
+        env: &Env,
+        limit: Option<u32>,
+    ) -> Result<(u64, bool), ContractError> {
+        let rebuild = INDEX_REBUILD
+            .may_load(deps.storage)?
+            .ok_or_else(|| StdError::generic_err("No index rebuild in progress"))?;
+        let registrar = ClaimRegistrar::try_new(deps.storage)?;
+        let page = registrar.scan_credentials(deps.querier, rebuild.cursor, limit)?;
+
+        let indexed = page.credentials.len() as u64;
+        let sequence = SUBMISSION_SEQUENCE
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            + indexed;
+        SUBMISSION_SEQUENCE.save(deps.storage, &sequence)?;
+
+        let mut digest = STATE_DIGEST.may_load(deps.storage)?.unwrap_or_default();
+        for id in &page.credentials {
+            let credential = registrar.registered_credential(deps.querier, id)?;
+            let expires_at = credential.valid_until.as_deref().and_then(parse_date_time);
+            if let (Some(expires_at), Some(valid_until)) = (expires_at, &credential.valid_until) {
+                if expires_at > env.block.time.seconds() {
+                    EXPIRING_CREDENTIALS.save(
+                        deps.storage,
+                        (expires_at, id),
+                        &ExpiringCredential {
+                            issuer: credential.issuer.clone(),
+                            subject: credential.subject.clone(),
+                            valid_until: valid_until.clone(),
+                        },
+                    )?;
+                }
+            }
+
+            let issued =
+                match ISSUER_CREDENTIALS.may_load(deps.storage, (&credential.issuer, id))? {
+                    Some(issued) => issued,
+                    None => {
+                        let issued = IssuedCredential {
+                            canonical_hash: vec![],
+                            submitted_at: credential.submitted_at,
+                            expires_at,
+                        };
+                        ISSUER_CREDENTIALS.save(deps.storage, (&credential.issuer, id), &issued)?;
+                        issued
+                    }
+                };
+            if rebuild.chain_digest {
+                digest = digest.chain(ClaimOperation::Submission, id, &issued.canonical_hash);
+            }
+
+            let holder_key = registrar
+                .claim_literal(deps.querier, id, HOLDER_BINDING_KEY)?
+                .and_then(|key| HexBinary::from_hex(&key).ok());
+            if let Some(holder_key) = holder_key {
+                HOLDER_BINDINGS.save(deps.storage, id, &holder_key.to_vec())?;
+                HOLDER_CLAIMS.save(
+                    deps.storage,
+                    (holder_key.as_slice(), id),
+                    &BoundClaim {
+                        subject: credential.subject,
+                        r#type: credential.r#type,
+                        issuer: credential.issuer,
+                    },
+                )?;
+            }
+        }
+        if rebuild.chain_digest {
+            STATE_DIGEST.save(deps.storage, &digest)?;
+        }
+
+        let completed = page.cursor.is_none();
+        match page.cursor {
+            Some(cursor) => INDEX_REBUILD.save(
+                deps.storage,
+                &IndexRebuild {
+                    cursor: Some(cursor),
+                    ..rebuild
+                },
+            )?,
+            None => INDEX_REBUILD.remove(deps.storage),
+        }
+
+        Ok((indexed, completed))
+    }
+
+    /// Verifies the credential through its natively supported proofs, falling back on the external
+    /// verifier registered for the type of its other proofs if none is suitable.
+    fn verify_credential(
+        deps: &DepsMut<'_>,
+        vc: &VerifiableCredential<'_>,
+    ) -> Result<(), ContractError> {
+        match vc.verify(deps) {
+            Err(VerificationError::NoSuitableProof) => {
+                for proof in vc.external_proofs() {
+                    if let Some(verifier) = PROOF_VERIFIERS.may_load(deps.storage, proof.r#type)? {
+                        return Ok(vc.verify_external(deps, proof, &verifier)?);
+                    }
+                }
+                Err(VerificationError::NoSuitableProof)?
+            }
+            res => Ok(res?),
+        }
+    }
+
+    /// Ensures the sender is the admin of the contract, which is expected to be the governance.
+    fn ensure_admin(deps: Deps<'_>, env: &Env, info: &MessageInfo) -> Result<(), ContractError> {
+        let admin = deps
+            .querier
+            .query_wasm_contract_info(&env.contract.address)?
+            .admin;
+        if admin.as_ref() != Some(&info.sender) {
+            Err(ContractError::Unauthorized)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps<'_>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Dataverse {} => to_json_binary(&query::dataverse(deps)?),
+        QueryMsg::CompareClaims {
+            credential_a,
+            credential_b,
+        } => to_json_binary(&query::compare_claims(deps, credential_a, credential_b)?),
+        QueryMsg::StateDigest {} => to_json_binary(&query::state_digest(deps)?),
+        QueryMsg::Anchors {} => to_json_binary(&query::anchors(deps)?),
+        QueryMsg::ProofVerifiers {} => to_json_binary(&query::proof_verifiers(deps)?),
+        QueryMsg::Claims { filter } => to_json_binary(&query::claims(deps, filter)?),
+        QueryMsg::FederatedClaims { filter, max_peers } => {
+            to_json_binary(&query::federated_claims(deps, env, filter, max_peers)?)
+        }
+        QueryMsg::Peers {} => to_json_binary(&query::peers(deps)?),
+        QueryMsg::AccessCount { credential_id } => {
+            to_json_binary(&query::access_count(deps, credential_id)?)
+        }
+        QueryMsg::Gateways {} => to_json_binary(&query::gateways(deps)?),
+        QueryMsg::ClaimsByIssuer { did, first, after } => {
+            to_json_binary(&query::claims_by_issuer(deps, did, first, after)?)
+        }
+        QueryMsg::AddressHolder { address } => {
+            to_json_binary(&query::address_holder(deps, address)?)
+        }
+        QueryMsg::ClaimTemplate { r#type } => to_json_binary(&query::claim_template(r#type)?),
+        QueryMsg::Emergency { after, limit } => {
+            to_json_binary(&query::emergency(deps, after, limit)?)
+        }
+    }
+}
+
+pub mod query {
+    use crate::msg::{
+        AccessCountResponse, AddressHolderResponse, Anchor, AnchorsResponse, ClaimTemplateProperty,
+        ClaimTemplateResponse, ClaimsByIssuerResponse, ClaimsFilter, ClaimsResponse,
+        CompareClaimsResponse, DataverseResponse, EmergencyResponse, FederatedClaim,
+        FederatedClaimsResponse, GatewaysResponse, IssuedClaim, PeersResponse, ProofVerifier,
+        ProofVerifiersResponse, QueryMsg, QueuedOverride, StateDigestResponse,
+    };
+    use crate::registrar::policy::PolicyEvaluator;
+    use crate::registrar::registry::ClaimRegistrar;
+    use crate::registrar::template::{ClaimShape, VC_CONTEXT};
+    use crate::state::{
+        ACCESS_COUNTS, ADDRESS_HOLDERS, ANCHORING, ANCHORS, DATAVERSE, GATEWAYS, GOVERNANCE,
+        GUARDIAN, HOLDER_BINDINGS, ISSUER_CREDENTIALS, OVERRIDES, PAUSED, PEERS, PROOF_VERIFIERS,
+        STATE_DIGEST,
+    };
+    use cosmwasm_std::{Addr, Deps, Env, HexBinary, Order, StdError, StdResult, Timestamp, Uint64};
+    use cw_storage_plus::Bound;
+
+    const DEFAULT_PAGE_SIZE: u32 = 10;
+    const MAX_PAGE_SIZE: u32 = 30;
+    const DEFAULT_FEDERATED_PEERS: u32 = 10;
+    const MAX_FEDERATED_PEERS: u32 = 30;
+
+    pub fn dataverse(deps: Deps<'_>) -> StdResult<DataverseResponse> {
+        let governance = GOVERNANCE.may_load(deps.storage)?;
+        DATAVERSE.load(deps.storage).map(|d| DataverseResponse {
+            name: d.name,
+            triplestore_address: d.triplestore_address,
+            governance,
+        })
+    }
+
+    pub fn compare_claims(
+        deps: Deps<'_>,
+        credential_a: String,
+        credential_b: String,
+    ) -> StdResult<CompareClaimsResponse> {
+        ClaimRegistrar::try_new(deps.storage)?.compare_claims(
+            deps.querier,
+            &credential_a,
+            &credential_b,
+        )
+    }
+
+    pub fn state_digest(deps: Deps<'_>) -> StdResult<StateDigestResponse> {
+        STATE_DIGEST
+            .may_load(deps.storage)
+            .map(|digest| digest.unwrap_or_default().into())
+    }
+
+    pub fn anchors(deps: Deps<'_>) -> StdResult<AnchorsResponse> {
+        let anchors = ANCHORS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|res| {
+                res.map(|(to_sequence, anchor)| Anchor {
+                    from_sequence: Uint64::new(anchor.from_sequence),
+                    to_sequence: Uint64::new(to_sequence),
+                    digest: HexBinary::from(anchor.digest),
+                    anchored_at: Timestamp::from_seconds(anchor.anchored_at),
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(AnchorsResponse {
+            anchoring: ANCHORING.may_load(deps.storage)?,
+            anchors,
+        })
+    }
+
+    pub fn proof_verifiers(deps: Deps<'_>) -> StdResult<ProofVerifiersResponse> {
+        PROOF_VERIFIERS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|res| {
+                res.map(|(proof_type, verifier)| ProofVerifier {
+                    proof_type,
+                    verifier,
+                })
+            })
+            .collect::<StdResult<_>>()
+            .map(|verifiers| ProofVerifiersResponse { verifiers })
+    }
+
+    pub fn claims(deps: Deps<'_>, filter: ClaimsFilter) -> StdResult<ClaimsResponse> {
+        let registrar = ClaimRegistrar::try_new(deps.storage)?;
+        let purpose = filter.purpose.clone();
+        // the holder-bound claims are filtered out of the listing, being listed to their holder
+        // instead, see [execute::retrieve_holder_claims], though their triples are public in the
+        // triple store
+        let selection = registrar.claims(deps.querier, filter)?;
+        let mut claims = selection
+            .claims
+            .into_iter()
+            .filter(|claim| !HOLDER_BINDINGS.has(deps.storage, &claim.credential))
+            .collect::<Vec<_>>();
+
+        if let Some(purpose) = purpose {
+            let mut evaluator = PolicyEvaluator::new(
+                deps.querier,
+                &registrar,
+                GOVERNANCE.may_load(deps.storage)?,
+                purpose,
+            );
+            let mut allowed = Vec::with_capacity(claims.len());
+            for claim in claims {
+                if evaluator.allows(&claim.subject)? {
+                    allowed.push(claim);
+                }
+            }
+            claims = allowed;
+        }
+
+        Ok(ClaimsResponse {
+            claims,
+            truncated: selection.truncated,
+        })
+    }
+
+    pub fn federated_claims(
+        deps: Deps<'_>,
+        env: Env,
+        filter: ClaimsFilter,
+        max_peers: Option<u32>,
+    ) -> StdResult<FederatedClaimsResponse> {
+        let annotate = |origin: &Addr, resp: ClaimsResponse| -> Vec<FederatedClaim> {
+            resp.claims
+                .into_iter()
+                .map(|claim| FederatedClaim {
+                    origin: origin.clone(),
+                    claim,
+                })
+                .collect()
+        };
+
+        let local = claims(deps, filter.clone())?;
+        let mut resp = FederatedClaimsResponse {
+            truncated: local.truncated,
+            claims: annotate(&env.contract.address, local),
+            unreachable_peers: vec![],
+        };
+
+        let peers = PEERS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .take(
+                max_peers
+                    .unwrap_or(DEFAULT_FEDERATED_PEERS)
+                    .min(MAX_FEDERATED_PEERS) as usize,
+            )
+            .collect::<StdResult<Vec<_>>>()?;
+        for peer in peers {
+            match deps.querier.query_wasm_smart::<ClaimsResponse>(
+                &peer,
+                &QueryMsg::Claims {
+                    filter: filter.clone(),
+                },
+            ) {
+                Ok(peer_resp) => {
+                    resp.truncated |= peer_resp.truncated;
+                    resp.claims.extend(annotate(&peer, peer_resp));
+                }
+                Err(_) => resp.unreachable_peers.push(peer),
+            }
+        }
+
+        Ok(resp)
+    }
+
+    pub fn peers(deps: Deps<'_>) -> StdResult<PeersResponse> {
+        PEERS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()
+            .map(|peers| PeersResponse { peers })
+    }
+
+    pub fn access_count(deps: Deps<'_>, credential_id: String) -> StdResult<AccessCountResponse> {
+        let count = ACCESS_COUNTS
+            .may_load(deps.storage, &credential_id)?
+            .unwrap_or_default();
+        Ok(AccessCountResponse {
+            credential: credential_id,
+            count: Uint64::new(count),
+        })
+    }
+
+    pub fn gateways(deps: Deps<'_>) -> StdResult<GatewaysResponse> {
+        GATEWAYS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()
+            .map(|gateways| GatewaysResponse { gateways })
+    }
+
+    pub fn claims_by_issuer(
+        deps: Deps<'_>,
+        did: String,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> StdResult<ClaimsByIssuerResponse> {
+        let first = first.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE) as usize;
+        ISSUER_CREDENTIALS
+            .prefix(&did)
+            .range(
+                deps.storage,
+                after.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(first)
+            .map(|res| {
+                res.map(|(credential, issued)| IssuedClaim {
+                    credential,
+                    canonical_hash: HexBinary::from(issued.canonical_hash),
+                    submitted_at: Timestamp::from_seconds(issued.submitted_at),
+                })
+            })
+            .collect::<StdResult<_>>()
+            .map(|claims| ClaimsByIssuerResponse { claims })
+    }
+
+    pub fn address_holder(deps: Deps<'_>, address: String) -> StdResult<AddressHolderResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let account = deps.api.addr_canonicalize(address.as_str())?;
+
+        Ok(AddressHolderResponse {
+            holder: ADDRESS_HOLDERS.may_load(deps.storage, account.as_slice())?,
+        })
+    }
+
+    pub fn claim_template(r#type: String) -> StdResult<ClaimTemplateResponse> {
+        let shape = ClaimShape::find(&r#type).ok_or_else(|| {
+            StdError::generic_err(format!("Unsupported credential type: {}", r#type))
+        })?;
+
+        Ok(ClaimTemplateResponse {
+            r#type,
+            context: vec![VC_CONTEXT.to_string(), shape.vocabulary().to_string()],
+            properties: shape
+                .properties
+                .iter()
+                .map(|property| ClaimTemplateProperty {
+                    predicate: property.predicate.to_string(),
+                    datatype: property.datatype.map(str::to_string),
+                    required: property.required,
+                })
+                .collect(),
+            document: shape.json_ld_skeleton(),
+        })
+    }
+
+    pub fn emergency(
+        deps: Deps<'_>,
+        after: Option<Uint64>,
+        limit: Option<u32>,
+    ) -> StdResult<EmergencyResponse> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE) as usize;
+        let guardian = GUARDIAN.may_load(deps.storage)?;
+        let overrides = OVERRIDES
+            .range(
+                deps.storage,
+                after.map(|after| Bound::exclusive(after.u64())),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|res| {
+                res.map(|(id, r#override)| QueuedOverride {
+                    id: Uint64::new(id),
+                    action: r#override.action,
+                    guardian: r#override.guardian,
+                    queued_at: Timestamp::from_seconds(r#override.queued_at),
+                    executable_at: Timestamp::from_seconds(r#override.executable_at),
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(EmergencyResponse {
+            delay: Uint64::new(guardian.as_ref().map_or(0, |guardian| guardian.delay)),
+            guardian: guardian.map(|guardian| guardian.address),
+            paused: PAUSED.exists(deps.storage),
+            overrides,
+        })
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut<'_>, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let mut resp = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", previous.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION);
+    if msg.rebuild_indexes {
+        let (indexed, completed) = migrate::start_index_rebuild(deps, &env)?;
+        resp = resp
+            .add_attribute("indexed_credential_count", indexed.to_string())
+            .add_attribute("completed", completed.to_string());
+    }
+
+    Ok(resp)
+}
+
+pub mod migrate {
+    use super::*;
+    use crate::state::{
+        IndexRebuild, EXPIRING_CREDENTIALS, INDEX_REBUILD, STATE_DIGEST, SUBMISSION_SEQUENCE,
+    };
+
+    /// Resets the secondary indexes of the claim registry and scans the first batch of triples to
+    /// rebuild them, see [execute::rebuild_indexes_batch].
+    ///
+    /// The indexes by issuer and by holder are completed rather than reset, as the canonical hashes
+    /// they hold can't be recovered. The state digest is only rebuilt if none has been maintained
+    /// yet, the operations it chains otherwise covering the registered credentials already.
+    pub fn start_index_rebuild(deps: DepsMut<'_>, env: &Env) -> Result<(u64, bool), ContractError> {
+        EXPIRING_CREDENTIALS.clear(deps.storage);
+        SUBMISSION_SEQUENCE.save(deps.storage, &0)?;
+        let chain_digest = !STATE_DIGEST.exists(deps.storage);
+        INDEX_REBUILD.save(
+            deps.storage,
+            &IndexRebuild {
+                cursor: None,
+                chain_digest,
+            },
+        )?;
+
+        execute::rebuild_indexes_batch(deps, env, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::{
+        AccessCountResponse, ClaimSummary, ClaimTemplateProperty, ClaimTemplateResponse,
+        ClaimsFilter, ClaimsResponse, DataverseResponse, EmergencyResponse, FederatedClaim,
+        FederatedClaimsResponse, GatewaysResponse, OverrideAction, PeersResponse, ProofVerifier,
+        ProofVerifierQueryMsg, ProofVerifiersResponse, QueuedOverride, RdfDatasetFormat,
+        SubmitClaimsReceipt, TripleStoreConfig, TripleStoreLimitsInput, VerifyProofResponse,
+    };
+    use crate::state::{GOVERNANCE, PAUSED, PROOF_VERIFIERS, SUBMISSION_SEQUENCE};
+    use crate::testutil::testutil::{read_test_data, store_response};
+    use axone_cognitarium::msg::{
+        DataFormat, Head, Node, Results, SelectItem, SelectQuery, SelectResponse, TriplePattern,
+        Value, VarOrNamedNode, VarOrNode, VarOrNodeOrLiteral, WhereClause, IRI,
+    };
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+    use cosmwasm_std::{
+        coins, from_json, Addr, Attribute, Checksum, ContractInfoResponse, ContractResult,
+        CosmosMsg, Event, HexBinary, SubMsg, SystemError, SystemResult, Timestamp, Uint128, Uint64,
+        WasmQuery,
+    };
+    use cw_utils::PaymentError::NonPayable;
+    use std::collections::BTreeMap;
+    use testing::addr::{addr, CREATOR, OWNER, SENDER};
+    use testing::mock::mock_env_addr;
+
+    #[test]
+    fn proper_instantiate() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::CodeInfo { code_id, .. } => {
+                let resp = CodeInfoResponse::new(
+                    code_id.clone(),
+                    addr(CREATOR),
+                    Checksum::from_hex(
+                        "3B94AAF0B7D804B5B458DED0D20CACF95D2A1C8DF78ED3C89B61291760454AEC",
+                    )
+                    .unwrap(),
+                );
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&resp).unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+
+        let store_limits = TripleStoreLimitsInput {
+            max_byte_size: Some(Uint128::from(50000u128)),
+            ..Default::default()
+        };
+
+        let msg = InstantiateMsg {
+            name: "my-dataverse".to_string(),
+            triplestore_config: TripleStoreConfig {
+                code_id: Uint64::from(17u64),
+                limits: store_limits.clone(),
+            },
+        };
+
+        let env = mock_env_addr();
+        let info = message_info(&addr(CREATOR), &[]);
+        let res = instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        assert_eq!(
+            res.attributes,
+            vec![Attribute::new("triplestore_address", "predicted address")]
+        );
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(WasmMsg::Instantiate2 {
+                admin: Some(env.contract.address.to_string()),
+                code_id: 17,
+                label: "my-dataverse_triplestore".to_string(),
+                msg: to_json_binary(&axone_cognitarium::msg::InstantiateMsg {
+                    limits: store_limits.into(),
+                    recent_window: None,
+                    query_audit: None,
+                    conflict_policy: Default::default(),
+                    literal_offload: None,
+                    disable_literal_normalization: false,
+                    soft_limit: None,
+                })
+                .unwrap(),
+                funds: vec![],
+                salt: Binary::from("my-dataverse".as_bytes()),
+            })]
+        );
+        assert_eq!(
+            DATAVERSE.load(&deps.storage).unwrap(),
+            Dataverse {
+                name: "my-dataverse".to_string(),
+                triplestore_address: Addr::unchecked("predicted address"),
+            }
+        )
+    }
+
+    #[test]
+    fn funds_initialization() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&addr(SENDER), &coins(10, "uaxone"));
+
+        let msg = InstantiateMsg {
+            name: "my-dataverse".to_string(),
+            triplestore_config: TripleStoreConfig {
+                code_id: Uint64::from(17u64),
+                limits: TripleStoreLimitsInput::default(),
+            },
+        };
+
+        let result = instantiate(deps.as_mut(), env, info, msg);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            ContractError::Payment(NonPayable {})
+        ));
+    }
+
+    #[test]
+    fn proper_dataverse() {
+        let mut deps = mock_dependencies();
+
+        DATAVERSE
+            .save(
+                deps.as_mut().storage,
+                &Dataverse {
+                    name: "my-dataverse".to_string(),
+                    triplestore_address: Addr::unchecked("my-dataverse-addr"),
+                },
+            )
+            .unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Dataverse {});
+        assert!(res.is_ok());
+        let res: StdResult<DataverseResponse> = from_json(res.unwrap());
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            DataverseResponse {
+                name: "my-dataverse".to_string(),
+                triplestore_address: Addr::unchecked("my-dataverse-addr"),
+                governance: None,
+            }
+        );
+    }
+
+    #[test]
+    fn execute_fail_with_funds() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&addr(SENDER), &coins(10, "uaxone"));
+
+        let msg = ExecuteMsg::SubmitClaims {
+            claims: Binary::from("data".as_bytes()),
+            format: Some(RdfDatasetFormat::NQuads),
+        };
+
+        let result = execute(deps.as_mut(), env, info, msg);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            ContractError::Payment(NonPayable {})
+        ));
+    }
+
+    #[test]
+    fn proper_submit_claims() {
+        let mut deps = mock_dependencies();
+        deps.api = MockApi::default().with_prefix("axone");
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                if contract_addr != "my-dataverse-addr" {
+                    return SystemResult::Err(SystemError::NoSuchContract {
+                        addr: contract_addr.to_string(),
+                    });
+                }
+                let query_msg: StdResult<axone_cognitarium::msg::QueryMsg> = from_json(msg);
+                assert_eq!(
+                    query_msg,
+                    Ok(axone_cognitarium::msg::QueryMsg::Select {
+                        query: SelectQuery {
+                            prefixes: vec![],
+                            limit: Some(1u32),
+                            select: vec![SelectItem::Variable("p".to_string())],
+                            r#where: WhereClause::Bgp {
+                                patterns: vec![TriplePattern {
+                                    subject: VarOrNode::Node(Node::NamedNode(IRI::Full(
+                                        "http://example.edu/credentials/3732".to_string(),
+                                    ))),
+                                    predicate: VarOrNamedNode::Variable("p".to_string()),
+                                    object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                                }]
+                            },
+                            reasoning: None,
+                            skolemize: false,
+                            adaptive_joins: false,
+                            ordered: false,
+                            from: vec![],
+                            from_named: vec![],
+                        }
+                    })
+                );
+
+                let select_resp = SelectResponse {
+                    results: Results { bindings: vec![] },
+                    head: Head { vars: vec![] },
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&select_resp).unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+
+        DATAVERSE
+            .save(
+                deps.as_mut().storage,
+                &Dataverse {
+                    name: "my-dataverse".to_string(),
+                    triplestore_address: Addr::unchecked("my-dataverse-addr"),
+                },
+            )
+            .unwrap();
+
+        let resp = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(
+                &Addr::unchecked("axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0"),
+                &[],
+            ),
+            ExecuteMsg::SubmitClaims {
+                claims: Binary::new(read_test_data("vc-eddsa-2020-ok.nq")),
+                format: Some(RdfDatasetFormat::NQuads),
+            },
+        );
+
+        assert!(resp.is_ok());
+        let resp = resp.unwrap();
+        assert_eq!(resp.messages.len(), 1);
+        assert_eq!(
+            from_json::<SubmitClaimsReceipt>(resp.data.clone().unwrap()).unwrap(),
+            SubmitClaimsReceipt {
+                credential: "http://example.edu/credentials/3732".to_string(),
+                canonical_hash: HexBinary::from_hex(
+                    "47e942758ac9c9f498aa817019519e579994246c96456da96e587c327948ebbb"
+                )
+                .unwrap(),
+                sequence: Uint64::one(),
+                triplestore_address: Addr::unchecked("my-dataverse-addr"),
+            }
+        );
+        assert_eq!(SUBMISSION_SEQUENCE.load(&deps.storage).unwrap(), 1);
+        assert_eq!(
+            resp.attributes,
+            vec![
+                Attribute::new("action", "submit_claims"),
+                Attribute::new("credential", "http://example.edu/credentials/3732"),
+                Attribute::new(
+                    "subject",
+                    "did:key:zDnaeUm3QkcyZWZTPttxB711jgqRDhkwvhF485SFw1bDZ9AQw"
+                ),
+                Attribute::new(
+                    "type",
+                    "https://example.org/examples#UniversityDegreeCredential"
+                ),
+            ]
+        );
+
+        let expected_data = r#"<http://example.edu/credentials/3732> <dataverse:credential:header#height> "12345" .
+<http://example.edu/credentials/3732> <dataverse:credential:header#timestamp> "1571797419" .
+<http://example.edu/credentials/3732> <dataverse:credential:header#sender> "axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0" .
+<http://example.edu/credentials/3732> <dataverse:credential:header#sender_account> "7f953c6b28c98035334c087c433b7a387ec04ec9" .
+<http://example.edu/credentials/3732> <dataverse:credential:header#sender_prefix> "axone" .
+<http://example.edu/credentials/3732> <dataverse:credential:body#issuer> <did:key:z6MkpwdnLPAm4apwcrRYQ6fZ3rAcqjLZR4AMk14vimfnozqY> .
+<http://example.edu/credentials/3732> <dataverse:credential:body#type> <https://example.org/examples#UniversityDegreeCredential> .
+<http://example.edu/credentials/3732> <dataverse:credential:body#validFrom> "2024-02-16T00:00:00Z"^^<http://www.w3.org/2001/XMLSchema#dateTime> .
+<http://example.edu/credentials/3732> <dataverse:credential:body#subject> <did:key:zDnaeUm3QkcyZWZTPttxB711jgqRDhkwvhF485SFw1bDZ9AQw> .
+<http://example.edu/credentials/3732> <dataverse:credential:header#tx_index> "3" .
+_:c0 <https://example.org/examples#degree> _:b0 .
+_:b0 <http://schema.org/name> "Bachelor of Science and Arts"^^<http://www.w3.org/1999/02/22-rdf-syntax-ns#HTML> .
+_:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/examples#BachelorDegree> .
+<http://example.edu/credentials/3732> <dataverse:credential:body#claim> _:c0 .
+<http://example.edu/credentials/3732> <dataverse:credential:body#validUntil> "2026-02-16T00:00:00Z"^^<http://www.w3.org/2001/XMLSchema#dateTime> .
+"#;
+
+        match resp.messages[0].msg.clone() {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) if contract_addr == "my-dataverse-addr".to_string() && funds == vec![] => {
+                let exec_msg: StdResult<axone_cognitarium::msg::ExecuteMsg> = from_json(msg);
+                assert!(exec_msg.is_ok());
+                match exec_msg.unwrap() {
+                    axone_cognitarium::msg::ExecuteMsg::InsertData { format, data, .. } => {
+                        assert_eq!(format, Some(DataFormat::NTriples));
+                        assert_eq!(String::from_utf8(data.to_vec()).unwrap(), expected_data);
+                    }
+                    _ => assert!(false),
+                }
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn submit_nonrdf_claims() {
+        let resp = execute(
+            mock_dependencies().as_mut(),
+            mock_env(),
+            message_info(
+                &Addr::unchecked("axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0"),
+                &[],
+            ),
+            ExecuteMsg::SubmitClaims {
+                claims: Binary::new("notrdf".as_bytes().to_vec()),
+                format: Some(RdfDatasetFormat::NQuads),
+            },
+        );
+
+        assert!(resp.is_err());
+        assert!(matches!(resp.err().unwrap(), ContractError::ParseRDF(_)))
+    }
+
+    #[test]
+    fn submit_invalid_claims() {
+        let resp = execute(
+            mock_dependencies().as_mut(),
+            mock_env(),
+            message_info(
+                &Addr::unchecked("axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0"),
+                &[],
+            ),
+            ExecuteMsg::SubmitClaims {
+                claims: Binary::new(vec![]),
+                format: Some(RdfDatasetFormat::NQuads),
+            },
+        );
+
+        assert!(resp.is_err());
+        assert!(matches!(
+            resp.err().unwrap(),
+            ContractError::InvalidCredential(_)
+        ))
+    }
+
+    #[test]
+    fn submit_unverified_claims() {
+        let resp = execute(
+            mock_dependencies().as_mut(),
+            mock_env(),
+            message_info(
+                &Addr::unchecked("axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0"),
+                &[],
+            ),
+            ExecuteMsg::SubmitClaims {
+                claims: Binary::new(read_test_data("vc-eddsa-2020-ok-unsecured.nq")),
+                format: Some(RdfDatasetFormat::NQuads),
+            },
+        );
+
+        assert!(resp.is_err());
+        assert!(matches!(
+            resp.err().unwrap(),
+            ContractError::CredentialVerification(_)
+        ))
+    }
+
+    #[test]
+    fn submit_unsupported_claims() {
+        let mut deps = mock_dependencies();
+        deps.api = MockApi::default().with_prefix("axone");
+        let resp = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(
+                &Addr::unchecked("axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0"),
+                &[],
+            ),
+            ExecuteMsg::SubmitClaims {
+                claims: Binary::new(read_test_data("vc-unsupported-1.nq")),
+                format: Some(RdfDatasetFormat::NQuads),
+            },
+        );
+
+        assert!(resp.is_err());
+        assert!(matches!(
+            resp.err().unwrap(),
+            ContractError::UnsupportedCredential(_)
+        ))
+    }
+
+    #[test]
+    fn submit_existing_claims() {
+        let mut deps = mock_dependencies();
+        deps.api = MockApi::default().with_prefix("axone");
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { .. } => {
+                let select_resp = SelectResponse {
+                    results: Results {
+                        bindings: vec![BTreeMap::from([(
+                            "p".to_string(),
+                            Value::BlankNode {
+                                value: "".to_string(),
+                            },
+                        )])],
+                    },
+                    head: Head { vars: vec![] },
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&select_resp).unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+
+        DATAVERSE
+            .save(
+                deps.as_mut().storage,
+                &Dataverse {
+                    name: "my-dataverse".to_string(),
+                    triplestore_address: Addr::unchecked("my-dataverse-addr"),
+                },
+            )
+            .unwrap();
+
+        let resp = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(
+                &Addr::unchecked("axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0"),
+                &[],
+            ),
+            ExecuteMsg::SubmitClaims {
+                claims: Binary::new(read_test_data("vc-eddsa-2020-ok.nq")),
+                format: Some(RdfDatasetFormat::NQuads),
+            },
+        );
+
+        assert!(resp.is_err());
+        assert!(
+            matches!(resp.err().unwrap(), ContractError::CredentialAlreadyExists(id) if id == "http://example.edu/credentials/3732")
+        );
+    }
+
+    #[test]
+    fn proof_verifiers_registration() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::ContractInfo { .. } => SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&ContractInfoResponse::new(
+                    1,
+                    addr(CREATOR),
+                    Some(addr(OWNER)),
+                    false,
+                    None,
+                ))
+                .unwrap(),
+            )),
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+
+        let register = |deps: DepsMut<'_>, sender: &str, proof_type: &str, verifier: &str| {
+            execute(
+                deps,
+                mock_env(),
+                message_info(&addr(sender), &[]),
+                ExecuteMsg::RegisterProofVerifier {
+                    proof_type: proof_type.to_string(),
+                    verifier: addr(verifier).to_string(),
+                },
+            )
+        };
+
+        let res = register(
+            deps.as_mut(),
+            SENDER,
+            "https://w3id.org/security#BbsBlsSignature2020",
+            "bbs",
+        );
+        assert!(matches!(res, Err(ContractError::Unauthorized)));
+
+        let res = register(
+            deps.as_mut(),
+            OWNER,
+            "https://w3id.org/security#BbsBlsSignature2020",
+            "bbs",
+        );
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap().attributes,
+            vec![
+                Attribute::new("action", "register_proof_verifier"),
+                Attribute::new(
+                    "proof_type",
+                    "https://w3id.org/security#BbsBlsSignature2020"
+                ),
+                Attribute::new("verifier", addr("bbs")),
+            ]
+        );
+        assert!(register(
+            deps.as_mut(),
+            OWNER,
+            "https://w3id.org/security#Groth16Proof",
+            "groth16"
+        )
+        .is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ProofVerifiers {});
+        assert_eq!(
+            from_json::<ProofVerifiersResponse>(res.unwrap()).unwrap(),
+            ProofVerifiersResponse {
+                verifiers: vec![
+                    ProofVerifier {
+                        proof_type: "https://w3id.org/security#BbsBlsSignature2020".to_string(),
+                        verifier: addr("bbs"),
+                    },
+                    ProofVerifier {
+                        proof_type: "https://w3id.org/security#Groth16Proof".to_string(),
+                        verifier: addr("groth16"),
+                    },
+                ]
+            }
+        );
+
+        let unregister = |deps: DepsMut<'_>, sender: &str, proof_type: &str| {
+            execute(
+                deps,
+                mock_env(),
+                message_info(&addr(sender), &[]),
+                ExecuteMsg::UnregisterProofVerifier {
+                    proof_type: proof_type.to_string(),
+                },
+            )
+        };
+
+        let res = unregister(
+            deps.as_mut(),
+            SENDER,
+            "https://w3id.org/security#Groth16Proof",
+        );
+        assert!(matches!(res, Err(ContractError::Unauthorized)));
+
+        let res = unregister(
+            deps.as_mut(),
+            OWNER,
+            "https://w3id.org/security#Groth16Proof",
+        );
+        assert!(res.is_ok());
+
+        let res = unregister(
+            deps.as_mut(),
+            OWNER,
+            "https://w3id.org/security#Groth16Proof",
+        );
+        assert!(matches!(
+            res,
+            Err(ContractError::Std(StdError::NotFound { .. }))
+        ));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ProofVerifiers {});
+        assert_eq!(
+            from_json::<ProofVerifiersResponse>(res.unwrap()).unwrap(),
+            ProofVerifiersResponse {
+                verifiers: vec![ProofVerifier {
+                    proof_type: "https://w3id.org/security#BbsBlsSignature2020".to_string(),
+                    verifier: addr("bbs"),
+                }]
+            }
+        );
+    }
+
+    #[test]
+    fn submit_claims_with_external_proof() {
+        let cases = vec![
+            (None, Err("Couldn't find a suitable proof")),
+            (Some(true), Ok(())),
+            (
+                Some(false),
+                Err("External verifier rejected proof of type 'https://w3id.org/security#BbsBlsSignature2020'"),
+            ),
+        ];
+
+        for (verified, expected) in cases {
+            let mut deps = mock_dependencies();
+            deps.api = MockApi::default().with_prefix("axone");
+            deps.querier.update_wasm(move |query| match query {
+                WasmQuery::Smart { contract_addr, msg } if contract_addr == "verifier" => {
+                    let ProofVerifierQueryMsg::VerifyProof {
+                        proof_type,
+                        issuer,
+                        document,
+                        proof,
+                    } = from_json(msg).unwrap();
+                    assert_eq!(proof_type, "https://w3id.org/security#BbsBlsSignature2020");
+                    assert_eq!(
+                        issuer,
+                        "did:key:z6MkpwdnLPAm4apwcrRYQ6fZ3rAcqjLZR4AMk14vimfnozqY"
+                    );
+                    let document = String::from_utf8(document.to_vec()).unwrap();
+                    assert_eq!(document.lines().count(), 9);
+                    assert!(!document.contains("proofValue"));
+                    let proof = String::from_utf8(proof.to_vec()).unwrap();
+                    assert_eq!(proof.lines().count(), 5);
+                    assert!(proof.contains("<https://w3id.org/security#proofValue> \"zUuTPsT5aKs53ciMY6qEj2dqZxK4XnLoZhX26amB9GMCMhfcTmLbtndcW5JS4gUqPkxGxsCmZCKuvkFnDgrGFrWD\"^^<https://w3id.org/security#multibase> .\n"));
+
+                    SystemResult::Ok(ContractResult::Ok(
+                        to_json_binary(&VerifyProofResponse {
+                            verified: verified.unwrap(),
+                        })
+                        .unwrap(),
+                    ))
+                }
+                WasmQuery::Smart { .. } => {
+                    let select_resp = SelectResponse {
+                        results: Results { bindings: vec![] },
+                        head: Head { vars: vec![] },
+                    };
+                    SystemResult::Ok(ContractResult::Ok(to_json_binary(&select_resp).unwrap()))
+                }
+                _ => SystemResult::Err(SystemError::Unknown {}),
+            });
+
+            DATAVERSE
+                .save(
+                    deps.as_mut().storage,
+                    &Dataverse {
+                        name: "my-dataverse".to_string(),
+                        triplestore_address: Addr::unchecked("my-dataverse-addr"),
+                    },
+                )
+                .unwrap();
+            if verified.is_some() {
+                PROOF_VERIFIERS
+                    .save(
+                        deps.as_mut().storage,
+                        "https://w3id.org/security#BbsBlsSignature2020",
+                        &Addr::unchecked("verifier"),
+                    )
+                    .unwrap();
+            }
+
+            let resp = execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(
+                    &Addr::unchecked("axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0"),
+                    &[],
+                ),
+                ExecuteMsg::SubmitClaims {
+                    claims: Binary::new(read_test_data("vc-external-proof.nq")),
+                    format: Some(RdfDatasetFormat::NQuads),
+                },
+            );
+
+            match expected {
+                Ok(()) => assert_eq!(resp.unwrap().messages.len(), 1),
+                Err(msg) => assert!(matches!(
+                    resp.unwrap_err(),
+                    ContractError::CredentialVerification(e) if e.to_string() == msg
+                )),
+            }
+        }
+    }
+
+    #[test]
+    fn claims_usage_policies() {
+        const POLICY: &str =
+            "https://w3id.org/axone/ontology/vnext/schema/credential/usage-policy/";
+        let iri = |value: &str| Value::URI {
+            value: IRI::Full(value.to_string()),
+        };
+        let subject = |n: u8| format!("http://example.edu/resources/{n}");
+
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::ContractInfo { .. } => SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&ContractInfoResponse::new(
+                    1,
+                    addr(CREATOR),
+                    Some(addr(OWNER)),
+                    false,
+                    None,
+                ))
+                .unwrap(),
+            )),
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "my-dataverse-addr" => {
+                let query = match from_json(msg).unwrap() {
+                    axone_cognitarium::msg::QueryMsg::Store {} => {
+                        return SystemResult::Ok(ContractResult::Ok(
+                            to_json_binary(&store_response(4)).unwrap(),
+                        ))
+                    }
+                    axone_cognitarium::msg::QueryMsg::Select { query } => query,
+                    _ => panic!("unexpected triple store query"),
+                };
+                let WhereClause::Bgp { patterns } = query.r#where else {
+                    panic!("unexpected where clause");
+                };
+
+                let bindings = match &patterns[0].object {
+                    // the usage policies of a resource
+                    VarOrNodeOrLiteral::Node(Node::NamedNode(IRI::Full(resource))) => {
+                        let policy = |credential: &str, p: &str, o: &str| {
+                            BTreeMap::from([
+                                ("credential".to_string(), iri(credential)),
+                                ("p".to_string(), iri(&format!("{POLICY}{p}"))),
+                                ("o".to_string(), iri(o)),
+                            ])
+                        };
+                        match resource.trim_start_matches("http://example.edu/resources/") {
+                            "1" => vec![
+                                policy("urn:policy:1", "allowedPurpose", "urn:purpose:research"),
+                                policy("urn:policy:1", "license", "urn:license:a"),
+                            ],
+                            "2" => vec![
+                                policy("urn:policy:2", "allowedPurpose", "urn:purpose:marketing"),
+                                policy("urn:policy:2", "license", "urn:license:a"),
+                                policy("urn:policy:3", "license", "urn:license:b"),
+                            ],
+                            "4" => vec![policy("urn:policy:4", "license", "urn:license:c")],
+                            _ => vec![],
+                        }
+                    }
+                    // the claims matching the filter
+                    _ => (1..=4)
+                        .map(|n| {
+                            BTreeMap::from([
+                                (
+                                    "credential".to_string(),
+                                    iri(&format!("http://example.edu/credentials/{n}")),
+                                ),
+                                ("subject".to_string(), iri(&subject(n))),
+                                ("type".to_string(), iri("https://example.org/Dataset")),
+                                ("issuer".to_string(), iri("did:key:issuer")),
+                            ])
+                        })
+                        .collect(),
+                };
+
+                let select_resp = SelectResponse {
+                    head: Head { vars: vec![] },
+                    results: Results { bindings },
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&select_resp).unwrap()))
+            }
+            WasmQuery::Smart { contract_addr, msg } if *contract_addr == addr("law-stone").as_str() => {
+                let axone_law_stone::msg::QueryMsg::Verdict { goal } = from_json(msg).unwrap()
+                else {
+                    panic!("unexpected law stone query");
+                };
+                let resp = axone_law_stone::msg::VerdictResponse {
+                    allowed: goal
+                        == "usage_allowed('http://example.edu/resources/2', 'urn:purpose:research', ['urn:license:a','urn:license:b']).",
+                    reasons: vec![],
+                    obligations: vec![],
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&resp).unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+
+        DATAVERSE
+            .save(
+                deps.as_mut().storage,
+                &Dataverse {
+                    name: "my-dataverse".to_string(),
+                    triplestore_address: Addr::unchecked("my-dataverse-addr"),
+                },
+            )
+            .unwrap();
+
+        let subjects = |deps: Deps<'_>, purpose: Option<&str>| {
+            let res = query(
+                deps,
+                mock_env(),
+                QueryMsg::Claims {
+                    filter: ClaimsFilter {
+                        purpose: purpose.map(ToString::to_string),
+                        ..Default::default()
+                    },
+                },
+            );
+            let resp = from_json::<ClaimsResponse>(res.unwrap()).unwrap();
+            // the selection reaches the query limit of the triple store
+            assert!(resp.truncated);
+            resp.claims
+                .into_iter()
+                .map(|c| c.subject)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            subjects(deps.as_ref(), None),
+            (1..=4).map(subject).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            subjects(deps.as_ref(), Some("urn:purpose:research")),
+            vec![subject(1)]
+        );
+
+        let set_governance = |deps: DepsMut<'_>, sender: &str, law_stone: Option<String>| {
+            execute(
+                deps,
+                mock_env(),
+                message_info(&addr(sender), &[]),
+                ExecuteMsg::SetGovernance { law_stone },
+            )
+        };
+        let res = set_governance(deps.as_mut(), SENDER, Some(addr("law-stone").to_string()));
+        assert!(matches!(res, Err(ContractError::Unauthorized)));
+        let res = set_governance(deps.as_mut(), OWNER, Some(addr("law-stone").to_string()));
+        assert_eq!(
+            res.unwrap().attributes,
+            vec![
+                Attribute::new("action", "set_governance"),
+                Attribute::new("law_stone", addr("law-stone")),
+            ]
+        );
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Dataverse {});
+        assert_eq!(
+            from_json::<DataverseResponse>(res.unwrap())
+                .unwrap()
+                .governance,
+            Some(addr("law-stone"))
+        );
+
+        assert_eq!(
+            subjects(deps.as_ref(), Some("urn:purpose:research")),
+            vec![subject(1), subject(2)]
+        );
+        assert_eq!(
+            subjects(deps.as_ref(), Some("urn:purpose:marketing")),
+            vec![subject(2)]
+        );
+
+        assert!(set_governance(deps.as_mut(), OWNER, None).is_ok());
+        assert_eq!(
+            subjects(deps.as_ref(), Some("urn:purpose:research")),
+            vec![subject(1)]
+        );
+    }
+
+    #[test]
+    fn federated_claims() {
+        let claim = |credential: &str| ClaimSummary {
+            credential: credential.to_string(),
+            subject: "http://example.edu/subjects/1".to_string(),
+            r#type: "https://example.org/examples#UniversityDegreeCredential".to_string(),
+            issuer: "did:key:issuer".to_string(),
+        };
+        let iri = |value: &str| Value::URI {
+            value: IRI::Full(value.to_string()),
+        };
+
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::ContractInfo { .. } => SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&ContractInfoResponse::new(
+                    1,
+                    addr(CREATOR),
+                    Some(addr(OWNER)),
+                    false,
+                    None,
+                ))
+                .unwrap(),
+            )),
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "my-dataverse-addr" => {
+                let query_msg: axone_cognitarium::msg::QueryMsg = from_json(msg).unwrap();
+                let query = match query_msg {
+                    axone_cognitarium::msg::QueryMsg::Store {} => {
+                        return SystemResult::Ok(ContractResult::Ok(
+                            to_json_binary(&store_response(30)).unwrap(),
+                        ))
+                    }
+                    axone_cognitarium::msg::QueryMsg::Select { query } => query,
+                    _ => panic!("unexpected triple store query"),
+                };
+                assert_eq!(query.limit, Some(30));
+                assert_eq!(
+                    query.select,
+                    vec![
+                        SelectItem::Variable("credential".to_string()),
+                        SelectItem::Variable("subject".to_string()),
+                        SelectItem::Variable("issuer".to_string()),
+                    ]
+                );
+
+                let select_resp = SelectResponse {
+                    head: Head { vars: vec![] },
+                    results: Results {
+                        bindings: vec![BTreeMap::from([
+                            (
+                                "credential".to_string(),
+                                iri("http://example.edu/credentials/1"),
+                            ),
+                            ("subject".to_string(), iri("http://example.edu/subjects/1")),
+                            ("issuer".to_string(), iri("did:key:issuer")),
+                        ])],
+                    },
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&select_resp).unwrap()))
+            }
+            WasmQuery::Smart { contract_addr, msg } if *contract_addr == addr("peer1").as_str() => {
+                assert_eq!(
+                    from_json::<QueryMsg>(msg).unwrap(),
+                    QueryMsg::Claims {
+                        filter: ClaimsFilter {
+                            r#type: Some(
+                                "https://example.org/examples#UniversityDegreeCredential"
+                                    .to_string()
+                            ),
+                            ..Default::default()
+                        }
+                    }
+                );
+                let resp = ClaimsResponse {
+                    claims: vec![claim("http://example.edu/credentials/2")],
+                    truncated: true,
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&resp).unwrap()))
+            }
+            WasmQuery::Smart { contract_addr, .. } => {
+                SystemResult::Err(SystemError::NoSuchContract {
+                    addr: contract_addr.to_string(),
+                })
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+
+        DATAVERSE
+            .save(
+                deps.as_mut().storage,
+                &Dataverse {
+                    name: "my-dataverse".to_string(),
+                    triplestore_address: Addr::unchecked("my-dataverse-addr"),
+                },
+            )
+            .unwrap();
+
+        let register = |deps: DepsMut<'_>, sender: &str, address: String| {
+            execute(
+                deps,
+                mock_env(),
+                message_info(&addr(sender), &[]),
+                ExecuteMsg::RegisterPeer { address },
+            )
+        };
+
+        let res = register(deps.as_mut(), SENDER, addr("peer1").to_string());
+        assert!(matches!(res, Err(ContractError::Unauthorized)));
+        let res = register(
+            deps.as_mut(),
+            OWNER,
+            mock_env().contract.address.to_string(),
+        );
+        assert!(matches!(
+            res,
+            Err(ContractError::Std(StdError::GenericErr { .. }))
+        ));
+        for peer in ["peer1", "peer2", "peer3"] {
+            let res = register(deps.as_mut(), OWNER, addr(peer).to_string());
+            assert_eq!(
+                res.unwrap().attributes,
+                vec![
+                    Attribute::new("action", "register_peer"),
+                    Attribute::new("address", addr(peer)),
+                ]
+            );
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(OWNER), &[]),
+            ExecuteMsg::UnregisterPeer {
+                address: addr("peer3").to_string(),
+            },
+        );
+        assert!(res.is_ok());
+
+        let mut peers = vec![addr("peer1"), addr("peer2")];
+        peers.sort();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Peers {});
+        assert_eq!(
+            from_json::<PeersResponse>(res.unwrap()).unwrap(),
+            PeersResponse {
+                peers: peers.clone()
+            }
+        );
+
+        let filter = ClaimsFilter {
+            r#type: Some("https://example.org/examples#UniversityDegreeCredential".to_string()),
+            ..Default::default()
+        };
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::FederatedClaims {
+                filter: filter.clone(),
+                max_peers: None,
+            },
+        );
+        assert_eq!(
+            from_json::<FederatedClaimsResponse>(res.unwrap()).unwrap(),
+            FederatedClaimsResponse {
+                claims: vec![
+                    FederatedClaim {
+                        origin: mock_env().contract.address,
+                        claim: claim("http://example.edu/credentials/1"),
+                    },
+                    FederatedClaim {
+                        origin: addr("peer1"),
+                        claim: claim("http://example.edu/credentials/2"),
+                    },
+                ],
+                unreachable_peers: vec![addr("peer2")],
+                truncated: true,
+            }
+        );
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::FederatedClaims {
+                filter,
+                max_peers: Some(0),
+            },
+        );
+        assert_eq!(
+            from_json::<FederatedClaimsResponse>(res.unwrap()).unwrap(),
+            FederatedClaimsResponse {
+                claims: vec![FederatedClaim {
+                    origin: mock_env().contract.address,
+                    claim: claim("http://example.edu/credentials/1"),
+                }],
+                unreachable_peers: vec![],
+                truncated: false,
+            }
+        );
+    }
+
+    #[test]
+    fn record_access() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::ContractInfo { .. } => SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&ContractInfoResponse::new(
+                    1,
+                    addr(CREATOR),
+                    Some(addr(OWNER)),
+                    false,
+                    None,
+                ))
+                .unwrap(),
+            )),
+            WasmQuery::Smart { msg, .. } => {
+                let query_msg: axone_cognitarium::msg::QueryMsg = from_json(msg).unwrap();
+                let axone_cognitarium::msg::QueryMsg::Select { query } = query_msg else {
+                    panic!("unexpected triple store query");
+                };
+                let WhereClause::Bgp { patterns } = query.r#where else {
+                    panic!("unexpected where clause");
+                };
+                let registered = patterns[0].subject
+                    == VarOrNode::Node(Node::NamedNode(IRI::Full(
+                        "http://example.edu/credentials/1".to_string(),
+                    )));
 
+                let iri = |value: &str| Value::URI {
+                    value: IRI::Full(value.to_string()),
+                };
                 let select_resp = SelectResponse {
-                    results: Results { bindings: vec![] },
                     head: Head { vars: vec![] },
+                    results: Results {
+                        bindings: registered
+                            .then(|| {
+                                BTreeMap::from([
+                                    ("issuer".to_string(), iri("did:key:issuer")),
+                                    ("subject".to_string(), iri("http://example.edu/subjects/1")),
+                                ])
+                            })
+                            .into_iter()
+                            .collect(),
+                    },
                 };
                 SystemResult::Ok(ContractResult::Ok(to_json_binary(&select_resp).unwrap()))
             }
@@ -336,179 +2687,121 @@ mod tests {
             )
             .unwrap();
 
-        let resp = execute(
+        let res = execute(
             deps.as_mut(),
             mock_env(),
-            message_info(
-                &Addr::unchecked("axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0"),
-                &[],
-            ),
-            ExecuteMsg::SubmitClaims {
-                claims: Binary::new(read_test_data("vc-eddsa-2020-ok.nq")),
-                format: Some(RdfDatasetFormat::NQuads),
+            message_info(&addr(SENDER), &[]),
+            ExecuteMsg::RegisterGateway {
+                address: addr("gateway").to_string(),
             },
         );
-
-        assert!(resp.is_ok());
-        let resp = resp.unwrap();
-        assert_eq!(resp.messages.len(), 1);
-        assert_eq!(
-            resp.attributes,
-            vec![
-                Attribute::new("action", "submit_claims"),
-                Attribute::new("credential", "http://example.edu/credentials/3732"),
-                Attribute::new(
-                    "subject",
-                    "did:key:zDnaeUm3QkcyZWZTPttxB711jgqRDhkwvhF485SFw1bDZ9AQw"
-                ),
-                Attribute::new(
-                    "type",
-                    "https://example.org/examples#UniversityDegreeCredential"
-                ),
-            ]
-        );
-
-        let expected_data = r#"<http://example.edu/credentials/3732> <dataverse:credential:header#height> "12345" .
-<http://example.edu/credentials/3732> <dataverse:credential:header#timestamp> "1571797419" .
-<http://example.edu/credentials/3732> <dataverse:credential:header#sender> "axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0" .
-<http://example.edu/credentials/3732> <dataverse:credential:body#issuer> <did:key:z6MkpwdnLPAm4apwcrRYQ6fZ3rAcqjLZR4AMk14vimfnozqY> .
-<http://example.edu/credentials/3732> <dataverse:credential:body#type> <https://example.org/examples#UniversityDegreeCredential> .
-<http://example.edu/credentials/3732> <dataverse:credential:body#validFrom> "2024-02-16T00:00:00Z"^^<http://www.w3.org/2001/XMLSchema#dateTime> .
-<http://example.edu/credentials/3732> <dataverse:credential:body#subject> <did:key:zDnaeUm3QkcyZWZTPttxB711jgqRDhkwvhF485SFw1bDZ9AQw> .
-<http://example.edu/credentials/3732> <dataverse:credential:header#tx_index> "3" .
-_:c0 <https://example.org/examples#degree> _:b0 .
-_:b0 <http://schema.org/name> "Bachelor of Science and Arts"^^<http://www.w3.org/1999/02/22-rdf-syntax-ns#HTML> .
-_:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/examples#BachelorDegree> .
-<http://example.edu/credentials/3732> <dataverse:credential:body#claim> _:c0 .
-<http://example.edu/credentials/3732> <dataverse:credential:body#validUntil> "2026-02-16T00:00:00Z"^^<http://www.w3.org/2001/XMLSchema#dateTime> .
-"#;
-
-        match resp.messages[0].msg.clone() {
-            CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr,
-                msg,
-                funds,
-            }) if contract_addr == "my-dataverse-addr".to_string() && funds == vec![] => {
-                let exec_msg: StdResult<axone_cognitarium::msg::ExecuteMsg> = from_json(msg);
-                assert!(exec_msg.is_ok());
-                match exec_msg.unwrap() {
-                    axone_cognitarium::msg::ExecuteMsg::InsertData { format, data } => {
-                        assert_eq!(format, Some(DataFormat::NTriples));
-                        assert_eq!(String::from_utf8(data.to_vec()).unwrap(), expected_data);
-                    }
-                    _ => assert!(false),
-                }
-            }
-            _ => assert!(false),
+        assert!(matches!(res, Err(ContractError::Unauthorized)));
+        for gateway in ["gateway", "other-gateway"] {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(OWNER), &[]),
+                ExecuteMsg::RegisterGateway {
+                    address: addr(gateway).to_string(),
+                },
+            );
+            assert_eq!(
+                res.unwrap().attributes,
+                vec![
+                    Attribute::new("action", "register_gateway"),
+                    Attribute::new("address", addr(gateway)),
+                ]
+            );
         }
-    }
-
-    #[test]
-    fn submit_nonrdf_claims() {
-        let resp = execute(
-            mock_dependencies().as_mut(),
+        let res = execute(
+            deps.as_mut(),
             mock_env(),
-            message_info(
-                &Addr::unchecked("axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0"),
-                &[],
-            ),
-            ExecuteMsg::SubmitClaims {
-                claims: Binary::new("notrdf".as_bytes().to_vec()),
-                format: Some(RdfDatasetFormat::NQuads),
+            message_info(&addr(OWNER), &[]),
+            ExecuteMsg::UnregisterGateway {
+                address: addr("other-gateway").to_string(),
             },
         );
-
-        assert!(resp.is_err());
-        assert!(matches!(resp.err().unwrap(), ContractError::ParseRDF(_)))
-    }
-
-    #[test]
-    fn submit_invalid_claims() {
-        let resp = execute(
-            mock_dependencies().as_mut(),
-            mock_env(),
-            message_info(
-                &Addr::unchecked("axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0"),
-                &[],
-            ),
-            ExecuteMsg::SubmitClaims {
-                claims: Binary::new(vec![]),
-                format: Some(RdfDatasetFormat::NQuads),
-            },
+        assert!(res.is_ok());
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Gateways {});
+        assert_eq!(
+            from_json::<GatewaysResponse>(res.unwrap()).unwrap(),
+            GatewaysResponse {
+                gateways: vec![addr("gateway")]
+            }
         );
 
-        assert!(resp.is_err());
-        assert!(matches!(
-            resp.err().unwrap(),
-            ContractError::InvalidCredential(_)
-        ))
-    }
+        let record = |deps: DepsMut<'_>, sender: &str, credential_id: &str| {
+            execute(
+                deps,
+                mock_env(),
+                message_info(&addr(sender), &[]),
+                ExecuteMsg::RecordAccess {
+                    credential_id: credential_id.to_string(),
+                },
+            )
+        };
 
-    #[test]
-    fn submit_unverified_claims() {
-        let resp = execute(
-            mock_dependencies().as_mut(),
-            mock_env(),
-            message_info(
-                &Addr::unchecked("axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0"),
-                &[],
-            ),
-            ExecuteMsg::SubmitClaims {
-                claims: Binary::new(read_test_data("vc-eddsa-2020-ok-unsecured.nq")),
-                format: Some(RdfDatasetFormat::NQuads),
-            },
+        let res = record(
+            deps.as_mut(),
+            "other-gateway",
+            "http://example.edu/credentials/1",
         );
-
-        assert!(resp.is_err());
+        assert!(matches!(res, Err(ContractError::Unauthorized)));
+        let res = record(deps.as_mut(), "gateway", "http://example.edu/credentials/2");
         assert!(matches!(
-            resp.err().unwrap(),
-            ContractError::CredentialVerification(_)
-        ))
-    }
-
-    #[test]
-    fn submit_unsupported_claims() {
-        let resp = execute(
-            mock_dependencies().as_mut(),
-            mock_env(),
-            message_info(
-                &Addr::unchecked("axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0"),
-                &[],
-            ),
-            ExecuteMsg::SubmitClaims {
-                claims: Binary::new(read_test_data("vc-unsupported-1.nq")),
-                format: Some(RdfDatasetFormat::NQuads),
-            },
-        );
+            res,
+            Err(ContractError::Std(StdError::NotFound { .. }))
+        ));
+        for count in 1..=2 {
+            let res = record(deps.as_mut(), "gateway", "http://example.edu/credentials/1");
+            assert_eq!(
+                res.unwrap().attributes,
+                vec![
+                    Attribute::new("action", "record_access"),
+                    Attribute::new("credential", "http://example.edu/credentials/1"),
+                    Attribute::new("access_count", count.to_string()),
+                ]
+            );
+        }
 
-        assert!(resp.is_err());
-        assert!(matches!(
-            resp.err().unwrap(),
-            ContractError::UnsupportedCredential(_)
-        ))
+        let cases = vec![
+            ("http://example.edu/credentials/1", 2u64),
+            ("http://example.edu/credentials/2", 0u64),
+        ];
+        for (credential_id, count) in cases {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AccessCount {
+                    credential_id: credential_id.to_string(),
+                },
+            );
+            assert_eq!(
+                from_json::<AccessCountResponse>(res.unwrap()).unwrap(),
+                AccessCountResponse {
+                    credential: credential_id.to_string(),
+                    count: Uint64::new(count),
+                }
+            );
+        }
     }
 
     #[test]
-    fn submit_existing_claims() {
+    fn emergency_override() {
         let mut deps = mock_dependencies();
-        deps.querier.update_wasm(|query| match query {
-            WasmQuery::Smart { .. } => {
-                let select_resp = SelectResponse {
-                    results: Results {
-                        bindings: vec![BTreeMap::from([(
-                            "p".to_string(),
-                            Value::BlankNode {
-                                value: "".to_string(),
-                            },
-                        )])],
-                    },
-                    head: Head { vars: vec![] },
-                };
-                SystemResult::Ok(ContractResult::Ok(to_json_binary(&select_resp).unwrap()))
-            }
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::ContractInfo { .. } => SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&ContractInfoResponse::new(
+                    1,
+                    addr(CREATOR),
+                    Some(addr(OWNER)),
+                    false,
+                    None,
+                ))
+                .unwrap(),
+            )),
             _ => SystemResult::Err(SystemError::Unknown {}),
         });
-
         DATAVERSE
             .save(
                 deps.as_mut().storage,
@@ -519,22 +2812,223 @@ _:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/exam
             )
             .unwrap();
 
-        let resp = execute(
+        let run = |deps: DepsMut<'_>, sender: &str, seconds: u64, msg: ExecuteMsg| {
+            let mut env = mock_env();
+            env.block.time = Timestamp::from_seconds(seconds);
+            execute(deps, env, message_info(&addr(sender), &[]), msg)
+        };
+        let set_guardian = |guardian: &str, delay: u64| ExecuteMsg::SetGuardian {
+            guardian: Some(addr(guardian).to_string()),
+            delay: Uint64::new(delay),
+        };
+        let queue = |action: OverrideAction| ExecuteMsg::QueueOverride { action };
+
+        let res = run(deps.as_mut(), SENDER, 0, set_guardian("guardian", 3600));
+        assert!(matches!(res, Err(ContractError::Unauthorized)));
+        let res = run(deps.as_mut(), OWNER, 0, set_guardian("guardian", 0));
+        assert!(matches!(
+            res,
+            Err(ContractError::InvalidGuardian(msg)) if msg == "delay must be at least 3600 seconds"
+        ));
+        run(deps.as_mut(), OWNER, 0, set_guardian("guardian", 3600)).unwrap();
+        let res = run(deps.as_mut(), SENDER, 0, queue(OverrideAction::Pause {}));
+        assert!(matches!(res, Err(ContractError::Unauthorized)));
+
+        let res = run(
+            deps.as_mut(),
+            "guardian",
+            10,
+            queue(OverrideAction::Pause {}),
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "queue_override"),
+                Attribute::new("id", "1"),
+            ]
+        );
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(
+            res.events,
+            vec![Event::new("override_queued")
+                .add_attribute("id", "1")
+                .add_attribute("action", "pause")
+                .add_attribute("executable_at", "3610")]
+        );
+        run(
             deps.as_mut(),
+            "guardian",
+            20,
+            queue(OverrideAction::SetGovernance {
+                law_stone: Some(addr("law-stone").to_string()),
+            }),
+        )
+        .unwrap();
+
+        let res = query(
+            deps.as_ref(),
             mock_env(),
-            message_info(
-                &Addr::unchecked("axone1072nc6egexqr2v6vpp7yxwm68plvqnkf5uemr0"),
-                &[],
-            ),
+            QueryMsg::Emergency {
+                after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(
+            from_json::<EmergencyResponse>(res.unwrap()).unwrap(),
+            EmergencyResponse {
+                guardian: Some(addr("guardian")),
+                delay: Uint64::new(3600),
+                paused: false,
+                overrides: vec![
+                    QueuedOverride {
+                        id: Uint64::new(1),
+                        action: OverrideAction::Pause {},
+                        guardian: addr("guardian"),
+                        queued_at: Timestamp::from_seconds(10),
+                        executable_at: Timestamp::from_seconds(3610),
+                    },
+                    QueuedOverride {
+                        id: Uint64::new(2),
+                        action: OverrideAction::SetGovernance {
+                            law_stone: Some(addr("law-stone").to_string()),
+                        },
+                        guardian: addr("guardian"),
+                        queued_at: Timestamp::from_seconds(20),
+                        executable_at: Timestamp::from_seconds(3620),
+                    },
+                ],
+            }
+        );
+        for (after, limit, ids) in [
+            (None, Some(1), vec![1]),
+            (Some(1), None, vec![2]),
+            (Some(2), None, vec![]),
+        ] {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Emergency {
+                    after: after.map(Uint64::new),
+                    limit,
+                },
+            );
+            assert_eq!(
+                from_json::<EmergencyResponse>(res.unwrap())
+                    .unwrap()
+                    .overrides
+                    .into_iter()
+                    .map(|r#override| r#override.id.u64())
+                    .collect::<Vec<_>>(),
+                ids
+            );
+        }
+
+        let execute_override = |id: u64| ExecuteMsg::ExecuteOverride {
+            id: Uint64::new(id),
+        };
+        let res = run(deps.as_mut(), SENDER, 3609, execute_override(1));
+        assert!(matches!(
+            res,
+            Err(ContractError::OverrideTimelocked(1, 3610))
+        ));
+        let res = run(deps.as_mut(), SENDER, 3610, execute_override(1)).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert!(PAUSED.exists(&deps.storage));
+        let res = run(
+            deps.as_mut(),
+            SENDER,
+            3610,
             ExecuteMsg::SubmitClaims {
                 claims: Binary::new(read_test_data("vc-eddsa-2020-ok.nq")),
-                format: Some(RdfDatasetFormat::NQuads),
+                format: None,
             },
         );
+        assert!(matches!(res, Err(ContractError::Paused)));
 
-        assert!(resp.is_err());
-        assert!(
-            matches!(resp.err().unwrap(), ContractError::CredentialAlreadyExists(id) if id == "http://example.edu/credentials/3732")
+        let cancel = ExecuteMsg::CancelOverride { id: Uint64::new(2) };
+        let res = run(deps.as_mut(), "guardian", 3610, cancel.clone());
+        assert!(matches!(res, Err(ContractError::Unauthorized)));
+        let res = run(deps.as_mut(), OWNER, 3610, cancel).unwrap();
+        assert_eq!(
+            res.events,
+            vec![Event::new("override_cancelled").add_attribute("id", "2")]
+        );
+        let res = run(deps.as_mut(), SENDER, 3700, execute_override(2));
+        assert!(matches!(
+            res,
+            Err(ContractError::Std(StdError::NotFound { .. }))
+        ));
+        assert_eq!(GOVERNANCE.may_load(&deps.storage).unwrap(), None);
+
+        // the overrides of a replaced guardian can't be executed anymore
+        run(
+            deps.as_mut(),
+            "guardian",
+            3700,
+            queue(OverrideAction::Resume {}),
+        )
+        .unwrap();
+        run(deps.as_mut(), OWNER, 3700, set_guardian("guardian2", 3600)).unwrap();
+        let res = run(deps.as_mut(), SENDER, 7300, execute_override(3));
+        assert!(matches!(
+            res,
+            Err(ContractError::OverrideGuardianRevoked(3))
+        ));
+        assert!(PAUSED.exists(&deps.storage));
+    }
+
+    #[test]
+    fn claim_template() {
+        let deps = mock_dependencies();
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ClaimTemplate {
+                r#type: "https://w3id.org/axone/ontology/vnext/schema/credential/usage-policy/UsagePolicyCredential".to_string(),
+            },
+        );
+        let template = from_json::<ClaimTemplateResponse>(res.unwrap()).unwrap();
+        assert_eq!(
+            template.context,
+            vec![
+                "https://www.w3.org/2018/credentials/v1".to_string(),
+                "https://w3id.org/axone/ontology/vnext/schema/credential/usage-policy/".to_string(),
+            ]
+        );
+        assert_eq!(
+            template.properties,
+            vec![
+                ClaimTemplateProperty {
+                    predicate: "https://w3id.org/axone/ontology/vnext/schema/credential/usage-policy/allowedPurpose".to_string(),
+                    datatype: None,
+                    required: false,
+                },
+                ClaimTemplateProperty {
+                    predicate: "https://w3id.org/axone/ontology/vnext/schema/credential/usage-policy/license".to_string(),
+                    datatype: None,
+                    required: false,
+                },
+            ]
+        );
+        assert!(template.document.contains(
+            r#""allowedPurpose": { "@id": "https://w3id.org/axone/ontology/vnext/schema/credential/usage-policy/allowedPurpose", "@type": "@id" }"#
+        ));
+        assert!(template
+            .document
+            .contains(r#""type": ["VerifiableCredential", "UsagePolicyCredential"]"#));
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ClaimTemplate {
+                r#type: "https://ex.org/UnknownCredential".to_string(),
+            },
+        );
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Generic error: Unsupported credential type: https://ex.org/UnknownCredential"
         );
     }
 }
@@ -69,4 +69,10 @@ pub enum VerificationError {
 
     #[error("Couldn't find a suitable proof")]
     NoSuitableProof,
+
+    #[error("Couldn't query external proof verifier: {0}")]
+    ExternalVerifier(#[from] cosmwasm_std::StdError),
+
+    #[error("External verifier rejected proof of type '{0}'")]
+    ExternalProofRejected(String),
 }
@@ -89,6 +89,28 @@ impl<'a> Proof<'a> {
         }
     }
 
+    fn extract_type(
+        dataset: &'a Dataset<'a>,
+        proof_graph: GraphName<'a>,
+    ) -> Result<&'a str, InvalidProofError> {
+        dataset
+            .match_pattern(None, Some(RDF_TYPE), None, Some(Some(proof_graph)))
+            .objects()
+            .exactly_one()
+            .map_err(|e| match e.size_hint() {
+                (_, Some(_)) => {
+                    InvalidProofError::Malformed("Proof cannot have more than one type".to_string())
+                }
+                _ => InvalidProofError::MissingProofType,
+            })
+            .and_then(|o| match o {
+                Term::NamedNode(n) => Ok(n.iri),
+                _ => Err(InvalidProofError::Malformed(
+                    "Proof type must be a named node".to_string(),
+                )),
+            })
+    }
+
     fn extract_verification_method(
         dataset: &'a Dataset<'a>,
         proof_graph: GraphName<'a>,
@@ -260,24 +282,7 @@ impl<'a> TryFrom<(&'a Dataset<'a>, GraphName<'a>)> for Proof<'a> {
     fn try_from(
         (dataset, proof_graph): (&'a Dataset<'a>, GraphName<'a>),
     ) -> Result<Self, Self::Error> {
-        let proof_type = dataset
-            .match_pattern(None, Some(RDF_TYPE), None, Some(Some(proof_graph)))
-            .objects()
-            .exactly_one()
-            .map_err(|e| match e.size_hint() {
-                (_, Some(_)) => {
-                    InvalidProofError::Malformed("Proof cannot have more than one type".to_string())
-                }
-                _ => InvalidProofError::MissingProofType,
-            })
-            .and_then(|o| match o {
-                Term::NamedNode(n) => Ok(n.iri),
-                _ => Err(InvalidProofError::Malformed(
-                    "Proof type must be a named node".to_string(),
-                )),
-            })?;
-
-        match proof_type {
+        match Self::extract_type(dataset, proof_graph)? {
             "https://w3id.org/security#Ed25519Signature2018" => Ok(Self::Ed25519Signature2018(
                 Ed25519Signature2018Proof::try_from((dataset, proof_graph))?,
             )),
@@ -297,6 +302,51 @@ impl<'a> TryFrom<(&'a Dataset<'a>, GraphName<'a>)> for Proof<'a> {
     }
 }
 
+/// A proof whose type is not natively supported, its verification being delegated to the external
+/// verifier registered for its type.
+#[derive(Debug, PartialEq)]
+pub struct ExternalProof<'a> {
+    pub r#type: &'a str,
+    purpose: ProofPurpose,
+    content: Dataset<'a>,
+}
+
+impl<'a> ExternalProof<'a> {
+    /// Tells if the proof can be used for the given purpose, the binding between the proof and the
+    /// issuer being left to the external verifier.
+    pub fn suitable(&self, purpose: ProofPurpose) -> bool {
+        self.purpose == purpose
+    }
+
+    pub fn content(&'a self) -> &'a [Quad<'a>] {
+        self.content.as_ref()
+    }
+}
+
+impl<'a> TryFrom<(&'a Dataset<'a>, GraphName<'a>)> for ExternalProof<'a> {
+    type Error = InvalidProofError;
+
+    fn try_from(
+        (dataset, proof_graph): (&'a Dataset<'a>, GraphName<'a>),
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            r#type: Proof::extract_type(dataset, proof_graph)?,
+            purpose: Proof::extract_proof_purpose(dataset, proof_graph)?.into(),
+            content: Dataset::new(
+                dataset
+                    .match_pattern(None, None, None, Some(Some(proof_graph)))
+                    .map(|quad| Quad {
+                        subject: quad.subject,
+                        predicate: quad.predicate,
+                        object: quad.object,
+                        graph_name: None,
+                    })
+                    .collect(),
+            ),
+        })
+    }
+}
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum ProofPurpose {
     AssertionMethod,
@@ -657,4 +707,25 @@ mod test {
             assert_eq!(proof_res, expected)
         }
     }
+
+    #[test]
+    fn external_proof_from_dataset() {
+        let owned_quads = testutil::read_test_quads("proof-unsupported.nq");
+        let dataset = Dataset::from(owned_quads.as_slice());
+
+        let proof =
+            ExternalProof::try_from((&dataset, GraphName::BlankNode(BlankNode { id: "b0" })))
+                .unwrap();
+        assert_eq!(proof.r#type, "https://w3id.org/security#Unknown");
+        assert!(proof.suitable(ProofPurpose::AssertionMethod));
+        assert_eq!(proof.content().len(), 5);
+        assert!(proof.content().iter().all(|q| q.graph_name.is_none()));
+
+        let owned_quads = testutil::read_test_quads("proof-missing-type.nq");
+        let dataset = Dataset::from(owned_quads.as_slice());
+        assert_eq!(
+            ExternalProof::try_from((&dataset, GraphName::BlankNode(BlankNode { id: "b0" }))),
+            Err(InvalidProofError::MissingProofType)
+        );
+    }
 }
@@ -1,11 +1,14 @@
 use crate::credential::error::{InvalidCredentialError, InvalidProofError, VerificationError};
-use crate::credential::proof::{Proof, ProofPurpose};
+use crate::credential::proof::{ExternalProof, Proof, ProofPurpose};
 use crate::credential::rdf_marker::*;
+use crate::msg::{ProofVerifierQueryMsg, VerifyProofResponse};
 use axone_rdf::dataset::QuadIterator;
 use axone_rdf::dataset::{Dataset, QuadPattern};
-use cosmwasm_std::DepsMut;
+use axone_rdf::normalize::Normalizer;
+use cosmwasm_std::{Addr, Binary, DepsMut};
 use itertools::Itertools;
-use rio_api::model::{BlankNode, Literal, NamedNode, Subject, Term};
+use rio_api::model::{Literal, NamedNode, Quad, Subject, Term};
+use sha2::{Digest, Sha256};
 
 #[derive(Debug, PartialEq)]
 pub struct VerifiableCredential<'a> {
@@ -17,6 +20,7 @@ pub struct VerifiableCredential<'a> {
     pub claims: Vec<Claim<'a>>,
     pub status: Option<Status<'a>>,
     pub proof: Vec<Proof<'a>>,
+    pub external_proof: Vec<ExternalProof<'a>>,
     unsecured_document: Dataset<'a>,
 }
 
@@ -39,12 +43,17 @@ impl<'a> TryFrom<&'a Dataset<'a>> for VerifiableCredential<'a> {
     fn try_from(dataset: &'a Dataset<'a>) -> Result<Self, Self::Error> {
         let id = Self::extract_identifier(dataset)?;
 
-        let (proofs, proof_graphs): (Vec<Proof<'a>>, Vec<BlankNode<'a>>) =
-            Self::extract_proofs(dataset, id)?.into_iter().unzip();
+        let (proofs, external_proofs) = Self::extract_proofs(dataset, id)?;
 
-        let mut unsecured_filter: Vec<QuadPattern<'_>> = proof_graphs
-            .into_iter()
-            .map(|g| (None, None, None, Some(Some(g.into()))).into())
+        // All the proof graphs are excluded, including the ignored ones, so the unsecured document
+        // and its canonical hash don't depend on the proofs the dataverse is able to interpret.
+        let mut unsecured_filter: Vec<QuadPattern<'_>> = dataset
+            .match_pattern(Some(id.into()), Some(VC_RDF_PROOF), None, None)
+            .objects()
+            .filter_map(|o| match o {
+                Term::BlankNode(n) => Some((None, None, None, Some(Some(n.into()))).into()),
+                _ => None,
+            })
             .collect();
 
         unsecured_filter.push((Some(id.into()), Some(VC_RDF_PROOF), None, None).into());
@@ -58,6 +67,7 @@ impl<'a> TryFrom<&'a Dataset<'a>> for VerifiableCredential<'a> {
             claims: Self::extract_claims(dataset, id)?,
             status: Self::extract_status(dataset, id)?,
             proof: proofs,
+            external_proof: external_proofs,
             unsecured_document: Dataset::new(
                 dataset
                     .iter()
@@ -87,6 +97,56 @@ impl<'a> VerifiableCredential<'a> {
         )
     }
 
+    /// Returns the proofs of a type not natively supported which are suitable to assert the
+    /// credential, to be verified through [VerifiableCredential::verify_external].
+    pub fn external_proofs(&self) -> impl Iterator<Item = &ExternalProof<'a>> {
+        self.external_proof
+            .iter()
+            .filter(|p| p.suitable(ProofPurpose::AssertionMethod))
+    }
+
+    /// Delegates the verification of an external proof to the given verifier contract, providing it
+    /// the unsecured document and the proof as N-Quads.
+    pub fn verify_external(
+        &self,
+        deps: &'_ DepsMut<'_>,
+        proof: &ExternalProof<'a>,
+        verifier: &Addr,
+    ) -> Result<(), VerificationError> {
+        let VerifyProofResponse { verified } = deps.querier.query_wasm_smart(
+            verifier,
+            &ProofVerifierQueryMsg::VerifyProof {
+                proof_type: proof.r#type.to_string(),
+                issuer: self.issuer.to_string(),
+                document: Self::to_nquads(self.unsecured_document.as_ref()),
+                proof: Self::to_nquads(proof.content()),
+            },
+        )?;
+
+        if !verified {
+            Err(VerificationError::ExternalProofRejected(
+                proof.r#type.to_string(),
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn to_nquads(quads: &[Quad<'_>]) -> Binary {
+        Binary::new(
+            quads
+                .iter()
+                .map(|q| format!("{q} .\n"))
+                .join("")
+                .into_bytes(),
+        )
+    }
+
+    /// Returns the SHA-256 hash of the URDNA2015 canonical form of the credential, proofs excluded.
+    pub fn canonical_hash(&self) -> Result<Vec<u8>, VerificationError> {
+        let canonical = Normalizer::new().normalize(self.unsecured_document.as_ref())?;
+        Ok(Sha256::digest(canonical).to_vec())
+    }
+
     fn extract_identifier(
         dataset: &'a Dataset<'a>,
     ) -> Result<NamedNode<'a>, InvalidCredentialError> {
@@ -261,27 +321,31 @@ impl<'a> VerifiableCredential<'a> {
     fn extract_proofs(
         dataset: &'a Dataset<'a>,
         id: NamedNode<'a>,
-    ) -> Result<Vec<(Proof<'a>, BlankNode<'a>)>, InvalidCredentialError> {
-        dataset
+    ) -> Result<(Vec<Proof<'a>>, Vec<ExternalProof<'a>>), InvalidCredentialError> {
+        let mut proofs = Vec::new();
+        let mut external_proofs = Vec::new();
+        for o in dataset
             .match_pattern(Some(id.into()), Some(VC_RDF_PROOF), None, None)
             .objects()
-            .filter_map(|o| match o {
-                Term::BlankNode(n) => {
-                    let proof_res = Proof::try_from((dataset, n.into()));
-                    match proof_res {
-                        Err(InvalidProofError::Unsupported) => None,
-                        _ => Some(
-                            proof_res
-                                .map(|p| (p, n))
-                                .map_err(InvalidCredentialError::from),
-                        ),
+        {
+            let Term::BlankNode(n) = o else {
+                Err(InvalidCredentialError::Malformed(
+                    "Credential proof must be encapsulated in blank node graph names".to_string(),
+                ))?
+            };
+            match Proof::try_from((dataset, n.into())) {
+                Ok(proof) => proofs.push(proof),
+                Err(InvalidProofError::Unsupported) => {
+                    // Unsupported proofs which can't be handed to an external verifier are ignored.
+                    if let Ok(proof) = ExternalProof::try_from((dataset, n.into())) {
+                        external_proofs.push(proof);
                     }
                 }
-                _ => Some(Err(InvalidCredentialError::Malformed(
-                    "Credential proof must be encapsulated in blank node graph names".to_string(),
-                ))),
-            })
-            .collect()
+                Err(e) => Err(e)?,
+            }
+        }
+
+        Ok((proofs, external_proofs))
     }
 }
 
@@ -290,7 +354,7 @@ mod test {
     use super::*;
     use crate::testutil::testutil;
     use cosmwasm_std::testing::mock_dependencies;
-    use rio_api::model::Quad;
+    use rio_api::model::BlankNode;
 
     #[test]
     fn proper_vc_from_dataset() {
@@ -366,6 +430,19 @@ mod test {
         assert_eq!(vc.unsecured_document, unsecure_dataset);
     }
 
+    #[test]
+    fn ignore_malformed_unsupported_proof() {
+        let owned_quads = testutil::read_test_quads("vc-malformed-external-proof.nq");
+        let dataset = Dataset::from(owned_quads.as_slice());
+
+        let vc = VerifiableCredential::try_from(&dataset).unwrap();
+        assert_eq!(vc.proof.len(), 1usize);
+        assert!(vc.external_proof.is_empty());
+
+        let owned_quads = testutil::read_test_quads("vc-eddsa-2020-ok-unsecured.nq");
+        assert_eq!(vc.unsecured_document, Dataset::from(owned_quads.as_slice()));
+    }
+
     #[test]
     fn vc_verify() {
         let cases = vec![
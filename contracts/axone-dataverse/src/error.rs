@@ -27,6 +27,36 @@ pub enum ContractError {
     #[error("Credential already exists: '{0}'")]
     CredentialAlreadyExists(String),
 
+    #[error("Invalid revocation credential: '{0}'")]
+    InvalidRevocation(String),
+
+    #[error("Invalid address linkage credential: '{0}'")]
+    InvalidAddressLinkage(String),
+
+    #[error("Invalid holder key: '{0}'")]
+    InvalidHolderKey(String),
+
+    #[error("Secondary indexes are being rebuilt")]
+    IndexRebuildInProgress,
+
+    #[error("Dataverse is paused")]
+    Paused,
+
+    #[error("Override {0} is timelocked until {1}")]
+    OverrideTimelocked(u64, u64),
+
+    #[error("Override {0} was queued by a guardian no longer appointed")]
+    OverrideGuardianRevoked(u64),
+
+    #[error("Invalid guardian: '{0}'")]
+    InvalidGuardian(String),
+
+    #[error("Invalid anchoring: '{0}'")]
+    InvalidAnchoring(String),
+
+    #[error("Unauthorized")]
+    Unauthorized,
+
     #[error("An unexpected error occurred: {0}")]
     Unexpected(String),
 
@@ -1,5 +1,6 @@
+use axone_cognitarium::msg::Value;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, Uint128, Uint64};
+use cosmwasm_std::{Addr, Binary, HexBinary, Timestamp, Uint128, Uint64};
 
 /// `InstantiateMsg` is used to initialize a new instance of the dataverse.
 #[cw_serde]
@@ -11,6 +12,22 @@ pub struct InstantiateMsg {
     pub triplestore_config: TripleStoreConfig,
 }
 
+/// `MigrateMsg` is used to migrate the dataverse to a new version.
+#[cw_serde]
+#[derive(Default)]
+pub struct MigrateMsg {
+    /// Whether to rebuild the secondary indexes of the claim registry (i.e. the submission
+    /// sequence, the expiring credentials, the credentials by issuer and by holder, and the state
+    /// digest if none is maintained yet) from the triple store, to recover from their corruption or
+    /// to adopt a triple store populated beforehand.
+    ///
+    /// The triple store is scanned in batches, the first one during the migration and the following
+    /// ones through [ExecuteMsg::RebuildIndexes], the submission of claims being rejected until the
+    /// rebuild completes.
+    #[serde(default)]
+    pub rebuild_indexes: bool,
+}
+
 /// `ExecuteMsg` defines the set of possible actions that can be performed on the dataverse.
 ///
 /// This enum provides variants for registering services, datasets, and other operations related to the dataverse.
@@ -45,6 +62,19 @@ pub enum ExecuteMsg {
     ///
     ///   4. **Content**: The actual implementation supports the submission of a single Verifiable Credential, containing a single claim.
     ///
+    /// #### Submitter
+    ///
+    /// The submitter address is recorded along with its canonical form and bech32 prefix, relating the submissions of a
+    /// same account whatever the prefix it is given with, and the holder DID it is linked to, if any.
+    ///
+    /// An address is linked to a holder by submitting from it an `AddressLinkageCredential` issued by the holder about
+    /// itself and claiming the address through the `hasAddress` property, see [QueryMsg::AddressHolder].
+    ///
+    /// #### Receipt
+    ///
+    /// On success, a JSON-encoded [SubmitClaimsReceipt] is set as the response data, allowing integrators to keep a
+    /// verifiable record of the registration from the transaction result alone.
+    ///
     /// #### Supported cryptographic proofs
     ///
     /// - `Ed25519Signature2018`
@@ -55,6 +85,9 @@ pub enum ExecuteMsg {
     ///
     /// - `DataIntegrity` with the following cryptosuites: `eddsa-2022`, `eddsa-rdfc-2022`.
     ///
+    /// Other proof types are verified by the external verifier registered for them, if any, see
+    /// [ExecuteMsg::RegisterProofVerifier].
+    ///
     SubmitClaims {
         /// The Verifiable Credential containing the claims.
         /// The claims must be serialized in the format specified by the `format` field.
@@ -74,6 +107,349 @@ pub enum ExecuteMsg {
         /// The unique identifier of the claims to be revoked.
         identifier: Uri,
     },
+
+    /// # RegisterProofVerifier
+    /// Registers an external verifier contract for a proof type not natively supported by the
+    /// dataverse (e.g. zero-knowledge proofs), replacing any verifier previously registered for it.
+    ///
+    /// When a submitted credential holds no suitable natively supported proof, its proofs whose type
+    /// has a registered verifier are verified by querying the verifier with a
+    /// [ProofVerifierQueryMsg::VerifyProof], the credential being accepted on success.
+    ///
+    /// Only the admin of the dataverse contract (i.e. the governance) can register verifiers.
+    RegisterProofVerifier {
+        /// The type of the proof, as the IRI found in the proof graph (e.g. `https://w3id.org/security#BbsBlsSignature2020`).
+        proof_type: Uri,
+        /// The address of the verifier contract, implementing the [ProofVerifierQueryMsg] interface.
+        verifier: String,
+    },
+
+    /// # UnregisterProofVerifier
+    /// Removes the external verifier registered for a proof type, credentials only holding proofs
+    /// of this type being no longer verifiable.
+    ///
+    /// Only the admin of the dataverse contract (i.e. the governance) can unregister verifiers.
+    UnregisterProofVerifier {
+        /// The type of the proof whose verifier is removed.
+        proof_type: Uri,
+    },
+
+    /// # RegisterPeer
+    /// Registers a peer dataverse whose claims are included in the [QueryMsg::FederatedClaims]
+    /// query. The peer must implement the dataverse [QueryMsg::Claims] query.
+    ///
+    /// Only the admin of the dataverse contract (i.e. the governance) can register peers.
+    RegisterPeer {
+        /// The address of the peer dataverse contract.
+        address: String,
+    },
+
+    /// # UnregisterPeer
+    /// Removes a peer dataverse from the registry.
+    ///
+    /// Only the admin of the dataverse contract (i.e. the governance) can unregister peers.
+    UnregisterPeer {
+        /// The address of the peer dataverse contract.
+        address: String,
+    },
+
+    /// # SetGovernance
+    /// Sets the governance law stone consulted to decide whether the usage policies of a resource
+    /// allow a purpose when none of them explicitly allows it, see [ClaimsFilter::purpose]. The
+    /// governance is removed if no law stone is provided.
+    ///
+    /// Only the admin of the dataverse contract (i.e. the governance) can set it.
+    SetGovernance {
+        /// The address of the `axone-law-stone` contract.
+        law_stone: Option<String>,
+    },
+
+    /// # Tick
+    /// Notifies the submitted credentials expiring within the next 7 days, so their issuers and
+    /// holders can renew them before they vanish from the validity-filtered queries.
+    ///
+    /// The credentials are processed in the order of their expiration date, each one being notified
+    /// once through a `credential_expiring_soon` event carrying its `credential` identifier, its
+    /// `issuer`, its claim `subject` and its `valid_until` expiration date. The number of notified
+    /// credentials is reported in the `notified_count` attribute of the response.
+    ///
+    /// Anyone can invoke this message.
+    Tick {
+        /// The maximum number of credentials to notify, 10 by default and at most 30.
+        limit: Option<u32>,
+    },
+
+    /// # RebuildIndexes
+    /// Continues the rebuild of the secondary indexes started by a migration, see
+    /// [MigrateMsg::rebuild_indexes].
+    ///
+    /// Each call scans the next batch of triples of the triple store, counting the credentials in
+    /// the submission sequence, indexing the ones not expired yet for their notification (see
+    /// [ExecuteMsg::Tick]) and indexing the ones missing from the credentials by issuer (see
+    /// [QueryMsg::ClaimsByIssuer]) and by holder, their canonical hash being left empty as it can't
+    /// be recovered. If no state digest was maintained before the rebuild, the credentials are
+    /// chained in it as submissions. The number of credentials indexed by the call is reported in the
+    /// `indexed_credential_count` attribute of the response, and the `completed` attribute tells
+    /// whether the whole triple store has been scanned.
+    ///
+    /// Anyone can invoke this message while a rebuild is in progress.
+    RebuildIndexes {
+        /// The maximum number of triples to scan.
+        /// If not provided, the maximum query limit of the triple store is used, which it can't exceed.
+        limit: Option<u32>,
+    },
+
+    /// # RegisterGateway
+    /// Registers a consumer gateway allowed to record the accesses to the registered resources
+    /// through [ExecuteMsg::RecordAccess].
+    ///
+    /// Only the admin of the dataverse contract (i.e. the governance) can register gateways.
+    RegisterGateway {
+        /// The address of the gateway.
+        address: String,
+    },
+
+    /// # UnregisterGateway
+    /// Removes a consumer gateway, which can no longer record accesses.
+    ///
+    /// Only the admin of the dataverse contract (i.e. the governance) can unregister gateways.
+    UnregisterGateway {
+        /// The address of the gateway.
+        address: String,
+    },
+
+    /// # RecordAccess
+    /// Records an access to the resource described by a registered credential, incrementing its
+    /// access counter (see [QueryMsg::AccessCount]) so data providers get usage signals for their
+    /// resources.
+    ///
+    /// The counters are anonymous: only the gateway serving the access is known, not who accessed
+    /// the resource. The new value of the counter is reported in the `access_count` attribute of
+    /// the response.
+    ///
+    /// Only the registered consumer gateways (see [ExecuteMsg::RegisterGateway]) can record
+    /// accesses.
+    RecordAccess {
+        /// The identifier of the credential.
+        credential_id: Uri,
+    },
+
+    /// # RevokeAllByIssuer
+    /// Revokes the credentials submitted by an issuer, e.g. in response to the compromise of its
+    /// keys, see [QueryMsg::ClaimsByIssuer] to list them.
+    ///
+    /// The issuer proves its will through a revocation credential it signed, of type
+    /// `https://w3id.org/axone/ontology/vnext/schema/credential/issuer-revocation/IssuerRevocationCredential`
+    /// and whose subject is the issuer itself. Its issuance date can't be in the future, and only
+    /// the credentials submitted up to it are revoked, so it can't be replayed against the ones
+    /// submitted afterwards. Once all the credentials of the issuer have been scanned, the
+    /// revocation credential is rejected if submitted again.
+    ///
+    /// The credentials are revoked in batches, each call scanning the credentials of the issuer
+    /// following the ones scanned by the previous call with the same revocation credential. Their
+    /// triples are removed from the triple store and each revocation chained in the state digest
+    /// (see [QueryMsg::StateDigest]). The revoked credentials are notified through
+    /// `credential_revoked` events carrying their `credential` identifier. The number of revoked
+    /// credentials is reported in the `revoked_count` attribute of the response, and the
+    /// `completed` attribute tells whether all the credentials of the issuer have been scanned.
+    ///
+    /// Note: only the triples of the credential and of its claim node are removed, the nested
+    /// nodes of the claim being left unreachable.
+    ///
+    /// Anyone holding the revocation credential can invoke this message.
+    RevokeAllByIssuer {
+        /// The DID of the issuer.
+        did: Uri,
+        /// The revocation credential signed by the issuer, serialized as N-Quads.
+        revocation: Binary,
+        /// The maximum number of credentials of the issuer to scan, 10 by default and at most 30.
+        limit: Option<u32>,
+    },
+
+    /// # SetGuardian
+    /// Sets the guardian allowed to queue emergency overrides (see [ExecuteMsg::QueueOverride])
+    /// along with the delay they're timelocked for, at least an hour. The guardian is removed if
+    /// none is provided, the overrides it already queued no longer being executable once it is
+    /// removed or replaced by another guardian, until they're cancelled.
+    ///
+    /// Only the admin of the dataverse contract (i.e. the governance) can set it.
+    SetGuardian {
+        /// The address of the guardian.
+        guardian: Option<String>,
+        /// The delay, in seconds, between the queuing of an override and its earliest execution.
+        delay: Uint64,
+    },
+
+    /// # QueueOverride
+    /// Queues an emergency override, a recourse against a defective governance (e.g. a law stone
+    /// granting unwanted usages) the governance can't fix by itself. The override can be executed
+    /// through [ExecuteMsg::ExecuteOverride] once the delay set along the guardian has elapsed,
+    /// unless the governance cancels it meanwhile through [ExecuteMsg::CancelOverride].
+    ///
+    /// The override is recorded in the triple store as the `dataverse:override:<id>` resource,
+    /// whose `dataverse:override#status` follows its lifecycle: `queued`, then `executed` or
+    /// `cancelled`. It is notified through an `override_queued` event carrying its `id`, its
+    /// `action` and the `executable_at` time, in seconds.
+    ///
+    /// Only the guardian (see [ExecuteMsg::SetGuardian]) can queue overrides.
+    QueueOverride {
+        /// The action the override performs.
+        action: OverrideAction,
+    },
+
+    /// # CancelOverride
+    /// Cancels a queued emergency override, notified through an `override_cancelled` event
+    /// carrying its `id`.
+    ///
+    /// Only the admin of the dataverse contract (i.e. the governance) can cancel overrides.
+    CancelOverride {
+        /// The identifier of the override.
+        id: Uint64,
+    },
+
+    /// # ExecuteOverride
+    /// Executes a queued emergency override whose delay has elapsed, notified through an
+    /// `override_executed` event carrying its `id` and its `action`. The override is rejected if
+    /// the guardian who queued it is no longer the guardian.
+    ///
+    /// Anyone can invoke this message.
+    ExecuteOverride {
+        /// The identifier of the override.
+        id: Uint64,
+    },
+
+    /// # SetAnchoring
+    /// Sets the periodic anchoring of the state digest (see [QueryMsg::StateDigest]), allowing
+    /// off-chain mirrors of the claim registry to checkpoint their sync and third parties to verify
+    /// the state a mirror claims against the anchors. The anchoring is disabled if none is provided,
+    /// the anchors already stored being kept.
+    ///
+    /// Every `interval` accepted claim submissions, an anchor carrying the state digest along with
+    /// the range of operation sequence numbers it covers since the previous anchor is stored, the
+    /// last `retention` ones being kept (see [QueryMsg::Anchors]), and notified through an `anchor`
+    /// event carrying its `from_sequence`, `to_sequence` and `digest`.
+    ///
+    /// Only the admin of the dataverse contract (i.e. the governance) can set it.
+    SetAnchoring {
+        /// The anchoring configuration.
+        anchoring: Option<Anchoring>,
+    },
+
+    /// # OpenHolderSession
+    /// Opens a session for the holder of a verification key to retrieve the claims bound to it
+    /// through [ExecuteMsg::RetrieveHolderClaims].
+    ///
+    /// A claim is bound to its holder by declaring the property
+    /// `https://w3id.org/axone/ontology/vnext/schema/credential/holder-binding/holderKey`, whose
+    /// value is the hex-encoded public key of the holder: a 32 bytes Ed25519 key or a compressed
+    /// secp256k1 one. The bound claims are filtered out of the listing of the [QueryMsg::Claims]
+    /// query, which is no access control: their triples are stored in the triple store as any
+    /// other claim, hence public.
+    /// The retrieval doesn't keep them private either: the listed claims are set in the data of a
+    /// transaction, which anyone can read once executed.
+    ///
+    /// The session is given a fresh nonce the holder has to sign to prove the possession of the key,
+    /// so a presentation made for a session can't be replayed in another one. Only the address
+    /// opening the session can retrieve its claims. The session expires 5 minutes after its
+    /// opening, the expired ones being pruned as new sessions are opened. The session is reported
+    /// in the `session` and `nonce` attributes of the response, and in the [HolderSessionReceipt]
+    /// set in its data.
+    ///
+    /// Anyone can invoke this message.
+    OpenHolderSession {
+        /// The public key of the holder.
+        holder_key: HexBinary,
+    },
+
+    /// # RetrieveHolderClaims
+    /// Lists a page of the claims bound to the holder of a session opened through
+    /// [ExecuteMsg::OpenHolderSession], ordered by credential identifier, provided the signature
+    /// by the holder key of the SHA-256 digest of the session nonce followed by the address of the
+    /// sender, so a signature intercepted can't be presented by another address. The claims are
+    /// set in the data of the response as a JSON-encoded [ClaimsResponse], which is public once
+    /// the transaction executed.
+    ///
+    /// Ed25519 keys sign the digest itself, while secp256k1 ones sign it as a prehashed message.
+    /// The `completed` attribute of the response tells whether the last page has been listed, the
+    /// session being closed then so its nonce can't be presented again. Fails if the session is
+    /// unknown, expired or already closed, or if the signature doesn't verify.
+    ///
+    /// Only the address which opened the session can invoke this message.
+    RetrieveHolderClaims {
+        /// The identifier of the session.
+        session: Uint64,
+        /// The signature of the digest of the session nonce and of the sender address by the
+        /// holder key.
+        signature: HexBinary,
+        /// The maximum number of claims to list, 10 by default and at most 30.
+        first: Option<u32>,
+        /// The identifier of the credential to start after, the first page being listed if not
+        /// provided.
+        after: Option<Uri>,
+    },
+}
+
+/// # Anchoring
+/// The periodic anchoring of the state digest, see [ExecuteMsg::SetAnchoring].
+#[cw_serde]
+pub struct Anchoring {
+    /// The number of accepted claim submissions between two anchors, must be positive.
+    pub interval: u32,
+    /// The number of anchors kept, the oldest ones being removed, between 1 and 100.
+    pub retention: u32,
+}
+
+/// # OverrideAction
+/// The action of an emergency override, see [ExecuteMsg::QueueOverride].
+#[cw_serde]
+pub enum OverrideAction {
+    /// # Pause
+    /// Pauses the dataverse, the submission of claims being rejected until it is resumed.
+    Pause {},
+    /// # Resume
+    /// Resumes a paused dataverse.
+    Resume {},
+    /// # RevokeClaims
+    /// Revokes a registered credential, as [ExecuteMsg::RevokeAllByIssuer] does.
+    RevokeClaims {
+        /// The identifier of the credential.
+        identifier: Uri,
+    },
+    /// # SetGovernance
+    /// Sets or removes the governance law stone, as [ExecuteMsg::SetGovernance] does.
+    SetGovernance {
+        /// The address of the `axone-law-stone` contract.
+        law_stone: Option<String>,
+    },
+}
+
+/// # ProofVerifierQueryMsg
+/// `ProofVerifierQueryMsg` is the query interface external proof verifier contracts must implement.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum ProofVerifierQueryMsg {
+    /// # VerifyProof
+    /// Verifies a proof of a credential, the verifier being responsible for checking the proof is
+    /// bound to the issuer.
+    #[returns(VerifyProofResponse)]
+    VerifyProof {
+        /// The type of the proof.
+        proof_type: Uri,
+        /// The issuer of the credential.
+        issuer: Uri,
+        /// The credential with its proofs removed, serialized as N-Quads.
+        document: Binary,
+        /// The content of the proof graph, serialized as N-Quads in the default graph.
+        proof: Binary,
+    },
+}
+
+/// # VerifyProofResponse
+/// `VerifyProofResponse` is the response of the [ProofVerifierQueryMsg::VerifyProof] query.
+#[cw_serde]
+pub struct VerifyProofResponse {
+    /// Whether the proof has been successfully verified.
+    pub verified: bool,
 }
 
 /// # TripleStoreConfig
@@ -119,6 +495,29 @@ pub struct TripleStoreLimitsInput {
     /// The maximum number of triples an insert data query can contain (after parsing).
     /// Default to [Uint128::MAX] if not set, which can be considered as no limit.
     pub max_insert_data_triple_count: Option<Uint128>,
+    /// The maximum number of triple patterns the where clause of a query can contain.
+    /// Default to 30 if not set.
+    pub max_query_pattern_count: Option<u32>,
+    /// The maximum nesting depth of the where clause of a query.
+    /// Default to 10 if not set.
+    pub max_query_where_depth: Option<u32>,
+    /// The maximum number of join variables of the where clause of a query, i.e. of the variables
+    /// shared by several of its triple patterns.
+    /// Default to 20 if not set.
+    pub max_query_join_variable_count: Option<u32>,
+    /// The maximum number of class and property hierarchy levels a query can infer over.
+    /// Default to 5 if not set.
+    pub max_query_inference_depth: Option<u32>,
+    /// The maximum number of bytes of the serialized response of a query.
+    /// Default to [Uint128::MAX] if not set, which can be considered as no limit.
+    pub max_result_byte_size: Option<Uint128>,
+    /// The maximum number of distinct blank nodes an insert data query can contain.
+    /// Default to [Uint128::MAX] if not set, which can be considered as no limit.
+    ///
+    /// The submitted claims are only checked against it when inserted in the triple store, the
+    /// credential being parsed and verified as a whole beforehand: the memory this takes is only
+    /// bounded by the size of the transaction.
+    pub max_insert_blank_node_count: Option<Uint128>,
 }
 
 impl From<TripleStoreLimitsInput> for axone_cognitarium::msg::StoreLimitsInput {
@@ -145,6 +544,24 @@ impl From<TripleStoreLimitsInput> for axone_cognitarium::msg::StoreLimitsInput {
         if let Some(max_insert_data_triple_count) = value.max_insert_data_triple_count {
             limits.max_insert_data_triple_count = max_insert_data_triple_count;
         }
+        if let Some(max_query_pattern_count) = value.max_query_pattern_count {
+            limits.max_query_pattern_count = max_query_pattern_count;
+        }
+        if let Some(max_query_where_depth) = value.max_query_where_depth {
+            limits.max_query_where_depth = max_query_where_depth;
+        }
+        if let Some(max_query_join_variable_count) = value.max_query_join_variable_count {
+            limits.max_query_join_variable_count = max_query_join_variable_count;
+        }
+        if let Some(max_query_inference_depth) = value.max_query_inference_depth {
+            limits.max_query_inference_depth = max_query_inference_depth;
+        }
+        if let Some(max_result_byte_size) = value.max_result_byte_size {
+            limits.max_result_byte_size = max_result_byte_size;
+        }
+        if let Some(max_insert_blank_node_count) = value.max_insert_blank_node_count {
+            limits.max_insert_blank_node_count = max_insert_blank_node_count;
+        }
 
         limits
     }
@@ -182,6 +599,464 @@ pub enum QueryMsg {
     /// Retrieves information about the current dataverse instance.
     #[returns(DataverseResponse)]
     Dataverse {},
+
+    /// # CompareClaims
+    /// Compares the claims of two registered credentials, typically an original credential and its
+    /// amendment, returning the claim properties added, removed and changed from the first one to
+    /// the second one.
+    ///
+    /// Only the properties directly held by the claims are compared, nested claim nodes being
+    /// compared by their position in the claim and not by their content. The comparison fails if
+    /// the claims of a credential hold as many properties as the query limit of the triple store,
+    /// as they may not all be retrieved.
+    #[returns(CompareClaimsResponse)]
+    CompareClaims {
+        /// The identifier of the credential to compare from.
+        credential_a: Uri,
+        /// The identifier of the credential to compare to.
+        credential_b: Uri,
+    },
+
+    /// # StateDigest
+    /// Retrieves the rolling digest of the claim registry, chaining every claim submission and
+    /// revocation in sequence, allowing indexers and auditors to verify they reconstructed the same
+    /// registry from the events.
+    ///
+    /// Starting from 32 zero bytes, each operation replaces the digest by the SHA-256 of the
+    /// concatenation of:
+    ///
+    ///  1. the previous digest;
+    ///  2. the sequence number of the operation, starting from 1, as a big-endian `u64`;
+    ///  3. the operation tag, `0` for a submission and `1` for a revocation, as a single byte;
+    ///  4. the length of the credential identifier as a big-endian `u32`, followed by the identifier;
+    ///  5. the canonical hash of the credential, as found in the [SubmitClaimsReceipt].
+    #[returns(StateDigestResponse)]
+    StateDigest {},
+
+    /// # Anchors
+    /// Retrieves the anchoring configuration, if any, along with the anchors of the state digest
+    /// kept (see [ExecuteMsg::SetAnchoring]), ordered by sequence.
+    #[returns(AnchorsResponse)]
+    Anchors {},
+
+    /// # ProofVerifiers
+    /// Lists the external proof verifiers registered in the dataverse, ordered by proof type.
+    #[returns(ProofVerifiersResponse)]
+    ProofVerifiers {},
+
+    /// # Claims
+    /// Lists the credentials registered in the dataverse matching the filter, bounded by the query
+    /// limit of the triple store.
+    ///
+    /// The credentials matching the subject, type and issuer criteria are selected from the triple
+    /// store, up to its maximum query limit, before being filtered by the dataverse: the ones
+    /// bound to their holder (see [ExecuteMsg::OpenHolderSession]) and, if a purpose is given, the
+    /// ones whose subject isn't allowed for it are left out. Hence, when the selection reaches the
+    /// limit, the listing may miss some matching credentials, even hold none, which the
+    /// `truncated` flag of the response reports; the criteria should then be narrowed.
+    ///
+    /// This filtering only shapes the listing and is no access control: the triples of all the
+    /// credentials remain readable from the triple store.
+    #[returns(ClaimsResponse)]
+    Claims {
+        /// The criteria the credentials must match.
+        filter: ClaimsFilter,
+    },
+
+    /// # FederatedClaims
+    /// Lists the credentials matching the filter across the dataverse and its registered peers
+    /// (see [ExecuteMsg::RegisterPeer]), each one annotated with the dataverse it originates from.
+    ///
+    /// The peers are queried with the [QueryMsg::Claims] query in the order of their address, the
+    /// ones failing to answer being reported instead of failing the whole query.
+    #[returns(FederatedClaimsResponse)]
+    FederatedClaims {
+        /// The criteria the credentials must match.
+        filter: ClaimsFilter,
+        /// The maximum number of peers to query, 10 by default and at most 30.
+        max_peers: Option<u32>,
+    },
+
+    /// # Peers
+    /// Lists the peer dataverses registered in the dataverse, ordered by address.
+    #[returns(PeersResponse)]
+    Peers {},
+
+    /// # AccessCount
+    /// Retrieves the number of accesses recorded for a credential by the consumer gateways, see
+    /// [ExecuteMsg::RecordAccess].
+    #[returns(AccessCountResponse)]
+    AccessCount {
+        /// The identifier of the credential.
+        credential_id: Uri,
+    },
+
+    /// # Gateways
+    /// Lists the consumer gateways registered in the dataverse, ordered by address.
+    #[returns(GatewaysResponse)]
+    Gateways {},
+
+    /// # ClaimsByIssuer
+    /// Lists the credentials submitted by an issuer and not revoked, ordered by identifier.
+    #[returns(ClaimsByIssuerResponse)]
+    ClaimsByIssuer {
+        /// The DID of the issuer.
+        did: Uri,
+        /// The maximum number of credentials to return, 10 by default and at most 30.
+        first: Option<u32>,
+        /// The identifier of the credential to start after, the first page being returned if not
+        /// provided.
+        after: Option<Uri>,
+    },
+
+    /// # AddressHolder
+    /// Returns the holder DID an address is linked to through an address linkage credential.
+    ///
+    /// The address is only validated by the chain API, the dataverse not checking its bech32
+    /// prefix itself, and the link is looked up by the canonical form of the address.
+    #[returns(AddressHolderResponse)]
+    AddressHolder {
+        /// The address.
+        address: String,
+    },
+
+    /// # ClaimTemplate
+    /// Returns the shape of the claims of a credential type the dataverse interprets, i.e. the
+    /// properties it reads from them along with their expected values, and a skeleton JSON-LD
+    /// credential of that type for issuers to fill in.
+    ///
+    /// The credential types the dataverse doesn't interpret aren't supported, any claim being
+    /// accepted for them.
+    #[returns(ClaimTemplateResponse)]
+    ClaimTemplate {
+        /// The IRI of the credential type.
+        r#type: Uri,
+    },
+
+    /// # Emergency
+    /// Retrieves the state of the emergency path: the guardian along with the delay of its
+    /// overrides, whether the dataverse is paused and the overrides queued, ordered by identifier.
+    #[returns(EmergencyResponse)]
+    Emergency {
+        /// The identifier of the override to start after, the first page being returned if not
+        /// provided.
+        after: Option<Uint64>,
+        /// The maximum number of overrides to return, 10 by default and at most 30.
+        limit: Option<u32>,
+    },
+}
+
+/// # ClaimsFilter
+/// `ClaimsFilter` defines the criteria the credentials must match in a [QueryMsg::Claims] query,
+/// the ones not set matching any credential.
+#[cw_serde]
+#[derive(Default)]
+pub struct ClaimsFilter {
+    /// The subject the claims must be about.
+    pub subject: Option<Uri>,
+    /// The type of the credentials.
+    pub r#type: Option<Uri>,
+    /// The issuer of the credentials.
+    pub issuer: Option<Uri>,
+    /// The purpose the caller intends to use the subjects of the claims for, only keeping the
+    /// claims whose subject has usage policies allowing it.
+    ///
+    /// Usage policies (e.g. licenses, consent scopes) are claims about a resource held by
+    /// credentials of type `https://w3id.org/axone/ontology/vnext/schema/credential/usage-policy/UsagePolicyCredential`,
+    /// whose claim can declare the following properties, in the same namespace:
+    ///
+    /// - `allowedPurpose`: a purpose the resource is allowed to be used for;
+    /// - `license`: a license the resource is distributed under.
+    ///
+    /// A resource is allowed if one of its policies explicitly allows the purpose. Otherwise, and
+    /// if a governance law stone is set (see [ExecuteMsg::SetGovernance]), it is allowed if the
+    /// law stone grants the goal `usage_allowed(Resource, Purpose, Licenses)` through its `Verdict`
+    /// query, `Licenses` being the list of the licenses of its policies. A resource without any
+    /// usage policy is never allowed.
+    pub purpose: Option<Uri>,
+    /// The maximum number of `owl:sameAs` links to follow from the subject to find the resources
+    /// equivalent to it, the claims about them being returned as well.
+    ///
+    /// The links are declared by claims `<A> owl:sameAs <B>`, each linking the subject `A` of the
+    /// claim to the resource `B`, and are followed in either direction. The number of links is
+    /// capped to the `max_query_inference_depth` limit of the triple store. Ignored if no subject is
+    /// set.
+    pub same_as: Option<u32>,
+}
+
+/// # ClaimsResponse
+/// `ClaimsResponse` is the response of the [QueryMsg::Claims] query.
+#[cw_serde]
+pub struct ClaimsResponse {
+    /// The matching credentials.
+    pub claims: Vec<ClaimSummary>,
+    /// Whether the credentials have been selected from a truncated result of the triple store,
+    /// some matching credentials being possibly missing, see [QueryMsg::Claims].
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+/// # ClaimSummary
+/// `ClaimSummary` describes a credential registered in a dataverse.
+#[cw_serde]
+pub struct ClaimSummary {
+    /// The identifier of the credential.
+    pub credential: Uri,
+    /// The subject of the claim.
+    pub subject: Uri,
+    /// The type of the credential.
+    pub r#type: Uri,
+    /// The issuer of the credential.
+    pub issuer: Uri,
+}
+
+/// # FederatedClaimsResponse
+/// `FederatedClaimsResponse` is the response of the [QueryMsg::FederatedClaims] query.
+#[cw_serde]
+pub struct FederatedClaimsResponse {
+    /// The matching credentials, the ones of the dataverse first.
+    pub claims: Vec<FederatedClaim>,
+    /// The queried peers which failed to answer.
+    pub unreachable_peers: Vec<Addr>,
+    /// Whether the listing of the dataverse or of one of the peers has been truncated, see
+    /// [ClaimsResponse::truncated].
+    pub truncated: bool,
+}
+
+/// # FederatedClaim
+/// `FederatedClaim` is a credential along with the dataverse it is registered in.
+#[cw_serde]
+pub struct FederatedClaim {
+    /// The address of the dataverse the credential is registered in.
+    pub origin: Addr,
+    /// The credential.
+    pub claim: ClaimSummary,
+}
+
+/// # PeersResponse
+/// `PeersResponse` is the response of the [QueryMsg::Peers] query.
+#[cw_serde]
+pub struct PeersResponse {
+    /// The addresses of the registered peers.
+    pub peers: Vec<Addr>,
+}
+
+/// # AccessCountResponse
+/// `AccessCountResponse` is the response of the [QueryMsg::AccessCount] query.
+#[cw_serde]
+pub struct AccessCountResponse {
+    /// The identifier of the credential.
+    pub credential: Uri,
+    /// The number of recorded accesses.
+    pub count: Uint64,
+}
+
+/// # GatewaysResponse
+/// `GatewaysResponse` is the response of the [QueryMsg::Gateways] query.
+#[cw_serde]
+pub struct GatewaysResponse {
+    /// The addresses of the registered gateways.
+    pub gateways: Vec<Addr>,
+}
+
+/// # ClaimsByIssuerResponse
+/// `ClaimsByIssuerResponse` is the response of the [QueryMsg::ClaimsByIssuer] query.
+#[cw_serde]
+pub struct ClaimsByIssuerResponse {
+    /// The credentials of the page.
+    pub claims: Vec<IssuedClaim>,
+}
+
+/// # IssuedClaim
+/// `IssuedClaim` is a credential submitted by an issuer.
+#[cw_serde]
+pub struct IssuedClaim {
+    /// The identifier of the credential.
+    pub credential: Uri,
+    /// The SHA-256 hash of the URDNA2015 canonical form of the credential, proofs excluded.
+    /// Empty if the credential has been indexed by a rebuild of the indexes (see
+    /// [ExecuteMsg::RebuildIndexes]), as it can't be recovered from the triple store.
+    pub canonical_hash: HexBinary,
+    /// The block time of the submission.
+    pub submitted_at: Timestamp,
+}
+
+/// # AddressHolderResponse
+/// `AddressHolderResponse` is the response of the [QueryMsg::AddressHolder] query.
+#[cw_serde]
+pub struct AddressHolderResponse {
+    /// The DID of the holder the address is linked to, if any.
+    pub holder: Option<Uri>,
+}
+
+/// # ClaimTemplateResponse
+/// `ClaimTemplateResponse` is the response of the [QueryMsg::ClaimTemplate] query.
+#[cw_serde]
+pub struct ClaimTemplateResponse {
+    /// The IRI of the credential type.
+    pub r#type: Uri,
+    /// The IRIs of the JSON-LD contexts the credentials are expressed with: the verifiable
+    /// credentials one and the vocabulary of the credential type.
+    pub context: Vec<Uri>,
+    /// The properties of the claims.
+    pub properties: Vec<ClaimTemplateProperty>,
+    /// A skeleton JSON-LD credential of the type, the values to provide being left empty.
+    pub document: String,
+}
+
+/// # ClaimTemplateProperty
+/// A property of the claims of a credential type, see [ClaimTemplateResponse].
+#[cw_serde]
+pub struct ClaimTemplateProperty {
+    /// The predicate of the property.
+    pub predicate: Uri,
+    /// The datatype of the literal value of the property, the value being an IRI if not set.
+    pub datatype: Option<Uri>,
+    /// Whether the claims must hold the property.
+    pub required: bool,
+}
+
+/// # SubmitClaimsReceipt
+/// `SubmitClaimsReceipt` is the receipt of a [ExecuteMsg::SubmitClaims], JSON-encoded in the response data.
+#[cw_serde]
+pub struct SubmitClaimsReceipt {
+    /// The identifier of the submitted credential.
+    pub credential: Uri,
+    /// The SHA-256 hash of the URDNA2015 canonical form of the credential, proofs excluded.
+    pub canonical_hash: HexBinary,
+    /// The sequence number of the submission in the dataverse, starting from 1.
+    pub sequence: Uint64,
+    /// The address of the triple store the claims have been registered in.
+    pub triplestore_address: Addr,
+}
+
+/// # HolderSessionReceipt
+/// `HolderSessionReceipt` is the receipt of a [ExecuteMsg::OpenHolderSession], JSON-encoded in the
+/// response data.
+#[cw_serde]
+pub struct HolderSessionReceipt {
+    /// The identifier of the session.
+    pub session: Uint64,
+    /// The nonce the holder has to sign.
+    pub nonce: HexBinary,
+    /// The expiration time of the session.
+    pub expires_at: Timestamp,
+}
+
+/// # StateDigestResponse
+/// `StateDigestResponse` is the response of the [QueryMsg::StateDigest] query.
+#[cw_serde]
+pub struct StateDigestResponse {
+    /// The number of operations chained in the digest.
+    pub sequence: Uint64,
+    /// The SHA-256 rolling digest of the claim registry.
+    pub digest: HexBinary,
+}
+
+/// # AnchorsResponse
+/// `AnchorsResponse` is the response of the [QueryMsg::Anchors] query.
+#[cw_serde]
+pub struct AnchorsResponse {
+    /// The anchoring configuration, if enabled.
+    pub anchoring: Option<Anchoring>,
+    /// The anchors kept, oldest first.
+    pub anchors: Vec<Anchor>,
+}
+
+/// # Anchor
+/// `Anchor` is a checkpoint of the state digest, see [ExecuteMsg::SetAnchoring].
+#[cw_serde]
+pub struct Anchor {
+    /// The sequence number of the first operation chained since the previous anchor.
+    pub from_sequence: Uint64,
+    /// The sequence number of the last operation chained in the digest.
+    pub to_sequence: Uint64,
+    /// The state digest as of the last operation.
+    pub digest: HexBinary,
+    /// The block time of the anchoring.
+    pub anchored_at: Timestamp,
+}
+
+/// # ProofVerifiersResponse
+/// `ProofVerifiersResponse` is the response of the [QueryMsg::ProofVerifiers] query.
+#[cw_serde]
+pub struct ProofVerifiersResponse {
+    /// The registered verifiers.
+    pub verifiers: Vec<ProofVerifier>,
+}
+
+/// # ProofVerifier
+/// `ProofVerifier` is an external verifier registered for a proof type.
+#[cw_serde]
+pub struct ProofVerifier {
+    /// The type of the proofs the verifier handles.
+    pub proof_type: Uri,
+    /// The address of the verifier contract.
+    pub verifier: Addr,
+}
+
+/// # CompareClaimsResponse
+/// `CompareClaimsResponse` is the response of the [QueryMsg::CompareClaims] query.
+#[cw_serde]
+pub struct CompareClaimsResponse {
+    /// The properties only held by the claim of the second credential.
+    pub added: Vec<ClaimProperty>,
+    /// The properties only held by the claim of the first credential.
+    pub removed: Vec<ClaimProperty>,
+    /// The properties held by both claims with different values.
+    pub changed: Vec<ClaimPropertyChange>,
+}
+
+/// # ClaimProperty
+/// `ClaimProperty` is a property of a claim along with its values.
+#[cw_serde]
+pub struct ClaimProperty {
+    /// The predicate of the property.
+    pub predicate: Uri,
+    /// The values of the property.
+    pub values: Vec<Value>,
+}
+
+/// # ClaimPropertyChange
+/// `ClaimPropertyChange` is a property of a claim whose values differ between two credentials.
+#[cw_serde]
+pub struct ClaimPropertyChange {
+    /// The predicate of the property.
+    pub predicate: Uri,
+    /// The values of the property in the claim of the first credential.
+    pub from: Vec<Value>,
+    /// The values of the property in the claim of the second credential.
+    pub to: Vec<Value>,
+}
+
+/// # EmergencyResponse
+/// `EmergencyResponse` is the response of the [QueryMsg::Emergency] query.
+#[cw_serde]
+pub struct EmergencyResponse {
+    /// The guardian allowed to queue overrides, if any, see [ExecuteMsg::SetGuardian].
+    pub guardian: Option<Addr>,
+    /// The delay, in seconds, between the queuing of an override and its earliest execution.
+    pub delay: Uint64,
+    /// Whether the dataverse is paused.
+    pub paused: bool,
+    /// The page of the overrides queued, neither executed nor cancelled yet.
+    pub overrides: Vec<QueuedOverride>,
+}
+
+/// # QueuedOverride
+/// `QueuedOverride` is an emergency override awaiting its execution, see [ExecuteMsg::QueueOverride].
+#[cw_serde]
+pub struct QueuedOverride {
+    /// The identifier of the override.
+    pub id: Uint64,
+    /// The action the override performs.
+    pub action: OverrideAction,
+    /// The guardian who queued the override.
+    pub guardian: Addr,
+    /// The block time the override has been queued at.
+    pub queued_at: Timestamp,
+    /// The time from which the override can be executed.
+    pub executable_at: Timestamp,
 }
 
 /// # DataverseResponse
@@ -192,4 +1067,6 @@ pub struct DataverseResponse {
     pub name: String,
     /// The cognitarium contract address.
     pub triplestore_address: Addr,
+    /// The governance law stone address, if any, see [ExecuteMsg::SetGovernance].
+    pub governance: Option<Addr>,
 }
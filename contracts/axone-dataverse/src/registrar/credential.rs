@@ -1,15 +1,49 @@
 use crate::credential::rdf_marker::IRI_VC_TYPE;
 use crate::credential::vc::{Claim, VerifiableCredential};
+use crate::state::ADDRESS_HOLDERS;
 use crate::ContractError;
-use cosmwasm_std::{Addr, Env, MessageInfo};
+use axone_cognitarium_client::parse_date_time;
+use cosmwasm_std::{Addr, Deps, Env, HexBinary};
 use itertools::Itertools;
 
+/// The address submitting a credential, along with its chain-agnostic canonical form so the
+/// submissions of a same account remain related whatever the bech32 prefix it is given with.
+#[derive(Debug, PartialEq)]
+pub struct Submitter {
+    /// The address, as validated by the chain.
+    pub address: Addr,
+    /// The canonical form of the address, hex encoded.
+    pub account: String,
+    /// The bech32 prefix of the address.
+    pub prefix: String,
+    /// The DID of the holder the address is linked to, if any.
+    pub holder: Option<String>,
+}
+
+impl Submitter {
+    pub fn try_new(deps: Deps<'_>, address: &str) -> Result<Self, ContractError> {
+        let address = deps.api.addr_validate(address)?;
+        let account = deps.api.addr_canonicalize(address.as_str())?;
+
+        Ok(Submitter {
+            prefix: address
+                .as_str()
+                .rsplit_once('1')
+                .map_or("", |(prefix, _)| prefix)
+                .to_string(),
+            account: HexBinary::from(account.as_slice()).to_hex(),
+            holder: ADDRESS_HOLDERS.may_load(deps.storage, account.as_slice())?,
+            address,
+        })
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct DataverseCredential<'a> {
     pub height: String,
     pub timestamp: String,
     pub tx_index: Option<String>,
-    pub sender: Addr,
+    pub sender: Submitter,
     pub id: &'a str,
     pub issuer: &'a str,
     pub r#type: &'a str,
@@ -32,6 +66,20 @@ impl<'a> DataverseCredential<'a> {
             .copied()
     }
 
+    /// The expiration time of the credential in seconds since the Unix epoch, if any, the
+    /// expiration date being interpreted as UTC when it has no timezone.
+    pub fn expires_at(&self) -> Result<Option<u64>, ContractError> {
+        self.valid_until
+            .map(|date| {
+                parse_date_time(date).ok_or_else(|| {
+                    ContractError::UnsupportedCredential(format!(
+                        "credential expiration date '{date}' is not a valid date time"
+                    ))
+                })
+            })
+            .transpose()
+    }
+
     fn extract_vc_claim(vc: &'a VerifiableCredential<'a>) -> Result<&'a Claim<'a>, ContractError> {
         vc.claims.iter().exactly_one().map_err(|_| {
             ContractError::UnsupportedCredential(
@@ -41,17 +89,17 @@ impl<'a> DataverseCredential<'a> {
     }
 }
 
-impl<'a> TryFrom<(Env, MessageInfo, &'a VerifiableCredential<'a>)> for DataverseCredential<'a> {
+impl<'a> TryFrom<(Env, Submitter, &'a VerifiableCredential<'a>)> for DataverseCredential<'a> {
     type Error = ContractError;
 
     fn try_from(
-        (env, info, vc): (Env, MessageInfo, &'a VerifiableCredential<'a>),
+        (env, sender, vc): (Env, Submitter, &'a VerifiableCredential<'a>),
     ) -> Result<Self, Self::Error> {
         Ok(DataverseCredential {
             height: env.block.height.to_string(),
             timestamp: env.block.time.seconds().to_string(),
             tx_index: env.transaction.map(|tx| tx.index.to_string()),
-            sender: info.sender,
+            sender,
             id: vc.id,
             issuer: vc.issuer,
             r#type: DataverseCredential::extract_vc_type(vc)?,
@@ -67,25 +115,55 @@ mod test {
     use super::*;
     use crate::testutil::testutil;
     use axone_rdf::dataset::Dataset;
-    use cosmwasm_std::testing::message_info;
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::Api;
     use rio_api::model::{Literal, NamedNode, Quad};
     use testing::addr::{addr, SENDER};
     use testing::mock::mock_env_addr;
 
+    #[test]
+    fn proper_submitter() {
+        let mut deps = mock_dependencies();
+        let sender = addr(SENDER);
+        let account = deps.api.addr_canonicalize(sender.as_str()).unwrap();
+
+        let submitter = Submitter::try_new(deps.as_ref(), sender.as_str()).unwrap();
+        assert_eq!(
+            submitter,
+            Submitter {
+                address: sender.clone(),
+                account: HexBinary::from(account.as_slice()).to_hex(),
+                prefix: "cosmwasm".to_string(),
+                holder: None,
+            }
+        );
+
+        ADDRESS_HOLDERS
+            .save(
+                deps.as_mut().storage,
+                account.as_slice(),
+                &"did:key:holder".to_string(),
+            )
+            .unwrap();
+        let submitter = Submitter::try_new(deps.as_ref(), sender.as_str()).unwrap();
+        assert_eq!(submitter.holder, Some("did:key:holder".to_string()));
+
+        assert!(Submitter::try_new(deps.as_ref(), &sender.as_str().to_uppercase()).is_err());
+    }
+
     #[test]
     fn proper_from_verifiable() {
         let owned_quads = testutil::read_test_quads("vc-valid.nq");
         let dataset = Dataset::from(owned_quads.as_slice());
         let vc = VerifiableCredential::try_from(&dataset).unwrap();
-        let dc_res =
-            DataverseCredential::try_from((mock_env_addr(), message_info(&addr(SENDER), &[]), &vc));
+        let dc_res = DataverseCredential::try_from((mock_env_addr(), testutil::submitter(), &vc));
 
         assert!(dc_res.is_ok());
         assert_eq!(dc_res.unwrap(), DataverseCredential {
             height: "12345".to_string(),
             timestamp: "1571797419".to_string(),
             tx_index: Some("3".to_string()),
-            sender: addr(SENDER),
+            sender: testutil::submitter(),
                 id: "https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9",
                 issuer: "did:key:zQ3shs7auhJSmVJpiUbQWco6bxxEhSqWnVEPvaBHBRvBKw6Q3",
                 r#type: "https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/DigitalServiceDescriptionCredential",
@@ -129,11 +207,8 @@ mod test {
             let owned_quads = testutil::read_test_quads(case.0);
             let dataset = Dataset::from(owned_quads.as_slice());
             let vc = VerifiableCredential::try_from(&dataset).unwrap();
-            let dc_res = DataverseCredential::try_from((
-                mock_env_addr(),
-                message_info(&addr(SENDER), &[]),
-                &vc,
-            ));
+            let dc_res =
+                DataverseCredential::try_from((mock_env_addr(), testutil::submitter(), &vc));
 
             assert!(dc_res.is_err());
             if let ContractError::UnsupportedCredential(msg) = dc_res.err().unwrap() {
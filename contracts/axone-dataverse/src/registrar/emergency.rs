@@ -0,0 +1,204 @@
+use crate::msg::OverrideAction;
+use crate::state::{Override, DATAVERSE};
+use axone_cognitarium::msg::{
+    DataFormat, Node, TripleDeleteTemplate, TriplePattern, VarOrNamedNode, VarOrNamedNodeOrLiteral,
+    VarOrNode, VarOrNodeOrLiteral, WhereClause, IRI,
+};
+use axone_cognitarium_client::CognitariumClient;
+use axone_rdf::serde::TripleWriter;
+use cosmwasm_std::{Binary, StdError, StdResult, Storage, WasmMsg};
+use rio_api::model::{Literal, NamedNode, Subject, Term, Triple};
+
+pub const OVERRIDE_ACTION: NamedNode<'_> = NamedNode {
+    iri: "dataverse:override#action",
+};
+/// The credential or law stone the action of the override applies to, if any.
+pub const OVERRIDE_TARGET: NamedNode<'_> = NamedNode {
+    iri: "dataverse:override#target",
+};
+pub const OVERRIDE_GUARDIAN: NamedNode<'_> = NamedNode {
+    iri: "dataverse:override#guardian",
+};
+pub const OVERRIDE_QUEUED_AT: NamedNode<'_> = NamedNode {
+    iri: "dataverse:override#queued_at",
+};
+pub const OVERRIDE_EXECUTABLE_AT: NamedNode<'_> = NamedNode {
+    iri: "dataverse:override#executable_at",
+};
+/// The lifecycle status of the override: `queued`, `executed` or `cancelled`.
+pub const OVERRIDE_STATUS: NamedNode<'_> = NamedNode {
+    iri: "dataverse:override#status",
+};
+
+impl OverrideAction {
+    /// The name of the action, as recorded in the triple store and the events.
+    pub fn name(&self) -> &'static str {
+        match self {
+            OverrideAction::Pause {} => "pause",
+            OverrideAction::Resume {} => "resume",
+            OverrideAction::RevokeClaims { .. } => "revoke_claims",
+            OverrideAction::SetGovernance { .. } => "set_governance",
+        }
+    }
+
+    /// The credential or law stone the action applies to, if any.
+    pub fn target(&self) -> Option<&str> {
+        match self {
+            OverrideAction::RevokeClaims { identifier } => Some(identifier),
+            OverrideAction::SetGovernance { law_stone } => law_stone.as_deref(),
+            OverrideAction::Pause {} | OverrideAction::Resume {} => None,
+        }
+    }
+}
+
+/// OverrideRecorder records the lifecycle of the emergency overrides in the triple store, each
+/// override being described by the `dataverse:override:<id>` resource.
+pub struct OverrideRecorder {
+    triplestore: CognitariumClient,
+}
+
+impl OverrideRecorder {
+    pub fn try_new(storage: &dyn Storage) -> StdResult<Self> {
+        let dataverse = DATAVERSE.load(storage)?;
+        Ok(Self {
+            triplestore: CognitariumClient::new(dataverse.triplestore_address),
+        })
+    }
+
+    /// Forge the message recording a queued override.
+    pub fn record_queued(&self, id: u64, r#override: &Override) -> StdResult<WasmMsg> {
+        let iri = override_iri(id);
+        let subject = Subject::NamedNode(NamedNode { iri: &iri });
+        let (queued_at, executable_at) = (
+            r#override.queued_at.to_string(),
+            r#override.executable_at.to_string(),
+        );
+        let literal = |value| Term::Literal(Literal::Simple { value });
+
+        let mut triples = vec![
+            (OVERRIDE_ACTION, literal(r#override.action.name())),
+            (OVERRIDE_GUARDIAN, literal(r#override.guardian.as_str())),
+            (OVERRIDE_QUEUED_AT, literal(&queued_at)),
+            (OVERRIDE_EXECUTABLE_AT, literal(&executable_at)),
+            (OVERRIDE_STATUS, literal("queued")),
+        ];
+        if let Some(target) = r#override.action.target() {
+            triples.push((OVERRIDE_TARGET, literal(target)));
+        }
+
+        let mut writer = TripleWriter::new(&axone_rdf::serde::DataFormat::NTriples, vec![]);
+        for (predicate, object) in triples {
+            writer
+                .write(&Triple {
+                    subject,
+                    predicate,
+                    object,
+                })
+                .map_err(|e| {
+                    StdError::serialize_err("triple", format!("Error writing triple: {e}"))
+                })?;
+        }
+        let data = writer
+            .finish()
+            .map_err(|e| StdError::serialize_err("triple", format!("Error writing triple: {e}")))?;
+
+        self.triplestore
+            .insert_data(Some(DataFormat::NTriples), Binary::from(data))
+    }
+
+    /// Forge the messages replacing the recorded status of an override, once executed or
+    /// cancelled.
+    pub fn record_status(&self, id: u64, status: &str) -> StdResult<Vec<WasmMsg>> {
+        let iri = override_iri(id);
+        let status_triple = format!(
+            "{} {} {} .\n",
+            NamedNode { iri: &iri },
+            OVERRIDE_STATUS,
+            Literal::Simple { value: status }
+        );
+
+        Ok(vec![
+            self.triplestore.delete_data(
+                vec![TripleDeleteTemplate {
+                    subject: VarOrNamedNode::NamedNode(IRI::Full(iri.clone())),
+                    predicate: VarOrNamedNode::NamedNode(IRI::Full(
+                        OVERRIDE_STATUS.iri.to_string(),
+                    )),
+                    object: VarOrNamedNodeOrLiteral::Variable("status".to_string()),
+                }],
+                Some(WhereClause::Bgp {
+                    patterns: vec![TriplePattern {
+                        subject: VarOrNode::Node(Node::NamedNode(IRI::Full(iri))),
+                        predicate: VarOrNamedNode::NamedNode(IRI::Full(
+                            OVERRIDE_STATUS.iri.to_string(),
+                        )),
+                        object: VarOrNodeOrLiteral::Variable("status".to_string()),
+                    }],
+                }),
+            )?,
+            self.triplestore.insert_data(
+                Some(DataFormat::NTriples),
+                Binary::from(status_triple.into_bytes()),
+            )?,
+        ])
+    }
+}
+
+/// The IRI of the resource describing an override in the triple store.
+pub fn override_iri(id: u64) -> String {
+    format!("dataverse:override:{id}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Dataverse;
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{from_json, Addr};
+
+    #[test]
+    fn proper_record_queued() {
+        let mut deps = mock_dependencies();
+        DATAVERSE
+            .save(
+                deps.as_mut().storage,
+                &Dataverse {
+                    name: "my-dataverse".to_string(),
+                    triplestore_address: Addr::unchecked("my-dataverse-addr"),
+                },
+            )
+            .unwrap();
+
+        let msg = OverrideRecorder::try_new(&deps.storage)
+            .unwrap()
+            .record_queued(
+                3,
+                &Override {
+                    action: OverrideAction::RevokeClaims {
+                        identifier: "http://example.edu/credentials/1".to_string(),
+                    },
+                    guardian: Addr::unchecked("guardian"),
+                    queued_at: 100,
+                    executable_at: 200,
+                },
+            )
+            .unwrap();
+
+        let WasmMsg::Execute { msg, .. } = msg else {
+            panic!("unexpected message");
+        };
+        let axone_cognitarium::msg::ExecuteMsg::InsertData { data, .. } = from_json(msg).unwrap()
+        else {
+            panic!("unexpected triple store message");
+        };
+        assert_eq!(
+            String::from_utf8(data.to_vec()).unwrap(),
+            "<dataverse:override:3> <dataverse:override#action> \"revoke_claims\" .\n\
+             <dataverse:override:3> <dataverse:override#guardian> \"guardian\" .\n\
+             <dataverse:override:3> <dataverse:override#queued_at> \"100\" .\n\
+             <dataverse:override:3> <dataverse:override#executable_at> \"200\" .\n\
+             <dataverse:override:3> <dataverse:override#status> \"queued\" .\n\
+             <dataverse:override:3> <dataverse:override#target> \"http://example.edu/credentials/1\" .\n"
+        );
+    }
+}
@@ -1,3 +1,6 @@
 pub mod credential;
+pub mod emergency;
+pub mod policy;
 mod rdf;
 pub mod registry;
+pub mod template;
@@ -0,0 +1,142 @@
+use crate::registrar::registry::ClaimRegistrar;
+use axone_law_stone::context::quote_atom;
+use axone_law_stone::msg::{QueryMsg as LawStoneQueryMsg, VerdictResponse};
+use cosmwasm_std::{Addr, QuerierWrapper, StdResult};
+use std::collections::BTreeMap;
+
+/// The type of the credentials declaring the usage policy of a resource, i.e. its subject.
+pub const USAGE_POLICY_CREDENTIAL: &str =
+    "https://w3id.org/axone/ontology/vnext/schema/credential/usage-policy/UsagePolicyCredential";
+
+/// The claim property holding a purpose the resource is allowed to be used for (e.g. a consent
+/// scope).
+pub const USAGE_POLICY_ALLOWED_PURPOSE: &str =
+    "https://w3id.org/axone/ontology/vnext/schema/credential/usage-policy/allowedPurpose";
+
+/// The claim property holding a license the resource is distributed under.
+pub const USAGE_POLICY_LICENSE: &str =
+    "https://w3id.org/axone/ontology/vnext/schema/credential/usage-policy/license";
+
+/// The usage policy of a resource, as declared by a usage policy claim about it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UsagePolicy {
+    /// The purposes the resource is explicitly allowed to be used for.
+    pub allowed_purposes: Vec<String>,
+    /// The licenses the resource is distributed under.
+    pub licenses: Vec<String>,
+}
+
+/// PolicyEvaluator tells whether the usage policies of resources allow a purpose, remembering the
+/// outcome for each resource evaluated.
+///
+/// A resource is allowed if one of its policies explicitly allows the purpose. Otherwise, and if a
+/// governance law stone is set, the resource is allowed if the law stone grants the goal
+/// `usage_allowed(Resource, Purpose, Licenses)`, `Licenses` being the licenses of its policies. A
+/// resource without any usage policy is never allowed.
+pub struct PolicyEvaluator<'a> {
+    querier: QuerierWrapper<'a>,
+    registrar: &'a ClaimRegistrar,
+    governance: Option<Addr>,
+    purpose: String,
+    outcomes: BTreeMap<String, bool>,
+}
+
+impl<'a> PolicyEvaluator<'a> {
+    pub fn new(
+        querier: QuerierWrapper<'a>,
+        registrar: &'a ClaimRegistrar,
+        governance: Option<Addr>,
+        purpose: String,
+    ) -> Self {
+        Self {
+            querier,
+            registrar,
+            governance,
+            purpose,
+            outcomes: BTreeMap::new(),
+        }
+    }
+
+    pub fn allows(&mut self, resource: &str) -> StdResult<bool> {
+        if let Some(outcome) = self.outcomes.get(resource) {
+            return Ok(*outcome);
+        }
+
+        let policies = self.registrar.usage_policies(self.querier, resource)?;
+        let outcome = if policies.is_empty() {
+            false
+        } else if policies
+            .iter()
+            .any(|p| p.allowed_purposes.contains(&self.purpose))
+        {
+            true
+        } else if let Some(law_stone) = &self.governance {
+            self.querier
+                .query_wasm_smart::<VerdictResponse>(
+                    law_stone,
+                    &LawStoneQueryMsg::Verdict {
+                        goal: usage_goal(resource, &self.purpose, &policies),
+                    },
+                )?
+                .allowed
+        } else {
+            false
+        };
+
+        self.outcomes.insert(resource.to_string(), outcome);
+        Ok(outcome)
+    }
+}
+
+/// Build the goal submitted to the governance law stone to decide whether a resource can be used for
+/// a purpose given its policies.
+fn usage_goal(resource: &str, purpose: &str, policies: &[UsagePolicy]) -> String {
+    let licenses = policies
+        .iter()
+        .flat_map(|p| p.licenses.iter())
+        .map(|l| quote_atom(l))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        "usage_allowed({}, {}, [{}]).",
+        quote_atom(resource),
+        quote_atom(purpose),
+        licenses
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proper_usage_goal() {
+        let cases = vec![
+            (
+                vec![],
+                "usage_allowed('https://ex.org/r', 'https://ex.org/research', []).",
+            ),
+            (
+                vec![
+                    UsagePolicy {
+                        allowed_purposes: vec!["https://ex.org/marketing".to_string()],
+                        licenses: vec!["https://spdx.org/licenses/CC-BY-4.0".to_string()],
+                    },
+                    UsagePolicy {
+                        allowed_purposes: vec![],
+                        licenses: vec!["https://ex.org/licenses/it's".to_string()],
+                    },
+                ],
+                "usage_allowed('https://ex.org/r', 'https://ex.org/research', ['https://spdx.org/licenses/CC-BY-4.0','https://ex.org/licenses/it\\'s']).",
+            ),
+        ];
+
+        for (policies, expected) in cases {
+            assert_eq!(
+                usage_goal("https://ex.org/r", "https://ex.org/research", &policies),
+                expected
+            );
+        }
+    }
+}
@@ -12,6 +12,9 @@ pub const VC_RESERVED_PREDICATES: &[NamedNode<'_>] = &[
     VC_HEADER_TIMESTAMP,
     VC_HEADER_TX,
     VC_HEADER_SENDER,
+    VC_HEADER_SENDER_ACCOUNT,
+    VC_HEADER_SENDER_PREFIX,
+    VC_HEADER_HOLDER,
     VC_BODY_TYPE,
     VC_BODY_ISSUER,
     VC_BODY_VALID_FROM,
@@ -33,6 +36,18 @@ pub const VC_HEADER_TX: NamedNode<'_> = NamedNode {
 pub const VC_HEADER_SENDER: NamedNode<'_> = NamedNode {
     iri: "dataverse:credential:header#sender",
 };
+/// The canonical form of the sender address, hex encoded, relating the submissions of a same account
+/// whatever the bech32 prefix of the address.
+pub const VC_HEADER_SENDER_ACCOUNT: NamedNode<'_> = NamedNode {
+    iri: "dataverse:credential:header#sender_account",
+};
+pub const VC_HEADER_SENDER_PREFIX: NamedNode<'_> = NamedNode {
+    iri: "dataverse:credential:header#sender_prefix",
+};
+/// The holder DID the sender address is linked to through an address linkage credential, if any.
+pub const VC_HEADER_HOLDER: NamedNode<'_> = NamedNode {
+    iri: "dataverse:credential:header#holder",
+};
 pub const VC_BODY_TYPE: NamedNode<'_> = NamedNode {
     iri: "dataverse:credential:body#type",
 };
@@ -113,7 +128,21 @@ impl<'a> DataverseCredential<'a> {
                 subject: c_subject,
                 predicate: VC_HEADER_SENDER,
                 object: Term::Literal(Literal::Simple {
-                    value: self.sender.as_str(),
+                    value: self.sender.address.as_str(),
+                }),
+            },
+            Triple {
+                subject: c_subject,
+                predicate: VC_HEADER_SENDER_ACCOUNT,
+                object: Term::Literal(Literal::Simple {
+                    value: &self.sender.account,
+                }),
+            },
+            Triple {
+                subject: c_subject,
+                predicate: VC_HEADER_SENDER_PREFIX,
+                object: Term::Literal(Literal::Simple {
+                    value: &self.sender.prefix,
                 }),
             },
             Triple {
@@ -149,6 +178,14 @@ impl<'a> DataverseCredential<'a> {
             });
         }
 
+        if let Some(holder) = &self.sender.holder {
+            triples.push(Triple {
+                subject: c_subject,
+                predicate: VC_HEADER_HOLDER,
+                object: Term::NamedNode(NamedNode { iri: holder }),
+            });
+        }
+
         triples.extend(self.claim_as_triples(claim_node, named_issuer, blank_issuer)?);
 
         if let Some(valid_until) = self.valid_until {
@@ -275,8 +312,6 @@ mod test {
     use crate::credential::vc::VerifiableCredential;
     use crate::testutil::testutil;
     use axone_rdf::dataset::Dataset;
-    use cosmwasm_std::testing::message_info;
-    use testing::addr::{addr, SENDER};
     use testing::mock::mock_env_addr;
 
     #[test]
@@ -285,12 +320,13 @@ mod test {
         let dataset = Dataset::from(owned_quads.as_slice());
         let vc = VerifiableCredential::try_from(&dataset).unwrap();
         let dc =
-            DataverseCredential::try_from((mock_env_addr(), message_info(&addr(SENDER), &[]), &vc))
-                .unwrap();
+            DataverseCredential::try_from((mock_env_addr(), testutil::submitter(), &vc)).unwrap();
 
         let expected = r#"<https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:header#height> "12345" .
 <https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:header#timestamp> "1571797419" .
 <https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:header#sender> "cosmwasm1pgm8hyk0pvphmlvfjc8wsvk4daluz5tgrw6pu5mfpemk74uxnx9qlm3aqg" .
+<https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:header#sender_account> "0a367b92cf0b037dfd89960ee832d56f7fc151681bb41e53690e776f5786998a" .
+<https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:header#sender_prefix> "cosmwasm" .
 <https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:body#issuer> <did:key:zQ3shs7auhJSmVJpiUbQWco6bxxEhSqWnVEPvaBHBRvBKw6Q3> .
 <https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:body#type> <https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/DigitalServiceDescriptionCredential> .
 <https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:body#validFrom> "2024-01-22T00:00:00"^^<http://www.w3.org/2001/XMLSchema#dateTime> .
@@ -317,12 +353,13 @@ _:c0 <https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/de
         let dataset = Dataset::from(owned_quads.as_slice());
         let vc = VerifiableCredential::try_from(&dataset).unwrap();
         let dc =
-            DataverseCredential::try_from((mock_env_addr(), message_info(&addr(SENDER), &[]), &vc))
-                .unwrap();
+            DataverseCredential::try_from((mock_env_addr(), testutil::submitter(), &vc)).unwrap();
 
         let expected = r#"<https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:header#height> "12345" .
 <https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:header#timestamp> "1571797419" .
 <https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:header#sender> "cosmwasm1pgm8hyk0pvphmlvfjc8wsvk4daluz5tgrw6pu5mfpemk74uxnx9qlm3aqg" .
+<https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:header#sender_account> "0a367b92cf0b037dfd89960ee832d56f7fc151681bb41e53690e776f5786998a" .
+<https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:header#sender_prefix> "cosmwasm" .
 <https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:body#issuer> <did:key:zQ3shs7auhJSmVJpiUbQWco6bxxEhSqWnVEPvaBHBRvBKw6Q3> .
 <https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:body#type> <https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/DigitalServiceDescriptionCredential> .
 <https://w3id.org/axone/ontology/vnext/schema/credential/digital-service/description/72cab400-5bd6-4eb4-8605-a5ee8c1a45c9> <dataverse:credential:body#validFrom> "2024-01-22T00:00:00"^^<http://www.w3.org/2001/XMLSchema#dateTime> .
@@ -352,8 +389,7 @@ _:a0 <dataverse:claim#original-node> <test:named-link> .
         let dataset = Dataset::from(owned_quads.as_slice());
         let vc = VerifiableCredential::try_from(&dataset).unwrap();
         let dc =
-            DataverseCredential::try_from((mock_env_addr(), message_info(&addr(SENDER), &[]), &vc))
-                .unwrap();
+            DataverseCredential::try_from((mock_env_addr(), testutil::submitter(), &vc)).unwrap();
 
         let res = dc.serialize(DataFormat::NQuads);
         assert!(res.is_err());
@@ -1,12 +1,57 @@
+use crate::msg::{
+    ClaimProperty, ClaimPropertyChange, ClaimSummary, ClaimsFilter, CompareClaimsResponse,
+};
 use crate::registrar::credential::DataverseCredential;
+use crate::registrar::policy::{
+    UsagePolicy, USAGE_POLICY_ALLOWED_PURPOSE, USAGE_POLICY_CREDENTIAL, USAGE_POLICY_LICENSE,
+};
+use crate::registrar::rdf::{
+    VC_BODY_CLAIM, VC_BODY_ISSUER, VC_BODY_SUBJECT, VC_BODY_TYPE, VC_BODY_VALID_UNTIL,
+    VC_HEADER_TIMESTAMP,
+};
 use crate::state::DATAVERSE;
 use crate::ContractError;
 use axone_cognitarium::msg::{
-    DataFormat, Node, SelectItem, SelectQuery, TriplePattern, VarOrNamedNode, VarOrNode,
-    VarOrNodeOrLiteral, WhereClause, IRI,
+    DataFormat, Node, SelectItem, SelectQuery, TripleDeleteTemplate, TriplePattern, Value,
+    VarOrNamedNode, VarOrNamedNodeOrLiteral, VarOrNode, VarOrNodeOrLiteral, WhereClause, IRI,
 };
-use axone_cognitarium_client::CognitariumClient;
-use cosmwasm_std::{DepsMut, StdResult, Storage, WasmMsg};
+use axone_cognitarium_client::{BindingExt, CognitariumClient};
+use axone_rdf::serde::NQuadsReader;
+use cosmwasm_std::{Binary, DepsMut, QuerierWrapper, StdError, StdResult, Storage, WasmMsg};
+use rio_api::model::{Quad, Subject};
+use std::collections::BTreeMap;
+
+const OWL_SAME_AS: &str = "http://www.w3.org/2002/07/owl#sameAs";
+
+/// A page of the triple store scanned for the registered credentials, see
+/// [ClaimRegistrar::scan_credentials].
+pub struct CredentialsPage {
+    /// The credentials whose type is held by the page, which is unique per credential.
+    pub credentials: Vec<String>,
+    /// The cursor to scan the following page with, if some triples remain.
+    pub cursor: Option<Binary>,
+}
+
+/// The registered credentials selected from the triple store, see [ClaimRegistrar::claims].
+pub struct ClaimsSelection {
+    /// The selected credentials.
+    pub claims: Vec<ClaimSummary>,
+    /// Whether the selection reached the maximum query limit of the triple store, some matching
+    /// credentials being possibly missing.
+    pub truncated: bool,
+}
+
+/// A registered credential as described in the triple store, see
+/// [ClaimRegistrar::registered_credential].
+pub struct RegisteredCredential {
+    pub issuer: String,
+    pub subject: String,
+    pub r#type: String,
+    /// The block time of the submission, in seconds.
+    pub submitted_at: u64,
+    /// The expiration date, as found in the credential, if any.
+    pub valid_until: Option<String>,
+}
 
 /// ClaimRegistrar is the entity responsible to manage claims (i.e. submission and revocation) into
 /// the Dataverse, ensuring that any pre-condition criteria to an action is met, and any attached
@@ -45,6 +90,12 @@ impl ClaimRegistrar {
                         object: VarOrNodeOrLiteral::Variable("o".to_string()),
                     }],
                 },
+                reasoning: None,
+                skolemize: false,
+                adaptive_joins: false,
+                ordered: false,
+                from: vec![],
+                from_named: vec![],
             },
         )?;
 
@@ -57,8 +108,633 @@ impl ClaimRegistrar {
         self.triplestore
             .insert_data(
                 Some(Self::RDF_DATA_FORMAT),
-                credential.serialize((&Self::RDF_DATA_FORMAT).into())?,
+                credential.serialize((&Self::RDF_DATA_FORMAT).try_into()?)?,
             )
             .map_err(ContractError::from)
     }
+
+    /// Forge the messages removing the triples of a registered credential from the triple store,
+    /// the ones of its claim node first as they are found through the credential.
+    pub fn revoke_claim(&self, credential: &str) -> StdResult<Vec<WasmMsg>> {
+        let credential_node = || IRI::Full(credential.to_string());
+
+        Ok(vec![
+            self.triplestore.delete_data(
+                vec![TripleDeleteTemplate {
+                    subject: VarOrNamedNode::Variable("c".to_string()),
+                    predicate: VarOrNamedNode::Variable("p".to_string()),
+                    object: VarOrNamedNodeOrLiteral::Variable("o".to_string()),
+                }],
+                Some(WhereClause::Bgp {
+                    patterns: vec![
+                        TriplePattern {
+                            subject: VarOrNode::Node(Node::NamedNode(credential_node())),
+                            predicate: VarOrNamedNode::NamedNode(IRI::Full(
+                                VC_BODY_CLAIM.iri.to_string(),
+                            )),
+                            object: VarOrNodeOrLiteral::Variable("c".to_string()),
+                        },
+                        TriplePattern {
+                            subject: VarOrNode::Variable("c".to_string()),
+                            predicate: VarOrNamedNode::Variable("p".to_string()),
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        },
+                    ],
+                }),
+            )?,
+            self.triplestore.delete_data(
+                vec![TripleDeleteTemplate {
+                    subject: VarOrNamedNode::NamedNode(credential_node()),
+                    predicate: VarOrNamedNode::Variable("p".to_string()),
+                    object: VarOrNamedNodeOrLiteral::Variable("o".to_string()),
+                }],
+                Some(WhereClause::Bgp {
+                    patterns: vec![TriplePattern {
+                        subject: VarOrNode::Node(Node::NamedNode(credential_node())),
+                        predicate: VarOrNamedNode::Variable("p".to_string()),
+                        object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                    }],
+                }),
+            )?,
+        ])
+    }
+
+    /// Compare the claims of two registered credentials, see [crate::msg::QueryMsg::CompareClaims].
+    pub fn compare_claims(
+        &self,
+        querier: QuerierWrapper<'_>,
+        credential_a: &str,
+        credential_b: &str,
+    ) -> StdResult<CompareClaimsResponse> {
+        let mut from = self.claim_properties(querier, credential_a)?;
+        let to = self.claim_properties(querier, credential_b)?;
+
+        let mut resp = CompareClaimsResponse {
+            added: vec![],
+            removed: vec![],
+            changed: vec![],
+        };
+        for (predicate, to_values) in to {
+            match from.remove(&predicate) {
+                None => resp.added.push(ClaimProperty {
+                    predicate,
+                    values: to_values,
+                }),
+                Some(from_values) if !same_values(&from_values, &to_values) => {
+                    resp.changed.push(ClaimPropertyChange {
+                        predicate,
+                        from: from_values,
+                        to: to_values,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        resp.removed = from
+            .into_iter()
+            .map(|(predicate, values)| ClaimProperty { predicate, values })
+            .collect();
+
+        Ok(resp)
+    }
+
+    /// List the registered credentials matching the filter, see [crate::msg::QueryMsg::Claims].
+    pub fn claims(
+        &self,
+        querier: QuerierWrapper<'_>,
+        filter: ClaimsFilter,
+    ) -> StdResult<ClaimsSelection> {
+        let limits = self.triplestore.store(querier)?.limits;
+        let (Some(subject), Some(max_links)) = (&filter.subject, filter.same_as) else {
+            return self.select_claims(querier, filter, limits.max_query_limit);
+        };
+        let max_links = max_links.min(limits.max_query_inference_depth);
+
+        let mut selection = ClaimsSelection {
+            claims: vec![],
+            truncated: false,
+        };
+        for subject in self.same_resources(querier, subject, max_links)? {
+            let selected = self.select_claims(
+                querier,
+                ClaimsFilter {
+                    subject: Some(subject),
+                    ..filter.clone()
+                },
+                limits.max_query_limit,
+            )?;
+            selection.truncated |= selected.truncated;
+            for claim in selected.claims {
+                if !selection
+                    .claims
+                    .iter()
+                    .any(|c| c.credential == claim.credential)
+                {
+                    selection.claims.push(claim);
+                }
+            }
+        }
+
+        Ok(selection)
+    }
+
+    /// Retrieve the resources equivalent to the given one through the `owl:sameAs` claims, in
+    /// either direction, following up to the given number of links. The resource comes first.
+    fn same_resources(
+        &self,
+        querier: QuerierWrapper<'_>,
+        resource: &str,
+        max_links: u32,
+    ) -> StdResult<Vec<String>> {
+        let mut resources = vec![resource.to_string()];
+        let mut level = 0;
+        for _ in 0..max_links {
+            let frontier = resources[level..].to_vec();
+            level = resources.len();
+            for resource in frontier {
+                for linked in self.linked_resources(querier, &resource)? {
+                    if !resources.contains(&linked) {
+                        resources.push(linked);
+                    }
+                }
+            }
+            if level == resources.len() {
+                break;
+            }
+        }
+
+        Ok(resources)
+    }
+
+    /// Retrieve the resources directly linked to the given one by an `owl:sameAs` claim, in either
+    /// direction.
+    fn linked_resources(
+        &self,
+        querier: QuerierWrapper<'_>,
+        resource: &str,
+    ) -> StdResult<Vec<String>> {
+        let resource = VarOrNodeOrLiteral::Node(Node::NamedNode(IRI::Full(resource.to_string())));
+        let linked_resource = VarOrNodeOrLiteral::Variable("r".to_string());
+
+        let mut linked = vec![];
+        for (claim_subject, same_as) in [
+            (resource.clone(), linked_resource.clone()),
+            (linked_resource, resource),
+        ] {
+            let resp = self.triplestore.select(
+                querier,
+                SelectQuery {
+                    prefixes: vec![],
+                    limit: None,
+                    select: vec![SelectItem::Variable("r".to_string())],
+                    r#where: WhereClause::Bgp {
+                        patterns: vec![
+                            TriplePattern {
+                                subject: VarOrNode::Variable("credential".to_string()),
+                                predicate: VarOrNamedNode::NamedNode(IRI::Full(
+                                    VC_BODY_SUBJECT.iri.to_string(),
+                                )),
+                                object: claim_subject,
+                            },
+                            TriplePattern {
+                                subject: VarOrNode::Variable("credential".to_string()),
+                                predicate: VarOrNamedNode::NamedNode(IRI::Full(
+                                    VC_BODY_CLAIM.iri.to_string(),
+                                )),
+                                object: VarOrNodeOrLiteral::Variable("c".to_string()),
+                            },
+                            TriplePattern {
+                                subject: VarOrNode::Variable("c".to_string()),
+                                predicate: VarOrNamedNode::NamedNode(IRI::Full(
+                                    OWL_SAME_AS.to_string(),
+                                )),
+                                object: same_as,
+                            },
+                        ],
+                    },
+                    reasoning: None,
+                    skolemize: false,
+                    adaptive_joins: false,
+                    ordered: false,
+                    from: vec![],
+                    from_named: vec![],
+                },
+            )?;
+
+            linked.extend(
+                resp.results
+                    .bindings
+                    .iter()
+                    .filter_map(|binding| binding.iri("r").ok()),
+            );
+        }
+
+        Ok(linked)
+    }
+
+    /// List the registered credentials matching the criteria of the filter, up to the given limit.
+    fn select_claims(
+        &self,
+        querier: QuerierWrapper<'_>,
+        filter: ClaimsFilter,
+        limit: u32,
+    ) -> StdResult<ClaimsSelection> {
+        let criteria = [
+            ("subject", VC_BODY_SUBJECT, filter.subject),
+            ("type", VC_BODY_TYPE, filter.r#type),
+            ("issuer", VC_BODY_ISSUER, filter.issuer),
+        ];
+
+        let mut select = vec![SelectItem::Variable("credential".to_string())];
+        let mut patterns = vec![];
+        for (variable, predicate, value) in &criteria {
+            if value.is_none() {
+                select.push(SelectItem::Variable(variable.to_string()));
+            }
+            patterns.push(TriplePattern {
+                subject: VarOrNode::Variable("credential".to_string()),
+                predicate: VarOrNamedNode::NamedNode(IRI::Full(predicate.iri.to_string())),
+                object: match value {
+                    Some(iri) => VarOrNodeOrLiteral::Node(Node::NamedNode(IRI::Full(iri.clone()))),
+                    None => VarOrNodeOrLiteral::Variable(variable.to_string()),
+                },
+            });
+        }
+
+        let resp = self.triplestore.select(
+            querier,
+            SelectQuery {
+                prefixes: vec![],
+                limit: Some(limit),
+                select,
+                r#where: WhereClause::Bgp { patterns },
+                reasoning: None,
+                skolemize: false,
+                adaptive_joins: false,
+                ordered: false,
+                from: vec![],
+                from_named: vec![],
+            },
+        )?;
+
+        let claims = resp
+            .results
+            .bindings
+            .iter()
+            .map(|binding| {
+                let iri = |variable: &str, value: &Option<String>| match value {
+                    Some(value) => Ok(value.clone()),
+                    None => binding.iri(variable),
+                };
+                Ok(ClaimSummary {
+                    credential: iri("credential", &None)?,
+                    subject: iri(criteria[0].0, &criteria[0].2)?,
+                    r#type: iri(criteria[1].0, &criteria[1].2)?,
+                    issuer: iri(criteria[2].0, &criteria[2].2)?,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(ClaimsSelection {
+            truncated: claims.len() >= limit as usize,
+            claims,
+        })
+    }
+
+    /// Retrieve the usage policies declared about a resource by the registered usage policy
+    /// credentials, one per credential.
+    pub fn usage_policies(
+        &self,
+        querier: QuerierWrapper<'_>,
+        resource: &str,
+    ) -> StdResult<Vec<UsagePolicy>> {
+        let named = |iri: &str| Node::NamedNode(IRI::Full(iri.to_string()));
+        let credential_pattern = |predicate: &str, object: VarOrNodeOrLiteral| TriplePattern {
+            subject: VarOrNode::Variable("credential".to_string()),
+            predicate: VarOrNamedNode::NamedNode(IRI::Full(predicate.to_string())),
+            object,
+        };
+
+        let resp = self.triplestore.select(
+            querier,
+            SelectQuery {
+                prefixes: vec![],
+                limit: None,
+                select: vec![
+                    SelectItem::Variable("credential".to_string()),
+                    SelectItem::Variable("p".to_string()),
+                    SelectItem::Variable("o".to_string()),
+                ],
+                r#where: WhereClause::Bgp {
+                    patterns: vec![
+                        credential_pattern(
+                            VC_BODY_SUBJECT.iri,
+                            VarOrNodeOrLiteral::Node(named(resource)),
+                        ),
+                        credential_pattern(
+                            VC_BODY_TYPE.iri,
+                            VarOrNodeOrLiteral::Node(named(USAGE_POLICY_CREDENTIAL)),
+                        ),
+                        credential_pattern(
+                            VC_BODY_CLAIM.iri,
+                            VarOrNodeOrLiteral::Variable("c".to_string()),
+                        ),
+                        TriplePattern {
+                            subject: VarOrNode::Variable("c".to_string()),
+                            predicate: VarOrNamedNode::Variable("p".to_string()),
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        },
+                    ],
+                },
+                reasoning: None,
+                skolemize: false,
+                adaptive_joins: false,
+                ordered: false,
+                from: vec![],
+                from_named: vec![],
+            },
+        )?;
+
+        let mut policies: BTreeMap<String, UsagePolicy> = BTreeMap::new();
+        for binding in &resp.results.bindings {
+            let policy = policies.entry(binding.iri("credential")?).or_default();
+            let values = match binding.iri("p")?.as_str() {
+                USAGE_POLICY_ALLOWED_PURPOSE => &mut policy.allowed_purposes,
+                USAGE_POLICY_LICENSE => &mut policy.licenses,
+                _ => continue,
+            };
+            if let Ok(iri) = binding.iri("o") {
+                values.push(iri);
+            }
+        }
+
+        Ok(policies.into_values().collect())
+    }
+
+    /// Scan a page of up to `limit` triples of the triple store for the registered credentials, in
+    /// the stable order of the triple store export, starting after the given cursor. The maximum
+    /// query limit of the triple store is used if no limit is given.
+    ///
+    /// As the triples of a credential can be spread over several pages, a credential is only reported
+    /// in the page holding its type.
+    pub fn scan_credentials(
+        &self,
+        querier: QuerierWrapper<'_>,
+        cursor: Option<Binary>,
+        limit: Option<u32>,
+    ) -> Result<CredentialsPage, ContractError> {
+        let resp = self
+            .triplestore
+            .export(querier, cursor, limit, Some(DataFormat::NQuads))?;
+        let quads = NQuadsReader::new(resp.data.as_slice()).read_all()?;
+
+        let credentials = quads
+            .iter()
+            .map(Quad::from)
+            .filter_map(|quad| match quad.subject {
+                Subject::NamedNode(credential) if quad.predicate == VC_BODY_TYPE => {
+                    Some(credential.iri.to_string())
+                }
+                _ => None,
+            })
+            .collect();
+
+        Ok(CredentialsPage {
+            credentials,
+            cursor: resp.cursor,
+        })
+    }
+
+    /// Retrieve the description of a registered credential held by the triple store.
+    pub fn registered_credential(
+        &self,
+        querier: QuerierWrapper<'_>,
+        credential: &str,
+    ) -> StdResult<RegisteredCredential> {
+        let credential_pattern = |predicate: &str, variable: &str| TriplePattern {
+            subject: VarOrNode::Node(Node::NamedNode(IRI::Full(credential.to_string()))),
+            predicate: VarOrNamedNode::NamedNode(IRI::Full(predicate.to_string())),
+            object: VarOrNodeOrLiteral::Variable(variable.to_string()),
+        };
+
+        let resp = self.triplestore.select(
+            querier,
+            SelectQuery {
+                prefixes: vec![],
+                limit: Some(1),
+                select: vec![
+                    SelectItem::Variable("issuer".to_string()),
+                    SelectItem::Variable("subject".to_string()),
+                    SelectItem::Variable("type".to_string()),
+                    SelectItem::Variable("timestamp".to_string()),
+                ],
+                r#where: WhereClause::Bgp {
+                    patterns: vec![
+                        credential_pattern(VC_BODY_ISSUER.iri, "issuer"),
+                        credential_pattern(VC_BODY_SUBJECT.iri, "subject"),
+                        credential_pattern(VC_BODY_TYPE.iri, "type"),
+                        credential_pattern(VC_HEADER_TIMESTAMP.iri, "timestamp"),
+                    ],
+                },
+                reasoning: None,
+                skolemize: false,
+                adaptive_joins: false,
+                ordered: false,
+                from: vec![],
+                from_named: vec![],
+            },
+        )?;
+
+        let binding = resp
+            .results
+            .bindings
+            .first()
+            .ok_or_else(|| StdError::not_found(format!("credential '{credential}'")))?;
+        let submitted_at = binding.string("timestamp")?.parse().map_err(|_| {
+            StdError::generic_err(format!(
+                "Invalid submission timestamp of credential '{credential}'"
+            ))
+        })?;
+
+        Ok(RegisteredCredential {
+            issuer: binding.iri("issuer")?,
+            subject: binding.iri("subject")?,
+            r#type: binding.iri("type")?,
+            submitted_at,
+            valid_until: self.literal(
+                querier,
+                vec![credential_pattern(VC_BODY_VALID_UNTIL.iri, "o")],
+            )?,
+        })
+    }
+
+    /// Retrieve the lexical form of a literal property of the claim of a registered credential, the
+    /// first one found if the property has several values.
+    pub fn claim_literal(
+        &self,
+        querier: QuerierWrapper<'_>,
+        credential: &str,
+        predicate: &str,
+    ) -> StdResult<Option<String>> {
+        self.literal(
+            querier,
+            vec![
+                TriplePattern {
+                    subject: VarOrNode::Node(Node::NamedNode(IRI::Full(credential.to_string()))),
+                    predicate: VarOrNamedNode::NamedNode(IRI::Full(VC_BODY_CLAIM.iri.to_string())),
+                    object: VarOrNodeOrLiteral::Variable("c".to_string()),
+                },
+                TriplePattern {
+                    subject: VarOrNode::Variable("c".to_string()),
+                    predicate: VarOrNamedNode::NamedNode(IRI::Full(predicate.to_string())),
+                    object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                },
+            ],
+        )
+    }
+
+    /// Retrieve the lexical form of the first literal bound to the `o` variable by the patterns.
+    fn literal(
+        &self,
+        querier: QuerierWrapper<'_>,
+        patterns: Vec<TriplePattern>,
+    ) -> StdResult<Option<String>> {
+        let resp = self.triplestore.select(
+            querier,
+            SelectQuery {
+                prefixes: vec![],
+                limit: Some(1),
+                select: vec![SelectItem::Variable("o".to_string())],
+                r#where: WhereClause::Bgp { patterns },
+                reasoning: None,
+                skolemize: false,
+                adaptive_joins: false,
+                ordered: false,
+                from: vec![],
+                from_named: vec![],
+            },
+        )?;
+
+        Ok(resp
+            .results
+            .bindings
+            .first()
+            .and_then(|binding| match binding.get("o") {
+                Some(Value::Literal { value, .. }) => Some(value.clone()),
+                _ => None,
+            }))
+    }
+
+    /// Retrieve the issuer and the claim subject of a registered credential.
+    pub fn credential_parties(
+        &self,
+        querier: QuerierWrapper<'_>,
+        credential: &str,
+    ) -> StdResult<(String, String)> {
+        let credential_pattern = |predicate: &str, variable: &str| TriplePattern {
+            subject: VarOrNode::Node(Node::NamedNode(IRI::Full(credential.to_string()))),
+            predicate: VarOrNamedNode::NamedNode(IRI::Full(predicate.to_string())),
+            object: VarOrNodeOrLiteral::Variable(variable.to_string()),
+        };
+
+        let resp = self.triplestore.select(
+            querier,
+            SelectQuery {
+                prefixes: vec![],
+                limit: Some(1),
+                select: vec![
+                    SelectItem::Variable("issuer".to_string()),
+                    SelectItem::Variable("subject".to_string()),
+                ],
+                r#where: WhereClause::Bgp {
+                    patterns: vec![
+                        credential_pattern(VC_BODY_ISSUER.iri, "issuer"),
+                        credential_pattern(VC_BODY_SUBJECT.iri, "subject"),
+                    ],
+                },
+                reasoning: None,
+                skolemize: false,
+                adaptive_joins: false,
+                ordered: false,
+                from: vec![],
+                from_named: vec![],
+            },
+        )?;
+
+        let binding = resp
+            .results
+            .bindings
+            .first()
+            .ok_or_else(|| StdError::not_found(format!("credential '{credential}'")))?;
+        Ok((binding.iri("issuer")?, binding.iri("subject")?))
+    }
+
+    /// Retrieve the properties held by the claim of a registered credential, grouped by predicate.
+    fn claim_properties(
+        &self,
+        querier: QuerierWrapper<'_>,
+        credential: &str,
+    ) -> StdResult<BTreeMap<String, Vec<Value>>> {
+        let limit = self.triplestore.store(querier)?.limits.max_query_limit;
+        let resp = self.triplestore.select(
+            querier,
+            SelectQuery {
+                prefixes: vec![],
+                limit: Some(limit),
+                select: vec![
+                    SelectItem::Variable("p".to_string()),
+                    SelectItem::Variable("o".to_string()),
+                ],
+                r#where: WhereClause::Bgp {
+                    patterns: vec![
+                        TriplePattern {
+                            subject: VarOrNode::Node(Node::NamedNode(IRI::Full(
+                                credential.to_string(),
+                            ))),
+                            predicate: VarOrNamedNode::NamedNode(IRI::Full(
+                                VC_BODY_CLAIM.iri.to_string(),
+                            )),
+                            object: VarOrNodeOrLiteral::Variable("c".to_string()),
+                        },
+                        TriplePattern {
+                            subject: VarOrNode::Variable("c".to_string()),
+                            predicate: VarOrNamedNode::Variable("p".to_string()),
+                            object: VarOrNodeOrLiteral::Variable("o".to_string()),
+                        },
+                    ],
+                },
+                reasoning: None,
+                skolemize: false,
+                adaptive_joins: false,
+                ordered: false,
+                from: vec![],
+                from_named: vec![],
+            },
+        )?;
+
+        if resp.results.bindings.is_empty() {
+            Err(StdError::not_found(format!("credential '{credential}'")))?;
+        }
+        // The properties can't be paged through, so the ones beyond the limit would be silently
+        // missed from the comparison.
+        if resp.results.bindings.len() >= limit as usize {
+            Err(StdError::generic_err(format!(
+                "Too many claim properties in credential '{credential}', exceeding the query limit of the triple store: {limit}"
+            )))?;
+        }
+
+        let mut properties: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+        for binding in &resp.results.bindings {
+            properties
+                .entry(binding.iri("p")?)
+                .or_default()
+                .push(binding.value("o")?.clone());
+        }
+
+        Ok(properties)
+    }
+}
+
+fn same_values(a: &[Value], b: &[Value]) -> bool {
+    a.len() == b.len() && a.iter().all(|v| b.contains(v))
 }
@@ -0,0 +1,164 @@
+use crate::contract::execute::{
+    ADDRESS_LINKAGE_ADDRESS, ADDRESS_LINKAGE_CREDENTIAL, ISSUER_REVOCATION_CREDENTIAL,
+};
+use crate::registrar::policy::{
+    USAGE_POLICY_ALLOWED_PURPOSE, USAGE_POLICY_CREDENTIAL, USAGE_POLICY_LICENSE,
+};
+
+/// The JSON-LD context of the W3C verifiable credentials data model the dataverse interprets.
+pub const VC_CONTEXT: &str = "https://www.w3.org/2018/credentials/v1";
+
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+
+/// The shape of the claims of a credential type the dataverse interprets, i.e. the properties it
+/// reads from them.
+pub struct ClaimShape {
+    /// The type of the credentials.
+    pub r#type: &'static str,
+    /// The properties of the claims.
+    pub properties: &'static [PropertyShape],
+}
+
+/// A property of a [ClaimShape].
+pub struct PropertyShape {
+    /// The predicate of the property.
+    pub predicate: &'static str,
+    /// The datatype of the literal value of the property, the value being an IRI if not set.
+    pub datatype: Option<&'static str>,
+    /// Whether the claims must hold the property.
+    pub required: bool,
+}
+
+/// The shapes of the credential types the dataverse interprets.
+pub const CLAIM_SHAPES: &[ClaimShape] = &[
+    ClaimShape {
+        r#type: ADDRESS_LINKAGE_CREDENTIAL,
+        properties: &[PropertyShape {
+            predicate: ADDRESS_LINKAGE_ADDRESS,
+            datatype: Some(XSD_STRING),
+            required: true,
+        }],
+    },
+    ClaimShape {
+        r#type: ISSUER_REVOCATION_CREDENTIAL,
+        properties: &[],
+    },
+    ClaimShape {
+        r#type: USAGE_POLICY_CREDENTIAL,
+        properties: &[
+            PropertyShape {
+                predicate: USAGE_POLICY_ALLOWED_PURPOSE,
+                datatype: None,
+                required: false,
+            },
+            PropertyShape {
+                predicate: USAGE_POLICY_LICENSE,
+                datatype: None,
+                required: false,
+            },
+        ],
+    },
+];
+
+impl ClaimShape {
+    /// The shape of the given credential type, if interpreted by the dataverse.
+    pub fn find(r#type: &str) -> Option<&'static ClaimShape> {
+        CLAIM_SHAPES.iter().find(|shape| shape.r#type == r#type)
+    }
+
+    /// The namespace of the credential type vocabulary, i.e. its IRI up to its last `/` or `#`.
+    pub fn vocabulary(&self) -> &'static str {
+        self.r#type
+            .rfind(['/', '#'])
+            .map_or(self.r#type, |i| &self.r#type[..=i])
+    }
+
+    /// A skeleton JSON-LD credential of the shape, the values to provide being left empty.
+    ///
+    /// The terms of the credential type and of its properties are defined in an embedded context,
+    /// so the document expands to the IRIs the dataverse expects without resolving any remote
+    /// context but the verifiable credentials one.
+    pub fn json_ld_skeleton(&self) -> String {
+        let term = |iri: &'static str| &iri[self.vocabulary().len()..];
+
+        let mut context = vec![format!(
+            r#"      "{}": "{}""#,
+            term(self.r#type),
+            self.r#type
+        )];
+        let mut subject = vec![r#"    "id": """#.to_string()];
+        for property in self.properties {
+            // plain strings are left untyped to expand to simple literals
+            let definition = match property.datatype {
+                Some(XSD_STRING) => format!(r#""{}""#, property.predicate),
+                Some(datatype) => format!(
+                    r#"{{ "@id": "{}", "@type": "{datatype}" }}"#,
+                    property.predicate
+                ),
+                None => format!(r#"{{ "@id": "{}", "@type": "@id" }}"#, property.predicate),
+            };
+            context.push(format!(
+                r#"      "{}": {definition}"#,
+                term(property.predicate)
+            ));
+            subject.push(format!(r#"    "{}": """#, term(property.predicate)));
+        }
+
+        format!(
+            r#"{{
+  "@context": [
+    "{VC_CONTEXT}",
+    {{
+{}
+    }}
+  ],
+  "id": "",
+  "type": ["VerifiableCredential", "{}"],
+  "issuer": "",
+  "issuanceDate": "",
+  "credentialSubject": {{
+{}
+  }}
+}}"#,
+            context.join(",\n"),
+            term(self.r#type),
+            subject.join(",\n"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proper_json_ld_skeleton() {
+        let shape = ClaimShape::find(ADDRESS_LINKAGE_CREDENTIAL).unwrap();
+        assert_eq!(
+            shape.vocabulary(),
+            "https://w3id.org/axone/ontology/vnext/schema/credential/address-linkage/"
+        );
+        assert_eq!(
+            shape.json_ld_skeleton(),
+            r#"{
+  "@context": [
+    "https://www.w3.org/2018/credentials/v1",
+    {
+      "AddressLinkageCredential": "https://w3id.org/axone/ontology/vnext/schema/credential/address-linkage/AddressLinkageCredential",
+      "hasAddress": "https://w3id.org/axone/ontology/vnext/schema/credential/address-linkage/hasAddress"
+    }
+  ],
+  "id": "",
+  "type": ["VerifiableCredential", "AddressLinkageCredential"],
+  "issuer": "",
+  "issuanceDate": "",
+  "credentialSubject": {
+    "id": "",
+    "hasAddress": ""
+  }
+}"#
+        );
+
+        assert!(ClaimShape::find("https://ex.org/UnknownCredential").is_none());
+    }
+}
@@ -1,11 +1,241 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use crate::msg::{Anchoring, OverrideAction, StateDigestResponse};
+use cosmwasm_std::{Addr, Binary, Empty, HexBinary, Uint64};
+use cw_storage_plus::{Item, Map};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 pub const DATAVERSE: Item<Dataverse> = Item::new("dataverse");
 
+/// The sequence number of the last submitted credential, incremented on each successful submission.
+pub const SUBMISSION_SEQUENCE: Item<u64> = Item::new("submission_sequence");
+
+/// The rolling digest of the claim registry, chaining every operation performed on it in sequence.
+pub const STATE_DIGEST: Item<StateDigest> = Item::new("state_digest");
+
+/// The periodic anchoring of the state digest, if enabled.
+pub const ANCHORING: Item<Anchoring> = Item::new("anchoring");
+
+/// The anchors of the state digest kept, by the sequence number of their last operation.
+pub const ANCHORS: Map<u64, Anchor> = Map::new("anchors");
+
+/// The external verifier contracts, by the type of the proofs they verify.
+pub const PROOF_VERIFIERS: Map<&str, Addr> = Map::new("proof_verifiers");
+
+/// The peer dataverses included in the federated queries.
+pub const PEERS: Map<&Addr, Empty> = Map::new("peers");
+
+/// The consumer gateways allowed to record the accesses to the registered resources.
+pub const GATEWAYS: Map<&Addr, Empty> = Map::new("gateways");
+
+/// The number of accesses recorded by the consumer gateways, by credential id.
+pub const ACCESS_COUNTS: Map<&str, u64> = Map::new("access_counts");
+
+/// The governance law stone deciding on the usage of resources not explicitly allowed by their
+/// usage policies.
+pub const GOVERNANCE: Item<Addr> = Item::new("governance");
+
+/// The guardian allowed to queue emergency overrides, if any.
+pub const GUARDIAN: Item<Guardian> = Item::new("guardian");
+
+/// The emergency overrides queued, by identifier.
+pub const OVERRIDES: Map<u64, Override> = Map::new("overrides");
+
+/// The identifier of the last queued emergency override.
+pub const OVERRIDE_SEQUENCE: Item<u64> = Item::new("override_sequence");
+
+/// Present while the dataverse is paused by an emergency override.
+pub const PAUSED: Item<Empty> = Item::new("paused");
+
+/// The submitted credentials with an expiration date not notified yet (see
+/// [crate::msg::ExecuteMsg::Tick]), by expiration time in seconds and credential id so the ones
+/// expiring first come first.
+pub const EXPIRING_CREDENTIALS: Map<(u64, &str), ExpiringCredential> =
+    Map::new("expiring_credentials");
+
+/// The submitted credentials not revoked, by issuer and credential id.
+///
+/// Unlike the other secondary indexes, it is completed rather than reset by
+/// [crate::msg::ExecuteMsg::RebuildIndexes] as the canonical hashes of the credentials can't be
+/// recovered from the triple store, the ones of the credentials it indexes being left empty.
+pub const ISSUER_CREDENTIALS: Map<(&str, &str), IssuedCredential> = Map::new("issuer_credentials");
+
+/// The issuer revocations applied (see [crate::msg::ExecuteMsg::RevokeAllByIssuer]), by identifier
+/// of their revocation credential, so they can't be replayed once completed.
+pub const ISSUER_REVOCATIONS: Map<&str, IssuerRevocation> = Map::new("issuer_revocations");
+
+/// The holder DID the submitter addresses are linked to through address linkage credentials, by
+/// canonical address so the addresses given with different bech32 prefixes are linked alike.
+///
+/// It is not rebuilt by [crate::msg::ExecuteMsg::RebuildIndexes].
+pub const ADDRESS_HOLDERS: Map<&[u8], String> = Map::new("address_holders");
+
+/// The public key of the holder the claims of the holder-bound credentials are bound to, by
+/// credential id.
+pub const HOLDER_BINDINGS: Map<&str, Vec<u8>> = Map::new("holder_bindings");
+
+/// The holder-bound credentials not revoked, by holder public key and credential id.
+pub const HOLDER_CLAIMS: Map<(&[u8], &str), BoundClaim> = Map::new("holder_claims");
+
+/// The holder sessions opened (see [crate::msg::ExecuteMsg::OpenHolderSession]), by identifier.
+pub const HOLDER_SESSIONS: Map<u64, HolderSession> = Map::new("holder_sessions");
+
+/// The identifier of the last opened holder session.
+pub const HOLDER_SESSION_SEQUENCE: Item<u64> = Item::new("holder_session_sequence");
+
+/// The rebuild of the secondary indexes in progress, if any (see
+/// [crate::msg::ExecuteMsg::RebuildIndexes]).
+pub const INDEX_REBUILD: Item<IndexRebuild> = Item::new("index_rebuild");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct IndexRebuild {
+    /// The cursor of the triple store export to resume the scan from, [None] to start it.
+    pub cursor: Option<Binary>,
+    /// Whether the scanned credentials are chained in the state digest, which is the case when
+    /// none was maintained before the rebuild.
+    #[serde(default)]
+    pub chain_digest: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Anchor {
+    /// The sequence number of the first operation chained since the previous anchor.
+    pub from_sequence: u64,
+    /// The SHA-256 digest as of the last operation, whose sequence number is the key of the anchor.
+    pub digest: Vec<u8>,
+    /// The block time of the anchoring, in seconds.
+    pub anchored_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Guardian {
+    pub address: Addr,
+    /// The delay, in seconds, the overrides are timelocked for.
+    pub delay: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Override {
+    pub action: OverrideAction,
+    /// The guardian who queued the override.
+    pub guardian: Addr,
+    /// The block time of the queuing, in seconds.
+    pub queued_at: u64,
+    /// The time from which the override can be executed, in seconds.
+    pub executable_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct IssuerRevocation {
+    /// The identifier of the last credential of the issuer scanned, [None] once all of them have
+    /// been scanned.
+    pub cursor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct IssuedCredential {
+    /// The SHA-256 hash of the canonical form of the credential, proofs excluded.
+    pub canonical_hash: Vec<u8>,
+    /// The block time of the submission, in seconds.
+    pub submitted_at: u64,
+    /// The expiration time of the credential in seconds, if any, as indexed in
+    /// [EXPIRING_CREDENTIALS].
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BoundClaim {
+    pub subject: String,
+    pub r#type: String,
+    pub issuer: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct HolderSession {
+    /// The address which opened the session, the only one allowed to retrieve its claims.
+    pub opener: Addr,
+    /// The public key of the holder.
+    pub holder_key: Vec<u8>,
+    /// The nonce the holder has to sign, along with the address of the opener, to retrieve its
+    /// claims.
+    pub nonce: Vec<u8>,
+    /// The expiration time of the session, in seconds.
+    pub expires_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExpiringCredential {
+    pub issuer: String,
+    pub subject: String,
+    /// The expiration date, as found in the credential.
+    pub valid_until: String,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Dataverse {
     pub name: String,
     pub triplestore_address: Addr,
 }
+
+/// The operations performed on the claim registry which are chained in the [StateDigest].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClaimOperation {
+    Submission,
+    Revocation,
+}
+
+impl ClaimOperation {
+    fn tag(self) -> u8 {
+        match self {
+            ClaimOperation::Submission => 0,
+            ClaimOperation::Revocation => 1,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StateDigest {
+    /// The number of operations chained in the digest.
+    pub sequence: u64,
+    /// The SHA-256 digest of the last chained operation, zeroed when there is none.
+    pub digest: Vec<u8>,
+}
+
+impl Default for StateDigest {
+    fn default() -> Self {
+        Self {
+            sequence: 0,
+            digest: vec![0; 32],
+        }
+    }
+}
+
+impl StateDigest {
+    /// Chain an operation on a credential, identified by its id and canonical hash, to the digest.
+    ///
+    /// The new digest is the SHA-256 of the previous digest, the big-endian new sequence, the
+    /// operation tag, the big-endian length of the credential id, the credential id and the
+    /// canonical hash.
+    pub fn chain(self, operation: ClaimOperation, credential: &str, canonical_hash: &[u8]) -> Self {
+        let sequence = self.sequence + 1;
+        let digest = Sha256::new()
+            .chain_update(self.digest)
+            .chain_update(sequence.to_be_bytes())
+            .chain_update([operation.tag()])
+            .chain_update((credential.len() as u32).to_be_bytes())
+            .chain_update(credential)
+            .chain_update(canonical_hash)
+            .finalize()
+            .to_vec();
+
+        Self { sequence, digest }
+    }
+}
+
+impl From<StateDigest> for StateDigestResponse {
+    fn from(value: StateDigest) -> Self {
+        Self {
+            sequence: Uint64::new(value.sequence),
+            digest: HexBinary::from(value.digest),
+        }
+    }
+}
@@ -1,11 +1,16 @@
 #[cfg(test)]
 pub mod testutil {
+    use crate::registrar::credential::Submitter;
+    use axone_cognitarium::msg::{ConflictPolicy, StoreLimitsInput, StoreResponse};
+    use axone_cognitarium::state::Store;
     use axone_rdf::owned_model::OwnedQuad;
     use axone_rdf::serde::NQuadsReader;
+    use cosmwasm_std::testing::mock_dependencies;
     use std::env;
     use std::fs::File;
     use std::io::{BufReader, Read};
     use std::path::Path;
+    use testing::addr::{addr, CREATOR, SENDER};
 
     pub fn read_test_quads(file: &str) -> Vec<OwnedQuad> {
         let raw_rdf = read_test_data(file);
@@ -14,6 +19,25 @@ pub mod testutil {
         reader.read_all().unwrap()
     }
 
+    pub fn submitter() -> Submitter {
+        Submitter::try_new(mock_dependencies().as_ref(), addr(SENDER).as_str()).unwrap()
+    }
+
+    /// The response of the `Store` query of a triple store instantiated with the given query limit.
+    pub fn store_response(max_query_limit: u32) -> StoreResponse {
+        let limits = StoreLimitsInput {
+            max_query_limit,
+            ..Default::default()
+        };
+        Store::new(
+            addr(CREATOR),
+            limits.into(),
+            ConflictPolicy::default(),
+            true,
+        )
+        .into()
+    }
+
     pub fn read_test_data(file: &str) -> Vec<u8> {
         let mut bytes: Vec<u8> = Vec::new();
 
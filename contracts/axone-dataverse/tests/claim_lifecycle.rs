@@ -0,0 +1,1677 @@
+mod fixtures;
+
+use axone_cognitarium::msg::{
+    Node, SelectItem, SelectQuery, SelectResponse, TriplePattern, Value, VarOrNamedNode, VarOrNode,
+    VarOrNodeOrLiteral, WhereClause, IRI,
+};
+use axone_dataverse::contract::execute::{
+    ADDRESS_LINKAGE_ADDRESS, ADDRESS_LINKAGE_CREDENTIAL, HOLDER_BINDING_KEY,
+    ISSUER_REVOCATION_CREDENTIAL,
+};
+use axone_dataverse::msg::{
+    AddressHolderResponse, Anchor, Anchoring, AnchorsResponse, ClaimProperty, ClaimPropertyChange,
+    ClaimsByIssuerResponse, ClaimsFilter, ClaimsResponse, CompareClaimsResponse, DataverseResponse,
+    ExecuteMsg, HolderSessionReceipt, InstantiateMsg, MigrateMsg, QueryMsg, RdfDatasetFormat,
+    StateDigestResponse, SubmitClaimsReceipt, TripleStoreConfig, TripleStoreLimitsInput,
+};
+use axone_dataverse::state::{
+    EXPIRING_CREDENTIALS, HOLDER_BINDINGS, HOLDER_CLAIMS, INDEX_REBUILD, ISSUER_CREDENTIALS,
+    STATE_DIGEST, SUBMISSION_SEQUENCE,
+};
+use axone_dataverse::ContractError;
+use cosmwasm_std::{from_json, Addr, Api, Binary, HexBinary, Order, Timestamp, Uint64};
+use cw_multi_test::{App, ContractWrapper, Executor};
+use fixtures::{CredentialTemplate, KeyPair};
+use sha2::{Digest, Sha256};
+
+struct Dataverse {
+    app: App,
+    address: Addr,
+    triplestore: Addr,
+}
+
+impl Dataverse {
+    fn setup() -> Self {
+        Self::setup_with_limits(TripleStoreLimitsInput::default())
+    }
+
+    fn setup_with_limits(limits: TripleStoreLimitsInput) -> Self {
+        let mut app = App::default();
+        let creator = app.api().addr_make("creator");
+
+        let triplestore_code_id = app.store_code(Box::new(ContractWrapper::new(
+            axone_cognitarium::contract::execute,
+            axone_cognitarium::contract::instantiate,
+            axone_cognitarium::contract::query,
+        )));
+        let dataverse_code_id = app.store_code(Box::new(
+            ContractWrapper::new(
+                axone_dataverse::contract::execute,
+                axone_dataverse::contract::instantiate,
+                axone_dataverse::contract::query,
+            )
+            .with_migrate(axone_dataverse::contract::migrate),
+        ));
+
+        let address = app
+            .instantiate_contract(
+                dataverse_code_id,
+                creator.clone(),
+                &InstantiateMsg {
+                    name: "my-dataverse".to_string(),
+                    triplestore_config: TripleStoreConfig {
+                        code_id: Uint64::from(triplestore_code_id),
+                        limits,
+                    },
+                },
+                &[],
+                "dataverse",
+                Some(creator.to_string()),
+            )
+            .unwrap();
+
+        let DataverseResponse {
+            triplestore_address,
+            ..
+        } = app
+            .wrap()
+            .query_wasm_smart(&address, &QueryMsg::Dataverse {})
+            .unwrap();
+
+        Self {
+            app,
+            address,
+            triplestore: triplestore_address,
+        }
+    }
+
+    fn submit(&mut self, sender: &str, claims: Binary) -> Result<SubmitClaimsReceipt, String> {
+        let sender = self.app.api().addr_make(sender);
+        self.app
+            .execute_contract(
+                sender,
+                self.address.clone(),
+                &ExecuteMsg::SubmitClaims {
+                    claims,
+                    format: Some(RdfDatasetFormat::NQuads),
+                },
+                &[],
+            )
+            .map(|res| from_json(res.data.expect("receipt should be set")).unwrap())
+            .map_err(|e| e.root_cause().to_string())
+    }
+
+    fn credential_property(&self, credential: &str, property: &str) -> Vec<Value> {
+        self.select_values(vec![TriplePattern {
+            subject: VarOrNode::Node(Node::NamedNode(IRI::Full(credential.to_string()))),
+            predicate: VarOrNamedNode::NamedNode(IRI::Full(property.to_string())),
+            object: VarOrNodeOrLiteral::Variable("v".to_string()),
+        }])
+    }
+
+    /// Retrieve the values of a property of the claim of a credential, held by its claim node.
+    fn claim_property(&self, credential: &str, property: &str) -> Vec<Value> {
+        self.select_values(vec![
+            TriplePattern {
+                subject: VarOrNode::Node(Node::NamedNode(IRI::Full(credential.to_string()))),
+                predicate: VarOrNamedNode::NamedNode(IRI::Full(
+                    "dataverse:credential:body#claim".to_string(),
+                )),
+                object: VarOrNodeOrLiteral::Variable("c".to_string()),
+            },
+            TriplePattern {
+                subject: VarOrNode::Variable("c".to_string()),
+                predicate: VarOrNamedNode::NamedNode(IRI::Full(property.to_string())),
+                object: VarOrNodeOrLiteral::Variable("v".to_string()),
+            },
+        ])
+    }
+
+    fn select_values(&self, patterns: Vec<TriplePattern>) -> Vec<Value> {
+        let resp: SelectResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.triplestore,
+                &axone_cognitarium::msg::QueryMsg::Select {
+                    query: SelectQuery {
+                        prefixes: vec![],
+                        limit: None,
+                        select: vec![SelectItem::Variable("v".to_string())],
+                        r#where: WhereClause::Bgp { patterns },
+                        reasoning: None,
+                        skolemize: false,
+                        adaptive_joins: false,
+                        ordered: false,
+                        from: vec![],
+                        from_named: vec![],
+                    },
+                },
+            )
+            .unwrap();
+
+        resp.results
+            .bindings
+            .into_iter()
+            .filter_map(|mut b| b.remove("v"))
+            .collect()
+    }
+}
+
+fn literal(value: &str) -> Value {
+    Value::Literal {
+        value: value.to_string(),
+        lang: None,
+        datatype: None,
+    }
+}
+
+fn uri(value: &str) -> Value {
+    Value::URI {
+        value: IRI::Full(value.to_string()),
+    }
+}
+
+#[test]
+fn submit_then_query_claims() {
+    let cases = vec![KeyPair::ed25519(1), KeyPair::secp256k1(2)];
+
+    for issuer in cases {
+        let mut dataverse = Dataverse::setup();
+        let template = CredentialTemplate::default();
+
+        assert!(dataverse.submit("sender", template.sign(&issuer)).is_ok());
+
+        assert_eq!(
+            dataverse.credential_property(template.id, "dataverse:credential:body#issuer"),
+            vec![uri(&issuer.did())]
+        );
+        assert_eq!(
+            dataverse.credential_property(template.id, "dataverse:credential:body#type"),
+            vec![uri(template.r#type)]
+        );
+        assert_eq!(
+            dataverse.credential_property(template.id, "dataverse:credential:body#subject"),
+            vec![uri(template.subject)]
+        );
+        assert_eq!(
+            dataverse.credential_property(template.id, "dataverse:credential:header#sender"),
+            vec![Value::Literal {
+                value: dataverse.app.api().addr_make("sender").to_string(),
+                lang: None,
+                datatype: None,
+            }]
+        );
+    }
+}
+
+#[test]
+fn submit_several_credentials() {
+    let mut dataverse = Dataverse::setup();
+    let issuer = KeyPair::ed25519(1);
+
+    let first = CredentialTemplate::default();
+    let second = CredentialTemplate {
+        id: "https://example.org/credentials/2",
+        subject: "https://example.org/datasets/2",
+        expiration_date: Some("2030-01-01T00:00:00Z"),
+        ..CredentialTemplate::default()
+    };
+
+    assert!(dataverse.submit("sender", first.sign(&issuer)).is_ok());
+    assert!(dataverse.submit("sender", second.sign(&issuer)).is_ok());
+
+    assert_eq!(
+        dataverse.credential_property(second.id, "dataverse:credential:body#validUntil"),
+        vec![Value::Literal {
+            value: "2030-01-01T00:00:00Z".to_string(),
+            lang: None,
+            datatype: Some(IRI::Full(
+                "http://www.w3.org/2001/XMLSchema#dateTime".to_string()
+            )),
+        }]
+    );
+}
+
+#[test]
+fn submission_receipts() {
+    let mut dataverse = Dataverse::setup();
+    let issuer = KeyPair::secp256k1(3);
+
+    let first = CredentialTemplate::default();
+    let second = CredentialTemplate {
+        id: "https://example.org/credentials/2",
+        ..CredentialTemplate::default()
+    };
+
+    for (sequence, template) in [(1u64, &first), (2u64, &second)] {
+        assert_eq!(
+            dataverse.submit("sender", template.sign(&issuer)),
+            Ok(SubmitClaimsReceipt {
+                credential: template.id.to_string(),
+                canonical_hash: HexBinary::from(template.canonical_hash(&issuer)),
+                sequence: Uint64::new(sequence),
+                triplestore_address: dataverse.triplestore.clone(),
+            })
+        );
+    }
+}
+
+#[test]
+fn state_digest() {
+    let mut dataverse = Dataverse::setup();
+    let issuer = KeyPair::ed25519(4);
+
+    let state_digest = |dataverse: &Dataverse| -> StateDigestResponse {
+        dataverse
+            .app
+            .wrap()
+            .query_wasm_smart(&dataverse.address, &QueryMsg::StateDigest {})
+            .unwrap()
+    };
+
+    let mut expected = StateDigestResponse {
+        sequence: Uint64::zero(),
+        digest: HexBinary::from(vec![0; 32]),
+    };
+    assert_eq!(state_digest(&dataverse), expected);
+
+    let first = CredentialTemplate::default();
+    let second = CredentialTemplate {
+        id: "https://example.org/credentials/2",
+        ..CredentialTemplate::default()
+    };
+    for template in [&first, &second] {
+        let receipt = dataverse.submit("sender", template.sign(&issuer)).unwrap();
+
+        let sequence = expected.sequence.u64() + 1;
+        expected = StateDigestResponse {
+            sequence: Uint64::new(sequence),
+            digest: HexBinary::from(
+                Sha256::new()
+                    .chain_update(expected.digest)
+                    .chain_update(sequence.to_be_bytes())
+                    .chain_update([0])
+                    .chain_update((receipt.credential.len() as u32).to_be_bytes())
+                    .chain_update(receipt.credential)
+                    .chain_update(receipt.canonical_hash)
+                    .finalize()
+                    .to_vec(),
+            ),
+        };
+        assert_eq!(state_digest(&dataverse), expected);
+    }
+
+    assert!(dataverse.submit("sender", first.sign(&issuer)).is_err());
+    assert_eq!(state_digest(&dataverse), expected);
+}
+
+#[test]
+fn state_anchors() {
+    let mut dataverse = Dataverse::setup();
+    let issuer = KeyPair::ed25519(4);
+    let creator = dataverse.app.api().addr_make("creator");
+
+    let set_anchoring = ExecuteMsg::SetAnchoring {
+        anchoring: Some(Anchoring {
+            interval: 2,
+            retention: 2,
+        }),
+    };
+    assert!(dataverse
+        .app
+        .execute_contract(
+            dataverse.app.api().addr_make("sender"),
+            dataverse.address.clone(),
+            &set_anchoring,
+            &[],
+        )
+        .is_err());
+    assert!(dataverse
+        .app
+        .execute_contract(
+            creator.clone(),
+            dataverse.address.clone(),
+            &ExecuteMsg::SetAnchoring {
+                anchoring: Some(Anchoring {
+                    interval: 2,
+                    retention: 0,
+                }),
+            },
+            &[],
+        )
+        .is_err());
+    dataverse
+        .app
+        .execute_contract(creator, dataverse.address.clone(), &set_anchoring, &[])
+        .unwrap();
+
+    let mut digests = vec![];
+    for i in 1..=6 {
+        let id = format!("https://example.org/credentials/{i}");
+        let template = CredentialTemplate {
+            id: &id,
+            ..CredentialTemplate::default()
+        };
+        let sender = dataverse.app.api().addr_make("sender");
+        let res = dataverse
+            .app
+            .execute_contract(
+                sender,
+                dataverse.address.clone(),
+                &ExecuteMsg::SubmitClaims {
+                    claims: template.sign(&issuer),
+                    format: Some(RdfDatasetFormat::NQuads),
+                },
+                &[],
+            )
+            .unwrap();
+
+        let digest: StateDigestResponse = dataverse
+            .app
+            .wrap()
+            .query_wasm_smart(&dataverse.address, &QueryMsg::StateDigest {})
+            .unwrap();
+        let anchored = res.events.iter().any(|event| {
+            event.ty == "wasm-anchor"
+                && event
+                    .attributes
+                    .iter()
+                    .any(|attr| attr.key == "digest" && attr.value == digest.digest.to_hex())
+        });
+        assert_eq!(anchored, i % 2 == 0);
+        digests.push(digest.digest);
+    }
+
+    let block_time = Timestamp::from_seconds(dataverse.app.block_info().time.seconds());
+    assert_eq!(
+        dataverse
+            .app
+            .wrap()
+            .query_wasm_smart::<AnchorsResponse>(&dataverse.address, &QueryMsg::Anchors {})
+            .unwrap(),
+        AnchorsResponse {
+            anchoring: Some(Anchoring {
+                interval: 2,
+                retention: 2,
+            }),
+            anchors: vec![
+                Anchor {
+                    from_sequence: Uint64::new(3),
+                    to_sequence: Uint64::new(4),
+                    digest: digests[3].clone(),
+                    anchored_at: block_time,
+                },
+                Anchor {
+                    from_sequence: Uint64::new(5),
+                    to_sequence: Uint64::new(6),
+                    digest: digests[5].clone(),
+                    anchored_at: block_time,
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn compare_claims() {
+    let mut dataverse = Dataverse::setup();
+    let issuer = KeyPair::ed25519(1);
+
+    let original = CredentialTemplate::default();
+    let amendment = CredentialTemplate {
+        id: "https://example.org/credentials/2",
+        claims: vec![
+            (
+                "https://example.org/examples#title",
+                "\"My amended dataset\"",
+            ),
+            (
+                "https://example.org/examples#license",
+                "<https://creativecommons.org/licenses/by/4.0/>",
+            ),
+            ("https://example.org/examples#version", "\"2\""),
+        ],
+        ..CredentialTemplate::default()
+    };
+    assert!(dataverse.submit("sender", original.sign(&issuer)).is_ok());
+    assert!(dataverse.submit("sender", amendment.sign(&issuer)).is_ok());
+
+    let compare = |a: &str, b: &str| -> Result<CompareClaimsResponse, String> {
+        dataverse
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &dataverse.address,
+                &QueryMsg::CompareClaims {
+                    credential_a: a.to_string(),
+                    credential_b: b.to_string(),
+                },
+            )
+            .map_err(|e| e.to_string())
+    };
+
+    assert_eq!(
+        compare(original.id, amendment.id),
+        Ok(CompareClaimsResponse {
+            added: vec![ClaimProperty {
+                predicate: "https://example.org/examples#version".to_string(),
+                values: vec![literal("2")],
+            }],
+            removed: vec![],
+            changed: vec![ClaimPropertyChange {
+                predicate: "https://example.org/examples#title".to_string(),
+                from: vec![literal("My dataset")],
+                to: vec![literal("My amended dataset")],
+            }],
+        })
+    );
+    assert_eq!(
+        compare(amendment.id, original.id).map(|r| r.removed),
+        Ok(vec![ClaimProperty {
+            predicate: "https://example.org/examples#version".to_string(),
+            values: vec![literal("2")],
+        }])
+    );
+    assert_eq!(
+        compare(original.id, original.id),
+        Ok(CompareClaimsResponse {
+            added: vec![],
+            removed: vec![],
+            changed: vec![],
+        })
+    );
+    assert!(
+        compare(original.id, "https://example.org/credentials/unknown")
+            .unwrap_err()
+            .contains("credential 'https://example.org/credentials/unknown' not found")
+    );
+
+    let mut dataverse = Dataverse::setup_with_limits(TripleStoreLimitsInput {
+        max_query_limit: Some(3),
+        ..TripleStoreLimitsInput::default()
+    });
+    assert!(dataverse.submit("sender", original.sign(&issuer)).is_ok());
+    assert!(dataverse.submit("sender", amendment.sign(&issuer)).is_ok());
+    let res: Result<CompareClaimsResponse, _> = dataverse.app.wrap().query_wasm_smart(
+        &dataverse.address,
+        &QueryMsg::CompareClaims {
+            credential_a: original.id.to_string(),
+            credential_b: amendment.id.to_string(),
+        },
+    );
+    assert!(res.unwrap_err().to_string().contains(
+        "Too many claim properties in credential 'https://example.org/credentials/2', exceeding the query limit of the triple store: 3"
+    ));
+}
+
+#[test]
+fn claims_about_same_resources() {
+    let mut dataverse = Dataverse::setup();
+    let issuer = KeyPair::ed25519(1);
+
+    let original = CredentialTemplate {
+        claims: vec![(
+            "http://www.w3.org/2002/07/owl#sameAs",
+            "<https://example.org/datasets/1-mirror>",
+        )],
+        ..CredentialTemplate::default()
+    };
+    let mirror = CredentialTemplate {
+        id: "https://example.org/credentials/2",
+        subject: "https://example.org/datasets/1-mirror",
+        ..CredentialTemplate::default()
+    };
+    let other = CredentialTemplate {
+        id: "https://example.org/credentials/3",
+        subject: "https://example.org/datasets/2",
+        ..CredentialTemplate::default()
+    };
+    for template in [&original, &mirror, &other] {
+        assert!(dataverse.submit("sender", template.sign(&issuer)).is_ok());
+    }
+
+    let credentials = |subject: &str, same_as: Option<u32>| -> Vec<String> {
+        dataverse
+            .app
+            .wrap()
+            .query_wasm_smart::<ClaimsResponse>(
+                &dataverse.address,
+                &QueryMsg::Claims {
+                    filter: ClaimsFilter {
+                        subject: Some(subject.to_string()),
+                        same_as,
+                        ..Default::default()
+                    },
+                },
+            )
+            .unwrap()
+            .claims
+            .into_iter()
+            .map(|c| c.credential)
+            .collect()
+    };
+
+    assert_eq!(credentials(original.subject, None), vec![original.id]);
+    assert_eq!(credentials(original.subject, Some(0)), vec![original.id]);
+    assert_eq!(
+        credentials(original.subject, Some(1)),
+        vec![original.id, mirror.id]
+    );
+    assert_eq!(
+        credentials(mirror.subject, Some(1)),
+        vec![mirror.id, original.id]
+    );
+    assert_eq!(credentials(other.subject, Some(1)), vec![other.id]);
+
+    // The links followed are capped to the inference depth limit of the triple store.
+    let mut dataverse = Dataverse::setup_with_limits(TripleStoreLimitsInput {
+        max_query_inference_depth: Some(0),
+        ..TripleStoreLimitsInput::default()
+    });
+    for template in [&original, &mirror] {
+        assert!(dataverse.submit("sender", template.sign(&issuer)).is_ok());
+    }
+    let res: ClaimsResponse = dataverse
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &dataverse.address,
+            &QueryMsg::Claims {
+                filter: ClaimsFilter {
+                    subject: Some(original.subject.to_string()),
+                    same_as: Some(1),
+                    ..Default::default()
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.claims
+            .into_iter()
+            .map(|c| c.credential)
+            .collect::<Vec<_>>(),
+        vec![original.id]
+    );
+}
+
+#[test]
+fn reject_already_submitted_credential() {
+    let mut dataverse = Dataverse::setup();
+    let template = CredentialTemplate::default();
+
+    assert!(dataverse
+        .submit("sender", template.sign(&KeyPair::ed25519(1)))
+        .is_ok());
+    assert_eq!(
+        dataverse.submit("other", template.sign(&KeyPair::secp256k1(2))),
+        Err(ContractError::CredentialAlreadyExists(template.id.to_string()).to_string())
+    );
+}
+
+#[test]
+fn amend_then_revoke_claims() {
+    let mut dataverse = Dataverse::setup();
+    let issuer = KeyPair::ed25519(1);
+    let did = issuer.did();
+    let title = "https://example.org/examples#title";
+    dataverse
+        .app
+        .update_block(|block| block.time = Timestamp::from_seconds(1_700_000_000)); // 2023-11-14T22:13:20Z
+
+    let original = CredentialTemplate::default();
+    let amendment = CredentialTemplate {
+        id: "https://example.org/credentials/2",
+        claims: vec![(title, "\"My amended dataset\"")],
+        ..CredentialTemplate::default()
+    };
+    assert!(dataverse.submit("sender", original.sign(&issuer)).is_ok());
+
+    // A credential can't be amended in place, its amendment being submitted as a new credential.
+    let in_place = CredentialTemplate {
+        id: original.id,
+        claims: vec![(title, "\"My amended dataset\"")],
+        ..CredentialTemplate::default()
+    };
+    assert_eq!(
+        dataverse.submit("sender", in_place.sign(&issuer)),
+        Err(ContractError::CredentialAlreadyExists(original.id.to_string()).to_string())
+    );
+    assert!(dataverse.submit("sender", amendment.sign(&issuer)).is_ok());
+    assert_eq!(
+        dataverse.claim_property(original.id, title),
+        vec![literal("My dataset")]
+    );
+    assert_eq!(
+        dataverse.claim_property(amendment.id, title),
+        vec![literal("My amended dataset")]
+    );
+
+    let claims = |dataverse: &Dataverse| -> Vec<String> {
+        let mut claims: Vec<String> = dataverse
+            .app
+            .wrap()
+            .query_wasm_smart::<ClaimsResponse>(
+                &dataverse.address,
+                &QueryMsg::Claims {
+                    filter: ClaimsFilter {
+                        issuer: Some(did.clone()),
+                        ..ClaimsFilter::default()
+                    },
+                },
+            )
+            .unwrap()
+            .claims
+            .into_iter()
+            .map(|claim| claim.credential)
+            .collect();
+        claims.sort();
+        claims
+    };
+    assert_eq!(claims(&dataverse), vec![original.id, amendment.id]);
+
+    dataverse
+        .app
+        .update_block(|block| block.time = Timestamp::from_seconds(1_705_000_000)); // 2024-01-11T19:06:40Z
+    let revocation = CredentialTemplate {
+        id: "https://example.org/revocations/1",
+        r#type: ISSUER_REVOCATION_CREDENTIAL,
+        subject: &did,
+        issuance_date: "2024-01-05T00:00:00Z",
+        ..CredentialTemplate::default()
+    };
+    let res = dataverse.app.execute_contract(
+        dataverse.app.api().addr_make("anyone"),
+        dataverse.address.clone(),
+        &ExecuteMsg::RevokeAllByIssuer {
+            did: did.clone(),
+            revocation: revocation.sign(&issuer),
+            limit: None,
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+
+    assert!(claims(&dataverse).is_empty());
+    for credential in [original.id, amendment.id] {
+        assert!(dataverse.claim_property(credential, title).is_empty());
+        for property in [
+            "dataverse:credential:body#issuer",
+            "dataverse:credential:body#type",
+            "dataverse:credential:body#subject",
+            "dataverse:credential:body#claim",
+            "dataverse:credential:header#sender",
+        ] {
+            assert!(
+                dataverse
+                    .credential_property(credential, property)
+                    .is_empty(),
+                "{property} of {credential} should have been removed"
+            );
+        }
+    }
+    let storage = dataverse.app.contract_storage(&dataverse.address);
+    assert!(ISSUER_CREDENTIALS
+        .prefix(&did)
+        .keys(storage.as_ref(), None, None, Order::Ascending)
+        .next()
+        .is_none());
+}
+
+#[test]
+fn reject_tampered_credential() {
+    let mut dataverse = Dataverse::setup();
+    let issuer = KeyPair::ed25519(1);
+
+    let signed = CredentialTemplate::default().sign(&issuer);
+    let tampered = String::from_utf8(signed.to_vec())
+        .unwrap()
+        .replace("\"My dataset\"", "\"Not my dataset\"");
+
+    let res = dataverse.submit("sender", Binary::from(tampered.into_bytes()));
+    assert!(res.is_err());
+    assert!(res
+        .unwrap_err()
+        .starts_with("Credential verification failed"));
+}
+
+#[test]
+fn notify_expiring_credentials() {
+    let mut dataverse = Dataverse::setup();
+    let issuer = KeyPair::ed25519(1);
+    dataverse
+        .app
+        .update_block(|block| block.time = Timestamp::from_seconds(1_700_000_000)); // 2023-11-14T22:13:20Z
+
+    let credentials = [
+        ("https://example.org/credentials/1", None),
+        (
+            "https://example.org/credentials/2",
+            Some("2023-11-20T00:00:00Z"),
+        ),
+        (
+            "https://example.org/credentials/3",
+            Some("2023-11-17T00:00:00+02:00"),
+        ),
+        (
+            "https://example.org/credentials/4",
+            Some("2024-01-01T00:00:00Z"),
+        ),
+    ];
+    for (id, expiration_date) in credentials {
+        let template = CredentialTemplate {
+            id,
+            expiration_date,
+            ..CredentialTemplate::default()
+        };
+        assert!(dataverse.submit("sender", template.sign(&issuer)).is_ok());
+    }
+
+    let tick = |dataverse: &mut Dataverse, limit: Option<u32>| -> Vec<String> {
+        dataverse
+            .app
+            .execute_contract(
+                dataverse.app.api().addr_make("anyone"),
+                dataverse.address.clone(),
+                &ExecuteMsg::Tick { limit },
+                &[],
+            )
+            .unwrap()
+            .events
+            .into_iter()
+            .filter(|event| event.ty == "wasm-credential_expiring_soon")
+            .map(|event| {
+                assert!(event
+                    .attributes
+                    .iter()
+                    .any(|a| a.key == "issuer" && a.value == issuer.did()));
+                event
+                    .attributes
+                    .into_iter()
+                    .find(|a| a.key == "credential")
+                    .unwrap()
+                    .value
+            })
+            .collect()
+    };
+
+    assert_eq!(
+        tick(&mut dataverse, Some(1)),
+        vec!["https://example.org/credentials/3"]
+    );
+    assert_eq!(
+        tick(&mut dataverse, None),
+        vec!["https://example.org/credentials/2"]
+    );
+    assert_eq!(tick(&mut dataverse, None), Vec::<String>::new());
+
+    dataverse
+        .app
+        .update_block(|block| block.time = Timestamp::from_seconds(1_703_800_000)); // 2023-12-28T21:46:40Z
+    assert_eq!(
+        tick(&mut dataverse, None),
+        vec!["https://example.org/credentials/4"]
+    );
+}
+
+#[test]
+fn rebuild_indexes() {
+    let mut dataverse = Dataverse::setup();
+    let issuer = KeyPair::ed25519(1);
+    dataverse
+        .app
+        .update_block(|block| block.time = Timestamp::from_seconds(1_700_000_000)); // 2023-11-14T22:13:20Z
+
+    let credentials = [
+        ("https://example.org/credentials/1", None),
+        (
+            "https://example.org/credentials/2",
+            Some("2023-11-01T00:00:00Z"),
+        ),
+        (
+            "https://example.org/credentials/3",
+            Some("2024-01-01T00:00:00Z"),
+        ),
+        ("https://example.org/credentials/4", None),
+        ("https://example.org/credentials/5", None),
+        ("https://example.org/credentials/6", None),
+        ("https://example.org/credentials/7", None),
+        ("https://example.org/credentials/8", None),
+    ];
+    for (id, expiration_date) in credentials {
+        let template = CredentialTemplate {
+            id,
+            expiration_date,
+            ..CredentialTemplate::default()
+        };
+        assert!(dataverse.submit("sender", template.sign(&issuer)).is_ok());
+    }
+
+    let expiring = |dataverse: &Dataverse| -> Vec<String> {
+        let storage = dataverse.app.contract_storage(&dataverse.address);
+        EXPIRING_CREDENTIALS
+            .keys(storage.as_ref(), None, None, Order::Ascending)
+            .map(|key| key.unwrap().1)
+            .collect()
+    };
+    assert_eq!(
+        expiring(&dataverse),
+        vec![
+            "https://example.org/credentials/2",
+            "https://example.org/credentials/3"
+        ]
+    );
+
+    // the indexes get lost
+    {
+        let mut storage = dataverse.app.contract_storage_mut(&dataverse.address);
+        SUBMISSION_SEQUENCE.remove(storage.as_mut());
+        EXPIRING_CREDENTIALS.clear(storage.as_mut());
+    }
+
+    let code_id = dataverse
+        .app
+        .wrap()
+        .query_wasm_contract_info(&dataverse.address)
+        .unwrap()
+        .code_id;
+    let res = dataverse
+        .app
+        .migrate_contract(
+            dataverse.app.api().addr_make("creator"),
+            dataverse.address.clone(),
+            &MigrateMsg {
+                rebuild_indexes: true,
+            },
+            code_id,
+        )
+        .unwrap();
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|a| a.key == "completed" && a.value == "false")));
+
+    let template = CredentialTemplate {
+        id: "https://example.org/credentials/9",
+        ..CredentialTemplate::default()
+    };
+    assert_eq!(
+        dataverse.submit("sender", template.sign(&issuer)),
+        Err("Secondary indexes are being rebuilt".to_string())
+    );
+
+    let mut batches = 0;
+    loop {
+        batches += 1;
+        let res = dataverse
+            .app
+            .execute_contract(
+                dataverse.app.api().addr_make("anyone"),
+                dataverse.address.clone(),
+                &ExecuteMsg::RebuildIndexes { limit: Some(20) },
+                &[],
+            )
+            .unwrap();
+        if res.events.iter().any(|event| {
+            event
+                .attributes
+                .iter()
+                .any(|a| a.key == "completed" && a.value == "true")
+        }) {
+            break;
+        }
+    }
+    assert!(batches > 1);
+
+    {
+        let storage = dataverse.app.contract_storage(&dataverse.address);
+        assert_eq!(
+            SUBMISSION_SEQUENCE.load(storage.as_ref()).unwrap(),
+            credentials.len() as u64
+        );
+        assert!(!INDEX_REBUILD.exists(storage.as_ref()));
+    }
+    assert_eq!(
+        expiring(&dataverse),
+        vec!["https://example.org/credentials/3"]
+    );
+
+    assert!(dataverse
+        .app
+        .execute_contract(
+            dataverse.app.api().addr_make("anyone"),
+            dataverse.address.clone(),
+            &ExecuteMsg::RebuildIndexes { limit: None },
+            &[],
+        )
+        .is_err());
+    let receipt = dataverse.submit("sender", template.sign(&issuer)).unwrap();
+    assert_eq!(receipt.sequence, Uint64::new(9));
+}
+
+#[test]
+fn rebuild_indexes_then_revoke_all_by_issuer() {
+    let mut dataverse = Dataverse::setup();
+    let issuer = KeyPair::ed25519(1);
+    let holder = KeyPair::ed25519(2);
+    dataverse
+        .app
+        .update_block(|block| block.time = Timestamp::from_seconds(1_700_000_000)); // 2023-11-14T22:13:20Z
+
+    let holder_key = format!("\"{}\"", HexBinary::from(holder.public_key()).to_hex());
+    for template in [
+        CredentialTemplate {
+            id: "https://example.org/credentials/1",
+            expiration_date: Some("2024-01-01T00:00:00Z"),
+            ..CredentialTemplate::default()
+        },
+        CredentialTemplate {
+            id: "https://example.org/credentials/2",
+            claims: vec![(HOLDER_BINDING_KEY, &holder_key)],
+            ..CredentialTemplate::default()
+        },
+    ] {
+        assert!(dataverse.submit("sender", template.sign(&issuer)).is_ok());
+    }
+
+    // the credentials got submitted before the introduction of the issuer, holder and digest
+    // indexes
+    {
+        let mut storage = dataverse.app.contract_storage_mut(&dataverse.address);
+        ISSUER_CREDENTIALS.clear(storage.as_mut());
+        HOLDER_BINDINGS.clear(storage.as_mut());
+        HOLDER_CLAIMS.clear(storage.as_mut());
+        STATE_DIGEST.remove(storage.as_mut());
+    }
+
+    let code_id = dataverse
+        .app
+        .wrap()
+        .query_wasm_contract_info(&dataverse.address)
+        .unwrap()
+        .code_id;
+    dataverse
+        .app
+        .migrate_contract(
+            dataverse.app.api().addr_make("creator"),
+            dataverse.address.clone(),
+            &MigrateMsg {
+                rebuild_indexes: true,
+            },
+            code_id,
+        )
+        .unwrap();
+    while INDEX_REBUILD.exists(dataverse.app.contract_storage(&dataverse.address).as_ref()) {
+        dataverse
+            .app
+            .execute_contract(
+                dataverse.app.api().addr_make("anyone"),
+                dataverse.address.clone(),
+                &ExecuteMsg::RebuildIndexes { limit: None },
+                &[],
+            )
+            .unwrap();
+    }
+
+    let ClaimsByIssuerResponse { claims } = dataverse
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &dataverse.address,
+            &QueryMsg::ClaimsByIssuer {
+                did: issuer.did(),
+                first: None,
+                after: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        claims
+            .into_iter()
+            .map(|claim| (claim.credential, claim.canonical_hash, claim.submitted_at))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "https://example.org/credentials/1".to_string(),
+                HexBinary::default(),
+                Timestamp::from_seconds(1_700_000_000)
+            ),
+            (
+                "https://example.org/credentials/2".to_string(),
+                HexBinary::default(),
+                Timestamp::from_seconds(1_700_000_000)
+            ),
+        ]
+    );
+    {
+        let storage = dataverse.app.contract_storage(&dataverse.address);
+        assert_eq!(
+            HOLDER_BINDINGS
+                .load(storage.as_ref(), "https://example.org/credentials/2")
+                .unwrap(),
+            holder.public_key()
+        );
+        assert!(HOLDER_CLAIMS.has(
+            storage.as_ref(),
+            (&holder.public_key(), "https://example.org/credentials/2")
+        ));
+        assert_eq!(STATE_DIGEST.load(storage.as_ref()).unwrap().sequence, 2);
+    }
+
+    let did = issuer.did();
+    let revocation = CredentialTemplate {
+        id: "https://example.org/revocations/1",
+        r#type: ISSUER_REVOCATION_CREDENTIAL,
+        subject: &did,
+        claims: vec![("https://example.org/examples#reason", "\"Key compromised\"")],
+        issuance_date: "2024-01-05T00:00:00Z",
+        ..CredentialTemplate::default()
+    };
+    dataverse
+        .app
+        .update_block(|block| block.time = Timestamp::from_seconds(1_705_000_000)); // 2024-01-11T19:06:40Z
+    let res = dataverse
+        .app
+        .execute_contract(
+            dataverse.app.api().addr_make("anyone"),
+            dataverse.address.clone(),
+            &ExecuteMsg::RevokeAllByIssuer {
+                did: did.clone(),
+                revocation: revocation.sign(&issuer),
+                limit: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        res.events
+            .iter()
+            .filter(|event| event.ty == "wasm-credential_revoked")
+            .count(),
+        2
+    );
+
+    for id in [
+        "https://example.org/credentials/1",
+        "https://example.org/credentials/2",
+    ] {
+        assert!(dataverse
+            .credential_property(id, "dataverse:credential:body#issuer")
+            .is_empty());
+    }
+    let storage = dataverse.app.contract_storage(&dataverse.address);
+    assert!(ISSUER_CREDENTIALS.is_empty(storage.as_ref()));
+    assert!(HOLDER_BINDINGS.is_empty(storage.as_ref()));
+    assert!(HOLDER_CLAIMS.is_empty(storage.as_ref()));
+    assert!(EXPIRING_CREDENTIALS.is_empty(storage.as_ref()));
+    assert_eq!(STATE_DIGEST.load(storage.as_ref()).unwrap().sequence, 4);
+}
+
+#[test]
+fn revoke_all_by_issuer() {
+    let mut dataverse = Dataverse::setup();
+    let issuer = KeyPair::ed25519(1);
+    let other_issuer = KeyPair::ed25519(2);
+    dataverse
+        .app
+        .update_block(|block| block.time = Timestamp::from_seconds(1_700_000_000)); // 2023-11-14T22:13:20Z
+
+    let credentials = [
+        ("https://example.org/credentials/1", None, &issuer),
+        (
+            "https://example.org/credentials/2",
+            Some("2024-01-01T00:00:00Z"),
+            &issuer,
+        ),
+        ("https://example.org/credentials/3", None, &issuer),
+        ("https://example.org/credentials/4", None, &other_issuer),
+    ];
+    for (id, expiration_date, issuer) in credentials {
+        let template = CredentialTemplate {
+            id,
+            expiration_date,
+            ..CredentialTemplate::default()
+        };
+        assert!(dataverse.submit("sender", template.sign(issuer)).is_ok());
+    }
+
+    let claims_by_issuer = |dataverse: &Dataverse,
+                            did: &str,
+                            first: Option<u32>,
+                            after: Option<&str>|
+     -> Vec<String> {
+        dataverse
+            .app
+            .wrap()
+            .query_wasm_smart::<ClaimsByIssuerResponse>(
+                &dataverse.address,
+                &QueryMsg::ClaimsByIssuer {
+                    did: did.to_string(),
+                    first,
+                    after: after.map(str::to_string),
+                },
+            )
+            .unwrap()
+            .claims
+            .into_iter()
+            .map(|claim| claim.credential)
+            .collect()
+    };
+    assert_eq!(
+        claims_by_issuer(&dataverse, &issuer.did(), Some(2), None),
+        vec![
+            "https://example.org/credentials/1",
+            "https://example.org/credentials/2"
+        ]
+    );
+    assert_eq!(
+        claims_by_issuer(
+            &dataverse,
+            &issuer.did(),
+            None,
+            Some("https://example.org/credentials/2")
+        ),
+        vec!["https://example.org/credentials/3"]
+    );
+    assert_eq!(
+        claims_by_issuer(&dataverse, &other_issuer.did(), None, None),
+        vec!["https://example.org/credentials/4"]
+    );
+
+    // submitted after the issuance of the revocation credential, hence not covered by it
+    dataverse
+        .app
+        .update_block(|block| block.time = Timestamp::from_seconds(1_705_000_000)); // 2024-01-11T19:06:40Z
+    let template = CredentialTemplate {
+        id: "https://example.org/credentials/5",
+        ..CredentialTemplate::default()
+    };
+    assert!(dataverse.submit("sender", template.sign(&issuer)).is_ok());
+
+    let did = issuer.did();
+    let revocation = CredentialTemplate {
+        id: "https://example.org/revocations/1",
+        r#type: ISSUER_REVOCATION_CREDENTIAL,
+        subject: &did,
+        claims: vec![("https://example.org/examples#reason", "\"Key compromised\"")],
+        issuance_date: "2024-01-05T00:00:00Z",
+        ..CredentialTemplate::default()
+    };
+    let revoke = |dataverse: &mut Dataverse,
+                  revocation: Binary,
+                  limit: Option<u32>|
+     -> Result<(Vec<String>, bool), String> {
+        dataverse
+            .app
+            .execute_contract(
+                dataverse.app.api().addr_make("anyone"),
+                dataverse.address.clone(),
+                &ExecuteMsg::RevokeAllByIssuer {
+                    did: did.clone(),
+                    revocation,
+                    limit,
+                },
+                &[],
+            )
+            .map(|res| {
+                let revoked = res
+                    .events
+                    .iter()
+                    .filter(|event| event.ty == "wasm-credential_revoked")
+                    .flat_map(|event| &event.attributes)
+                    .filter(|a| a.key == "credential")
+                    .map(|a| a.value.clone())
+                    .collect();
+                let completed = res.events.iter().any(|event| {
+                    event
+                        .attributes
+                        .iter()
+                        .any(|a| a.key == "completed" && a.value == "true")
+                });
+                (revoked, completed)
+            })
+            .map_err(|e| e.root_cause().to_string())
+    };
+
+    let cases = vec![
+        (
+            revocation.sign(&other_issuer),
+            "Invalid revocation credential: 'credential is expected to be issued by the revoking issuer'",
+        ),
+        (
+            CredentialTemplate::default().sign(&issuer),
+            "Invalid revocation credential: 'credential is expected to be of type 'https://w3id.org/axone/ontology/vnext/schema/credential/issuer-revocation/IssuerRevocationCredential''",
+        ),
+        (
+            CredentialTemplate {
+                subject: "https://example.org/datasets/1",
+                ..CredentialTemplate {
+                    r#type: ISSUER_REVOCATION_CREDENTIAL,
+                    ..CredentialTemplate::default()
+                }
+            }
+            .sign(&issuer),
+            "Invalid revocation credential: 'credential is expected to be about the revoking issuer'",
+        ),
+        (
+            CredentialTemplate {
+                id: "https://example.org/revocations/2",
+                r#type: ISSUER_REVOCATION_CREDENTIAL,
+                subject: &did,
+                issuance_date: "2024-02-01T00:00:00Z",
+                ..CredentialTemplate::default()
+            }
+            .sign(&issuer),
+            "Invalid revocation credential: 'credential is expected to be issued in the past'",
+        ),
+    ];
+    for (revocation, expected) in cases {
+        assert_eq!(
+            revoke(&mut dataverse, revocation, None),
+            Err(expected.to_string())
+        );
+    }
+
+    let state_digest = |dataverse: &Dataverse| -> StateDigestResponse {
+        dataverse
+            .app
+            .wrap()
+            .query_wasm_smart(&dataverse.address, &QueryMsg::StateDigest {})
+            .unwrap()
+    };
+    let sequence = state_digest(&dataverse).sequence;
+
+    assert_eq!(
+        revoke(&mut dataverse, revocation.sign(&issuer), Some(2)),
+        Ok((
+            vec![
+                "https://example.org/credentials/1".to_string(),
+                "https://example.org/credentials/2".to_string()
+            ],
+            false
+        ))
+    );
+    assert_eq!(
+        revoke(&mut dataverse, revocation.sign(&issuer), Some(2)),
+        Ok((vec!["https://example.org/credentials/3".to_string()], true))
+    );
+    assert_eq!(
+        revoke(&mut dataverse, revocation.sign(&issuer), None),
+        Err("Invalid revocation credential: 'credential has already been applied'".to_string())
+    );
+    assert_eq!(state_digest(&dataverse).sequence, sequence + Uint64::new(3));
+
+    assert_eq!(
+        claims_by_issuer(&dataverse, &issuer.did(), None, None),
+        vec!["https://example.org/credentials/5"]
+    );
+    assert_eq!(
+        claims_by_issuer(&dataverse, &other_issuer.did(), None, None),
+        vec!["https://example.org/credentials/4"]
+    );
+    assert!(dataverse
+        .credential_property(
+            "https://example.org/credentials/1",
+            "dataverse:credential:body#issuer"
+        )
+        .is_empty());
+    assert_eq!(
+        dataverse.credential_property(
+            "https://example.org/credentials/5",
+            "dataverse:credential:body#issuer"
+        ),
+        vec![uri(&issuer.did())]
+    );
+
+    let resp: ClaimsResponse = dataverse
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &dataverse.address,
+            &QueryMsg::Claims {
+                filter: ClaimsFilter {
+                    issuer: Some(issuer.did()),
+                    ..ClaimsFilter::default()
+                },
+            },
+        )
+        .unwrap();
+    assert!(!resp.truncated);
+    assert_eq!(
+        resp.claims
+            .into_iter()
+            .map(|claim| claim.credential)
+            .collect::<Vec<_>>(),
+        vec!["https://example.org/credentials/5"]
+    );
+
+    let storage = dataverse.app.contract_storage(&dataverse.address);
+    assert!(EXPIRING_CREDENTIALS
+        .keys(storage.as_ref(), None, None, Order::Ascending)
+        .next()
+        .is_none());
+}
+
+#[test]
+fn link_submitter_address() {
+    let mut dataverse = Dataverse::setup();
+    let holder = KeyPair::ed25519(1);
+    let did = holder.did();
+    let sender = dataverse.app.api().addr_make("sender");
+    let other = dataverse.app.api().addr_make("other");
+
+    let address_holder = |dataverse: &Dataverse, address: &Addr| -> Option<String> {
+        dataverse
+            .app
+            .wrap()
+            .query_wasm_smart::<AddressHolderResponse>(
+                &dataverse.address,
+                &QueryMsg::AddressHolder {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap()
+            .holder
+    };
+    assert_eq!(address_holder(&dataverse, &sender), None);
+
+    let claimed_sender = format!("\"{sender}\"");
+    let claimed_other = format!("\"{other}\"");
+    let linkage = |id, subject, address| CredentialTemplate {
+        id,
+        r#type: ADDRESS_LINKAGE_CREDENTIAL,
+        subject,
+        claims: vec![(ADDRESS_LINKAGE_ADDRESS, address)],
+        ..CredentialTemplate::default()
+    };
+
+    let cases = vec![
+        (
+            linkage("https://example.org/links/1", &did, &claimed_other),
+            "Invalid address linkage credential: 'credential is expected to claim the submitter address'",
+        ),
+        (
+            linkage(
+                "https://example.org/links/1",
+                "https://example.org/datasets/1",
+                &claimed_sender,
+            ),
+            "Invalid address linkage credential: 'credential is expected to be issued by the linked holder'",
+        ),
+    ];
+    for (template, expected) in cases {
+        assert_eq!(
+            dataverse.submit("sender", template.sign(&holder)),
+            Err(expected.to_string())
+        );
+    }
+
+    assert!(dataverse
+        .submit(
+            "sender",
+            linkage("https://example.org/links/1", &did, &claimed_sender).sign(&holder)
+        )
+        .is_ok());
+    assert_eq!(address_holder(&dataverse, &sender), Some(did.clone()));
+    assert_eq!(address_holder(&dataverse, &other), None);
+
+    // the submissions of the linked address are attributed to the holder
+    assert!(dataverse
+        .submit(
+            "sender",
+            CredentialTemplate::default().sign(&KeyPair::ed25519(2))
+        )
+        .is_ok());
+    let credential = "https://example.org/credentials/1";
+    let account = HexBinary::from(
+        dataverse
+            .app
+            .api()
+            .addr_canonicalize(sender.as_str())
+            .unwrap()
+            .as_slice(),
+    );
+    for (property, expected) in [
+        (
+            "dataverse:credential:header#sender",
+            literal(sender.as_str()),
+        ),
+        (
+            "dataverse:credential:header#sender_account",
+            literal(&account.to_hex()),
+        ),
+        (
+            "dataverse:credential:header#sender_prefix",
+            literal("cosmwasm"),
+        ),
+        ("dataverse:credential:header#holder", uri(&did)),
+    ] {
+        assert_eq!(
+            dataverse.credential_property(credential, property),
+            vec![expected],
+            "{property}"
+        );
+    }
+}
+
+#[test]
+fn holder_bound_claims() {
+    let mut dataverse = Dataverse::setup();
+    let issuer = KeyPair::ed25519(1);
+    let holder = KeyPair::secp256k1(2);
+    let other_holder = KeyPair::ed25519(3);
+    dataverse
+        .app
+        .update_block(|block| block.time = Timestamp::from_seconds(1_700_000_000)); // 2023-11-14T22:13:20Z
+
+    let holder_key = format!("\"{}\"", HexBinary::from(holder.public_key()).to_hex());
+    let other_holder_key = format!(
+        "\"{}\"",
+        HexBinary::from(other_holder.public_key()).to_hex()
+    );
+    let bound = |id, key| CredentialTemplate {
+        id,
+        claims: vec![(HOLDER_BINDING_KEY, key)],
+        ..CredentialTemplate::default()
+    };
+    for (template, expected) in [
+        (
+            bound("https://example.org/credentials/1", "\"zz\""),
+            "Invalid holder key: 'key is expected to be hex encoded'",
+        ),
+        (
+            bound("https://example.org/credentials/1", "\"0404\""),
+            "Invalid holder key: 'key is expected to be a 32 bytes Ed25519 key or a compressed secp256k1 key'",
+        ),
+    ] {
+        assert_eq!(
+            dataverse.submit("sender", template.sign(&issuer)),
+            Err(expected.to_string())
+        );
+    }
+    for template in [
+        bound("https://example.org/credentials/1", &holder_key),
+        bound("https://example.org/credentials/2", &other_holder_key),
+        CredentialTemplate {
+            id: "https://example.org/credentials/3",
+            ..CredentialTemplate::default()
+        },
+        bound("https://example.org/credentials/4", &holder_key),
+    ] {
+        assert!(dataverse.submit("sender", template.sign(&issuer)).is_ok());
+    }
+
+    // the bound claims are withheld from the claims query, though public in the triple store
+    let ClaimsResponse { claims, .. } = dataverse
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &dataverse.address,
+            &QueryMsg::Claims {
+                filter: ClaimsFilter::default(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        claims
+            .into_iter()
+            .map(|claim| claim.credential)
+            .collect::<Vec<_>>(),
+        vec!["https://example.org/credentials/3"]
+    );
+    assert_eq!(
+        dataverse.credential_property(
+            "https://example.org/credentials/1",
+            "dataverse:credential:body#issuer"
+        ),
+        vec![uri(&issuer.did())]
+    );
+
+    let sender = dataverse.app.api().addr_make("holder");
+    let open_session = |dataverse: &mut Dataverse, key: &KeyPair| -> HolderSessionReceipt {
+        let res = dataverse
+            .app
+            .execute_contract(
+                sender.clone(),
+                dataverse.address.clone(),
+                &ExecuteMsg::OpenHolderSession {
+                    holder_key: HexBinary::from(key.public_key()),
+                },
+                &[],
+            )
+            .unwrap();
+        from_json(res.data.unwrap()).unwrap()
+    };
+    let retrieve = |dataverse: &mut Dataverse,
+                    sender: &Addr,
+                    session: Uint64,
+                    signature: Vec<u8>,
+                    first: Option<u32>,
+                    after: Option<&str>|
+     -> Result<(Vec<String>, bool), String> {
+        dataverse
+            .app
+            .execute_contract(
+                sender.clone(),
+                dataverse.address.clone(),
+                &ExecuteMsg::RetrieveHolderClaims {
+                    session,
+                    signature: HexBinary::from(signature),
+                    first,
+                    after: after.map(str::to_string),
+                },
+                &[],
+            )
+            .map(|res| {
+                let claims = from_json::<ClaimsResponse>(res.data.unwrap())
+                    .unwrap()
+                    .claims
+                    .into_iter()
+                    .map(|claim| claim.credential)
+                    .collect();
+                let completed = res.events.iter().any(|event| {
+                    event
+                        .attributes
+                        .iter()
+                        .any(|a| a.key == "completed" && a.value == "true")
+                });
+                (claims, completed)
+            })
+            .map_err(|e| e.root_cause().to_string())
+    };
+    let holder_claims = |dataverse: &mut Dataverse, session: Uint64, signature: Vec<u8>| {
+        retrieve(dataverse, &sender, session, signature, None, None).map(|(claims, _)| claims)
+    };
+
+    let session = open_session(&mut dataverse, &holder);
+    assert_eq!(session.session, Uint64::new(1));
+    assert_eq!(session.expires_at, Timestamp::from_seconds(1_700_000_300));
+    let signature = holder.sign_session(session.nonce.as_slice(), sender.as_str());
+
+    // the session can only be used by the address which opened it, the signature covering it
+    let intruder = dataverse.app.api().addr_make("intruder");
+    assert_eq!(
+        retrieve(
+            &mut dataverse,
+            &intruder,
+            session.session,
+            signature.clone(),
+            None,
+            None
+        ),
+        Err("Unauthorized".to_string())
+    );
+    assert_eq!(
+        holder_claims(
+            &mut dataverse,
+            session.session,
+            holder.sign_session(session.nonce.as_slice(), intruder.as_str())
+        ),
+        Err("Generic error: Invalid holder signature".to_string())
+    );
+
+    // the claims are listed page by page, the session remaining open until the last one
+    assert_eq!(
+        retrieve(
+            &mut dataverse,
+            &sender,
+            session.session,
+            signature.clone(),
+            Some(1),
+            None
+        ),
+        Ok((vec!["https://example.org/credentials/1".to_string()], false))
+    );
+    assert_eq!(
+        retrieve(
+            &mut dataverse,
+            &sender,
+            session.session,
+            signature.clone(),
+            Some(1),
+            Some("https://example.org/credentials/1")
+        ),
+        Ok((vec!["https://example.org/credentials/4".to_string()], true))
+    );
+
+    // the session is closed once the claims retrieved
+    assert_eq!(
+        holder_claims(&mut dataverse, session.session, signature.clone()),
+        Err("Generic error: Holder session '1' not found or expired".to_string())
+    );
+
+    let other_session = open_session(&mut dataverse, &other_holder);
+    assert_ne!(other_session.nonce, session.nonce);
+    assert_eq!(
+        holder_claims(
+            &mut dataverse,
+            other_session.session,
+            other_holder.sign_session(other_session.nonce.as_slice(), sender.as_str())
+        ),
+        Ok(vec!["https://example.org/credentials/2".to_string()])
+    );
+
+    // the presentation of a session can't be replayed against another one
+    let replayed = open_session(&mut dataverse, &holder);
+    for signature in [
+        signature,
+        other_holder.sign_session(replayed.nonce.as_slice(), sender.as_str()),
+    ] {
+        assert_eq!(
+            holder_claims(&mut dataverse, replayed.session, signature),
+            Err("Generic error: Invalid holder signature".to_string())
+        );
+    }
+
+    dataverse
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(300));
+    assert_eq!(
+        holder_claims(
+            &mut dataverse,
+            replayed.session,
+            holder.sign_session(replayed.nonce.as_slice(), sender.as_str())
+        ),
+        Err("Generic error: Holder session '3' not found or expired".to_string())
+    );
+}
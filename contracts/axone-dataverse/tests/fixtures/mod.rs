@@ -0,0 +1,266 @@
+//! Verifiable credential fixtures forged at test time.
+//!
+//! Rather than relying on static `.nq` files, which have to be re-signed by hand every time the
+//! vocabulary or a proof suite evolves, credentials are rendered from a [CredentialTemplate] and
+//! signed on the fly by a deterministic [KeyPair] using the exact same canonicalization and
+//! hashing scheme the contract verifies against.
+
+use axone_rdf::normalize::Normalizer;
+use axone_rdf::serde::NQuadsReader;
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use base64::Engine;
+use cosmwasm_std::Binary;
+use k256::ecdsa::signature::hazmat::PrehashSigner;
+use multibase::Base;
+use rio_api::model::Quad;
+use sha2::{Digest, Sha256};
+use std::io::BufReader;
+
+const ED25519_MULTICODEC: u16 = 0xed;
+const SECP256K1_MULTICODEC: u16 = 0xe7;
+
+/// The detached JWS header used by the `EcdsaSecp256k1Signature2019` proof suite.
+const ES256K_JWS_HEADER: &str = r#"{"alg":"ES256K","crit":["b64"],"b64":false}"#;
+
+/// A signing key pair identified by a `did:key`.
+pub enum KeyPair {
+    Ed25519(ed25519_zebra::SigningKey),
+    Secp256k1(k256::ecdsa::SigningKey),
+}
+
+impl KeyPair {
+    pub fn ed25519(seed: u8) -> Self {
+        Self::Ed25519(ed25519_zebra::SigningKey::from([seed; 32]))
+    }
+
+    pub fn secp256k1(seed: u8) -> Self {
+        Self::Secp256k1(
+            k256::ecdsa::SigningKey::from_bytes(&[seed; 32].into())
+                .expect("seed should be a valid secp256k1 scalar"),
+        )
+    }
+
+    /// Returns the `did:key` identifier of the key pair.
+    pub fn did(&self) -> String {
+        format!("did:key:{}", self.multikey())
+    }
+
+    /// Returns the verification method IRI, i.e. the `did:key` with its key fragment.
+    pub fn verification_method(&self) -> String {
+        format!("{}#{}", self.did(), self.multikey())
+    }
+
+    /// Returns the public key of the key pair, compressed for secp256k1.
+    pub fn public_key(&self) -> Vec<u8> {
+        match self {
+            KeyPair::Ed25519(key) => ed25519_zebra::VerificationKey::from(key).as_ref().to_vec(),
+            KeyPair::Secp256k1(key) => key
+                .verifying_key()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+        }
+    }
+
+    /// Signs the SHA-256 digest of the nonce of a holder session followed by the address of the
+    /// sender retrieving its claims.
+    pub fn sign_session(&self, nonce: &[u8], sender: &str) -> Vec<u8> {
+        self.sign_nonce(
+            &Sha256::new()
+                .chain_update(nonce)
+                .chain_update(sender.as_bytes())
+                .finalize(),
+        )
+    }
+
+    /// Signs a 32 bytes nonce, taken as the prehashed message for secp256k1.
+    pub fn sign_nonce(&self, nonce: &[u8]) -> Vec<u8> {
+        match self {
+            KeyPair::Ed25519(key) => key.sign(nonce).to_bytes().to_vec(),
+            KeyPair::Secp256k1(key) => {
+                let signature: k256::ecdsa::Signature =
+                    key.sign_prehash(nonce).expect("nonce should be signable");
+                signature
+                    .normalize_s()
+                    .unwrap_or(signature)
+                    .to_bytes()
+                    .to_vec()
+            }
+        }
+    }
+
+    fn multikey(&self) -> String {
+        let codec = match self {
+            KeyPair::Ed25519(_) => ED25519_MULTICODEC,
+            KeyPair::Secp256k1(_) => SECP256K1_MULTICODEC,
+        };
+
+        let mut buf = unsigned_varint::encode::u16_buffer();
+        multibase::encode(
+            Base::Base58Btc,
+            [
+                unsigned_varint::encode::u16(codec, &mut buf),
+                &self.public_key(),
+            ]
+            .concat(),
+        )
+    }
+
+    fn proof_type(&self) -> &'static str {
+        match self {
+            KeyPair::Ed25519(_) => "https://w3id.org/security#Ed25519Signature2020",
+            KeyPair::Secp256k1(_) => "https://w3id.org/security#EcdsaSecp256k1Signature2019",
+        }
+    }
+
+    /// Signs the given hash data, returning the proof value triple predicate and object.
+    fn prove(&self, hash: &[u8]) -> (&'static str, String) {
+        match self {
+            KeyPair::Ed25519(key) => (
+                "https://w3id.org/security#proofValue",
+                format!(
+                    "\"{}\"^^<https://w3id.org/security#multibase>",
+                    multibase::encode(Base::Base58Btc, key.sign(hash).to_bytes())
+                ),
+            ),
+            KeyPair::Secp256k1(key) => {
+                let header_b64 = BASE64_URL_SAFE_NO_PAD.encode(ES256K_JWS_HEADER);
+                let signing_input = Sha256::digest([header_b64.as_bytes(), b".", hash].concat());
+                let signature: k256::ecdsa::Signature = key
+                    .sign_prehash(&signing_input)
+                    .expect("prehash should be signable");
+                let signature = signature.normalize_s().unwrap_or(signature);
+
+                (
+                    "https://w3id.org/security#jws",
+                    format!(
+                        "\"{}..{}\"",
+                        header_b64,
+                        BASE64_URL_SAFE_NO_PAD.encode(signature.to_bytes())
+                    ),
+                )
+            }
+        }
+    }
+}
+
+/// The template of a verifiable credential holding a single claim about its subject.
+///
+/// Claims are expressed as `(predicate, object)` couples, the object being written in its
+/// N-Quads form (e.g. `<https://example.org/a>` or `"value"`).
+pub struct CredentialTemplate<'a> {
+    pub id: &'a str,
+    pub r#type: &'a str,
+    pub subject: &'a str,
+    pub claims: Vec<(&'a str, &'a str)>,
+    pub issuance_date: &'a str,
+    pub expiration_date: Option<&'a str>,
+    pub proof_created: &'a str,
+}
+
+impl Default for CredentialTemplate<'_> {
+    fn default() -> Self {
+        Self {
+            id: "https://example.org/credentials/1",
+            r#type: "https://example.org/examples#DatasetCredential",
+            subject: "https://example.org/datasets/1",
+            claims: vec![
+                ("https://example.org/examples#title", "\"My dataset\""),
+                (
+                    "https://example.org/examples#license",
+                    "<https://creativecommons.org/licenses/by/4.0/>",
+                ),
+            ],
+            issuance_date: "2024-01-01T00:00:00Z",
+            expiration_date: None,
+            proof_created: "2024-01-01T00:00:00Z",
+        }
+    }
+}
+
+impl CredentialTemplate<'_> {
+    /// Renders the credential and secures it with a proof forged by the given issuer, the issuer
+    /// of the credential being the `did:key` of the key pair.
+    pub fn sign(&self, issuer: &KeyPair) -> Binary {
+        let document = self.render_document(&issuer.did());
+        let proof_options = self.render_proof_options(issuer);
+
+        let hash = [
+            Self::hash_canonical(&proof_options),
+            Self::hash_canonical(&document),
+        ]
+        .concat();
+        let (value_predicate, value) = issuer.prove(&hash);
+
+        let mut out = document;
+        out.push_str(&format!(
+            "<{}> <https://w3id.org/security#proof> _:b0 .\n",
+            self.id
+        ));
+        for line in proof_options.lines() {
+            out.push_str(&format!("{} _:b0 .\n", line.trim_end_matches(" .")));
+        }
+        out.push_str(&format!("_:b1 <{}> {} _:b0 .\n", value_predicate, value));
+
+        Binary::from(out.into_bytes())
+    }
+
+    /// Returns the SHA-256 hash of the canonical form of the credential issued by the given issuer,
+    /// proof excluded.
+    pub fn canonical_hash(&self, issuer: &KeyPair) -> Vec<u8> {
+        Self::hash_canonical(&self.render_document(&issuer.did()))
+    }
+
+    fn render_document(&self, issuer: &str) -> String {
+        let mut doc = format!(
+            "<{id}> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/2018/credentials#VerifiableCredential> .\n\
+            <{id}> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <{type}> .\n\
+            <{id}> <https://www.w3.org/2018/credentials#issuer> <{issuer}> .\n\
+            <{id}> <https://www.w3.org/2018/credentials#issuanceDate> \"{issuance}\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n\
+            <{id}> <https://www.w3.org/2018/credentials#credentialSubject> <{subject}> .\n",
+            id = self.id,
+            type = self.r#type,
+            issuer = issuer,
+            issuance = self.issuance_date,
+            subject = self.subject,
+        );
+        if let Some(expiration) = self.expiration_date {
+            doc.push_str(&format!(
+                "<{}> <https://www.w3.org/2018/credentials#expirationDate> \"{}\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n",
+                self.id, expiration
+            ));
+        }
+        for (predicate, object) in &self.claims {
+            doc.push_str(&format!(
+                "<{}> <{}> {} .\n",
+                self.subject, predicate, object
+            ));
+        }
+
+        doc
+    }
+
+    fn render_proof_options(&self, issuer: &KeyPair) -> String {
+        format!(
+            "_:b1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <{}> .\n\
+            _:b1 <http://purl.org/dc/terms/created> \"{}\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n\
+            _:b1 <https://w3id.org/security#proofPurpose> <https://w3id.org/security#assertionMethod> .\n\
+            _:b1 <https://w3id.org/security#verificationMethod> <{}> .\n",
+            issuer.proof_type(),
+            self.proof_created,
+            issuer.verification_method(),
+        )
+    }
+
+    fn hash_canonical(nquads: &str) -> Vec<u8> {
+        let owned_quads = NQuadsReader::new(BufReader::new(nquads.as_bytes()))
+            .read_all()
+            .expect("rendered template should be valid N-Quads");
+        let quads: Vec<Quad<'_>> = owned_quads.iter().map(Quad::from).collect();
+        let canonical = Normalizer::new()
+            .normalize(&quads)
+            .expect("rendered template should be normalizable");
+
+        Sha256::digest(canonical).to_vec()
+    }
+}
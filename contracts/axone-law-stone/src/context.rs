@@ -0,0 +1,107 @@
+use cosmwasm_std::{Addr, Env, MessageInfo};
+
+/// Version of the evaluation context, asserted as the `context_version/1` fact, bumped whenever the
+/// set or the shape of the asserted facts changes.
+pub const CONTEXT_VERSION: u32 = 1;
+
+/// The block and chain facts asserted into every evaluation of the law program, so laws can
+/// express temporal and identity conditions without relying on the callers to provide them:
+///
+/// - `context_version(V)`: the version of the evaluation context, see [CONTEXT_VERSION];
+/// - `block_height(H)`: the height of the block the evaluation takes place in;
+/// - `block_time(T)`: the time of the block, in seconds since the Unix epoch;
+/// - `chain_id(C)`: the identifier of the chain, as an atom;
+/// - `sender(S)`: the address of the message sender, as an atom, only defined when evaluating on
+///   the execute path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EvaluationContext {
+    pub height: u64,
+    pub time: u64,
+    pub chain_id: String,
+    pub sender: Option<Addr>,
+}
+
+impl EvaluationContext {
+    /// The context of an evaluation performed by a query, where no sender is known.
+    pub fn query(env: &Env) -> Self {
+        Self {
+            height: env.block.height,
+            time: env.block.time.seconds(),
+            chain_id: env.block.chain_id.clone(),
+            sender: None,
+        }
+    }
+
+    /// The context of an evaluation performed while executing a message.
+    pub fn execute(env: &Env, info: &MessageInfo) -> Self {
+        Self {
+            sender: Some(info.sender.clone()),
+            ..Self::query(env)
+        }
+    }
+
+    /// Render the context as Prolog clauses, declaring `sender/1` as dynamic so it simply fails
+    /// when no sender is known.
+    pub fn to_program(&self) -> String {
+        let mut clauses = vec![
+            ":- dynamic(sender/1).".to_string(),
+            format!("context_version({}).", CONTEXT_VERSION),
+            format!("block_height({}).", self.height),
+            format!("block_time({}).", self.time),
+            format!("chain_id({}).", quote_atom(&self.chain_id)),
+        ];
+        if let Some(sender) = &self.sender {
+            clauses.push(format!("sender({}).", quote_atom(sender.as_str())));
+        }
+
+        clauses.join("\n")
+    }
+}
+
+/// Quote a value as a Prolog atom, escaping the backslashes and single quotes it contains.
+pub fn quote_atom(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{message_info, mock_env};
+    use testing::addr::{addr, SENDER};
+
+    #[test]
+    fn to_program() {
+        let env = mock_env();
+        let sender = addr(SENDER);
+
+        assert_eq!(
+            EvaluationContext::query(&env).to_program(),
+            format!(
+                ":- dynamic(sender/1).\ncontext_version(1).\nblock_height({}).\nblock_time({}).\nchain_id('{}').",
+                env.block.height,
+                env.block.time.seconds(),
+                env.block.chain_id
+            )
+        );
+        assert_eq!(
+            EvaluationContext::execute(&env, &message_info(&sender, &[])).to_program(),
+            format!(
+                ":- dynamic(sender/1).\ncontext_version(1).\nblock_height({}).\nblock_time({}).\nchain_id('{}').\nsender('{}').",
+                env.block.height,
+                env.block.time.seconds(),
+                env.block.chain_id,
+                sender
+            )
+        );
+        assert_eq!(
+            EvaluationContext {
+                height: 1,
+                time: 2,
+                chain_id: "it's\\".to_string(),
+                sender: None,
+            }
+            .to_program(),
+            ":- dynamic(sender/1).\ncontext_version(1).\nblock_height(1).\nblock_time(2).\nchain_id('it\\'s\\\\')."
+        );
+    }
+}
@@ -13,14 +13,17 @@ use axone_objectarium::msg::{
 };
 use axone_objectarium_client::ObjectRef;
 
+use crate::context::EvaluationContext;
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{METADATA, SCHEDULER};
 
 // version info for migration info
 const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const STORE_PROGRAM_REPLY_ID: u64 = 1;
+const SUBSCRIPTION_CALLBACK_REPLY_ID: u64 = 2;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -32,10 +35,18 @@ pub fn instantiate(
     nonpayable(&info)?;
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    if let Some(scheduler) = msg.scheduler {
+        SCHEDULER.save(deps.storage, &deps.api.addr_validate(&scheduler)?)?;
+    }
+    METADATA.save(deps.storage, &msg.metadata)?;
+
     let store_msg = StorageMsg::StoreObject {
         data: msg.program.clone(),
         pin: true,
         compression_algorithm: None,
+        expected_digest: None,
+        reject_duplicate: false,
+        keep_content: false,
     };
 
     let store_program_msg = WasmMsg::Execute {
@@ -44,15 +55,31 @@ pub fn instantiate(
         funds: vec![],
     };
 
-    Ok(Response::new().add_submessage(
-        SubMsg::reply_on_success(store_program_msg, STORE_PROGRAM_REPLY_ID)
-            .with_payload(Binary::from(msg.storage_address.as_bytes())),
-    ))
+    let metadata = msg.metadata;
+    Ok(Response::new()
+        .add_attributes(metadata.title.map(|title| ("title", title)))
+        .add_attributes(
+            metadata
+                .jurisdiction
+                .map(|jurisdiction| ("jurisdiction", jurisdiction)),
+        )
+        .add_attributes(metadata.version.map(|version| ("version", version)))
+        .add_attributes(
+            metadata
+                .authors
+                .into_iter()
+                .map(|author| ("author", author)),
+        )
+        .add_attributes(metadata.license.map(|license| ("license", license)))
+        .add_submessage(
+            SubMsg::reply_on_success(store_program_msg, STORE_PROGRAM_REPLY_ID)
+                .with_payload(Binary::from(msg.storage_address.as_bytes())),
+        ))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    deps: DepsMut<'_>,
+    deps: DepsMut<'_, LogicCustomQuery>,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
@@ -60,18 +87,31 @@ pub fn execute(
     nonpayable(&info)?;
     match msg {
         ExecuteMsg::BreakStone {} => execute::break_stone(deps, env, info),
+        ExecuteMsg::Reevaluate { query, callback } => {
+            execute::reevaluate(deps, env, info, query, callback)
+        }
+        ExecuteMsg::Subscribe { goal } => execute::subscribe(deps, env, info, goal),
+        ExecuteMsg::Unsubscribe { id } => execute::unsubscribe(deps, info, id),
+        ExecuteMsg::ReevaluateSubscriptions { start_after, limit } => {
+            execute::reevaluate_subscriptions(deps, env, start_after, limit)
+        }
     }
 }
 
 pub mod execute {
-    use cosmwasm_std::{ensure_eq, Order};
+    use cosmwasm_std::{ensure_eq, Addr, Order};
+    use cw_storage_plus::Bound;
 
-    use crate::state::{DEPENDENCIES, PROGRAM};
+    use crate::msg::ReevaluationCallbackMsg;
+    use crate::state::{Subscription, DEPENDENCIES, PROGRAM, SUBSCRIPTIONS, SUBSCRIPTION_COUNTER};
 
     use super::*;
 
+    const DEFAULT_SUBSCRIPTION_LIMIT: u32 = 10;
+    const MAX_SUBSCRIPTION_LIMIT: u32 = 30;
+
     pub fn break_stone(
-        deps: DepsMut<'_>,
+        deps: DepsMut<'_, LogicCustomQuery>,
         env: Env,
         info: MessageInfo,
     ) -> Result<Response, ContractError> {
@@ -118,51 +158,371 @@ pub mod execute {
                 .collect::<StdResult<Vec<WasmMsg>>>()?,
         ))
     }
+
+    pub fn reevaluate(
+        deps: DepsMut<'_, LogicCustomQuery>,
+        env: Env,
+        info: MessageInfo,
+        query: String,
+        callback: String,
+    ) -> Result<Response, ContractError> {
+        ensure_eq!(
+            SCHEDULER.may_load(deps.storage)?,
+            Some(info.sender.clone()),
+            ContractError::UnauthorizedScheduler
+        );
+        let callback = deps.api.addr_validate(&callback)?;
+
+        let (outcome, height) = outcome(deps.as_ref(), &env, &info.sender, query.clone())?;
+
+        Ok(Response::new()
+            .add_attribute("action", "reevaluate")
+            .add_attribute("outcome", outcome.to_string())
+            .add_message(WasmMsg::Execute {
+                contract_addr: callback.to_string(),
+                msg: to_json_binary(&ReevaluationCallbackMsg::ReevaluationOutcome {
+                    query,
+                    outcome,
+                    height,
+                })?,
+                funds: vec![],
+            }))
+    }
+
+    pub fn subscribe(
+        deps: DepsMut<'_, LogicCustomQuery>,
+        env: Env,
+        info: MessageInfo,
+        goal: String,
+    ) -> Result<Response, ContractError> {
+        let (outcome, height) = outcome(deps.as_ref(), &env, &info.sender, goal.clone())?;
+
+        let id = SUBSCRIPTION_COUNTER.may_load(deps.storage)?.unwrap_or(0) + 1;
+        SUBSCRIPTION_COUNTER.save(deps.storage, &id)?;
+        SUBSCRIPTIONS.save(
+            deps.storage,
+            id,
+            &Subscription {
+                subscriber: info.sender,
+                goal,
+                outcome,
+                height,
+                error: None,
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "subscribe")
+            .add_attribute("subscription_id", id.to_string())
+            .add_attribute("outcome", outcome.to_string()))
+    }
+
+    pub fn unsubscribe(
+        deps: DepsMut<'_, LogicCustomQuery>,
+        info: MessageInfo,
+        id: u64,
+    ) -> Result<Response, ContractError> {
+        ensure_eq!(
+            SUBSCRIPTIONS.load(deps.storage, id)?.subscriber,
+            info.sender,
+            ContractError::UnauthorizedSubscriber
+        );
+        SUBSCRIPTIONS.remove(deps.storage, id);
+
+        Ok(Response::new()
+            .add_attribute("action", "unsubscribe")
+            .add_attribute("subscription_id", id.to_string()))
+    }
+
+    pub fn reevaluate_subscriptions(
+        deps: DepsMut<'_, LogicCustomQuery>,
+        env: Env,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let limit = limit
+            .unwrap_or(DEFAULT_SUBSCRIPTION_LIMIT)
+            .min(MAX_SUBSCRIPTION_LIMIT) as usize;
+        let subscriptions = SUBSCRIPTIONS
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut resp = Response::new()
+            .add_attribute("action", "reevaluate_subscriptions")
+            .add_attribute("subscription_count", subscriptions.len().to_string());
+        if let Some((last, _)) = subscriptions.last() {
+            resp = resp.add_attribute("last_subscription_id", last.to_string());
+        }
+
+        let (mut changed_count, mut failed_count) = (0, 0);
+        for (id, mut subscription) in subscriptions {
+            // A failing goal must not prevent the other ones from being re-evaluated.
+            let (outcome, height) = match outcome(
+                deps.as_ref(),
+                &env,
+                &subscription.subscriber,
+                subscription.goal.clone(),
+            ) {
+                Ok(res) => res,
+                Err(err) => {
+                    failed_count += 1;
+                    subscription.error = Some(err.to_string());
+                    SUBSCRIPTIONS.save(deps.storage, id, &subscription)?;
+                    continue;
+                }
+            };
+            let changed = outcome != subscription.outcome;
+            subscription.outcome = outcome;
+            subscription.height = height;
+            subscription.error = None;
+            SUBSCRIPTIONS.save(deps.storage, id, &subscription)?;
+
+            if changed {
+                changed_count += 1;
+                // A failing subscriber must not revert the notification of the other ones.
+                resp = resp.add_submessage(
+                    SubMsg::reply_on_error(
+                        WasmMsg::Execute {
+                            contract_addr: subscription.subscriber.to_string(),
+                            msg: to_json_binary(
+                                &ReevaluationCallbackMsg::SubscriptionOutcomeChanged {
+                                    subscription_id: id,
+                                    goal: subscription.goal,
+                                    outcome,
+                                    height,
+                                },
+                            )?,
+                            funds: vec![],
+                        },
+                        SUBSCRIPTION_CALLBACK_REPLY_ID,
+                    )
+                    .with_payload(to_json_binary(&id)?),
+                );
+            }
+        }
+
+        Ok(resp
+            .add_attribute("changed_count", changed_count.to_string())
+            .add_attribute("failed_count", failed_count.to_string()))
+    }
+
+    /// Evaluate the goal on behalf of the sender, returning whether it has at least one solution
+    /// along with the height at which it has been evaluated.
+    fn outcome(
+        deps: Deps<'_, LogicCustomQuery>,
+        env: &Env,
+        sender: &Addr,
+        goal: String,
+    ) -> StdResult<(bool, u64)> {
+        let context = EvaluationContext {
+            sender: Some(sender.clone()),
+            ..EvaluationContext::query(env)
+        };
+        let res = query::evaluate(deps, env, context, goal)?;
+        Ok((
+            res.answer
+                .map(|a| a.results.iter().any(|r| r.error.is_none()))
+                .unwrap_or(false),
+            res.height,
+        ))
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps<'_, LogicCustomQuery>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Ask { query } => to_json_binary(&query::ask(deps, env, query)?),
+        QueryMsg::Ask { query, sample } => to_json_binary(&query::ask(deps, env, query, sample)?),
+        QueryMsg::Verdict { goal } => to_json_binary(&query::verdict(deps, env, goal)?),
         QueryMsg::Program {} => to_json_binary(&query::program(deps)?),
         QueryMsg::ProgramCode {} => to_json_binary(&query::program_code(deps)?),
+        QueryMsg::Metadata {} => to_json_binary(&query::metadata(deps)?),
+        QueryMsg::Predicates {} => to_json_binary(&query::predicates(deps)?),
+        QueryMsg::Subscription { id } => to_json_binary(&query::subscription(deps, id)?),
     }
 }
 
 pub mod query {
-    use cosmwasm_std::QueryRequest;
+    use axone_objectarium::msg::{BucketResponse, ObjectResponse};
+    use axone_objectarium::{crypto, state as bucket_state};
+    use cosmwasm_std::{QueryRequest, StdError, Uint128};
 
     use axone_logic_bindings::{Answer, AskResponse};
 
-    use crate::helper::object_ref_to_uri;
-    use crate::msg::ProgramResponse;
-    use crate::state::PROGRAM;
+    use crate::helper::{
+        ask_response_to_count, ask_response_to_verdict, object_ref_to_uri, sample_positions,
+    };
+    use crate::msg::{
+        LawMetadata, PredicatesResponse, ProgramResponse, SubscriptionResponse, VerdictResponse,
+    };
+    use crate::state::{PREDICATES, PROGRAM, SUBSCRIPTIONS};
+    use itertools::Itertools;
 
     use super::*;
 
+    /// The size of the chunks the program code is retrieved in, each one fitting in a smart query
+    /// response.
+    const PROGRAM_CHUNK_SIZE: u128 = 64 * 1024;
+
     const ERR_STONE_BROKEN: &str = "system_error(broken_law_stone)";
+    const REASON_STONE_BROKEN: &str = "broken_law_stone";
 
     pub fn program(deps: Deps<'_, LogicCustomQuery>) -> StdResult<ProgramResponse> {
         let program = PROGRAM.load(deps.storage)?.into();
         Ok(program)
     }
 
+    pub fn metadata(deps: Deps<'_, LogicCustomQuery>) -> StdResult<LawMetadata> {
+        Ok(METADATA.may_load(deps.storage)?.unwrap_or_default())
+    }
+
+    pub fn predicates(deps: Deps<'_, LogicCustomQuery>) -> StdResult<PredicatesResponse> {
+        Ok(PredicatesResponse {
+            predicates: PREDICATES.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn subscription(
+        deps: Deps<'_, LogicCustomQuery>,
+        id: u64,
+    ) -> StdResult<SubscriptionResponse> {
+        Ok(SUBSCRIPTIONS.load(deps.storage, id)?.into_response(id))
+    }
+
     pub fn program_code(deps: Deps<'_, LogicCustomQuery>) -> StdResult<Binary> {
         let ObjectRef {
             storage_address,
             object_id,
         } = PROGRAM.load(deps.storage)?.law;
 
-        deps.querier.query_wasm_smart::<Binary>(
-            storage_address,
-            &StorageQuery::ObjectData { id: object_id },
-        )
+        let object: ObjectResponse = deps.querier.query_wasm_smart(
+            &storage_address,
+            &StorageQuery::Object {
+                id: object_id.clone(),
+            },
+        )?;
+        let mut code = Vec::with_capacity(object.size.u128() as usize);
+        while (code.len() as u128) < object.size.u128() {
+            let chunk: Binary = deps.querier.query_wasm_smart(
+                &storage_address,
+                &StorageQuery::ObjectDataRange {
+                    id: object_id.clone(),
+                    offset: Uint128::new(code.len() as u128),
+                    length: Uint128::new(PROGRAM_CHUNK_SIZE),
+                },
+            )?;
+            if chunk.is_empty() {
+                break;
+            }
+            code.extend_from_slice(&chunk);
+        }
+
+        let bucket: BucketResponse = deps
+            .querier
+            .query_wasm_smart(&storage_address, &StorageQuery::Bucket {})?;
+        let algorithm = bucket_state::HashAlgorithm::from(bucket.config.hash_algorithm).into();
+        if crypto::hash(&algorithm, &code).to_string() != object_id {
+            return Err(StdError::generic_err(
+                "Program code doesn't match its object id",
+            ));
+        }
+
+        Ok(Binary::from(code))
     }
 
+    /// The variables the sampling queries bind besides the ones of the goal, removed from the
+    /// answer.
+    const SAMPLE_VARIABLES: [&str; 5] = [
+        "SampleCount__",
+        "SampleGoal__",
+        "SampleSolutions__",
+        "SampleList__",
+        "SamplePosition__",
+    ];
+
     pub fn ask(
         deps: Deps<'_, LogicCustomQuery>,
         env: Env,
         query: String,
+        sample: Option<u32>,
+    ) -> StdResult<AskResponse> {
+        match sample {
+            Some(size) => ask_sample(deps, &env, query, size),
+            None => evaluate(deps, &env, EvaluationContext::query(&env), query),
+        }
+    }
+
+    /// Evaluate the query, returning a sample of `size` of its solutions drawn from the chain id
+    /// and the block height.
+    ///
+    /// The solutions are first counted, then the goal is evaluated again to collect the drawn
+    /// solutions, its variables being bound to each one of them in turn.
+    fn ask_sample(
+        deps: Deps<'_, LogicCustomQuery>,
+        env: &Env,
+        query: String,
+        size: u32,
+    ) -> StdResult<AskResponse> {
+        let goal = query.trim().trim_end_matches('.');
+        let res = evaluate(
+            deps,
+            env,
+            EvaluationContext::query(env),
+            format!(
+                "findall(SampleCount__, (findall(x, ({goal}), SampleList__), length(SampleList__, SampleCount__)), [SampleCount__])."
+            ),
+        )?;
+        if res
+            .answer
+            .as_ref()
+            .is_some_and(|a| a.results.iter().any(|r| r.error.is_some()))
+        {
+            return Ok(res);
+        }
+        let count = ask_response_to_count(res, "SampleCount__")
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+        let mut seed = env.block.chain_id.as_bytes().to_vec();
+        seed.extend(env.block.height.to_be_bytes());
+        let positions = sample_positions(&seed, count, size.into());
+
+        let mut res = evaluate(
+            deps,
+            env,
+            EvaluationContext::query(env),
+            format!(
+                "SampleGoal__ = ({goal}), \
+                findall(SampleGoal__, (findall(SampleGoal__, SampleGoal__, SampleList__), member(SamplePosition__, [{}]), nth1(SamplePosition__, SampleList__, SampleGoal__)), SampleSolutions__), \
+                member(SampleGoal__, SampleSolutions__).",
+                positions.iter().join(",")
+            ),
+        )?;
+        if let Some(answer) = res.answer.as_mut() {
+            answer
+                .variables
+                .retain(|v| !SAMPLE_VARIABLES.contains(&v.as_str()));
+            for result in &mut answer.results {
+                result
+                    .substitutions
+                    .retain(|s| !SAMPLE_VARIABLES.contains(&s.variable.as_str()));
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Evaluate the query against the law program, asserting the facts of the given evaluation
+    /// context.
+    pub fn evaluate(
+        deps: Deps<'_, LogicCustomQuery>,
+        env: &Env,
+        context: EvaluationContext,
+        query: String,
     ) -> StdResult<AskResponse> {
         let stone = PROGRAM.load(deps.storage)?;
         if stone.broken {
@@ -176,15 +536,62 @@ pub mod query {
             });
         }
 
-        let req: QueryRequest<LogicCustomQuery> = build_ask_query(stone.law, query)?.into();
+        let req: QueryRequest<LogicCustomQuery> =
+            build_ask_query(stone.law, context, query)?.into();
         deps.querier.query(&req)
     }
 
-    pub fn build_ask_query(program: ObjectRef, query: String) -> StdResult<LogicCustomQuery> {
+    pub fn verdict(
+        deps: Deps<'_, LogicCustomQuery>,
+        env: Env,
+        goal: String,
+    ) -> StdResult<VerdictResponse> {
+        let stone = PROGRAM.load(deps.storage)?;
+        if stone.broken {
+            return Ok(VerdictResponse {
+                allowed: false,
+                reasons: vec![REASON_STONE_BROKEN.to_string()],
+                obligations: vec![],
+            });
+        }
+
+        let req: QueryRequest<LogicCustomQuery> =
+            build_verdict_query(stone.law, EvaluationContext::query(&env), goal)?.into();
+        ask_response_to_verdict(deps.querier.query(&req)?)
+            .map_err(|e| StdError::generic_err(e.to_string()))
+    }
+
+    /// Build the query evaluating the goal of an authorization check, collecting the reasons and
+    /// obligations of the verdict from the conventional predicates of the program, ignored if not
+    /// defined.
+    pub fn build_verdict_query(
+        program: ObjectRef,
+        context: EvaluationContext,
+        goal: String,
+    ) -> StdResult<LogicCustomQuery> {
+        let goal = goal.trim().trim_end_matches('.');
+        build_ask_query(
+            program,
+            context,
+            format!(
+                "Goal = ({goal}), (Goal -> Allowed = true ; Allowed = false), \
+                findall(R, catch(verdict_reason(Goal, R), _, fail), Reasons), \
+                findall(O, (catch(verdict_obligation(Goal, T), _, fail), term_to_atom(T, O)), Obligations)."
+            ),
+        )
+    }
+
+    /// Build the query evaluated against the law program, preceded by the facts of the evaluation
+    /// context.
+    pub fn build_ask_query(
+        program: ObjectRef,
+        context: EvaluationContext,
+        query: String,
+    ) -> StdResult<LogicCustomQuery> {
         let program_uri = object_ref_to_uri(program)?;
 
         Ok(LogicCustomQuery::Ask {
-            program: format!(":- consult('{}').", program_uri),
+            program: format!("{}\n:- consult('{}').", context.to_program(), program_uri),
             query,
         })
     }
@@ -198,18 +605,37 @@ pub fn reply(
 ) -> Result<Response, ContractError> {
     match msg.id {
         STORE_PROGRAM_REPLY_ID => reply::store_program_reply(deps, env, msg),
+        SUBSCRIPTION_CALLBACK_REPLY_ID => reply::subscription_callback_reply(msg),
         _ => Err(ContractError::UnknownReplyID),
     }
 }
 
 pub mod reply {
+    use cosmwasm_std::{from_json, SubMsgResult};
     use cw_utils::ParseReplyError;
 
-    use crate::helper::{ask_response_to_objects, get_reply_event_attribute, object_ref_to_uri};
-    use crate::state::{LawStone, DEPENDENCIES, PROGRAM};
+    use crate::helper::{
+        ask_response_to_objects, ask_response_to_predicates, get_reply_event_attribute,
+        object_ref_to_uri,
+    };
+    use crate::state::{LawStone, DEPENDENCIES, PREDICATES, PROGRAM};
 
     use super::*;
 
+    /// Ignore the failure of a subscriber notified of a subscription outcome change, only reporting
+    /// it in the attributes.
+    pub fn subscription_callback_reply(msg: Reply) -> Result<Response, ContractError> {
+        let subscription_id: u64 = from_json(&msg.payload)?;
+        let mut resp = Response::new()
+            .add_attribute("action", "subscription_callback_failed")
+            .add_attribute("subscription_id", subscription_id.to_string());
+        if let SubMsgResult::Err(err) = msg.result {
+            resp = resp.add_attribute("error", err);
+        }
+
+        Ok(resp)
+    }
+
     pub fn store_program_reply(
         deps: DepsMut<'_, LogicCustomQuery>,
         _env: Env,
@@ -220,24 +646,24 @@ pub mod reply {
             .map_err(ParseReplyError::SubMsgFailure)
             .map_err(Into::into)
             .and_then(|e| {
-                get_reply_event_attribute(&e.events, "id").ok_or_else(|| {
-                    ParseReplyError::SubMsgFailure(
-                        "reply event doesn't contains object id".to_string(),
-                    )
-                    .into()
-                })
-            })
-            .and_then(|obj_id| {
+                let storage_address = String::from_utf8(msg.payload.to_vec()).map_err(|e| {
+                    ParseReplyError::SubMsgFailure(format!(
+                        "could not convert reply payload into string address: {}",
+                        e
+                    ))
+                })?;
+                let object_id = get_reply_event_attribute(&e.events, &storage_address, "id")
+                    .ok_or_else(|| {
+                        ParseReplyError::SubMsgFailure(
+                            "reply event doesn't contains object id".to_string(),
+                        )
+                    })?;
+
                 Ok(LawStone {
                     broken: false,
                     law: ObjectRef {
-                        object_id: obj_id,
-                        storage_address: String::from_utf8(msg.payload.to_vec()).map_err(|e| {
-                            ParseReplyError::SubMsgFailure(format!(
-                                "could not convert reply payload into string address: {}",
-                                e
-                            ))
-                        })?,
+                        object_id,
+                        storage_address,
                     },
                 })
             })
@@ -246,6 +672,13 @@ pub mod reply {
                     .save(deps.storage, &stone)
                     .map_err(ContractError::from)?;
 
+                let req = build_predicates_query(stone.law.clone())?.into();
+                let res = deps.querier.query(&req).map_err(ContractError::from)?;
+                PREDICATES.save(
+                    deps.storage,
+                    &ask_response_to_predicates(res, "Predicates")?,
+                )?;
+
                 let req = build_source_files_query(stone.law.clone())?.into();
                 let res = deps.querier.query(&req).map_err(ContractError::from)?;
 
@@ -262,6 +695,20 @@ pub mod reply {
             .map(|msg| Response::new().add_submessages(msg))
     }
 
+    /// Build the query introspecting the predicates defined by the program, excluding the built-in
+    /// ones.
+    pub fn build_predicates_query(program: ObjectRef) -> StdResult<LogicCustomQuery> {
+        let program_uri = object_ref_to_uri(program)?.to_string();
+
+        Ok(LogicCustomQuery::Ask {
+            program: String::new(),
+            query: format!(
+                "consult('{program_uri}'), \
+                findall([N, A], (current_predicate(N/A), functor(H, N, A), \\+ catch(predicate_property(H, built_in), _, fail)), Predicates)."
+            ),
+        })
+    }
+
     pub fn build_source_files_query(program: ObjectRef) -> StdResult<LogicCustomQuery> {
         let program_uri = object_ref_to_uri(program)?.to_string();
 
@@ -283,12 +730,12 @@ mod tests {
     use std::marker::PhantomData;
 
     use cosmwasm_std::testing::{
-        message_info, mock_dependencies, mock_env, MockApi, MockQuerier,
-        MockQuerierCustomHandlerResult, MockStorage,
+        message_info, mock_env, MockApi, MockQuerier, MockQuerierCustomHandlerResult, MockStorage,
     };
     use cosmwasm_std::{
-        coins, from_json, to_json_binary, ContractInfoResponse, ContractResult, CosmosMsg, Event,
-        Order, OwnedDeps, SubMsgResponse, SubMsgResult, SystemError, SystemResult, WasmQuery,
+        coins, from_json, to_json_binary, Attribute, ContractInfoResponse, ContractResult,
+        CosmosMsg, Event, Order, OwnedDeps, ReplyOn, SubMsgResponse, SubMsgResult, SystemError,
+        SystemResult, Uint128, WasmQuery,
     };
     use cw_utils::ParseReplyError::SubMsgFailure;
     use cw_utils::PaymentError;
@@ -298,12 +745,15 @@ mod tests {
     use axone_logic_bindings::{
         Answer, AskResponse, LogicCustomQuery, Result as LogicResult, Substitution,
     };
-    use axone_objectarium::msg::PageInfo;
+    use axone_objectarium::msg::{BucketResponse, CompressionAlgorithm, ObjectResponse, PageInfo};
     use axone_wasm::uri::CosmwasmUri;
     use testing::addr::{addr, CREATOR, SENDER};
 
-    use crate::msg::ProgramResponse;
-    use crate::state::{LawStone, DEPENDENCIES, PROGRAM};
+    use crate::msg::{
+        LawMetadata, PredicateIndicator, PredicatesResponse, ProgramResponse,
+        ReevaluationCallbackMsg, SubscriptionResponse, VerdictResponse,
+    };
+    use crate::state::{LawStone, DEPENDENCIES, PREDICATES, PROGRAM};
 
     use super::*;
 
@@ -315,12 +765,32 @@ mod tests {
         let mut updated_deps = dependencies;
         updated_deps.push(CosmwasmUri::try_from(program.clone()).unwrap().to_string());
         let deps_name = format!("[{}]", &updated_deps.join(","));
+        let predicates_query = reply::build_predicates_query(program.clone()).unwrap();
         let LogicCustomQuery::Ask {
             program: exp_program,
             query: exp_query,
             ..
         } = reply::build_source_files_query(program).unwrap();
         match request {
+            request if *request == predicates_query => SystemResult::Ok(
+                to_json_binary(&AskResponse {
+                    height: 1,
+                    gas_used: 1000,
+                    answer: Some(Answer {
+                        has_more: false,
+                        variables: vec!["Predicates".to_string()],
+                        results: vec![LogicResult {
+                            error: None,
+                            substitutions: vec![Substitution {
+                                variable: "Predicates".to_string(),
+                                expression: "[[can,2],[verdict_reason,2],[can,1]]".to_string(),
+                            }],
+                        }],
+                    }),
+                    user_output: None,
+                })
+                .into(),
+            ),
             LogicCustomQuery::Ask { program, query }
                 if *query == exp_query && *program == exp_program =>
             {
@@ -361,6 +831,8 @@ mod tests {
             program: program.clone(),
             storage_address: "axone1ffzp0xmjhwkltuxcvccl0z9tyfuu7txp5ke0tpkcjpzuq9fcj3pq85yqlv"
                 .to_string(),
+            scheduler: None,
+            metadata: LawMetadata::default(),
         };
         let info = message_info(&addr(CREATOR), &[]);
 
@@ -384,6 +856,9 @@ mod tests {
                             data,
                             pin,
                             compression_algorithm,
+                            expected_digest: None,
+                            reject_duplicate: false,
+                            keep_content: false,
                         } => {
                             assert_eq!(data, program);
                             assert!(pin, "the main program should be pinned");
@@ -409,6 +884,8 @@ mod tests {
             program: to_json_binary("foo(_) :- true.").unwrap(),
             storage_address: "axone1ffzp0xmjhwkltuxcvccl0z9tyfuu7txp5ke0tpkcjpzuq9fcj3pq85yqlv"
                 .to_string(),
+            scheduler: None,
+            metadata: LawMetadata::default(),
         };
 
         let result = instantiate(deps.as_mut(), env, info, msg);
@@ -449,7 +926,7 @@ mod tests {
     fn program_code() {
         const CONTRACT_ID: &str =
             "axone1ffzp0xmjhwkltuxcvccl0z9tyfuu7txp5ke0tpkcjpzuq9fcj3pq85yqlv";
-        const OBJECT_ID: &str = "4cbe36399aabfcc7158ee7a66cbfffa525bb0ceab33d1ff2cff08759fe0a9b05";
+        const OBJECT_ID: &str = "ed73a07cad02d5aadb9739fb50acdd1033389154bfe7d96364c5207683cc7665";
         const A_PROGRAM: &str = "foo(_) :- true.";
 
         let mut deps =
@@ -459,11 +936,26 @@ mod tests {
                 let data = to_json_binary(&A_PROGRAM).unwrap();
                 let storage_query: StorageQuery = from_json(msg).unwrap();
 
-                assert!(
-                    matches!(storage_query, StorageQuery::ObjectData { id } if id == OBJECT_ID)
-                );
+                let res = match storage_query {
+                    StorageQuery::Object { id } if id == OBJECT_ID => {
+                        to_json_binary(&object_response(OBJECT_ID, data.len()))
+                    }
+                    StorageQuery::ObjectDataRange { id, offset, length } if id == OBJECT_ID => {
+                        assert_eq!((offset, length), (Uint128::zero(), Uint128::new(64 * 1024)));
+                        to_json_binary(&data)
+                    }
+                    StorageQuery::Bucket {} => to_json_binary(&BucketResponse {
+                        name: "bucket".to_string(),
+                        config: Default::default(),
+                        limits: Default::default(),
+                        pagination: Default::default(),
+                        stat: Default::default(),
+                        transformer: None,
+                    }),
+                    _ => panic!("unexpected storage query: {storage_query:?}"),
+                };
 
-                SystemResult::Ok(ContractResult::Ok(to_json_binary(&data).unwrap()))
+                SystemResult::Ok(ContractResult::Ok(res.unwrap()))
             }
             _ => {
                 panic!("UnsupportedRequest: query_wasm");
@@ -490,6 +982,89 @@ mod tests {
         assert_eq!(A_PROGRAM, program);
     }
 
+    #[test]
+    fn program_code_in_chunks() {
+        const CONTRACT_ID: &str =
+            "axone1ffzp0xmjhwkltuxcvccl0z9tyfuu7txp5ke0tpkcjpzuq9fcj3pq85yqlv";
+        let program: Vec<u8> = "law(_) :- true.\n".repeat(10_000).into_bytes();
+        let object_id = axone_objectarium::crypto::hash(
+            &axone_objectarium::state::HashAlgorithm::Sha256.into(),
+            &program,
+        )
+        .to_string();
+
+        let cases = vec![
+            (object_id.clone(), Ok(program.clone())),
+            (
+                "0".repeat(64),
+                Err("Generic error: Program code doesn't match its object id".to_string()),
+            ),
+        ];
+
+        for (id, expected) in cases {
+            let mut deps = mock_dependencies_with_logic_handler(|_| {
+                SystemResult::Err(SystemError::Unknown {})
+            });
+            let data = program.clone();
+            deps.querier.update_wasm(move |query| match query {
+                WasmQuery::Smart { contract_addr, msg } if contract_addr == CONTRACT_ID => {
+                    let res = match from_json(msg).unwrap() {
+                        StorageQuery::Object { id } => {
+                            to_json_binary(&object_response(&id, data.len()))
+                        }
+                        StorageQuery::ObjectDataRange { offset, length, .. } => {
+                            let start = (offset.u128() as usize).min(data.len());
+                            let end = (start + length.u128() as usize).min(data.len());
+                            to_json_binary(&Binary::from(&data[start..end]))
+                        }
+                        StorageQuery::Bucket {} => to_json_binary(&BucketResponse {
+                            name: "bucket".to_string(),
+                            config: Default::default(),
+                            limits: Default::default(),
+                            pagination: Default::default(),
+                            stat: Default::default(),
+                            transformer: None,
+                        }),
+                        storage_query => panic!("unexpected storage query: {storage_query:?}"),
+                    };
+                    SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+                }
+                _ => panic!("UnsupportedRequest: query_wasm"),
+            });
+
+            PROGRAM
+                .save(
+                    deps.as_mut().storage,
+                    &LawStone {
+                        broken: false,
+                        law: ObjectRef {
+                            object_id: id,
+                            storage_address: CONTRACT_ID.to_string(),
+                        },
+                    },
+                )
+                .unwrap();
+
+            let result = query(deps.as_ref(), mock_env(), QueryMsg::ProgramCode {})
+                .map(|res| from_json::<Binary>(res).unwrap().to_vec())
+                .map_err(|e| e.to_string());
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn object_response(id: &str, size: usize) -> ObjectResponse {
+        ObjectResponse {
+            id: id.to_string(),
+            owner: "owner".to_string(),
+            is_pinned: true,
+            size: Uint128::new(size as u128),
+            compressed_size: Uint128::new(size as u128),
+            compression_algorithm: CompressionAlgorithm::Passthrough,
+            recoverable_until: None,
+            external_digest: None,
+        }
+    }
+
     fn custom_logic_handler_with_query(
         env: &Env,
         query: String,
@@ -500,7 +1075,8 @@ mod tests {
             program: exp_program,
             query: exp_query,
             ..
-        } = query::build_ask_query(program, query.to_string()).unwrap();
+        } = query::build_ask_query(program, EvaluationContext::query(env), query.to_string())
+            .unwrap();
         match request {
             LogicCustomQuery::Ask {
                 program,
@@ -618,7 +1194,14 @@ mod tests {
                 )
                 .unwrap();
 
-            let res = query(deps.as_ref(), env, QueryMsg::Ask { query: case.1 });
+            let res = query(
+                deps.as_ref(),
+                env,
+                QueryMsg::Ask {
+                    query: case.1,
+                    sample: None,
+                },
+            );
 
             match res {
                 Ok(result) => {
@@ -637,6 +1220,228 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ask_sample() {
+        let env = mock_env();
+        let mut seed = env.block.chain_id.as_bytes().to_vec();
+        seed.extend(env.block.height.to_be_bytes());
+        let positions = crate::helper::sample_positions(&seed, 10, 3);
+        assert_eq!(positions.len(), 3);
+        let sample_query = format!(
+            "member(SamplePosition__, [{}])",
+            positions
+                .iter()
+                .map(u64::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+
+        let mut deps = mock_dependencies_with_logic_handler(move |request| {
+            let LogicCustomQuery::Ask { query, .. } = request;
+            let answer = if query.starts_with("findall(SampleCount__, (findall(x, (reviewer(R)), ")
+            {
+                Answer {
+                    has_more: false,
+                    variables: vec!["SampleCount__".to_string(), "SampleList__".to_string()],
+                    results: vec![LogicResult {
+                        error: None,
+                        substitutions: vec![Substitution {
+                            variable: "SampleCount__".to_string(),
+                            expression: "10".to_string(),
+                        }],
+                    }],
+                }
+            } else if query.starts_with("SampleGoal__ = (reviewer(R)), ")
+                && query.contains(&sample_query)
+            {
+                Answer {
+                    has_more: false,
+                    variables: vec![
+                        "SampleGoal__".to_string(),
+                        "R".to_string(),
+                        "SampleSolutions__".to_string(),
+                    ],
+                    results: vec![LogicResult {
+                        error: None,
+                        substitutions: vec![
+                            Substitution {
+                                variable: "SampleGoal__".to_string(),
+                                expression: "reviewer(bob)".to_string(),
+                            },
+                            Substitution {
+                                variable: "R".to_string(),
+                                expression: "bob".to_string(),
+                            },
+                        ],
+                    }],
+                }
+            } else {
+                return SystemResult::Err(SystemError::Unknown {});
+            };
+            SystemResult::Ok(
+                to_json_binary(&AskResponse {
+                    height: 12345,
+                    gas_used: 1000,
+                    answer: Some(answer),
+                    user_output: None,
+                })
+                .into(),
+            )
+        });
+
+        PROGRAM
+            .save(
+                deps.as_mut().storage,
+                &LawStone {
+                    broken: false,
+                    law: ObjectRef {
+                        object_id: "program-id".to_string(),
+                        storage_address: "axone-objectarium1".to_string(),
+                    },
+                },
+            )
+            .unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            env,
+            QueryMsg::Ask {
+                query: "reviewer(R).".to_string(),
+                sample: Some(3),
+            },
+        )
+        .unwrap();
+        let res: AskResponse = from_json(&res).unwrap();
+        assert_eq!(
+            res.answer,
+            Some(Answer {
+                has_more: false,
+                variables: vec!["R".to_string()],
+                results: vec![LogicResult {
+                    error: None,
+                    substitutions: vec![Substitution {
+                        variable: "R".to_string(),
+                        expression: "bob".to_string(),
+                    }],
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn verdict() {
+        let law = ObjectRef {
+            object_id: "4cbe36399aabfcc7158ee7a66cbfffa525bb0ceab33d1ff2cff08759fe0a9b05"
+                .to_string(),
+            storage_address: "axone1ffzp0xmjhwkltuxcvccl0z9tyfuu7txp5ke0tpkcjpzuq9fcj3pq85yqlv"
+                .to_string(),
+        };
+
+        for broken in [false, true] {
+            let expected_request = query::build_verdict_query(
+                law.clone(),
+                EvaluationContext::query(&mock_env()),
+                "can(transfer, alice).".to_string(),
+            )
+            .unwrap();
+            let mut deps = mock_dependencies_with_logic_handler(move |request| {
+                if *request != expected_request {
+                    return SystemResult::Err(SystemError::Unknown {});
+                }
+                SystemResult::Ok(
+                    to_json_binary(&AskResponse {
+                        answer: Some(Answer {
+                            variables: vec![
+                                "Allowed".to_string(),
+                                "Reasons".to_string(),
+                                "Obligations".to_string(),
+                            ],
+                            results: vec![LogicResult {
+                                error: None,
+                                substitutions: vec![
+                                    Substitution {
+                                        variable: "Allowed".to_string(),
+                                        expression: "true".to_string(),
+                                    },
+                                    Substitution {
+                                        variable: "Reasons".to_string(),
+                                        expression: "[owner]".to_string(),
+                                    },
+                                    Substitution {
+                                        variable: "Obligations".to_string(),
+                                        expression: "['log(transfer)']".to_string(),
+                                    },
+                                ],
+                            }],
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    })
+                    .into(),
+                )
+            });
+            PROGRAM
+                .save(
+                    deps.as_mut().storage,
+                    &LawStone {
+                        broken,
+                        law: law.clone(),
+                    },
+                )
+                .unwrap();
+
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Verdict {
+                    goal: "can(transfer, alice).".to_string(),
+                },
+            )
+            .unwrap();
+
+            assert_eq!(
+                from_json::<VerdictResponse>(&res).unwrap(),
+                if broken {
+                    VerdictResponse {
+                        allowed: false,
+                        reasons: vec!["broken_law_stone".to_string()],
+                        obligations: vec![],
+                    }
+                } else {
+                    VerdictResponse {
+                        allowed: true,
+                        reasons: vec!["owner".to_string()],
+                        obligations: vec!["log(transfer)".to_string()],
+                    }
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn build_verdict_query() {
+        let result = query::build_verdict_query(
+            ObjectRef {
+                object_id: "1cc6de7672c97db145a3940df2264140ea893c6688fa5ca55b73cb8b68e0574d"
+                    .to_string(),
+                storage_address: "axone1ffzp0xmjhwkltuxcvccl0z9tyfuu7txp5ke0tpkcjpzuq9fcj3pq85yqlv"
+                    .to_string(),
+            },
+            EvaluationContext::query(&mock_env()),
+            " can(X, bob). ".to_string(),
+        );
+
+        match result {
+            Ok(LogicCustomQuery::Ask { query, .. }) => assert_eq!(
+                query,
+                "Goal = (can(X, bob)), (Goal -> Allowed = true ; Allowed = false), \
+                findall(R, catch(verdict_reason(Goal, R), _, fail), Reasons), \
+                findall(O, (catch(verdict_obligation(Goal, T), _, fail), term_to_atom(T, O)), Obligations)."
+            ),
+            _ => panic!("Expected Ok(LogicCustomQuery)."),
+        }
+    }
+
     #[derive(Clone)]
     struct StoreTestCase {
         dependencies: Vec<(String, String, String)>, // URI, contract address, object id
@@ -710,8 +1515,20 @@ mod tests {
                 ),
                 gas_used: 0,
                 result: SubMsgResult::Ok(SubMsgResponse {
-                    events: vec![Event::new("e".to_string())
-                        .add_attribute("id".to_string(), case.clone().object_id)],
+                    events: vec![
+                        Event::new("wasm")
+                            .add_attribute(
+                                "_contract_address",
+                                "axone1wug8sewp6cedgkmrmvhl3lf3tulagm9hnvy8p0rppz9yjw0g4wtqfnxh0c",
+                            )
+                            .add_attribute("id", "a_foreign_id"),
+                        Event::new("wasm")
+                            .add_attribute(
+                                "_contract_address",
+                                "axone1dclchlcttf2uektxyryg0c6yau63eml5q9uq03myg44ml8cxpxnqen9apd",
+                            )
+                            .add_attribute("id".to_string(), case.clone().object_id),
+                    ],
                     data: None,
                     msg_responses: vec![],
                 }),
@@ -724,6 +1541,27 @@ mod tests {
             assert!(!program.broken);
             assert_eq!(case.clone().object_id, program.law.object_id);
 
+            let predicates: PredicatesResponse =
+                from_json(query(deps.as_ref(), mock_env(), QueryMsg::Predicates {}).unwrap())
+                    .unwrap();
+            assert_eq!(
+                predicates.predicates,
+                Some(vec![
+                    PredicateIndicator {
+                        name: "can".to_string(),
+                        arity: 1,
+                    },
+                    PredicateIndicator {
+                        name: "can".to_string(),
+                        arity: 2,
+                    },
+                    PredicateIndicator {
+                        name: "verdict_reason".to_string(),
+                        arity: 2,
+                    },
+                ])
+            );
+
             let deps_len_requirement = case.clone().dependencies.len();
 
             if deps_len_requirement > 0 {
@@ -797,7 +1635,8 @@ mod tests {
                     payload: Binary::from("axone1dclchlcttf2uektxyryg0c6yau63eml5q9uq03myg44ml8cxpxnqen9apd".as_bytes()),
                     gas_used: 0,
                     result: SubMsgResult::Ok(SubMsgResponse {
-                        events: vec![Event::new("e".to_string())
+                        events: vec![Event::new("wasm")
+                            .add_attribute("_contract_address", object_id)
                             .add_attribute("id".to_string(), object_id.to_string())],
                         data: None,
                         msg_responses: vec![],
@@ -811,7 +1650,8 @@ mod tests {
                     payload: Binary::from("axone1dclchlcttf2uektxyryg0c6yau63eml5q9uq03myg44ml8cxpxnqen9apd".as_bytes()),
                     gas_used: 0,
                     result: SubMsgResult::Ok(SubMsgResponse {
-                        events: vec![Event::new("e".to_string())],
+                        events: vec![Event::new("e".to_string())
+                            .add_attribute("id".to_string(), object_id.to_string())],
                         data: None,
                         msg_responses: vec![],
                     }),
@@ -851,6 +1691,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn build_predicates_query() {
+        let result = reply::build_predicates_query(ObjectRef {
+            object_id: "1cc6de7672c97db145a3940df2264140ea893c6688fa5ca55b73cb8b68e0574d"
+                .to_string(),
+            storage_address: "axone1ffzp0xmjhwkltuxcvccl0z9tyfuu7txp5ke0tpkcjpzuq9fcj3pq85yqlv"
+                .to_string(),
+        });
+
+        match result {
+            Ok(LogicCustomQuery::Ask { program, query }) => {
+                assert_eq!(program, "");
+                assert_eq!(query, "consult('cosmwasm:axone-objectarium:axone1ffzp0xmjhwkltuxcvccl0z9tyfuu7txp5ke0tpkcjpzuq9fcj3pq85yqlv?query=%7B%22object_data%22%3A%7B%22id%22%3A%221cc6de7672c97db145a3940df2264140ea893c6688fa5ca55b73cb8b68e0574d%22%7D%7D'), findall([N, A], (current_predicate(N/A), functor(H, N, A), \\+ catch(predicate_property(H, built_in), _, fail)), Predicates).")
+            }
+            _ => panic!("Expected Ok(LogicCustomQuery)."),
+        }
+    }
+
     #[test]
     fn build_source_files_query() {
         let result = reply::build_source_files_query(ObjectRef {
@@ -881,12 +1739,18 @@ mod tests {
                 storage_address: "axone1ffzp0xmjhwkltuxcvccl0z9tyfuu7txp5ke0tpkcjpzuq9fcj3pq85yqlv"
                     .to_string(),
             },
+            EvaluationContext {
+                height: 12345,
+                time: 1571797419,
+                chain_id: "axone-localnet".to_string(),
+                sender: None,
+            },
             "test(X).".to_string(),
         );
 
         match result {
             Ok(LogicCustomQuery::Ask { program, query }) => {
-                assert_eq!(program, ":- consult('cosmwasm:axone-objectarium:axone1ffzp0xmjhwkltuxcvccl0z9tyfuu7txp5ke0tpkcjpzuq9fcj3pq85yqlv?query=%7B%22object_data%22%3A%7B%22id%22%3A%221cc6de7672c97db145a3940df2264140ea893c6688fa5ca55b73cb8b68e0574d%22%7D%7D').");
+                assert_eq!(program, ":- dynamic(sender/1).\ncontext_version(1).\nblock_height(12345).\nblock_time(1571797419).\nchain_id('axone-localnet').\n:- consult('cosmwasm:axone-objectarium:axone1ffzp0xmjhwkltuxcvccl0z9tyfuu7txp5ke0tpkcjpzuq9fcj3pq85yqlv?query=%7B%22object_data%22%3A%7B%22id%22%3A%221cc6de7672c97db145a3940df2264140ea893c6688fa5ca55b73cb8b68e0574d%22%7D%7D').");
                 assert_eq!(query, "test(X).")
             }
             _ => panic!("Expected Ok(LogicCustomQuery)."),
@@ -895,7 +1759,8 @@ mod tests {
 
     #[test]
     fn execute_fail_with_funds() {
-        let mut deps = mock_dependencies();
+        let mut deps =
+            mock_dependencies_with_logic_handler(|_| SystemResult::Err(SystemError::Unknown {}));
         let env = mock_env();
         let info = message_info(&addr(SENDER), &coins(10, "uaxone"));
 
@@ -940,7 +1805,9 @@ mod tests {
         ];
 
         for case in cases {
-            let mut deps = mock_dependencies();
+            let mut deps = mock_dependencies_with_logic_handler(|_| {
+                SystemResult::Err(SystemError::Unknown {})
+            });
             deps.querier.update_wasm(move |req| match req {
                 WasmQuery::ContractInfo { .. } => SystemResult::Ok(ContractResult::Ok(
                     to_json_binary(&ContractInfoResponse::new(
@@ -1072,7 +1939,9 @@ mod tests {
         ];
 
         for case in cases {
-            let mut deps = mock_dependencies();
+            let mut deps = mock_dependencies_with_logic_handler(|_| {
+                SystemResult::Err(SystemError::Unknown {})
+            });
             deps.querier.update_wasm(move |req| match req {
                 WasmQuery::ContractInfo { .. } => {
                     let contract_info =
@@ -1125,7 +1994,8 @@ mod tests {
 
     #[test]
     fn break_broken_stone() {
-        let mut deps = mock_dependencies();
+        let mut deps =
+            mock_dependencies_with_logic_handler(|_| SystemResult::Err(SystemError::Unknown {}));
         deps.querier.update_wasm(|req| match req {
             WasmQuery::ContractInfo { .. } => SystemResult::Ok(ContractResult::Ok(
                 to_json_binary(&ContractInfoResponse::new(
@@ -1172,4 +2042,395 @@ mod tests {
         assert!(res.is_ok());
         assert_eq!(res.ok().unwrap().messages.len(), 0);
     }
+
+    #[test]
+    fn proper_scheduler_initialization() {
+        let mut deps =
+            mock_dependencies_with_logic_handler(|_| SystemResult::Err(SystemError::Unknown {}));
+
+        let msg = InstantiateMsg {
+            program: to_json_binary("foo(_) :- true.").unwrap(),
+            storage_address: "axone1ffzp0xmjhwkltuxcvccl0z9tyfuu7txp5ke0tpkcjpzuq9fcj3pq85yqlv"
+                .to_string(),
+            scheduler: Some(addr("scheduler").to_string()),
+            metadata: LawMetadata::default(),
+        };
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(CREATOR), &[]),
+            msg,
+        )
+        .unwrap();
+        assert_eq!(SCHEDULER.load(&deps.storage).unwrap(), addr("scheduler"));
+    }
+
+    #[test]
+    fn unknown_predicates() {
+        let mut deps =
+            mock_dependencies_with_logic_handler(|_| SystemResult::Err(SystemError::Unknown {}));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Predicates {}).unwrap();
+        assert_eq!(
+            from_json::<PredicatesResponse>(&res).unwrap(),
+            PredicatesResponse { predicates: None }
+        );
+
+        PREDICATES.save(deps.as_mut().storage, &vec![]).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Predicates {}).unwrap();
+        assert_eq!(
+            from_json::<PredicatesResponse>(&res).unwrap(),
+            PredicatesResponse {
+                predicates: Some(vec![])
+            }
+        );
+    }
+
+    #[test]
+    fn proper_metadata() {
+        let mut deps =
+            mock_dependencies_with_logic_handler(|_| SystemResult::Err(SystemError::Unknown {}));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Metadata {}).unwrap();
+        assert_eq!(
+            from_json::<LawMetadata>(&res).unwrap(),
+            LawMetadata::default()
+        );
+
+        let metadata = LawMetadata {
+            title: Some("Data sharing agreement".to_string()),
+            jurisdiction: Some("EU".to_string()),
+            version: Some("1.2.0".to_string()),
+            authors: vec!["Alice".to_string(), "Bob".to_string()],
+            license: None,
+        };
+        let msg = InstantiateMsg {
+            program: to_json_binary("foo(_) :- true.").unwrap(),
+            storage_address: "axone1ffzp0xmjhwkltuxcvccl0z9tyfuu7txp5ke0tpkcjpzuq9fcj3pq85yqlv"
+                .to_string(),
+            scheduler: None,
+            metadata: metadata.clone(),
+        };
+
+        let res = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(CREATOR), &[]),
+            msg,
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("title", "Data sharing agreement"),
+                Attribute::new("jurisdiction", "EU"),
+                Attribute::new("version", "1.2.0"),
+                Attribute::new("author", "Alice"),
+                Attribute::new("author", "Bob"),
+            ]
+        );
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Metadata {}).unwrap();
+        assert_eq!(from_json::<LawMetadata>(&res).unwrap(), metadata);
+    }
+
+    #[test]
+    fn reevaluate() {
+        let cases = vec![
+            // scheduler, sender, broken, results, expected
+            (
+                Some("scheduler"),
+                "scheduler",
+                false,
+                vec![LogicResult {
+                    error: None,
+                    substitutions: vec![],
+                }],
+                Ok(true),
+            ),
+            (Some("scheduler"), "scheduler", false, vec![], Ok(false)),
+            (
+                Some("scheduler"),
+                "scheduler",
+                false,
+                vec![LogicResult {
+                    error: Some("error(existence_error)".to_string()),
+                    substitutions: vec![],
+                }],
+                Ok(false),
+            ),
+            (Some("scheduler"), "scheduler", true, vec![], Ok(false)),
+            (
+                Some("scheduler"),
+                SENDER,
+                false,
+                vec![],
+                Err(ContractError::UnauthorizedScheduler),
+            ),
+            (
+                None,
+                "scheduler",
+                false,
+                vec![],
+                Err(ContractError::UnauthorizedScheduler),
+            ),
+        ];
+
+        for (scheduler, sender, broken, results, expected) in cases {
+            let sender_fact = format!("sender('{}').", addr(sender));
+            let mut deps = mock_dependencies_with_logic_handler(move |request| match request {
+                LogicCustomQuery::Ask { program, query }
+                    if query == "expired(X)." && program.contains(&sender_fact) =>
+                {
+                    SystemResult::Ok(
+                        to_json_binary(&AskResponse {
+                            height: 42,
+                            gas_used: 1000,
+                            answer: Some(Answer {
+                                has_more: false,
+                                variables: vec![],
+                                results: results.clone(),
+                            }),
+                            user_output: None,
+                        })
+                        .into(),
+                    )
+                }
+                _ => SystemResult::Err(SystemError::Unknown {}),
+            });
+
+            PROGRAM
+                .save(
+                    &mut deps.storage,
+                    &LawStone {
+                        broken,
+                        law: ObjectRef {
+                            object_id: "program-id".to_string(),
+                            storage_address: "axone-objectarium1".to_string(),
+                        },
+                    },
+                )
+                .unwrap();
+            if let Some(scheduler) = scheduler {
+                SCHEDULER.save(&mut deps.storage, &addr(scheduler)).unwrap();
+            }
+
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(sender), &[]),
+                ExecuteMsg::Reevaluate {
+                    query: "expired(X).".to_string(),
+                    callback: addr("callback").to_string(),
+                },
+            );
+
+            match expected {
+                Ok(outcome) => {
+                    let res = res.unwrap();
+                    assert_eq!(
+                        res.messages.first().map(|m| &m.msg),
+                        Some(&CosmosMsg::Wasm(WasmMsg::Execute {
+                            contract_addr: addr("callback").to_string(),
+                            msg: to_json_binary(&ReevaluationCallbackMsg::ReevaluationOutcome {
+                                query: "expired(X).".to_string(),
+                                outcome,
+                                height: if broken { mock_env().block.height } else { 42 },
+                            })
+                            .unwrap(),
+                            funds: vec![],
+                        }))
+                    );
+                }
+                Err(err) => assert_eq!(res.unwrap_err(), err),
+            }
+        }
+    }
+
+    #[test]
+    fn subscriptions() {
+        let subscriber_fact = format!("sender('{}').", addr("consumer"));
+        let mut deps = mock_dependencies_with_logic_handler(move |request| match request {
+            LogicCustomQuery::Ask { program, query }
+                if query == "open." && program.contains(&subscriber_fact) =>
+            {
+                if program.contains("block_height(300).") {
+                    return SystemResult::Err(SystemError::Unknown {});
+                }
+                SystemResult::Ok(
+                    to_json_binary(&AskResponse {
+                        height: 42,
+                        gas_used: 1000,
+                        answer: Some(Answer {
+                            has_more: false,
+                            variables: vec![],
+                            results: if program.contains("block_height(100).") {
+                                vec![LogicResult {
+                                    error: None,
+                                    substitutions: vec![],
+                                }]
+                            } else {
+                                vec![]
+                            },
+                        }),
+                        user_output: None,
+                    })
+                    .into(),
+                )
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+        PROGRAM
+            .save(
+                &mut deps.storage,
+                &LawStone {
+                    broken: false,
+                    law: ObjectRef {
+                        object_id: "program-id".to_string(),
+                        storage_address: "axone-objectarium1".to_string(),
+                    },
+                },
+            )
+            .unwrap();
+        let env_at = |height: u64| {
+            let mut env = mock_env();
+            env.block.height = height;
+            env
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            env_at(100),
+            message_info(&addr("consumer"), &[]),
+            ExecuteMsg::Subscribe {
+                goal: "open.".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                ("action", "subscribe"),
+                ("subscription_id", "1"),
+                ("outcome", "true")
+            ]
+        );
+
+        let cases = vec![
+            // height, expected notified outcome
+            (100, None),
+            (200, Some(false)),
+            (200, None),
+            (100, Some(true)),
+        ];
+        for (height, expected) in cases {
+            let res = execute(
+                deps.as_mut(),
+                env_at(height),
+                message_info(&addr(SENDER), &[]),
+                ExecuteMsg::ReevaluateSubscriptions {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+            assert!(res.messages.iter().all(|m| m.reply_on == ReplyOn::Error));
+            assert_eq!(
+                res.messages.first().map(|m| m.msg.clone()),
+                expected.map(|outcome| CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: addr("consumer").to_string(),
+                    msg: to_json_binary(&ReevaluationCallbackMsg::SubscriptionOutcomeChanged {
+                        subscription_id: 1,
+                        goal: "open.".to_string(),
+                        outcome,
+                        height: 42,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }))
+            );
+        }
+
+        // a goal failing to be evaluated has its error recorded, its outcome being kept
+        let subscription = |deps: Deps<'_, LogicCustomQuery>| {
+            from_json::<SubscriptionResponse>(
+                &query(deps, mock_env(), QueryMsg::Subscription { id: 1 }).unwrap(),
+            )
+            .unwrap()
+        };
+        for (height, failed_count) in [(300, "1"), (100, "0")] {
+            let res = execute(
+                deps.as_mut(),
+                env_at(height),
+                message_info(&addr(SENDER), &[]),
+                ExecuteMsg::ReevaluateSubscriptions {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+            assert!(res.messages.is_empty());
+            assert!(res
+                .attributes
+                .contains(&Attribute::new("failed_count", failed_count)));
+            assert_eq!(
+                subscription(deps.as_ref()).error.is_some(),
+                failed_count == "1"
+            );
+        }
+        assert_eq!(
+            subscription(deps.as_ref()),
+            SubscriptionResponse {
+                id: 1,
+                subscriber: addr("consumer").to_string(),
+                goal: "open.".to_string(),
+                outcome: true,
+                height: 42,
+                error: None,
+            }
+        );
+
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&addr(SENDER), &[]),
+                ExecuteMsg::Unsubscribe { id: 1 },
+            )
+            .unwrap_err(),
+            ContractError::UnauthorizedSubscriber
+        );
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr("consumer"), &[]),
+            ExecuteMsg::Unsubscribe { id: 1 },
+        )
+        .unwrap();
+        assert!(query(deps.as_ref(), mock_env(), QueryMsg::Subscription { id: 1 }).is_err());
+    }
+
+    #[test]
+    fn failing_subscriber_callback() {
+        let mut deps =
+            mock_dependencies_with_logic_handler(|_| SystemResult::Err(SystemError::Unknown {}));
+
+        let msg = Reply {
+            id: SUBSCRIPTION_CALLBACK_REPLY_ID,
+            payload: to_json_binary(&3u64).unwrap(),
+            gas_used: 0,
+            result: SubMsgResult::Err("subscriber reverted".to_string()),
+        };
+        let res = reply(deps.as_mut(), mock_env(), msg).unwrap();
+        assert!(res.messages.is_empty());
+        assert_eq!(
+            res.attributes,
+            vec![
+                ("action", "subscription_callback_failed"),
+                ("subscription_id", "3"),
+                ("error", "subscriber reverted"),
+            ]
+        );
+    }
 }
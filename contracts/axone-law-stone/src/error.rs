@@ -24,6 +24,12 @@ pub enum ContractError {
     #[error("Only the contract admin can perform this operation.")]
     Unauthorized,
 
+    #[error("Only the scheduler can trigger a re-evaluation.")]
+    UnauthorizedScheduler,
+
+    #[error("Only the subscriber can cancel its subscription.")]
+    UnauthorizedSubscriber,
+
     #[error("{0}")]
     Payment(#[from] PaymentError),
 }
@@ -1,12 +1,15 @@
 use crate::error::LogicAskResponseError;
+use crate::msg::{PredicateIndicator, VerdictResponse};
 use crate::ContractError;
-use axone_logic_bindings::{AskResponse, TermValue};
+use axone_logic_bindings::{AskResponse, Result as LogicResult, TermValue};
+use axone_objectarium::crypto;
 use axone_objectarium_client::ObjectRef;
 use axone_wasm::error::CosmwasmUriError;
 use axone_wasm::uri::CosmwasmUri;
 use cosmwasm_std::{Event, StdError, StdResult};
 use itertools::Itertools;
 use std::any::type_name;
+use std::collections::BTreeSet;
 
 pub fn object_ref_to_uri(object: ObjectRef) -> StdResult<CosmwasmUri> {
     CosmwasmUri::try_from(object).map_err(|e: CosmwasmUriError| {
@@ -14,9 +17,18 @@ pub fn object_ref_to_uri(object: ObjectRef) -> StdResult<CosmwasmUri> {
     })
 }
 
-pub fn get_reply_event_attribute(events: &[Event], key: &str) -> Option<String> {
+/// Returns the value of the first attribute with the given key among the `wasm` events emitted by
+/// the given contract, i.e. whose `_contract_address` attribute is its address, so that attributes
+/// emitted by any other contract involved in the sub message are ignored.
+pub fn get_reply_event_attribute(events: &[Event], contract: &str, key: &str) -> Option<String> {
     events
         .iter()
+        .filter(|e| {
+            e.ty == "wasm"
+                && e.attributes
+                    .iter()
+                    .any(|a| a.key == "_contract_address" && a.value == contract)
+        })
         .flat_map(|e| e.attributes.iter())
         .find(|a| a.key == key)
         .map(|a| a.value.clone())
@@ -47,6 +59,117 @@ pub fn ask_response_to_objects(
     res: AskResponse,
     variable: String,
 ) -> Result<Vec<ObjectRef>, ContractError> {
+    let result = ask_response_to_result(res)?;
+
+    substitution_term(result, &variable)
+        .and_then(term_as_vec)?
+        .into_iter()
+        .map(|raw| {
+            CosmwasmUri::try_from(raw)
+                .and_then(ObjectRef::try_from)
+                .map_err(ContractError::ParseCosmwasmUri)
+        })
+        .collect()
+}
+
+/// Extract the predicates substituted to the specified variable, assuming a single result in the
+/// response. The substitution is parsed as an array of `[Name, Arity]` pairs, the quoted names being
+/// unquoted, returned ordered by name and arity without duplicates.
+pub fn ask_response_to_predicates(
+    res: AskResponse,
+    variable: &str,
+) -> Result<Vec<PredicateIndicator>, ContractError> {
+    let result = ask_response_to_result(res)?;
+
+    match substitution_term(result, variable)? {
+        TermValue::Array(values) => values
+            .into_iter()
+            .map(|value| match value {
+                TermValue::Array(pair) => match &pair[..] {
+                    [TermValue::Value(name), TermValue::Value(arity)] => {
+                        arity.parse().ok().map(|arity| PredicateIndicator {
+                            name: name.clone(),
+                            arity,
+                        })
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .map(|predicate| {
+                predicate.ok_or(ContractError::LogicAskResponse(
+                    LogicAskResponseError::UnexpectedTerm,
+                ))
+            })
+            .collect::<Result<BTreeSet<_>, _>>()
+            .map(|predicates| predicates.into_iter().collect()),
+        _ => Err(ContractError::LogicAskResponse(
+            LogicAskResponseError::UnexpectedTerm,
+        )),
+    }
+}
+
+/// Extract the verdict of an authorization check from the response of the query built by
+/// [crate::contract::query::build_verdict_query], assuming a single result substituting the `Allowed`,
+/// `Reasons` and `Obligations` variables.
+pub fn ask_response_to_verdict(res: AskResponse) -> Result<VerdictResponse, ContractError> {
+    let result = ask_response_to_result(res)?;
+
+    let allowed = match substitution_term(result.clone(), "Allowed")? {
+        TermValue::Value(v) if v == "true" => true,
+        TermValue::Value(v) if v == "false" => false,
+        _ => Err(ContractError::LogicAskResponse(
+            LogicAskResponseError::UnexpectedTerm,
+        ))?,
+    };
+
+    Ok(VerdictResponse {
+        allowed,
+        reasons: substitution_term(result.clone(), "Reasons").and_then(term_as_vec)?,
+        obligations: substitution_term(result, "Obligations").and_then(term_as_vec)?,
+    })
+}
+
+/// Extract the number substituted to the specified variable, assuming a single result in the
+/// response.
+pub fn ask_response_to_count(res: AskResponse, variable: &str) -> Result<u64, ContractError> {
+    match substitution_term(ask_response_to_result(res)?, variable)? {
+        TermValue::Value(value) => value
+            .parse()
+            .map_err(|_| ContractError::LogicAskResponse(LogicAskResponseError::UnexpectedTerm)),
+        _ => Err(ContractError::LogicAskResponse(
+            LogicAskResponseError::UnexpectedTerm,
+        )),
+    }
+}
+
+/// Pseudo-randomly draw `size` distinct positions among `1..=count` from the seed, returned in
+/// ascending order, all of them being returned if `size` is not lower than `count`.
+///
+/// The positions are drawn with Floyd's algorithm, each draw taking the first 8 bytes of the
+/// SHA-256 of the seed followed by the big-endian draw number, reduced modulo the range size.
+pub fn sample_positions(seed: &[u8], count: u64, size: u64) -> Vec<u64> {
+    let size = size.min(count);
+    let mut positions = BTreeSet::new();
+    for draw in 0..size {
+        let upper = count - size + 1 + draw;
+        let mut data = seed.to_vec();
+        data.extend(draw.to_be_bytes());
+        let digest: Vec<u8> = crypto::hash(&crypto::HashAlgorithm::Sha256, &data).into();
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+
+        let position = u64::from_be_bytes(bytes) % upper + 1;
+        if !positions.insert(position) {
+            positions.insert(upper);
+        }
+    }
+
+    positions.into_iter().collect()
+}
+
+/// Extract the single result of the response, failing if it holds an error.
+fn ask_response_to_result(res: AskResponse) -> Result<LogicResult, ContractError> {
     let result = res
         .answer
         .map(|a| a.results)
@@ -65,7 +188,12 @@ pub fn ask_response_to_objects(
         ));
     }
 
-    let substitution = result
+    Ok(result)
+}
+
+/// Extract and parse the single substitution of the specified variable in the result.
+fn substitution_term(result: LogicResult, variable: &str) -> Result<TermValue, ContractError> {
+    result
         .substitutions
         .into_iter()
         .filter(|s| s.variable == variable)
@@ -74,19 +202,9 @@ pub fn ask_response_to_objects(
             ContractError::LogicAskResponse(LogicAskResponseError::Unexpected(
                 "expected exactly one substitution".to_string(),
             ))
-        })?;
-
-    substitution
+        })?
         .parse_expression()
         .map_err(|e| ContractError::LogicAskResponse(LogicAskResponseError::Parse(e)))
-        .and_then(term_as_vec)?
-        .into_iter()
-        .map(|raw| {
-            CosmwasmUri::try_from(raw)
-                .and_then(ObjectRef::try_from)
-                .map_err(ContractError::ParseCosmwasmUri)
-        })
-        .collect()
 }
 
 #[cfg(test)]
@@ -265,4 +383,181 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn logic_to_verdict() {
+        let substitutions = |allowed: &str, reasons: &str, obligations: &str| {
+            vec![axone_logic_bindings::Result {
+                error: None,
+                substitutions: vec![
+                    Substitution {
+                        variable: "Allowed".to_string(),
+                        expression: allowed.to_string(),
+                    },
+                    Substitution {
+                        variable: "Reasons".to_string(),
+                        expression: reasons.to_string(),
+                    },
+                    Substitution {
+                        variable: "Obligations".to_string(),
+                        expression: obligations.to_string(),
+                    },
+                ],
+            }]
+        };
+
+        let cases = vec![
+            (
+                substitutions("true", "[member,verified]", "['notify(admin)']"),
+                Ok(VerdictResponse {
+                    allowed: true,
+                    reasons: vec!["member".to_string(), "verified".to_string()],
+                    obligations: vec!["notify(admin)".to_string()],
+                }),
+            ),
+            (
+                substitutions("false", "[]", "[]"),
+                Ok(VerdictResponse {
+                    allowed: false,
+                    reasons: vec![],
+                    obligations: vec![],
+                }),
+            ),
+            (
+                substitutions("maybe", "[]", "[]"),
+                Err(ContractError::LogicAskResponse(
+                    LogicAskResponseError::UnexpectedTerm,
+                )),
+            ),
+            (
+                substitutions("true", "member", "[]"),
+                Err(ContractError::LogicAskResponse(
+                    LogicAskResponseError::UnexpectedTerm,
+                )),
+            ),
+            (
+                vec![axone_logic_bindings::Result {
+                    error: Some("error(existence_error(procedure,can/2),root)".to_string()),
+                    substitutions: vec![],
+                }],
+                Err(ContractError::LogicAskResponse(
+                    LogicAskResponseError::Substitution(
+                        "error(existence_error(procedure,can/2),root)".to_string(),
+                    ),
+                )),
+            ),
+        ];
+
+        for (results, expected) in cases {
+            assert_eq!(
+                ask_response_to_verdict(AskResponse {
+                    answer: Some(Answer {
+                        results,
+                        has_more: false,
+                        variables: vec![
+                            "Allowed".to_string(),
+                            "Reasons".to_string(),
+                            "Obligations".to_string(),
+                        ],
+                    }),
+                    height: 1,
+                    gas_used: 1,
+                    user_output: None,
+                }),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn logic_to_predicates() {
+        let predicate = |name: &str, arity| PredicateIndicator {
+            name: name.to_string(),
+            arity,
+        };
+        let cases = vec![
+            ("[]", Ok(vec![])),
+            (
+                "[[can,2],[verdict_reason,2],[can,1],[can,2]]",
+                Ok(vec![
+                    predicate("can", 1),
+                    predicate("can", 2),
+                    predicate("verdict_reason", 2),
+                ]),
+            ),
+            (
+                "[['a/b',1],[-,2],['it\\'s',0]]",
+                Ok(vec![
+                    predicate("-", 2),
+                    predicate("a/b", 1),
+                    predicate("it's", 0),
+                ]),
+            ),
+            (
+                "[can]",
+                Err(ContractError::LogicAskResponse(
+                    LogicAskResponseError::UnexpectedTerm,
+                )),
+            ),
+            (
+                "[[can]]",
+                Err(ContractError::LogicAskResponse(
+                    LogicAskResponseError::UnexpectedTerm,
+                )),
+            ),
+            (
+                "[[can,x]]",
+                Err(ContractError::LogicAskResponse(
+                    LogicAskResponseError::UnexpectedTerm,
+                )),
+            ),
+            (
+                "can",
+                Err(ContractError::LogicAskResponse(
+                    LogicAskResponseError::UnexpectedTerm,
+                )),
+            ),
+        ];
+
+        for (expression, expected) in cases {
+            assert_eq!(
+                ask_response_to_predicates(
+                    AskResponse {
+                        answer: Some(Answer {
+                            results: vec![axone_logic_bindings::Result {
+                                error: None,
+                                substitutions: vec![Substitution {
+                                    variable: "Predicates".to_string(),
+                                    expression: expression.to_string(),
+                                }],
+                            }],
+                            has_more: false,
+                            variables: vec!["Predicates".to_string()],
+                        }),
+                        height: 1,
+                        gas_used: 1,
+                        user_output: None,
+                    },
+                    "Predicates"
+                ),
+                expected,
+                "{expression}"
+            );
+        }
+    }
+
+    #[test]
+    fn proper_sample_positions() {
+        let positions = sample_positions(b"seed", 1000, 5);
+        assert_eq!(positions.len(), 5);
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+        assert!(positions.iter().all(|p| (1..=1000).contains(p)));
+        assert_eq!(sample_positions(b"seed", 1000, 5), positions);
+        assert_ne!(sample_positions(b"other seed", 1000, 5), positions);
+
+        assert_eq!(sample_positions(b"seed", 3, 5), vec![1, 2, 3]);
+        assert_eq!(sample_positions(b"seed", 3, 3), vec![1, 2, 3]);
+        assert_eq!(sample_positions(b"seed", 0, 5), Vec::<u64>::new());
+        assert_eq!(sample_positions(b"seed", 10, 0), Vec::<u64>::new());
+    }
 }
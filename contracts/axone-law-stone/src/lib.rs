@@ -1,3 +1,4 @@
+pub mod context;
 pub mod contract;
 mod error;
 mod helper;
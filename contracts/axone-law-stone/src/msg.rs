@@ -11,6 +11,37 @@ pub struct InstantiateMsg {
 
     /// The `axone-objectarium` contract address on which to store the law program.
     pub storage_address: String,
+
+    /// The address of the scheduler contract allowed to trigger re-evaluations through
+    /// [ExecuteMsg::Reevaluate], if any.
+    pub scheduler: Option<String>,
+
+    /// The human-readable description of the law, returned by [QueryMsg::Metadata] so it can be
+    /// rendered without fetching and parsing the program.
+    #[serde(default)]
+    pub metadata: LawMetadata,
+}
+
+/// # LawMetadata
+/// The human-readable description of a law, given at instantiation.
+#[cw_serde]
+#[derive(Default)]
+pub struct LawMetadata {
+    /// The title of the law.
+    #[serde(default)]
+    pub title: Option<String>,
+    /// The jurisdiction the law applies in, e.g. a country or an organization.
+    #[serde(default)]
+    pub jurisdiction: Option<String>,
+    /// The version of the law.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// The authors of the law.
+    #[serde(default)]
+    pub authors: Vec<String>,
+    /// The license the law is published under, e.g. as an SPDX identifier.
+    #[serde(default)]
+    pub license: Option<String>,
 }
 
 /// Execute messages
@@ -25,6 +56,93 @@ pub enum ExecuteMsg {
     /// this message.
     /// If already broken, this is a no-op.
     BreakStone {},
+
+    /// # Reevaluate
+    /// Evaluate the given Prolog goal against the law program and push its boolean outcome to the
+    /// callback contract through a [ReevaluationCallbackMsg::ReevaluationOutcome] message.
+    ///
+    /// The outcome is `true` if the goal has at least one solution, and `false` otherwise, including
+    /// when the law stone is broken.
+    ///
+    /// Only the scheduler address configured at instantiation is authorized to invoke this message,
+    /// allowing time-based effects to be triggered without keys held off-chain.
+    ///
+    /// The goal is evaluated with the facts of the evaluation context, the `sender(S)` fact being
+    /// set to the scheduler address.
+    Reevaluate {
+        /// The Prolog goal to evaluate.
+        query: String,
+        /// The address of the contract to notify with the outcome.
+        callback: String,
+    },
+
+    /// # Subscribe
+    /// Register a standing Prolog goal on behalf of the sender, evaluated right away and then
+    /// re-evaluated through [ExecuteMsg::ReevaluateSubscriptions], the sender being notified with a
+    /// [ReevaluationCallbackMsg::SubscriptionOutcomeChanged] message only when its outcome flips.
+    ///
+    /// The outcome is `true` if the goal has at least one solution, and `false` otherwise, including
+    /// when the law stone is broken. The goal is evaluated with the facts of the evaluation context,
+    /// the `sender(S)` fact being set to the subscriber address.
+    ///
+    /// The identifier of the subscription is returned in the `subscription_id` attribute.
+    Subscribe {
+        /// The Prolog goal to evaluate.
+        goal: String,
+    },
+
+    /// # Unsubscribe
+    /// Remove a subscription, only the subscriber being authorized to do so.
+    Unsubscribe {
+        /// The identifier of the subscription.
+        id: u64,
+    },
+
+    /// # ReevaluateSubscriptions
+    /// Re-evaluate a page of the subscriptions, in the order of their identifiers, notifying the
+    /// subscribers whose goal outcome flipped since its last evaluation.
+    ///
+    /// Anyone can invoke this message, e.g. once the facts a goal depends on have changed, the
+    /// subscribers only being notified of actual changes. A subscriber failing to handle its
+    /// notification doesn't prevent the others from being notified.
+    ///
+    /// A goal failing to be evaluated doesn't prevent the others from being re-evaluated either:
+    /// its error is recorded in the subscription, see [QueryMsg::Subscription], the number of such
+    /// failures being reported in the `failed_count` attribute.
+    ReevaluateSubscriptions {
+        /// The identifier of the subscription after which to start, excluded.
+        start_after: Option<u64>,
+        /// The maximum number of subscriptions to re-evaluate, 10 by default and at most 30.
+        limit: Option<u32>,
+    },
+}
+
+/// Messages sent by the law stone to the callback contracts.
+#[cw_serde]
+pub enum ReevaluationCallbackMsg {
+    /// # ReevaluationOutcome
+    /// Notify the outcome of a goal re-evaluation triggered by the scheduler.
+    ReevaluationOutcome {
+        /// The evaluated Prolog goal.
+        query: String,
+        /// Whether the goal has at least one solution.
+        outcome: bool,
+        /// The block height at which the goal has been evaluated.
+        height: u64,
+    },
+
+    /// # SubscriptionOutcomeChanged
+    /// Notify the subscriber that the outcome of its standing goal flipped.
+    SubscriptionOutcomeChanged {
+        /// The identifier of the subscription.
+        subscription_id: u64,
+        /// The evaluated Prolog goal.
+        goal: String,
+        /// Whether the goal has at least one solution.
+        outcome: bool,
+        /// The block height at which the goal has been evaluated.
+        height: u64,
+    },
 }
 
 /// Query messages
@@ -35,10 +153,50 @@ pub enum QueryMsg {
     /// Submits a Prolog query string to the `Logic` module, evaluating it against the
     /// law program associated with this contract.
     ///
+    /// The following facts of the evaluation context (version 1) are asserted into the evaluation,
+    /// allowing laws to express temporal and identity conditions:
+    ///
+    /// - `context_version(V)`: the version of the evaluation context;
+    /// - `block_height(H)`: the height of the current block;
+    /// - `block_time(T)`: the time of the current block, in seconds since the Unix epoch;
+    /// - `chain_id(C)`: the identifier of the chain, as an atom;
+    /// - `sender(S)`: the address of the message sender, as an atom, only defined on the execute
+    ///   path (i.e. [ExecuteMsg::Reevaluate]), failing otherwise.
+    ///
     /// If the law stone is broken the query returns a response with the error `error(system_error(broken_law_stone),root)`
     /// set in the `answer` field.
     #[returns(AskResponse)]
-    Ask { query: String },
+    Ask {
+        query: String,
+        /// The number of solutions to sample among all the solutions of the query, instead of
+        /// returning the first ones, for queries whose first solutions would bias the outcome (e.g.
+        /// a reviewer selection).
+        ///
+        /// The solutions are drawn pseudo-randomly but deterministically from the chain id and the
+        /// block height, all of them being evaluated, and returned in their evaluation order. Their
+        /// number remains bounded by the maximum result count of the `Logic` module.
+        #[serde(default)]
+        sample: Option<u32>,
+    },
+
+    /// # Verdict
+    /// Evaluates an authorization check against the law program, returning a structured verdict
+    /// consumer contracts can branch on.
+    ///
+    /// The check is allowed if the goal has a solution. The reasons and obligations are collected
+    /// from the following conventional predicates of the program, if defined:
+    ///
+    /// - `verdict_reason(Goal, Reason)`: the reasons of the verdict, as atoms;
+    /// - `verdict_obligation(Goal, Obligation)`: the obligations coming with the verdict, as terms.
+    ///
+    /// The goal is evaluated with the facts of the evaluation context, see [QueryMsg::Ask].
+    ///
+    /// If the law stone is broken, the check is denied with the `broken_law_stone` reason.
+    #[returns(VerdictResponse)]
+    Verdict {
+        /// The Prolog goal of the authorization check, e.g. `can(transfer, 'axone1...')`.
+        goal: String,
+    },
 
     /// # Program
     /// Retrieves the location metadata of the law program bound to this contract.
@@ -53,8 +211,86 @@ pub enum QueryMsg {
     ///
     /// If the law stone is broken, the query may fail if the program is no longer available in the
     /// `Objectarium`.
+    ///
+    /// The program is retrieved in chunks through the ranged query of the `Objectarium`, so it can
+    /// be larger than a query response, the assembled code being verified against the program
+    /// object ID, i.e. its hash.
     #[returns(Binary)]
     ProgramCode {},
+
+    /// # Metadata
+    /// Returns the human-readable description of the law given at instantiation, its fields being
+    /// empty if none was given.
+    #[returns(LawMetadata)]
+    Metadata {},
+
+    /// # Predicates
+    /// Returns the predicates defined by the law program, i.e. the goals it can answer, as found
+    /// by introspecting the program once stored at instantiation.
+    ///
+    /// The predicates of the files consulted by the program are included, whereas the built-in
+    /// ones and the facts of the evaluation context are not.
+    #[returns(PredicatesResponse)]
+    Predicates {},
+
+    /// # Subscription
+    /// Returns a subscription along with the outcome of its last evaluation.
+    #[returns(SubscriptionResponse)]
+    Subscription {
+        /// The identifier of the subscription.
+        id: u64,
+    },
+}
+
+/// # PredicatesResponse
+/// The predicates defined by the law program, see [QueryMsg::Predicates].
+#[cw_serde]
+pub struct PredicatesResponse {
+    /// The predicates, ordered by name and arity, or none if unknown, the stone having been
+    /// instantiated before the introspection of its program got introduced.
+    pub predicates: Option<Vec<PredicateIndicator>>,
+}
+
+/// # PredicateIndicator
+/// A predicate identified by its name and arity, e.g. `can/2`.
+#[cw_serde]
+#[derive(Eq, PartialOrd, Ord)]
+pub struct PredicateIndicator {
+    /// The name of the predicate.
+    pub name: String,
+    /// The number of arguments of the predicate.
+    pub arity: u32,
+}
+
+/// # SubscriptionResponse
+/// A standing goal registered through [ExecuteMsg::Subscribe].
+#[cw_serde]
+pub struct SubscriptionResponse {
+    /// The identifier of the subscription.
+    pub id: u64,
+    /// The address of the subscriber, notified when the outcome flips.
+    pub subscriber: String,
+    /// The Prolog goal.
+    pub goal: String,
+    /// The outcome of the last evaluation of the goal.
+    pub outcome: bool,
+    /// The block height at which the goal has last been evaluated.
+    pub height: u64,
+    /// The error the last re-evaluation of the goal failed with, if any, the outcome and height
+    /// being the ones of its last successful evaluation.
+    pub error: Option<String>,
+}
+
+/// # VerdictResponse
+/// The structured verdict of an authorization check.
+#[cw_serde]
+pub struct VerdictResponse {
+    /// Whether the check is allowed.
+    pub allowed: bool,
+    /// The reasons of the verdict, as atoms.
+    pub reasons: Vec<String>,
+    /// The obligations coming with the verdict, as the textual representation of their terms.
+    pub obligations: Vec<String>,
 }
 
 /// # ProgramResponse
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-use crate::msg::ProgramResponse;
+use crate::msg::{LawMetadata, PredicateIndicator, ProgramResponse, SubscriptionResponse};
 use axone_objectarium_client::ObjectRef;
+use cosmwasm_std::Addr;
 use cw_storage_plus::{Item, Map};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -22,3 +23,46 @@ impl From<LawStone> for ProgramResponse {
 pub const PROGRAM: Item<LawStone> = Item::new("program");
 
 pub const DEPENDENCIES: Map<&str, ObjectRef> = Map::new("dependencies");
+
+/// The human-readable description of the law given at instantiation.
+pub const METADATA: Item<LawMetadata> = Item::new("metadata");
+
+/// The predicates defined by the law program, found by introspecting it once stored.
+pub const PREDICATES: Item<Vec<PredicateIndicator>> = Item::new("predicates");
+
+/// The scheduler contract allowed to trigger re-evaluations, if any.
+pub const SCHEDULER: Item<Addr> = Item::new("scheduler");
+
+/// A standing goal whose outcome is notified to the subscriber when it flips.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Subscription {
+    pub subscriber: Addr,
+    pub goal: String,
+    /// The outcome of the last evaluation of the goal.
+    pub outcome: bool,
+    /// The block height at which the goal has last been evaluated.
+    pub height: u64,
+    /// The error the last re-evaluation of the goal failed with, if any, the outcome and height
+    /// being the ones of its last successful evaluation.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl Subscription {
+    pub fn into_response(self, id: u64) -> SubscriptionResponse {
+        SubscriptionResponse {
+            id,
+            subscriber: self.subscriber.into(),
+            goal: self.goal,
+            outcome: self.outcome,
+            height: self.height,
+            error: self.error,
+        }
+    }
+}
+
+/// The identifier of the last registered subscription.
+pub const SUBSCRIPTION_COUNTER: Item<u64> = Item::new("subscription_counter");
+
+/// The subscriptions, by identifier.
+pub const SUBSCRIPTIONS: Map<u64, Subscription> = Map::new("subscriptions");
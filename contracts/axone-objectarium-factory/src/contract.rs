@@ -0,0 +1,518 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    instantiate2_address, to_json_binary, Addr, Binary, CodeInfoResponse, Deps, DepsMut, Env,
+    MessageInfo, Response, StdError, StdResult,
+};
+use cw2::set_contract_version;
+use cw_utils::nonpayable;
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::BUCKET_CODE_ID;
+
+// version info for migration info
+const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut<'_>,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    BUCKET_CODE_ID.save(deps.storage, &msg.bucket_code_id.u64())?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    match msg {
+        ExecuteMsg::CreateBucket { label, bucket } => {
+            execute::create_bucket(deps, env, info, label, bucket)
+        }
+    }
+}
+
+pub mod execute {
+    use super::*;
+    use crate::state::{buckets, Bucket};
+    use cosmwasm_std::WasmMsg;
+
+    pub fn create_bucket(
+        deps: DepsMut<'_>,
+        env: Env,
+        info: MessageInfo,
+        label: String,
+        bucket: axone_objectarium::msg::InstantiateMsg,
+    ) -> Result<Response, ContractError> {
+        if label.is_empty() {
+            Err(ContractError::EmptyLabel)?;
+        }
+        if buckets().has(deps.storage, (&info.sender, &label)) {
+            Err(ContractError::BucketAlreadyExists(label.clone()))?;
+        }
+
+        let code_id = BUCKET_CODE_ID.load(deps.storage)?;
+        let (address, salt) = bucket_address(deps.as_ref(), &env, code_id, &info.sender, &label)?;
+        buckets().save(
+            deps.storage,
+            (&info.sender, &label),
+            &Bucket {
+                owner: info.sender.clone(),
+                label: label.clone(),
+                address: address.clone(),
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "create_bucket")
+            .add_attribute("owner", info.sender.to_string())
+            .add_attribute("label", label.clone())
+            .add_attribute("bucket_address", address.to_string())
+            .add_message(WasmMsg::Instantiate2 {
+                admin: Some(info.sender.to_string()),
+                code_id,
+                label: format!("{}_{}", info.sender, label),
+                msg: to_json_binary(&bucket)?,
+                funds: vec![],
+                salt,
+            }))
+    }
+}
+
+/// The salt the bucket of an owner with a label is instantiated with, i.e. the SHA-256 of the
+/// big-endian length of the owner address, the owner address and the label.
+pub fn bucket_salt(owner: &Addr, label: &str) -> Binary {
+    Binary::from(
+        Sha256::new()
+            .chain_update((owner.as_str().len() as u32).to_be_bytes())
+            .chain_update(owner.as_str())
+            .chain_update(label)
+            .finalize()
+            .to_vec(),
+    )
+}
+
+/// Compute the address the bucket of an owner with a label is instantiated at, along with the salt
+/// to instantiate it with.
+fn bucket_address(
+    deps: Deps<'_>,
+    env: &Env,
+    code_id: u64,
+    owner: &Addr,
+    label: &str,
+) -> StdResult<(Addr, Binary)> {
+    let creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+    let CodeInfoResponse { checksum, .. } = deps.querier.query_wasm_code_info(code_id)?;
+    let salt = bucket_salt(owner, label);
+
+    let address = instantiate2_address(checksum.as_slice(), &creator, &salt)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    Ok((deps.api.addr_humanize(&address)?, salt))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps<'_>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query::config(deps)?),
+        QueryMsg::Bucket { owner, label } => to_json_binary(&query::bucket(deps, owner, label)?),
+        QueryMsg::BucketByAddress { address } => {
+            to_json_binary(&query::bucket_by_address(deps, address)?)
+        }
+        QueryMsg::Buckets {
+            owner,
+            after,
+            limit,
+        } => to_json_binary(&query::buckets(deps, owner, after, limit)?),
+        QueryMsg::PredictAddress { owner, label } => {
+            to_json_binary(&query::predict_address(deps, env, owner, label)?)
+        }
+    }
+}
+
+pub mod query {
+    use super::*;
+    use crate::msg::{BucketResponse, BucketsResponse, ConfigResponse, PredictAddressResponse};
+    use crate::state;
+    use cosmwasm_std::Order;
+    use cw_storage_plus::Bound;
+
+    /// The number of buckets listed when no limit is given.
+    pub const DEFAULT_LIMIT: u32 = 10;
+    /// The maximum number of buckets that can be listed at once.
+    pub const MAX_LIMIT: u32 = 30;
+
+    pub fn config(deps: Deps<'_>) -> StdResult<ConfigResponse> {
+        Ok(ConfigResponse {
+            bucket_code_id: BUCKET_CODE_ID.load(deps.storage)?.into(),
+        })
+    }
+
+    pub fn bucket(deps: Deps<'_>, owner: String, label: String) -> StdResult<BucketResponse> {
+        let owner = deps.api.addr_validate(&owner)?;
+        state::buckets()
+            .load(deps.storage, (&owner, &label))
+            .map(Into::into)
+    }
+
+    pub fn bucket_by_address(deps: Deps<'_>, address: String) -> StdResult<BucketResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        state::buckets()
+            .idx
+            .address
+            .item(deps.storage, address.clone())?
+            .map(|(_, bucket)| bucket.into())
+            .ok_or_else(|| StdError::not_found(format!("Bucket at {}", address)))
+    }
+
+    pub fn buckets(
+        deps: Deps<'_>,
+        owner: String,
+        after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<BucketsResponse> {
+        let owner = deps.api.addr_validate(&owner)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let buckets = state::buckets()
+            .prefix(&owner)
+            .range(
+                deps.storage,
+                after.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|res| res.map(|(_, bucket)| bucket.into()))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(BucketsResponse { buckets })
+    }
+
+    pub fn predict_address(
+        deps: Deps<'_>,
+        env: Env,
+        owner: String,
+        label: String,
+    ) -> StdResult<PredictAddressResponse> {
+        let owner = deps.api.addr_validate(&owner)?;
+        let code_id = BUCKET_CODE_ID.load(deps.storage)?;
+        let (address, _) = bucket_address(deps, &env, code_id, &owner, &label)?;
+
+        Ok(PredictAddressResponse {
+            address: address.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::{BucketResponse, BucketsResponse, ConfigResponse, PredictAddressResponse};
+    use cosmwasm_std::testing::{message_info, mock_dependencies, MockApi, MockQuerier};
+    use cosmwasm_std::{
+        coins, from_json, Checksum, ContractResult, CosmosMsg, MemoryStorage, OwnedDeps,
+        SystemError, SystemResult, Uint64, WasmMsg, WasmQuery,
+    };
+    use cw_utils::PaymentError::NonPayable;
+    use testing::addr::{addr, CREATOR, OWNER, SENDER};
+    use testing::mock::mock_env_addr;
+
+    fn setup() -> OwnedDeps<MemoryStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::CodeInfo { code_id, .. } => {
+                let resp = CodeInfoResponse::new(
+                    *code_id,
+                    addr(CREATOR),
+                    Checksum::from_hex(
+                        "3B94AAF0B7D804B5B458DED0D20CACF95D2A1C8DF78ED3C89B61291760454AEC",
+                    )
+                    .unwrap(),
+                );
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&resp).unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+        instantiate(
+            deps.as_mut(),
+            mock_env_addr(),
+            message_info(&addr(CREATOR), &[]),
+            InstantiateMsg {
+                bucket_code_id: Uint64::new(17),
+            },
+        )
+        .unwrap();
+
+        deps
+    }
+
+    fn bucket_msg(name: &str) -> axone_objectarium::msg::InstantiateMsg {
+        axone_objectarium::msg::InstantiateMsg {
+            bucket: name.to_string(),
+            config: Default::default(),
+            limits: Default::default(),
+            pagination: Default::default(),
+        }
+    }
+
+    #[test]
+    fn proper_instantiate() {
+        let deps = setup();
+
+        let res = query(deps.as_ref(), mock_env_addr(), QueryMsg::Config {}).unwrap();
+        assert_eq!(
+            from_json::<ConfigResponse>(&res).unwrap(),
+            ConfigResponse {
+                bucket_code_id: Uint64::new(17),
+            }
+        );
+    }
+
+    #[test]
+    fn funds_initialization() {
+        let mut deps = mock_dependencies();
+
+        let result = instantiate(
+            deps.as_mut(),
+            mock_env_addr(),
+            message_info(&addr(CREATOR), &coins(10, "uaxone")),
+            InstantiateMsg {
+                bucket_code_id: Uint64::new(17),
+            },
+        );
+        assert_eq!(result, Err(ContractError::Payment(NonPayable {})));
+    }
+
+    #[test]
+    fn proper_bucket_salt() {
+        let owner = Addr::unchecked("owner");
+
+        assert_eq!(bucket_salt(&owner, "data").len(), 32);
+        assert_ne!(bucket_salt(&owner, "data"), bucket_salt(&owner, "data2"));
+        // the owner length prevents collisions between owner and label boundaries
+        assert_ne!(
+            bucket_salt(&Addr::unchecked("ownerd"), "ata"),
+            bucket_salt(&owner, "data")
+        );
+    }
+
+    #[test]
+    fn create_bucket() {
+        let mut deps = setup();
+        let owner = addr(OWNER);
+
+        let predicted = from_json::<PredictAddressResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env_addr(),
+                QueryMsg::PredictAddress {
+                    owner: owner.to_string(),
+                    label: "data".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .address;
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env_addr(),
+            message_info(&owner, &[]),
+            ExecuteMsg::CreateBucket {
+                label: "data".to_string(),
+                bucket: bucket_msg("my-bucket"),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            res.messages.iter().map(|m| &m.msg).collect::<Vec<_>>(),
+            vec![&CosmosMsg::Wasm(WasmMsg::Instantiate2 {
+                admin: Some(owner.to_string()),
+                code_id: 17,
+                label: format!("{}_data", owner),
+                msg: to_json_binary(&bucket_msg("my-bucket")).unwrap(),
+                funds: vec![],
+                salt: bucket_salt(&owner, "data"),
+            })]
+        );
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "bucket_address" && a.value == predicted));
+
+        let expected = BucketResponse {
+            owner: owner.to_string(),
+            label: "data".to_string(),
+            address: predicted.clone(),
+        };
+        let res = query(
+            deps.as_ref(),
+            mock_env_addr(),
+            QueryMsg::Bucket {
+                owner: owner.to_string(),
+                label: "data".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(from_json::<BucketResponse>(&res).unwrap(), expected);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env_addr(),
+            QueryMsg::BucketByAddress { address: predicted },
+        )
+        .unwrap();
+        assert_eq!(from_json::<BucketResponse>(&res).unwrap(), expected);
+
+        // the same label can be used by another owner, at another address
+        execute(
+            deps.as_mut(),
+            mock_env_addr(),
+            message_info(&addr(SENDER), &[]),
+            ExecuteMsg::CreateBucket {
+                label: "data".to_string(),
+                bucket: bucket_msg("my-bucket"),
+            },
+        )
+        .unwrap();
+        let res = query(
+            deps.as_ref(),
+            mock_env_addr(),
+            QueryMsg::Bucket {
+                owner: addr(SENDER).to_string(),
+                label: "data".to_string(),
+            },
+        )
+        .unwrap();
+        assert_ne!(
+            from_json::<BucketResponse>(&res).unwrap().address,
+            expected.address
+        );
+    }
+
+    #[test]
+    fn create_bucket_errors() {
+        let mut deps = setup();
+        let info = message_info(&addr(OWNER), &[]);
+
+        let cases = vec![
+            ("", ContractError::EmptyLabel),
+            (
+                "data",
+                ContractError::BucketAlreadyExists("data".to_string()),
+            ),
+        ];
+
+        execute(
+            deps.as_mut(),
+            mock_env_addr(),
+            info.clone(),
+            ExecuteMsg::CreateBucket {
+                label: "data".to_string(),
+                bucket: bucket_msg("my-bucket"),
+            },
+        )
+        .unwrap();
+        for (label, expected) in cases {
+            let result = execute(
+                deps.as_mut(),
+                mock_env_addr(),
+                info.clone(),
+                ExecuteMsg::CreateBucket {
+                    label: label.to_string(),
+                    bucket: bucket_msg("my-bucket"),
+                },
+            );
+            assert_eq!(result, Err(expected));
+        }
+
+        let result = execute(
+            deps.as_mut(),
+            mock_env_addr(),
+            message_info(&addr(OWNER), &coins(10, "uaxone")),
+            ExecuteMsg::CreateBucket {
+                label: "other".to_string(),
+                bucket: bucket_msg("my-bucket"),
+            },
+        );
+        assert_eq!(result, Err(ContractError::Payment(NonPayable {})));
+    }
+
+    #[test]
+    fn list_buckets() {
+        let mut deps = setup();
+        let owner = addr(OWNER);
+
+        for label in ["c", "a", "b"] {
+            execute(
+                deps.as_mut(),
+                mock_env_addr(),
+                message_info(&owner, &[]),
+                ExecuteMsg::CreateBucket {
+                    label: label.to_string(),
+                    bucket: bucket_msg(label),
+                },
+            )
+            .unwrap();
+        }
+
+        let cases = vec![
+            (None, None, vec!["a", "b", "c"]),
+            (Some("a"), None, vec!["b", "c"]),
+            (None, Some(2), vec!["a", "b"]),
+            (Some("c"), None, vec![]),
+        ];
+        for (after, limit, expected) in cases {
+            let res = query(
+                deps.as_ref(),
+                mock_env_addr(),
+                QueryMsg::Buckets {
+                    owner: owner.to_string(),
+                    after: after.map(ToString::to_string),
+                    limit,
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                from_json::<BucketsResponse>(&res)
+                    .unwrap()
+                    .buckets
+                    .into_iter()
+                    .map(|b| b.label)
+                    .collect::<Vec<_>>(),
+                expected
+            );
+        }
+
+        let res = query(
+            deps.as_ref(),
+            mock_env_addr(),
+            QueryMsg::Buckets {
+                owner: addr(SENDER).to_string(),
+                after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            from_json::<BucketsResponse>(&res).unwrap(),
+            BucketsResponse { buckets: vec![] }
+        );
+    }
+}
@@ -0,0 +1,18 @@
+use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Bucket label can't be empty")]
+    EmptyLabel,
+
+    #[error("Bucket already exists: {0}")]
+    BucketAlreadyExists(String),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+}
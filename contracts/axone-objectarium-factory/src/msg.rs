@@ -0,0 +1,114 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint64;
+
+/// Instantiate message
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The code id of the `axone-objectarium` contract the buckets are instantiated from.
+    pub bucket_code_id: Uint64,
+}
+
+/// Execute messages
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// # CreateBucket
+    /// Instantiate a new bucket owned by the sender under the given label, at an address derived
+    /// from the sender and the label only (see [QueryMsg::PredictAddress]), and record it.
+    ///
+    /// The sender is set as the admin of the bucket. A sender can't create two buckets under the
+    /// same label.
+    CreateBucket {
+        /// The label of the bucket, unique among the buckets of the sender.
+        label: String,
+        /// The instantiate message of the bucket.
+        bucket: axone_objectarium::msg::InstantiateMsg,
+    },
+}
+
+/// Query messages
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// # Config
+    /// Returns the configuration of the factory.
+    #[returns(ConfigResponse)]
+    Config {},
+
+    /// # Bucket
+    /// Returns the bucket created by the owner under the given label.
+    #[returns(BucketResponse)]
+    Bucket {
+        /// The address of the owner of the bucket.
+        owner: String,
+        /// The label of the bucket.
+        label: String,
+    },
+
+    /// # BucketByAddress
+    /// Returns the bucket instantiated at the given address, allowing to verify it has been
+    /// created by the factory.
+    #[returns(BucketResponse)]
+    BucketByAddress {
+        /// The address of the bucket.
+        address: String,
+    },
+
+    /// # Buckets
+    /// Returns the buckets created by the owner, ordered by label.
+    #[returns(BucketsResponse)]
+    Buckets {
+        /// The address of the owner of the buckets.
+        owner: String,
+        /// The label after which to start listing, excluded.
+        after: Option<String>,
+        /// The maximum number of buckets to return.
+        limit: Option<u32>,
+    },
+
+    /// # PredictAddress
+    /// Returns the address at which the bucket of the owner with the given label is, or would be,
+    /// instantiated.
+    ///
+    /// The address is the `instantiate2` address derived from the checksum of the bucket code, the
+    /// factory address and a salt being the SHA-256 of the big-endian length of the owner address
+    /// as a 4 bytes integer, the owner address and the label.
+    #[returns(PredictAddressResponse)]
+    PredictAddress {
+        /// The address of the owner of the bucket.
+        owner: String,
+        /// The label of the bucket.
+        label: String,
+    },
+}
+
+/// # ConfigResponse
+#[cw_serde]
+pub struct ConfigResponse {
+    /// The code id of the `axone-objectarium` contract the buckets are instantiated from.
+    pub bucket_code_id: Uint64,
+}
+
+/// # BucketResponse
+#[cw_serde]
+pub struct BucketResponse {
+    /// The address of the owner of the bucket.
+    pub owner: String,
+    /// The label of the bucket.
+    pub label: String,
+    /// The address of the bucket.
+    pub address: String,
+}
+
+/// # BucketsResponse
+#[cw_serde]
+pub struct BucketsResponse {
+    /// The buckets, ordered by label.
+    pub buckets: Vec<BucketResponse>,
+}
+
+/// # PredictAddressResponse
+#[cw_serde]
+pub struct PredictAddressResponse {
+    /// The predicted address of the bucket.
+    pub address: String,
+}
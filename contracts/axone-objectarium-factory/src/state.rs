@@ -0,0 +1,46 @@
+use crate::msg::BucketResponse;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, UniqueIndex};
+use serde::{Deserialize, Serialize};
+
+/// The code id of the `axone-objectarium` contract the buckets are instantiated from.
+pub const BUCKET_CODE_ID: Item<u64> = Item::new("bucket_code_id");
+
+/// A bucket created by the factory.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Bucket {
+    pub owner: Addr,
+    pub label: String,
+    pub address: Addr,
+}
+
+impl From<Bucket> for BucketResponse {
+    fn from(value: Bucket) -> Self {
+        Self {
+            owner: value.owner.into(),
+            label: value.label,
+            address: value.address.into(),
+        }
+    }
+}
+
+pub struct BucketIndexes<'a> {
+    pub address: UniqueIndex<'a, Addr, Bucket, (&'a Addr, &'a str)>,
+}
+
+impl IndexList<Bucket> for BucketIndexes<'_> {
+    fn get_indexes(&self) -> Box<dyn Iterator<Item = &'_ dyn Index<Bucket>> + '_> {
+        let address: &dyn Index<Bucket> = &self.address;
+        Box::new(vec![address].into_iter())
+    }
+}
+
+/// The buckets created by the factory, by owner and label, indexed by address.
+pub fn buckets<'a>() -> IndexedMap<(&'a Addr, &'a str), Bucket, BucketIndexes<'a>> {
+    IndexedMap::new(
+        "BUCKET",
+        BucketIndexes {
+            address: UniqueIndex::new(|bucket| bucket.address.clone(), "BUCKET__ADDRESS"),
+        },
+    )
+}
@@ -0,0 +1,96 @@
+use axone_objectarium::msg::{
+    BucketResponse as ObjectariumBucketResponse, QueryMsg as BucketQuery,
+};
+use axone_objectarium_factory::msg::{
+    BucketResponse, ExecuteMsg, InstantiateMsg, PredictAddressResponse, QueryMsg,
+};
+use cosmwasm_std::Uint64;
+use cw_multi_test::{App, ContractWrapper, Executor};
+
+#[test]
+fn buckets_are_instantiated_at_their_predicted_address() {
+    let mut app = App::default();
+    let creator = app.api().addr_make("creator");
+    let owner = app.api().addr_make("owner");
+
+    let bucket_code_id = app.store_code(Box::new(ContractWrapper::new(
+        axone_objectarium::contract::execute,
+        axone_objectarium::contract::instantiate,
+        axone_objectarium::contract::query,
+    )));
+    let factory_code_id = app.store_code(Box::new(ContractWrapper::new(
+        axone_objectarium_factory::contract::execute,
+        axone_objectarium_factory::contract::instantiate,
+        axone_objectarium_factory::contract::query,
+    )));
+    let factory = app
+        .instantiate_contract(
+            factory_code_id,
+            creator,
+            &InstantiateMsg {
+                bucket_code_id: Uint64::new(bucket_code_id),
+            },
+            &[],
+            "factory",
+            None,
+        )
+        .unwrap();
+
+    let PredictAddressResponse { address: predicted } = app
+        .wrap()
+        .query_wasm_smart(
+            &factory,
+            &QueryMsg::PredictAddress {
+                owner: owner.to_string(),
+                label: "data".to_string(),
+            },
+        )
+        .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        factory.clone(),
+        &ExecuteMsg::CreateBucket {
+            label: "data".to_string(),
+            bucket: axone_objectarium::msg::InstantiateMsg {
+                bucket: "my-bucket".to_string(),
+                config: Default::default(),
+                limits: Default::default(),
+                pagination: Default::default(),
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    let bucket: ObjectariumBucketResponse = app
+        .wrap()
+        .query_wasm_smart(&predicted, &BucketQuery::Bucket {})
+        .unwrap();
+    assert_eq!(bucket.name, "my-bucket");
+    assert_eq!(
+        app.wrap()
+            .query_wasm_contract_info(&predicted)
+            .unwrap()
+            .admin,
+        Some(owner.clone())
+    );
+
+    let recorded: BucketResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &factory,
+            &QueryMsg::BucketByAddress {
+                address: predicted.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        recorded,
+        BucketResponse {
+            owner: owner.to_string(),
+            label: "data".to_string(),
+            address: predicted,
+        }
+    );
+}
@@ -40,6 +40,82 @@ impl CompressionAlgorithm {
         };
         decompressor(data)
     }
+
+    /// decompress_range returns the given range of the decompressed data, truncated to its size.
+    /// The data is decompressed in a streaming way, only up to the end of the range.
+    pub fn decompress_range(
+        &self,
+        data: &[u8],
+        offset: usize,
+        length: usize,
+    ) -> Result<Vec<u8>, CompressionError> {
+        let mut writer = RangeWriter::new(offset, length);
+        let res = match self {
+            CompressionAlgorithm::Passthrough => {
+                let start = offset.min(data.len());
+                let end = start.saturating_add(length).min(data.len());
+                return Ok(data[start..end].to_vec());
+            }
+            CompressionAlgorithm::Snappy => {
+                let mut snappy_reader = snap::read::FrameDecoder::new(io::Cursor::new(data));
+                io::copy(&mut snappy_reader, &mut writer)
+                    .map(|_| ())
+                    .map_err(CompressionError::from)
+            }
+            CompressionAlgorithm::Lzma => {
+                lzma_rs::lzma_decompress(&mut io::Cursor::new(data), &mut writer)
+                    .map_err(CompressionError::from)
+            }
+        };
+
+        match res {
+            // the writer interrupts the decompression once the range is complete.
+            Err(_) if writer.is_complete() => Ok(writer.data),
+            res => res.map(|_| writer.data),
+        }
+    }
+}
+
+/// RangeWriter keeps the bytes of a range of the written data, failing once the range is complete
+/// to interrupt the writing.
+struct RangeWriter {
+    skip: usize,
+    remaining: usize,
+    data: Vec<u8>,
+}
+
+impl RangeWriter {
+    fn new(offset: usize, length: usize) -> Self {
+        Self {
+            skip: offset,
+            remaining: length,
+            data: Vec::new(),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+impl io::Write for RangeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.is_complete() {
+            return Err(io::Error::other("range complete"));
+        }
+
+        let skipped = self.skip.min(buf.len());
+        self.skip -= skipped;
+        let taken = self.remaining.min(buf.len() - skipped);
+        self.data.extend_from_slice(&buf[skipped..skipped + taken]);
+        self.remaining -= taken;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
 }
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -111,6 +187,32 @@ fn lzma_decompress(data: &[u8]) -> Result<Vec<u8>, CompressionError> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_decompress_range() {
+        let data = "0123456789".repeat(10_000).into_bytes();
+        let cases = vec![
+            (0, 4, data[..4].to_vec()),
+            (12, 5, data[12..17].to_vec()),
+            (99_990, 20, data[99_990..].to_vec()),
+            (40_000, 30_000, data[40_000..70_000].to_vec()),
+            (100_000, 1, vec![]),
+            (200_000, 1, vec![]),
+            (0, 0, vec![]),
+            (0, usize::MAX, data.clone()),
+        ];
+
+        for algorithm in enum_iterator::all::<CompressionAlgorithm>() {
+            let compressed = algorithm.compress(&data).unwrap();
+            for (offset, length, expected) in &cases {
+                assert_eq!(
+                    algorithm.decompress_range(&compressed, *offset, *length),
+                    Ok(expected.clone()),
+                    "{algorithm:?} {offset}..+{length}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_from_io_decompress_error() {
         let cases = vec![
@@ -1,7 +1,9 @@
 use crate::error::BucketError;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
+};
 use cw2::set_contract_version;
 use cw_utils::nonpayable;
 
@@ -9,12 +11,17 @@ use crate::crypto;
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, ObjectId, QueryMsg};
 use crate::state;
-use crate::state::{objects, pins, Bucket, Object, Pin, BUCKET, DATA};
+use crate::state::{
+    objects, pins, Bucket, Object, Pin, BUCKET, DATA, EXPIRING, FORGOTTEN, PENDING_OBJECT,
+    PIN_TRANSFER_APPROVALS, TRANSFORMER,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const TRANSFORM_OBJECT_REPLY_ID: u64 = 1;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut<'_>,
@@ -40,7 +47,7 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut<'_>,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
@@ -51,47 +58,163 @@ pub fn execute(
             data,
             pin,
             compression_algorithm,
-        } => execute::store_object(deps, info, data, pin, compression_algorithm),
+            expected_digest,
+            reject_duplicate,
+            keep_content,
+        } => execute::store_object(
+            deps,
+            env,
+            info,
+            data,
+            pin,
+            execute::StoreOptions {
+                compression_algorithm,
+                expected_digest,
+                reject_duplicate,
+                keep_content,
+            },
+        ),
         ExecuteMsg::PinObject { id } => execute::pin_object(deps, info, id),
         ExecuteMsg::UnpinObject { id } => execute::unpin_object(deps, info, id),
-        ExecuteMsg::ForgetObject { id } => execute::forget_object(deps, info, id),
+        ExecuteMsg::ApprovePinTransfer { id, from } => {
+            execute::approve_pin_transfer(deps, info, id, from)
+        }
+        ExecuteMsg::TransferPin { id, to } => execute::transfer_pin(deps, info, id, to),
+        ExecuteMsg::ForgetObject { id } => execute::forget_object(deps, env, info, id),
+        ExecuteMsg::RestoreObject { id } => execute::restore_object(deps, env, id),
+        ExecuteMsg::PurgeObjects {} => execute::purge_objects(deps, env),
+        ExecuteMsg::SetTransformer { transformer } => {
+            execute::set_transformer(deps, info, transformer)
+        }
     }
 }
 
 pub mod execute {
     use super::*;
-    use crate::compress::CompressionAlgorithm;
     use crate::crypto::Hash;
     use crate::msg;
-    use crate::state::BucketLimits;
-    use crate::ContractError::ObjectPinned;
-    use cosmwasm_std::{Addr, Order, Storage, Uint128};
+    use crate::state::{BucketLimits, PendingObject, Transformer};
+    use crate::ContractError::{
+        ObjectForgotten, ObjectNotForgotten, ObjectPinned, ObjectRetained, PinNotHeld,
+        PinTransferNotApproved, RecoveryWindowExpired,
+    };
+    use cosmwasm_std::{
+        ensure, Addr, Empty, Event, Order, Storage, SubMsg, Timestamp, Uint128, Uint256, WasmMsg,
+    };
+    use cw_storage_plus::{Map, PrefixBound};
+
+    /// The way an object is stored, see [ExecuteMsg::StoreObject].
+    #[derive(Default)]
+    pub struct StoreOptions {
+        pub compression_algorithm: Option<msg::CompressionAlgorithm>,
+        pub expected_digest: Option<String>,
+        pub reject_duplicate: bool,
+        pub keep_content: bool,
+    }
 
     pub fn store_object(
         deps: DepsMut<'_>,
+        env: Env,
         info: MessageInfo,
         data: Binary,
         pin: bool,
-        compression_algorithm: Option<msg::CompressionAlgorithm>,
+        options: StoreOptions,
+    ) -> Result<Response, ContractError> {
+        // the digest vouches for the submitted content, not for the one a transformer replaces it by
+        if let Some(digest) = &options.expected_digest {
+            let (algorithm, expected) = crypto::parse_digest(digest)?;
+            if crypto::hash(&algorithm, &data.to_vec()) != expected {
+                return Err(ContractError::DigestMismatch(digest.clone()));
+            }
+        }
+
+        let Some(transformer) = TRANSFORMER.may_load(deps.storage)? else {
+            return commit_object(deps, env, info.sender, data, pin, options);
+        };
+        // the transformer storing objects itself would override the one awaiting its reply
+        if PENDING_OBJECT.exists(deps.storage) {
+            return Err(ContractError::TransformationInProgress {});
+        }
+
+        let transform_msg = WasmMsg::Execute {
+            contract_addr: transformer.contract.into(),
+            msg: to_json_binary(&msg::TransformerMsg::TransformObject {
+                data: data.clone(),
+                sender: info.sender.to_string(),
+            })?,
+            funds: vec![],
+        };
+        PENDING_OBJECT.save(
+            deps.storage,
+            &PendingObject {
+                sender: info.sender,
+                data,
+                pin,
+                compression_algorithm: options.compression_algorithm,
+                expected_digest: options.expected_digest,
+                reject_duplicate: options.reject_duplicate,
+                keep_content: options.keep_content,
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "transform_object")
+            .add_submessage(SubMsg::reply_on_success(
+                transform_msg,
+                TRANSFORM_OBJECT_REPLY_ID,
+            )))
+    }
+
+    /// Store the object on behalf of the sender, once transformed if the bucket has a transformer.
+    ///
+    /// The expected digest, already checked against the submitted content, is recorded as is.
+    pub fn commit_object(
+        deps: DepsMut<'_>,
+        env: Env,
+        sender: Addr,
+        data: Binary,
+        pin: bool,
+        StoreOptions {
+            compression_algorithm,
+            expected_digest,
+            reject_duplicate,
+            ..
+        }: StoreOptions,
     ) -> Result<Response, ContractError> {
         let size = (data.len() as u128).into();
         let bucket = BUCKET.load(deps.storage)?;
         let compressions = &bucket.config.accepted_compression_algorithms;
-        let compression: CompressionAlgorithm = compression_algorithm
-            .map(Into::into)
-            .or_else(|| compressions.first().cloned())
-            .unwrap_or(CompressionAlgorithm::Passthrough);
+        let compression = bucket
+            .config
+            .compression_for(size, compression_algorithm.map(Into::into));
+
+        let id = crypto::hash(&bucket.config.hash_algorithm.into(), &data.to_vec());
+        let existing = objects().may_load(deps.storage, id.clone())?;
+        let deduplicated = existing
+            .as_ref()
+            .is_some_and(|object| object.owner == sender);
+        if deduplicated && reject_duplicate {
+            return Err(ContractError::ObjectAlreadyStored(id.to_string()));
+        }
 
         // pre-conditions
-        if let Some(limit) = bucket.limits.max_object_size {
-            if size > limit {
-                return Err(BucketError::MaxObjectSizeLimitExceeded(size, limit).into());
+        if existing.is_none() {
+            if let Some(limit) = bucket.limits.max_object_size {
+                if size > limit {
+                    return Err(BucketError::MaxObjectSizeLimitExceeded(size, limit).into());
+                }
             }
-        }
-        if let Some(limit) = bucket.limits.max_objects {
-            let value = bucket.stat.object_count + Uint128::one();
-            if value > limit {
-                return Err(BucketError::MaxObjectsLimitExceeded(value, limit).into());
+            if let Some(limit) = bucket.limits.max_objects {
+                let value = bucket.stat.object_count + Uint128::one();
+                if value > limit {
+                    return Err(BucketError::MaxObjectsLimitExceeded(value, limit).into());
+                }
+            }
+            if let Some(limit) = bucket.limits.max_total_size {
+                let value = bucket.stat.size + size;
+                if value > limit {
+                    return Err(BucketError::MaxTotalSizeLimitExceeded(value, limit).into());
+                }
             }
         }
         if let Some(limit) = bucket.limits.max_object_pins {
@@ -99,12 +222,6 @@ pub mod execute {
                 return Err(BucketError::MaxObjectPinsLimitExceeded(Uint128::one(), limit).into());
             }
         }
-        if let Some(limit) = bucket.limits.max_total_size {
-            let value = bucket.stat.size + size;
-            if value > limit {
-                return Err(BucketError::MaxTotalSizeLimitExceeded(value, limit).into());
-            }
-        }
         if !compressions.contains(&compression) {
             return Err(BucketError::CompressionAlgorithmNotAccepted(
                 compression.into(),
@@ -119,51 +236,82 @@ pub mod execute {
         }
 
         // store object data
-        let id = crypto::hash(&bucket.config.hash_algorithm.into(), &data.to_vec());
         let mut res = Response::new()
             .add_attribute("action", "store_object")
             .add_attribute("id", id.to_string());
 
-        let data_path = DATA.key(id.clone());
-
-        let (old_obj, mut new_obj) = if !data_path.has(deps.storage) {
+        let (old_obj, mut new_obj) = if let Some(old) = existing {
+            let mut new = old.clone();
+            if new.external_digest.is_none() {
+                new.external_digest = expected_digest;
+            }
+            if let Some(until) = new.recoverable_until.take() {
+                FORGOTTEN.remove(deps.storage, (until.nanos(), id.clone()));
+                res = res.add_attribute("restored", "true");
+            }
+            if deduplicated {
+                res = res.add_attribute("deduplicated", "true");
+            }
+            (Some(old), new)
+        } else {
+            let data_path = DATA.key(id.clone());
             let compressed_data = compression.compress(&data)?;
             data_path.save(deps.storage, &compressed_data)?;
 
             let compressed_size = (compressed_data.len() as u128).into();
 
             // save bucket stats
-            BUCKET.update(deps.storage, |mut bucket| -> Result<_, ContractError> {
-                let stat = &mut bucket.stat;
-                stat.size += size;
-                stat.object_count += Uint128::one();
-                stat.compressed_size += compressed_size;
-                Ok(bucket)
-            })?;
+            let updated =
+                BUCKET.update(deps.storage, |mut bucket| -> Result<_, ContractError> {
+                    let stat = &mut bucket.stat;
+                    stat.size += size;
+                    stat.object_count += Uint128::one();
+                    stat.compressed_size += compressed_size;
+                    Ok(bucket)
+                })?;
 
             res = res
                 .add_attribute("size", size)
-                .add_attribute("compressed_size", compressed_size);
+                .add_attribute("compressed_size", compressed_size)
+                .add_events(usage_alerts(
+                    &bucket.config.usage_alert_thresholds,
+                    "total_size",
+                    bucket.stat.size,
+                    updated.stat.size,
+                    bucket.limits.max_total_size,
+                ))
+                .add_events(usage_alerts(
+                    &bucket.config.usage_alert_thresholds,
+                    "objects",
+                    bucket.stat.object_count,
+                    updated.stat.object_count,
+                    bucket.limits.max_objects,
+                ));
+
+            let stored_at = Some(env.block.time);
+            if let Some(expires_at) = bucket.config.expires_at(stored_at) {
+                EXPIRING.save(deps.storage, (expires_at.nanos(), id.clone()), &Empty {})?;
+            }
 
             (
                 None,
                 Object {
                     id: id.clone(),
-                    owner: info.sender.clone(),
+                    owner: sender.clone(),
                     size,
                     pin_count: Uint128::zero(),
                     compression,
                     compressed_size,
+                    recoverable_until: None,
+                    stored_at,
+                    external_digest: expected_digest,
                 },
             )
-        } else {
-            let old = objects().load(deps.storage, id.clone())?;
-            (Some(old.clone()), old)
         };
 
         let mut pinned = false;
         if pin {
-            pinned = may_pin_object(deps.storage, info.sender, &mut new_obj)?;
+            pinned = may_pin_object(deps.storage, sender, &mut new_obj)?;
         }
 
         objects().replace(deps.storage, id, Some(&new_obj), old_obj.as_ref())?;
@@ -171,6 +319,34 @@ pub mod execute {
         Ok(res.add_attribute("pinned", pinned.to_string()))
     }
 
+    pub fn set_transformer(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        transformer: Option<msg::Transformer>,
+    ) -> Result<Response, ContractError> {
+        let bucket = BUCKET.load(deps.storage)?;
+        ensure!(info.sender == bucket.owner, ContractError::Unauthorized {});
+
+        let res = Response::new().add_attribute("action", "set_transformer");
+        Ok(match transformer {
+            Some(transformer) => {
+                let contract = deps.api.addr_validate(&transformer.contract_addr)?;
+                TRANSFORMER.save(
+                    deps.storage,
+                    &Transformer {
+                        contract: contract.clone(),
+                        allow_replace: transformer.allow_replace,
+                    },
+                )?;
+                res.add_attribute("transformer", contract)
+            }
+            None => {
+                TRANSFORMER.remove(deps.storage);
+                res
+            }
+        })
+    }
+
     pub fn pin_object(
         deps: DepsMut<'_>,
         info: MessageInfo,
@@ -182,6 +358,7 @@ pub mod execute {
 
         let id: Hash = object_id.try_into()?;
         let object = objects().load(deps.storage, id.clone())?;
+        ensure!(object.recoverable_until.is_none(), ObjectForgotten {});
         let mut updated_object = object.clone();
 
         if may_pin_object(deps.storage, info.sender, &mut updated_object)? {
@@ -216,8 +393,74 @@ pub mod execute {
         Ok(res)
     }
 
+    pub fn approve_pin_transfer(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        object_id: ObjectId,
+        from: String,
+    ) -> Result<Response, ContractError> {
+        let res = Response::new()
+            .add_attribute("action", "approve_pin_transfer")
+            .add_attribute("id", object_id.clone())
+            .add_attribute("from", from.clone());
+
+        let id: Hash = object_id.try_into()?;
+        objects().load(deps.storage, id.clone())?;
+        let from = deps.api.addr_validate(&from)?;
+
+        PIN_TRANSFER_APPROVALS.save(deps.storage, (id, &from, &info.sender), &Empty {})?;
+
+        Ok(res)
+    }
+
+    pub fn transfer_pin(
+        deps: DepsMut<'_>,
+        info: MessageInfo,
+        object_id: ObjectId,
+        to: String,
+    ) -> Result<Response, ContractError> {
+        let res = Response::new()
+            .add_attribute("action", "transfer_pin")
+            .add_attribute("id", object_id.clone())
+            .add_attribute("to", to.clone());
+
+        let id: Hash = object_id.try_into()?;
+        let to = deps.api.addr_validate(&to)?;
+        ensure!(
+            pins().has(deps.storage, (id.clone(), info.sender.clone())),
+            PinNotHeld {}
+        );
+        if to == info.sender {
+            return Ok(res);
+        }
+
+        let approval = (id.clone(), &info.sender, &to);
+        ensure!(
+            PIN_TRANSFER_APPROVALS.has(deps.storage, approval.clone()),
+            PinTransferNotApproved {}
+        );
+        PIN_TRANSFER_APPROVALS.remove(deps.storage, approval);
+
+        pins().remove(deps.storage, (id.clone(), info.sender))?;
+        if pins().has(deps.storage, (id.clone(), to.clone())) {
+            let object_path = objects().key(id);
+            let mut object = object_path.load(deps.storage)?;
+            object.pin_count -= Uint128::one();
+            object_path.save(deps.storage, &object)?;
+        } else {
+            pins().save(
+                deps.storage,
+                (id.clone(), to.clone()),
+                &Pin { id, address: to },
+            )?;
+        }
+
+        Ok(res)
+    }
+
     pub fn forget_object(
         deps: DepsMut<'_>,
+        env: Env,
         info: MessageInfo,
         object_id: ObjectId,
     ) -> Result<Response, ContractError> {
@@ -236,20 +479,179 @@ pub mod execute {
         {
             return Err(ObjectPinned {});
         }
-        let object = query::object(deps.as_ref(), object_id.clone())?;
-        BUCKET.update(deps.storage, |mut b| -> Result<_, ContractError> {
+        let object = objects().load(deps.storage, id.clone())?;
+        ensure!(object.recoverable_until.is_none(), ObjectForgotten {});
+
+        let config = BUCKET.load(deps.storage)?.config;
+        if let Some(until) = config.retained_until(object.stored_at) {
+            ensure!(env.block.time >= until, ObjectRetained { until });
+        }
+
+        let res = Response::new()
+            .add_attribute("action", "forget_object")
+            .add_attribute("id", object_id);
+
+        match config.recovery_window {
+            Some(window) => {
+                let until = env.block.time.plus_seconds(window.u64());
+                objects().save(
+                    deps.storage,
+                    id.clone(),
+                    &Object {
+                        recoverable_until: Some(until),
+                        ..object
+                    },
+                )?;
+                FORGOTTEN.save(deps.storage, (until.nanos(), id), &Empty {})?;
+
+                Ok(res.add_attribute("recoverable_until", until.to_string()))
+            }
+            None => {
+                purge_object(deps.storage, &object)?;
+                Ok(res)
+            }
+        }
+    }
+
+    pub fn restore_object(
+        deps: DepsMut<'_>,
+        env: Env,
+        object_id: ObjectId,
+    ) -> Result<Response, ContractError> {
+        let id: Hash = object_id.clone().try_into()?;
+        let object = objects().load(deps.storage, id.clone())?;
+        let until = object.recoverable_until.ok_or(ObjectNotForgotten {})?;
+        ensure!(env.block.time <= until, RecoveryWindowExpired {});
+
+        objects().save(
+            deps.storage,
+            id.clone(),
+            &Object {
+                recoverable_until: None,
+                ..object
+            },
+        )?;
+        FORGOTTEN.remove(deps.storage, (until.nanos(), id));
+
+        Ok(Response::new()
+            .add_attribute("action", "restore_object")
+            .add_attribute("id", object_id))
+    }
+
+    pub fn purge_objects(deps: DepsMut<'_>, env: Env) -> Result<Response, ContractError> {
+        let bucket = BUCKET.load(deps.storage)?;
+        let limit = bucket.pagination.max_page_size as usize;
+        let forgotten = elapsed(deps.storage, &FORGOTTEN, env.block.time, limit)?;
+        let expired = elapsed(
+            deps.storage,
+            &EXPIRING,
+            env.block.time,
+            limit - forgotten.len(),
+        )?;
+
+        let mut purged_count = 0;
+        for id in forgotten.into_iter().chain(expired) {
+            // an object can be both forgotten and expired
+            if let Some(object) = objects().may_load(deps.storage, id)? {
+                purge_object(deps.storage, &object)?;
+                purged_count += 1;
+            }
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "purge_objects")
+            .add_attribute("purged_count", purged_count.to_string()))
+    }
+
+    /// Returns up to `limit` of the objects of the index, keyed by time, whose time has elapsed.
+    fn elapsed(
+        storage: &dyn Storage,
+        index: &Map<(u64, Hash), Empty>,
+        time: Timestamp,
+        limit: usize,
+    ) -> StdResult<Vec<Hash>> {
+        index
+            .prefix_range(
+                storage,
+                None,
+                Some(PrefixBound::exclusive(time.nanos())),
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| item.map(|((_, id), _)| id))
+            .collect()
+    }
+
+    /// Permanently remove the object and its content from storage, releasing its usage of the bucket
+    /// along with the pins and the pin transfer approvals left on it.
+    fn purge_object(storage: &mut dyn Storage, object: &Object) -> Result<(), ContractError> {
+        let bucket = BUCKET.update(storage, |mut b| -> Result<_, ContractError> {
             b.stat.object_count -= Uint128::one();
             b.stat.size -= object.size;
             b.stat.compressed_size -= object.compressed_size;
             Ok(b)
         })?;
 
-        objects().remove(deps.storage, id.clone())?;
-        DATA.remove(deps.storage, id);
+        let id = object.id.clone();
+        if let Some(until) = object.recoverable_until {
+            FORGOTTEN.remove(storage, (until.nanos(), id.clone()));
+        }
+        if let Some(expires_at) = bucket.config.expires_at(object.stored_at) {
+            EXPIRING.remove(storage, (expires_at.nanos(), id.clone()));
+        }
 
-        Ok(Response::new()
-            .add_attribute("action", "forget_object")
-            .add_attribute("id", object_id))
+        let pinned = pins()
+            .idx
+            .object
+            .prefix(id.clone())
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for key in pinned {
+            pins().remove(storage, key)?;
+        }
+        let approvals = PIN_TRANSFER_APPROVALS
+            .sub_prefix(id.clone())
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (from, to) in approvals {
+            PIN_TRANSFER_APPROVALS.remove(storage, (id.clone(), &from, &to));
+        }
+
+        objects().remove(storage, id.clone())?;
+        DATA.remove(storage, id);
+
+        Ok(())
+    }
+
+    /// Build the usage alert events for the thresholds, in percent of the given limit, crossed by the
+    /// usage of a resource going from `old` to `new`.
+    fn usage_alerts(
+        thresholds: &[u8],
+        resource: &str,
+        old: Uint128,
+        new: Uint128,
+        limit: Option<Uint128>,
+    ) -> Vec<Event> {
+        let Some(limit) = limit else {
+            return vec![];
+        };
+        let hundred = Uint256::from(100u8);
+        let (old, new) = (Uint256::from(old) * hundred, Uint256::from(new) * hundred);
+
+        thresholds
+            .iter()
+            .filter(|threshold| {
+                let level = Uint256::from(limit) * Uint256::from(**threshold);
+                old < level && level <= new
+            })
+            .map(|threshold| {
+                Event::new("bucket_usage_alert")
+                    .add_attribute("resource", resource)
+                    .add_attribute("threshold", threshold.to_string())
+                    .add_attribute("usage", new / hundred)
+                    .add_attribute("limit", limit)
+            })
+            .collect()
     }
 
     fn may_pin_object(
@@ -287,12 +689,84 @@ pub mod execute {
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut<'_>, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        TRANSFORM_OBJECT_REPLY_ID => reply::transform_object_reply(deps, env, msg),
+        _ => Err(ContractError::UnknownReplyID {}),
+    }
+}
+
+pub mod reply {
+    use super::*;
+    use crate::msg::TransformObjectResponse;
+    use cosmwasm_std::from_json;
+    use cw_utils::{parse_execute_response_data, ParseReplyError};
+
+    /// Store the object awaiting the reply of the transformer, in place of its submitted content
+    /// the one returned by the transformer, if any, allowed and not opted out of by the sender.
+    pub fn transform_object_reply(
+        deps: DepsMut<'_>,
+        env: Env,
+        msg: Reply,
+    ) -> Result<Response, ContractError> {
+        let pending = PENDING_OBJECT.load(deps.storage)?;
+        PENDING_OBJECT.remove(deps.storage);
+
+        let response = msg
+            .result
+            .into_result()
+            .map_err(ParseReplyError::SubMsgFailure)?;
+        let transformed = match response.msg_responses.first() {
+            Some(response) => parse_execute_response_data(&response.value)?.data,
+            None => None,
+        }
+        .map(|data| from_json::<TransformObjectResponse>(&data))
+        .transpose()?
+        .and_then(|response| response.data)
+        .filter(|data| !pending.keep_content && *data != pending.data);
+
+        let replaced = transformed.is_some();
+        let data = match transformed {
+            Some(data) => {
+                let allowed = TRANSFORMER
+                    .may_load(deps.storage)?
+                    .is_some_and(|transformer| transformer.allow_replace);
+                if !allowed {
+                    return Err(ContractError::ReplacementNotAllowed {});
+                }
+                data
+            }
+            None => pending.data,
+        };
+
+        let res = execute::commit_object(
+            deps,
+            env,
+            pending.sender,
+            data,
+            pending.pin,
+            execute::StoreOptions {
+                compression_algorithm: pending.compression_algorithm,
+                // the digest of the submitted content doesn't describe the replacing one
+                expected_digest: pending.expected_digest.filter(|_| !replaced),
+                reject_duplicate: pending.reject_duplicate,
+                keep_content: pending.keep_content,
+            },
+        )?;
+        Ok(res.add_attribute("transformed", replaced.to_string()))
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps<'_>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Bucket {} => to_json_binary(&query::bucket(deps)?),
         QueryMsg::Object { id } => to_json_binary(&query::object(deps, id)?),
         QueryMsg::ObjectData { id } => to_json_binary(&query::data(deps, id)?),
+        QueryMsg::ObjectDataRange { id, offset, length } => {
+            to_json_binary(&query::data_range(deps, id, offset, length)?)
+        }
         QueryMsg::Objects {
             address,
             after,
@@ -312,7 +786,7 @@ pub mod query {
         BucketResponse, Cursor, ObjectPinsResponse, ObjectResponse, ObjectsResponse, PageInfo,
     };
     use crate::pagination::{PaginationHandler, QueryPage};
-    use cosmwasm_std::{Addr, Order, StdError};
+    use cosmwasm_std::{Addr, Order, StdError, Uint128};
 
     pub fn bucket(deps: Deps<'_>) -> StdResult<BucketResponse> {
         let bucket = BUCKET.load(deps.storage)?;
@@ -323,6 +797,7 @@ pub mod query {
             limits: bucket.limits.into(),
             pagination: bucket.pagination.into(),
             stat: bucket.stat.into(),
+            transformer: TRANSFORMER.may_load(deps.storage)?.map(Into::into),
         })
     }
 
@@ -343,6 +818,26 @@ pub mod query {
             .map(Binary::from)
     }
 
+    pub fn data_range(
+        deps: Deps<'_>,
+        object_id: ObjectId,
+        offset: Uint128,
+        length: Uint128,
+    ) -> StdResult<Binary> {
+        let id: Hash = object_id.try_into()?;
+        let compression = objects().load(deps.storage, id.clone())?.compression;
+        let data = DATA.load(deps.storage, id)?;
+
+        compression
+            .decompress_range(
+                &data,
+                usize::try_from(offset.u128()).unwrap_or(usize::MAX),
+                usize::try_from(length.u128()).unwrap_or(usize::MAX),
+            )
+            .map_err(|e| StdError::serialize_err(format!("{:?}", compression), e))
+            .map(Binary::from)
+    }
+
     pub fn fetch_objects(
         deps: Deps<'_>,
         address: Option<String>,
@@ -437,6 +932,7 @@ mod tests {
     use crate::compress;
     use crate::crypto::Hash;
     use crate::error::BucketError;
+    use crate::msg;
     use crate::msg::{
         BucketConfig, BucketConfigBuilder, BucketLimitsBuilder, BucketResponse, BucketStat,
         BucketStatBuilder, CompressionAlgorithm, HashAlgorithm, ObjectPinsResponse, ObjectResponse,
@@ -445,7 +941,10 @@ mod tests {
     use base64::{engine::general_purpose, Engine as _};
     use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
     use cosmwasm_std::StdError::NotFound;
-    use cosmwasm_std::{coins, from_json, Addr, Attribute, Order, StdError, Uint128};
+    use cosmwasm_std::{
+        coins, from_json, Addr, Attribute, MsgResponse, Order, StdError, Storage, SubMsg,
+        SubMsgResponse, SubMsgResult, Uint128, Uint64, WasmMsg,
+    };
     use cw_utils::PaymentError;
 
     use crate::msg::CompressionAlgorithm::{Passthrough, Snappy};
@@ -735,6 +1234,81 @@ mod tests {
                     "'accepted_compression_algorithms' cannot be empty",
                 )),
             ),
+            (
+                BucketConfigBuilder::default()
+                    .accepted_compression_algorithms(vec![
+                        CompressionAlgorithm::Passthrough,
+                        CompressionAlgorithm::Snappy,
+                    ])
+                    .compression_threshold(100u128)
+                    .build()
+                    .unwrap(),
+                Default::default(),
+                Default::default(),
+                None,
+            ),
+            (
+                BucketConfigBuilder::default()
+                    .accepted_compression_algorithms(vec![CompressionAlgorithm::Snappy])
+                    .compression_threshold(100u128)
+                    .build()
+                    .unwrap(),
+                Default::default(),
+                Default::default(),
+                Some(StdError::generic_err(
+                    "'compression_threshold' requires both Passthrough and a compressing algorithm to be accepted",
+                )),
+            ),
+            (
+                BucketConfigBuilder::default()
+                    .usage_alert_thresholds(vec![80u8, 101u8])
+                    .build()
+                    .unwrap(),
+                Default::default(),
+                Default::default(),
+                Some(StdError::generic_err(
+                    "'usage_alert_thresholds' must be between 1 and 100",
+                )),
+            ),
+            (
+                BucketConfigBuilder::default()
+                    .recovery_window(0u64)
+                    .build()
+                    .unwrap(),
+                Default::default(),
+                Default::default(),
+                Some(StdError::generic_err("'recovery_window' cannot be zero")),
+            ),
+            (
+                BucketConfigBuilder::default()
+                    .min_retention(0u64)
+                    .build()
+                    .unwrap(),
+                Default::default(),
+                Default::default(),
+                Some(StdError::generic_err("'min_retention' cannot be zero")),
+            ),
+            (
+                BucketConfigBuilder::default()
+                    .max_retention(0u64)
+                    .build()
+                    .unwrap(),
+                Default::default(),
+                Default::default(),
+                Some(StdError::generic_err("'max_retention' cannot be zero")),
+            ),
+            (
+                BucketConfigBuilder::default()
+                    .min_retention(60u64)
+                    .max_retention(59u64)
+                    .build()
+                    .unwrap(),
+                Default::default(),
+                Default::default(),
+                Some(StdError::generic_err(
+                    "'max_retention' cannot be less than 'min_retention'",
+                )),
+            ),
             (
                 Default::default(),
                 Default::default(),
@@ -832,6 +1406,9 @@ mod tests {
                 data: Binary::from("data".as_bytes()),
                 pin: false,
                 compression_algorithm: None,
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             },
             ExecuteMsg::PinObject {
                 id: "object_id".to_string(),
@@ -839,9 +1416,21 @@ mod tests {
             ExecuteMsg::UnpinObject {
                 id: "object_id".to_string(),
             },
+            ExecuteMsg::ApprovePinTransfer {
+                id: "object_id".to_string(),
+                from: addr(CREATOR).to_string(),
+            },
+            ExecuteMsg::TransferPin {
+                id: "object_id".to_string(),
+                to: addr(CREATOR).to_string(),
+            },
             ExecuteMsg::ForgetObject {
                 id: "object_id".to_string(),
             },
+            ExecuteMsg::RestoreObject {
+                id: "object_id".to_string(),
+            },
+            ExecuteMsg::PurgeObjects {},
         ];
 
         for msg in messages {
@@ -1062,6 +1651,9 @@ mod tests {
                     data: Binary::from_base64(content).unwrap(),
                     pin: *pin,
                     compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                    expected_digest: None,
+                    reject_duplicate: false,
+                    keep_content: false,
                 };
                 let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
                 assert_eq!(res.attributes, *expected_attr);
@@ -1143,6 +1735,9 @@ mod tests {
                 data: Binary::from_base64(object.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             },
         )
         .unwrap();
@@ -1155,6 +1750,9 @@ mod tests {
                 data: Binary::from_base64(object.as_str()).unwrap(),
                 pin: true,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             },
         );
 
@@ -1181,15 +1779,139 @@ mod tests {
     }
 
     #[test]
-    fn store_object_limits() {
-        let cases = vec![
-            (
-                BucketLimitsBuilder::default()
-                    .max_objects(2u128)
-                    .build()
-                    .unwrap(),
-                None,
-            ),
+    fn store_object_deduplicated() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            bucket: String::from("test"),
+            config: Default::default(),
+            limits: BucketLimitsBuilder::default()
+                .max_objects(1u128)
+                .max_total_size(5u128)
+                .build()
+                .unwrap(),
+            pagination: Default::default(),
+        };
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&addr(CREATOR), &[]),
+            msg,
+        )
+        .unwrap();
+
+        let store = |deps: DepsMut<'_>, sender: &str, reject_duplicate: bool| {
+            execute(
+                deps,
+                mock_env(),
+                message_info(&addr(sender), &[]),
+                ExecuteMsg::StoreObject {
+                    data: Binary::from("hello".as_bytes()),
+                    pin: false,
+                    compression_algorithm: None,
+                    expected_digest: None,
+                    reject_duplicate,
+                    keep_content: false,
+                },
+            )
+        };
+        let id = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        let deduplicated = Attribute::new("deduplicated", "true");
+
+        let res = store(deps.as_mut(), CREATOR, false).unwrap();
+        assert!(!res.attributes.contains(&deduplicated));
+
+        // the bucket is full, but storing the same content again doesn't change its usage
+        let res = store(deps.as_mut(), CREATOR, false).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "store_object"),
+                Attribute::new("id", id),
+                Attribute::new("deduplicated", "true"),
+                Attribute::new("pinned", "false"),
+            ]
+        );
+        assert_eq!(
+            store(deps.as_mut(), CREATOR, true).unwrap_err(),
+            ContractError::ObjectAlreadyStored(id.to_string())
+        );
+
+        // the content isn't a duplicate for another sender
+        let res = store(deps.as_mut(), SENDER, true).unwrap();
+        assert!(!res.attributes.contains(&deduplicated));
+
+        let bucket = BUCKET.load(&deps.storage).unwrap();
+        assert_eq!(bucket.stat.object_count, Uint128::one());
+        assert_eq!(bucket.stat.size, Uint128::new(5));
+    }
+
+    #[test]
+    fn store_object_expected_digest() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(CREATOR), &[]);
+        let msg = InstantiateMsg {
+            bucket: String::from("test"),
+            config: Default::default(),
+            limits: Default::default(),
+            pagination: Default::default(),
+        };
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let store = |deps: DepsMut<'_>, data: &str, digest: &str| {
+            execute(
+                deps,
+                mock_env(),
+                info.clone(),
+                ExecuteMsg::StoreObject {
+                    data: Binary::from(data.as_bytes()),
+                    pin: false,
+                    compression_algorithm: None,
+                    expected_digest: Some(digest.to_string()),
+                    reject_duplicate: false,
+                    keep_content: false,
+                },
+            )
+        };
+        let sri = "sha256-LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=";
+        let md5_multihash = "fd501105d41402abc4b2a76b9719d911017c592";
+
+        assert_eq!(
+            store(deps.as_mut(), "hallo", sri).unwrap_err(),
+            ContractError::DigestMismatch(sri.to_string())
+        );
+        assert_eq!(
+            store(deps.as_mut(), "hello", "sha256-%%").unwrap_err(),
+            ContractError::Std(StdError::generic_err("Invalid digest: sha256-%%"))
+        );
+        assert!(store(deps.as_mut(), "hello", md5_multihash).is_ok());
+        // the digest first recorded is kept
+        assert!(store(deps.as_mut(), "hello", sri).is_ok());
+
+        let object: ObjectResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Object {
+                    id: "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+                        .to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(object.external_digest, Some(md5_multihash.to_string()));
+    }
+
+    #[test]
+    fn store_object_limits() {
+        let cases = vec![
+            (
+                BucketLimitsBuilder::default()
+                    .max_objects(2u128)
+                    .build()
+                    .unwrap(),
+                None,
+            ),
             (
                 BucketLimitsBuilder::default()
                     .max_object_size(5u128)
@@ -1268,12 +1990,18 @@ mod tests {
                 data: Binary::from_base64(obj1.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             };
             execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
             let msg = ExecuteMsg::StoreObject {
                 data: Binary::from_base64(obj2.as_str()).unwrap(),
                 pin: true,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             };
             let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
 
@@ -1281,6 +2009,78 @@ mod tests {
         }
     }
 
+    #[test]
+    fn store_object_usage_alerts() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(CREATOR), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                bucket: String::from("test"),
+                config: BucketConfigBuilder::default()
+                    .usage_alert_thresholds(vec![50u8, 80u8, 95u8])
+                    .build()
+                    .unwrap(),
+                limits: BucketLimitsBuilder::default()
+                    .max_total_size(10u128)
+                    .max_objects(2u128)
+                    .build()
+                    .unwrap(),
+                pagination: Default::default(),
+            },
+        )
+        .unwrap();
+
+        let alerts = |res: Response| {
+            res.events
+                .into_iter()
+                .map(|event| {
+                    assert_eq!(event.ty, "bucket_usage_alert");
+                    event
+                        .attributes
+                        .into_iter()
+                        .map(|attr| attr.value)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let cases = vec![
+            ("okp4", vec![vec!["objects", "50", "1", "2"]]),
+            ("okp4", vec![]),
+            (
+                "hello",
+                vec![
+                    vec!["total_size", "50", "9", "10"],
+                    vec!["total_size", "80", "9", "10"],
+                    vec!["objects", "80", "2", "2"],
+                    vec!["objects", "95", "2", "2"],
+                ],
+            ),
+        ];
+
+        for (data, expected) in cases {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ExecuteMsg::StoreObject {
+                    data: Binary::from(data.as_bytes()),
+                    pin: false,
+                    compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                    expected_digest: None,
+                    reject_duplicate: false,
+                    keep_content: false,
+                },
+            )
+            .unwrap();
+
+            assert_eq!(alerts(res), expected);
+        }
+    }
+
     #[test]
     fn store_object_compressed() {
         use either::Either;
@@ -1291,6 +2091,7 @@ mod tests {
         }
         struct TC {
             accepted_compression_algorithms: Vec<CompressionAlgorithm>,
+            compression_threshold: Option<Uint128>,
             compression_algorithm: Option<CompressionAlgorithm>,
             expected_result: Either<ContractError, ExpectedCompressionResult>,
         }
@@ -1302,6 +2103,7 @@ mod tests {
                     CompressionAlgorithm::Snappy,
                     CompressionAlgorithm::Lzma,
                 ],
+                compression_threshold: None,
                 compression_algorithm: None,
                 expected_result: Either::Right(ExpectedCompressionResult {
                     compression_algorithm: CompressionAlgorithm::Passthrough,
@@ -1314,6 +2116,7 @@ mod tests {
                     CompressionAlgorithm::Snappy,
                     CompressionAlgorithm::Lzma,
                 ],
+                compression_threshold: None,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
                 expected_result: Either::Right(ExpectedCompressionResult {
                     compression_algorithm: CompressionAlgorithm::Passthrough,
@@ -1326,6 +2129,7 @@ mod tests {
                     CompressionAlgorithm::Snappy,
                     CompressionAlgorithm::Lzma,
                 ],
+                compression_threshold: None,
                 compression_algorithm: Some(CompressionAlgorithm::Snappy),
                 expected_result: Either::Right(ExpectedCompressionResult {
                     compression_algorithm: CompressionAlgorithm::Snappy,
@@ -1338,6 +2142,7 @@ mod tests {
                     CompressionAlgorithm::Snappy,
                     CompressionAlgorithm::Lzma,
                 ],
+                compression_threshold: None,
                 compression_algorithm: Some(CompressionAlgorithm::Lzma),
                 expected_result: Either::Right(ExpectedCompressionResult {
                     compression_algorithm: CompressionAlgorithm::Lzma,
@@ -1346,6 +2151,7 @@ mod tests {
             },
             TC {
                 accepted_compression_algorithms: vec![CompressionAlgorithm::Passthrough],
+                compression_threshold: None,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
                 expected_result: Either::Right(ExpectedCompressionResult {
                     compression_algorithm: CompressionAlgorithm::Passthrough,
@@ -1354,6 +2160,7 @@ mod tests {
             },
             TC {
                 accepted_compression_algorithms: vec![CompressionAlgorithm::Snappy],
+                compression_threshold: None,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
                 expected_result: Either::Left(ContractError::Bucket(
                     BucketError::CompressionAlgorithmNotAccepted(
@@ -1362,6 +2169,42 @@ mod tests {
                     ),
                 )),
             },
+            TC {
+                accepted_compression_algorithms: vec![
+                    CompressionAlgorithm::Passthrough,
+                    CompressionAlgorithm::Lzma,
+                ],
+                compression_threshold: Some(500u128.into()),
+                compression_algorithm: None,
+                expected_result: Either::Right(ExpectedCompressionResult {
+                    compression_algorithm: CompressionAlgorithm::Passthrough,
+                    compressed_size: 466,
+                }),
+            },
+            TC {
+                accepted_compression_algorithms: vec![
+                    CompressionAlgorithm::Passthrough,
+                    CompressionAlgorithm::Lzma,
+                ],
+                compression_threshold: Some(100u128.into()),
+                compression_algorithm: None,
+                expected_result: Either::Right(ExpectedCompressionResult {
+                    compression_algorithm: CompressionAlgorithm::Lzma,
+                    compressed_size: 344,
+                }),
+            },
+            TC {
+                accepted_compression_algorithms: vec![
+                    CompressionAlgorithm::Passthrough,
+                    CompressionAlgorithm::Lzma,
+                ],
+                compression_threshold: Some(100u128.into()),
+                compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_result: Either::Right(ExpectedCompressionResult {
+                    compression_algorithm: CompressionAlgorithm::Passthrough,
+                    compressed_size: 466,
+                }),
+            },
         ];
         let data ="In a magical land,  there were many realms, one of which was known as OKP4. Within \
             this realm, druid programmers possessed the power to create smart contracts. As the kingdom \
@@ -1380,6 +2223,7 @@ mod tests {
                 bucket: String::from("test"),
                 config: BucketConfig {
                     accepted_compression_algorithms: case.accepted_compression_algorithms,
+                    compression_threshold: case.compression_threshold,
                     ..Default::default()
                 },
                 limits: Default::default(),
@@ -1396,6 +2240,9 @@ mod tests {
                     data: Binary::from_base64(obj.as_str()).unwrap(),
                     pin: false,
                     compression_algorithm: case.compression_algorithm,
+                    expected_digest: None,
+                    reject_duplicate: false,
+                    keep_content: false,
                 },
             );
 
@@ -1416,6 +2263,8 @@ mod tests {
                             size: Uint128::from(data.len() as u128),
                             compressed_size: expected.compressed_size.into(),
                             compression_algorithm: expected.compression_algorithm,
+                            recoverable_until: None,
+                            external_digest: None,
                         }
                     );
                     assert_eq!(res_object_data, data.as_bytes().to_vec());
@@ -1491,6 +2340,7 @@ mod tests {
                         "45a8243ff863a08531c666569ce9997b63df94c2e2aeedaed3d32656ee1ae622"
                             .to_string(),
                     ),
+                    Attribute::new("deduplicated", "true"),
                     Attribute::new("pinned", "true"),
                 ],
             ),
@@ -1505,6 +2355,7 @@ mod tests {
                         "45a8243ff863a08531c666569ce9997b63df94c2e2aeedaed3d32656ee1ae622"
                             .to_string(),
                     ),
+                    Attribute::new("deduplicated", "true"),
                     Attribute::new("pinned", "false"),
                 ],
             ),
@@ -1519,6 +2370,7 @@ mod tests {
                         "2ea88c7a30351b12a4dcfc06cdce2af6eab18416176466c2500cb6ef74f745bf"
                             .to_string(),
                     ),
+                    Attribute::new("deduplicated", "true"),
                     Attribute::new("pinned", "false"),
                 ],
             ),
@@ -1533,6 +2385,7 @@ mod tests {
                         "2ea88c7a30351b12a4dcfc06cdce2af6eab18416176466c2500cb6ef74f745bf"
                             .to_string(),
                     ),
+                    Attribute::new("deduplicated", "true"),
                     Attribute::new("pinned", "false"),
                 ],
             ),
@@ -1557,6 +2410,9 @@ mod tests {
                     data: Binary::from_base64(obj_exist_content).unwrap(),
                     pin: false,
                     compression_algorithm: Some(Passthrough),
+                    expected_digest: None,
+                    reject_duplicate: false,
+                    keep_content: false,
                 },
             );
 
@@ -1568,6 +2424,9 @@ mod tests {
                     data: Binary::from_base64(obj_exist_pinned_content).unwrap(),
                     pin: true,
                     compression_algorithm: Some(Passthrough),
+                    expected_digest: None,
+                    reject_duplicate: false,
+                    keep_content: false,
                 },
             );
 
@@ -1575,6 +2434,9 @@ mod tests {
                 data: Binary::from_base64(content).unwrap(),
                 pin: *pin,
                 compression_algorithm: Some(*compression_algorithm),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             };
 
             let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
@@ -1614,6 +2476,9 @@ mod tests {
             data: Binary::from_base64(data.as_str()).unwrap(),
             pin: true,
             compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            expected_digest: None,
+            reject_duplicate: false,
+            keep_content: false,
         };
         execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1635,6 +2500,9 @@ mod tests {
             data: Binary::from_base64(data.as_str()).unwrap(),
             pin: false,
             compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            expected_digest: None,
+            reject_duplicate: false,
+            keep_content: false,
         };
         execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1703,6 +2571,9 @@ mod tests {
                 data: data.clone(),
                 pin: false,
                 compression_algorithm: case.compression_algorithm,
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             };
             execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1714,6 +2585,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn object_data_range() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(CREATOR), &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg {
+                bucket: String::from("test"),
+                config: Default::default(),
+                limits: Default::default(),
+                pagination: Default::default(),
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::StoreObject {
+                data: Binary::from("okp4".as_bytes()),
+                pin: false,
+                compression_algorithm: Some(CompressionAlgorithm::Snappy),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
+            },
+        )
+        .unwrap();
+
+        let cases = vec![
+            (0u128, 4u128, "okp4"),
+            (1, 2, "kp"),
+            (2, 10, "p4"),
+            (4, 1, ""),
+            (10, 1, ""),
+            (0, u128::MAX, "okp4"),
+        ];
+        for (offset, length, expected) in cases {
+            let result = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ObjectDataRange {
+                    id: "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6"
+                        .to_string(),
+                    offset: offset.into(),
+                    length: length.into(),
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                from_json::<Binary>(&result).unwrap(),
+                Binary::from(expected.as_bytes()),
+                "{offset}..+{length}"
+            );
+        }
+    }
+
     #[test]
     fn object_data_error() {
         let mut deps = mock_dependencies();
@@ -1727,6 +2657,9 @@ mod tests {
             pin_count: Uint128::one(),
             compression: compress::CompressionAlgorithm::Lzma,
             compressed_size: Uint128::from(data.len() as u128),
+            recoverable_until: None,
+            stored_at: None,
+            external_digest: None,
         };
 
         objects()
@@ -2021,6 +2954,9 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -2029,6 +2965,9 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -2037,6 +2976,9 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -2294,6 +3236,9 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -2302,6 +3247,9 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -2310,6 +3258,9 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -2364,6 +3315,117 @@ mod tests {
         }
     }
 
+    #[test]
+    fn transfer_pin() {
+        let id = "315d0d9ab12c5f8884100055f79de50b72db4bd2c9bfd3df049d89640fed1fa6".to_string();
+        let alice = message_info(&addr("alice"), &[]);
+        let bob = message_info(&addr("bob"), &[]);
+        let approve = |from: &MessageInfo| ExecuteMsg::ApprovePinTransfer {
+            id: id.clone(),
+            from: from.sender.to_string(),
+        };
+        let transfer = |to: &MessageInfo| ExecuteMsg::TransferPin {
+            id: id.clone(),
+            to: to.sender.to_string(),
+        };
+
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            alice.clone(),
+            InstantiateMsg {
+                bucket: "test".to_string(),
+                config: Default::default(),
+                limits: Default::default(),
+                pagination: Default::default(),
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            alice.clone(),
+            ExecuteMsg::StoreObject {
+                data: Binary::from("okp4".as_bytes()),
+                pin: true,
+                compression_algorithm: None,
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
+            },
+        )
+        .unwrap();
+
+        let pinners = |storage: &dyn Storage| {
+            pins()
+                .keys(storage, None, None, Order::Ascending)
+                .map(|key| key.unwrap().1)
+                .collect::<Vec<_>>()
+        };
+        let pin_count = |storage: &dyn Storage| {
+            objects()
+                .load(storage, decode_hex(&id).into())
+                .unwrap()
+                .pin_count
+        };
+
+        assert_eq!(
+            execute(deps.as_mut(), mock_env(), alice.clone(), transfer(&bob)).unwrap_err(),
+            ContractError::PinTransferNotApproved {}
+        );
+        assert_eq!(
+            execute(deps.as_mut(), mock_env(), bob.clone(), transfer(&alice)).unwrap_err(),
+            ContractError::PinNotHeld {}
+        );
+
+        execute(deps.as_mut(), mock_env(), bob.clone(), approve(&alice)).unwrap();
+        let res = execute(deps.as_mut(), mock_env(), alice.clone(), transfer(&bob)).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "transfer_pin"),
+                Attribute::new("id", id.clone()),
+                Attribute::new("to", bob.sender.to_string()),
+            ]
+        );
+        assert_eq!(pinners(&deps.storage), vec![bob.sender.clone()]);
+        assert_eq!(pin_count(&deps.storage), Uint128::one());
+
+        // the approval is consumed by the transfer
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            alice.clone(),
+            ExecuteMsg::PinObject { id: id.clone() },
+        )
+        .unwrap();
+        assert_eq!(
+            execute(deps.as_mut(), mock_env(), alice.clone(), transfer(&bob)).unwrap_err(),
+            ContractError::PinTransferNotApproved {}
+        );
+
+        // transferring to a recipient already pinning the object releases the sender pin
+        execute(deps.as_mut(), mock_env(), bob.clone(), approve(&alice)).unwrap();
+        execute(deps.as_mut(), mock_env(), alice.clone(), transfer(&bob)).unwrap();
+        assert_eq!(pinners(&deps.storage), vec![bob.sender.clone()]);
+        assert_eq!(pin_count(&deps.storage), Uint128::one());
+
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                bob,
+                ExecuteMsg::ApprovePinTransfer {
+                    id: "abafa4428bdc8c34dae28bbc17303a62175f274edf59757b3e9898215a428a56"
+                        .to_string(),
+                    from: alice.sender.to_string(),
+                },
+            ),
+            Err(ContractError::Std(StdError::NotFound { .. }))
+        ));
+    }
+
     #[test]
     fn fetch_objects() {
         let mut deps = mock_dependencies();
@@ -2399,6 +3461,9 @@ mod tests {
             data: Binary::from_base64(data.as_str()).unwrap(),
             pin: false,
             compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            expected_digest: None,
+            reject_duplicate: false,
+            keep_content: false,
         };
         execute(deps.as_mut(), mock_env(), info1.clone(), msg).unwrap();
         let data = general_purpose::STANDARD.encode("object2");
@@ -2406,6 +3471,9 @@ mod tests {
             data: Binary::from_base64(data.as_str()).unwrap(),
             pin: false,
             compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            expected_digest: None,
+            reject_duplicate: false,
+            keep_content: false,
         };
         execute(deps.as_mut(), mock_env(), info1, msg).unwrap();
         let data = general_purpose::STANDARD.encode("object3");
@@ -2413,6 +3481,9 @@ mod tests {
             data: Binary::from_base64(data.as_str()).unwrap(),
             pin: false,
             compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            expected_digest: None,
+            reject_duplicate: false,
+            keep_content: false,
         };
         execute(deps.as_mut(), mock_env(), info2, msg).unwrap();
 
@@ -2503,6 +3574,8 @@ mod tests {
                 size: 7u128.into(),
                 compressed_size: 7u128.into(),
                 compression_algorithm: CompressionAlgorithm::Passthrough,
+                recoverable_until: None,
+                external_digest: None,
             }
         );
     }
@@ -2526,6 +3599,9 @@ mod tests {
             data: Binary::from_base64(data.as_str()).unwrap(),
             pin: false,
             compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            expected_digest: None,
+            reject_duplicate: false,
+            keep_content: false,
         };
         execute(deps.as_mut(), mock_env(), info1.clone(), msg).unwrap();
         // 1: 445008b7f2932922bdb184771d9978516a4f89d77000c2d6eab18b0894aac3a7
@@ -2534,6 +3610,9 @@ mod tests {
             data: Binary::from_base64(data.as_str()).unwrap(),
             pin: true,
             compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            expected_digest: None,
+            reject_duplicate: false,
+            keep_content: false,
         };
         execute(deps.as_mut(), mock_env(), info2, msg).unwrap();
         // 2: abafa4428bdc8c34dae28bbc17303a62175f274edf59757b3e9898215a428a56
@@ -2845,6 +3924,9 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -2853,6 +3935,9 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -2861,6 +3946,9 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -2877,6 +3965,9 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Snappy),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             };
             let _ = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -2976,6 +4067,9 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             },
         )
         .unwrap();
@@ -2998,6 +4092,9 @@ mod tests {
                 data: Binary::from_base64(data.as_str()).unwrap(),
                 pin: false,
                 compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
             },
         );
 
@@ -3007,4 +4104,527 @@ mod tests {
             "Object should successfully restored after a forgot"
         );
     }
+
+    #[test]
+    fn forget_object_with_recovery_window() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(CREATOR), &[]);
+        let mut env = mock_env();
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            InstantiateMsg {
+                bucket: "test".to_string(),
+                config: BucketConfigBuilder::default()
+                    .recovery_window(3_600u64)
+                    .build()
+                    .unwrap(),
+                limits: Default::default(),
+                pagination: Default::default(),
+            },
+        )
+        .unwrap();
+
+        let id = "3a6eb0790f39ac87c94f3856b2dd2c5d110e6811602261a9a923d3bb23adc8b7".to_string();
+        let store_msg = ExecuteMsg::StoreObject {
+            data: Binary::from_base64(general_purpose::STANDARD.encode("data").as_str()).unwrap(),
+            pin: false,
+            compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+            expected_digest: None,
+            reject_duplicate: false,
+            keep_content: false,
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), store_msg.clone()).unwrap();
+
+        // forgetting keeps the object until its recovery window elapses
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::ForgetObject { id: id.clone() },
+        )
+        .unwrap();
+        let until = env.block.time.plus_seconds(3_600);
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "forget_object"),
+                Attribute::new("id", id.clone()),
+                Attribute::new("recoverable_until", until.to_string()),
+            ]
+        );
+        let object = query::object(deps.as_ref(), id.clone()).unwrap();
+        assert_eq!(object.recoverable_until, Some(until));
+        assert_eq!(
+            query::bucket(deps.as_ref()).unwrap().stat.object_count,
+            Uint128::one()
+        );
+
+        for msg in [
+            ExecuteMsg::ForgetObject { id: id.clone() },
+            ExecuteMsg::PinObject { id: id.clone() },
+        ] {
+            assert_eq!(
+                execute(deps.as_mut(), env.clone(), info.clone(), msg).err(),
+                Some(ContractError::ObjectForgotten {})
+            );
+        }
+
+        // restoring within the window undoes the forgetting
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::RestoreObject { id: id.clone() },
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "restore_object"),
+                Attribute::new("id", id.clone()),
+            ]
+        );
+        assert_eq!(
+            query::object(deps.as_ref(), id.clone())
+                .unwrap()
+                .recoverable_until,
+            None
+        );
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                ExecuteMsg::RestoreObject { id: id.clone() },
+            )
+            .err(),
+            Some(ContractError::ObjectNotForgotten {})
+        );
+
+        // storing the same content again restores the object as well
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::ForgetObject { id: id.clone() },
+        )
+        .unwrap();
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), store_msg).unwrap();
+        assert!(res.attributes.contains(&Attribute::new("restored", "true")));
+        assert_eq!(
+            query::object(deps.as_ref(), id.clone())
+                .unwrap()
+                .recoverable_until,
+            None
+        );
+
+        // the sweep only purges the objects whose recovery window has elapsed
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::ForgetObject { id: id.clone() },
+        )
+        .unwrap();
+        env.block.time = env.block.time.plus_seconds(3_600);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::PurgeObjects {},
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("purged_count", "0")));
+        assert!(query::object(deps.as_ref(), id.clone()).is_ok());
+
+        env.block.time = env.block.time.plus_seconds(1);
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                ExecuteMsg::RestoreObject { id: id.clone() },
+            )
+            .err(),
+            Some(ContractError::RecoveryWindowExpired {})
+        );
+        let res = execute(deps.as_mut(), env, info, ExecuteMsg::PurgeObjects {}).unwrap();
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("purged_count", "1")));
+
+        assert!(query::object(deps.as_ref(), id.clone()).is_err());
+        assert!(!DATA.has(&deps.storage, id.try_into().unwrap()));
+        assert_eq!(
+            query::bucket(deps.as_ref()).unwrap().stat,
+            BucketStat {
+                size: Uint128::zero(),
+                compressed_size: Uint128::zero(),
+                object_count: Uint128::zero(),
+            }
+        );
+    }
+
+    #[test]
+    fn retention_policy() {
+        let mut deps = mock_dependencies();
+        let info = message_info(&addr(CREATOR), &[]);
+        let mut env = mock_env();
+        let stored_at = env.block.time;
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            InstantiateMsg {
+                bucket: "test".to_string(),
+                config: BucketConfigBuilder::default()
+                    .min_retention(3_600u64)
+                    .max_retention(7_200u64)
+                    .build()
+                    .unwrap(),
+                limits: Default::default(),
+                pagination: Default::default(),
+            },
+        )
+        .unwrap();
+        let config = query::bucket(deps.as_ref()).unwrap().config;
+        assert_eq!(config.min_retention, Some(Uint64::new(3_600)));
+        assert_eq!(config.max_retention, Some(Uint64::new(7_200)));
+
+        let mut ids = vec![];
+        for (data, pin) in [("data", false), ("pinned", true)] {
+            let res = execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                ExecuteMsg::StoreObject {
+                    data: Binary::from(data.as_bytes()),
+                    pin,
+                    compression_algorithm: Some(CompressionAlgorithm::Passthrough),
+                    expected_digest: None,
+                    reject_duplicate: false,
+                    keep_content: false,
+                },
+            )
+            .unwrap();
+            ids.push(res.attributes[1].value.clone());
+        }
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&addr("bob"), &[]),
+            ExecuteMsg::ApprovePinTransfer {
+                id: ids[1].clone(),
+                from: addr(CREATOR).to_string(),
+            },
+        )
+        .unwrap();
+
+        // objects can't be forgotten before their minimum retention
+        env.block.time = stored_at.plus_seconds(3_599);
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                ExecuteMsg::ForgetObject { id: ids[0].clone() },
+            )
+            .err(),
+            Some(ContractError::ObjectRetained {
+                until: stored_at.plus_seconds(3_600),
+            })
+        );
+
+        env.block.time = stored_at.plus_seconds(3_600);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::ForgetObject { id: ids[0].clone() },
+        )
+        .unwrap();
+        assert!(query::object(deps.as_ref(), ids[0].clone()).is_err());
+
+        // objects are swept past their maximum retention, even if pinned
+        env.block.time = stored_at.plus_seconds(7_200);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::PurgeObjects {},
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("purged_count", "0")));
+        assert!(
+            query::object(deps.as_ref(), ids[1].clone())
+                .unwrap()
+                .is_pinned
+        );
+
+        env.block.time = stored_at.plus_seconds(7_201);
+        let res = execute(deps.as_mut(), env, info, ExecuteMsg::PurgeObjects {}).unwrap();
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("purged_count", "1")));
+
+        let id: Hash = ids[1].clone().try_into().unwrap();
+        assert!(query::object(deps.as_ref(), ids[1].clone()).is_err());
+        assert!(!DATA.has(&deps.storage, id.clone()));
+        assert!(!pins().has(&deps.storage, (id.clone(), addr(CREATOR))));
+        assert!(!PIN_TRANSFER_APPROVALS.has(&deps.storage, (id, &addr(CREATOR), &addr("bob"))));
+        assert_eq!(
+            query::bucket(deps.as_ref()).unwrap().stat,
+            BucketStat {
+                size: Uint128::zero(),
+                compressed_size: Uint128::zero(),
+                object_count: Uint128::zero(),
+            }
+        );
+    }
+
+    #[test]
+    fn store_transformed_object() {
+        let owner = message_info(&addr(CREATOR), &[]);
+        let sender = message_info(&addr(SENDER), &[]);
+        let transformer = addr("transformer");
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            owner.clone(),
+            InstantiateMsg {
+                bucket: "test".to_string(),
+                config: Default::default(),
+                limits: Default::default(),
+                pagination: Default::default(),
+            },
+        )
+        .unwrap();
+
+        let set_transformer = |allow_replace| ExecuteMsg::SetTransformer {
+            transformer: Some(msg::Transformer {
+                contract_addr: transformer.to_string(),
+                allow_replace,
+            }),
+        };
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                sender.clone(),
+                set_transformer(true)
+            )
+            .unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            owner.clone(),
+            set_transformer(false),
+        )
+        .unwrap();
+        let bucket: BucketResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Bucket {}).unwrap()).unwrap();
+        assert_eq!(
+            bucket.transformer,
+            Some(msg::Transformer {
+                contract_addr: transformer.to_string(),
+                allow_replace: false,
+            })
+        );
+
+        let store = ExecuteMsg::StoreObject {
+            data: Binary::from("a.\r\nb.".as_bytes()),
+            pin: true,
+            compression_algorithm: None,
+            expected_digest: None,
+            reject_duplicate: false,
+            keep_content: false,
+        };
+        let transform_reply = |msg_responses| {
+            #[allow(deprecated)]
+            let reply = Reply {
+                id: TRANSFORM_OBJECT_REPLY_ID,
+                payload: Binary::default(),
+                gas_used: 0,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                    msg_responses,
+                }),
+            };
+            reply
+        };
+        // the transformer sets the normalized content as the data of its response, wrapped in the
+        // response of the execution
+        let transformed_reply = || {
+            let data = to_json_binary(&msg::TransformObjectResponse {
+                data: Some(Binary::from("a.\nb.".as_bytes())),
+            })
+            .unwrap();
+            transform_reply(vec![MsgResponse {
+                type_url: "/cosmwasm.wasm.v1.MsgExecuteContractResponse".to_string(),
+                value: Binary::from([&[0x0a, data.len() as u8], data.as_slice()].concat()),
+            }])
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), sender.clone(), store.clone()).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::reply_on_success(
+                WasmMsg::Execute {
+                    contract_addr: transformer.to_string(),
+                    msg: to_json_binary(&msg::TransformerMsg::TransformObject {
+                        data: Binary::from("a.\r\nb.".as_bytes()),
+                        sender: sender.sender.to_string(),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                },
+                TRANSFORM_OBJECT_REPLY_ID,
+            )]
+        );
+        assert_eq!(
+            execute(deps.as_mut(), mock_env(), sender.clone(), store.clone()).unwrap_err(),
+            ContractError::TransformationInProgress {}
+        );
+        assert_eq!(
+            reply(deps.as_mut(), mock_env(), transformed_reply()).unwrap_err(),
+            ContractError::ReplacementNotAllowed {}
+        );
+
+        // the content left unchanged by the transformer is stored as submitted
+        execute(deps.as_mut(), mock_env(), sender.clone(), store.clone()).unwrap();
+        let res = reply(deps.as_mut(), mock_env(), transform_reply(vec![])).unwrap();
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("transformed", "false")));
+        let submitted = crypto::hash(&crypto::HashAlgorithm::Sha256, &b"a.\r\nb.".to_vec());
+        assert!(objects().has(&deps.storage, submitted.clone()));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            owner.clone(),
+            set_transformer(true),
+        )
+        .unwrap();
+        execute(deps.as_mut(), mock_env(), sender.clone(), store.clone()).unwrap();
+        let res = reply(deps.as_mut(), mock_env(), transformed_reply()).unwrap();
+        let normalized = crypto::hash(&crypto::HashAlgorithm::Sha256, &b"a.\nb.".to_vec());
+        assert_eq!(
+            res.attributes[..2],
+            [
+                Attribute::new("action", "store_object"),
+                Attribute::new("id", normalized.to_string()),
+            ]
+        );
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("transformed", "true")));
+        assert_eq!(
+            query::data(deps.as_ref(), normalized.to_string()).unwrap(),
+            Binary::from("a.\nb.".as_bytes())
+        );
+        assert!(!PENDING_OBJECT.exists(&deps.storage));
+
+        // the expected digest is checked against the submitted content, and not recorded for the
+        // replacing one
+        let with_digest = |digest: &str| {
+            let ExecuteMsg::StoreObject { data, pin, .. } = store.clone() else {
+                unreachable!()
+            };
+            ExecuteMsg::StoreObject {
+                data,
+                pin,
+                compression_algorithm: None,
+                expected_digest: Some(digest.to_string()),
+                reject_duplicate: false,
+                keep_content: false,
+            }
+        };
+        let wrong = "sha256-LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=";
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                sender.clone(),
+                with_digest(wrong)
+            )
+            .unwrap_err(),
+            ContractError::DigestMismatch(wrong.to_string())
+        );
+        let sri = "sha256-wJnPdSZazKBMRulwGKxwTV8R+Agbbe10MknBi/7JGvw=";
+        execute(deps.as_mut(), mock_env(), sender.clone(), with_digest(sri)).unwrap();
+        reply(deps.as_mut(), mock_env(), transformed_reply()).unwrap();
+        assert_eq!(
+            objects()
+                .load(&deps.storage, normalized.clone())
+                .unwrap()
+                .external_digest,
+            None
+        );
+
+        // the sender opting out of the replacement keeps the id of the submitted content
+        let ExecuteMsg::StoreObject {
+            data,
+            pin,
+            compression_algorithm,
+            expected_digest,
+            reject_duplicate,
+            ..
+        } = store
+        else {
+            unreachable!()
+        };
+        let keep = ExecuteMsg::StoreObject {
+            data,
+            pin,
+            compression_algorithm,
+            expected_digest,
+            reject_duplicate,
+            keep_content: true,
+        };
+        execute(deps.as_mut(), mock_env(), sender.clone(), keep).unwrap();
+        let res = reply(deps.as_mut(), mock_env(), transformed_reply()).unwrap();
+        assert_eq!(
+            res.attributes[1],
+            Attribute::new("id", submitted.to_string())
+        );
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("transformed", "false")));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            owner,
+            ExecuteMsg::SetTransformer { transformer: None },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            sender,
+            ExecuteMsg::StoreObject {
+                data: Binary::from("c.".as_bytes()),
+                pin: false,
+                compression_algorithm: None,
+                expected_digest: None,
+                reject_duplicate: false,
+                keep_content: false,
+            },
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+    }
 }
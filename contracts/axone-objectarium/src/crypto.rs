@@ -1,4 +1,4 @@
-use cosmwasm_std::{StdError, StdResult};
+use cosmwasm_std::{Binary, StdError, StdResult};
 use cw_storage_plus::{Key, KeyDeserialize, Prefixer, PrimaryKey};
 use md5;
 use schemars::JsonSchema;
@@ -9,6 +9,7 @@ use std::any::type_name;
 use std::fmt;
 
 /// HashAlgorithm is the type of the hash algorithm.
+#[derive(Debug, PartialEq, Eq)]
 pub enum HashAlgorithm {
     /// Represents the MD5 algorithm.
     MD5,
@@ -35,6 +36,69 @@ impl HashAlgorithm {
     }
 }
 
+/// parse_digest returns the hash algorithm and the hash denoted by a digest computed outside the
+/// bucket, given either as a [Subresource Integrity](https://www.w3.org/TR/SRI/) string (e.g.
+/// `sha256-<base64>`) or as a [multihash](https://multiformats.io/multihash/) of the raw content,
+/// encoded in base58btc or in one of the `z` (base58btc) and `f` (base16) multibase encodings.
+pub fn parse_digest(digest: &str) -> StdResult<(HashAlgorithm, Hash)> {
+    let invalid = || StdError::generic_err(format!("Invalid digest: {digest}"));
+
+    let sri = digest.split_once('-').and_then(|(algorithm, hash)| {
+        match algorithm {
+            "sha256" => Some(HashAlgorithm::Sha256),
+            "sha384" => Some(HashAlgorithm::Sha384),
+            "sha512" => Some(HashAlgorithm::Sha512),
+            _ => None,
+        }
+        .map(|algorithm| (algorithm, hash))
+    });
+    if let Some((algorithm, hash)) = sri {
+        let hash = Binary::from_base64(hash).map_err(|_| invalid())?;
+        return Ok((algorithm, Hash(hash.to_vec())));
+    }
+
+    let multihash = if let Some(encoded) = digest.strip_prefix('z') {
+        bs58::decode(encoded).into_vec().ok()
+    } else if let Some(encoded) = digest.strip_prefix('f') {
+        base16ct::lower::decode_vec(encoded).ok()
+    } else {
+        bs58::decode(digest).into_vec().ok()
+    }
+    .ok_or_else(invalid)?;
+    let mut bytes = multihash.as_slice();
+    let code = read_varint(&mut bytes).ok_or_else(invalid)?;
+    let length = read_varint(&mut bytes).ok_or_else(invalid)?;
+    let algorithm = match code {
+        0xd5 => HashAlgorithm::MD5,
+        0x12 => HashAlgorithm::Sha256,
+        0x13 => HashAlgorithm::Sha512,
+        0x20 => HashAlgorithm::Sha384,
+        0x1013 => HashAlgorithm::Sha224,
+        _ => Err(StdError::generic_err(format!(
+            "Unsupported multihash function: {code:#x}"
+        )))?,
+    };
+    if length != bytes.len() as u64 {
+        return Err(invalid());
+    }
+
+    Ok((algorithm, Hash(bytes.to_vec())))
+}
+
+/// read_varint reads an unsigned varint (i.e. LEB128) from the start of the given bytes, advancing
+/// them past it.
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            *bytes = &bytes[i + 1..];
+            return Some(value);
+        }
+    }
+    None
+}
+
 /// Hash represent a Object hash as binary value.
 #[derive(
     Serialize, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, JsonSchema,
@@ -156,7 +220,8 @@ impl AsRef<[u8]> for Hash {
 
 #[cfg(test)]
 mod tests {
-    use crate::crypto::Hash;
+    use crate::crypto::{hash, parse_digest, Hash, HashAlgorithm};
+    use cosmwasm_std::StdError;
 
     #[test]
     fn vec_from_hash() {
@@ -168,4 +233,52 @@ mod tests {
         let result: Vec<u8> = h.into();
         assert_eq!(result, vec![3, 2, 1])
     }
+
+    #[test]
+    fn proper_parse_digest() {
+        let data = "hello".as_bytes().to_vec();
+        let sha256 = hash(&HashAlgorithm::Sha256, &data);
+        let cases = vec![
+            (
+                "sha256-LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=",
+                Ok((HashAlgorithm::Sha256, sha256.clone())),
+            ),
+            (
+                "QmRN6wdp1S2A5EtjW9A3M1vKSBuQQGcgvuhoMUoEz4iiT5",
+                Ok((HashAlgorithm::Sha256, sha256.clone())),
+            ),
+            (
+                "zQmRN6wdp1S2A5EtjW9A3M1vKSBuQQGcgvuhoMUoEz4iiT5",
+                Ok((HashAlgorithm::Sha256, sha256.clone())),
+            ),
+            (
+                "f12202cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+                Ok((HashAlgorithm::Sha256, sha256)),
+            ),
+            (
+                "fd501105d41402abc4b2a76b9719d911017c592",
+                Ok((HashAlgorithm::MD5, hash(&HashAlgorithm::MD5, &data))),
+            ),
+            (
+                "sha1-qvTGHdzF6KLavt4PO0gs2a6pQ00=",
+                Err(StdError::generic_err(
+                    "Invalid digest: sha1-qvTGHdzF6KLavt4PO0gs2a6pQ00=",
+                )),
+            ),
+            (
+                "f1220aabb",
+                Err(StdError::generic_err("Invalid digest: f1220aabb")),
+            ),
+            (
+                "f1114aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d",
+                Err(StdError::generic_err(
+                    "Unsupported multihash function: 0x11",
+                )),
+            ),
+        ];
+
+        for (digest, expected) in cases {
+            assert_eq!(parse_digest(digest), expected, "{digest}");
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use crate::compress::CompressionError;
 use crate::msg::CompressionAlgorithm;
-use cosmwasm_std::{StdError, Uint128};
-use cw_utils::PaymentError;
+use cosmwasm_std::{StdError, Timestamp, Uint128};
+use cw_utils::{ParseReplyError, PaymentError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -15,9 +15,48 @@ pub enum ContractError {
     #[error("Object is pinned and cannot be forgotten")]
     ObjectPinned {},
 
+    #[error("Object is forgotten")]
+    ObjectForgotten {},
+
+    #[error("Object is not forgotten and cannot be restored")]
+    ObjectNotForgotten {},
+
+    #[error("Recovery window of the object has elapsed")]
+    RecoveryWindowExpired {},
+
+    #[error("Object is retained until {until} and cannot be forgotten")]
+    ObjectRetained { until: Timestamp },
+
+    #[error("Object is not pinned by the sender")]
+    PinNotHeld {},
+
+    #[error("Pin transfer has not been approved by the recipient")]
+    PinTransferNotApproved {},
+
     #[error("Compression error: {0}")]
     CompressionError(String),
 
+    #[error("Object content doesn't match the expected digest: {0}")]
+    DigestMismatch(String),
+
+    #[error("Object {0} is already stored by the sender")]
+    ObjectAlreadyStored(String),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("An object is already being transformed")]
+    TransformationInProgress {},
+
+    #[error("Transformer is not allowed to replace the content of the objects")]
+    ReplacementNotAllowed {},
+
+    #[error("Unknown reply ID")]
+    UnknownReplyID {},
+
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
     #[error("{0}")]
     Payment(#[from] PaymentError),
 }
@@ -91,10 +130,34 @@ fn test_bucket_error_messages() {
             "Compression algorithm is not accepted: Snappy (accepted: \"[Passthrough]\")",
         ),
         (ContractError::ObjectPinned {}, "Object is pinned and cannot be forgotten"),
+        (ContractError::ObjectForgotten {}, "Object is forgotten"),
+        (
+            ContractError::ObjectNotForgotten {},
+            "Object is not forgotten and cannot be restored",
+        ),
+        (
+            ContractError::RecoveryWindowExpired {},
+            "Recovery window of the object has elapsed",
+        ),
+        (
+            ContractError::ObjectRetained {
+                until: Timestamp::from_seconds(42),
+            },
+            "Object is retained until 42.000000000 and cannot be forgotten",
+        ),
+        (ContractError::PinNotHeld {}, "Object is not pinned by the sender"),
+        (
+            ContractError::PinTransferNotApproved {},
+            "Pin transfer has not been approved by the recipient",
+        ),
         (
             ContractError::CompressionError("Insufficient ch'i to compress file".to_string()),
             "Compression error: Insufficient ch'i to compress file",
         ),
+        (
+            ContractError::DigestMismatch("sha256-AAAA".to_string()),
+            "Object content doesn't match the expected digest: sha256-AAAA",
+        ),
         (
             CompressionError::Error("Cannot compress empty data".to_string()).into(),
             "Compression error: Cannot compress empty data",
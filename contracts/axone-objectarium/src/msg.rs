@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Binary, Uint128};
+use cosmwasm_std::{Binary, Timestamp, Uint128, Uint64};
 use derive_builder::Builder;
 use enum_iterator::{all, Sequence};
 
@@ -33,7 +33,12 @@ pub enum ExecuteMsg {
     /// # StoreObject
     /// StoreObject store an object to the bucket and make the sender the owner of the object.
     /// The object is referenced by the hash of its content and this value is returned.
-    /// If the object is already stored, it is a no-op. It may be pinned though.
+    /// If the object is already stored, it is a no-op. It may be pinned though. The limits on the
+    /// object count and size don't apply then, as the bucket usage doesn't change.
+    ///
+    /// Storing a content the sender already stored succeeds as well, so clients can safely retry a
+    /// store, the response carrying a `deduplicated` attribute to tell it apart from a first
+    /// success. Setting the "reject_duplicate" parameter makes it fail instead.
     ///
     /// The "pin" parameter specifies whether the object should be pinned for the sender. Pinning an
     /// object ensures it is protected from being removed from storage, making it persistent and
@@ -43,9 +48,16 @@ pub enum ExecuteMsg {
     ///
     /// The "compression_algorithm" parameter specifies the algorithm for compressing the object before
     /// storing it in the storage, which is optional. If no algorithm is specified, the algorithm used
-    /// is the first algorithm of the bucket configuration limits. Note that the chosen algorithm can
+    /// is the first algorithm of the bucket configuration limits, or the one resulting from the
+    /// compression threshold policy if configured (see [BucketConfig::compression_threshold]). Note that the chosen algorithm can
     /// save storage space, but it will increase CPU usage. Depending on the chosen compression algorithm
     /// and the achieved compression ratio, the gas cost of the operation will vary, either increasing or decreasing.
+    ///
+    /// The "expected_digest" parameter allows to check the content against a digest computed outside
+    /// the bucket, the object being stored only if the content hashes to it with the algorithm it
+    /// designates, regardless of the bucket one. The digest is recorded along with the object.
+    /// With a transformer, the digest is checked against the submitted content, and isn't recorded
+    /// if the transformer replaces it.
     StoreObject {
         /// The content of the object to store.
         data: Binary,
@@ -54,8 +66,25 @@ pub enum ExecuteMsg {
         pin: bool,
         /// Specifies the compression algorithm to use when storing the object.
         /// If None, the first algorithm specified in the list of accepted compression algorithms of the bucket
-        /// is used (see [BucketLimits::accepted_compression_algorithms]).
+        /// is used (see [BucketLimits::accepted_compression_algorithms]), unless a compression threshold is
+        /// configured (see [BucketConfig::compression_threshold]).
         compression_algorithm: Option<CompressionAlgorithm>,
+        /// The digest the content must match, either a [Subresource Integrity](https://www.w3.org/TR/SRI/)
+        /// string (e.g. `sha256-<base64>`) or a [multihash](https://multiformats.io/multihash/) of the
+        /// raw content encoded in base58btc or in one of the `z` (base58btc) and `f` (base16)
+        /// multibase encodings. An IPFS CID doesn't qualify, as it hashes the DAG encoding of the
+        /// content rather than the content itself.
+        #[serde(default)]
+        expected_digest: Option<String>,
+        /// Specifies whether storing a content the sender already stored fails instead of being
+        /// acknowledged as deduplicated.
+        #[serde(default)]
+        reject_duplicate: bool,
+        /// Specifies whether the content is stored as submitted even though the transformer of the
+        /// bucket returns another one, so the object is identified by the hash of the submitted
+        /// content (see [ExecuteMsg::SetTransformer]). The transformer still validates the content.
+        #[serde(default)]
+        keep_content: bool,
     },
 
     /// # ForgetObject
@@ -63,8 +92,30 @@ pub enum ExecuteMsg {
     /// it from storage if it is no longer pinned by anyone.
     /// If the object is still pinned by other senders, it is not removed from storage and an error is returned.
     /// If the object is not pinned for the sender, this operation is a no-op.
+    ///
+    /// If the bucket has a recovery window (see [BucketConfig::recovery_window]), the object is only
+    /// marked as forgotten and can be restored with [ExecuteMsg::RestoreObject] until the window
+    /// elapses. Its content keeps counting in the bucket usage until it is purged.
+    ///
+    /// If the bucket has a minimum retention (see [BucketConfig::min_retention]), the object cannot
+    /// be forgotten before it has been stored for that duration.
     ForgetObject { id: ObjectId },
 
+    /// # RestoreObject
+    /// RestoreObject undoes the forgetting of an object whose recovery window has not elapsed yet.
+    /// Storing the same content again also restores the object, whatever its recovery window.
+    RestoreObject { id: ObjectId },
+
+    /// # PurgeObjects
+    /// PurgeObjects permanently removes from storage the forgotten objects whose recovery window
+    /// has elapsed, in the order of their expiry and at most `max_page_size` of them per call
+    /// (see [PaginationConfig::max_page_size]).
+    ///
+    /// If the bucket has a maximum retention (see [BucketConfig::max_retention]), the objects stored
+    /// for longer are removed as well, whether they are pinned or not, once the forgotten ones have
+    /// been purged and within the same page size.
+    PurgeObjects {},
+
     /// # PinObject
     /// PinObject pins the object in the bucket for the sender. If the object is already pinned
     /// for the sender, this operation is a no-op.
@@ -76,6 +127,83 @@ pub enum ExecuteMsg {
     /// for the sender, this operation is a no-op.
     /// The object can be removed from storage if it is no longer pinned by anyone.
     UnpinObject { id: ObjectId },
+
+    /// # ApprovePinTransfer
+    /// ApprovePinTransfer records the consent of the sender to receive the pin that the `from`
+    /// address holds on the object, allowing it to be transferred with [ExecuteMsg::TransferPin].
+    /// The approval is consumed by the transfer.
+    ApprovePinTransfer {
+        /// The id of the object.
+        id: ObjectId,
+        /// The address holding the pin to receive.
+        from: String,
+    },
+
+    /// # TransferPin
+    /// TransferPin moves the pin the sender holds on the object to the `to` address in a single
+    /// operation, so the object never ends up unpinned in between. The recipient must have
+    /// approved the transfer beforehand (see [ExecuteMsg::ApprovePinTransfer]).
+    /// If the object is already pinned by the recipient, the sender pin is simply released.
+    TransferPin {
+        /// The id of the object.
+        id: ObjectId,
+        /// The address receiving the pin.
+        to: String,
+    },
+
+    /// # SetTransformer
+    /// SetTransformer registers the contract the content of the objects is submitted to before
+    /// being stored, through a [TransformerMsg::TransformObject] message, or unregisters it if not
+    /// provided.
+    ///
+    /// The transformer validates the content, failing to reject it, and may normalize it (e.g.
+    /// the line endings of a Prolog program), the returned content being stored in place of the
+    /// submitted one if the transformer is allowed to replace it. The object is then identified by
+    /// the hash of the stored content, which differs from the one of the submitted content, so the
+    /// callers identifying the object beforehand have to opt out of the replacement (see
+    /// [ExecuteMsg::StoreObject::keep_content]).
+    ///
+    /// Only the bucket owner is authorized to perform this action.
+    SetTransformer {
+        /// The transformer to register, none to unregister the current one.
+        transformer: Option<Transformer>,
+    },
+}
+
+/// # Transformer
+/// The contract the content of the objects is submitted to before being stored, see
+/// [ExecuteMsg::SetTransformer].
+#[cw_serde]
+pub struct Transformer {
+    /// The address of the transformer contract.
+    pub contract_addr: String,
+    /// Whether the content returned by the transformer replaces the submitted one, the objects
+    /// being only validated otherwise.
+    pub allow_replace: bool,
+}
+
+/// Messages sent by the bucket to its transformer, see [ExecuteMsg::SetTransformer].
+#[cw_serde]
+pub enum TransformerMsg {
+    /// # TransformObject
+    /// Submit the content of an object about to be stored. The transformer fails to reject it, or
+    /// sets a [TransformObjectResponse] as the data of its response, no data leaving the content
+    /// unchanged.
+    TransformObject {
+        /// The content of the object.
+        data: Binary,
+        /// The address storing the object.
+        sender: String,
+    },
+}
+
+/// # TransformObjectResponse
+/// The data of the response of a transformer to a [TransformerMsg::TransformObject] message.
+#[cw_serde]
+#[derive(Default)]
+pub struct TransformObjectResponse {
+    /// The content to store in place of the submitted one, none leaving it unchanged.
+    pub data: Option<Binary>,
 }
 
 /// Query messages
@@ -115,6 +243,23 @@ pub enum QueryMsg {
         id: ObjectId,
     },
 
+    /// # ObjectDataRange
+    /// ObjectDataRange returns a range of bytes of the content of the object with the given id,
+    /// allowing to retrieve in chunks an object whose content doesn't fit in a single query
+    /// response.
+    ///
+    /// The range is truncated to the size of the object, being empty if it starts past its end. A
+    /// compressed content is only decompressed up to the end of the range.
+    #[returns(Binary)]
+    ObjectDataRange {
+        /// The id of the object to get.
+        id: ObjectId,
+        /// The offset of the first byte of the range.
+        offset: Uint128,
+        /// The maximum number of bytes of the range.
+        length: Uint128,
+    },
+
     /// # ObjectPins
     /// ObjectPins returns the list of addresses that pinned the object with the given id with
     /// support for pagination.
@@ -153,6 +298,8 @@ pub struct BucketResponse {
     pub pagination: PaginationConfig,
     /// The statistics of the bucket.
     pub stat: BucketStat,
+    /// The transformer the content of the objects is submitted to, if any.
+    pub transformer: Option<Transformer>,
 }
 
 /// CompressionAlgorithm is an enumeration that defines the different compression algorithms
@@ -284,6 +431,44 @@ pub struct BucketConfig {
     /// here will fail.
     #[serde(default = "CompressionAlgorithm::values")]
     pub accepted_compression_algorithms: Vec<CompressionAlgorithm>,
+    /// The size threshold, in bytes, driving the compression of the objects stored without a specified
+    /// compression algorithm.
+    /// If this parameter is not set, such objects are compressed with the first accepted compression
+    /// algorithm.
+    /// If this parameter is set, objects whose size is above the threshold are compressed with the first
+    /// accepted compression algorithm other than Passthrough, while the other ones are stored raw. This
+    /// allows to spend the gas cost of compression only where the state savings are worth it.
+    ///
+    /// When set, both the Passthrough algorithm and at least one other algorithm must be accepted.
+    #[serde(default)]
+    pub compression_threshold: Option<Uint128>,
+    /// The usage thresholds, in percent of the `max_total_size` and `max_objects` limits, whose
+    /// crossing by an object storage emits a `bucket_usage_alert` event.
+    /// If this parameter is not set, no alert is emitted.
+    ///
+    /// Each threshold must be between 1 and 100, and only applies to the limits that are set.
+    #[serde(default)]
+    pub usage_alert_thresholds: Vec<u8>,
+    /// The duration, in seconds, during which a forgotten object can be restored before being
+    /// permanently purged.
+    /// If this parameter is not set, forgotten objects are removed from storage immediately.
+    ///
+    /// When set, the window cannot be zero.
+    #[serde(default)]
+    pub recovery_window: Option<Uint64>,
+    /// The duration, in seconds, during which a stored object cannot be forgotten.
+    /// If this parameter is not set, objects can be forgotten at any time.
+    ///
+    /// When set, the duration cannot be zero.
+    #[serde(default)]
+    pub min_retention: Option<Uint64>,
+    /// The duration, in seconds, after which a stored object is removed from storage by the next
+    /// [ExecuteMsg::PurgeObjects], even if it is pinned.
+    /// If this parameter is not set, objects are kept as long as they are not forgotten.
+    ///
+    /// When set, the duration cannot be zero nor less than the `min_retention`.
+    #[serde(default)]
+    pub max_retention: Option<Uint64>,
 }
 
 impl Default for BucketConfig {
@@ -291,6 +476,11 @@ impl Default for BucketConfig {
         Self {
             hash_algorithm: Default::default(),
             accepted_compression_algorithms: CompressionAlgorithm::values(),
+            compression_threshold: None,
+            usage_alert_thresholds: vec![],
+            recovery_window: None,
+            min_retention: None,
+            max_retention: None,
         }
     }
 }
@@ -388,6 +578,12 @@ pub struct ObjectResponse {
     pub compressed_size: Uint128,
     /// The compression algorithm used to compress the content of the object.
     pub compression_algorithm: CompressionAlgorithm,
+    /// The time until which the object can be restored, set only if the object has been forgotten.
+    /// Past this time, the object is purged from storage by the next [ExecuteMsg::PurgeObjects].
+    pub recoverable_until: Option<Timestamp>,
+    /// The digest the content has been checked against when stored, if any (see
+    /// [ExecuteMsg::StoreObject]).
+    pub external_digest: Option<String>,
 }
 
 /// # ObjectsResponse
@@ -440,6 +636,10 @@ mod tests {
             config.accepted_compression_algorithms,
             vec![Passthrough, Snappy, Lzma]
         );
+        assert_eq!(config.compression_threshold, None);
+        assert_eq!(config.recovery_window, None);
+        assert_eq!(config.min_retention, None);
+        assert_eq!(config.max_retention, None);
     }
 
     #[test]
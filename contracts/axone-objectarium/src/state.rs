@@ -4,7 +4,9 @@ use crate::error::BucketError;
 use crate::error::BucketError::EmptyName;
 use crate::msg;
 use crate::msg::{ObjectResponse, PaginationConfig};
-use cosmwasm_std::{ensure, ensure_ne, Addr, StdError, StdResult, Uint128};
+use cosmwasm_std::{
+    ensure, ensure_ne, Addr, Binary, Empty, StdError, StdResult, Timestamp, Uint128, Uint64,
+};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -138,23 +140,117 @@ pub struct BucketConfig {
     ///
     /// The default is all compression algorithms.
     pub accepted_compression_algorithms: Vec<CompressionAlgorithm>,
+    /// The size threshold above which objects are automatically compressed, if any.
+    #[serde(default)]
+    pub compression_threshold: Option<Uint128>,
+    /// The usage thresholds, in percent of the bucket limits, emitting an alert when crossed.
+    #[serde(default)]
+    pub usage_alert_thresholds: Vec<u8>,
+    /// The duration, in seconds, during which a forgotten object can be restored, if any.
+    #[serde(default)]
+    pub recovery_window: Option<Uint64>,
+    /// The duration, in seconds, during which a stored object cannot be forgotten, if any.
+    #[serde(default)]
+    pub min_retention: Option<Uint64>,
+    /// The duration, in seconds, after which a stored object is swept, if any.
+    #[serde(default)]
+    pub max_retention: Option<Uint64>,
 }
 
 impl BucketConfig {
     fn try_new(
         hash_algorithm: HashAlgorithm,
         accepted_compression_algorithms: Vec<CompressionAlgorithm>,
+        compression_threshold: Option<Uint128>,
+        usage_alert_thresholds: Vec<u8>,
+        recovery_window: Option<Uint64>,
+        min_retention: Option<Uint64>,
+        max_retention: Option<Uint64>,
     ) -> StdResult<BucketConfig> {
         ensure!(
             !accepted_compression_algorithms.is_empty(),
             StdError::generic_err("'accepted_compression_algorithms' cannot be empty")
         );
+        if compression_threshold.is_some() {
+            ensure!(
+                accepted_compression_algorithms.contains(&CompressionAlgorithm::Passthrough)
+                    && accepted_compression_algorithms
+                        .iter()
+                        .any(|c| *c != CompressionAlgorithm::Passthrough),
+                StdError::generic_err(
+                    "'compression_threshold' requires both Passthrough and a compressing algorithm to be accepted"
+                )
+            );
+        }
+
+        ensure!(
+            usage_alert_thresholds.iter().all(|t| (1..=100).contains(t)),
+            StdError::generic_err("'usage_alert_thresholds' must be between 1 and 100")
+        );
+        ensure_ne!(
+            recovery_window,
+            Some(Uint64::zero()),
+            StdError::generic_err("'recovery_window' cannot be zero")
+        );
+        ensure_ne!(
+            min_retention,
+            Some(Uint64::zero()),
+            StdError::generic_err("'min_retention' cannot be zero")
+        );
+        ensure_ne!(
+            max_retention,
+            Some(Uint64::zero()),
+            StdError::generic_err("'max_retention' cannot be zero")
+        );
+        ensure!(
+            !matches!(
+                (min_retention, max_retention),
+                (Some(min_retention), Some(max_retention)) if max_retention < min_retention
+            ),
+            StdError::generic_err("'max_retention' cannot be less than 'min_retention'")
+        );
 
         Ok(BucketConfig {
             hash_algorithm,
             accepted_compression_algorithms,
+            compression_threshold,
+            usage_alert_thresholds,
+            recovery_window,
+            min_retention,
+            max_retention,
         })
     }
+
+    /// The time until which an object stored at the given time cannot be forgotten, if retained.
+    pub fn retained_until(&self, stored_at: Option<Timestamp>) -> Option<Timestamp> {
+        Some(stored_at?.plus_seconds(self.min_retention?.u64()))
+    }
+
+    /// The time after which an object stored at the given time gets swept, if its retention is
+    /// bounded.
+    pub fn expires_at(&self, stored_at: Option<Timestamp>) -> Option<Timestamp> {
+        Some(stored_at?.plus_seconds(self.max_retention?.u64()))
+    }
+
+    /// Resolve the compression algorithm to use for storing an object of the given size, the requested
+    /// algorithm taking precedence over the bucket compression policy.
+    pub fn compression_for(
+        &self,
+        size: Uint128,
+        requested: Option<CompressionAlgorithm>,
+    ) -> CompressionAlgorithm {
+        let accepted = &self.accepted_compression_algorithms;
+        requested
+            .or_else(|| {
+                let compress = size > self.compression_threshold?;
+                accepted
+                    .iter()
+                    .find(|c| (**c != CompressionAlgorithm::Passthrough) == compress)
+                    .cloned()
+            })
+            .or_else(|| accepted.first().cloned())
+            .unwrap_or(CompressionAlgorithm::Passthrough)
+    }
 }
 
 impl TryFrom<msg::BucketConfig> for BucketConfig {
@@ -168,6 +264,11 @@ impl TryFrom<msg::BucketConfig> for BucketConfig {
                 .into_iter()
                 .map(Into::into)
                 .collect(),
+            config.compression_threshold,
+            config.usage_alert_thresholds,
+            config.recovery_window,
+            config.min_retention,
+            config.max_retention,
         )
     }
 }
@@ -181,6 +282,11 @@ impl From<BucketConfig> for msg::BucketConfig {
                 .into_iter()
                 .map(Into::into)
                 .collect(),
+            compression_threshold: config.compression_threshold,
+            usage_alert_thresholds: config.usage_alert_thresholds,
+            recovery_window: config.recovery_window,
+            min_retention: config.min_retention,
+            max_retention: config.max_retention,
         }
     }
 }
@@ -338,6 +444,16 @@ pub struct Object {
     pub compression: CompressionAlgorithm,
     /// The size of the object after compression.
     pub compressed_size: Uint128,
+    /// The time until which the object can be restored, if it has been forgotten.
+    #[serde(default)]
+    pub recoverable_until: Option<Timestamp>,
+    /// The time the object has been stored at, unknown for the objects stored before it was
+    /// recorded.
+    #[serde(default)]
+    pub stored_at: Option<Timestamp>,
+    /// The external digest the content has been checked against when stored, if any.
+    #[serde(default)]
+    pub external_digest: Option<String>,
 }
 
 impl From<&Object> for ObjectResponse {
@@ -349,6 +465,8 @@ impl From<&Object> for ObjectResponse {
             is_pinned: object.pin_count > Uint128::zero(),
             compressed_size: object.compressed_size,
             compression_algorithm: object.compression.into(),
+            recoverable_until: object.recoverable_until,
+            external_digest: object.external_digest.clone(),
         }
     }
 }
@@ -373,6 +491,49 @@ pub fn objects<'a>() -> IndexedMap<Hash, Object, ObjectIndexes<'a>> {
     )
 }
 
+/// The forgotten objects awaiting their purge, keyed by the end of their recovery window in
+/// nanoseconds.
+pub const FORGOTTEN: Map<(u64, Hash), Empty> = Map::new("FORGOTTEN");
+
+/// The contract the content of the objects is submitted to before being stored.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Transformer {
+    pub contract: Addr,
+    pub allow_replace: bool,
+}
+
+impl From<Transformer> for msg::Transformer {
+    fn from(value: Transformer) -> Self {
+        msg::Transformer {
+            contract_addr: value.contract.into(),
+            allow_replace: value.allow_replace,
+        }
+    }
+}
+
+pub const TRANSFORMER: Item<Transformer> = Item::new("transformer");
+
+/// An object submitted to the transformer, awaiting its reply to be stored.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PendingObject {
+    /// The address storing the object.
+    pub sender: Addr,
+    /// The content of the object, as submitted.
+    pub data: Binary,
+    pub pin: bool,
+    pub compression_algorithm: Option<msg::CompressionAlgorithm>,
+    pub expected_digest: Option<String>,
+    pub reject_duplicate: bool,
+    #[serde(default)]
+    pub keep_content: bool,
+}
+
+pub const PENDING_OBJECT: Item<PendingObject> = Item::new("pending_object");
+
+/// The objects whose retention is bounded by the bucket maximum retention, keyed by the end of
+/// their retention in nanoseconds.
+pub const EXPIRING: Map<(u64, Hash), Empty> = Map::new("EXPIRING");
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Pin {
     /// The id of the object.
@@ -392,6 +553,10 @@ impl IndexList<Pin> for PinIndexes<'_> {
     }
 }
 
+/// The approved pin transfers, keyed by object id, current pin holder and recipient.
+pub const PIN_TRANSFER_APPROVALS: Map<(Hash, &Addr, &Addr), Empty> =
+    Map::new("PIN_TRANSFER_APPROVALS");
+
 pub fn pins<'a>() -> IndexedMap<(Hash, Addr), Pin, PinIndexes<'a>> {
     IndexedMap::new(
         "PIN",
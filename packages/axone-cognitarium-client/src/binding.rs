@@ -0,0 +1,261 @@
+use axone_cognitarium::msg::{SelectResponse, Value, IRI};
+use cosmwasm_std::{StdError, StdResult, Timestamp};
+use std::collections::BTreeMap;
+
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+const XSD_DATE_TIME: &str = "http://www.w3.org/2001/XMLSchema#dateTime";
+const XSD_INTEGERS: [&str; 13] = [
+    "http://www.w3.org/2001/XMLSchema#integer",
+    "http://www.w3.org/2001/XMLSchema#long",
+    "http://www.w3.org/2001/XMLSchema#int",
+    "http://www.w3.org/2001/XMLSchema#short",
+    "http://www.w3.org/2001/XMLSchema#byte",
+    "http://www.w3.org/2001/XMLSchema#nonNegativeInteger",
+    "http://www.w3.org/2001/XMLSchema#nonPositiveInteger",
+    "http://www.w3.org/2001/XMLSchema#negativeInteger",
+    "http://www.w3.org/2001/XMLSchema#positiveInteger",
+    "http://www.w3.org/2001/XMLSchema#unsignedLong",
+    "http://www.w3.org/2001/XMLSchema#unsignedInt",
+    "http://www.w3.org/2001/XMLSchema#unsignedShort",
+    "http://www.w3.org/2001/XMLSchema#unsignedByte",
+];
+
+/// A solution of a select query, binding the selected variables to their values.
+pub type Binding = BTreeMap<String, Value>;
+
+/// Typed access to the values of a [Binding], failing if the variable is unbound or bound to a
+/// value of another kind.
+pub trait BindingExt {
+    /// The value bound to the variable, if any.
+    fn value(&self, variable: &str) -> StdResult<&Value>;
+
+    /// The IRI bound to the variable.
+    fn iri(&self, variable: &str) -> StdResult<String> {
+        match self.value(variable)? {
+            Value::URI {
+                value: IRI::Full(iri),
+            } => Ok(iri.clone()),
+            _ => Err(unexpected_value(variable, "an IRI")),
+        }
+    }
+
+    /// The lexical form of the literal bound to the variable, without language tag and typed
+    /// `xsd:string` if typed at all.
+    fn string(&self, variable: &str) -> StdResult<String> {
+        match self.value(variable)? {
+            Value::Literal {
+                value,
+                lang: None,
+                datatype: None,
+            } => Ok(value.clone()),
+            Value::Literal {
+                value,
+                lang: None,
+                datatype: Some(IRI::Full(datatype)),
+            } if datatype == XSD_STRING => Ok(value.clone()),
+            _ => Err(unexpected_value(variable, "a string literal")),
+        }
+    }
+
+    /// The integer bound to the variable, as a literal of one of the `xsd` integer datatypes.
+    fn int(&self, variable: &str) -> StdResult<i64> {
+        typed_literal(self.value(variable)?, &XSD_INTEGERS)
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| unexpected_value(variable, "an integer literal"))
+    }
+
+    /// The point in time bound to the variable, as an `xsd:dateTime` literal.
+    fn date_time(&self, variable: &str) -> StdResult<Timestamp> {
+        typed_literal(self.value(variable)?, &[XSD_DATE_TIME])
+            .and_then(parse_date_time)
+            .map(Timestamp::from_seconds)
+            .ok_or_else(|| unexpected_value(variable, "a date time literal"))
+    }
+}
+
+impl BindingExt for Binding {
+    fn value(&self, variable: &str) -> StdResult<&Value> {
+        self.get(variable)
+            .ok_or_else(|| StdError::not_found(format!("binding of variable '{variable}'")))
+    }
+}
+
+/// The lexical form of a literal typed with one of the given datatypes.
+fn typed_literal<'a>(value: &'a Value, datatypes: &[&str]) -> Option<&'a str> {
+    match value {
+        Value::Literal {
+            value,
+            datatype: Some(IRI::Full(datatype)),
+            ..
+        } if datatypes.contains(&datatype.as_str()) => Some(value),
+        _ => None,
+    }
+}
+
+fn unexpected_value(variable: &str, expected: &str) -> StdError {
+    StdError::generic_err(format!(
+        "Unexpected binding of variable '{variable}', expected {expected}"
+    ))
+}
+
+/// A type built from the values of a select query [Binding], see [from_binding](crate::from_binding).
+pub trait FromBinding: Sized {
+    fn from_binding(binding: &Binding) -> StdResult<Self>;
+}
+
+/// Map each solution of a select response into a value.
+pub fn rows<T: FromBinding>(response: &SelectResponse) -> StdResult<Vec<T>> {
+    response
+        .results
+        .bindings
+        .iter()
+        .map(T::from_binding)
+        .collect()
+}
+
+/// Implement [FromBinding] for a struct, each of its fields being read from the variable it is
+/// mapped to through the given [BindingExt] getter.
+///
+/// ```
+/// use axone_cognitarium_client::from_binding;
+///
+/// struct Claim {
+///     credential: String,
+///     issued: cosmwasm_std::Timestamp,
+/// }
+///
+/// from_binding!(Claim {
+///     credential: iri("c"),
+///     issued: date_time("date"),
+/// });
+/// ```
+#[macro_export]
+macro_rules! from_binding {
+    ($ty:ident { $($field:ident : $getter:ident($variable:expr)),* $(,)? }) => {
+        impl $crate::FromBinding for $ty {
+            fn from_binding(
+                binding: &$crate::Binding,
+            ) -> ::cosmwasm_std::StdResult<Self> {
+                Ok($ty {
+                    $($field: $crate::BindingExt::$getter(binding, $variable)?,)*
+                })
+            }
+        }
+    };
+}
+
+/// Parse an `xsd:dateTime` (e.g. `2025-01-22T00:00:00Z`) into seconds since the Unix epoch, as
+/// the triple store does when comparing them, the fractional seconds being ignored. Dates before
+/// the epoch are not supported.
+pub fn parse_date_time(value: &str) -> Option<u64> {
+    axone_cognitarium::parse_date_time(value)
+        .and_then(|((seconds, _), _)| u64::try_from(seconds).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axone_cognitarium::msg::{Head, Results};
+
+    #[test]
+    fn proper_parse_date_time() {
+        let cases = vec![
+            ("1970-01-01T00:00:00Z", Some(0)),
+            ("2025-01-22T00:00:00", Some(1737504000)),
+            ("2024-02-29T12:30:15.250Z", Some(1709209815)),
+            ("2024-03-01T02:00:00+02:00", Some(1709251200)),
+            ("2024-02-29T22:00:00-02:00", Some(1709251200)),
+            ("1969-12-31T23:59:59Z", None),
+            ("2024-13-01T00:00:00Z", None),
+            ("2023-02-29T00:00:00Z", None),
+            ("2024-01-01T00:00:00+15:00", None),
+            ("2024-01-01T00:00:00.Z", None),
+            ("2024-01-01", None),
+            ("2024-1-01T00:00:00Z", None),
+            ("2024-01-01T00:00Z", None),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(parse_date_time(value), expected, "{value}");
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Row {
+        subject: String,
+        name: String,
+        age: i64,
+        born: Timestamp,
+    }
+
+    from_binding!(Row {
+        subject: iri("s"),
+        name: string("name"),
+        age: int("age"),
+        born: date_time("born"),
+    });
+
+    #[test]
+    fn proper_binding_extraction() {
+        let literal = |value: &str, datatype: Option<&str>| Value::Literal {
+            value: value.to_string(),
+            lang: None,
+            datatype: datatype.map(|d| IRI::Full(d.to_string())),
+        };
+        let binding = Binding::from([
+            (
+                "s".to_string(),
+                Value::URI {
+                    value: IRI::Full("https://ex.org/alice".to_string()),
+                },
+            ),
+            ("name".to_string(), literal("Alice", None)),
+            (
+                "age".to_string(),
+                literal("42", Some("http://www.w3.org/2001/XMLSchema#integer")),
+            ),
+            (
+                "born".to_string(),
+                literal("1982-01-01T00:00:00Z", Some(XSD_DATE_TIME)),
+            ),
+        ]);
+
+        let response = SelectResponse {
+            head: Head { vars: vec![] },
+            results: Results {
+                bindings: vec![binding.clone()],
+            },
+        };
+        assert_eq!(
+            rows::<Row>(&response),
+            Ok(vec![Row {
+                subject: "https://ex.org/alice".to_string(),
+                name: "Alice".to_string(),
+                age: 42,
+                born: Timestamp::from_seconds(378691200),
+            }])
+        );
+
+        let cases = vec![
+            (
+                binding.iri("name").map(|_| ()),
+                "Generic error: Unexpected binding of variable 'name', expected an IRI",
+            ),
+            (
+                binding.iri("unknown").map(|_| ()),
+                "binding of variable 'unknown' not found",
+            ),
+            (
+                binding.int("name").map(|_| ()),
+                "Generic error: Unexpected binding of variable 'name', expected an integer literal",
+            ),
+            (
+                binding.string("age").map(|_| ()),
+                "Generic error: Unexpected binding of variable 'age', expected a string literal",
+            ),
+        ];
+        for (res, expected) in cases {
+            assert_eq!(res.unwrap_err().to_string(), expected);
+        }
+    }
+}
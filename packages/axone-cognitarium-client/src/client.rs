@@ -1,4 +1,7 @@
-use axone_cognitarium::msg::{DataFormat, ExecuteMsg, QueryMsg, SelectQuery, SelectResponse};
+use axone_cognitarium::msg::{
+    DataFormat, ExecuteMsg, ExportResponse, QueryMsg, SelectQuery, SelectResponse, StoreResponse,
+    TripleDeleteTemplate, WhereClause,
+};
 use cosmwasm_std::{
     to_json_binary, Addr, Binary, Coin, CustomQuery, QuerierWrapper, QueryRequest, StdResult,
     WasmMsg, WasmQuery,
@@ -15,6 +18,13 @@ impl CognitariumClient {
         Self { address }
     }
 
+    pub fn store<C: CustomQuery>(
+        &self,
+        querier: QuerierWrapper<'_, C>,
+    ) -> StdResult<StoreResponse> {
+        self.query_wasm(querier, &QueryMsg::Store {})
+    }
+
     pub fn select<C: CustomQuery>(
         &self,
         querier: QuerierWrapper<'_, C>,
@@ -23,8 +33,50 @@ impl CognitariumClient {
         self.query_wasm(querier, &QueryMsg::Select { query })
     }
 
+    pub fn export<C: CustomQuery>(
+        &self,
+        querier: QuerierWrapper<'_, C>,
+        cursor: Option<Binary>,
+        limit: Option<u32>,
+        format: Option<DataFormat>,
+    ) -> StdResult<ExportResponse> {
+        self.query_wasm(
+            querier,
+            &QueryMsg::Export {
+                cursor,
+                limit,
+                format,
+            },
+        )
+    }
+
     pub fn insert_data(&self, format: Option<DataFormat>, data: Binary) -> StdResult<WasmMsg> {
-        self.to_wasm_exec_msg(&ExecuteMsg::InsertData { format, data }, vec![])
+        self.to_wasm_exec_msg(
+            &ExecuteMsg::InsertData {
+                format,
+                data,
+                compression: None,
+                expires: None,
+                graph: None,
+                skip_oversized: false,
+            },
+            vec![],
+        )
+    }
+
+    pub fn delete_data(
+        &self,
+        delete: Vec<TripleDeleteTemplate>,
+        r#where: Option<WhereClause>,
+    ) -> StdResult<WasmMsg> {
+        self.to_wasm_exec_msg(
+            &ExecuteMsg::DeleteData {
+                prefixes: vec![],
+                delete,
+                r#where,
+            },
+            vec![],
+        )
     }
 
     fn query_wasm<C, T, U>(&self, querier: QuerierWrapper<'_, C>, msg: &T) -> StdResult<U>
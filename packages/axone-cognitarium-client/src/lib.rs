@@ -1,3 +1,5 @@
+mod binding;
 mod client;
 
+pub use binding::*;
 pub use client::*;
@@ -1,11 +1,12 @@
 use crate::owned_model::OwnedQuad;
 use rio_api::formatter::TriplesFormatter;
-use rio_api::model::{Quad, Triple};
+use rio_api::model::{GraphName, Quad, Triple};
 use rio_api::parser::{QuadsParser, TriplesParser};
 use rio_turtle::{
     NQuadsFormatter, NQuadsParser, NTriplesFormatter, NTriplesParser, TurtleError, TurtleFormatter,
     TurtleParser,
 };
+#[cfg(feature = "rdf-xml")]
 use rio_xml::{RdfXmlError, RdfXmlFormatter, RdfXmlParser};
 use std::io::{self, BufRead};
 use thiserror::Error;
@@ -31,10 +32,25 @@ pub struct TripleWriter<W: io::Write> {
     writer: TriplesWriterKind<W>,
 }
 
+/// The errors the parsers of the enabled formats can fail with.
+#[cfg(feature = "rdf-xml")]
+pub trait ReadError: From<TurtleError> + From<RdfXmlError> {}
+
+#[cfg(feature = "rdf-xml")]
+impl<E: From<TurtleError> + From<RdfXmlError>> ReadError for E {}
+
+/// The errors the parsers of the enabled formats can fail with.
+#[cfg(not(feature = "rdf-xml"))]
+pub trait ReadError: From<TurtleError> {}
+
+#[cfg(not(feature = "rdf-xml"))]
+impl<E: From<TurtleError>> ReadError for E {}
+
 #[allow(clippy::large_enum_variant)]
 pub enum TriplesParserKind<R: BufRead> {
     NTriples(NTriplesParser<R>),
     Turtle(TurtleParser<R>),
+    #[cfg(feature = "rdf-xml")]
     RdfXml(RdfXmlParser<R>),
     NQuads(NQuadsParser<R>),
 }
@@ -42,12 +58,15 @@ pub enum TriplesParserKind<R: BufRead> {
 pub enum TriplesWriterKind<W: io::Write> {
     NTriples(NTriplesFormatter<W>),
     Turtle(TurtleFormatter<W>),
+    #[cfg(feature = "rdf-xml")]
     RdfXml(io::Result<RdfXmlFormatter<W>>),
     NQuads(NQuadsFormatter<W>),
 }
 
 pub enum DataFormat {
-    /// Represents a [RDF/XML](https://www.w3.org/TR/rdf-syntax-grammar/) format.
+    /// Represents a [RDF/XML](https://www.w3.org/TR/rdf-syntax-grammar/) format, only available with
+    /// the `rdf-xml` feature.
+    #[cfg(feature = "rdf-xml")]
     RDFXml,
     /// Represents a [Turtle](https://www.w3.org/TR/turtle/) format.
     Turtle,
@@ -61,6 +80,7 @@ impl<R: BufRead> TripleReader<R> {
     pub fn new(format: &DataFormat, src: R) -> Self {
         TripleReader {
             parser: match format {
+                #[cfg(feature = "rdf-xml")]
                 DataFormat::RDFXml => TriplesParserKind::RdfXml(RdfXmlParser::new(src, None)),
                 DataFormat::Turtle => TriplesParserKind::Turtle(TurtleParser::new(src, None)),
                 DataFormat::NTriples => TriplesParserKind::NTriples(NTriplesParser::new(src)),
@@ -72,19 +92,34 @@ impl<R: BufRead> TripleReader<R> {
     pub fn read_all<E, UF>(&mut self, mut use_fn: UF) -> Result<(), E>
     where
         UF: FnMut(Triple<'_>) -> Result<(), E>,
-        E: From<TurtleError> + From<RdfXmlError>,
+        E: ReadError,
+    {
+        self.read_all_in_graphs(|triple, _| use_fn(triple))
+    }
+
+    /// Read all the triples along with the name of the graph they belong to, [None] standing for the
+    /// default graph, which is the only one of the formats other than N-Quads.
+    pub fn read_all_in_graphs<E, UF>(&mut self, mut use_fn: UF) -> Result<(), E>
+    where
+        UF: FnMut(Triple<'_>, Option<GraphName<'_>>) -> Result<(), E>,
+        E: ReadError,
     {
+        let mut use_triple = |triple: Triple<'_>| use_fn(triple, None);
         match &mut self.parser {
-            TriplesParserKind::NTriples(parser) => parser.parse_all(&mut use_fn),
-            TriplesParserKind::Turtle(parser) => parser.parse_all(&mut use_fn),
-            TriplesParserKind::RdfXml(parser) => parser.parse_all(&mut use_fn),
+            TriplesParserKind::NTriples(parser) => parser.parse_all(&mut use_triple),
+            TriplesParserKind::Turtle(parser) => parser.parse_all(&mut use_triple),
+            #[cfg(feature = "rdf-xml")]
+            TriplesParserKind::RdfXml(parser) => parser.parse_all(&mut use_triple),
             TriplesParserKind::NQuads(parser) => {
                 parser.parse_all(&mut |quad: Quad<'_>| -> Result<(), E> {
-                    use_fn(Triple {
-                        subject: quad.subject,
-                        predicate: quad.predicate,
-                        object: quad.object,
-                    })
+                    use_fn(
+                        Triple {
+                            subject: quad.subject,
+                            predicate: quad.predicate,
+                            object: quad.object,
+                        },
+                        quad.graph_name,
+                    )
                 })
             }
         }
@@ -118,6 +153,7 @@ impl<W: io::Write> TripleWriter<W> {
     pub fn new(format: &DataFormat, dst: W) -> Self {
         TripleWriter {
             writer: match format {
+                #[cfg(feature = "rdf-xml")]
                 DataFormat::RDFXml => TriplesWriterKind::RdfXml(RdfXmlFormatter::new(dst)),
                 DataFormat::Turtle => TriplesWriterKind::Turtle(TurtleFormatter::new(dst)),
                 DataFormat::NTriples => TriplesWriterKind::NTriples(NTriplesFormatter::new(dst)),
@@ -127,6 +163,16 @@ impl<W: io::Write> TripleWriter<W> {
     }
 
     pub fn write(&mut self, triple: &Triple<'_>) -> io::Result<()> {
+        self.write_in_graph(triple, None)
+    }
+
+    /// Write the triple in the given graph, [None] standing for the default graph, which is the only
+    /// one of the formats other than N-Quads, the graph being dropped by them.
+    pub fn write_in_graph(
+        &mut self,
+        triple: &Triple<'_>,
+        graph_name: Option<GraphName<'_>>,
+    ) -> io::Result<()> {
         match &mut self.writer {
             TriplesWriterKind::Turtle(formatter) => formatter.format(triple),
             TriplesWriterKind::NTriples(formatter) => formatter.format(triple),
@@ -137,11 +183,12 @@ impl<W: io::Write> TripleWriter<W> {
                     subject: triple.subject,
                     predicate: triple.predicate,
                     object: triple.object,
-                    graph_name: None,
+                    graph_name,
                 };
 
                 formatter.format(quad)
             }
+            #[cfg(feature = "rdf-xml")]
             TriplesWriterKind::RdfXml(format_result) => match format_result {
                 Ok(formatter) => formatter.format(triple),
                 Err(e) => Err(io::Error::new(io::ErrorKind::Other, e.to_string())),
@@ -161,6 +208,7 @@ impl<W: io::Write> TripleWriter<W> {
             TriplesWriterKind::Turtle(formatter) => formatter.finish(),
             TriplesWriterKind::NTriples(formatter) => formatter.finish(),
             TriplesWriterKind::NQuads(formatter) => formatter.finish(),
+            #[cfg(feature = "rdf-xml")]
             TriplesWriterKind::RdfXml(format_result) => match format_result {
                 Ok(formatter) => formatter.finish(),
                 Err(e) => Err(io::Error::new(io::ErrorKind::Other, e.to_string())),